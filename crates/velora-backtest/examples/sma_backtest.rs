@@ -202,6 +202,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             slippage_bps: 5.0,      // 5 basis points slippage
             fill_delay_ms: 0,
             fill_model: FillModel::Realistic,
+            ..Default::default()
         });
 
     // Generate test data
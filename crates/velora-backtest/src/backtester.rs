@@ -2,17 +2,35 @@
 
 use crate::config::BacktestConfig;
 use crate::errors::{BacktestError, BacktestResult};
-use crate::execution::{ExecutionSimulator, Fill};
-use crate::performance::{calculate_metrics, PerformanceMetrics};
-use crate::portfolio::{CompletedTrade, EquityPoint, Portfolio};
+use crate::execution::{Bracket, ExecutionSimulator, Fill};
+use crate::feed::{merge_series, select_series, CandleSeries};
+use crate::performance::{calculate_benchmark, calculate_metrics, PerformanceMetrics};
+use crate::portfolio::{
+    BarRange, CompletedTrade, EquityPoint, FundingPayment, Liquidation, Portfolio,
+};
+use crate::replay::{sort_events, ReplayEvent};
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
-use velora_core::types::{Candle, Side};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::warn;
+use velora_core::types::{Candle, Side, Symbol, Tick};
+use velora_core::{Clock, ReplayClock, Schedule};
+use velora_exchange::{FundingRate, Liquidity};
+use velora_risk::{
+    sizing, OrderRequest, PortfolioState, PositionSizer, RiskDecision, RiskError, RiskManager,
+};
 use velora_strategy::{MarketSnapshot, PositionSide, Signal, Strategy, StrategyContext};
 
 /// Main backtester struct
 pub struct Backtester {
     config: BacktestConfig,
     strategy: Option<Box<dyn Strategy>>,
+    risk_manager: Option<RiskManager>,
+    position_sizer: Option<Box<dyn PositionSizer>>,
+    funding_rates: Vec<FundingRate>,
+    benchmark: Option<CandleSeries>,
 }
 
 /// Complete backtest report
@@ -29,6 +47,14 @@ pub struct BacktestReport {
 
     /// All completed trades
     pub trades: Vec<CompletedTrade>,
+
+    /// Funding paid and received on perpetual positions
+    #[serde(default)]
+    pub funding_payments: Vec<FundingPayment>,
+
+    /// Positions closed because the maintenance margin was breached
+    #[serde(default)]
+    pub liquidations: Vec<Liquidation>,
 }
 
 impl Backtester {
@@ -37,6 +63,10 @@ impl Backtester {
         Self {
             config,
             strategy: None,
+            risk_manager: None,
+            position_sizer: None,
+            funding_rates: Vec::new(),
+            benchmark: None,
         }
     }
 
@@ -46,207 +76,890 @@ impl Backtester {
         self
     }
 
-    /// Run the backtest
-    pub async fn run(mut self, candles: Vec<Candle>) -> BacktestResult<BacktestReport> {
+    /// Add a risk manager
+    ///
+    /// Every Buy/Sell signal is checked against it before reaching the
+    /// execution simulator, using the same rules as the live engine.
+    /// Rejected signals are dropped and resized signals are submitted
+    /// with the reduced quantity.
+    pub fn with_risk_manager(mut self, risk_manager: RiskManager) -> Self {
+        self.risk_manager = Some(risk_manager);
+        self
+    }
+
+    /// Add a position sizer
+    ///
+    /// Unsized Buy/Sell signals (see [`Signal::buy_unsized`]) get their
+    /// quantity from the sizer before risk checks run. Signals the sizer
+    /// cannot size yet (e.g., not enough history for volatility) are skipped.
+    /// Without one, the backtester sizes them as the strategy's configuration
+    /// describes (see [`velora_risk::sizing::from_strategy_config`]), like the
+    /// live engine.
+    pub fn with_position_sizer(mut self, sizer: Box<dyn PositionSizer>) -> Self {
+        self.position_sizer = Some(sizer);
+        self
+    }
+
+    /// Add a funding rate history (see `MarketData::get_funding_rate_history`)
+    ///
+    /// Each rate is settled at its timestamp on the open position of its
+    /// symbol, marked at the symbol's last price: longs pay shorts when the
+    /// rate is positive. Funding is credited to cash and reported in
+    /// [`BacktestReport::funding_payments`].
+    pub fn with_funding_rates(mut self, mut funding_rates: Vec<FundingRate>) -> Self {
+        funding_rates.sort_by_key(|rate| rate.timestamp);
+        self.funding_rates = funding_rates;
+        self
+    }
+
+    /// Compare the run with a benchmark series, e.g. buy-and-hold BTC
+    ///
+    /// Alpha, beta and correlation against it are reported in
+    /// [`PerformanceMetrics::benchmark`].
+    pub fn with_benchmark(mut self, benchmark: CandleSeries) -> Self {
+        self.benchmark = Some(benchmark);
+        self
+    }
+
+    /// Run the backtest on candles of one or more symbols
+    ///
+    /// Candles are grouped into one series per symbol, with each symbol's
+    /// interval inferred from its data; see [`Backtester::run_series`].
+    pub async fn run(self, candles: Vec<Candle>) -> BacktestResult<BacktestReport> {
+        let mut grouped: Vec<(String, Vec<Candle>)> = Vec::new();
+        for candle in candles {
+            match grouped
+                .iter_mut()
+                .find(|(symbol, _)| symbol == candle.symbol.as_str())
+            {
+                Some((_, candles)) => candles.push(candle),
+                None => grouped.push((candle.symbol.to_string(), vec![candle])),
+            }
+        }
+
+        let series = grouped
+            .into_iter()
+            .map(|(symbol, candles)| CandleSeries::from_candles(symbol, candles))
+            .collect();
+        self.run_series(series).await
+    }
+
+    /// Run the backtest on per-symbol candle series
+    ///
+    /// The series are merged into one stream ordered by candle close time
+    /// (see [`merge_series`]). At each instant, every candle closing at it
+    /// is applied (fills, prices, context) before the strategy is called once
+    /// through [`Strategy::on_candles`], and one portfolio-wide equity point
+    /// is recorded.
+    ///
+    /// Orders are sent at that instant and can fill on the first candle
+    /// closing after they reach the exchange, so a latency longer than a bar
+    /// delays the fill by whole bars (see [`crate::config::LatencyConfig`]).
+    ///
+    /// After `on_candles`, the strategy's [`Strategy::on_order_update`] is
+    /// called if orders filled on the slice, then [`Strategy::on_timer`] if
+    /// its deadline passed (see [`BacktestConfig::timer_interval_ms`]). The
+    /// context's clock follows the candle close times.
+    ///
+    /// When [`BacktestConfig::symbols`] is set, only those symbols are
+    /// traded, in that order for candles closing at the same instant, and
+    /// each of them must have data. Candles closing within
+    /// [`BacktestConfig::warmup_until`] only warm the strategy up.
+    pub async fn run_series(mut self, series: Vec<CandleSeries>) -> BacktestResult<BacktestReport> {
         // Validate we have a strategy
         let mut strategy = self
             .strategy
             .take()
             .ok_or_else(|| BacktestError::InvalidConfig("No strategy provided".to_string()))?;
+        self.init_position_sizer(strategy.as_ref())?;
 
         // Validate we have data
-        if candles.is_empty() {
+        let series = select_series(&self.config.symbols, series)?;
+        let total_candles: usize = series.iter().map(|series| series.candles.len()).sum();
+        if total_candles == 0 {
             return Err(BacktestError::DataError("No candles provided".to_string()));
         }
 
         // Initialize components
-        let mut portfolio = Portfolio::new(self.config.initial_capital);
+        let mut portfolio = Portfolio::new(self.config.initial_capital)
+            .with_lot_matching(self.config.lot_matching)
+            .with_perpetuals(self.config.perpetuals.clone());
+        let mut next_funding = 0;
         let mut simulator = ExecutionSimulator::new(self.config.execution.clone());
-        let ctx = StrategyContext::new(self.config.initial_capital);
+        let slices = merge_series(&series);
+        let clock = Arc::new(ReplayClock::new(slices[0].timestamp));
+        let ctx = StrategyContext::new(self.config.initial_capital).with_clock(clock.clone());
+        let mut callbacks = Callbacks::new(&self.config);
+        let mut last_candles: HashMap<String, Candle> = HashMap::new();
 
         // Initialize strategy
         strategy.initialize(&ctx).await?;
 
-        // Sort candles by timestamp
-        let mut sorted_candles = candles;
-        sorted_candles.sort_by_key(|c| c.timestamp);
-
-        println!("Running backtest with {} candles...", sorted_candles.len());
+        println!(
+            "Running backtest with {} candles across {} symbols...",
+            total_candles,
+            series.len()
+        );
 
         // Main event loop
-        for (idx, candle) in sorted_candles.iter().enumerate() {
-            if idx % 1000 == 0 && idx > 0 {
-                println!("Processed {idx} candles...");
+        let mut processed = 0;
+        for slice in slices {
+            clock.observe(slice.timestamp);
+            let warming_up = self
+                .config
+                .warmup_until
+                .is_some_and(|until| slice.timestamp <= until);
+            for candle in &slice.candles {
+                processed += 1;
+                if processed % 1000 == 0 {
+                    println!("Processed {processed} candles...");
+                }
+
+                self.apply_candle(
+                    candle,
+                    slice.timestamp,
+                    &mut simulator,
+                    &mut portfolio,
+                    &ctx,
+                )?;
+                last_candles.insert(candle.symbol.to_string(), candle.clone());
             }
 
-            // 1. Update market data in context
-            let snapshot = MarketSnapshot {
-                last_price: candle.close.into_inner(),
-                timestamp: candle.timestamp,
-                best_bid: Some(candle.close.into_inner() - 0.5),
-                best_ask: Some(candle.close.into_inner() + 0.5),
-                volume_24h: Some(candle.volume.into_inner()),
-            };
-            ctx.update_market_snapshot(candle.symbol.as_str(), snapshot)?;
-            ctx.add_candle(candle.symbol.as_str(), candle.clone())?;
+            // 3. Settle funding and liquidate positions short of margin
+            self.apply_funding(&mut next_funding, slice.timestamp, &mut portfolio, &ctx)?;
+            self.liquidate(
+                slice.timestamp,
+                &slice.candles,
+                &mut simulator,
+                &mut portfolio,
+                &ctx,
+            )?;
 
-            // 2. Process pending orders (check for fills)
-            let fills = simulator.process_candle(candle);
-            for fill in fills {
-                self.process_fill(&fill, &mut portfolio, &ctx)?;
+            if let Some(risk_manager) = self.risk_manager.as_mut() {
+                risk_manager.update_equity(account_equity(&portfolio), slice.timestamp);
             }
 
-            // 3. Update portfolio prices
-            portfolio.update_price(
-                candle.symbol.as_str().to_string(),
-                candle.close.into_inner(),
+            // 4. Call strategy once all symbols are updated for this instant
+            let mut signals = strategy.on_candles(&slice.candles, &ctx).await?;
+            signals.extend(
+                callbacks
+                    .poll(
+                        strategy.as_mut(),
+                        &ctx,
+                        simulator.fills().len(),
+                        slice.timestamp,
+                    )
+                    .await?,
             );
+            if warming_up {
+                continue;
+            }
 
-            // 4. Call strategy with new candle
-            let signal = strategy.on_candle(candle, &ctx).await?;
-
-            // 5. Execute signal if actionable
-            if signal.is_actionable() {
+            // 5. Execute actionable signals
+            for signal in signals {
+                let Some(symbol) = signal.symbol() else {
+                    continue;
+                };
+                let candle = last_candles.get(symbol).cloned().ok_or_else(|| {
+                    BacktestError::InvalidOrder(format!("No market data for {symbol}"))
+                })?;
                 self.execute_signal(
                     signal,
                     &mut simulator,
                     &mut portfolio,
                     &ctx,
-                    candle.timestamp,
+                    candle.close.into_inner(),
+                    slice.timestamp,
                 )?;
             }
 
             // 6. Record equity snapshot
-            portfolio.record_snapshot(candle.timestamp);
+            portfolio.record_snapshot(slice.timestamp);
         }
 
         // Shutdown strategy
         strategy.shutdown(&ctx).await?;
 
         println!("Backtest complete!");
-        println!("Processed {} candles", sorted_candles.len());
+        println!("Processed {total_candles} candles");
+        println!("Completed {} trades", portfolio.trades().len());
+
+        Ok(self.report(&portfolio))
+    }
+
+    /// Run the backtest on recorded trades, ticks and order books
+    ///
+    /// Events are replayed in time order (see [`sort_events`]). Each one
+    /// updates the simulated book and fills, then reaches the strategy:
+    /// trades through [`Strategy::on_trade`], ticks through
+    /// [`Strategy::on_tick`], and book updates through `on_tick` with the mid
+    /// price, the book itself being available from
+    /// [`StrategyContext::get_order_book`]. Orders are matched against the
+    /// replayed book as soon as they reach the exchange; resting limit orders keep
+    /// their place in the queue (see [`ExecutionSimulator`]).
+    ///
+    /// Once the event's signal is executed, [`Strategy::on_order_update`] is
+    /// called if orders filled, then [`Strategy::on_timer`] if its deadline
+    /// passed, their signals being executed the same way. The context's
+    /// clock follows the event timestamps.
+    ///
+    /// One equity point is recorded per distinct timestamp. When
+    /// [`BacktestConfig::symbols`] is set, events of other symbols are
+    /// dropped.
+    pub async fn run_replay(
+        mut self,
+        mut events: Vec<ReplayEvent>,
+    ) -> BacktestResult<BacktestReport> {
+        let mut strategy = self
+            .strategy
+            .take()
+            .ok_or_else(|| BacktestError::InvalidConfig("No strategy provided".to_string()))?;
+        self.init_position_sizer(strategy.as_ref())?;
+
+        if !self.config.symbols.is_empty() {
+            let symbols = &self.config.symbols;
+            events.retain(|event| symbols.iter().any(|symbol| symbol == event.symbol()));
+        }
+        if events.is_empty() {
+            return Err(BacktestError::DataError(
+                "No market events provided".to_string(),
+            ));
+        }
+        sort_events(&mut events);
+
+        let mut portfolio = Portfolio::new(self.config.initial_capital)
+            .with_lot_matching(self.config.lot_matching)
+            .with_perpetuals(self.config.perpetuals.clone());
+        let mut next_funding = 0;
+        let mut simulator = ExecutionSimulator::new(self.config.execution.clone());
+        let clock = Arc::new(ReplayClock::new(events[0].timestamp()));
+        let ctx = StrategyContext::new(self.config.initial_capital).with_clock(clock.clone());
+        let mut callbacks = Callbacks::new(&self.config);
+        let mut last_prices: HashMap<String, f64> = HashMap::new();
+
+        strategy.initialize(&ctx).await?;
+
+        let total_events = events.len();
+        println!("Running replay backtest with {total_events} events...");
+
+        let mut events = events.into_iter().peekable();
+        while let Some(event) = events.next() {
+            let symbol = event.symbol().to_string();
+            let timestamp = event.timestamp();
+            clock.observe(timestamp);
+            self.apply_funding(&mut next_funding, timestamp, &mut portfolio, &ctx)?;
+
+            // 1. Replay the event through the simulator
+            let (fills, traded) = match &event {
+                ReplayEvent::Trade(trade) => {
+                    ctx.add_trade(symbol.as_str(), trade.clone())?;
+                    (
+                        simulator.process_trade(trade),
+                        Some(trade.price.into_inner()),
+                    )
+                }
+                ReplayEvent::Tick(tick) => {
+                    (simulator.process_tick(tick), Some(tick.price.into_inner()))
+                }
+                ReplayEvent::BookSnapshot(book) => (simulator.apply_book(book), None),
+                ReplayEvent::BookDelta(delta) => (simulator.apply_book_delta(delta), None),
+            };
+
+            // 2. Update market data in context
+            let book = simulator.order_book(&symbol, timestamp);
+            let best_bid = book
+                .as_ref()
+                .and_then(|book| book.best_bid())
+                .map(|l| l.price);
+            let best_ask = book
+                .as_ref()
+                .and_then(|book| book.best_ask())
+                .map(|l| l.price);
+            let mid = book
+                .as_ref()
+                .and_then(|book| book.mid_price())
+                .map(|price| price.into_inner());
+            if let Some(price) = traded.or(mid) {
+                last_prices.insert(symbol.clone(), price);
+            }
+            let Some(&price) = last_prices.get(&symbol) else {
+                continue;
+            };
+
+            let snapshot = MarketSnapshot {
+                last_price: price,
+                timestamp,
+                best_bid: best_bid.map(|price| price.into_inner()),
+                best_ask: best_ask.map(|price| price.into_inner()),
+                volume_24h: None,
+            };
+            ctx.update_market_snapshot(symbol.as_str(), snapshot)?;
+            if let Some(book) = book {
+                ctx.update_order_book(book)?;
+            }
+            ctx.update_position_prices()?;
+
+            // 3. Apply fills and mark the portfolio
+            self.settle(&symbol, fills, price, &mut simulator, &mut portfolio, &ctx)?;
+            self.liquidate(timestamp, &[], &mut simulator, &mut portfolio, &ctx)?;
+
+            if let Some(risk_manager) = self.risk_manager.as_mut() {
+                risk_manager.update_equity(account_equity(&portfolio), timestamp);
+            }
+
+            // 4. Call the strategy hook matching the event
+            let signal = match &event {
+                ReplayEvent::Trade(trade) => strategy.on_trade(trade, &ctx).await?,
+                ReplayEvent::Tick(tick) => strategy.on_tick(tick, &ctx).await?,
+                ReplayEvent::BookSnapshot(_) | ReplayEvent::BookDelta(_) => match mid {
+                    Some(mid) => {
+                        let quote = Tick {
+                            symbol: Symbol::new(symbol.as_str()),
+                            price: mid.into(),
+                            volume: 0.0.into(),
+                            timestamp,
+                        };
+                        strategy.on_tick(&quote, &ctx).await?
+                    }
+                    None => Signal::Hold,
+                },
+            };
+
+            // 5. Execute the signal, then those of the fill and timer callbacks
+            self.execute_replay_signal(
+                signal,
+                &last_prices,
+                timestamp,
+                &mut simulator,
+                &mut portfolio,
+                &ctx,
+            )?;
+            let signals = callbacks
+                .poll(strategy.as_mut(), &ctx, simulator.fills().len(), timestamp)
+                .await?;
+            for signal in signals {
+                self.execute_replay_signal(
+                    signal,
+                    &last_prices,
+                    timestamp,
+                    &mut simulator,
+                    &mut portfolio,
+                    &ctx,
+                )?;
+            }
+
+            // 6. Record equity once every event of this instant is applied
+            if events
+                .peek()
+                .is_none_or(|next| next.timestamp() != timestamp)
+            {
+                portfolio.record_snapshot(timestamp);
+            }
+        }
+
+        strategy.shutdown(&ctx).await?;
+
+        println!("Backtest complete!");
+        println!("Processed {total_events} events");
         println!("Completed {} trades", portfolio.trades().len());
 
+        Ok(self.report(&portfolio))
+    }
+
+    /// Execute a replay signal at the last price, matching new orders against the book
+    fn execute_replay_signal(
+        &mut self,
+        signal: Signal,
+        last_prices: &HashMap<String, f64>,
+        timestamp: DateTime<Utc>,
+        simulator: &mut ExecutionSimulator,
+        portfolio: &mut Portfolio,
+        ctx: &StrategyContext,
+    ) -> BacktestResult<()> {
+        let Some(symbol) = signal.symbol().map(str::to_string) else {
+            return Ok(());
+        };
+        let price = *last_prices
+            .get(&symbol)
+            .ok_or_else(|| BacktestError::InvalidOrder(format!("No market data for {symbol}")))?;
+        self.execute_signal(signal, simulator, portfolio, ctx, price, timestamp)?;
+
+        let fills = simulator.match_orders(&symbol, timestamp);
+        self.settle(&symbol, fills, price, simulator, portfolio, ctx)
+    }
+
+    /// Build the report of a finished run
+    fn report(self, portfolio: &Portfolio) -> BacktestReport {
         // Calculate metrics
-        let metrics = calculate_metrics(
+        let mut metrics = calculate_metrics(
             portfolio.equity_curve(),
             portfolio.trades(),
+            portfolio.funding_pnl(),
             self.config.initial_capital,
+            self.config.rolling_window,
         );
+        metrics.benchmark = self.benchmark.as_ref().and_then(|benchmark| {
+            calculate_benchmark(
+                portfolio.equity_curve(),
+                benchmark,
+                metrics.periods_per_year,
+            )
+        });
 
-        // Build report
-        let report = BacktestReport {
+        BacktestReport {
             config: self.config,
             metrics,
             equity_curve: portfolio.equity_curve().to_vec(),
             trades: portfolio.trades().to_vec(),
+            funding_payments: portfolio.funding_payments().to_vec(),
+            liquidations: portfolio.liquidations().to_vec(),
+        }
+    }
+
+    /// Settle the funding rates due by `until`, starting at `next`
+    fn apply_funding(
+        &self,
+        next: &mut usize,
+        until: DateTime<Utc>,
+        portfolio: &mut Portfolio,
+        ctx: &StrategyContext,
+    ) -> BacktestResult<()> {
+        let due = self.funding_rates[*next..]
+            .iter()
+            .take_while(|rate| rate.timestamp <= until);
+        let mut settled = 0;
+        for rate in due {
+            settled += 1;
+            let value = rate.rate.to_f64().unwrap_or_default();
+            portfolio.apply_funding(rate.symbol.as_str(), value, rate.timestamp);
+        }
+        *next += settled;
+
+        if settled > 0 {
+            ctx.update_capital(account_capital(portfolio))?;
+        }
+        Ok(())
+    }
+
+    /// Close every perpetual position if equity fell below the maintenance
+    /// margin at any point of the `candles` just applied
+    fn liquidate(
+        &self,
+        timestamp: DateTime<Utc>,
+        candles: &[Candle],
+        simulator: &mut ExecutionSimulator,
+        portfolio: &mut Portfolio,
+        ctx: &StrategyContext,
+    ) -> BacktestResult<()> {
+        let liquidations = portfolio.liquidate_if_breached(
+            |symbol| {
+                candles
+                    .iter()
+                    .find(|candle| candle.symbol.as_str() == symbol)
+                    .map(|candle| BarRange {
+                        open: candle.open.into_inner(),
+                        low: candle.low.into_inner(),
+                        high: candle.high.into_inner(),
+                    })
+            },
+            |symbol| simulator.fee_rate(symbol, Liquidity::Taker),
+            timestamp,
+        );
+        for liquidation in &liquidations {
+            simulator.sync_bracket(&liquidation.symbol, None);
+            ctx.remove_position(&liquidation.symbol)?;
+        }
+
+        if !liquidations.is_empty() {
+            ctx.update_capital(account_capital(portfolio))?;
+        }
+        Ok(())
+    }
+
+    /// Apply one candle closing at `close_time`: market data, fills and portfolio prices
+    fn apply_candle(
+        &self,
+        candle: &Candle,
+        close_time: DateTime<Utc>,
+        simulator: &mut ExecutionSimulator,
+        portfolio: &mut Portfolio,
+        ctx: &StrategyContext,
+    ) -> BacktestResult<()> {
+        // 1. Update market data in context
+        let snapshot = MarketSnapshot {
+            last_price: candle.close.into_inner(),
+            timestamp: candle.timestamp,
+            best_bid: Some(candle.close.into_inner() - 0.5),
+            best_ask: Some(candle.close.into_inner() + 0.5),
+            volume_24h: Some(candle.volume.into_inner()),
         };
+        ctx.update_market_snapshot(candle.symbol.as_str(), snapshot)?;
+        ctx.add_candle(candle.symbol.as_str(), candle.clone())?;
+        ctx.update_position_prices()?;
+
+        // 2. Process pending orders (check for fills)
+        let fills = simulator.process_candle_until(candle, close_time);
+
+        // Lots open before this candle saw its whole range, including
+        // those its fills close
+        portfolio.mark_range(
+            candle.symbol.as_str(),
+            candle.low.into_inner(),
+            candle.high.into_inner(),
+        );
+        self.settle(
+            candle.symbol.as_str(),
+            fills,
+            candle.close.into_inner(),
+            simulator,
+            portfolio,
+            ctx,
+        )
+    }
 
-        Ok(report)
+    /// Apply a symbol's fills and mark its position at `price`
+    fn settle(
+        &self,
+        symbol: &str,
+        fills: Vec<Fill>,
+        price: f64,
+        simulator: &mut ExecutionSimulator,
+        portfolio: &mut Portfolio,
+        ctx: &StrategyContext,
+    ) -> BacktestResult<()> {
+        for fill in fills {
+            self.process_fill(&fill, portfolio, ctx)?;
+        }
+        // Exits follow the position's size and are void once it is closed
+        let position = portfolio
+            .get_position(symbol)
+            .map(|position| (position.side, position.quantity));
+        simulator.sync_bracket(symbol, position);
+        ctx.update_capital(account_capital(portfolio))?;
+
+        // 3. Update portfolio prices
+        portfolio.update_price(symbol.to_string(), price);
+        sync_exit_levels(symbol, simulator, portfolio, ctx)
     }
 
     /// Process a fill event
+    ///
+    /// Reduce-only fills are capped at the position they close, so they
+    /// never open one on the other side.
     fn process_fill(
         &self,
         fill: &Fill,
         portfolio: &mut Portfolio,
         ctx: &StrategyContext,
     ) -> BacktestResult<()> {
-        match fill.side {
-            Side::Buy => {
-                // Opening a long position or closing a short
-                if portfolio.has_position(&fill.symbol) {
-                    // Closing short position
-                    portfolio.close_position(
-                        &fill.symbol,
-                        fill.price,
-                        fill.commission,
-                        fill.timestamp,
-                    );
-                    ctx.remove_position(&fill.symbol)?;
-                } else {
-                    // Opening long position
-                    portfolio.open_position(
-                        fill.symbol.clone(),
-                        PositionSide::Long,
-                        fill.quantity,
-                        fill.price,
-                        fill.commission,
-                        fill.timestamp,
-                    );
-
-                    if let Some(position) = portfolio.get_position(&fill.symbol) {
-                        ctx.update_position(position.clone())?;
-                    }
+        let quantity = if fill.reduce_only {
+            let closable = match (portfolio.get_position(&fill.symbol), fill.side) {
+                (Some(position), Side::Buy) if position.side == PositionSide::Short => {
+                    position.quantity
                 }
-            }
-            Side::Sell => {
-                // Opening a short position or closing a long
-                if portfolio.has_position(&fill.symbol) {
-                    // Closing long position
-                    portfolio.close_position(
-                        &fill.symbol,
-                        fill.price,
-                        fill.commission,
-                        fill.timestamp,
-                    );
-                    ctx.remove_position(&fill.symbol)?;
-                } else {
-                    // Opening short position
-                    portfolio.open_position(
-                        fill.symbol.clone(),
-                        PositionSide::Short,
-                        fill.quantity,
-                        fill.price,
-                        fill.commission,
-                        fill.timestamp,
-                    );
-
-                    if let Some(position) = portfolio.get_position(&fill.symbol) {
-                        ctx.update_position(position.clone())?;
-                    }
+                (Some(position), Side::Sell) if position.side == PositionSide::Long => {
+                    position.quantity
                 }
+                _ => 0.0,
+            };
+            fill.quantity.min(closable)
+        } else {
+            fill.quantity
+        };
+        if quantity <= 0.0 {
+            return Ok(());
+        }
+
+        portfolio.apply_fill(
+            &fill.symbol,
+            fill.side,
+            quantity,
+            fill.price,
+            fill.commission * quantity / fill.quantity,
+            fill.timestamp,
+        );
+
+        match portfolio.get_position(&fill.symbol) {
+            Some(position) => ctx.update_position(position.clone())?,
+            None => {
+                ctx.remove_position(&fill.symbol)?;
             }
         }
 
         Ok(())
     }
 
-    /// Execute a signal
+    /// Execute a signal at the symbol's last price
     fn execute_signal(
-        &self,
+        &mut self,
         signal: Signal,
         simulator: &mut ExecutionSimulator,
         portfolio: &mut Portfolio,
-        _ctx: &StrategyContext,
-        timestamp: chrono::DateTime<chrono::Utc>,
+        ctx: &StrategyContext,
+        price: f64,
+        timestamp: DateTime<Utc>,
     ) -> BacktestResult<()> {
         match signal {
             Signal::Buy { .. } | Signal::Sell { .. } => {
-                // Submit new order
-                simulator.submit_order(signal, timestamp)?;
+                let Some(signal) = self.size_signal(signal, ctx)? else {
+                    return Ok(());
+                };
+
+                // Submit new order if it passes risk and margin checks
+                if let Some(signal) = self.check_risk(signal, portfolio, price, timestamp) {
+                    if has_margin(&signal, portfolio, price) {
+                        simulator.submit_order(signal, timestamp)?;
+                    }
+                }
             }
-            Signal::Close { ref symbol, .. } => {
-                // Close existing position
+            Signal::Close {
+                ref symbol,
+                quantity,
+                ..
+            } => {
+                // Close existing position, in full unless a quantity is given
                 if let Some(position) = portfolio.get_position(symbol) {
                     let close_side = match position.side {
                         PositionSide::Long => Side::Sell,
                         PositionSide::Short => Side::Buy,
                     };
+                    let quantity = quantity.map_or(position.quantity, |q| q.min(position.quantity));
 
                     simulator.submit_close_order(
                         symbol.clone(),
-                        position.quantity,
+                        quantity,
                         close_side,
                         timestamp,
                     )?;
                 }
             }
-            _ => {}
+            Signal::Modify {
+                ref symbol,
+                stop_loss,
+                take_profit,
+                trailing_stop,
+                ..
+            } => {
+                // Exits only apply to an open position
+                let Some(position) = portfolio.get_position(symbol) else {
+                    return Ok(());
+                };
+
+                let current = simulator.bracket(symbol).copied().unwrap_or_default();
+                let exits = Bracket {
+                    stop_loss: stop_loss.or(current.stop_loss),
+                    take_profit: take_profit.or(current.take_profit),
+                    trailing_stop: trailing_stop.or(current.trailing_stop),
+                };
+                simulator.attach_bracket(
+                    symbol.clone(),
+                    position.side,
+                    position.quantity,
+                    position.current_price,
+                    exits,
+                );
+                sync_exit_levels(symbol, simulator, portfolio, ctx)?;
+            }
+            Signal::Hold => {}
         }
 
         Ok(())
     }
+
+    /// Default to the sizer described by the strategy's configuration.
+    fn init_position_sizer(&mut self, strategy: &dyn Strategy) -> BacktestResult<()> {
+        if self.position_sizer.is_none() {
+            self.position_sizer = Some(sizing::from_strategy_config(strategy.config())?);
+        }
+        Ok(())
+    }
+
+    /// Fill in the quantity of an unsized entry signal.
+    ///
+    /// Returns None if the sizer does not have enough data yet, or if there
+    /// is no sizer to ask.
+    fn size_signal(&self, signal: Signal, ctx: &StrategyContext) -> BacktestResult<Option<Signal>> {
+        if !signal.needs_sizing() {
+            return Ok(Some(signal));
+        }
+
+        let (symbol, side) = match &signal {
+            Signal::Buy { symbol, .. } => (symbol.as_str(), Side::Buy),
+            Signal::Sell { symbol, .. } => (symbol.as_str(), Side::Sell),
+            _ => return Ok(Some(signal)),
+        };
+
+        let Some(sizer) = self.position_sizer.as_ref() else {
+            warn!("Skipping unsized {} signal: no position sizer", symbol);
+            return Ok(None);
+        };
+
+        match sizer.size(symbol, side, ctx) {
+            Ok(quantity) if quantity > 0.0 => Ok(Some(signal.with_quantity(quantity))),
+            Ok(_) | Err(RiskError::InsufficientData(_)) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Apply risk checks to an entry signal.
+    ///
+    /// Returns the signal to submit (possibly resized), or None if rejected.
+    fn check_risk(
+        &mut self,
+        signal: Signal,
+        portfolio: &Portfolio,
+        price: f64,
+        timestamp: DateTime<Utc>,
+    ) -> Option<Signal> {
+        let Some(risk_manager) = self.risk_manager.as_mut() else {
+            return Some(signal);
+        };
+
+        let (symbol, side, quantity, limit_price, stop_price) = match &signal {
+            Signal::Buy {
+                symbol,
+                quantity,
+                limit_price,
+                stop_price,
+                ..
+            } => (symbol, Side::Buy, *quantity, *limit_price, *stop_price),
+            Signal::Sell {
+                symbol,
+                quantity,
+                limit_price,
+                stop_price,
+                ..
+            } => (symbol, Side::Sell, *quantity, *limit_price, *stop_price),
+            _ => return Some(signal),
+        };
+
+        let price = limit_price.or(stop_price).unwrap_or(price);
+        let request = OrderRequest::new(symbol.clone(), side, quantity, price);
+
+        let mut state = PortfolioState::new(account_equity(portfolio), timestamp);
+        for (symbol, position) in portfolio.positions() {
+            let signed = match position.side {
+                PositionSide::Long => position.value(),
+                PositionSide::Short => -position.value(),
+            };
+            state.positions.insert(symbol.clone(), signed);
+        }
+
+        match risk_manager.check_order(&request, &state) {
+            RiskDecision::Approved => Some(signal),
+            RiskDecision::Resized {
+                quantity: resized, ..
+            } => {
+                let mut signal = signal;
+                if let Signal::Buy { quantity, .. } | Signal::Sell { quantity, .. } = &mut signal {
+                    *quantity = resized;
+                }
+                Some(signal)
+            }
+            RiskDecision::Rejected(_) => None,
+        }
+    }
+}
+
+/// Strategy callbacks that are not driven by market data
+///
+/// Polled after each slice or event: [`Strategy::on_order_update`] when
+/// orders filled since the last call, then [`Strategy::on_timer`] when the
+/// timer is due on the event's time.
+struct Callbacks {
+    timer: Option<Schedule>,
+    reported_fills: usize,
+}
+
+impl Callbacks {
+    fn new(config: &BacktestConfig) -> Self {
+        Self {
+            timer: config.timer(),
+            reported_fills: 0,
+        }
+    }
+
+    /// Actionable signals of the callbacks due, given the fills so far
+    async fn poll(
+        &mut self,
+        strategy: &mut dyn Strategy,
+        ctx: &StrategyContext,
+        fills: usize,
+        timestamp: DateTime<Utc>,
+    ) -> BacktestResult<Vec<Signal>> {
+        let mut signals = Vec::new();
+        if fills > self.reported_fills {
+            self.reported_fills = fills;
+            signals.push(strategy.on_order_update(ctx).await?);
+        }
+        if self
+            .timer
+            .as_mut()
+            .is_some_and(|timer| timer.is_due(timestamp))
+        {
+            signals.push(strategy.on_timer(ctx).await?);
+        }
+        signals.retain(Signal::is_actionable);
+        Ok(signals)
+    }
+}
+
+/// Mirror the simulator's bracket levels onto the portfolio and strategy positions
+fn sync_exit_levels(
+    symbol: &str,
+    simulator: &ExecutionSimulator,
+    portfolio: &mut Portfolio,
+    ctx: &StrategyContext,
+) -> BacktestResult<()> {
+    let (stop_loss, take_profit) = simulator.exit_levels(symbol).unwrap_or_default();
+    if let Some(position) = portfolio.set_exit_levels(symbol, stop_loss, take_profit) {
+        ctx.update_position(position.clone())?;
+    }
+    Ok(())
+}
+
+/// Capital available to the strategy: cash net of every fee and funding
+/// payment, without the open positions' value
+///
+/// The same as the engine's `PositionTracker` cash, so sizers and risk checks
+/// see the same equity in both.
+fn account_capital(portfolio: &Portfolio) -> f64 {
+    account_equity(portfolio) - portfolio.unrealized_pnl()
+}
+
+/// Account equity used for risk checks (capital + unrealized P&L)
+fn account_equity(portfolio: &Portfolio) -> f64 {
+    portfolio.total_equity()
+}
+
+/// Check that an entry on a perpetual leaves enough equity for its initial margin
+///
+/// The order is valued at its limit or stop price, else at `price`. Entries
+/// on other symbols always pass.
+fn has_margin(signal: &Signal, portfolio: &Portfolio, price: f64) -> bool {
+    let (symbol, side, quantity, limit_price, stop_price) = match signal {
+        Signal::Buy {
+            symbol,
+            quantity,
+            limit_price,
+            stop_price,
+            ..
+        } => (symbol, Side::Buy, *quantity, *limit_price, *stop_price),
+        Signal::Sell {
+            symbol,
+            quantity,
+            limit_price,
+            stop_price,
+            ..
+        } => (symbol, Side::Sell, *quantity, *limit_price, *stop_price),
+        _ => return true,
+    };
+    if !portfolio.is_perpetual(symbol) {
+        return true;
+    }
+
+    let price = limit_price.or(stop_price).unwrap_or(price);
+    portfolio.margin_after_fill(symbol, side, quantity, price) <= account_equity(portfolio)
 }
 
 impl BacktestReport {
@@ -366,4 +1079,788 @@ mod tests {
         let report = backtester.run(candles).await.unwrap();
         assert_eq!(report.metrics.total_trades, 0); // Dummy strategy doesn't trade
     }
+
+    fn flat_candles(count: i64) -> Vec<Candle> {
+        let start = Utc::now();
+        (0..count)
+            .map(|i| Candle {
+                symbol: Symbol::new("BTC-USD-PERP"),
+                timestamp: start + chrono::Duration::minutes(i),
+                open: 50000.0.into(),
+                high: 50000.0.into(),
+                low: 50000.0.into(),
+                close: 50000.0.into(),
+                volume: 100.0.into(),
+            })
+            .collect()
+    }
+
+    /// Buys once on the first candle, closes on the third
+    struct BuyThenCloseStrategy {
+        config: StrategyConfig,
+        entry: Signal,
+        candles_seen: usize,
+    }
+
+    impl BuyThenCloseStrategy {
+        fn new(entry: Signal) -> Self {
+            Self {
+                config: StrategyConfig::new("BuyThenClose"),
+                entry,
+                candles_seen: 0,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Strategy for BuyThenCloseStrategy {
+        fn name(&self) -> &str {
+            "BuyThenClose"
+        }
+        fn config(&self) -> &StrategyConfig {
+            &self.config
+        }
+        fn state(&self) -> StrategyState {
+            StrategyState::Running
+        }
+
+        async fn on_candle(
+            &mut self,
+            _candle: &Candle,
+            _ctx: &StrategyContext,
+        ) -> velora_strategy::StrategyResult<Signal> {
+            self.candles_seen += 1;
+            Ok(match self.candles_seen {
+                1 => self.entry.clone(),
+                3 => Signal::close("BTC-USD-PERP"),
+                _ => Signal::Hold,
+            })
+        }
+
+        fn reset(&mut self) {
+            self.candles_seen = 0;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backtester_applies_risk_limits() {
+        let config = BacktestConfig::new()
+            .with_capital(10_000.0)
+            .with_execution(crate::config::ExecutionConfig::optimistic());
+
+        let risk_manager = RiskManager::new(velora_core::RiskConfig {
+            max_position_size: 1_000.0,
+            ..Default::default()
+        });
+
+        let strategy = Box::new(BuyThenCloseStrategy::new(Signal::buy("BTC-USD-PERP", 1.0)));
+
+        let report = Backtester::new(config)
+            .with_strategy(strategy)
+            .with_risk_manager(risk_manager)
+            .run(flat_candles(4))
+            .await
+            .unwrap();
+
+        // 1.0 BTC requested, capped at 1,000 notional = 0.02 BTC
+        assert_eq!(report.trades.len(), 1);
+        assert_eq!(report.trades[0].quantity, 0.02);
+    }
+
+    #[tokio::test]
+    async fn test_backtester_sizes_unsized_signals() {
+        let config = BacktestConfig::new()
+            .with_capital(10_000.0)
+            .with_execution(crate::config::ExecutionConfig::optimistic());
+
+        let strategy = Box::new(BuyThenCloseStrategy::new(Signal::buy_unsized(
+            "BTC-USD-PERP",
+        )));
+
+        let report = Backtester::new(config)
+            .with_strategy(strategy)
+            .with_position_sizer(Box::new(velora_risk::FixedFractional::new(0.1).unwrap()))
+            .run(flat_candles(4))
+            .await
+            .unwrap();
+
+        // 10% of 10,000 at 50,000 = 0.02 BTC
+        assert_eq!(report.trades.len(), 1);
+        assert_eq!(report.trades[0].quantity, 0.02);
+    }
+
+    #[tokio::test]
+    async fn test_backtester_sizes_from_strategy_config() {
+        let config = BacktestConfig::new()
+            .with_capital(10_000.0)
+            .with_execution(crate::config::ExecutionConfig::optimistic());
+
+        let strategy = Box::new(BuyThenCloseStrategy::new(Signal::buy_unsized(
+            "BTC-USD-PERP",
+        )));
+
+        let report = Backtester::new(config)
+            .with_strategy(strategy)
+            .run(flat_candles(4))
+            .await
+            .unwrap();
+
+        // The default max_position_size_pct of 10% sizes 0.02 BTC at 50,000
+        assert_eq!(report.trades.len(), 1);
+        assert_eq!(report.trades[0].quantity, 0.02);
+    }
+
+    fn ohlc_candles(bars: &[(f64, f64, f64, f64)]) -> Vec<Candle> {
+        let start = Utc::now();
+        bars.iter()
+            .enumerate()
+            .map(|(i, &(open, high, low, close))| Candle {
+                symbol: Symbol::new("BTC-USD-PERP"),
+                timestamp: start + chrono::Duration::minutes(i as i64),
+                open: open.into(),
+                high: high.into(),
+                low: low.into(),
+                close: close.into(),
+                volume: 100.0.into(),
+            })
+            .collect()
+    }
+
+    /// Emits one scripted signal per candle and records the stop-loss it sees
+    struct ScriptedStrategy {
+        config: StrategyConfig,
+        signals: std::vec::IntoIter<Signal>,
+        observed_stops: std::sync::Arc<std::sync::Mutex<Vec<Option<f64>>>>,
+    }
+
+    impl ScriptedStrategy {
+        fn new(signals: Vec<Signal>) -> Self {
+            Self {
+                config: StrategyConfig::new("Scripted"),
+                signals: signals.into_iter(),
+                observed_stops: Default::default(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Strategy for ScriptedStrategy {
+        fn name(&self) -> &str {
+            "Scripted"
+        }
+        fn config(&self) -> &StrategyConfig {
+            &self.config
+        }
+        fn state(&self) -> StrategyState {
+            StrategyState::Running
+        }
+
+        async fn on_candle(
+            &mut self,
+            candle: &Candle,
+            ctx: &StrategyContext,
+        ) -> velora_strategy::StrategyResult<Signal> {
+            let stop = ctx
+                .get_position(candle.symbol.as_str())?
+                .and_then(|position| position.stop_loss);
+            self.observed_stops.lock().unwrap().push(stop);
+            Ok(self.signals.next().unwrap_or(Signal::Hold))
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    #[tokio::test]
+    async fn test_backtester_bracket_take_profit() {
+        let config = BacktestConfig::new()
+            .with_capital(100_000.0)
+            .with_execution(crate::config::ExecutionConfig::optimistic());
+
+        let entry = Signal::buy("BTC-USD-PERP", 1.0)
+            .with_stop_loss(49_000.0)
+            .with_take_profit(51_000.0);
+        let strategy = ScriptedStrategy::new(vec![entry]);
+        let observed = strategy.observed_stops.clone();
+
+        let candles = ohlc_candles(&[
+            (50_000.0, 50_000.0, 50_000.0, 50_000.0),
+            (50_000.0, 50_000.0, 50_000.0, 50_000.0), // entry fills
+            (50_000.0, 50_500.0, 49_800.0, 50_400.0),
+            (50_400.0, 51_200.0, 50_300.0, 51_100.0), // take-profit
+            (51_100.0, 51_100.0, 48_000.0, 48_500.0),
+        ]);
+
+        let report = Backtester::new(config)
+            .with_strategy(Box::new(strategy))
+            .run(candles)
+            .await
+            .unwrap();
+
+        assert_eq!(report.trades.len(), 1);
+        assert_eq!(report.trades[0].entry_price, 50_000.0);
+        assert_eq!(report.trades[0].exit_price, 51_000.0);
+
+        // Strategy sees the levels on its position while it is open
+        let observed = observed.lock().unwrap().clone();
+        assert_eq!(
+            observed,
+            vec![None, Some(49_000.0), Some(49_000.0), None, None]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_backtester_modify_attaches_exits() {
+        let config = BacktestConfig::new()
+            .with_capital(100_000.0)
+            .with_execution(crate::config::ExecutionConfig::optimistic());
+
+        let modify = Signal::Modify {
+            symbol: "BTC-USD-PERP".to_string(),
+            stop_loss: None,
+            take_profit: None,
+            trailing_stop: None,
+            metadata: std::collections::HashMap::new(),
+        };
+        let strategy = ScriptedStrategy::new(vec![
+            // Without a position, Modify is ignored
+            modify.clone().with_stop_loss(45_000.0),
+            Signal::buy("BTC-USD-PERP", 1.0),
+            modify.with_trailing_stop(1_000.0),
+        ]);
+        let observed = strategy.observed_stops.clone();
+
+        let candles = ohlc_candles(&[
+            (50_000.0, 50_000.0, 50_000.0, 50_000.0),
+            (50_000.0, 50_000.0, 50_000.0, 50_000.0),
+            (50_000.0, 50_000.0, 50_000.0, 50_000.0), // entry fills
+            (50_000.0, 52_000.0, 49_500.0, 51_800.0), // trail follows the high
+            (51_800.0, 51_900.0, 50_500.0, 50_800.0), // stopped at 51,000
+        ]);
+
+        let report = Backtester::new(config)
+            .with_strategy(Box::new(strategy))
+            .run(candles)
+            .await
+            .unwrap();
+
+        assert_eq!(report.trades.len(), 1);
+        assert_eq!(report.trades[0].exit_price, 51_000.0);
+
+        let observed = observed.lock().unwrap().clone();
+        assert_eq!(observed, vec![None, None, None, Some(51_000.0), None]);
+    }
+
+    fn series(symbol: &str, step_minutes: i64, closes: &[f64]) -> CandleSeries {
+        let start = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let candles = closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Candle {
+                symbol: Symbol::new(symbol),
+                timestamp: start + chrono::Duration::minutes(i as i64 * step_minutes),
+                open: close.into(),
+                high: close.into(),
+                low: close.into(),
+                close: close.into(),
+                volume: 100.0.into(),
+            })
+            .collect();
+        CandleSeries::from_candles(symbol, candles)
+    }
+
+    /// Callback names with the clock time they were called at
+    type CallLog = std::sync::Arc<std::sync::Mutex<Vec<(&'static str, DateTime<Utc>)>>>;
+
+    /// Buys on the first candle and closes on the second timer, logging the
+    /// clock time of each timer and fill notification
+    struct TimerStrategy {
+        config: StrategyConfig,
+        calls: CallLog,
+    }
+
+    impl TimerStrategy {
+        fn log(&self, call: &'static str, ctx: &StrategyContext) -> usize {
+            let mut calls = self.calls.lock().unwrap();
+            calls.push((call, ctx.now()));
+            calls.iter().filter(|(logged, _)| *logged == call).count()
+        }
+    }
+
+    #[async_trait]
+    impl Strategy for TimerStrategy {
+        fn name(&self) -> &str {
+            &self.config.name
+        }
+        fn config(&self) -> &StrategyConfig {
+            &self.config
+        }
+        fn state(&self) -> StrategyState {
+            StrategyState::Running
+        }
+
+        async fn on_candle(
+            &mut self,
+            candle: &Candle,
+            ctx: &StrategyContext,
+        ) -> velora_strategy::StrategyResult<Signal> {
+            if ctx.has_position(candle.symbol.as_str())? || !self.calls.lock().unwrap().is_empty() {
+                return Ok(Signal::Hold);
+            }
+            Ok(Signal::buy(candle.symbol.as_str(), 1.0))
+        }
+
+        async fn on_order_update(
+            &mut self,
+            ctx: &StrategyContext,
+        ) -> velora_strategy::StrategyResult<Signal> {
+            self.log("fill", ctx);
+            Ok(Signal::Hold)
+        }
+
+        async fn on_timer(
+            &mut self,
+            ctx: &StrategyContext,
+        ) -> velora_strategy::StrategyResult<Signal> {
+            if self.log("timer", ctx) == 2 {
+                return Ok(Signal::close("BTC-USD-PERP"));
+            }
+            Ok(Signal::Hold)
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    #[tokio::test]
+    async fn test_backtester_calls_timer_and_fill_callbacks() {
+        let calls = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let strategy = Box::new(TimerStrategy {
+            config: StrategyConfig::new("Timer"),
+            calls: calls.clone(),
+        });
+        let config = BacktestConfig::new()
+            .with_capital(10_000.0)
+            .with_execution(crate::config::ExecutionConfig::optimistic())
+            .with_timer_interval_ms(Some(3 * 60_000));
+
+        let report = Backtester::new(config)
+            .with_strategy(strategy)
+            .run_series(vec![series("BTC-USD-PERP", 1, &[100.0; 6])])
+            .await
+            .unwrap();
+
+        // Timer every 3 minutes of candle close time; fills one bar after orders
+        let minute = |n: i64| {
+            chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+                + chrono::Duration::minutes(n)
+        };
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                ("timer", minute(1)),
+                ("fill", minute(2)),
+                ("timer", minute(4)),
+                ("fill", minute(5)),
+            ]
+        );
+        assert_eq!(report.trades.len(), 1);
+    }
+
+    /// Symbols of an instant with the BTC and ETH prices seen at it
+    type Observation = (Vec<String>, Option<f64>, Option<f64>);
+
+    /// Goes long ETH / short BTC on the second instant, flat on the fourth,
+    /// recording what it sees of both symbols at each instant
+    struct PairStrategy {
+        config: StrategyConfig,
+        calls: usize,
+        seen: std::sync::Arc<std::sync::Mutex<Vec<Observation>>>,
+    }
+
+    #[async_trait]
+    impl Strategy for PairStrategy {
+        fn name(&self) -> &str {
+            "Pair"
+        }
+        fn config(&self) -> &StrategyConfig {
+            &self.config
+        }
+        fn state(&self) -> StrategyState {
+            StrategyState::Running
+        }
+
+        async fn on_candles(
+            &mut self,
+            candles: &[Candle],
+            ctx: &StrategyContext,
+        ) -> velora_strategy::StrategyResult<Vec<Signal>> {
+            let symbols = candles.iter().map(|c| c.symbol.to_string()).collect();
+            self.seen.lock().unwrap().push((
+                symbols,
+                ctx.get_last_price("BTC")?,
+                ctx.get_last_price("ETH")?,
+            ));
+
+            self.calls += 1;
+            Ok(match self.calls {
+                2 => vec![Signal::buy("ETH", 1.0), Signal::sell("BTC", 1.0)],
+                4 => vec![Signal::close("ETH"), Signal::close("BTC")],
+                _ => vec![],
+            })
+        }
+
+        fn reset(&mut self) {
+            self.calls = 0;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backtester_synchronizes_symbols() {
+        let config = BacktestConfig::new()
+            .with_capital(10_000.0)
+            .with_symbols(vec!["ETH".to_string(), "BTC".to_string()])
+            .with_execution(crate::config::ExecutionConfig::optimistic());
+
+        let strategy = PairStrategy {
+            config: StrategyConfig::new("Pair"),
+            calls: 0,
+            seen: Default::default(),
+        };
+        let seen = strategy.seen.clone();
+
+        // 1 minute BTC candles and 2 minute ETH candles
+        let report = Backtester::new(config)
+            .with_strategy(Box::new(strategy))
+            .run_series(vec![
+                series("BTC", 1, &[100.0, 101.0, 102.0, 103.0, 104.0, 105.0]),
+                series("ETH", 2, &[10.0, 12.0, 14.0]),
+                series("SOL", 1, &[20.0, 21.0]),
+            ])
+            .await
+            .unwrap();
+
+        // One call and one equity point per instant; SOL is not configured
+        let seen = seen.lock().unwrap().clone();
+        let symbols: Vec<_> = seen
+            .iter()
+            .map(|(symbols, _, _)| symbols.join(","))
+            .collect();
+        assert_eq!(
+            symbols,
+            vec!["BTC", "ETH,BTC", "BTC", "ETH,BTC", "BTC", "ETH,BTC"]
+        );
+        assert_eq!(seen[1].1, Some(101.0));
+        assert_eq!(seen[1].2, Some(10.0));
+        assert_eq!(seen[2].2, Some(10.0));
+        assert_eq!(report.equity_curve.len(), 6);
+
+        assert_eq!(report.trades.len(), 2);
+        let btc = &report.trades[0];
+        assert_eq!(btc.symbol, "BTC");
+        assert_eq!(btc.side, PositionSide::Short);
+        assert_eq!((btc.entry_price, btc.exit_price), (102.0, 104.0));
+        let eth = &report.trades[1];
+        assert_eq!(eth.symbol, "ETH");
+        assert_eq!((eth.entry_price, eth.exit_price), (12.0, 14.0));
+    }
+
+    #[tokio::test]
+    async fn test_backtester_requires_configured_symbols() {
+        let config = BacktestConfig::new().with_symbols(vec!["ETH".to_string()]);
+
+        let result = Backtester::new(config)
+            .with_strategy(Box::new(DummyStrategy::new()))
+            .run_series(vec![series("BTC", 1, &[100.0])])
+            .await;
+
+        assert!(matches!(result, Err(BacktestError::DataError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_backtester_pyramids_reduces_and_flips() {
+        let config = BacktestConfig::new()
+            .with_capital(10_000.0)
+            .with_execution(crate::config::ExecutionConfig::optimistic());
+
+        let strategy = ScriptedStrategy::new(vec![
+            Signal::buy("BTC-USD-PERP", 1.0),
+            Signal::buy("BTC-USD-PERP", 1.0),
+            Signal::Close {
+                symbol: "BTC-USD-PERP".to_string(),
+                quantity: Some(1.0),
+                metadata: std::collections::HashMap::new(),
+            },
+            Signal::sell("BTC-USD-PERP", 2.0),
+            Signal::close("BTC-USD-PERP"),
+        ]);
+
+        let candles = ohlc_candles(&[
+            (100.0, 100.0, 100.0, 100.0),
+            (110.0, 110.0, 110.0, 110.0), // buy 1 @ 110
+            (120.0, 120.0, 120.0, 120.0), // buy 1 @ 120
+            (130.0, 130.0, 130.0, 130.0), // sell 1 @ 130: first lot closed
+            (125.0, 125.0, 125.0, 125.0), // sell 2 @ 125: second lot closed, 1 short
+            (120.0, 120.0, 120.0, 120.0), // buy 1 @ 120: short closed
+        ]);
+
+        let report = Backtester::new(config)
+            .with_strategy(Box::new(strategy))
+            .run(candles)
+            .await
+            .unwrap();
+
+        let trades: Vec<_> = report
+            .trades
+            .iter()
+            .map(|t| (t.side, t.entry_price, t.exit_price, t.quantity, t.pnl))
+            .collect();
+        assert_eq!(
+            trades,
+            vec![
+                (PositionSide::Long, 110.0, 130.0, 1.0, 20.0),
+                (PositionSide::Long, 120.0, 125.0, 1.0, 5.0),
+                (PositionSide::Short, 125.0, 120.0, 1.0, 5.0),
+            ]
+        );
+        assert_eq!(report.equity_curve.last().unwrap().equity, 10_030.0);
+    }
+
+    /// Joins the best bid once, then closes at the next quote after filling
+    struct QuotingStrategy {
+        config: StrategyConfig,
+        quoted: bool,
+    }
+
+    #[async_trait]
+    impl Strategy for QuotingStrategy {
+        fn name(&self) -> &str {
+            "Quoting"
+        }
+        fn config(&self) -> &StrategyConfig {
+            &self.config
+        }
+        fn state(&self) -> StrategyState {
+            StrategyState::Running
+        }
+
+        async fn on_tick(
+            &mut self,
+            tick: &Tick,
+            ctx: &StrategyContext,
+        ) -> velora_strategy::StrategyResult<Signal> {
+            let symbol = tick.symbol.as_str();
+            if ctx.has_position(symbol)? {
+                return Ok(Signal::close(symbol));
+            }
+            if self.quoted {
+                return Ok(Signal::Hold);
+            }
+
+            let book = ctx.get_order_book(symbol)?.unwrap();
+            let mut signal = Signal::buy(symbol, 1.0);
+            if let Signal::Buy {
+                ref mut limit_price,
+                ..
+            } = signal
+            {
+                *limit_price = book.best_bid().map(|level| level.price.into_inner());
+            }
+            self.quoted = true;
+            Ok(signal)
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    #[tokio::test]
+    async fn test_backtester_replays_order_book() {
+        let start = Utc::now();
+        let at = |seconds| start + chrono::Duration::seconds(seconds);
+        let book = |seconds, bid: (f64, f64), ask: (f64, f64)| {
+            let level = |(price, quantity): (f64, f64)| velora_core::types::BookLevel {
+                price: price.into(),
+                quantity: quantity.into(),
+            };
+            ReplayEvent::from(velora_core::types::OrderBook {
+                symbol: Symbol::new("BTC-USD-PERP"),
+                bids: vec![level(bid)],
+                asks: vec![level(ask)],
+                timestamp: at(seconds),
+            })
+        };
+        let sell = |seconds, quantity: f64| {
+            ReplayEvent::from(velora_core::types::Trade {
+                id: uuid::Uuid::new_v4(),
+                order_id: uuid::Uuid::nil(),
+                symbol: Symbol::new("BTC-USD-PERP"),
+                side: Side::Sell,
+                price: 99.0.into(),
+                quantity: quantity.into(),
+                fee: 0.0.into(),
+                timestamp: at(seconds),
+            })
+        };
+
+        // Out of order on purpose: the replay sorts by time
+        let events = vec![
+            sell(2, 2.5),
+            book(1, (99.0, 2.0), (101.0, 2.0)),
+            sell(3, 1.0),
+            book(4, (102.0, 3.0), (103.0, 1.0)),
+        ];
+
+        let strategy = QuotingStrategy {
+            config: StrategyConfig::new("Quoting"),
+            quoted: false,
+        };
+        let report = Backtester::new(
+            BacktestConfig::new()
+                .with_capital(10_000.0)
+                .with_execution(crate::config::ExecutionConfig::optimistic()),
+        )
+        .with_strategy(Box::new(strategy))
+        .run_replay(events)
+        .await
+        .unwrap();
+
+        // The bid filled in two parts once the 2 units ahead had traded,
+        // then the position was sold into the new bid
+        let trades: Vec<_> = report
+            .trades
+            .iter()
+            .map(|t| (t.entry_price, t.exit_price, t.quantity))
+            .collect();
+        assert_eq!(trades, vec![(99.0, 102.0, 0.5), (99.0, 102.0, 0.5)]);
+        assert_eq!(report.equity_curve.len(), 4);
+        assert_eq!(report.equity_curve.last().unwrap().equity, 10_003.0);
+    }
+
+    #[tokio::test]
+    async fn test_backtester_replay_requires_events() {
+        let result = Backtester::new(BacktestConfig::new())
+            .with_strategy(Box::new(DummyStrategy::new()))
+            .run_replay(vec![])
+            .await;
+        assert!(matches!(result, Err(BacktestError::DataError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_backtester_order_latency_delays_fills() {
+        let run = |order_latency_ms: f64| async move {
+            let mut execution = crate::config::ExecutionConfig::optimistic();
+            execution.latency.order = Some(crate::config::Latency::Fixed {
+                ms: order_latency_ms,
+            });
+            let strategy = ScriptedStrategy::new(vec![Signal::buy("BTC-USD-PERP", 1.0)]);
+            let candles = ohlc_candles(&[
+                (100.0, 100.0, 100.0, 100.0),
+                (101.0, 101.0, 101.0, 101.0),
+                (102.0, 102.0, 102.0, 102.0),
+                (103.0, 103.0, 103.0, 103.0),
+            ]);
+            Backtester::new(BacktestConfig::new().with_execution(execution))
+                .with_strategy(Box::new(strategy))
+                .run(candles)
+                .await
+                .unwrap()
+        };
+
+        // Sent at the first close: fills on the next bar within a minute,
+        // one bar later beyond it
+        assert_eq!(run(0.0).await.equity_curve.last().unwrap().equity, 10_002.0);
+        assert_eq!(
+            run(90_000.0).await.equity_curve.last().unwrap().equity,
+            10_001.0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_backtester_perpetual_funding_and_liquidation() {
+        let config = BacktestConfig::new()
+            .with_capital(1_000.0)
+            .with_execution(crate::config::ExecutionConfig::optimistic())
+            .with_perpetual(
+                "BTC-USD-PERP",
+                crate::config::PerpetualConfig::new(10.0).unwrap(),
+            );
+        let candles = ohlc_candles(&[
+            (100.0, 100.0, 100.0, 100.0),
+            (100.0, 100.0, 100.0, 100.0),
+            (100.0, 100.0, 100.0, 100.0),
+            // Wicks through the liquidation level and recovers by the close
+            (100.0, 100.0, 83.0, 95.0),
+        ]);
+        let funding = FundingRate {
+            symbol: Symbol::new("BTC-USD-PERP"),
+            rate: rust_decimal::Decimal::new(1, 3),
+            next_funding_time: candles[3].timestamp,
+            timestamp: candles[2].timestamp,
+        };
+
+        // 10x leverage: 5,000 of notional on 1,000 of capital
+        let strategy = ScriptedStrategy::new(vec![Signal::buy("BTC-USD-PERP", 50.0)]);
+        let report = Backtester::new(config.clone())
+            .with_strategy(Box::new(strategy))
+            .with_funding_rates(vec![funding])
+            .run(candles.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(report.funding_payments.len(), 1);
+        assert_eq!(report.funding_payments[0].amount, -5.0);
+        assert_eq!(report.metrics.funding_pnl, -5.0);
+        assert_eq!(report.equity_curve[1].margin_used, 500.0);
+
+        // Equity of 145 at the low is below the 207.5 maintenance margin, so
+        // the position closes where equity met it: 50 * p - 4,005 = 2.5 * p
+        let level = 4_005.0 / 47.5;
+        assert_eq!(report.liquidations.len(), 1);
+        assert_eq!(report.liquidations[0].equity, 145.0);
+        assert!((report.liquidations[0].price - level).abs() < 1e-9);
+        assert_eq!(report.trades.len(), 1);
+        let equity = report.equity_curve.last().unwrap().equity;
+        assert!((equity - 2.5 * level).abs() < 1e-9);
+        assert!((report.metrics.total_pnl - (equity - 1_000.0)).abs() < 1e-9);
+
+        // Beyond the leverage cap the order is dropped
+        let strategy = ScriptedStrategy::new(vec![Signal::buy("BTC-USD-PERP", 200.0)]);
+        let report = Backtester::new(config)
+            .with_strategy(Box::new(strategy))
+            .run(candles)
+            .await
+            .unwrap();
+        assert!(report.trades.is_empty());
+        assert_eq!(report.equity_curve.last().unwrap().equity, 1_000.0);
+    }
+
+    #[tokio::test]
+    async fn test_backtester_excursions_and_benchmark() {
+        let config = BacktestConfig::new()
+            .with_capital(10_000.0)
+            .with_execution(crate::config::ExecutionConfig::optimistic());
+        let candles = ohlc_candles(&[
+            (100.0, 100.0, 100.0, 100.0),
+            (100.0, 110.0, 90.0, 105.0),
+            (105.0, 108.0, 95.0, 100.0),
+            (100.0, 100.0, 100.0, 100.0),
+        ]);
+
+        let strategy = BuyThenCloseStrategy::new(Signal::buy("BTC-USD-PERP", 10.0));
+        let report = Backtester::new(config)
+            .with_strategy(Box::new(strategy))
+            .with_benchmark(CandleSeries::from_candles("BTC-USD-PERP", candles.clone()))
+            .run(candles)
+            .await
+            .unwrap();
+
+        assert_eq!(report.trades.len(), 1);
+        // Entered at 105 on the second close; the third candle ranged 95-108
+        assert_eq!(report.trades[0].mae, -100.0);
+        assert_eq!(report.trades[0].mfe, 30.0);
+        assert!(report.metrics.time_in_market > 0.0);
+        assert!(report.metrics.max_exposure > 0.0);
+
+        let benchmark = report.metrics.benchmark.unwrap();
+        assert_eq!(benchmark.total_return, 0.0);
+        assert!(benchmark.beta > 0.0);
+    }
 }
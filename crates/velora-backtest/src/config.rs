@@ -1,7 +1,13 @@
 //! Configuration types for backtesting.
 
-use chrono::{DateTime, Utc};
+use crate::errors::{BacktestError, BacktestResult};
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use velora_core::Schedule;
+use velora_exchange::types::{FeeModel, PerpetualInfo};
 
 /// Main configuration for a backtest run
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,16 +26,56 @@ pub struct BacktestConfig {
 
     /// Execution configuration
     pub execution: ExecutionConfig,
+
+    /// Order in which reducing fills consume open lots
+    #[serde(default)]
+    pub lot_matching: LotMatching,
+
+    /// Symbols traded as perpetuals, with their margin rules
+    ///
+    /// Other symbols are traded without margin checks.
+    #[serde(default)]
+    pub perpetuals: HashMap<String, PerpetualConfig>,
+
+    /// Number of equity snapshots in each rolling Sharpe/volatility window
+    #[serde(default = "default_rolling_window")]
+    pub rolling_window: usize,
+
+    /// Interval between `Strategy::on_timer` calls in milliseconds, on the
+    /// replayed time (None disables the timer)
+    #[serde(default = "default_timer_interval_ms")]
+    pub timer_interval_ms: Option<u64>,
+
+    /// End of the warm-up period of candle backtests
+    ///
+    /// Candles closing at or before it reach the strategy, so indicators
+    /// fill up, but its signals are dropped and no equity is recorded.
+    #[serde(default)]
+    pub warmup_until: Option<DateTime<Utc>>,
+}
+
+fn default_rolling_window() -> usize {
+    30
+}
+
+/// Same default as the trading engine, so replays of both call the timer alike
+fn default_timer_interval_ms() -> Option<u64> {
+    Some(1000)
 }
 
 impl Default for BacktestConfig {
     fn default() -> Self {
         Self {
             initial_capital: 10_000.0,
-            start_date: Utc::now() - chrono::Duration::days(365),
+            start_date: Utc::now() - Duration::days(365),
             end_date: Utc::now(),
             symbols: vec![],
             execution: ExecutionConfig::default(),
+            lot_matching: LotMatching::default(),
+            perpetuals: HashMap::new(),
+            rolling_window: default_rolling_window(),
+            timer_interval_ms: default_timer_interval_ms(),
+            warmup_until: None,
         }
     }
 }
@@ -64,31 +110,192 @@ impl BacktestConfig {
         self.execution = execution;
         self
     }
+
+    /// Set lot matching
+    pub fn with_lot_matching(mut self, lot_matching: LotMatching) -> Self {
+        self.lot_matching = lot_matching;
+        self
+    }
+
+    /// Trade a symbol as a perpetual with the given margin rules
+    pub fn with_perpetual(mut self, symbol: impl Into<String>, perpetual: PerpetualConfig) -> Self {
+        self.perpetuals.insert(symbol.into(), perpetual);
+        self
+    }
+
+    /// Set the window of rolling metrics, in equity snapshots
+    pub fn with_rolling_window(mut self, snapshots: usize) -> Self {
+        self.rolling_window = snapshots;
+        self
+    }
+
+    /// Set the strategy timer interval (None disables `Strategy::on_timer`)
+    pub fn with_timer_interval_ms(mut self, ms: Option<u64>) -> Self {
+        self.timer_interval_ms = ms;
+        self
+    }
+
+    /// Set the end of the warm-up period (None trades from the first candle)
+    pub fn with_warmup_until(mut self, until: Option<DateTime<Utc>>) -> Self {
+        self.warmup_until = until;
+        self
+    }
+
+    /// Schedule of the strategy timer, if enabled
+    pub(crate) fn timer(&self) -> Option<Schedule> {
+        self.timer_interval_ms
+            .map(|ms| Schedule::every(Duration::milliseconds(ms as i64)))
+    }
+}
+
+/// Margin rules of a perpetual contract
+///
+/// Positions share the account equity as collateral (cross margin). New
+/// orders need initial margin on the resulting position; once equity falls
+/// below the maintenance margin of all positions, they are liquidated.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PerpetualConfig {
+    /// Highest leverage allowed
+    pub max_leverage: f64,
+
+    /// Initial margin rate (share of the notional)
+    pub initial_margin: f64,
+
+    /// Maintenance margin rate (share of the notional)
+    pub maintenance_margin: f64,
+}
+
+impl PerpetualConfig {
+    /// Create margin rules from the maximum leverage
+    ///
+    /// The initial margin is `1 / max_leverage` and the maintenance margin
+    /// half of it. The leverage must be finite and at least 1.
+    pub fn new(max_leverage: f64) -> BacktestResult<Self> {
+        if !max_leverage.is_finite() || max_leverage < 1.0 {
+            return Err(BacktestError::InvalidConfig(format!(
+                "Maximum leverage must be finite and at least 1, got {max_leverage}"
+            )));
+        }
+        Ok(Self::with_leverage(max_leverage))
+    }
+
+    /// Margin rules for a leverage known to be valid
+    fn with_leverage(max_leverage: f64) -> Self {
+        let initial_margin = 1.0 / max_leverage;
+        Self {
+            max_leverage,
+            initial_margin,
+            maintenance_margin: initial_margin / 2.0,
+        }
+    }
+
+    /// Set the maintenance margin rate
+    ///
+    /// The rate must lie between 0 and the initial margin rate.
+    pub fn with_maintenance_margin(mut self, rate: f64) -> BacktestResult<Self> {
+        if !(0.0..=self.initial_margin).contains(&rate) {
+            return Err(BacktestError::InvalidConfig(format!(
+                "Maintenance margin {rate} must lie between 0 and the initial margin {}",
+                self.initial_margin
+            )));
+        }
+        self.maintenance_margin = rate;
+        Ok(self)
+    }
+
+    /// Margin rate needed to open a position, honouring the maximum leverage
+    pub fn initial_margin_rate(&self) -> f64 {
+        self.initial_margin.max(1.0 / self.max_leverage)
+    }
+}
+
+impl From<&PerpetualInfo> for PerpetualConfig {
+    fn from(info: &PerpetualInfo) -> Self {
+        let defaults = Self::with_leverage(f64::from(info.max_leverage.max(1)));
+        Self {
+            initial_margin: info
+                .initial_margin
+                .and_then(|rate| rate.to_f64())
+                .unwrap_or(defaults.initial_margin),
+            maintenance_margin: info
+                .maintenance_margin
+                .and_then(|rate| rate.to_f64())
+                .unwrap_or(defaults.maintenance_margin),
+            ..defaults
+        }
+    }
+}
+
+/// Order in which a reducing fill consumes the lots of a position
+///
+/// Each consumed lot (or part of one) is reported as its own completed trade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LotMatching {
+    /// Oldest lot first
+    #[default]
+    Fifo,
+
+    /// Newest lot first
+    Lifo,
 }
 
 /// Configuration for order execution simulation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionConfig {
-    /// Commission rate (e.g., 0.001 = 0.1%)
+    /// Commission rate (e.g., 0.001 = 0.1%), unless `fees` is set
     pub commission_rate: f64,
 
+    /// Exchange fee schedule with maker/taker rates, tiers and rebates
+    #[serde(default)]
+    pub fees: Option<FeeModel>,
+
     /// Slippage in basis points
     pub slippage_bps: f64,
 
-    /// Simulated fill delay in milliseconds
+    /// Order submission latency in milliseconds, unless `latency.order` is set
     pub fill_delay_ms: u64,
 
     /// Fill model to use
     pub fill_model: FillModel,
+
+    /// Price impact in basis points of an order trading a whole candle's volume
+    /// (see [`FillModel::SquareRootImpact`])
+    #[serde(default)]
+    pub impact_bps: f64,
+
+    /// Bid-ask spread in basis points (see [`FillModel::SpreadCrossing`])
+    #[serde(default)]
+    pub spread_bps: f64,
+
+    /// Largest share of a candle's volume orders may fill on it
+    ///
+    /// The rest of a larger order fills on the following candles as partial
+    /// fills. Bracket exits and candles without volume are not limited.
+    #[serde(default)]
+    pub max_participation: Option<f64>,
+
+    /// Which exit fills when a bar touches both stop-loss and take-profit
+    #[serde(default)]
+    pub intrabar_policy: IntrabarPolicy,
+
+    /// Latencies between the strategy and the exchange
+    #[serde(default)]
+    pub latency: LatencyConfig,
 }
 
 impl Default for ExecutionConfig {
     fn default() -> Self {
         Self {
             commission_rate: 0.001, // 0.1%
-            slippage_bps: 5.0,      // 5 basis points
+            fees: None,
+            slippage_bps: 5.0, // 5 basis points
             fill_delay_ms: 0,
             fill_model: FillModel::Market,
+            impact_bps: 0.0,
+            spread_bps: 0.0,
+            max_participation: None,
+            intrabar_policy: IntrabarPolicy::StopLossFirst,
+            latency: LatencyConfig::default(),
         }
     }
 }
@@ -98,9 +305,15 @@ impl ExecutionConfig {
     pub fn realistic() -> Self {
         Self {
             commission_rate: 0.001,
+            fees: None,
             slippage_bps: 5.0,
             fill_delay_ms: 100,
             fill_model: FillModel::Realistic,
+            impact_bps: 0.0,
+            spread_bps: 0.0,
+            max_participation: None,
+            intrabar_policy: IntrabarPolicy::StopLossFirst,
+            latency: LatencyConfig::default(),
         }
     }
 
@@ -108,9 +321,15 @@ impl ExecutionConfig {
     pub fn pessimistic() -> Self {
         Self {
             commission_rate: 0.002,
+            fees: None,
             slippage_bps: 10.0,
             fill_delay_ms: 500,
             fill_model: FillModel::Pessimistic,
+            impact_bps: 0.0,
+            spread_bps: 0.0,
+            max_participation: None,
+            intrabar_policy: IntrabarPolicy::StopLossFirst,
+            latency: LatencyConfig::default(),
         }
     }
 
@@ -118,24 +337,157 @@ impl ExecutionConfig {
     pub fn optimistic() -> Self {
         Self {
             commission_rate: 0.0,
+            fees: None,
             slippage_bps: 0.0,
             fill_delay_ms: 0,
             fill_model: FillModel::Market,
+            impact_bps: 0.0,
+            spread_bps: 0.0,
+            max_participation: None,
+            intrabar_policy: IntrabarPolicy::StopLossFirst,
+            latency: LatencyConfig::default(),
         }
     }
 }
 
+impl ExecutionConfig {
+    /// Charge fills by an exchange fee schedule instead of `commission_rate`
+    pub fn with_fees(mut self, fees: FeeModel) -> Self {
+        self.fees = Some(fees);
+        self
+    }
+
+    /// Fee schedule of fills; `commission_rate` on every fill when unset
+    pub fn fee_model(&self) -> FeeModel {
+        self.fees
+            .clone()
+            .unwrap_or_else(|| FeeModel::flat(self.commission_rate))
+    }
+
+    /// Latency of order submissions
+    pub fn order_latency(&self) -> Latency {
+        self.latency.order.unwrap_or(Latency::Fixed {
+            ms: self.fill_delay_ms as f64,
+        })
+    }
+}
+
+/// Simulated latencies
+///
+/// An order reaches the exchange once the strategy has seen the market data
+/// it reacts to and the order has travelled: market data latency plus order
+/// latency after the event. Cancels take market data plus cancel latency,
+/// and the order can still fill until then. Random latencies are drawn from
+/// a generator seeded with `seed`, so runs are reproducible.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencyConfig {
+    /// Order submission latency; `fill_delay_ms` when unset
+    #[serde(default)]
+    pub order: Option<Latency>,
+
+    /// Order cancel latency
+    #[serde(default)]
+    pub cancel: Latency,
+
+    /// Delay before the strategy sees market data
+    #[serde(default)]
+    pub market_data: Latency,
+
+    /// Seed of the random latencies
+    #[serde(default)]
+    pub seed: u64,
+}
+
+/// A latency, fixed or drawn at random
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Latency {
+    /// Always the same
+    Fixed {
+        /// Latency in milliseconds
+        ms: f64,
+    },
+
+    /// Uniformly distributed between two bounds
+    Uniform {
+        /// Lowest latency in milliseconds
+        min_ms: f64,
+
+        /// Highest latency in milliseconds
+        max_ms: f64,
+    },
+
+    /// A floor plus exponentially distributed jitter (long-tailed)
+    Exponential {
+        /// Lowest latency in milliseconds
+        min_ms: f64,
+
+        /// Mean of the jitter in milliseconds
+        mean_jitter_ms: f64,
+    },
+}
+
+impl Default for Latency {
+    fn default() -> Self {
+        Latency::Fixed { ms: 0.0 }
+    }
+}
+
+impl Latency {
+    /// Draw a latency
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Duration {
+        let ms = match *self {
+            Latency::Fixed { ms } => ms,
+            Latency::Uniform { min_ms, max_ms } if max_ms > min_ms => {
+                rng.random_range(min_ms..=max_ms)
+            }
+            Latency::Uniform { min_ms, .. } => min_ms,
+            Latency::Exponential {
+                min_ms,
+                mean_jitter_ms,
+            } => {
+                let uniform: f64 = rng.random();
+                min_ms - mean_jitter_ms * (1.0 - uniform).ln()
+            }
+        };
+        Duration::microseconds((ms.max(0.0) * 1_000.0).round() as i64)
+    }
+}
+
 /// Model for simulating order fills
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FillModel {
     /// Always fill at current market price (close)
     Market,
 
-    /// Realistic fills with slippage based on order size
+    /// Realistic fills with a fixed slippage (`slippage_bps`)
     Realistic,
 
     /// Pessimistic fills (worst case pricing)
     Pessimistic,
+
+    /// Square-root market impact: `impact_bps * sqrt(quantity / candle volume)`
+    SquareRootImpact,
+
+    /// Aggressive orders pay half of a fixed spread (`spread_bps`)
+    SpreadCrossing,
+}
+
+/// Resolution of a bar that touches both exits of a bracket
+///
+/// Candles do not tell which extreme came first, so the simulator needs a
+/// rule. When the bar opens beyond one of the levels, that exit fills
+/// regardless of the policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum IntrabarPolicy {
+    /// Assume the stop-loss was hit first (conservative)
+    #[default]
+    StopLossFirst,
+
+    /// Assume the take-profit was hit first (optimistic)
+    TakeProfitFirst,
+
+    /// Assume the level closer to the bar open was hit first
+    NearestToOpen,
 }
 
 #[cfg(test)]
@@ -150,6 +502,56 @@ mod tests {
 
         assert_eq!(config.initial_capital, 50_000.0);
         assert_eq!(config.symbols.len(), 1);
+        assert_eq!(config.lot_matching, LotMatching::Fifo);
+
+        let config = config.with_lot_matching(LotMatching::Lifo);
+        assert_eq!(config.lot_matching, LotMatching::Lifo);
+
+        assert_eq!(config.rolling_window, 30);
+        let config = config.with_rolling_window(90);
+        assert_eq!(config.rolling_window, 90);
+
+        assert_eq!(config.timer_interval_ms, Some(1000));
+        let config = config.with_timer_interval_ms(None);
+        assert!(config.timer().is_none());
+    }
+
+    #[test]
+    fn test_perpetual_margin_rates() {
+        let perpetual = PerpetualConfig::new(20.0).unwrap();
+        assert_eq!(perpetual.initial_margin, 0.05);
+        assert_eq!(perpetual.maintenance_margin, 0.025);
+        let perpetual = perpetual.with_maintenance_margin(0.05).unwrap();
+        assert_eq!(perpetual.maintenance_margin, 0.05);
+
+        for leverage in [0.0, 0.5, -2.0, f64::INFINITY, f64::NAN] {
+            assert!(matches!(
+                PerpetualConfig::new(leverage),
+                Err(BacktestError::InvalidConfig(_))
+            ));
+        }
+        for rate in [0.06, -0.01, f64::NAN] {
+            assert!(matches!(
+                perpetual.with_maintenance_margin(rate),
+                Err(BacktestError::InvalidConfig(_))
+            ));
+        }
+
+        let info = PerpetualInfo {
+            funding_interval: std::time::Duration::from_secs(8 * 3600),
+            max_leverage: 10,
+            maker_fee: rust_decimal::Decimal::ZERO,
+            taker_fee: rust_decimal::Decimal::ZERO,
+            initial_margin: Some(rust_decimal::Decimal::new(5, 2)),
+            maintenance_margin: Some(rust_decimal::Decimal::new(3, 2)),
+        };
+        let perpetual = PerpetualConfig::from(&info);
+        assert_eq!(perpetual.maintenance_margin, 0.03);
+        // The leverage cap is stricter than the exchange's initial margin
+        assert_eq!(perpetual.initial_margin_rate(), 0.1);
+
+        let config = BacktestConfig::new().with_perpetual("BTC-USD-PERP", perpetual);
+        assert_eq!(config.perpetuals["BTC-USD-PERP"], perpetual);
     }
 
     #[test]
@@ -165,5 +567,61 @@ mod tests {
         let optimistic = ExecutionConfig::optimistic();
         assert_eq!(optimistic.commission_rate, 0.0);
         assert_eq!(optimistic.slippage_bps, 0.0);
+        assert_eq!(optimistic.intrabar_policy, IntrabarPolicy::StopLossFirst);
+    }
+
+    #[test]
+    fn test_intrabar_policy_defaults_when_missing() {
+        let json = r#"{
+            "commission_rate": 0.001,
+            "slippage_bps": 5.0,
+            "fill_delay_ms": 0,
+            "fill_model": "Market"
+        }"#;
+        let config: ExecutionConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.intrabar_policy, IntrabarPolicy::StopLossFirst);
+        assert_eq!(config.latency, LatencyConfig::default());
+        assert_eq!(config.max_participation, None);
+        assert_eq!(config.fee_model(), FeeModel::flat(0.001));
+    }
+
+    #[test]
+    fn test_latency_sampling() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let fixed = Latency::Fixed { ms: 1.5 };
+        assert_eq!(fixed.sample(&mut rng), Duration::microseconds(1_500));
+
+        let uniform = Latency::Uniform {
+            min_ms: 10.0,
+            max_ms: 20.0,
+        };
+        let exponential = Latency::Exponential {
+            min_ms: 5.0,
+            mean_jitter_ms: 2.0,
+        };
+        for _ in 0..100 {
+            let sample = uniform.sample(&mut rng);
+            assert!(sample >= Duration::milliseconds(10) && sample <= Duration::milliseconds(20));
+            assert!(exponential.sample(&mut rng) >= Duration::milliseconds(5));
+        }
+
+        // The same seed draws the same latencies
+        let draw = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..5).map(|_| uniform.sample(&mut rng)).collect::<Vec<_>>()
+        };
+        assert_eq!(draw(1), draw(1));
+        assert_ne!(draw(1), draw(2));
+    }
+
+    #[test]
+    fn test_order_latency_defaults_to_fill_delay() {
+        let mut config = ExecutionConfig::realistic();
+        assert_eq!(config.order_latency(), Latency::Fixed { ms: 100.0 });
+
+        config.latency.order = Some(Latency::Fixed { ms: 3.0 });
+        assert_eq!(config.order_latency(), Latency::Fixed { ms: 3.0 });
     }
 }
@@ -12,6 +12,14 @@ pub enum BacktestError {
     #[error("Strategy error: {0}")]
     Strategy(#[from] velora_strategy::StrategyError),
 
+    /// Position sizing error
+    #[error("Risk error: {0}")]
+    Risk(#[from] velora_risk::RiskError),
+
+    /// Error of the live trading engine during an engine replay
+    #[error("Engine error: {0}")]
+    Engine(#[from] velora_engine::EngineError),
+
     /// Data loading error
     #[error("Data error: {0}")]
     DataError(String),
@@ -1,12 +1,16 @@
 //! Order execution simulation.
 
-use crate::config::{ExecutionConfig, FillModel};
+use crate::config::{ExecutionConfig, FillModel, IntrabarPolicy, Latency};
 use crate::errors::{BacktestError, BacktestResult};
+use crate::replay::{BookDelta, DepthBook};
 use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use velora_core::types::{Candle, Side};
-use velora_strategy::Signal;
+use velora_core::types::{Candle, OrderBook, Side, Symbol, Tick, Trade};
+use velora_exchange::types::{FeeModel, Liquidity};
+use velora_strategy::{PositionSide, Signal};
 
 /// Unique order identifier
 pub type OrderId = u64;
@@ -23,15 +27,33 @@ pub struct Order {
     /// Order side
     pub side: Side,
 
-    /// Quantity
+    /// Quantity left to fill
     pub quantity: f64,
 
     /// Limit price (if applicable)
     pub limit_price: Option<f64>,
 
+    /// Stop trigger price (if applicable)
+    pub stop_price: Option<f64>,
+
+    /// Exits attached to the position once the order fills
+    pub bracket: Option<Bracket>,
+
+    /// Only closes an existing position
+    pub reduce_only: bool,
+
     /// When the order was created
     pub created_at: DateTime<Utc>,
 
+    /// When the order reaches the exchange and can fill
+    pub active_at: DateTime<Utc>,
+
+    /// When a requested cancel takes effect
+    pub cancel_at: Option<DateTime<Utc>>,
+
+    /// Has rested through a candle, so it fills as a maker
+    pub resting: bool,
+
     /// Order status
     pub status: OrderStatus,
 }
@@ -49,6 +71,124 @@ pub enum OrderStatus {
     Cancelled,
 }
 
+/// Protective exits attached to a position
+///
+/// The stop-loss and take-profit form an OCO pair: when one fills, the
+/// other is cancelled. A trailing stop follows the best price seen since the
+/// bracket was attached; the tighter of it and `stop_loss` applies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Bracket {
+    /// Stop-loss price
+    pub stop_loss: Option<f64>,
+
+    /// Take-profit price
+    pub take_profit: Option<f64>,
+
+    /// Trailing stop distance (in price)
+    pub trailing_stop: Option<f64>,
+}
+
+impl Bracket {
+    /// Check if no exit is set
+    pub fn is_empty(&self) -> bool {
+        self.stop_loss.is_none() && self.take_profit.is_none() && self.trailing_stop.is_none()
+    }
+}
+
+/// Bracket protecting an open position
+#[derive(Debug, Clone)]
+struct ActiveBracket {
+    side: PositionSide,
+    quantity: f64,
+    exits: Bracket,
+
+    /// Best price seen since the bracket was attached
+    extreme: f64,
+}
+
+impl ActiveBracket {
+    /// Effective stop level, combining the fixed and trailing stops
+    fn stop_level(&self) -> Option<f64> {
+        let trailing = self.exits.trailing_stop.map(|distance| match self.side {
+            PositionSide::Long => self.extreme - distance,
+            PositionSide::Short => self.extreme + distance,
+        });
+
+        match (self.exits.stop_loss, trailing) {
+            (Some(fixed), Some(trailing)) => Some(match self.side {
+                PositionSide::Long => fixed.max(trailing),
+                PositionSide::Short => fixed.min(trailing),
+            }),
+            (fixed, trailing) => fixed.or(trailing),
+        }
+    }
+
+    /// Side of the orders closing the position
+    fn exit_side(&self) -> Side {
+        match self.side {
+            PositionSide::Long => Side::Sell,
+            PositionSide::Short => Side::Buy,
+        }
+    }
+
+    /// Price at which an exit is triggered by the candle, if any
+    ///
+    /// The stop fills at its level, or at the open when the bar gaps through
+    /// it. The take-profit fills at its level.
+    fn triggered_exit(&self, candle: &Candle, policy: IntrabarPolicy) -> Option<f64> {
+        let open = candle.open.into_inner();
+        let (high, low) = (candle.high.into_inner(), candle.low.into_inner());
+        let stop = self.stop_level();
+        let target = self.exits.take_profit;
+
+        let (stop_hit, stop_gapped, target_hit, target_gapped) = match self.side {
+            PositionSide::Long => (
+                stop.is_some_and(|s| low <= s),
+                stop.is_some_and(|s| open <= s),
+                target.is_some_and(|t| high >= t),
+                target.is_some_and(|t| open >= t),
+            ),
+            PositionSide::Short => (
+                stop.is_some_and(|s| high >= s),
+                stop.is_some_and(|s| open >= s),
+                target.is_some_and(|t| low <= t),
+                target.is_some_and(|t| open <= t),
+            ),
+        };
+
+        let stop_first = match (stop_hit, target_hit) {
+            (false, false) => return None,
+            (true, false) => true,
+            (false, true) => false,
+            _ if stop_gapped => true,
+            _ if target_gapped => false,
+            _ => match policy {
+                IntrabarPolicy::StopLossFirst => true,
+                IntrabarPolicy::TakeProfitFirst => false,
+                IntrabarPolicy::NearestToOpen => {
+                    let stop = stop.unwrap_or(open);
+                    let target = target.unwrap_or(open);
+                    (open - stop).abs() <= (target - open).abs()
+                }
+            },
+        };
+
+        if stop_first {
+            stop.map(|stop| if stop_gapped { open } else { stop })
+        } else {
+            target
+        }
+    }
+
+    /// Move the trailing reference to the candle's best price
+    fn ratchet(&mut self, candle: &Candle) {
+        self.extreme = match self.side {
+            PositionSide::Long => self.extreme.max(candle.high.into_inner()),
+            PositionSide::Short => self.extreme.min(candle.low.into_inner()),
+        };
+    }
+}
+
 /// Fill event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Fill {
@@ -67,51 +207,126 @@ pub struct Fill {
     /// Fill price
     pub price: f64,
 
-    /// Commission paid
+    /// Commission paid (negative for a maker rebate)
     pub commission: f64,
 
+    /// Whether the fill added or took liquidity
+    #[serde(default)]
+    pub liquidity: Liquidity,
+
     /// When the fill occurred
     pub timestamp: DateTime<Utc>,
+
+    /// Fill of an order that only closes a position
+    #[serde(default)]
+    pub reduce_only: bool,
 }
 
 /// Execution simulator
+///
+/// Orders fill against candles ([`ExecutionSimulator::process_candle`]) or
+/// against replayed trades, ticks and order books. In replay, aggressive
+/// orders take the book's liquidity and the rest of a limit order joins the
+/// back of its price level; it fills once the volume ahead of it has traded
+/// or the price trades through it.
+///
+/// Orders only fill once they have reached the exchange, see
+/// [`crate::config::LatencyConfig`]. Fills pay the maker or taker rate of
+/// [`ExecutionConfig::fee_model`]: limit orders filling from the book are
+/// makers, orders crossing the spread (market, stop, marketable limit and
+/// bracket exits) are takers.
 pub struct ExecutionSimulator {
     config: ExecutionConfig,
+    fees: FeeModel,
     next_order_id: OrderId,
     pending_orders: HashMap<OrderId, Order>,
+    brackets: HashMap<String, ActiveBracket>,
     fills: Vec<Fill>,
+
+    /// Replayed order books by symbol
+    books: HashMap<String, DepthBook>,
+
+    /// Quantity queued ahead of each resting limit order
+    queue_ahead: HashMap<OrderId, f64>,
+
+    /// Source of random latencies
+    rng: StdRng,
+
+    /// Notional traded so far, for volume fee tiers
+    traded_volume: f64,
 }
 
 impl ExecutionSimulator {
     /// Create a new execution simulator
     pub fn new(config: ExecutionConfig) -> Self {
+        let rng = StdRng::seed_from_u64(config.latency.seed);
         Self {
+            fees: config.fee_model(),
             config,
             next_order_id: 1,
             pending_orders: HashMap::new(),
+            brackets: HashMap::new(),
             fills: Vec::new(),
+            books: HashMap::new(),
+            queue_ahead: HashMap::new(),
+            rng,
+            traded_volume: 0.0,
         }
     }
 
     /// Submit a new order from a strategy signal
+    ///
+    /// A `stop_price` turns the order into a stop (or stop-limit) entry, and
+    /// `stop_loss`/`take_profit`/`trailing_stop` attach a bracket to the
+    /// position once the order fills.
     pub fn submit_order(
         &mut self,
         signal: Signal,
         timestamp: DateTime<Utc>,
     ) -> BacktestResult<OrderId> {
-        let (symbol, quantity, side, limit_price) = match signal {
+        let (symbol, quantity, side, limit_price, stop_price, bracket) = match signal {
             Signal::Buy {
                 symbol,
                 quantity,
                 limit_price,
+                stop_price,
+                stop_loss,
+                take_profit,
+                trailing_stop,
                 ..
-            } => (symbol, quantity, Side::Buy, limit_price),
+            } => (
+                symbol,
+                quantity,
+                Side::Buy,
+                limit_price,
+                stop_price,
+                Bracket {
+                    stop_loss,
+                    take_profit,
+                    trailing_stop,
+                },
+            ),
             Signal::Sell {
                 symbol,
                 quantity,
                 limit_price,
+                stop_price,
+                stop_loss,
+                take_profit,
+                trailing_stop,
                 ..
-            } => (symbol, quantity, Side::Sell, limit_price),
+            } => (
+                symbol,
+                quantity,
+                Side::Sell,
+                limit_price,
+                stop_price,
+                Bracket {
+                    stop_loss,
+                    take_profit,
+                    trailing_stop,
+                },
+            ),
             Signal::Close { .. } => {
                 // Close signals are handled separately
                 return Err(BacktestError::InvalidOrder(
@@ -134,7 +349,13 @@ impl ExecutionSimulator {
             side,
             quantity,
             limit_price,
+            stop_price,
+            bracket: (!bracket.is_empty()).then_some(bracket),
+            reduce_only: false,
             created_at: timestamp,
+            active_at: self.arrival(timestamp, self.config.order_latency()),
+            cancel_at: None,
+            resting: false,
             status: OrderStatus::Pending,
         };
 
@@ -160,7 +381,13 @@ impl ExecutionSimulator {
             side,
             quantity,
             limit_price: None, // Market order
+            stop_price: None,
+            bracket: None,
+            reduce_only: true,
             created_at: timestamp,
+            active_at: self.arrival(timestamp, self.config.order_latency()),
+            cancel_at: None,
+            resting: false,
             status: OrderStatus::Pending,
         };
 
@@ -169,86 +396,617 @@ impl ExecutionSimulator {
         Ok(order_id)
     }
 
+    /// Request the cancel of a pending order
+    ///
+    /// The order can still fill until the cancel reaches the exchange.
+    /// Returns false if the order is no longer pending.
+    pub fn cancel_order(&mut self, order_id: OrderId, timestamp: DateTime<Utc>) -> bool {
+        let cancel_at = self.arrival(timestamp, self.config.latency.cancel);
+        let Some(order) = self.pending_orders.get_mut(&order_id) else {
+            return false;
+        };
+        order.cancel_at = Some(order.cancel_at.map_or(cancel_at, |at| at.min(cancel_at)));
+        true
+    }
+
+    /// When a request sent in reaction to an event at `timestamp` arrives
+    fn arrival(&mut self, timestamp: DateTime<Utc>, latency: Latency) -> DateTime<Utc> {
+        let seen_at = timestamp + self.config.latency.market_data.sample(&mut self.rng);
+        seen_at + latency.sample(&mut self.rng)
+    }
+
+    /// Attach a bracket to an open position, replacing the current one
+    ///
+    /// `reference_price` seeds the trailing stop. When the position already
+    /// has a bracket, the best price it has seen is kept so that a trailing
+    /// stop never loosens. An empty bracket removes the exits.
+    pub fn attach_bracket(
+        &mut self,
+        symbol: impl Into<String>,
+        side: PositionSide,
+        quantity: f64,
+        reference_price: f64,
+        exits: Bracket,
+    ) {
+        let symbol = symbol.into();
+        if exits.is_empty() {
+            self.brackets.remove(&symbol);
+            return;
+        }
+
+        let extreme = match self.brackets.get(&symbol) {
+            Some(current) if current.side == side => match side {
+                PositionSide::Long => current.extreme.max(reference_price),
+                PositionSide::Short => current.extreme.min(reference_price),
+            },
+            _ => reference_price,
+        };
+
+        self.brackets.insert(
+            symbol,
+            ActiveBracket {
+                side,
+                quantity,
+                exits,
+                extreme,
+            },
+        );
+    }
+
+    /// Resize a symbol's bracket to its position
+    ///
+    /// The bracket is removed when the position is gone or has flipped side.
+    pub fn sync_bracket(&mut self, symbol: &str, position: Option<(PositionSide, f64)>) {
+        match (self.brackets.get_mut(symbol), position) {
+            (Some(bracket), Some((side, quantity))) if bracket.side == side => {
+                bracket.quantity = quantity;
+            }
+            (Some(_), _) => {
+                self.brackets.remove(symbol);
+            }
+            (None, _) => {}
+        }
+    }
+
+    /// Remove the bracket of a symbol
+    pub fn cancel_bracket(&mut self, symbol: &str) -> Option<Bracket> {
+        self.brackets.remove(symbol).map(|bracket| bracket.exits)
+    }
+
+    /// Get the bracket attached to a symbol's position
+    pub fn bracket(&self, symbol: &str) -> Option<&Bracket> {
+        self.brackets.get(symbol).map(|bracket| &bracket.exits)
+    }
+
+    /// Current stop-loss and take-profit levels of a symbol's bracket
+    ///
+    /// The stop-loss includes the trailing stop.
+    pub fn exit_levels(&self, symbol: &str) -> Option<(Option<f64>, Option<f64>)> {
+        self.brackets
+            .get(symbol)
+            .map(|bracket| (bracket.stop_level(), bracket.exits.take_profit))
+    }
+
     /// Process a candle and generate fills for pending orders
+    ///
+    /// Brackets attached before this candle are resolved first, using the
+    /// candle's high and low. Brackets of orders filled on this candle only
+    /// become active from the next one. The candle is taken to close at its
+    /// open time; see [`ExecutionSimulator::process_candle_until`].
     pub fn process_candle(&mut self, candle: &Candle) -> Vec<Fill> {
+        self.process_candle_until(candle, candle.timestamp)
+    }
+
+    /// Process a candle that closes at `close_time`
+    ///
+    /// Orders reaching the exchange before the close can fill on the candle.
+    /// Cancels take effect on it if they arrived by its open.
+    pub fn process_candle_until(
+        &mut self,
+        candle: &Candle,
+        close_time: DateTime<Utc>,
+    ) -> Vec<Fill> {
+        let symbol = candle.symbol.as_str();
+        self.expire_cancels(symbol, candle.timestamp);
+
         let mut fills = Vec::new();
-        let mut filled_orders = Vec::new();
+        self.resolve_bracket(candle, &mut fills);
+
+        // Volume the orders may take from this candle; zero volume means
+        // unknown and uncapped
+        let volume = candle.volume.into_inner();
+        let mut available = match self.config.max_participation {
+            Some(rate) if volume > 0.0 => rate * volume,
+            _ => f64::INFINITY,
+        };
 
-        for (order_id, order) in &self.pending_orders {
-            // Only process orders for this symbol
-            if order.symbol != candle.symbol.as_str() {
+        for order_id in self.live_order_ids(symbol, close_time) {
+            let Some(order) = self.pending_orders.get_mut(&order_id) else {
+                continue;
+            };
+
+            // A triggered stop-limit outside its limit rests as a plain limit
+            if let (Some(stop_price), Some(limit_price)) = (order.stop_price, order.limit_price) {
+                if let Some(trigger) = stop_trigger_price(order.side, stop_price, candle) {
+                    let within_limit = match order.side {
+                        Side::Buy => trigger <= limit_price,
+                        Side::Sell => trigger >= limit_price,
+                    };
+                    if !within_limit {
+                        order.stop_price = None;
+                        continue;
+                    }
+                }
+            }
+
+            let order = &self.pending_orders[&order_id];
+            let quantity = order.quantity.min(available);
+            if quantity <= 0.0 {
                 continue;
             }
 
             // Check if order can be filled
-            if let Some(fill_price) = self.can_fill_order(order, candle) {
-                let commission = self.calculate_commission(order.quantity, fill_price);
-
-                let fill = Fill {
-                    order_id: *order_id,
-                    symbol: order.symbol.clone(),
-                    side: order.side,
-                    quantity: order.quantity,
-                    price: fill_price,
-                    commission,
-                    timestamp: candle.timestamp,
+            let Some((fill_price, liquidity)) = self.can_fill_order(order, quantity, candle) else {
+                continue;
+            };
+
+            available -= quantity;
+            fills.extend(self.fill_order(
+                order_id,
+                quantity,
+                fill_price,
+                liquidity,
+                candle.timestamp,
+            ));
+        }
+
+        // Limit orders still pending have rested on the book
+        for order_id in self.live_order_ids(symbol, close_time) {
+            if let Some(order) = self.pending_orders.get_mut(&order_id) {
+                order.resting |= order.limit_price.is_some() && order.stop_price.is_none();
+            }
+        }
+
+        fills
+    }
+
+    /// Replace a symbol's order book and match its orders against it
+    ///
+    /// Orders that arrived since the previous event are matched against the
+    /// book they found before it is replaced.
+    pub fn apply_book(&mut self, book: &OrderBook) -> Vec<Fill> {
+        let symbol = book.symbol.as_str();
+        let mut fills = self.match_orders(symbol, book.timestamp);
+        let depth = self.books.entry(symbol.to_string()).or_default();
+        depth.reset(book);
+
+        // Liquidity that left the level can no longer be ahead of us
+        for order in self.pending_orders.values() {
+            if let (Some(ahead), Some(limit)) =
+                (self.queue_ahead.get_mut(&order.id), order.limit_price)
+            {
+                if order.symbol == symbol {
+                    *ahead = ahead.min(depth.level(order.side, limit));
+                }
+            }
+        }
+
+        fills.extend(self.match_orders(symbol, book.timestamp));
+        fills
+    }
+
+    /// Apply a change of one book level and match the symbol's orders
+    pub fn apply_book_delta(&mut self, delta: &BookDelta) -> Vec<Fill> {
+        let mut fills = self.match_orders(&delta.symbol, delta.timestamp);
+        let depth = self.books.entry(delta.symbol.clone()).or_default();
+        depth.set_level(delta.side, delta.price, delta.quantity);
+
+        for order in self.pending_orders.values() {
+            let at_level = order.symbol == delta.symbol
+                && order.side == delta.side
+                && order.limit_price == Some(delta.price);
+            if let Some(ahead) = self.queue_ahead.get_mut(&order.id).filter(|_| at_level) {
+                *ahead = ahead.min(delta.quantity);
+            }
+        }
+
+        fills.extend(self.match_orders(&delta.symbol, delta.timestamp));
+        fills
+    }
+
+    /// Process a public trade; its side is the aggressor's
+    pub fn process_trade(&mut self, trade: &Trade) -> Vec<Fill> {
+        self.process_print(
+            trade.symbol.as_str(),
+            Some(trade.side),
+            trade.price.into_inner(),
+            trade.quantity.into_inner(),
+            trade.timestamp,
+        )
+    }
+
+    /// Process a tick, a traded price without an aggressor side
+    pub fn process_tick(&mut self, tick: &Tick) -> Vec<Fill> {
+        self.process_print(
+            tick.symbol.as_str(),
+            None,
+            tick.price.into_inner(),
+            tick.volume.into_inner(),
+            tick.timestamp,
+        )
+    }
+
+    /// Match a symbol's orders against its replayed book
+    ///
+    /// New orders take liquidity up to their limit; what is left of a limit
+    /// order rests behind the quantity already at its price. A resting order
+    /// fills at its limit once the other side of the book reaches it. Only
+    /// orders that have reached the exchange by `timestamp` are matched.
+    /// Does nothing for symbols without a book.
+    pub fn match_orders(&mut self, symbol: &str, timestamp: DateTime<Utc>) -> Vec<Fill> {
+        let mut fills = Vec::new();
+        if !self.books.contains_key(symbol) {
+            return fills;
+        }
+        self.expire_cancels(symbol, timestamp);
+
+        for order_id in self.live_order_ids(symbol, timestamp) {
+            let order = &self.pending_orders[&order_id];
+            if order.stop_price.is_some() {
+                continue;
+            }
+            let (side, quantity, limit) = (order.side, order.quantity, order.limit_price);
+            let Some(depth) = self.books.get_mut(symbol) else {
+                continue;
+            };
+
+            if let (true, Some(limit)) = (self.queue_ahead.contains_key(&order_id), limit) {
+                let reached = match side {
+                    Side::Buy => depth.best_ask().is_some_and(|ask| ask <= limit),
+                    Side::Sell => depth.best_bid().is_some_and(|bid| bid >= limit),
                 };
+                if reached {
+                    fills.extend(self.fill_order(
+                        order_id,
+                        quantity,
+                        limit,
+                        Liquidity::Maker,
+                        timestamp,
+                    ));
+                }
+                continue;
+            }
+
+            let taken = depth.take(side, quantity, limit);
+            let ahead = limit.map(|limit| depth.level(side, limit));
+            if let Some((filled, price)) = taken {
+                fills.extend(self.fill_order(order_id, filled, price, Liquidity::Taker, timestamp));
+            }
+            if let Some(ahead) = ahead.filter(|_| self.pending_orders.contains_key(&order_id)) {
+                self.queue_ahead.insert(order_id, ahead);
+            }
+        }
+
+        fills
+    }
+
+    /// Resolve exits, stops and resting orders against a traded price
+    ///
+    /// Stops and bracket exits trigger on traded prices only. Without a
+    /// book, market orders fill at the print and new limit orders fill at it
+    /// when it is within their limit, otherwise they rest with nothing ahead.
+    /// A print at a resting order's price first consumes the quantity ahead
+    /// of it.
+    fn process_print(
+        &mut self,
+        symbol: &str,
+        aggressor: Option<Side>,
+        price: f64,
+        quantity: f64,
+        timestamp: DateTime<Utc>,
+    ) -> Vec<Fill> {
+        let print = Candle {
+            symbol: Symbol::new(symbol),
+            open: price.into(),
+            high: price.into(),
+            low: price.into(),
+            close: price.into(),
+            volume: quantity.into(),
+            timestamp,
+        };
+
+        // Orders that arrived since the previous event meet the book first
+        let mut fills = self.match_orders(symbol, timestamp);
+        self.expire_cancels(symbol, timestamp);
+        self.resolve_bracket(&print, &mut fills);
 
-                fills.push(fill.clone());
-                self.fills.push(fill);
-                filled_orders.push(*order_id);
+        for order in self.pending_orders.values_mut() {
+            let live = order.symbol == symbol && order.active_at <= timestamp;
+            if let Some(stop_price) = order.stop_price.filter(|_| live) {
+                if stop_trigger_price(order.side, stop_price, &print).is_some() {
+                    order.stop_price = None;
+                }
             }
         }
 
-        // Remove filled orders
-        for order_id in filled_orders {
+        if self.books.contains_key(symbol) {
+            fills.extend(self.match_orders(symbol, timestamp));
+        }
+        let has_book = self.books.contains_key(symbol);
+
+        let mut unmatched = quantity;
+        for order_id in self.live_order_ids(symbol, timestamp) {
+            let order = &self.pending_orders[&order_id];
+            if order.stop_price.is_some() {
+                continue;
+            }
+            let (side, remaining) = (order.side, order.quantity);
+
+            let Some(limit) = order.limit_price else {
+                if !has_book {
+                    let fill_price = self.calculate_fill_price(side, remaining, &print, None);
+                    fills.extend(self.fill_order(
+                        order_id,
+                        remaining,
+                        fill_price,
+                        Liquidity::Taker,
+                        timestamp,
+                    ));
+                }
+                continue;
+            };
+
+            let (through, at) = match side {
+                Side::Buy => (price < limit, price == limit),
+                Side::Sell => (price > limit, price == limit),
+            };
+
+            let Some(ahead) = self.queue_ahead.get(&order_id).copied() else {
+                if !has_book && (through || at) {
+                    fills.extend(self.fill_order(
+                        order_id,
+                        remaining,
+                        price,
+                        Liquidity::Taker,
+                        timestamp,
+                    ));
+                } else if !has_book {
+                    self.queue_ahead.insert(order_id, 0.0);
+                }
+                continue;
+            };
+
+            if through {
+                fills.extend(self.fill_order(
+                    order_id,
+                    remaining,
+                    limit,
+                    Liquidity::Maker,
+                    timestamp,
+                ));
+            } else if at && aggressor != Some(side) {
+                self.queue_ahead
+                    .insert(order_id, (ahead - unmatched).max(0.0));
+                let filled = (unmatched - ahead).max(0.0).min(remaining);
+                if filled > 0.0 {
+                    unmatched -= filled;
+                    fills.extend(self.fill_order(
+                        order_id,
+                        filled,
+                        limit,
+                        Liquidity::Maker,
+                        timestamp,
+                    ));
+                }
+            }
+        }
+
+        fills
+    }
+
+    /// Fill (part of) a pending order
+    ///
+    /// The order's bracket is attached on its first fill.
+    fn fill_order(
+        &mut self,
+        order_id: OrderId,
+        quantity: f64,
+        price: f64,
+        liquidity: Liquidity,
+        timestamp: DateTime<Utc>,
+    ) -> Option<Fill> {
+        let symbol = self.pending_orders.get(&order_id)?.symbol.clone();
+        let commission = self.calculate_commission(&symbol, liquidity, quantity, price);
+        let order = self.pending_orders.get_mut(&order_id)?;
+        let fill = Fill {
+            order_id,
+            symbol,
+            side: order.side,
+            quantity,
+            price,
+            commission,
+            liquidity,
+            timestamp,
+            reduce_only: order.reduce_only,
+        };
+
+        if let Some(exits) = order.bracket.take() {
+            let side = match order.side {
+                Side::Buy => PositionSide::Long,
+                Side::Sell => PositionSide::Short,
+            };
+            let symbol = order.symbol.clone();
+            self.attach_bracket(symbol, side, quantity, price, exits);
+        }
+
+        let order = self.pending_orders.get_mut(&order_id)?;
+        order.quantity -= quantity;
+        if order.quantity <= FILL_EPSILON {
             if let Some(mut order) = self.pending_orders.remove(&order_id) {
                 order.status = OrderStatus::Filled;
             }
+            self.queue_ahead.remove(&order_id);
         }
 
-        fills
+        self.fills.push(fill.clone());
+        Some(fill)
+    }
+
+    /// Pending orders of a symbol that reached the exchange by `now`, oldest first
+    fn live_order_ids(&self, symbol: &str, now: DateTime<Utc>) -> Vec<OrderId> {
+        let mut order_ids: Vec<OrderId> = self
+            .pending_orders
+            .values()
+            .filter(|order| order.symbol == symbol && order.active_at <= now)
+            .map(|order| order.id)
+            .collect();
+        order_ids.sort_unstable();
+        order_ids
     }
 
-    /// Check if an order can be filled against a candle
-    fn can_fill_order(&self, order: &Order, candle: &Candle) -> Option<f64> {
+    /// Remove the orders of a symbol whose cancel arrived by `now`
+    fn expire_cancels(&mut self, symbol: &str, now: DateTime<Utc>) {
+        let cancelled: Vec<OrderId> = self
+            .pending_orders
+            .values()
+            .filter(|order| order.symbol == symbol)
+            .filter(|order| order.cancel_at.is_some_and(|at| at <= now))
+            .map(|order| order.id)
+            .collect();
+        for order_id in cancelled {
+            if let Some(mut order) = self.pending_orders.remove(&order_id) {
+                order.status = OrderStatus::Cancelled;
+            }
+            self.queue_ahead.remove(&order_id);
+        }
+    }
+
+    /// Resolve the bracket of the candle's symbol, pushing its exit fill
+    fn resolve_bracket(&mut self, candle: &Candle, fills: &mut Vec<Fill>) {
+        if let Some(fill) = self.process_bracket(candle) {
+            // The position is closed, so pending closes have nothing left to do
+            let symbol = fill.symbol.as_str();
+            self.pending_orders
+                .retain(|_, order| !(order.reduce_only && order.symbol == symbol));
+            let pending = &self.pending_orders;
+            self.queue_ahead
+                .retain(|order_id, _| pending.contains_key(order_id));
+
+            self.fills.push(fill.clone());
+            fills.push(fill);
+        }
+    }
+
+    /// Resolve the bracket of the candle's symbol
+    ///
+    /// Returns the exit fill if a stop-loss or take-profit was hit; otherwise
+    /// the trailing stop follows the candle.
+    fn process_bracket(&mut self, candle: &Candle) -> Option<Fill> {
+        let symbol = candle.symbol.as_str();
+        let bracket = self.brackets.get_mut(symbol)?;
+
+        let Some(level) = bracket.triggered_exit(candle, self.config.intrabar_policy) else {
+            bracket.ratchet(candle);
+            return None;
+        };
+
+        let bracket = self.brackets.remove(symbol)?;
+        let side = bracket.exit_side();
+        let price = self.calculate_fill_price(side, bracket.quantity, candle, Some(level));
+
+        let order_id = self.next_order_id;
+        self.next_order_id += 1;
+
+        Some(Fill {
+            order_id,
+            symbol: symbol.to_string(),
+            side,
+            quantity: bracket.quantity,
+            price,
+            commission: self.calculate_commission(
+                symbol,
+                Liquidity::Taker,
+                bracket.quantity,
+                price,
+            ),
+            liquidity: Liquidity::Taker,
+            timestamp: candle.timestamp,
+            reduce_only: true,
+        })
+    }
+
+    /// Check if `quantity` of an order can be filled against a candle
+    ///
+    /// Returns the fill price and whether the order made or took liquidity. A
+    /// new limit order is a taker if the candle opens through its limit.
+    fn can_fill_order(
+        &self,
+        order: &Order,
+        quantity: f64,
+        candle: &Candle,
+    ) -> Option<(f64, Liquidity)> {
+        if let Some(stop_price) = order.stop_price {
+            // Stop order - fills at the trigger once the price trades through it
+            let trigger = stop_trigger_price(order.side, stop_price, candle)?;
+            let price = self.calculate_fill_price(order.side, quantity, candle, Some(trigger));
+            return Some((price, Liquidity::Taker));
+        }
+
         match order.limit_price {
             Some(limit_price) => {
                 // Limit order - check if price was reached
-                match order.side {
-                    Side::Buy => {
-                        // Buy limit: fill if low <= limit_price
-                        if candle.low.into_inner() <= limit_price {
-                            Some(self.calculate_fill_price(order, candle, Some(limit_price)))
-                        } else {
-                            None
-                        }
-                    }
-                    Side::Sell => {
-                        // Sell limit: fill if high >= limit_price
-                        if candle.high.into_inner() >= limit_price {
-                            Some(self.calculate_fill_price(order, candle, Some(limit_price)))
-                        } else {
-                            None
-                        }
-                    }
+                let reached = match order.side {
+                    // Buy limit: fill if low <= limit_price
+                    Side::Buy => candle.low.into_inner() <= limit_price,
+                    // Sell limit: fill if high >= limit_price
+                    Side::Sell => candle.high.into_inner() >= limit_price,
+                };
+                if !reached {
+                    return None;
                 }
+
+                // Never fill through the limit
+                let price =
+                    self.calculate_fill_price(order.side, quantity, candle, Some(limit_price));
+                let open = candle.open.into_inner();
+                let crossing = match order.side {
+                    Side::Buy => open <= limit_price,
+                    Side::Sell => open >= limit_price,
+                };
+                let liquidity = if order.resting || !crossing {
+                    Liquidity::Maker
+                } else {
+                    Liquidity::Taker
+                };
+                Some(match order.side {
+                    Side::Buy => (price.min(limit_price), liquidity),
+                    Side::Sell => (price.max(limit_price), liquidity),
+                })
             }
             None => {
                 // Market order - always fills
-                Some(self.calculate_fill_price(order, candle, None))
+                let price = self.calculate_fill_price(order.side, quantity, candle, None);
+                Some((price, Liquidity::Taker))
             }
         }
     }
 
-    /// Calculate the actual fill price based on fill model
+    /// Calculate the actual fill price of `quantity` based on fill model
     fn calculate_fill_price(
         &self,
-        order: &Order,
+        side: Side,
+        quantity: f64,
         candle: &Candle,
         limit_price: Option<f64>,
     ) -> f64 {
         let base_price = limit_price.unwrap_or_else(|| candle.close.into_inner());
+        let adverse = |cost_bps: f64| {
+            let cost = base_price * cost_bps / 10_000.0;
+            match side {
+                Side::Buy => base_price + cost,
+                Side::Sell => base_price - cost,
+            }
+        };
 
         match self.config.fill_model {
             FillModel::Market => {
@@ -257,26 +1015,47 @@ impl ExecutionSimulator {
             }
             FillModel::Realistic => {
                 // Apply slippage
-                let slippage = base_price * (self.config.slippage_bps / 10_000.0);
-                match order.side {
-                    Side::Buy => base_price + slippage,
-                    Side::Sell => base_price - slippage,
-                }
+                adverse(self.config.slippage_bps)
             }
             FillModel::Pessimistic => {
                 // Use worst price
-                match order.side {
+                match side {
                     Side::Buy => candle.high.into_inner(),
                     Side::Sell => candle.low.into_inner(),
                 }
             }
+            FillModel::SquareRootImpact => {
+                // Impact grows with the square root of the share of the volume
+                let volume = candle.volume.into_inner();
+                let participation = if volume > 0.0 { quantity / volume } else { 1.0 };
+                adverse(self.config.impact_bps * participation.sqrt())
+            }
+            FillModel::SpreadCrossing => {
+                // Pay half the spread around the reference price
+                adverse(self.config.spread_bps / 2.0)
+            }
         }
     }
 
-    /// Calculate commission for an order
-    fn calculate_commission(&self, quantity: f64, price: f64) -> f64 {
+    /// Calculate commission for a fill and count its volume
+    fn calculate_commission(
+        &mut self,
+        symbol: &str,
+        liquidity: Liquidity,
+        quantity: f64,
+        price: f64,
+    ) -> f64 {
         let notional = quantity * price;
-        notional * self.config.commission_rate
+        let commission = self
+            .fees
+            .fee(symbol, liquidity, notional, self.traded_volume);
+        self.traded_volume += notional.abs();
+        commission
+    }
+
+    /// Fee rate of a symbol's next fill, given the volume traded so far
+    pub fn fee_rate(&self, symbol: &str, liquidity: Liquidity) -> f64 {
+        self.fees.rate(symbol, liquidity, self.traded_volume)
     }
 
     /// Get all fills
@@ -288,6 +1067,39 @@ impl ExecutionSimulator {
     pub fn pending_order_count(&self) -> usize {
         self.pending_orders.len()
     }
+
+    /// Get a pending order
+    pub fn pending_order(&self, order_id: OrderId) -> Option<&Order> {
+        self.pending_orders.get(&order_id)
+    }
+
+    /// Quantity queued ahead of a resting limit order
+    pub fn queue_position(&self, order_id: OrderId) -> Option<f64> {
+        self.queue_ahead.get(&order_id).copied()
+    }
+
+    /// Current replayed order book of a symbol
+    pub fn order_book(&self, symbol: &str, timestamp: DateTime<Utc>) -> Option<OrderBook> {
+        self.books
+            .get(symbol)
+            .map(|depth| depth.to_order_book(symbol, timestamp))
+    }
+}
+
+/// Remaining quantity below which an order counts as filled
+const FILL_EPSILON: f64 = 1e-9;
+
+/// Price at which a stop order triggers on a candle, if it does
+///
+/// Buy stops trigger when the high reaches the stop and sell stops when the
+/// low does; a bar opening beyond the stop triggers at the open.
+fn stop_trigger_price(side: Side, stop_price: f64, candle: &Candle) -> Option<f64> {
+    let open = candle.open.into_inner();
+    match side {
+        Side::Buy if candle.high.into_inner() >= stop_price => Some(open.max(stop_price)),
+        Side::Sell if candle.low.into_inner() <= stop_price => Some(open.min(stop_price)),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -351,15 +1163,674 @@ mod tests {
         assert_eq!(fills2.len(), 1);
     }
 
+    fn ohlc(open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle {
+            symbol: Symbol::new("BTC-USD-PERP"),
+            timestamp: Utc::now(),
+            open: open.into(),
+            high: high.into(),
+            low: low.into(),
+            close: close.into(),
+            volume: 100.0.into(),
+        }
+    }
+
+    /// Simulator holding a filled 1 BTC long at 50,000 with the given exits
+    fn long_with(exits: Signal, policy: IntrabarPolicy) -> ExecutionSimulator {
+        let mut simulator = ExecutionSimulator::new(ExecutionConfig {
+            intrabar_policy: policy,
+            ..Default::default()
+        });
+        simulator.submit_order(exits, Utc::now()).unwrap();
+
+        let entry = simulator.process_candle(&ohlc(50_000.0, 50_000.0, 50_000.0, 50_000.0));
+        assert_eq!(entry.len(), 1);
+        simulator
+    }
+
+    #[test]
+    fn test_stop_entry_order() {
+        let mut simulator = ExecutionSimulator::new(ExecutionConfig::default());
+
+        let mut signal = Signal::buy("BTC-USD-PERP", 1.0);
+        if let Signal::Buy {
+            ref mut stop_price, ..
+        } = signal
+        {
+            *stop_price = Some(50_500.0);
+        }
+        simulator.submit_order(signal, Utc::now()).unwrap();
+
+        // High below the stop
+        let fills = simulator.process_candle(&ohlc(50_000.0, 50_400.0, 49_900.0, 50_300.0));
+        assert!(fills.is_empty());
+
+        // Trades through the stop
+        let fills = simulator.process_candle(&ohlc(50_200.0, 50_800.0, 50_100.0, 50_700.0));
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, 50_500.0);
+    }
+
+    #[test]
+    fn test_stop_entry_gap_fills_at_open() {
+        let mut simulator = ExecutionSimulator::new(ExecutionConfig::default());
+
+        let mut signal = Signal::sell("BTC-USD-PERP", 1.0);
+        if let Signal::Sell {
+            ref mut stop_price, ..
+        } = signal
+        {
+            *stop_price = Some(49_500.0);
+        }
+        simulator.submit_order(signal, Utc::now()).unwrap();
+
+        let fills = simulator.process_candle(&ohlc(49_000.0, 49_200.0, 48_800.0, 49_100.0));
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, 49_000.0);
+    }
+
+    #[test]
+    fn test_stop_limit_rests_past_limit() {
+        let mut simulator = ExecutionSimulator::new(ExecutionConfig::default());
+
+        let mut signal = Signal::buy("BTC-USD-PERP", 1.0);
+        if let Signal::Buy {
+            ref mut stop_price,
+            ref mut limit_price,
+            ..
+        } = signal
+        {
+            *stop_price = Some(50_500.0);
+            *limit_price = Some(50_600.0);
+        }
+        simulator.submit_order(signal, Utc::now()).unwrap();
+
+        // Gaps above the limit: triggered, but rests as a limit
+        let fills = simulator.process_candle(&ohlc(51_000.0, 51_200.0, 50_900.0, 51_000.0));
+        assert!(fills.is_empty());
+        assert_eq!(simulator.pending_order_count(), 1);
+
+        // Comes back to the limit
+        let fills = simulator.process_candle(&ohlc(50_800.0, 50_900.0, 50_550.0, 50_700.0));
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, 50_600.0);
+    }
+
+    #[test]
+    fn test_bracket_take_profit_cancels_stop() {
+        let signal = Signal::buy("BTC-USD-PERP", 1.0)
+            .with_stop_loss(49_000.0)
+            .with_take_profit(51_000.0);
+        let mut simulator = long_with(signal, IntrabarPolicy::StopLossFirst);
+        assert_eq!(
+            simulator.exit_levels("BTC-USD-PERP"),
+            Some((Some(49_000.0), Some(51_000.0)))
+        );
+
+        let fills = simulator.process_candle(&ohlc(50_000.0, 51_200.0, 49_500.0, 51_100.0));
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].side, Side::Sell);
+        assert_eq!(fills[0].price, 51_000.0);
+        assert_eq!(fills[0].quantity, 1.0);
+
+        // The stop went with it
+        assert!(simulator.bracket("BTC-USD-PERP").is_none());
+        let fills = simulator.process_candle(&ohlc(48_000.0, 48_500.0, 47_000.0, 48_000.0));
+        assert!(fills.is_empty());
+    }
+
+    #[test]
+    fn test_bracket_inactive_on_entry_bar() {
+        let mut simulator = ExecutionSimulator::new(ExecutionConfig::default());
+        let signal = Signal::buy("BTC-USD-PERP", 1.0).with_stop_loss(49_500.0);
+        simulator.submit_order(signal, Utc::now()).unwrap();
+
+        // Market entry fills at the close; the earlier low does not count
+        let fills = simulator.process_candle(&ohlc(50_000.0, 50_100.0, 49_000.0, 50_000.0));
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].side, Side::Buy);
+        assert!(simulator.bracket("BTC-USD-PERP").is_some());
+    }
+
+    #[test]
+    fn test_intrabar_policy() {
+        let signal = Signal::buy("BTC-USD-PERP", 1.0)
+            .with_stop_loss(49_000.0)
+            .with_take_profit(51_000.0);
+        let both = ohlc(50_000.0, 51_100.0, 48_900.0, 50_000.0);
+
+        let mut simulator = long_with(signal.clone(), IntrabarPolicy::StopLossFirst);
+        assert_eq!(simulator.process_candle(&both)[0].price, 49_000.0);
+
+        let mut simulator = long_with(signal.clone(), IntrabarPolicy::TakeProfitFirst);
+        assert_eq!(simulator.process_candle(&both)[0].price, 51_000.0);
+
+        let mut simulator = long_with(signal.clone(), IntrabarPolicy::NearestToOpen);
+        let near_target = ohlc(50_800.0, 51_100.0, 48_900.0, 50_000.0);
+        assert_eq!(simulator.process_candle(&near_target)[0].price, 51_000.0);
+
+        // Opening through the stop fills it at the open, whatever the policy
+        let mut simulator = long_with(signal, IntrabarPolicy::TakeProfitFirst);
+        let gap = ohlc(48_500.0, 51_100.0, 48_000.0, 50_000.0);
+        assert_eq!(simulator.process_candle(&gap)[0].price, 48_500.0);
+    }
+
+    #[test]
+    fn test_trailing_stop() {
+        let signal = Signal::buy("BTC-USD-PERP", 1.0).with_trailing_stop(500.0);
+        let mut simulator = long_with(signal, IntrabarPolicy::StopLossFirst);
+        assert_eq!(
+            simulator.exit_levels("BTC-USD-PERP"),
+            Some((Some(49_500.0), None))
+        );
+
+        // New high ratchets the stop up
+        let fills = simulator.process_candle(&ohlc(50_000.0, 51_000.0, 50_200.0, 50_900.0));
+        assert!(fills.is_empty());
+        assert_eq!(
+            simulator.exit_levels("BTC-USD-PERP"),
+            Some((Some(50_500.0), None))
+        );
+
+        // Pullback does not loosen it
+        let fills = simulator.process_candle(&ohlc(50_900.0, 50_950.0, 50_600.0, 50_700.0));
+        assert!(fills.is_empty());
+        assert_eq!(
+            simulator.exit_levels("BTC-USD-PERP"),
+            Some((Some(50_500.0), None))
+        );
+
+        let fills = simulator.process_candle(&ohlc(50_700.0, 50_750.0, 50_300.0, 50_400.0));
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, 50_500.0);
+    }
+
+    #[test]
+    fn test_attach_bracket_keeps_trailing_reference() {
+        let signal = Signal::buy("BTC-USD-PERP", 1.0).with_trailing_stop(500.0);
+        let mut simulator = long_with(signal, IntrabarPolicy::StopLossFirst);
+        simulator.process_candle(&ohlc(50_000.0, 51_000.0, 50_200.0, 50_600.0));
+
+        // Tightening the trail keeps the 51,000 high as reference
+        let exits = Bracket {
+            trailing_stop: Some(200.0),
+            ..Default::default()
+        };
+        simulator.attach_bracket("BTC-USD-PERP", PositionSide::Long, 1.0, 50_600.0, exits);
+        assert_eq!(
+            simulator.exit_levels("BTC-USD-PERP"),
+            Some((Some(50_800.0), None))
+        );
+
+        simulator.attach_bracket(
+            "BTC-USD-PERP",
+            PositionSide::Long,
+            1.0,
+            50_600.0,
+            Bracket::default(),
+        );
+        assert!(simulator.bracket("BTC-USD-PERP").is_none());
+    }
+
+    #[test]
+    fn test_bracket_exit_drops_pending_close() {
+        let signal = Signal::buy("BTC-USD-PERP", 1.0).with_stop_loss(49_000.0);
+        let mut simulator = long_with(signal, IntrabarPolicy::StopLossFirst);
+        simulator
+            .submit_close_order("BTC-USD-PERP".to_string(), 1.0, Side::Sell, Utc::now())
+            .unwrap();
+
+        let fills = simulator.process_candle(&ohlc(49_500.0, 49_600.0, 48_500.0, 48_800.0));
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, 49_000.0);
+        assert_eq!(simulator.pending_order_count(), 0);
+    }
+
     #[test]
     fn test_commission_calculation() {
         let config = ExecutionConfig {
             commission_rate: 0.001,
             ..Default::default()
         };
-        let simulator = ExecutionSimulator::new(config);
+        let mut simulator = ExecutionSimulator::new(config);
 
-        let commission = simulator.calculate_commission(1.0, 50_000.0);
+        let commission = simulator.calculate_commission("BTC", Liquidity::Taker, 1.0, 50_000.0);
         assert_eq!(commission, 50.0); // 0.1% of 50,000
     }
+
+    #[test]
+    fn test_maker_taker_fees() {
+        // 1 bps maker rebate and 5 bps taker fee, halved after 75,000 traded
+        let fees = FeeModel::new(-0.0001, 0.0005).with_tier(75_000.0, -0.0001, 0.00025);
+        let config = ExecutionConfig::optimistic().with_fees(fees);
+        let mut simulator = ExecutionSimulator::new(config);
+        let limit_buy = |price: f64| {
+            let mut signal = Signal::buy("BTC-USD-PERP", 1.0);
+            if let Signal::Buy {
+                ref mut limit_price,
+                ..
+            } = signal
+            {
+                *limit_price = Some(price);
+            }
+            signal
+        };
+
+        // Market orders take liquidity
+        simulator
+            .submit_order(Signal::buy("BTC-USD-PERP", 1.0), Utc::now())
+            .unwrap();
+        let fills = simulator.process_candle(&ohlc(50_000.0, 50_100.0, 49_900.0, 50_000.0));
+        assert_eq!(fills[0].liquidity, Liquidity::Taker);
+        assert_eq!(fills[0].commission, 25.0);
+
+        // A limit below the market rests and earns the rebate
+        simulator
+            .submit_order(limit_buy(49_000.0), Utc::now())
+            .unwrap();
+        assert!(simulator
+            .process_candle(&ohlc(50_000.0, 50_100.0, 49_500.0, 49_800.0))
+            .is_empty());
+        let fills = simulator.process_candle(&ohlc(49_800.0, 49_900.0, 48_900.0, 49_000.0));
+        assert_eq!(fills[0].liquidity, Liquidity::Maker);
+        assert_eq!(fills[0].commission, -4.9);
+
+        // A limit the market opens through crosses the spread, at the next tier
+        simulator
+            .submit_order(limit_buy(50_000.0), Utc::now())
+            .unwrap();
+        let fills = simulator.process_candle(&ohlc(49_000.0, 49_100.0, 48_900.0, 49_000.0));
+        assert_eq!(fills[0].liquidity, Liquidity::Taker);
+        assert_eq!(fills[0].commission, 50_000.0 * 0.00025);
+        assert_eq!(
+            simulator.fee_rate("BTC-USD-PERP", Liquidity::Taker),
+            0.00025
+        );
+    }
+
+    fn book(bids: &[(f64, f64)], asks: &[(f64, f64)]) -> OrderBook {
+        let levels = |levels: &[(f64, f64)]| {
+            levels
+                .iter()
+                .map(|&(price, quantity)| velora_core::types::BookLevel {
+                    price: price.into(),
+                    quantity: quantity.into(),
+                })
+                .collect()
+        };
+        OrderBook {
+            symbol: Symbol::new("BTC-USD-PERP"),
+            bids: levels(bids),
+            asks: levels(asks),
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn trade(side: Side, price: f64, quantity: f64) -> Trade {
+        Trade {
+            id: uuid::Uuid::new_v4(),
+            order_id: uuid::Uuid::nil(),
+            symbol: Symbol::new("BTC-USD-PERP"),
+            side,
+            price: price.into(),
+            quantity: quantity.into(),
+            fee: 0.0.into(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn tick(price: f64) -> Tick {
+        Tick {
+            symbol: Symbol::new("BTC-USD-PERP"),
+            price: price.into(),
+            volume: 1.0.into(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_market_order_walks_book() {
+        let mut simulator = ExecutionSimulator::new(ExecutionConfig::optimistic());
+        simulator.apply_book(&book(&[(99.0, 1.0)], &[(101.0, 1.0), (102.0, 2.0)]));
+
+        simulator
+            .submit_order(Signal::buy("BTC-USD-PERP", 2.0), Utc::now())
+            .unwrap();
+        let fills = simulator.match_orders("BTC-USD-PERP", Utc::now());
+
+        assert_eq!(fills.len(), 1);
+        assert_eq!((fills[0].quantity, fills[0].price), (2.0, 101.5));
+        let book = simulator.order_book("BTC-USD-PERP", Utc::now()).unwrap();
+        assert_eq!(book.asks.len(), 1);
+        assert_eq!(book.asks[0].quantity.into_inner(), 1.0);
+
+        // A thin book fills what it can; the rest waits for liquidity
+        let order_id = simulator
+            .submit_order(Signal::buy("BTC-USD-PERP", 3.0), Utc::now())
+            .unwrap();
+        let fills = simulator.match_orders("BTC-USD-PERP", Utc::now());
+        assert_eq!((fills[0].quantity, fills[0].price), (1.0, 102.0));
+        assert_eq!(simulator.pending_order(order_id).unwrap().quantity, 2.0);
+    }
+
+    #[test]
+    fn test_resting_order_keeps_queue_position() {
+        let mut simulator = ExecutionSimulator::new(ExecutionConfig::optimistic());
+        simulator.apply_book(&book(&[(99.0, 5.0)], &[(101.0, 1.0)]));
+
+        let mut signal = Signal::buy("BTC-USD-PERP", 1.0);
+        if let Signal::Buy {
+            ref mut limit_price,
+            ..
+        } = signal
+        {
+            *limit_price = Some(99.0);
+        }
+        let order_id = simulator.submit_order(signal, Utc::now()).unwrap();
+        assert!(simulator
+            .match_orders("BTC-USD-PERP", Utc::now())
+            .is_empty());
+        assert_eq!(simulator.queue_position(order_id), Some(5.0));
+
+        // Sells at our price work through the queue ahead of us
+        assert!(simulator
+            .process_trade(&trade(Side::Sell, 99.0, 3.0))
+            .is_empty());
+        assert_eq!(simulator.queue_position(order_id), Some(2.0));
+
+        // Cancellations can only shrink the queue to what is left at the level
+        simulator.apply_book_delta(&BookDelta {
+            symbol: "BTC-USD-PERP".to_string(),
+            side: Side::Buy,
+            price: 99.0,
+            quantity: 2.0,
+            timestamp: Utc::now(),
+        });
+        simulator.apply_book_delta(&BookDelta {
+            symbol: "BTC-USD-PERP".to_string(),
+            side: Side::Buy,
+            price: 99.0,
+            quantity: 1.0,
+            timestamp: Utc::now(),
+        });
+        assert_eq!(simulator.queue_position(order_id), Some(1.0));
+
+        // Buy aggressors never hit a bid
+        assert!(simulator
+            .process_trade(&trade(Side::Buy, 99.0, 5.0))
+            .is_empty());
+
+        let fills = simulator.process_trade(&trade(Side::Sell, 99.0, 1.5));
+        assert_eq!((fills[0].quantity, fills[0].price), (0.5, 99.0));
+        assert_eq!(simulator.queue_position(order_id), Some(0.0));
+
+        // Trading through the price fills the rest at the limit
+        let fills = simulator.process_trade(&trade(Side::Sell, 98.5, 0.1));
+        assert_eq!((fills[0].quantity, fills[0].price), (0.5, 99.0));
+        assert_eq!(simulator.pending_order_count(), 0);
+        assert_eq!(simulator.queue_position(order_id), None);
+    }
+
+    #[test]
+    fn test_book_reaching_resting_order_fills_it() {
+        let mut simulator = ExecutionSimulator::new(ExecutionConfig::optimistic());
+        simulator.apply_book(&book(&[(99.0, 1.0)], &[(101.0, 1.0)]));
+
+        let mut signal = Signal::sell("BTC-USD-PERP", 1.0);
+        if let Signal::Sell {
+            ref mut limit_price,
+            ..
+        } = signal
+        {
+            *limit_price = Some(100.0);
+        }
+        simulator.submit_order(signal, Utc::now()).unwrap();
+        assert!(simulator
+            .match_orders("BTC-USD-PERP", Utc::now())
+            .is_empty());
+
+        let fills = simulator.apply_book(&book(&[(100.5, 1.0)], &[(101.0, 1.0)]));
+        assert_eq!((fills[0].quantity, fills[0].price), (1.0, 100.0));
+    }
+
+    #[test]
+    fn test_orders_fill_on_prints_without_book() {
+        let config = ExecutionConfig {
+            commission_rate: 0.001,
+            ..ExecutionConfig::optimistic()
+        };
+        let mut simulator = ExecutionSimulator::new(config);
+
+        simulator
+            .submit_order(Signal::buy("BTC-USD-PERP", 2.0), Utc::now())
+            .unwrap();
+        let fills = simulator.process_tick(&tick(100.0));
+        assert_eq!((fills[0].quantity, fills[0].price), (2.0, 100.0));
+        assert!((fills[0].commission - 0.2).abs() < 1e-12);
+
+        // A sell limit above the market rests, then fills when traded through
+        let mut signal = Signal::sell("BTC-USD-PERP", 1.0);
+        if let Signal::Sell {
+            ref mut limit_price,
+            ..
+        } = signal
+        {
+            *limit_price = Some(105.0);
+        }
+        simulator.submit_order(signal, Utc::now()).unwrap();
+        assert!(simulator.process_tick(&tick(104.0)).is_empty());
+        let fills = simulator.process_tick(&tick(106.0));
+        assert_eq!(fills[0].price, 105.0);
+
+        // Stops trigger on traded prices
+        let mut signal = Signal::sell("BTC-USD-PERP", 1.0);
+        if let Signal::Sell {
+            ref mut stop_price, ..
+        } = signal
+        {
+            *stop_price = Some(95.0);
+        }
+        simulator.submit_order(signal, Utc::now()).unwrap();
+        assert!(simulator.process_tick(&tick(96.0)).is_empty());
+        let fills = simulator.process_trade(&trade(Side::Sell, 94.0, 1.0));
+        assert_eq!(fills[0].price, 94.0);
+    }
+
+    fn latency_config(order_ms: f64, cancel_ms: f64, market_data_ms: f64) -> ExecutionConfig {
+        let mut config = ExecutionConfig::optimistic();
+        config.latency = crate::config::LatencyConfig {
+            order: Some(Latency::Fixed { ms: order_ms }),
+            cancel: Latency::Fixed { ms: cancel_ms },
+            market_data: Latency::Fixed { ms: market_data_ms },
+            seed: 0,
+        };
+        config
+    }
+
+    fn tick_at(price: f64, timestamp: DateTime<Utc>) -> Tick {
+        Tick {
+            timestamp,
+            ..tick(price)
+        }
+    }
+
+    #[test]
+    fn test_order_waits_for_latency_on_candles() {
+        let mut simulator = ExecutionSimulator::new(latency_config(90_000.0, 0.0, 0.0));
+        let start = Utc::now();
+        simulator
+            .submit_order(Signal::buy("BTC-USD-PERP", 1.0), start)
+            .unwrap();
+
+        let mut candle = create_test_candle(100.0, 99.0, 101.0);
+        candle.timestamp = start;
+        let minute = chrono::Duration::minutes(1);
+        assert!(simulator
+            .process_candle_until(&candle, start + minute)
+            .is_empty());
+
+        candle.timestamp = start + minute;
+        let fills = simulator.process_candle_until(&candle, start + minute * 2);
+        assert_eq!(fills.len(), 1);
+    }
+
+    #[test]
+    fn test_order_and_market_data_latency_on_ticks() {
+        let mut simulator = ExecutionSimulator::new(latency_config(5.0, 0.0, 5.0));
+        let start = Utc::now();
+        let ms = chrono::Duration::milliseconds;
+        let order_id = simulator
+            .submit_order(Signal::buy("BTC-USD-PERP", 1.0), start)
+            .unwrap();
+        assert_eq!(
+            simulator.pending_order(order_id).unwrap().active_at,
+            start + ms(10)
+        );
+
+        assert!(simulator
+            .process_tick(&tick_at(100.0, start + ms(9)))
+            .is_empty());
+        let fills = simulator.process_tick(&tick_at(101.0, start + ms(10)));
+        assert_eq!(fills[0].price, 101.0);
+    }
+
+    #[test]
+    fn test_order_fills_until_cancel_arrives() {
+        let ms = chrono::Duration::milliseconds;
+        let start = Utc::now();
+        let resting_bid = |simulator: &mut ExecutionSimulator| {
+            let mut signal = Signal::buy("BTC-USD-PERP", 1.0);
+            if let Signal::Buy {
+                ref mut limit_price,
+                ..
+            } = signal
+            {
+                *limit_price = Some(100.0);
+            }
+            let order_id = simulator.submit_order(signal, start).unwrap();
+            assert!(simulator.process_tick(&tick_at(101.0, start)).is_empty());
+            assert!(simulator.cancel_order(order_id, start + ms(1)));
+        };
+
+        // Traded through before the cancel lands
+        let mut simulator = ExecutionSimulator::new(latency_config(0.0, 10.0, 0.0));
+        resting_bid(&mut simulator);
+        let fills = simulator.process_tick(&tick_at(99.0, start + ms(5)));
+        assert_eq!(fills[0].price, 100.0);
+
+        // Traded through after it
+        let mut simulator = ExecutionSimulator::new(latency_config(0.0, 10.0, 0.0));
+        resting_bid(&mut simulator);
+        assert!(simulator
+            .process_tick(&tick_at(99.0, start + ms(11)))
+            .is_empty());
+        assert_eq!(simulator.pending_order_count(), 0);
+        assert!(!simulator.cancel_order(1, start + ms(12)));
+    }
+
+    #[test]
+    fn test_square_root_impact_scales_with_size() {
+        let config = ExecutionConfig {
+            fill_model: FillModel::SquareRootImpact,
+            impact_bps: 100.0,
+            ..ExecutionConfig::optimistic()
+        };
+        let mut simulator = ExecutionSimulator::new(config);
+
+        // A quarter of the volume costs half of the full impact
+        simulator
+            .submit_order(Signal::buy("BTC-USD-PERP", 25.0), Utc::now())
+            .unwrap();
+        let fills = simulator.process_candle(&create_test_candle(100.0, 99.0, 101.0));
+        assert!((fills[0].price - 100.5).abs() < 1e-9);
+
+        simulator
+            .submit_order(Signal::sell("BTC-USD-PERP", 1.0), Utc::now())
+            .unwrap();
+        let fills = simulator.process_candle(&create_test_candle(100.0, 99.0, 101.0));
+        assert!((fills[0].price - 99.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_spread_crossing_spares_limit_orders() {
+        let config = ExecutionConfig {
+            fill_model: FillModel::SpreadCrossing,
+            spread_bps: 20.0,
+            ..ExecutionConfig::optimistic()
+        };
+        let mut simulator = ExecutionSimulator::new(config);
+        simulator
+            .submit_order(Signal::buy("BTC-USD-PERP", 1.0), Utc::now())
+            .unwrap();
+
+        let mut signal = Signal::buy("BTC-USD-PERP", 1.0);
+        if let Signal::Buy {
+            ref mut limit_price,
+            ..
+        } = signal
+        {
+            *limit_price = Some(99.5);
+        }
+        simulator.submit_order(signal, Utc::now()).unwrap();
+
+        let fills = simulator.process_candle(&create_test_candle(100.0, 99.0, 101.0));
+        assert!((fills[0].price - 100.1).abs() < 1e-9);
+        assert_eq!(fills[1].price, 99.5);
+    }
+
+    #[test]
+    fn test_participation_cap_spreads_fills() {
+        let config = ExecutionConfig {
+            max_participation: Some(0.1),
+            ..ExecutionConfig::optimistic()
+        };
+        let mut simulator = ExecutionSimulator::new(config);
+        let first = simulator
+            .submit_order(Signal::buy("BTC-USD-PERP", 25.0), Utc::now())
+            .unwrap();
+        let second = simulator
+            .submit_order(Signal::buy("BTC-USD-PERP", 5.0), Utc::now())
+            .unwrap();
+
+        // 10% of 100 per candle, oldest order first
+        let candle = create_test_candle(100.0, 99.0, 101.0);
+        let filled = |fills: Vec<Fill>| -> Vec<(OrderId, f64)> {
+            fills.iter().map(|f| (f.order_id, f.quantity)).collect()
+        };
+        assert_eq!(
+            filled(simulator.process_candle(&candle)),
+            vec![(first, 10.0)]
+        );
+        assert_eq!(
+            filled(simulator.process_candle(&candle)),
+            vec![(first, 10.0)]
+        );
+        assert_eq!(
+            filled(simulator.process_candle(&candle)),
+            vec![(first, 5.0), (second, 5.0)]
+        );
+        assert_eq!(simulator.pending_order_count(), 0);
+    }
+
+    #[test]
+    fn test_participation_cap_ignores_zero_volume() {
+        let config = ExecutionConfig {
+            max_participation: Some(0.1),
+            ..ExecutionConfig::optimistic()
+        };
+        let mut simulator = ExecutionSimulator::new(config);
+        simulator
+            .submit_order(Signal::buy("BTC-USD-PERP", 25.0), Utc::now())
+            .unwrap();
+
+        // Data without volume does not hold the order back
+        let candle = Candle {
+            volume: 0.0.into(),
+            ..create_test_candle(100.0, 99.0, 101.0)
+        };
+        let fills = simulator.process_candle(&candle);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, 25.0);
+        assert_eq!(simulator.pending_order_count(), 0);
+    }
 }
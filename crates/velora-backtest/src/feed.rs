@@ -0,0 +1,229 @@
+//! Merging per-symbol candle series into one time-ordered event stream.
+
+use crate::errors::{BacktestError, BacktestResult};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeMap, HashMap};
+use velora_core::types::{Candle, Interval};
+
+/// Candles of one symbol at a fixed interval
+#[derive(Debug, Clone)]
+pub struct CandleSeries {
+    /// Symbol of the candles
+    pub symbol: String,
+
+    /// Length of one candle
+    pub bar_duration: Duration,
+
+    /// Candles, sorted by open time
+    pub candles: Vec<Candle>,
+}
+
+impl CandleSeries {
+    /// Create a series of candles at a known interval
+    pub fn new(symbol: impl Into<String>, interval: Interval, candles: Vec<Candle>) -> Self {
+        Self::with_duration(symbol, Duration::seconds(interval.to_seconds()), candles)
+    }
+
+    /// Create a series, inferring the interval from the smallest gap between candles
+    ///
+    /// A single candle is treated as closing at its open time.
+    pub fn from_candles(symbol: impl Into<String>, candles: Vec<Candle>) -> Self {
+        let mut series = Self::with_duration(symbol, Duration::zero(), candles);
+        series.bar_duration = series
+            .candles
+            .windows(2)
+            .map(|pair| pair[1].timestamp - pair[0].timestamp)
+            .filter(|gap| *gap > Duration::zero())
+            .min()
+            .unwrap_or_else(Duration::zero);
+        series
+    }
+
+    fn with_duration(
+        symbol: impl Into<String>,
+        bar_duration: Duration,
+        candles: Vec<Candle>,
+    ) -> Self {
+        let mut candles = candles;
+        candles.sort_by_key(|candle| candle.timestamp);
+        Self {
+            symbol: symbol.into(),
+            bar_duration,
+            candles,
+        }
+    }
+
+    /// Instant a candle of this series closes, when its data becomes known
+    pub fn close_time(&self, candle: &Candle) -> DateTime<Utc> {
+        candle.timestamp + self.bar_duration
+    }
+
+    /// Copy of the series with the candles opening in `[start, end)`
+    pub fn window(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self {
+            symbol: self.symbol.clone(),
+            bar_duration: self.bar_duration,
+            candles: self
+                .candles
+                .iter()
+                .filter(|candle| candle.timestamp >= start && candle.timestamp < end)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// Open time of the first candle and close time of the last one across series
+pub fn time_span(series: &[CandleSeries]) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let start = series
+        .iter()
+        .filter_map(|series| series.candles.first())
+        .map(|candle| candle.timestamp)
+        .min()?;
+    let end = series
+        .iter()
+        .filter_map(|series| {
+            series
+                .candles
+                .last()
+                .map(|candle| series.close_time(candle))
+        })
+        .max()?;
+    Some((start, end))
+}
+
+/// Candles closing at the same instant
+#[derive(Debug, Clone)]
+pub struct CandleSlice {
+    /// Close time shared by the candles
+    pub timestamp: DateTime<Utc>,
+
+    /// Candles in the order of their series
+    pub candles: Vec<Candle>,
+}
+
+/// Merge series into slices ordered by close time
+///
+/// Series with different intervals line up on close times, so a strategy
+/// never sees a candle before it has finished. Candles closing at the same
+/// instant share a slice and keep the order of `series`, which makes the
+/// stream deterministic for equal timestamps.
+pub fn merge_series(series: &[CandleSeries]) -> Vec<CandleSlice> {
+    let mut slices: BTreeMap<DateTime<Utc>, Vec<Candle>> = BTreeMap::new();
+    for series in series {
+        for candle in &series.candles {
+            slices
+                .entry(series.close_time(candle))
+                .or_default()
+                .push(candle.clone());
+        }
+    }
+
+    slices
+        .into_iter()
+        .map(|(timestamp, candles)| CandleSlice { timestamp, candles })
+        .collect()
+}
+
+/// Keep the series of `symbols`, in that order (all series when empty)
+pub(crate) fn select_series(
+    symbols: &[String],
+    series: Vec<CandleSeries>,
+) -> BacktestResult<Vec<CandleSeries>> {
+    if symbols.is_empty() {
+        return Ok(series);
+    }
+
+    let mut by_symbol: HashMap<String, CandleSeries> = series
+        .into_iter()
+        .map(|series| (series.symbol.clone(), series))
+        .collect();
+
+    symbols
+        .iter()
+        .map(|symbol| {
+            by_symbol
+                .remove(symbol)
+                .ok_or_else(|| BacktestError::DataError(format!("No candles for {symbol}")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use velora_core::types::Symbol;
+
+    fn candles(symbol: &str, step_minutes: i64, count: i64) -> Vec<Candle> {
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        (0..count)
+            .map(|i| Candle {
+                symbol: Symbol::new(symbol),
+                timestamp: start + Duration::minutes(i * step_minutes),
+                open: 100.0.into(),
+                high: 100.0.into(),
+                low: 100.0.into(),
+                close: 100.0.into(),
+                volume: 1.0.into(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_infers_interval() {
+        let mut shuffled = candles("BTC", 5, 4);
+        shuffled.swap(0, 3);
+
+        let series = CandleSeries::from_candles("BTC", shuffled);
+        assert_eq!(series.bar_duration, Duration::minutes(5));
+        assert!(series
+            .candles
+            .windows(2)
+            .all(|pair| pair[0].timestamp < pair[1].timestamp));
+
+        let single = CandleSeries::from_candles("BTC", candles("BTC", 5, 1));
+        assert_eq!(single.bar_duration, Duration::zero());
+    }
+
+    #[test]
+    fn test_merge_aligns_on_close_time() {
+        let series = vec![
+            CandleSeries::new("ETH", Interval::Minute5, candles("ETH", 5, 2)),
+            CandleSeries::new("BTC", Interval::Minute1, candles("BTC", 1, 10)),
+        ];
+
+        let slices = merge_series(&series);
+        assert_eq!(slices.len(), 10);
+        assert!(slices
+            .windows(2)
+            .all(|pair| pair[0].timestamp < pair[1].timestamp));
+
+        // The 5 minute candle closes together with the fifth 1 minute candle,
+        // and comes first because its series was given first
+        let symbols = |slice: &CandleSlice| -> Vec<String> {
+            slice
+                .candles
+                .iter()
+                .map(|candle| candle.symbol.to_string())
+                .collect()
+        };
+        assert_eq!(symbols(&slices[3]), vec!["BTC"]);
+        assert_eq!(symbols(&slices[4]), vec!["ETH", "BTC"]);
+        assert_eq!(symbols(&slices[9]), vec!["ETH", "BTC"]);
+    }
+
+    #[test]
+    fn test_window_and_time_span() {
+        let series = vec![
+            CandleSeries::new("ETH", Interval::Minute5, candles("ETH", 5, 2)),
+            CandleSeries::new("BTC", Interval::Minute1, candles("BTC", 1, 10)),
+        ];
+        let (start, end) = time_span(&series).unwrap();
+        assert_eq!(end - start, Duration::minutes(10));
+
+        let window = series[1].window(start + Duration::minutes(2), start + Duration::minutes(5));
+        assert_eq!(window.candles.len(), 3);
+        assert_eq!(window.candles[0].timestamp, start + Duration::minutes(2));
+        assert!(time_span(&[]).is_none());
+    }
+}
@@ -9,7 +9,15 @@
 //!
 //! - **Realistic Simulation**: Models commission fees, slippage, and market impact
 //! - **Event-Driven**: Same execution model as live trading
-//! - **Comprehensive Analytics**: Detailed performance metrics (Sharpe, drawdown, win rate, etc.)
+//! - **Multi-Symbol**: Per-symbol series merged into one time-synchronized stream
+//! - **Tick Replay**: Trades, ticks and order books replayed with queue-aware fills
+//! - **Fee Schedules**: Maker/taker classification, rebates, volume tiers and per-symbol rates
+//! - **Perpetuals**: Funding, leverage and liquidation from exchange margin rules
+//! - **Optimization**: Parallel grid, random, successive-halving and TPE-style parameter search
+//! - **Walk-Forward**: Rolling or anchored re-optimization validated out of sample
+//! - **Engine Parity**: Replay history through the live engine and diff it against the backtester
+//! - **Monte Carlo**: Seeded trade resampling and cost perturbation with confidence intervals
+//! - **Comprehensive Analytics**: Interval-aware risk ratios, tail risk, benchmark alpha/beta, MAE/MFE and monthly returns
 //! - **Multiple Fill Models**: Market, realistic, and pessimistic execution
 //! - **Fast Execution**: Process years of data in seconds
 //!
@@ -44,13 +52,35 @@ pub mod backtester;
 pub mod config;
 pub mod errors;
 pub mod execution;
+pub mod feed;
+pub mod optimizer;
+pub mod parity;
 pub mod performance;
 pub mod portfolio;
+pub mod replay;
+pub mod robustness;
+pub mod walkforward;
 
 // Re-exports
 pub use backtester::{BacktestReport, Backtester};
-pub use config::{BacktestConfig, ExecutionConfig, FillModel};
+pub use config::{
+    BacktestConfig, ExecutionConfig, FillModel, IntrabarPolicy, Latency, LatencyConfig,
+    LotMatching, PerpetualConfig,
+};
 pub use errors::{BacktestError, BacktestResult};
-pub use execution::{ExecutionSimulator, Fill, Order, OrderId, OrderStatus};
-pub use performance::PerformanceMetrics;
-pub use portfolio::{CompletedTrade, EquityPoint, Portfolio};
+pub use execution::{Bracket, ExecutionSimulator, Fill, Order, OrderId, OrderStatus};
+pub use feed::{merge_series, time_span, CandleSeries, CandleSlice};
+pub use optimizer::{
+    Objective, OptimizationReport, Optimizer, ParameterRange, ParameterSet, ParameterSpace,
+    SearchMethod, StrategyFactory, Trial,
+};
+pub use parity::{check_parity, Divergence, EngineReplay, ParityReport};
+pub use performance::{BenchmarkMetrics, MonthlyReturn, PerformanceMetrics, RollingMetrics};
+pub use portfolio::{
+    BarRange, CompletedTrade, EquityPoint, FundingPayment, Liquidation, Portfolio,
+};
+pub use replay::{sort_events, BookDelta, ReplayEvent};
+pub use robustness::{Distribution, MonteCarlo, MonteCarloReport, Perturbation, Resampling};
+pub use walkforward::{
+    ParameterStability, WalkForward, WalkForwardReport, WalkForwardWindow, WindowMode,
+};
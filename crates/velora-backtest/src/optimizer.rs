@@ -0,0 +1,1110 @@
+//! Parameter optimization over many backtests.
+//!
+//! An [`Optimizer`] builds a strategy for each candidate set of parameters
+//! (drawn from a [`ParameterSpace`]), backtests them in parallel on worker
+//! threads and ranks the results by an [`Objective`].
+
+use crate::backtester::{BacktestReport, Backtester};
+use crate::config::BacktestConfig;
+use crate::errors::{BacktestError, BacktestResult};
+use crate::feed::{time_span, CandleSeries};
+use crate::performance::PerformanceMetrics;
+use chrono::Duration;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing::warn;
+use velora_strategy::{ParameterInfo, Strategy, StrategyConfig};
+
+/// Candidates drawn per proposal by the TPE-style search
+const TPE_CANDIDATES: usize = 24;
+
+/// Builds a strategy from one set of parameter values
+pub type StrategyFactory =
+    Box<dyn Fn(&ParameterSet) -> BacktestResult<Box<dyn Strategy>> + Send + Sync>;
+
+/// Values of the optimized parameters for one trial
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ParameterSet(BTreeMap<String, Value>);
+
+impl ParameterSet {
+    /// Create an empty parameter set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the value of a parameter
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<Value>) {
+        self.0.insert(name.into(), value.into());
+    }
+
+    /// Get the value of a parameter
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0.get(name)
+    }
+
+    /// Get a numeric parameter
+    pub fn get_f64(&self, name: &str) -> Option<f64> {
+        self.get(name)?.as_f64()
+    }
+
+    /// Get an integer parameter
+    pub fn get_i64(&self, name: &str) -> Option<i64> {
+        self.get(name)?.as_i64()
+    }
+
+    /// Get a boolean parameter
+    pub fn get_bool(&self, name: &str) -> Option<bool> {
+        self.get(name)?.as_bool()
+    }
+
+    /// Iterate over parameters in name order
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Value)> {
+        self.0.iter()
+    }
+
+    /// Copy the values into the custom parameters of a strategy configuration
+    pub fn apply_to(&self, config: StrategyConfig) -> StrategyConfig {
+        self.iter().fold(config, |config, (name, value)| {
+            config.with_parameter(name.clone(), value.clone())
+        })
+    }
+}
+
+/// Values one parameter can take
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ParameterRange {
+    /// Integers from `min` to `max` in increments of `step`
+    Integer {
+        /// Smallest value
+        min: i64,
+        /// Largest value
+        max: i64,
+        /// Increment between values
+        step: i64,
+    },
+
+    /// Reals from `min` to `max`; grid search uses `steps` evenly spaced values
+    Float {
+        /// Smallest value
+        min: f64,
+        /// Largest value
+        max: f64,
+        /// Number of grid values, both bounds included
+        steps: usize,
+    },
+
+    /// One of a fixed list of values
+    Choice(Vec<Value>),
+}
+
+impl ParameterRange {
+    fn validate(&self, name: &str) -> BacktestResult<()> {
+        let valid = match self {
+            ParameterRange::Integer { min, max, step } => min <= max && *step > 0,
+            ParameterRange::Float { min, max, .. } => {
+                min.is_finite() && max.is_finite() && min <= max
+            }
+            ParameterRange::Choice(values) => !values.is_empty(),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(BacktestError::InvalidConfig(format!(
+                "Invalid range for parameter {name}"
+            )))
+        }
+    }
+
+    /// Every value searched by grid search
+    fn grid(&self) -> Vec<Value> {
+        match self {
+            ParameterRange::Integer { min, max, step } => (*min..=*max)
+                .step_by(*step as usize)
+                .map(Value::from)
+                .collect(),
+            ParameterRange::Float { min, max, steps } => {
+                if *steps <= 1 {
+                    return vec![Value::from(*min)];
+                }
+                (0..*steps)
+                    .map(|i| Value::from(min + (max - min) * i as f64 / (*steps - 1) as f64))
+                    .collect()
+            }
+            ParameterRange::Choice(values) => values.clone(),
+        }
+    }
+
+    /// Map a point of the unit interval to a value, uniformly over the range
+    fn at_unit(&self, unit: f64) -> Value {
+        match self {
+            ParameterRange::Integer { min, max, step } => {
+                let count = (max - min) / step;
+                let index = ((unit * (count + 1) as f64).floor() as i64).clamp(0, count);
+                Value::from(min + index * step)
+            }
+            ParameterRange::Float { min, max, .. } => Value::from(min + unit * (max - min)),
+            ParameterRange::Choice(values) => {
+                let index = ((unit * values.len() as f64).floor() as usize).min(values.len() - 1);
+                values[index].clone()
+            }
+        }
+    }
+
+    /// Position of a value in the unit interval, the inverse of `at_unit`
+    fn unit_of(&self, value: &Value) -> f64 {
+        match self {
+            ParameterRange::Integer { min, max, step } => {
+                let count = (max - min) / step;
+                let index = value.as_i64().map_or(0, |value| (value - min) / step);
+                (index.clamp(0, count) as f64 + 0.5) / (count + 1) as f64
+            }
+            ParameterRange::Float { min, max, .. } => {
+                if max > min {
+                    let value = value.as_f64().unwrap_or(*min);
+                    ((value - min) / (max - min)).clamp(0.0, 1.0)
+                } else {
+                    0.5
+                }
+            }
+            ParameterRange::Choice(values) => {
+                let index = values.iter().position(|v| v == value).unwrap_or(0);
+                (index as f64 + 0.5) / values.len() as f64
+            }
+        }
+    }
+}
+
+/// Parameters to optimize and the values each can take
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParameterSpace {
+    parameters: Vec<(String, ParameterRange)>,
+}
+
+impl ParameterSpace {
+    /// Create an empty parameter space
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a space from strategy metadata
+    ///
+    /// Integer parameters (`"integer"` or `"int"`) step by 1, numeric ones
+    /// (`"number"` or `"float"`) get `float_steps` grid values; both need a
+    /// `min` and a `max`. Boolean parameters take both values. Other
+    /// parameters are left out and keep their defaults.
+    pub fn from_metadata(parameters: &[ParameterInfo], float_steps: usize) -> Self {
+        parameters.iter().fold(Self::new(), |space, info| {
+            match (info.param_type.as_str(), info.min, info.max) {
+                ("integer" | "int", Some(min), Some(max)) => {
+                    space.integer(&info.name, min as i64, max as i64, 1)
+                }
+                ("number" | "float", Some(min), Some(max)) => {
+                    space.float(&info.name, min, max, float_steps)
+                }
+                ("boolean" | "bool", _, _) => space.boolean(&info.name),
+                _ => space,
+            }
+        })
+    }
+
+    /// Add a parameter, replacing any previous range with the same name
+    pub fn with_parameter(mut self, name: impl Into<String>, range: ParameterRange) -> Self {
+        let name = name.into();
+        self.parameters.retain(|(existing, _)| *existing != name);
+        self.parameters.push((name, range));
+        self
+    }
+
+    /// Add an integer parameter
+    pub fn integer(self, name: impl Into<String>, min: i64, max: i64, step: i64) -> Self {
+        self.with_parameter(name, ParameterRange::Integer { min, max, step })
+    }
+
+    /// Add a real parameter
+    pub fn float(self, name: impl Into<String>, min: f64, max: f64, steps: usize) -> Self {
+        self.with_parameter(name, ParameterRange::Float { min, max, steps })
+    }
+
+    /// Add a boolean parameter
+    pub fn boolean(self, name: impl Into<String>) -> Self {
+        self.with_parameter(
+            name,
+            ParameterRange::Choice(vec![Value::Bool(false), Value::Bool(true)]),
+        )
+    }
+
+    /// Add a parameter taking one of a list of values
+    pub fn choice(self, name: impl Into<String>, values: Vec<Value>) -> Self {
+        self.with_parameter(name, ParameterRange::Choice(values))
+    }
+
+    /// Parameters and their ranges, in the order they were added
+    pub fn parameters(&self) -> &[(String, ParameterRange)] {
+        &self.parameters
+    }
+
+    /// Every combination of grid values
+    pub fn grid(&self) -> Vec<ParameterSet> {
+        self.parameters
+            .iter()
+            .fold(vec![ParameterSet::new()], |sets, (name, range)| {
+                let values = range.grid();
+                sets.iter()
+                    .flat_map(|set| {
+                        values.iter().map(move |value| {
+                            let mut set = set.clone();
+                            set.insert(name.clone(), value.clone());
+                            set
+                        })
+                    })
+                    .collect()
+            })
+    }
+
+    fn validate(&self) -> BacktestResult<()> {
+        if self.parameters.is_empty() {
+            return Err(BacktestError::InvalidConfig(
+                "Parameter space is empty".to_string(),
+            ));
+        }
+        self.parameters
+            .iter()
+            .try_for_each(|(name, range)| range.validate(name))
+    }
+
+    /// Draw parameters uniformly at random
+    fn sample(&self, rng: &mut StdRng) -> ParameterSet {
+        let unit: Vec<f64> = self.parameters.iter().map(|_| rng.random()).collect();
+        self.at_unit(&unit)
+    }
+
+    fn at_unit(&self, unit: &[f64]) -> ParameterSet {
+        let mut set = ParameterSet::new();
+        for ((name, range), &unit) in self.parameters.iter().zip(unit) {
+            set.insert(name.clone(), range.at_unit(unit));
+        }
+        set
+    }
+
+    fn unit_of(&self, set: &ParameterSet) -> Vec<f64> {
+        self.parameters
+            .iter()
+            .map(|(name, range)| range.unit_of(set.get(name).unwrap_or(&Value::Null)))
+            .collect()
+    }
+}
+
+/// Metric a search maximizes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Objective {
+    /// Total return
+    TotalReturn,
+    /// Annualized return
+    AnnualizedReturn,
+    /// Total profit/loss
+    TotalPnl,
+    /// Sharpe ratio
+    #[default]
+    SharpeRatio,
+    /// Sortino ratio
+    SortinoRatio,
+    /// Maximum drawdown (a smaller drawdown scores higher)
+    MaxDrawdown,
+    /// Profit factor
+    ProfitFactor,
+    /// Win rate
+    WinRate,
+}
+
+impl Objective {
+    /// Score of a backtest, higher being better
+    ///
+    /// Infinite values are clamped to the largest finite ones and NaN
+    /// scores lowest, so scores always order and serialize.
+    pub fn score(&self, metrics: &PerformanceMetrics) -> f64 {
+        let value = match self {
+            Objective::TotalReturn => metrics.total_return,
+            Objective::AnnualizedReturn => metrics.annualized_return,
+            Objective::TotalPnl => metrics.total_pnl,
+            Objective::SharpeRatio => metrics.sharpe_ratio,
+            Objective::SortinoRatio => metrics.sortino_ratio,
+            // Drawdowns are negative percentages
+            Objective::MaxDrawdown => metrics.max_drawdown,
+            Objective::ProfitFactor => metrics.profit_factor,
+            Objective::WinRate => metrics.win_rate,
+        };
+        if value.is_nan() {
+            f64::MIN
+        } else {
+            value.clamp(f64::MIN, f64::MAX)
+        }
+    }
+}
+
+/// How candidate parameters are chosen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum SearchMethod {
+    /// Every combination of grid values
+    #[default]
+    Grid,
+
+    /// Parameters drawn uniformly at random
+    Random {
+        /// Number of backtests
+        trials: usize,
+    },
+
+    /// Random candidates raced on growing slices of the data
+    ///
+    /// All candidates run on the first part of the history; the best
+    /// `1/eta` move on to `eta` times more data, until the survivors run on
+    /// the full history.
+    SuccessiveHalving {
+        /// Number of initial candidates
+        candidates: usize,
+        /// Reduction factor between rounds (at least 2)
+        eta: usize,
+    },
+
+    /// Tree-structured Parzen Estimator style search
+    ///
+    /// After `startup_trials` random trials, each new candidate is the one,
+    /// among draws around the best quarter of past trials, most likely under
+    /// their distribution relative to the other trials'.
+    Tpe {
+        /// Number of backtests
+        trials: usize,
+        /// Random trials before the search is guided
+        startup_trials: usize,
+    },
+}
+
+/// Result of one backtest of an optimization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trial {
+    /// Parameters of the strategy
+    pub parameters: ParameterSet,
+
+    /// Objective score
+    pub score: f64,
+
+    /// Fraction of the history backtested (below 1 for early
+    /// successive-halving rounds)
+    pub budget: f64,
+
+    /// Performance metrics of the backtest (empty if it failed)
+    pub metrics: PerformanceMetrics,
+
+    /// Why the backtest failed; failed trials score lowest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Ranked results of an optimization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizationReport {
+    /// Objective the trials are ranked by
+    pub objective: Objective,
+
+    /// Search that produced the trials
+    pub search: SearchMethod,
+
+    /// Trials, best first; trials on the full history rank before partial ones
+    pub trials: Vec<Trial>,
+}
+
+impl OptimizationReport {
+    /// Best successful trial, on the full history if any succeeded there
+    pub fn best(&self) -> Option<&Trial> {
+        self.trials.iter().find(|trial| trial.error.is_none())
+    }
+
+    /// Export the report to JSON
+    pub fn to_json(&self) -> BacktestResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Export the results table to CSV, one row per trial
+    pub fn to_csv(&self) -> String {
+        let names: BTreeSet<&String> = self
+            .trials
+            .iter()
+            .flat_map(|trial| trial.parameters.iter().map(|(name, _)| name))
+            .collect();
+
+        let mut header = vec!["rank".to_string()];
+        header.extend(names.iter().map(|name| csv_field(name)));
+        header.extend(
+            [
+                "budget",
+                "score",
+                "total_return",
+                "annualized_return",
+                "sharpe_ratio",
+                "max_drawdown",
+                "total_trades",
+                "win_rate",
+                "error",
+            ]
+            .map(String::from),
+        );
+
+        let mut csv = header.join(",") + "\n";
+        for (rank, trial) in self.trials.iter().enumerate() {
+            let mut row = vec![(rank + 1).to_string()];
+            row.extend(names.iter().map(|name| match trial.parameters.get(name) {
+                Some(Value::String(value)) => csv_field(value),
+                Some(value) => csv_field(&value.to_string()),
+                None => String::new(),
+            }));
+            let metrics = &trial.metrics;
+            row.extend([
+                trial.budget.to_string(),
+                trial.score.to_string(),
+                metrics.total_return.to_string(),
+                metrics.annualized_return.to_string(),
+                metrics.sharpe_ratio.to_string(),
+                metrics.max_drawdown.to_string(),
+                metrics.total_trades.to_string(),
+                metrics.win_rate.to_string(),
+                csv_field(trial.error.as_deref().unwrap_or_default()),
+            ]);
+            csv += &(row.join(",") + "\n");
+        }
+        csv
+    }
+
+    /// Save the report as JSON
+    pub fn save_json(&self, path: impl AsRef<Path>) -> BacktestResult<()> {
+        Ok(std::fs::write(path, self.to_json()?)?)
+    }
+
+    /// Save the results table as CSV
+    pub fn save_csv(&self, path: impl AsRef<Path>) -> BacktestResult<()> {
+        Ok(std::fs::write(path, self.to_csv())?)
+    }
+}
+
+/// Quote a CSV field if needed
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Parallel parameter search over backtests
+///
+/// # Example
+///
+/// ```ignore
+/// let space = ParameterSpace::new()
+///     .integer("fast", 5, 20, 5)
+///     .integer("slow", 30, 90, 10);
+///
+/// let report = Optimizer::new(config, space, |params| {
+///     let fast = params.get_i64("fast").unwrap_or(10) as usize;
+///     let slow = params.get_i64("slow").unwrap_or(50) as usize;
+///     Ok(Box::new(SmaCrossover::new(fast, slow)) as Box<dyn Strategy>)
+/// })
+/// .with_objective(Objective::SharpeRatio)
+/// .with_search(SearchMethod::Tpe { trials: 64, startup_trials: 16 })
+/// .run(&series)?;
+///
+/// report.save_csv("optimization.csv")?;
+/// ```
+pub struct Optimizer {
+    config: BacktestConfig,
+    space: ParameterSpace,
+    factory: StrategyFactory,
+    objective: Objective,
+    search: SearchMethod,
+    threads: usize,
+    seed: u64,
+}
+
+impl Optimizer {
+    /// Create an optimizer backtesting the strategies built by `factory`
+    pub fn new<F>(config: BacktestConfig, space: ParameterSpace, factory: F) -> Self
+    where
+        F: Fn(&ParameterSet) -> BacktestResult<Box<dyn Strategy>> + Send + Sync + 'static,
+    {
+        Self {
+            config,
+            space,
+            factory: Box::new(factory),
+            objective: Objective::default(),
+            search: SearchMethod::default(),
+            threads: std::thread::available_parallelism().map_or(1, |threads| threads.get()),
+            seed: 0,
+        }
+    }
+
+    /// Set the metric to maximize
+    pub fn with_objective(mut self, objective: Objective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    /// Set the search method
+    pub fn with_search(mut self, search: SearchMethod) -> Self {
+        self.search = search;
+        self
+    }
+
+    /// Set the number of backtests run at once (defaults to the CPU count)
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Set the seed of the random searches
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Backtest configuration of every trial
+    pub fn config(&self) -> &BacktestConfig {
+        &self.config
+    }
+
+    /// Metric the search maximizes
+    pub fn objective(&self) -> Objective {
+        self.objective
+    }
+
+    /// Run the search on per-symbol candle series
+    ///
+    /// Backtests run on worker threads, each with its own single-threaded
+    /// runtime, and the calling thread blocks until they finish; from async
+    /// code, call this through `tokio::task::spawn_blocking`. A failing
+    /// backtest is recorded as a trial with its error and the search goes
+    /// on; only failures to run backtests at all abort it.
+    pub fn run(&self, series: &[CandleSeries]) -> BacktestResult<OptimizationReport> {
+        self.space.validate()?;
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        let mut trials = match self.search {
+            SearchMethod::Grid => self.evaluate(&self.space.grid(), series, 1.0)?,
+            SearchMethod::Random { trials } => {
+                let candidates: Vec<ParameterSet> =
+                    (0..trials).map(|_| self.space.sample(&mut rng)).collect();
+                self.evaluate(&candidates, series, 1.0)?
+            }
+            SearchMethod::SuccessiveHalving { candidates, eta } => {
+                self.successive_halving(candidates, eta, series, &mut rng)?
+            }
+            SearchMethod::Tpe {
+                trials,
+                startup_trials,
+            } => self.tpe(trials, startup_trials, series, &mut rng)?,
+        };
+
+        trials.sort_by(|a, b| {
+            b.budget
+                .total_cmp(&a.budget)
+                .then(b.score.total_cmp(&a.score))
+                .then(a.error.is_some().cmp(&b.error.is_some()))
+        });
+
+        Ok(OptimizationReport {
+            objective: self.objective,
+            search: self.search,
+            trials,
+        })
+    }
+
+    fn successive_halving(
+        &self,
+        candidates: usize,
+        eta: usize,
+        series: &[CandleSeries],
+        rng: &mut StdRng,
+    ) -> BacktestResult<Vec<Trial>> {
+        let eta = eta.max(2);
+        let mut rounds: i32 = 0;
+        let mut reach = 1;
+        while reach < candidates {
+            reach *= eta;
+            rounds += 1;
+        }
+
+        let mut survivors: Vec<ParameterSet> =
+            (0..candidates).map(|_| self.space.sample(rng)).collect();
+        let mut trials = Vec::new();
+        for round in 0..=rounds {
+            let budget = (eta as f64).powi(round - rounds);
+            let mut results = self.evaluate(&survivors, series, budget)?;
+            results.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+            let keep = results.len().div_ceil(eta);
+            survivors = results
+                .iter()
+                .take(keep)
+                .map(|trial| trial.parameters.clone())
+                .collect();
+            trials.extend(results);
+        }
+        Ok(trials)
+    }
+
+    fn tpe(
+        &self,
+        trials: usize,
+        startup_trials: usize,
+        series: &[CandleSeries],
+        rng: &mut StdRng,
+    ) -> BacktestResult<Vec<Trial>> {
+        let startup = startup_trials.max(1).min(trials);
+        let candidates: Vec<ParameterSet> = (0..startup).map(|_| self.space.sample(rng)).collect();
+        let mut done = self.evaluate(&candidates, series, 1.0)?;
+
+        // Propose a batch per round to keep every thread busy
+        while done.len() < trials {
+            let batch = self.threads.min(trials - done.len());
+            let candidates: Vec<ParameterSet> =
+                (0..batch).map(|_| self.propose(&done, rng)).collect();
+            done.extend(self.evaluate(&candidates, series, 1.0)?);
+        }
+        Ok(done)
+    }
+
+    /// Propose the next parameters from past trials
+    ///
+    /// Trials are split into the best quarter and the rest, each modelled
+    /// per parameter by Gaussian kernels on the unit interval. Of the
+    /// candidates drawn from the good model, the one with the highest
+    /// good-to-bad density ratio is returned.
+    fn propose(&self, trials: &[Trial], rng: &mut StdRng) -> ParameterSet {
+        let mut ranked: Vec<&Trial> = trials.iter().collect();
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+        let points: Vec<Vec<f64>> = ranked
+            .iter()
+            .map(|trial| self.space.unit_of(&trial.parameters))
+            .collect();
+        let (good, bad) = points.split_at(points.len().div_ceil(4));
+
+        let dimensions = self.space.parameters.len();
+        let (best, _) = (0..TPE_CANDIDATES)
+            .map(|_| {
+                let candidate: Vec<f64> = (0..dimensions)
+                    .map(|dimension| parzen_sample(good, dimension, rng))
+                    .collect();
+                let ratio: f64 = candidate
+                    .iter()
+                    .enumerate()
+                    .map(|(dimension, &x)| {
+                        parzen_log_density(good, dimension, x)
+                            - parzen_log_density(bad, dimension, x)
+                    })
+                    .sum();
+                (candidate, ratio)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or_default();
+        self.space.at_unit(&best)
+    }
+
+    /// Backtest candidates on the first `budget` fraction of the history
+    ///
+    /// Trials come back in the order of the candidates.
+    fn evaluate(
+        &self,
+        candidates: &[ParameterSet],
+        series: &[CandleSeries],
+        budget: f64,
+    ) -> BacktestResult<Vec<Trial>> {
+        let series = truncate(series, budget);
+        let jobs: Vec<(&ParameterSet, &[CandleSeries])> = candidates
+            .iter()
+            .map(|parameters| (parameters, series.as_slice()))
+            .collect();
+
+        let results = self.backtest_all(&jobs)?;
+        Ok(candidates
+            .iter()
+            .zip(results)
+            .map(|(parameters, result)| match result {
+                Ok(report) => Trial {
+                    parameters: parameters.clone(),
+                    score: self.objective.score(&report.metrics),
+                    budget,
+                    metrics: report.metrics,
+                    error: None,
+                },
+                Err(e) => {
+                    warn!("Trial with parameters {parameters:?} failed: {e}");
+                    Trial {
+                        parameters: parameters.clone(),
+                        score: f64::MIN,
+                        budget,
+                        metrics: PerformanceMetrics::default(),
+                        error: Some(e.to_string()),
+                    }
+                }
+            })
+            .collect())
+    }
+
+    /// Backtest each set of parameters on its data, in parallel
+    ///
+    /// Results come back in the order of the jobs. The outer error means
+    /// the backtests could not be run; the inner ones are per backtest.
+    pub(crate) fn backtest_all(
+        &self,
+        jobs: &[(&ParameterSet, &[CandleSeries])],
+    ) -> BacktestResult<Vec<BacktestResult<BacktestReport>>> {
+        let next = AtomicUsize::new(0);
+        let mut slots: Vec<Option<BacktestResult<BacktestReport>>> =
+            jobs.iter().map(|_| None).collect();
+
+        std::thread::scope(|scope| -> BacktestResult<()> {
+            let workers: Vec<_> = (0..self.threads.min(jobs.len()))
+                .map(|_| scope.spawn(|| self.work(jobs, &next)))
+                .collect();
+            for worker in workers {
+                match worker.join() {
+                    Ok(results) => {
+                        for (index, result) in results? {
+                            slots[index] = Some(result);
+                        }
+                    }
+                    Err(panic) => std::panic::resume_unwind(panic),
+                }
+            }
+            Ok(())
+        })?;
+
+        slots
+            .into_iter()
+            .map(|slot| slot.ok_or_else(|| BacktestError::Internal("Trial was not run".into())))
+            .collect()
+    }
+
+    /// Worker loop: run backtests until no job is left
+    fn work(
+        &self,
+        jobs: &[(&ParameterSet, &[CandleSeries])],
+        next: &AtomicUsize,
+    ) -> BacktestResult<Vec<(usize, BacktestResult<BacktestReport>)>> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .map_err(|e| BacktestError::Internal(format!("Failed to start runtime: {e}")))?;
+        let mut results = Vec::new();
+        loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some(&(parameters, series)) = jobs.get(index) else {
+                return Ok(results);
+            };
+            let result = runtime.block_on(self.backtest(&self.config, parameters, series));
+            results.push((index, result));
+        }
+    }
+
+    /// Backtest one set of parameters with a different configuration
+    ///
+    /// Blocks the calling thread, like [`Optimizer::run`].
+    pub(crate) fn backtest_with(
+        &self,
+        config: &BacktestConfig,
+        parameters: &ParameterSet,
+        series: &[CandleSeries],
+    ) -> BacktestResult<BacktestReport> {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .map_err(|e| BacktestError::Internal(format!("Failed to start runtime: {e}")))?
+            .block_on(self.backtest(config, parameters, series))
+    }
+
+    async fn backtest(
+        &self,
+        config: &BacktestConfig,
+        parameters: &ParameterSet,
+        series: &[CandleSeries],
+    ) -> BacktestResult<BacktestReport> {
+        let strategy = (self.factory)(parameters)?;
+        Backtester::new(config.clone())
+            .with_strategy(strategy)
+            .run_series(series.to_vec())
+            .await
+    }
+}
+
+/// Keep the first `budget` fraction of the history's time span
+fn truncate(series: &[CandleSeries], budget: f64) -> Vec<CandleSeries> {
+    let Some((start, end)) = time_span(series).filter(|_| budget < 1.0) else {
+        return series.to_vec();
+    };
+    let span = (end - start).num_milliseconds() as f64 * budget;
+    let cutoff = start + Duration::milliseconds((span.ceil() as i64).max(1));
+    series
+        .iter()
+        .map(|series| series.window(start, cutoff))
+        .collect()
+}
+
+/// Kernel width for `count` points on the unit interval
+fn bandwidth(count: usize) -> f64 {
+    (1.0 / ((count + 1) as f64).sqrt()).clamp(0.05, 0.5)
+}
+
+/// Draw from the kernel density of `points` mixed with a uniform prior
+fn parzen_sample(points: &[Vec<f64>], dimension: usize, rng: &mut StdRng) -> f64 {
+    let pick = rng.random_range(0..=points.len());
+    match points.get(pick) {
+        Some(point) => {
+            (point[dimension] + bandwidth(points.len()) * standard_normal(rng)).clamp(0.0, 1.0)
+        }
+        None => rng.random(),
+    }
+}
+
+/// Log density of the kernel estimate of `points` mixed with a uniform prior
+fn parzen_log_density(points: &[Vec<f64>], dimension: usize, x: f64) -> f64 {
+    let h = bandwidth(points.len());
+    let kernels: f64 = points
+        .iter()
+        .map(|point| {
+            let z = (x - point[dimension]) / h;
+            (-0.5 * z * z).exp() / (h * (2.0 * std::f64::consts::PI).sqrt())
+        })
+        .sum();
+    ((1.0 + kernels) / (points.len() + 1) as f64).ln()
+}
+
+/// Standard normal draw (Box-Muller)
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1: f64 = rng.random::<f64>().max(f64::MIN_POSITIVE);
+    let u2: f64 = rng.random();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use velora_core::types::{Candle, Interval, Symbol};
+    use velora_strategy::{Signal, StrategyContext, StrategyState};
+
+    /// Buys `quantity` on the first candle and closes on the fourth
+    struct HoldStrategy {
+        config: StrategyConfig,
+        quantity: f64,
+        candles_seen: usize,
+    }
+
+    #[async_trait]
+    impl Strategy for HoldStrategy {
+        fn name(&self) -> &str {
+            "Hold"
+        }
+        fn config(&self) -> &StrategyConfig {
+            &self.config
+        }
+        fn state(&self) -> StrategyState {
+            StrategyState::Running
+        }
+
+        async fn on_candle(
+            &mut self,
+            _candle: &Candle,
+            _ctx: &StrategyContext,
+        ) -> velora_strategy::StrategyResult<Signal> {
+            self.candles_seen += 1;
+            Ok(match self.candles_seen {
+                1 => Signal::buy("BTC", self.quantity),
+                4 => Signal::close("BTC"),
+                _ => Signal::Hold,
+            })
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    fn rising_series() -> Vec<CandleSeries> {
+        let start = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let candles = (0..8)
+            .map(|i| {
+                let price = 100.0 + i as f64;
+                Candle {
+                    symbol: Symbol::new("BTC"),
+                    timestamp: start + Duration::minutes(i),
+                    open: price.into(),
+                    high: price.into(),
+                    low: price.into(),
+                    close: price.into(),
+                    volume: 100.0.into(),
+                }
+            })
+            .collect();
+        vec![CandleSeries::new("BTC", Interval::Minute1, candles)]
+    }
+
+    fn optimizer(space: ParameterSpace) -> Optimizer {
+        let config =
+            BacktestConfig::new().with_execution(crate::config::ExecutionConfig::optimistic());
+        Optimizer::new(config, space, |params| {
+            let strategy = HoldStrategy {
+                config: params.apply_to(StrategyConfig::new("Hold")),
+                quantity: params.get_f64("quantity").unwrap_or(1.0),
+                candles_seen: 0,
+            };
+            Ok(Box::new(strategy) as Box<dyn Strategy>)
+        })
+        .with_objective(Objective::TotalPnl)
+        .with_threads(2)
+    }
+
+    #[test]
+    fn test_grid_covers_space() {
+        let space = ParameterSpace::new()
+            .integer("fast", 2, 6, 2)
+            .boolean("filter")
+            .float("band", 1.0, 2.0, 3);
+        let grid = space.grid();
+        assert_eq!(grid.len(), 18);
+        assert_eq!(grid[0].get_i64("fast"), Some(2));
+        assert_eq!(grid[17].get_f64("band"), Some(2.0));
+        assert_eq!(grid[17].get_bool("filter"), Some(true));
+    }
+
+    #[test]
+    fn test_unit_mapping_round_trips() {
+        let range = ParameterRange::Integer {
+            min: 10,
+            max: 50,
+            step: 10,
+        };
+        for value in [10, 30, 50] {
+            let unit = range.unit_of(&Value::from(value));
+            assert_eq!(range.at_unit(unit), Value::from(value));
+        }
+        assert_eq!(range.at_unit(1.0), Value::from(50));
+
+        let choice = ParameterRange::Choice(vec![Value::from("ema"), Value::from("sma")]);
+        assert_eq!(choice.at_unit(choice.unit_of(&Value::from("sma"))), "sma");
+    }
+
+    #[test]
+    fn test_grid_search_ranks_by_objective() {
+        let space = ParameterSpace::new().float("quantity", 1.0, 3.0, 3);
+        let report = optimizer(space).run(&rising_series()).unwrap();
+
+        assert_eq!(report.trials.len(), 3);
+        let best = report.best().unwrap();
+        assert_eq!(best.parameters.get_f64("quantity"), Some(3.0));
+        assert!(report
+            .trials
+            .windows(2)
+            .all(|pair| pair[0].score >= pair[1].score));
+
+        let csv = report.to_csv();
+        assert!(csv.starts_with("rank,quantity,budget,score"));
+        assert_eq!(csv.lines().count(), 4);
+    }
+
+    #[test]
+    fn test_random_searches_are_seeded() {
+        let space = ParameterSpace::new().integer("quantity", 1, 10, 1);
+        let run = |search| {
+            optimizer(space.clone())
+                .with_search(search)
+                .with_seed(7)
+                .run(&rising_series())
+                .unwrap()
+        };
+
+        for search in [
+            SearchMethod::Random { trials: 6 },
+            SearchMethod::Tpe {
+                trials: 8,
+                startup_trials: 4,
+            },
+        ] {
+            let first = run(search);
+            let second = run(search);
+            assert_eq!(first.trials.len(), second.trials.len());
+            assert!(first
+                .trials
+                .iter()
+                .zip(&second.trials)
+                .all(|(a, b)| a.parameters == b.parameters));
+        }
+    }
+
+    #[test]
+    fn test_successive_halving_grows_budget() {
+        let space = ParameterSpace::new().integer("quantity", 1, 10, 1);
+        let report = optimizer(space)
+            .with_search(SearchMethod::SuccessiveHalving {
+                candidates: 9,
+                eta: 3,
+            })
+            .run(&rising_series())
+            .unwrap();
+
+        // 9 candidates on a ninth of the data, 3 on a third, 1 on all of it
+        assert_eq!(report.trials.len(), 13);
+        assert_eq!(report.best().unwrap().budget, 1.0);
+        assert_eq!(
+            report
+                .trials
+                .iter()
+                .filter(|trial| trial.budget < 0.2)
+                .count(),
+            9
+        );
+    }
+
+    #[test]
+    fn test_failed_trials_are_recorded() {
+        let space = ParameterSpace::new().float("quantity", 1.0, 3.0, 3);
+        let base = optimizer(space.clone());
+        let optimizer = Optimizer::new(base.config().clone(), space, |params| {
+            let quantity = params.get_f64("quantity").unwrap_or(1.0);
+            if quantity > 2.5 {
+                return Err(BacktestError::InvalidConfig("Quantity too large".into()));
+            }
+            Ok(Box::new(HoldStrategy {
+                config: params.apply_to(StrategyConfig::new("Hold")),
+                quantity,
+                candles_seen: 0,
+            }) as Box<dyn Strategy>)
+        })
+        .with_objective(Objective::TotalPnl)
+        .with_threads(2);
+        let report = optimizer.run(&rising_series()).unwrap();
+
+        assert_eq!(report.trials.len(), 3);
+        let failed = report.trials.last().unwrap();
+        assert_eq!(failed.parameters.get_f64("quantity"), Some(3.0));
+        assert_eq!(failed.score, f64::MIN);
+        assert!(failed
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Quantity too large"));
+
+        let best = report.best().unwrap();
+        assert_eq!(best.parameters.get_f64("quantity"), Some(2.0));
+        assert!(best.error.is_none());
+
+        let csv = report.to_csv();
+        assert!(csv.lines().next().unwrap().ends_with(",error"));
+        assert!(csv.lines().last().unwrap().ends_with("Quantity too large"));
+    }
+
+    #[test]
+    fn test_empty_space_is_rejected() {
+        let result = optimizer(ParameterSpace::new()).run(&rising_series());
+        assert!(matches!(result, Err(BacktestError::InvalidConfig(_))));
+    }
+}
@@ -0,0 +1,602 @@
+//! Backtest/live parity: replaying history through the live trading engine.
+//!
+//! The [`Backtester`] and `velora_engine::TradingEngine` run a strategy
+//! through separate order, execution and position code. [`EngineReplay`]
+//! drives the real engine from stored candles and reports its run like a
+//! backtest, and [`ParityReport`] lists where the two disagree.
+
+use crate::backtester::{BacktestReport, Backtester};
+use crate::config::BacktestConfig;
+use crate::errors::{BacktestError, BacktestResult};
+use crate::feed::{merge_series, select_series, time_span, CandleSeries};
+use crate::performance::calculate_metrics;
+use crate::portfolio::{CompletedTrade, Portfolio};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use velora_core::types::Interval;
+use velora_core::ReplayClock;
+use velora_engine::{EngineConfig, ExecutionMode, MarketEvent, TradingEngine};
+use velora_risk::{PositionSizer, RiskManager};
+use velora_strategy::Strategy;
+
+/// Candle intervals the engine can run on
+const INTERVALS: [Interval; 9] = [
+    Interval::Second1,
+    Interval::Minute1,
+    Interval::Minute5,
+    Interval::Minute15,
+    Interval::Minute30,
+    Interval::Hour1,
+    Interval::Hour4,
+    Interval::Day1,
+    Interval::Week1,
+];
+
+/// Runs a strategy through the live trading engine on stored candles
+///
+/// The engine runs in dry-run mode, its simulated execution standing in for
+/// the exchange, on a [`ReplayClock`] so orders and fills carry historical
+/// time. Every candle is queued up front and fed through
+/// `TradingEngine::start_with_receiver` in close-time order (see
+/// [`merge_series`]), so the replay runs as fast as the engine processes it.
+///
+/// The engine's fills are booked into a [`Portfolio`] with the configured
+/// lot matching and marked at every candle close, giving a report in the
+/// [`Backtester`]'s format. The engine has no funding or liquidation, so
+/// those stay empty. All series must share one interval, as the engine
+/// trades a single candle interval.
+pub struct EngineReplay {
+    config: BacktestConfig,
+    strategy: Option<Box<dyn Strategy>>,
+    risk_manager: Option<RiskManager>,
+    position_sizer: Option<Box<dyn PositionSizer>>,
+}
+
+impl EngineReplay {
+    /// Create an engine replay
+    ///
+    /// The engine gets the capital, symbols and fee schedule of `config`.
+    pub fn new(config: BacktestConfig) -> Self {
+        Self {
+            config,
+            strategy: None,
+            risk_manager: None,
+            position_sizer: None,
+        }
+    }
+
+    /// Add the strategy to replay
+    pub fn with_strategy(mut self, strategy: Box<dyn Strategy>) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    /// Add a risk manager, enabling the engine's pre-trade risk checks
+    pub fn with_risk_manager(mut self, risk_manager: RiskManager) -> Self {
+        self.risk_manager = Some(risk_manager);
+        self
+    }
+
+    /// Add a position sizer for unsized Buy/Sell signals
+    pub fn with_position_sizer(mut self, sizer: Box<dyn PositionSizer>) -> Self {
+        self.position_sizer = Some(sizer);
+        self
+    }
+
+    /// Replay per-symbol candle series through the engine
+    pub async fn run_series(self, series: Vec<CandleSeries>) -> BacktestResult<BacktestReport> {
+        let Self {
+            config,
+            strategy,
+            risk_manager,
+            position_sizer,
+        } = self;
+        let strategy = strategy
+            .ok_or_else(|| BacktestError::InvalidConfig("No strategy provided".to_string()))?;
+
+        let series = select_series(&config.symbols, series)?;
+        let (start, _) = time_span(&series)
+            .ok_or_else(|| BacktestError::DataError("No candles provided".to_string()))?;
+
+        let engine_config = EngineConfig::builder()
+            .mode(ExecutionMode::DryRun)
+            .symbols(series.iter().map(|series| series.symbol.clone()).collect())
+            .candle_interval(engine_interval(&series)?)
+            .initial_capital(config.initial_capital)
+            .enable_risk_checks(risk_manager.is_some())
+            .build();
+        let mut engine = TradingEngine::new(engine_config)
+            .with_strategy(strategy)
+            .with_fee_model(config.execution.fee_model())
+            .with_clock(Arc::new(ReplayClock::new(start)));
+        if let Some(risk_manager) = risk_manager {
+            engine = engine.with_risk_manager(risk_manager);
+        }
+        if let Some(sizer) = position_sizer {
+            engine = engine.with_position_sizer(sizer);
+        }
+
+        let slices = merge_series(&series);
+        let (market_tx, market_rx) = mpsc::unbounded_channel();
+        for candle in slices.iter().flat_map(|slice| &slice.candles) {
+            // The receiver is alive until the engine starts
+            let _ = market_tx.send(MarketEvent::Candle(candle.clone()));
+        }
+        drop(market_tx);
+
+        println!(
+            "Replaying {} candle closes through the trading engine...",
+            slices.len()
+        );
+        engine.start_with_receiver(market_rx).await?;
+
+        // Book the engine's fills at the candle close they happened by
+        let mut portfolio = Portfolio::new(config.initial_capital)
+            .with_lot_matching(config.lot_matching)
+            .with_perpetuals(config.perpetuals.clone());
+        let mut fills = engine.fills().iter().peekable();
+        for slice in &slices {
+            for candle in &slice.candles {
+                portfolio.mark_range(
+                    candle.symbol.as_str(),
+                    candle.low.into_inner(),
+                    candle.high.into_inner(),
+                );
+            }
+            while let Some(fill) = fills.next_if(|fill| fill.timestamp <= slice.timestamp) {
+                portfolio.apply_fill(
+                    &fill.symbol,
+                    fill.side,
+                    fill.quantity,
+                    fill.price,
+                    fill.commission,
+                    fill.timestamp,
+                );
+            }
+            for candle in &slice.candles {
+                portfolio.update_price(candle.symbol.to_string(), candle.close.into_inner());
+            }
+            portfolio.record_snapshot(slice.timestamp);
+        }
+
+        println!("Engine replay complete!");
+        println!("Completed {} trades", portfolio.trades().len());
+
+        let metrics = calculate_metrics(
+            portfolio.equity_curve(),
+            portfolio.trades(),
+            0.0,
+            config.initial_capital,
+            config.rolling_window,
+        );
+        Ok(BacktestReport {
+            config,
+            metrics,
+            equity_curve: portfolio.equity_curve().to_vec(),
+            trades: portfolio.trades().to_vec(),
+            funding_payments: Vec::new(),
+            liquidations: Vec::new(),
+        })
+    }
+}
+
+/// Engine candle interval of the series
+fn engine_interval(series: &[CandleSeries]) -> BacktestResult<Interval> {
+    let mut durations = series.iter().map(|series| series.bar_duration);
+    let duration = durations
+        .next()
+        .ok_or_else(|| BacktestError::DataError("No candles provided".to_string()))?;
+    if durations.any(|other| other != duration) {
+        return Err(BacktestError::InvalidConfig(
+            "The trading engine needs all series on one interval".to_string(),
+        ));
+    }
+
+    INTERVALS
+        .into_iter()
+        .find(|interval| interval.to_seconds() == duration.num_seconds())
+        .ok_or_else(|| {
+            BacktestError::InvalidConfig(format!(
+                "No engine candle interval of {}s",
+                duration.num_seconds()
+            ))
+        })
+}
+
+/// One difference between a backtest and an engine replay
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Divergence {
+    /// What differs, such as `trades[2].exit_price`, `equity` or `total_return`
+    pub field: String,
+
+    /// Time the difference shows up at, if it is tied to one
+    pub timestamp: Option<DateTime<Utc>>,
+
+    /// Value in the backtest
+    pub backtest: String,
+
+    /// Value in the engine replay
+    pub engine: String,
+}
+
+/// Divergences between a backtest and an engine replay of the same data
+///
+/// Trades are matched in order and compared field by field; the equity
+/// curves are compared at their common timestamps, reporting the first point
+/// that differs; and a few summary metrics are compared last. Numbers match
+/// when they are within a relative tolerance of each other.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParityReport {
+    /// Differences found, trades first
+    pub divergences: Vec<Divergence>,
+
+    /// Number of trades both runs made
+    pub matched_trades: usize,
+
+    /// Largest equity difference at a common timestamp
+    pub max_equity_gap: f64,
+}
+
+impl ParityReport {
+    /// Compare a backtest with an engine replay
+    ///
+    /// `tolerance` is relative: numbers match when they differ by at most
+    /// `tolerance` times the larger of their magnitudes (or of 1).
+    pub fn compare(backtest: &BacktestReport, engine: &BacktestReport, tolerance: f64) -> Self {
+        let mut report = Self {
+            matched_trades: backtest.trades.len().min(engine.trades.len()),
+            ..Self::default()
+        };
+
+        if backtest.trades.len() != engine.trades.len() {
+            report.push(
+                "trades.len()",
+                None,
+                backtest.trades.len(),
+                engine.trades.len(),
+            );
+        }
+        for (index, (expected, actual)) in backtest.trades.iter().zip(&engine.trades).enumerate() {
+            report.compare_trade(index, expected, actual, tolerance);
+        }
+
+        let engine_equity: HashMap<DateTime<Utc>, f64> = engine
+            .equity_curve
+            .iter()
+            .map(|point| (point.timestamp, point.equity))
+            .collect();
+        let mut first_gap = None;
+        for point in &backtest.equity_curve {
+            let Some(&equity) = engine_equity.get(&point.timestamp) else {
+                continue;
+            };
+            report.max_equity_gap = report.max_equity_gap.max((point.equity - equity).abs());
+            if first_gap.is_none() && !within(point.equity, equity, tolerance) {
+                first_gap = Some((point.timestamp, point.equity, equity));
+            }
+        }
+        if let Some((timestamp, expected, actual)) = first_gap {
+            report.push("equity", Some(timestamp), expected, actual);
+        }
+
+        let (expected, actual) = (&backtest.metrics, &engine.metrics);
+        for (field, expected, actual) in [
+            ("total_return", expected.total_return, actual.total_return),
+            ("max_drawdown", expected.max_drawdown, actual.max_drawdown),
+            ("sharpe_ratio", expected.sharpe_ratio, actual.sharpe_ratio),
+        ] {
+            if !within(expected, actual, tolerance) {
+                report.push(field, None, expected, actual);
+            }
+        }
+
+        report
+    }
+
+    /// Whether no divergence was found
+    pub fn is_consistent(&self) -> bool {
+        self.divergences.is_empty()
+    }
+
+    /// Print the divergences
+    pub fn print_summary(&self) {
+        println!("\n=== Backtest/Engine Parity ===");
+        println!("Matched trades:   {}", self.matched_trades);
+        println!("Max equity gap:   {:.2}", self.max_equity_gap);
+
+        if self.is_consistent() {
+            println!("No divergences");
+            return;
+        }
+
+        println!("Divergences:      {}", self.divergences.len());
+        println!(
+            "\n{:<28} {:<22} {:>18} {:>18}",
+            "Field", "Time", "Backtest", "Engine"
+        );
+        for divergence in &self.divergences {
+            let time = divergence
+                .timestamp
+                .map(|timestamp| timestamp.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default();
+            println!(
+                "{:<28} {:<22} {:>18} {:>18}",
+                divergence.field, time, divergence.backtest, divergence.engine
+            );
+        }
+    }
+
+    /// Compare the fields of two trades at the same position
+    fn compare_trade(
+        &mut self,
+        index: usize,
+        expected: &CompletedTrade,
+        actual: &CompletedTrade,
+        tolerance: f64,
+    ) {
+        let field = |name: &str| format!("trades[{index}].{name}");
+        let timestamp = Some(expected.entry_time.min(actual.entry_time));
+
+        if expected.symbol != actual.symbol {
+            self.push(
+                &field("symbol"),
+                timestamp,
+                &expected.symbol,
+                &actual.symbol,
+            );
+        }
+        if expected.side != actual.side {
+            self.push(
+                &field("side"),
+                timestamp,
+                format!("{:?}", expected.side),
+                format!("{:?}", actual.side),
+            );
+        }
+        for (name, expected, actual) in [
+            ("entry_time", expected.entry_time, actual.entry_time),
+            ("exit_time", expected.exit_time, actual.exit_time),
+        ] {
+            if expected != actual {
+                self.push(&field(name), timestamp, expected, actual);
+            }
+        }
+        for (name, expected, actual) in [
+            ("quantity", expected.quantity, actual.quantity),
+            ("entry_price", expected.entry_price, actual.entry_price),
+            ("exit_price", expected.exit_price, actual.exit_price),
+            ("commission", expected.commission, actual.commission),
+            ("pnl", expected.pnl, actual.pnl),
+        ] {
+            if !within(expected, actual, tolerance) {
+                self.push(&field(name), timestamp, expected, actual);
+            }
+        }
+    }
+
+    fn push(
+        &mut self,
+        field: &str,
+        timestamp: Option<DateTime<Utc>>,
+        backtest: impl ToString,
+        engine: impl ToString,
+    ) {
+        self.divergences.push(Divergence {
+            field: field.to_string(),
+            timestamp,
+            backtest: backtest.to_string(),
+            engine: engine.to_string(),
+        });
+    }
+}
+
+/// Run a strategy through the backtester and the engine and compare them
+///
+/// `strategy` builds a fresh instance for each run. Use [`Backtester`] and
+/// [`EngineReplay`] directly to attach a risk manager or position sizer to
+/// both runs.
+pub async fn check_parity<F>(
+    config: BacktestConfig,
+    series: Vec<CandleSeries>,
+    strategy: F,
+    tolerance: f64,
+) -> BacktestResult<ParityReport>
+where
+    F: Fn() -> Box<dyn Strategy>,
+{
+    let backtest = Backtester::new(config.clone())
+        .with_strategy(strategy())
+        .run_series(series.clone())
+        .await?;
+    let engine = EngineReplay::new(config)
+        .with_strategy(strategy())
+        .run_series(series)
+        .await?;
+
+    Ok(ParityReport::compare(&backtest, &engine, tolerance))
+}
+
+/// Whether two numbers match within a relative tolerance
+fn within(a: f64, b: f64, tolerance: f64) -> bool {
+    (a - b).abs() <= tolerance * a.abs().max(b.abs()).max(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::{Duration, TimeZone};
+    use velora_core::types::{Candle, Symbol};
+    use velora_strategy::{Signal, StrategyConfig, StrategyContext, StrategyResult, StrategyState};
+
+    /// Emits a fixed signal per candle index, then holds
+    struct ScriptedStrategy {
+        config: StrategyConfig,
+        signals: Vec<Signal>,
+        candle: usize,
+    }
+
+    #[async_trait]
+    impl Strategy for ScriptedStrategy {
+        fn name(&self) -> &str {
+            "Scripted"
+        }
+
+        fn config(&self) -> &StrategyConfig {
+            &self.config
+        }
+
+        fn state(&self) -> StrategyState {
+            StrategyState::Running
+        }
+
+        async fn on_candle(
+            &mut self,
+            _candle: &Candle,
+            _ctx: &StrategyContext,
+        ) -> StrategyResult<Signal> {
+            let signal = self.signals.get(self.candle).cloned();
+            self.candle += 1;
+            Ok(signal.unwrap_or(Signal::Hold))
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    fn scripted(signals: Vec<Signal>) -> Box<dyn Strategy> {
+        Box::new(ScriptedStrategy {
+            config: StrategyConfig::new("Scripted"),
+            signals,
+            candle: 0,
+        })
+    }
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    /// One-minute candles with the given (open, close) prices
+    fn series(prices: &[(f64, f64)]) -> Vec<CandleSeries> {
+        let candles = prices
+            .iter()
+            .enumerate()
+            .map(|(i, &(open, close))| Candle {
+                symbol: Symbol::new("BTC"),
+                open: open.into(),
+                high: open.max(close).into(),
+                low: open.min(close).into(),
+                close: close.into(),
+                volume: 1_000.0.into(),
+                timestamp: start() + Duration::minutes(i as i64),
+            })
+            .collect();
+        vec![CandleSeries::new("BTC", Interval::Minute1, candles)]
+    }
+
+    fn config() -> BacktestConfig {
+        BacktestConfig::new()
+            .with_capital(10_000.0)
+            .with_symbols(vec!["BTC".to_string()])
+    }
+
+    #[tokio::test]
+    async fn test_engine_replay_report() {
+        let strategy = scripted(vec![
+            Signal::buy("BTC", 1.0),
+            Signal::Hold,
+            Signal::close("BTC"),
+        ]);
+        let report = EngineReplay::new(config())
+            .with_strategy(strategy)
+            .run_series(series(&[
+                (100.0, 100.0),
+                (100.0, 104.0),
+                (104.0, 110.0),
+                (110.0, 110.0),
+            ]))
+            .await
+            .unwrap();
+
+        // Orders fill in the engine at the close they were placed on
+        assert_eq!(report.trades.len(), 1);
+        let trade = &report.trades[0];
+        assert_eq!(trade.entry_time, start() + Duration::minutes(1));
+        assert_eq!(trade.exit_time, start() + Duration::minutes(3));
+        assert_eq!((trade.entry_price, trade.exit_price), (100.0, 110.0));
+
+        let commission = (100.0 + 110.0) * 0.001;
+        assert!((trade.pnl - (10.0 - commission)).abs() < 1e-9);
+        assert_eq!(report.equity_curve.len(), 4);
+        assert_eq!(
+            report.equity_curve[1].timestamp,
+            start() + Duration::minutes(2)
+        );
+        assert!((report.equity_curve[1].equity - (10_004.0 - 0.1)).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_parity_of_idle_strategy() {
+        let parity = check_parity(
+            config(),
+            series(&[(100.0, 101.0), (101.0, 99.0), (99.0, 102.0)]),
+            || scripted(Vec::new()),
+            1e-9,
+        )
+        .await
+        .unwrap();
+
+        assert!(parity.is_consistent(), "{:?}", parity.divergences);
+        assert_eq!(parity.max_equity_gap, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_parity_flags_fill_divergences() {
+        // The backtester fills on the next candle, the engine at the signal close
+        let parity = check_parity(
+            config(),
+            series(&[
+                (100.0, 100.0),
+                (102.0, 104.0),
+                (104.0, 110.0),
+                (112.0, 112.0),
+            ]),
+            || {
+                scripted(vec![
+                    Signal::buy("BTC", 1.0),
+                    Signal::Hold,
+                    Signal::close("BTC"),
+                ])
+            },
+            1e-9,
+        )
+        .await
+        .unwrap();
+
+        assert!(!parity.is_consistent());
+        assert_eq!(parity.matched_trades, 1);
+        let fields: Vec<&str> = parity
+            .divergences
+            .iter()
+            .map(|divergence| divergence.field.as_str())
+            .collect();
+        assert!(fields.contains(&"trades[0].entry_price"));
+        assert!(fields.contains(&"trades[0].exit_price"));
+        assert!(fields.contains(&"equity"));
+        assert!(parity.max_equity_gap > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_engine_replay_needs_one_interval() {
+        let mut data = series(&[(100.0, 100.0)]);
+        data.push(CandleSeries::new("ETH", Interval::Hour1, Vec::new()));
+
+        let result = EngineReplay::new(BacktestConfig::new())
+            .with_strategy(scripted(Vec::new()))
+            .run_series(data)
+            .await;
+        assert!(matches!(result, Err(BacktestError::InvalidConfig(_))));
+    }
+}
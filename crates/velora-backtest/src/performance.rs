@@ -1,11 +1,19 @@
 //! Performance analytics and metrics calculation.
 
+use crate::feed::CandleSeries;
 use crate::portfolio::{CompletedTrade, EquityPoint};
-use chrono::Duration;
+use chrono::{DateTime, Datelike, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Periods per year assumed when the spacing of snapshots is unknown
+const DEFAULT_PERIODS_PER_YEAR: f64 = 252.0;
+
+/// Milliseconds in an average calendar year
+const MILLIS_PER_YEAR: f64 = 365.25 * 24.0 * 3600.0 * 1000.0;
 
 /// Complete performance metrics for a backtest
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PerformanceMetrics {
     // Return Metrics
     /// Total return as percentage
@@ -14,9 +22,17 @@ pub struct PerformanceMetrics {
     /// Annualized return as percentage
     pub annualized_return: f64,
 
-    /// Total profit/loss in dollars
+    /// Total profit/loss in dollars, funding included
     pub total_pnl: f64,
 
+    /// Net funding received on perpetual positions (negative when paid)
+    #[serde(default)]
+    pub funding_pnl: f64,
+
+    /// Return of each calendar month, oldest first
+    #[serde(default)]
+    pub monthly_returns: Vec<MonthlyReturn>,
+
     // Risk Metrics
     /// Sharpe ratio (risk-adjusted return)
     pub sharpe_ratio: f64,
@@ -24,12 +40,50 @@ pub struct PerformanceMetrics {
     /// Sortino ratio (downside risk-adjusted return)
     pub sortino_ratio: f64,
 
+    /// Annualized volatility of returns as percentage
+    #[serde(default)]
+    pub volatility: f64,
+
+    /// Calmar ratio (annualized return / max drawdown)
+    #[serde(default)]
+    pub calmar_ratio: f64,
+
+    /// Omega ratio (sum of gains / sum of losses per period)
+    #[serde(default)]
+    pub omega_ratio: f64,
+
+    /// Tail ratio (95th percentile return / 5th percentile loss)
+    #[serde(default)]
+    pub tail_ratio: f64,
+
+    /// Historical 95% value at risk: loss per period as positive percentage
+    #[serde(default)]
+    pub var_95: f64,
+
+    /// Historical 95% conditional value at risk (expected shortfall)
+    #[serde(default)]
+    pub cvar_95: f64,
+
     /// Maximum drawdown as percentage
     pub max_drawdown: f64,
 
     /// Duration of maximum drawdown
     pub max_drawdown_duration_days: i64,
 
+    /// Return periods per year, from the median spacing of equity snapshots
+    ///
+    /// Sharpe, Sortino, volatility and alpha are annualized with it.
+    #[serde(default)]
+    pub periods_per_year: f64,
+
+    /// Sharpe ratio and volatility over a rolling window of snapshots
+    #[serde(default)]
+    pub rolling: Vec<RollingMetrics>,
+
+    /// Comparison with a benchmark, if one was given
+    #[serde(default)]
+    pub benchmark: Option<BenchmarkMetrics>,
+
     // Trade Statistics
     /// Total number of completed trades
     pub total_trades: usize,
@@ -58,21 +112,97 @@ pub struct PerformanceMetrics {
     /// Largest losing trade
     pub largest_loss: f64,
 
+    /// Average P&L per trade
+    #[serde(default)]
+    pub expectancy: f64,
+
+    /// Average maximum adverse excursion per trade
+    #[serde(default)]
+    pub avg_mae: f64,
+
+    /// Average maximum favorable excursion per trade
+    #[serde(default)]
+    pub avg_mfe: f64,
+
     // Time Metrics
     /// Average holding period in hours
     pub avg_holding_period_hours: f64,
 
     /// Total backtest duration in days
     pub duration_days: i64,
+
+    /// Share of time with an open position, as percentage
+    #[serde(default)]
+    pub time_in_market: f64,
+
+    /// Average gross exposure as percentage of equity
+    #[serde(default)]
+    pub avg_exposure: f64,
+
+    /// Highest gross exposure as percentage of equity
+    #[serde(default)]
+    pub max_exposure: f64,
+}
+
+/// Return of one calendar month
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonthlyReturn {
+    /// Calendar year
+    pub year: i32,
+
+    /// Month of the year, from 1
+    pub month: u32,
+
+    /// Return as percentage, from the previous month's closing equity
+    pub return_pct: f64,
+}
+
+/// Risk-adjusted performance over one rolling window
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RollingMetrics {
+    /// Snapshot closing the window
+    pub timestamp: DateTime<Utc>,
+
+    /// Annualized Sharpe ratio over the window
+    pub sharpe_ratio: f64,
+
+    /// Annualized volatility over the window as percentage
+    pub volatility: f64,
+}
+
+/// Performance relative to a benchmark series
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkMetrics {
+    /// Buy-and-hold return of the benchmark as percentage
+    pub total_return: f64,
+
+    /// Annualized return not explained by the benchmark, as percentage
+    pub alpha: f64,
+
+    /// Sensitivity of returns to benchmark returns
+    pub beta: f64,
+
+    /// Correlation of returns with benchmark returns
+    pub correlation: f64,
+
+    /// Annualized mean excess return over its volatility
+    pub information_ratio: f64,
 }
 
 /// Calculate performance metrics from backtest results
+///
+/// `funding_pnl` is the net funding of perpetual positions, counted in the
+/// total P&L but not in trade statistics. Rolling metrics cover
+/// `rolling_window` snapshots; the benchmark is left empty (see
+/// [`calculate_benchmark`]).
 pub fn calculate_metrics(
     equity_curve: &[EquityPoint],
     trades: &[CompletedTrade],
+    funding_pnl: f64,
     initial_capital: f64,
+    rolling_window: usize,
 ) -> PerformanceMetrics {
-    let total_pnl = calculate_total_pnl(trades);
+    let total_pnl = calculate_total_pnl(trades) + funding_pnl;
     let total_return = (total_pnl / initial_capital) * 100.0;
 
     let duration_days = calculate_duration_days(equity_curve);
@@ -96,22 +226,41 @@ pub fn calculate_metrics(
 
     let profit_factor = calculate_profit_factor(trades);
 
-    let daily_returns = calculate_daily_returns(equity_curve);
-    let sharpe_ratio = calculate_sharpe_ratio(&daily_returns);
-    let sortino_ratio = calculate_sortino_ratio(&daily_returns);
+    let periods_per_year = calculate_periods_per_year(equity_curve);
+    let returns = calculate_period_returns(equity_curve);
+    let sharpe_ratio = calculate_sharpe_ratio(&returns, periods_per_year);
+    let sortino_ratio = calculate_sortino_ratio(&returns, periods_per_year);
+    let volatility = calculate_volatility(&returns, periods_per_year);
+    let omega_ratio = calculate_omega_ratio(&returns);
+    let (tail_ratio, var_95, cvar_95) = calculate_tail_risk(&returns);
+    let rolling = calculate_rolling(equity_curve, &returns, rolling_window, periods_per_year);
 
     let (max_drawdown, max_drawdown_duration_days) = calculate_max_drawdown(equity_curve);
+    let calmar_ratio = calculate_calmar_ratio(annualized_return, max_drawdown);
 
     let avg_holding_period_hours = calculate_avg_holding_period(trades);
+    let (expectancy, avg_mae, avg_mfe) = calculate_trade_averages(trades);
+    let (time_in_market, avg_exposure, max_exposure) = calculate_exposure(equity_curve);
 
     PerformanceMetrics {
         total_return,
         annualized_return,
         total_pnl,
+        funding_pnl,
+        monthly_returns: calculate_monthly_returns(equity_curve, initial_capital),
         sharpe_ratio,
         sortino_ratio,
+        volatility,
+        calmar_ratio,
+        omega_ratio,
+        tail_ratio,
+        var_95,
+        cvar_95,
         max_drawdown,
         max_drawdown_duration_days,
+        periods_per_year,
+        rolling,
+        benchmark: None,
         total_trades,
         winning_trades,
         losing_trades,
@@ -121,11 +270,78 @@ pub fn calculate_metrics(
         profit_factor,
         largest_win,
         largest_loss,
+        expectancy,
+        avg_mae,
+        avg_mfe,
         avg_holding_period_hours,
         duration_days,
+        time_in_market,
+        avg_exposure,
+        max_exposure,
     }
 }
 
+/// Compare an equity curve with a benchmark series, e.g. buy-and-hold BTC
+///
+/// Each snapshot is paired with the close of the last benchmark candle
+/// finished by then; snapshots before the first close are skipped. Returns
+/// None without at least two paired returns.
+pub fn calculate_benchmark(
+    equity_curve: &[EquityPoint],
+    benchmark: &CandleSeries,
+    periods_per_year: f64,
+) -> Option<BenchmarkMetrics> {
+    let mut candles = benchmark.candles.iter().peekable();
+    let mut price = None;
+    let mut equities = Vec::new();
+    let mut prices = Vec::new();
+    for point in equity_curve {
+        while let Some(candle) =
+            candles.next_if(|candle| benchmark.close_time(candle) <= point.timestamp)
+        {
+            price = Some(candle.close.into_inner());
+        }
+        if let Some(price) = price {
+            equities.push(point.equity);
+            prices.push(price);
+        }
+    }
+    if prices.len() < 3 {
+        return None;
+    }
+
+    let strategy = percent_changes(&equities);
+    let market = percent_changes(&prices);
+    let (strategy_mean, strategy_std) = mean_std_dev(&strategy);
+    let (market_mean, market_std) = mean_std_dev(&market);
+    let covariance = strategy
+        .iter()
+        .zip(&market)
+        .map(|(s, m)| (s - strategy_mean) * (m - market_mean))
+        .sum::<f64>()
+        / strategy.len() as f64;
+
+    let beta = if market_std == 0.0 {
+        0.0
+    } else {
+        covariance / market_std.powi(2)
+    };
+    let correlation = if strategy_std == 0.0 || market_std == 0.0 {
+        0.0
+    } else {
+        covariance / (strategy_std * market_std)
+    };
+    let active: Vec<f64> = strategy.iter().zip(&market).map(|(s, m)| s - m).collect();
+
+    Some(BenchmarkMetrics {
+        total_return: (prices[prices.len() - 1] / prices[0] - 1.0) * 100.0,
+        alpha: (strategy_mean - beta * market_mean) * periods_per_year,
+        beta,
+        correlation,
+        information_ratio: calculate_sharpe_ratio(&active, periods_per_year),
+    })
+}
+
 fn calculate_total_pnl(trades: &[CompletedTrade]) -> f64 {
     trades.iter().map(|t| t.pnl).sum()
 }
@@ -200,57 +416,73 @@ fn calculate_profit_factor(trades: &[CompletedTrade]) -> f64 {
     }
 }
 
-fn calculate_daily_returns(equity_curve: &[EquityPoint]) -> Vec<f64> {
-    if equity_curve.len() < 2 {
-        return vec![];
+/// Return periods per year implied by the median spacing of snapshots
+fn calculate_periods_per_year(equity_curve: &[EquityPoint]) -> f64 {
+    let mut spacings: Vec<i64> = equity_curve
+        .windows(2)
+        .map(|window| (window[1].timestamp - window[0].timestamp).num_milliseconds())
+        .filter(|&ms| ms > 0)
+        .collect();
+    if spacings.is_empty() {
+        return DEFAULT_PERIODS_PER_YEAR;
     }
 
-    equity_curve
+    let middle = spacings.len() / 2;
+    let (_, median, _) = spacings.select_nth_unstable(middle);
+    MILLIS_PER_YEAR / *median as f64
+}
+
+fn calculate_period_returns(equity_curve: &[EquityPoint]) -> Vec<f64> {
+    let equities: Vec<f64> = equity_curve.iter().map(|point| point.equity).collect();
+    percent_changes(&equities)
+}
+
+/// Percentage change between consecutive values
+fn percent_changes(values: &[f64]) -> Vec<f64> {
+    values
         .windows(2)
         .map(|window| {
-            let prev = window[0].equity;
-            let curr = window[1].equity;
+            let prev = window[0];
+            let curr = window[1];
             ((curr - prev) / prev) * 100.0
         })
         .collect()
 }
 
-fn calculate_sharpe_ratio(daily_returns: &[f64]) -> f64 {
-    if daily_returns.is_empty() {
-        return 0.0;
+/// Mean and population standard deviation
+fn mean_std_dev(values: &[f64]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0.0, 0.0);
     }
 
-    let mean = daily_returns.iter().sum::<f64>() / daily_returns.len() as f64;
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    (mean, variance.sqrt())
+}
 
-    let variance = daily_returns
-        .iter()
-        .map(|r| (r - mean).powi(2))
-        .sum::<f64>()
-        / daily_returns.len() as f64;
+fn calculate_sharpe_ratio(returns: &[f64], periods_per_year: f64) -> f64 {
+    if returns.is_empty() {
+        return 0.0;
+    }
 
-    let std_dev = variance.sqrt();
+    let (mean, std_dev) = mean_std_dev(returns);
 
     if std_dev == 0.0 {
         0.0
     } else {
-        // Annualize: sqrt(252 trading days)
-        (mean / std_dev) * (252.0_f64).sqrt()
+        (mean / std_dev) * periods_per_year.sqrt()
     }
 }
 
-fn calculate_sortino_ratio(daily_returns: &[f64]) -> f64 {
-    if daily_returns.is_empty() {
+fn calculate_sortino_ratio(returns: &[f64], periods_per_year: f64) -> f64 {
+    if returns.is_empty() {
         return 0.0;
     }
 
-    let mean = daily_returns.iter().sum::<f64>() / daily_returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
 
     // Only consider downside deviation
-    let downside_returns: Vec<f64> = daily_returns
-        .iter()
-        .filter(|&&r| r < 0.0)
-        .copied()
-        .collect();
+    let downside_returns: Vec<f64> = returns.iter().filter(|&&r| r < 0.0).copied().collect();
 
     if downside_returns.is_empty() {
         return f64::INFINITY;
@@ -264,11 +496,97 @@ fn calculate_sortino_ratio(daily_returns: &[f64]) -> f64 {
     if downside_dev == 0.0 {
         0.0
     } else {
-        // Annualize
-        (mean / downside_dev) * (252.0_f64).sqrt()
+        (mean / downside_dev) * periods_per_year.sqrt()
+    }
+}
+
+fn calculate_volatility(returns: &[f64], periods_per_year: f64) -> f64 {
+    mean_std_dev(returns).1 * periods_per_year.sqrt()
+}
+
+fn calculate_calmar_ratio(annualized_return: f64, max_drawdown: f64) -> f64 {
+    if max_drawdown == 0.0 {
+        if annualized_return > 0.0 {
+            f64::INFINITY
+        } else {
+            0.0
+        }
+    } else {
+        annualized_return / max_drawdown.abs()
     }
 }
 
+fn calculate_omega_ratio(returns: &[f64]) -> f64 {
+    let gains: f64 = returns.iter().filter(|&&r| r > 0.0).sum();
+    let losses: f64 = returns.iter().filter(|&&r| r < 0.0).map(|r| r.abs()).sum();
+
+    if losses == 0.0 {
+        if gains > 0.0 {
+            f64::INFINITY
+        } else {
+            0.0
+        }
+    } else {
+        gains / losses
+    }
+}
+
+/// Tail ratio, 95% VaR and 95% CVaR of the returns
+fn calculate_tail_risk(returns: &[f64]) -> (f64, f64, f64) {
+    if returns.is_empty() {
+        return (0.0, 0.0, 0.0);
+    }
+
+    let mut sorted = returns.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let lower = percentile(&sorted, 5.0);
+    let upper = percentile(&sorted, 95.0);
+
+    let tail_ratio = if lower == 0.0 {
+        0.0
+    } else {
+        upper.abs() / lower.abs()
+    };
+    let tail: Vec<f64> = sorted.iter().copied().take_while(|&r| r <= lower).collect();
+    let expected_shortfall = tail.iter().sum::<f64>() / tail.len() as f64;
+
+    (
+        tail_ratio,
+        (-lower).max(0.0),
+        (-expected_shortfall).max(0.0),
+    )
+}
+
+/// Linearly interpolated percentile of sorted values
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p / 100.0) * (sorted.len() - 1) as f64;
+    let below = rank.floor() as usize;
+    let above = rank.ceil() as usize;
+    sorted[below] + (sorted[above] - sorted[below]) * (rank - below as f64)
+}
+
+/// Sharpe ratio and volatility of each window of `window` returns
+fn calculate_rolling(
+    equity_curve: &[EquityPoint],
+    returns: &[f64],
+    window: usize,
+    periods_per_year: f64,
+) -> Vec<RollingMetrics> {
+    if window < 2 {
+        return vec![];
+    }
+
+    returns
+        .windows(window)
+        .zip(equity_curve.iter().skip(window))
+        .map(|(returns, point)| RollingMetrics {
+            timestamp: point.timestamp,
+            sharpe_ratio: calculate_sharpe_ratio(returns, periods_per_year),
+            volatility: calculate_volatility(returns, periods_per_year),
+        })
+        .collect()
+}
+
 fn calculate_max_drawdown(equity_curve: &[EquityPoint]) -> (f64, i64) {
     if equity_curve.is_empty() {
         return (0.0, 0);
@@ -313,6 +631,99 @@ fn calculate_avg_holding_period(trades: &[CompletedTrade]) -> f64 {
     total_hours as f64 / trades.len() as f64
 }
 
+/// Average P&L, MAE and MFE per trade
+fn calculate_trade_averages(trades: &[CompletedTrade]) -> (f64, f64, f64) {
+    if trades.is_empty() {
+        return (0.0, 0.0, 0.0);
+    }
+
+    let count = trades.len() as f64;
+    (
+        trades.iter().map(|t| t.pnl).sum::<f64>() / count,
+        trades.iter().map(|t| t.mae).sum::<f64>() / count,
+        trades.iter().map(|t| t.mfe).sum::<f64>() / count,
+    )
+}
+
+/// Time in market, average and highest gross exposure, as percentages
+///
+/// Each snapshot's exposure is held until the next snapshot.
+fn calculate_exposure(equity_curve: &[EquityPoint]) -> (f64, f64, f64) {
+    if equity_curve.is_empty() {
+        return (0.0, 0.0, 0.0);
+    }
+
+    let exposure = |point: &EquityPoint| {
+        if point.equity > 0.0 {
+            point.gross_exposure / point.equity * 100.0
+        } else {
+            0.0
+        }
+    };
+
+    let mut total = 0;
+    let mut invested = 0;
+    for window in equity_curve.windows(2) {
+        let held = (window[1].timestamp - window[0].timestamp).num_milliseconds();
+        total += held;
+        if window[0].gross_exposure > 0.0 {
+            invested += held;
+        }
+    }
+    let time_in_market = if total > 0 {
+        invested as f64 / total as f64 * 100.0
+    } else {
+        let invested = equity_curve
+            .iter()
+            .filter(|point| point.gross_exposure > 0.0)
+            .count();
+        invested as f64 / equity_curve.len() as f64 * 100.0
+    };
+
+    let avg_exposure = equity_curve.iter().map(exposure).sum::<f64>() / equity_curve.len() as f64;
+    let max_exposure = equity_curve.iter().map(exposure).fold(0.0, f64::max);
+
+    (time_in_market, avg_exposure, max_exposure)
+}
+
+/// Return of each calendar month of the equity curve
+///
+/// The first month is measured from the initial capital, later months from
+/// the last equity of the month before.
+fn calculate_monthly_returns(
+    equity_curve: &[EquityPoint],
+    initial_capital: f64,
+) -> Vec<MonthlyReturn> {
+    let month_return = |year, month, opening: f64, closing: f64| MonthlyReturn {
+        year,
+        month,
+        return_pct: if opening == 0.0 {
+            0.0
+        } else {
+            (closing / opening - 1.0) * 100.0
+        },
+    };
+
+    let mut months = Vec::new();
+    let mut opening = initial_capital;
+    let mut current: Option<(i32, u32, f64)> = None;
+    for point in equity_curve {
+        let (year, month) = (point.timestamp.year(), point.timestamp.month());
+        if let Some((current_year, current_month, closing)) = current {
+            if (current_year, current_month) != (year, month) {
+                months.push(month_return(current_year, current_month, opening, closing));
+                opening = closing;
+            }
+        }
+        current = Some((year, month, point.equity));
+    }
+    if let Some((year, month, closing)) = current {
+        months.push(month_return(year, month, opening, closing));
+    }
+
+    months
+}
+
 impl PerformanceMetrics {
     /// Print a formatted summary of the metrics
     pub fn print_summary(&self) {
@@ -324,16 +735,37 @@ impl PerformanceMetrics {
         println!("  Total Return:        {:>10.2}%", self.total_return);
         println!("  Annualized Return:   {:>10.2}%", self.annualized_return);
         println!("  Total P&L:           {:>10.2}", self.total_pnl);
+        if self.funding_pnl != 0.0 {
+            println!("  Funding P&L:         {:>10.2}", self.funding_pnl);
+        }
 
         println!("\nRisk Metrics:");
         println!("  Sharpe Ratio:        {:>10.2}", self.sharpe_ratio);
         println!("  Sortino Ratio:       {:>10.2}", self.sortino_ratio);
+        println!("  Calmar Ratio:        {:>10.2}", self.calmar_ratio);
+        println!("  Omega Ratio:         {:>10.2}", self.omega_ratio);
+        println!("  Tail Ratio:          {:>10.2}", self.tail_ratio);
+        println!("  Volatility:          {:>10.2}%", self.volatility);
+        println!("  VaR (95%):           {:>10.2}%", self.var_95);
+        println!("  CVaR (95%):          {:>10.2}%", self.cvar_95);
         println!("  Max Drawdown:        {:>10.2}%", self.max_drawdown);
         println!(
             "  Max DD Duration:     {:>10} days",
             self.max_drawdown_duration_days
         );
 
+        if let Some(benchmark) = &self.benchmark {
+            println!("\nBenchmark:");
+            println!("  Benchmark Return:    {:>10.2}%", benchmark.total_return);
+            println!("  Alpha:               {:>10.2}%", benchmark.alpha);
+            println!("  Beta:                {:>10.2}", benchmark.beta);
+            println!("  Correlation:         {:>10.2}", benchmark.correlation);
+            println!(
+                "  Information Ratio:   {:>10.2}",
+                benchmark.information_ratio
+            );
+        }
+
         println!("\nTrade Statistics:");
         println!("  Total Trades:        {:>10}", self.total_trades);
         println!(
@@ -350,23 +782,79 @@ impl PerformanceMetrics {
         println!("  Profit Factor:       {:>10.2}", self.profit_factor);
         println!("  Largest Win:         {:>10.2}", self.largest_win);
         println!("  Largest Loss:        {:>10.2}", self.largest_loss);
+        println!("  Expectancy:          {:>10.2}", self.expectancy);
+        println!("  Avg MAE:             {:>10.2}", self.avg_mae);
+        println!("  Avg MFE:             {:>10.2}", self.avg_mfe);
 
         println!("\nPosition Metrics:");
         println!(
             "  Avg Holding Period:  {:>10.1} hours",
             self.avg_holding_period_hours
         );
+        println!("  Time in Market:      {:>10.2}%", self.time_in_market);
+        println!("  Avg Exposure:        {:>10.2}%", self.avg_exposure);
+        println!("  Max Exposure:        {:>10.2}%", self.max_exposure);
+
+        if !self.monthly_returns.is_empty() {
+            self.print_monthly_returns();
+        }
 
         println!();
     }
+
+    /// Print monthly returns as a year-by-month table
+    fn print_monthly_returns(&self) {
+        let mut years: BTreeMap<i32, [Option<f64>; 12]> = BTreeMap::new();
+        for month in &self.monthly_returns {
+            years.entry(month.year).or_default()[month.month as usize - 1] = Some(month.return_pct);
+        }
+
+        println!("\nMonthly Returns (%):");
+        print!("  Year");
+        for name in [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ] {
+            print!("{name:>8}");
+        }
+        println!();
+        for (year, months) in years {
+            print!("  {year}");
+            for month in months {
+                match month {
+                    Some(return_pct) => print!("{return_pct:>8.2}"),
+                    None => print!("{:>8}", "-"),
+                }
+            }
+            println!();
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
+    use chrono::{TimeZone, Utc};
+    use velora_core::types::{Candle, Interval, Symbol};
     use velora_strategy::PositionSide;
 
+    /// Equity snapshots `spacing` apart, fully invested while `exposure` is set
+    fn curve(equities: &[f64], spacing: Duration, exposure: f64) -> Vec<EquityPoint> {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        equities
+            .iter()
+            .enumerate()
+            .map(|(i, &equity)| EquityPoint {
+                timestamp: start + spacing * i as i32,
+                equity,
+                cash: equity - exposure,
+                positions_value: exposure,
+                gross_exposure: exposure,
+                margin_used: 0.0,
+                maintenance_margin: 0.0,
+            })
+            .collect()
+    }
+
     #[test]
     fn test_calculate_total_pnl() {
         let trades = vec![
@@ -381,6 +869,8 @@ mod tests {
                 pnl: 1000.0,
                 pnl_pct: 2.0,
                 commission: 50.0,
+                mae: 0.0,
+                mfe: 0.0,
             },
             CompletedTrade {
                 symbol: "BTC".to_string(),
@@ -393,6 +883,8 @@ mod tests {
                 pnl: -500.0,
                 pnl_pct: -1.0,
                 commission: 50.0,
+                mae: 0.0,
+                mfe: 0.0,
             },
         ];
 
@@ -413,6 +905,8 @@ mod tests {
                 pnl: 1000.0,
                 pnl_pct: 2.0,
                 commission: 0.0,
+                mae: 0.0,
+                mfe: 0.0,
             },
             CompletedTrade {
                 symbol: "BTC".to_string(),
@@ -425,6 +919,8 @@ mod tests {
                 pnl: -500.0,
                 pnl_pct: -1.0,
                 commission: 0.0,
+                mae: 0.0,
+                mfe: 0.0,
             },
         ];
 
@@ -447,6 +943,8 @@ mod tests {
                 pnl: 2000.0,
                 pnl_pct: 4.0,
                 commission: 0.0,
+                mae: 0.0,
+                mfe: 0.0,
             },
             CompletedTrade {
                 symbol: "BTC".to_string(),
@@ -459,10 +957,160 @@ mod tests {
                 pnl: -1000.0,
                 pnl_pct: -2.0,
                 commission: 0.0,
+                mae: 0.0,
+                mfe: 0.0,
             },
         ];
 
         let pf = calculate_profit_factor(&trades);
         assert_eq!(pf, 2.0); // 2000 / 1000
     }
+
+    #[test]
+    fn test_interval_aware_annualization() {
+        let hourly = curve(
+            &[100.0, 101.0, 100.5, 102.5, 103.0],
+            Duration::hours(1),
+            0.0,
+        );
+        let daily = curve(&[100.0, 101.0, 100.5, 102.5, 103.0], Duration::days(1), 0.0);
+
+        let hourly = calculate_metrics(&hourly, &[], 0.0, 100.0, 30);
+        let daily = calculate_metrics(&daily, &[], 0.0, 100.0, 30);
+        assert!((hourly.periods_per_year - 365.25 * 24.0).abs() < 1e-9);
+        assert!((daily.periods_per_year - 365.25).abs() < 1e-9);
+
+        // Same returns, annualized over 24 times as many periods
+        let ratio = hourly.sharpe_ratio / daily.sharpe_ratio;
+        assert!((ratio - 24.0_f64.sqrt()).abs() < 1e-9);
+        assert!(hourly.volatility > daily.volatility);
+        assert_eq!(calculate_periods_per_year(&[]), DEFAULT_PERIODS_PER_YEAR);
+    }
+
+    #[test]
+    fn test_tail_risk_and_ratios() {
+        let returns: Vec<f64> = (1..=20).map(|i| i as f64 - 10.0).collect();
+        let (tail_ratio, var_95, cvar_95) = calculate_tail_risk(&returns);
+        // 5th percentile -8.05, 95th percentile 9.05
+        assert!((var_95 - 8.05).abs() < 1e-9);
+        assert_eq!(cvar_95, 9.0);
+        assert!((tail_ratio - 9.05 / 8.05).abs() < 1e-9);
+
+        assert_eq!(calculate_omega_ratio(&[2.0, -1.0, 1.0]), 3.0);
+        assert_eq!(calculate_omega_ratio(&[]), 0.0);
+        assert_eq!(calculate_calmar_ratio(20.0, -10.0), 2.0);
+        assert_eq!(calculate_calmar_ratio(20.0, 0.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_rolling_windows() {
+        let equities: Vec<f64> = (0..10).map(|i| 100.0 + (i % 3) as f64).collect();
+        let points = curve(&equities, Duration::days(1), 0.0);
+        let metrics = calculate_metrics(&points, &[], 0.0, 100.0, 4);
+
+        // 9 returns give 6 windows of 4, the first closing at the 5th snapshot
+        assert_eq!(metrics.rolling.len(), 6);
+        assert_eq!(metrics.rolling[0].timestamp, points[4].timestamp);
+        assert_eq!(metrics.rolling[5].timestamp, points[9].timestamp);
+        assert!(metrics.rolling.iter().all(|window| window.volatility > 0.0));
+        assert!(calculate_metrics(&points, &[], 0.0, 100.0, 0)
+            .rolling
+            .is_empty());
+    }
+
+    #[test]
+    fn test_monthly_returns() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let points: Vec<EquityPoint> = [(0, 105.0), (10, 110.0), (20, 110.0), (50, 99.0)]
+            .into_iter()
+            .map(|(days, equity)| EquityPoint {
+                timestamp: start + Duration::days(days),
+                equity,
+                cash: equity,
+                positions_value: 0.0,
+                gross_exposure: 0.0,
+                margin_used: 0.0,
+                maintenance_margin: 0.0,
+            })
+            .collect();
+
+        let months = calculate_monthly_returns(&points, 100.0);
+        assert_eq!(months.len(), 3);
+        assert_eq!((months[0].year, months[0].month), (2024, 1));
+        assert!((months[0].return_pct - 10.0).abs() < 1e-9);
+        assert!((months[1].return_pct - 0.0).abs() < 1e-9);
+        assert!((months[2].return_pct + 10.0).abs() < 1e-9);
+        assert_eq!(months[2].month, 3);
+    }
+
+    #[test]
+    fn test_exposure_and_trade_averages() {
+        let mut points = curve(&[100.0, 100.0, 100.0, 100.0], Duration::hours(1), 0.0);
+        points[1].gross_exposure = 50.0;
+        points[2].gross_exposure = 200.0;
+        let (time_in_market, avg_exposure, max_exposure) = calculate_exposure(&points);
+        // Invested during 2 of 3 hours
+        assert!((time_in_market - 200.0 / 3.0).abs() < 1e-9);
+        assert_eq!(avg_exposure, 62.5);
+        assert_eq!(max_exposure, 200.0);
+
+        let trade = |pnl: f64, mae: f64, mfe: f64| CompletedTrade {
+            symbol: "BTC".to_string(),
+            side: PositionSide::Long,
+            entry_time: Utc::now(),
+            exit_time: Utc::now(),
+            entry_price: 100.0,
+            exit_price: 100.0 + pnl,
+            quantity: 1.0,
+            pnl,
+            pnl_pct: pnl,
+            commission: 0.0,
+            mae,
+            mfe,
+        };
+        let trades = [trade(10.0, -2.0, 12.0), trade(-4.0, -6.0, 2.0)];
+        assert_eq!(calculate_trade_averages(&trades), (3.0, -4.0, 7.0));
+    }
+
+    #[test]
+    fn test_benchmark_alpha_and_beta() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let prices = [100.0, 102.0, 101.0, 104.0, 103.0];
+        let candles = prices
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| Candle {
+                symbol: Symbol::new("BTC"),
+                timestamp: start + Duration::days(i as i64),
+                open: price.into(),
+                high: price.into(),
+                low: price.into(),
+                close: price.into(),
+                volume: 1.0.into(),
+            })
+            .collect();
+        let benchmark = CandleSeries::new("BTC", Interval::Day1, candles);
+
+        // Snapshots at each close; the strategy holds twice the benchmark
+        let mut equity = 1_000.0;
+        let mut equities = vec![equity];
+        for window in prices.windows(2) {
+            equity *= 1.0 + 2.0 * (window[1] / window[0] - 1.0);
+            equities.push(equity);
+        }
+        let mut points = curve(&equities, Duration::days(1), 0.0);
+        for point in &mut points {
+            point.timestamp += Duration::days(1);
+        }
+        // Before the first benchmark close: skipped
+        points.insert(0, curve(&[1_000.0], Duration::days(1), 0.0).remove(0));
+
+        let metrics = calculate_benchmark(&points, &benchmark, 365.25).unwrap();
+        assert!((metrics.total_return - 3.0).abs() < 1e-9);
+        assert!((metrics.beta - 2.0).abs() < 1e-9);
+        assert!((metrics.correlation - 1.0).abs() < 1e-9);
+        assert!(metrics.alpha.abs() < 1e-9);
+
+        assert!(calculate_benchmark(&points[..2], &benchmark, 365.25).is_none());
+    }
 }
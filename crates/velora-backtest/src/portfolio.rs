@@ -1,10 +1,39 @@
 //! Portfolio tracking for backtesting.
 
+use crate::config::{LotMatching, PerpetualConfig};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use velora_core::types::Side;
 use velora_strategy::{Position, PositionSide};
 
+/// Quantities below this are treated as zero
+const QUANTITY_EPSILON: f64 = 1e-9;
+
+/// Prices a symbol traded at during one bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BarRange {
+    /// First price of the bar
+    pub open: f64,
+
+    /// Lowest price of the bar
+    pub low: f64,
+
+    /// Highest price of the bar
+    pub high: f64,
+}
+
+impl BarRange {
+    /// Range of a single price, as for a tick
+    pub fn at(price: f64) -> Self {
+        Self {
+            open: price,
+            low: price,
+            high: price,
+        }
+    }
+}
+
 /// Portfolio state during backtest
 #[derive(Debug, Clone)]
 pub struct Portfolio {
@@ -17,6 +46,12 @@ pub struct Portfolio {
     /// Current positions
     positions: HashMap<String, Position>,
 
+    /// Open lots of each position, oldest first
+    lots: HashMap<String, VecDeque<Lot>>,
+
+    /// Order in which reducing fills consume lots
+    lot_matching: LotMatching,
+
     /// Completed trades
     trades: Vec<CompletedTrade>,
 
@@ -25,6 +60,30 @@ pub struct Portfolio {
 
     /// Current prices for each symbol
     current_prices: HashMap<String, f64>,
+
+    /// Margin rules of the symbols traded as perpetuals
+    perpetuals: HashMap<String, PerpetualConfig>,
+
+    /// Funding paid and received
+    funding_payments: Vec<FundingPayment>,
+
+    /// Positions closed by liquidation
+    liquidations: Vec<Liquidation>,
+}
+
+/// Quantity opened by one fill, until reduced
+#[derive(Debug, Clone)]
+struct Lot {
+    quantity: f64,
+    price: f64,
+
+    /// Entry commission not yet attributed to a completed trade
+    commission: f64,
+    opened_at: DateTime<Utc>,
+
+    /// Lowest and highest prices seen since the lot opened
+    low: f64,
+    high: f64,
 }
 
 /// Point on the equity curve
@@ -39,11 +98,73 @@ pub struct EquityPoint {
     /// Available cash
     pub cash: f64,
 
-    /// Total value of positions
+    /// Total value of positions (negative for shorts)
     pub positions_value: f64,
+
+    /// Total absolute value of positions
+    #[serde(default)]
+    pub gross_exposure: f64,
+
+    /// Initial margin of the perpetual positions
+    #[serde(default)]
+    pub margin_used: f64,
+
+    /// Maintenance margin of the perpetual positions
+    #[serde(default)]
+    pub maintenance_margin: f64,
+}
+
+/// Funding exchanged on a perpetual position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundingPayment {
+    /// Symbol of the position
+    pub symbol: String,
+
+    /// Funding time
+    pub timestamp: DateTime<Utc>,
+
+    /// Funding rate (positive: longs pay shorts)
+    pub rate: f64,
+
+    /// Side of the position
+    pub side: PositionSide,
+
+    /// Size of the position
+    pub quantity: f64,
+
+    /// Mark price the payment was computed at
+    pub price: f64,
+
+    /// Amount received (negative when paid)
+    pub amount: f64,
 }
 
-/// A completed trade (entry + exit)
+/// A position closed because equity fell below the maintenance margin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Liquidation {
+    /// Symbol of the position
+    pub symbol: String,
+
+    /// When the position was liquidated
+    pub timestamp: DateTime<Utc>,
+
+    /// Side of the position
+    pub side: PositionSide,
+
+    /// Size of the position
+    pub quantity: f64,
+
+    /// Price the position was closed at
+    pub price: f64,
+
+    /// Account equity when the margin was breached
+    pub equity: f64,
+
+    /// Maintenance margin when the margin was breached
+    pub maintenance_margin: f64,
+}
+
+/// A completed trade (entry + exit of one lot, or part of it)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompletedTrade {
     /// Symbol traded
@@ -73,8 +194,16 @@ pub struct CompletedTrade {
     /// P&L percentage
     pub pnl_pct: f64,
 
-    /// Commission paid
+    /// Commission paid (entry and exit share)
     pub commission: f64,
+
+    /// Maximum adverse excursion: worst unrealized P&L while open (zero or negative)
+    #[serde(default)]
+    pub mae: f64,
+
+    /// Maximum favorable excursion: best unrealized P&L while open (zero or positive)
+    #[serde(default)]
+    pub mfe: f64,
 }
 
 impl Portfolio {
@@ -84,13 +213,33 @@ impl Portfolio {
             cash: initial_capital,
             initial_capital,
             positions: HashMap::new(),
+            lots: HashMap::new(),
+            lot_matching: LotMatching::default(),
             trades: Vec::new(),
             equity_curve: Vec::new(),
             current_prices: HashMap::new(),
+            perpetuals: HashMap::new(),
+            funding_payments: Vec::new(),
+            liquidations: Vec::new(),
         }
     }
 
+    /// Set the symbols traded as perpetuals
+    pub fn with_perpetuals(mut self, perpetuals: HashMap<String, PerpetualConfig>) -> Self {
+        self.perpetuals = perpetuals;
+        self
+    }
+
+    /// Set the order in which reducing fills consume lots
+    pub fn with_lot_matching(mut self, lot_matching: LotMatching) -> Self {
+        self.lot_matching = lot_matching;
+        self
+    }
+
     /// Get available cash
+    ///
+    /// Buys pay and sells receive the notional, so cash goes negative while
+    /// long and above capital while short.
     pub fn cash(&self) -> f64 {
         self.cash
     }
@@ -127,6 +276,7 @@ impl Portfolio {
 
     /// Update current price for a symbol
     pub fn update_price(&mut self, symbol: String, price: f64) {
+        self.mark_range(&symbol, price, price);
         self.current_prices.insert(symbol.clone(), price);
 
         // Update position price if we have one
@@ -135,10 +285,49 @@ impl Portfolio {
         }
     }
 
+    /// Record the price range traded while a symbol's lots are open
+    ///
+    /// Feeds the excursions (MAE/MFE) of the trades closing those lots.
+    pub fn mark_range(&mut self, symbol: &str, low: f64, high: f64) {
+        for lot in self.lots.get_mut(symbol).into_iter().flatten() {
+            lot.low = lot.low.min(low);
+            lot.high = lot.high.max(high);
+        }
+    }
+
+    /// Set the stop-loss and take-profit shown on a position
+    ///
+    /// Returns the position if its levels changed.
+    pub fn set_exit_levels(
+        &mut self,
+        symbol: &str,
+        stop_loss: Option<f64>,
+        take_profit: Option<f64>,
+    ) -> Option<&Position> {
+        let position = self.positions.get_mut(symbol)?;
+        if position.stop_loss == stop_loss && position.take_profit == take_profit {
+            return None;
+        }
+
+        position.stop_loss = stop_loss;
+        position.take_profit = take_profit;
+        Some(position)
+    }
+
     /// Calculate total equity (cash + positions value)
     pub fn total_equity(&self) -> f64 {
-        let positions_value: f64 = self.positions.values().map(|p| p.value()).sum();
-        self.cash + positions_value
+        self.cash + self.positions_value()
+    }
+
+    /// Signed value of all positions at current prices
+    fn positions_value(&self) -> f64 {
+        self.positions
+            .values()
+            .map(|position| match position.side {
+                PositionSide::Long => position.value(),
+                PositionSide::Short => -position.value(),
+            })
+            .sum()
     }
 
     /// Calculate total unrealized P&L
@@ -151,29 +340,290 @@ impl Portfolio {
         self.trades.iter().map(|t| t.pnl).sum()
     }
 
-    /// Open a new position (buy)
-    pub fn open_position(
+    /// Net funding received (negative when paid)
+    pub fn funding_pnl(&self) -> f64 {
+        self.funding_payments.iter().map(|p| p.amount).sum()
+    }
+
+    /// Get funding payments
+    pub fn funding_payments(&self) -> &[FundingPayment] {
+        &self.funding_payments
+    }
+
+    /// Get liquidations
+    pub fn liquidations(&self) -> &[Liquidation] {
+        &self.liquidations
+    }
+
+    /// Check if a symbol is traded as a perpetual
+    pub fn is_perpetual(&self, symbol: &str) -> bool {
+        self.perpetuals.contains_key(symbol)
+    }
+
+    /// Initial margin of the open perpetual positions
+    pub fn margin_used(&self) -> f64 {
+        self.margin(PerpetualConfig::initial_margin_rate)
+    }
+
+    /// Maintenance margin of the open perpetual positions
+    pub fn maintenance_margin(&self) -> f64 {
+        self.margin(|perpetual| perpetual.maintenance_margin)
+    }
+
+    fn margin(&self, rate: impl Fn(&PerpetualConfig) -> f64) -> f64 {
+        self.positions
+            .iter()
+            .filter_map(|(symbol, position)| {
+                let perpetual = self.perpetuals.get(symbol)?;
+                Some(position.value() * rate(perpetual))
+            })
+            .sum()
+    }
+
+    /// Initial margin needed once a fill is applied
+    pub fn margin_after_fill(&self, symbol: &str, side: Side, quantity: f64, price: f64) -> f64 {
+        let Some(perpetual) = self.perpetuals.get(symbol) else {
+            return self.margin_used();
+        };
+
+        let rate = perpetual.initial_margin_rate();
+        let (current, current_margin) = match self.positions.get(symbol) {
+            Some(position) => {
+                let signed = match position.side {
+                    PositionSide::Long => position.quantity,
+                    PositionSide::Short => -position.quantity,
+                };
+                (signed, position.value() * rate)
+            }
+            None => (0.0, 0.0),
+        };
+        let resulting = match side {
+            Side::Buy => current + quantity,
+            Side::Sell => current - quantity,
+        };
+
+        self.margin_used() - current_margin + resulting.abs() * price * rate
+    }
+
+    /// Exchange funding on a perpetual position at its current price
+    ///
+    /// A positive rate has longs pay shorts. Returns the payment, or None
+    /// if there is no position.
+    pub fn apply_funding(
         &mut self,
-        symbol: String,
-        side: PositionSide,
+        symbol: &str,
+        rate: f64,
+        timestamp: DateTime<Utc>,
+    ) -> Option<&FundingPayment> {
+        let position = self.positions.get(symbol)?;
+        let price = position.current_price;
+        let amount = match position.side {
+            PositionSide::Long => -position.value() * rate,
+            PositionSide::Short => position.value() * rate,
+        };
+
+        self.cash += amount;
+        self.funding_payments.push(FundingPayment {
+            symbol: symbol.to_string(),
+            timestamp,
+            rate,
+            side: position.side,
+            quantity: position.quantity,
+            price,
+            amount,
+        });
+        self.funding_payments.last()
+    }
+
+    /// Liquidate every perpetual position if equity fell below the
+    /// maintenance margin during a bar
+    ///
+    /// Each position is marked at the adverse extreme of its symbol's `range`
+    /// (the low of a long, the high of a short), or at its current price
+    /// without one. If equity at those marks is below the maintenance margin,
+    /// every perpetual position closes at its liquidation price, where equity
+    /// meets the maintenance margin with the other positions at their marks,
+    /// or at the open if the bar gapped through it. Each pays the symbol's
+    /// `commission_rate` on the notional. Returns the liquidations performed.
+    pub fn liquidate_if_breached(
+        &mut self,
+        range: impl Fn(&str) -> Option<BarRange>,
+        commission_rate: impl Fn(&str) -> f64,
+        timestamp: DateTime<Utc>,
+    ) -> Vec<Liquidation> {
+        // Signed value and maintenance margin of a position at its adverse mark
+        let range = |symbol: &str, position: &Position| {
+            range(symbol).unwrap_or(BarRange::at(position.current_price))
+        };
+        let marked = |symbol: &str, position: &Position| {
+            let rate = self
+                .perpetuals
+                .get(symbol)
+                .map_or(0.0, |perpetual| perpetual.maintenance_margin);
+            let (mark, sign) = match position.side {
+                PositionSide::Long => (range(symbol, position).low, 1.0),
+                PositionSide::Short => (range(symbol, position).high, -1.0),
+            };
+            (
+                sign * position.quantity * mark,
+                position.quantity * mark * rate,
+            )
+        };
+
+        let (equity, maintenance_margin) =
+            self.positions
+                .iter()
+                .fold((self.cash, 0.0), |(equity, margin), (symbol, position)| {
+                    let (value, position_margin) = marked(symbol, position);
+                    (equity + value, margin + position_margin)
+                });
+        if maintenance_margin <= 0.0 || equity >= maintenance_margin {
+            return Vec::new();
+        }
+
+        let mut symbols: Vec<String> = self
+            .positions
+            .keys()
+            .filter(|symbol| self.perpetuals.contains_key(*symbol))
+            .cloned()
+            .collect();
+        symbols.sort();
+
+        let mut liquidations = Vec::new();
+        for symbol in symbols {
+            let position = &self.positions[&symbol];
+            let (side, quantity) = (position.side, position.quantity);
+            let range = range(&symbol, position);
+            let rate = self.perpetuals[&symbol].maintenance_margin;
+
+            // Solve equity = maintenance margin for this position's price
+            let (value, margin) = marked(&symbol, position);
+            let others_equity = equity - value;
+            let others_margin = maintenance_margin - margin;
+            let price = match side {
+                PositionSide::Long => {
+                    let level = (others_margin - others_equity) / (quantity * (1.0 - rate));
+                    range.open.min(level)
+                }
+                PositionSide::Short => {
+                    let level = (others_equity - others_margin) / (quantity * (1.0 + rate));
+                    range.open.max(level)
+                }
+            }
+            .max(range.low)
+            .min(range.high);
+
+            liquidations.push(Liquidation {
+                symbol,
+                timestamp,
+                side,
+                quantity,
+                price,
+                equity,
+                maintenance_margin,
+            });
+        }
+
+        for liquidation in &liquidations {
+            let commission =
+                liquidation.quantity * liquidation.price * commission_rate(&liquidation.symbol);
+            self.close_position(
+                &liquidation.symbol,
+                liquidation.price,
+                commission,
+                timestamp,
+            );
+        }
+
+        self.liquidations.extend(liquidations.iter().cloned());
+        liquidations
+    }
+
+    /// Apply a fill to the position of its symbol
+    ///
+    /// A fill on the side of the position adds a lot at its price; one on
+    /// the other side reduces lots in [`LotMatching`] order, realizing P&L
+    /// for each as a [`CompletedTrade`]. Any quantity left once the position
+    /// is flat opens a position on the other side. Commission is split
+    /// pro rata between the closing and opening parts.
+    pub fn apply_fill(
+        &mut self,
+        symbol: &str,
+        side: Side,
         quantity: f64,
         price: f64,
         commission: f64,
         timestamp: DateTime<Utc>,
     ) {
-        // In backtesting, we only deduct commission from cash
-        // The position value is tracked separately in total_equity()
+        if quantity <= QUANTITY_EPSILON {
+            return;
+        }
+
+        match side {
+            Side::Buy => self.cash -= quantity * price,
+            Side::Sell => self.cash += quantity * price,
+        }
         self.cash -= commission;
+        self.current_prices.insert(symbol.to_string(), price);
 
-        // Create position
-        let mut position = Position::new(&symbol, side, quantity, price);
-        position.opened_at = timestamp;
+        let fill_side = match side {
+            Side::Buy => PositionSide::Long,
+            Side::Sell => PositionSide::Short,
+        };
+
+        let closing = match self.positions.get(symbol) {
+            Some(position) if position.side != fill_side => quantity.min(position.quantity),
+            _ => 0.0,
+        };
+        if closing > 0.0 {
+            let closing_commission = commission * closing / quantity;
+            self.reduce_lots(symbol, closing, price, closing_commission, timestamp);
+            self.refresh_position(symbol, timestamp);
+        }
+
+        let opening = quantity - closing;
+        if opening > QUANTITY_EPSILON {
+            let lot = Lot {
+                quantity: opening,
+                price,
+                commission: commission * opening / quantity,
+                opened_at: timestamp,
+                low: price,
+                high: price,
+            };
+            self.lots
+                .entry(symbol.to_string())
+                .or_default()
+                .push_back(lot);
+
+            self.positions.entry(symbol.to_string()).or_insert_with(|| {
+                let mut position = Position::new(symbol, fill_side, 0.0, price);
+                position.opened_at = timestamp;
+                position
+            });
+        }
 
-        self.positions.insert(symbol.clone(), position);
-        self.current_prices.insert(symbol, price);
+        self.refresh_position(symbol, timestamp);
     }
 
-    /// Close an existing position (sell)
+    /// Open a new position, or add to the existing one
+    pub fn open_position(
+        &mut self,
+        symbol: String,
+        side: PositionSide,
+        quantity: f64,
+        price: f64,
+        commission: f64,
+        timestamp: DateTime<Utc>,
+    ) {
+        let side = match side {
+            PositionSide::Long => Side::Buy,
+            PositionSide::Short => Side::Sell,
+        };
+        self.apply_fill(&symbol, side, quantity, price, commission, timestamp);
+    }
+
+    /// Close an existing position in full
     pub fn close_position(
         &mut self,
         symbol: &str,
@@ -181,51 +631,125 @@ impl Portfolio {
         commission: f64,
         timestamp: DateTime<Utc>,
     ) {
-        if let Some(position) = self.positions.remove(symbol) {
-            // In backtesting, we realize the P&L to cash
-            // Cash gets: position value at exit - commission
-            let position_value = position.quantity * price;
-            self.cash += position_value - commission;
-
-            // Calculate realized P&L
-            let pnl = match position.side {
-                PositionSide::Long => {
-                    (price - position.entry_price) * position.quantity - commission - commission
-                }
-                PositionSide::Short => {
-                    (position.entry_price - price) * position.quantity - commission - commission
-                }
+        let Some(position) = self.positions.get(symbol) else {
+            return;
+        };
+        let side = match position.side {
+            PositionSide::Long => Side::Sell,
+            PositionSide::Short => Side::Buy,
+        };
+        let quantity = position.quantity;
+        self.apply_fill(symbol, side, quantity, price, commission, timestamp);
+    }
+
+    /// Consume `quantity` from the lots of a symbol, recording a trade per lot
+    fn reduce_lots(
+        &mut self,
+        symbol: &str,
+        quantity: f64,
+        price: f64,
+        commission: f64,
+        timestamp: DateTime<Utc>,
+    ) {
+        let Some(side) = self.positions.get(symbol).map(|position| position.side) else {
+            return;
+        };
+        let Some(lots) = self.lots.get_mut(symbol) else {
+            return;
+        };
+
+        let mut remaining = quantity;
+        while remaining > QUANTITY_EPSILON {
+            let lot = match self.lot_matching {
+                LotMatching::Fifo => lots.front_mut(),
+                LotMatching::Lifo => lots.back_mut(),
+            };
+            let Some(lot) = lot else {
+                break;
             };
 
-            let pnl_pct = (pnl / (position.entry_price * position.quantity)) * 100.0;
+            let matched = remaining.min(lot.quantity);
+            let entry_commission = lot.commission * matched / lot.quantity;
+            let exit_commission = commission * matched / quantity;
+            let gross = match side {
+                PositionSide::Long => (price - lot.price) * matched,
+                PositionSide::Short => (lot.price - price) * matched,
+            };
+            let pnl = gross - entry_commission - exit_commission;
+            let (low, high) = (lot.low.min(price), lot.high.max(price));
+            let (mae, mfe) = match side {
+                PositionSide::Long => ((low - lot.price) * matched, (high - lot.price) * matched),
+                PositionSide::Short => ((lot.price - high) * matched, (lot.price - low) * matched),
+            };
 
-            // Record completed trade
-            let trade = CompletedTrade {
+            self.trades.push(CompletedTrade {
                 symbol: symbol.to_string(),
-                side: position.side,
-                entry_time: position.opened_at,
+                side,
+                entry_time: lot.opened_at,
                 exit_time: timestamp,
-                entry_price: position.entry_price,
+                entry_price: lot.price,
                 exit_price: price,
-                quantity: position.quantity,
+                quantity: matched,
                 pnl,
-                pnl_pct,
-                commission: commission * 2.0, // entry + exit
-            };
+                pnl_pct: (pnl / (lot.price * matched)) * 100.0,
+                commission: entry_commission + exit_commission,
+                mae,
+                mfe,
+            });
 
-            self.trades.push(trade);
+            lot.quantity -= matched;
+            lot.commission -= entry_commission;
+            remaining -= matched;
+            if lot.quantity <= QUANTITY_EPSILON {
+                match self.lot_matching {
+                    LotMatching::Fifo => lots.pop_front(),
+                    LotMatching::Lifo => lots.pop_back(),
+                };
+            }
+        }
+    }
+
+    /// Recompute a position's size and average cost from its lots
+    fn refresh_position(&mut self, symbol: &str, timestamp: DateTime<Utc>) {
+        let (quantity, cost) = self
+            .lots
+            .get(symbol)
+            .map(|lots| {
+                lots.iter().fold((0.0, 0.0), |(quantity, cost), lot| {
+                    (quantity + lot.quantity, cost + lot.quantity * lot.price)
+                })
+            })
+            .unwrap_or_default();
+
+        if quantity <= QUANTITY_EPSILON {
+            self.positions.remove(symbol);
+            self.lots.remove(symbol);
+            return;
+        }
+
+        let price = self.current_prices.get(symbol).copied();
+        if let Some(position) = self.positions.get_mut(symbol) {
+            position.quantity = quantity;
+            position.entry_price = cost / quantity;
+            position.updated_at = timestamp;
+            if let Some(price) = price {
+                position.update_price(price);
+            }
         }
     }
 
     /// Record an equity snapshot
     pub fn record_snapshot(&mut self, timestamp: DateTime<Utc>) {
-        let positions_value: f64 = self.positions.values().map(|p| p.value()).sum();
+        let positions_value = self.positions_value();
 
         let snapshot = EquityPoint {
             timestamp,
             equity: self.cash + positions_value,
             cash: self.cash,
             positions_value,
+            gross_exposure: self.positions.values().map(Position::value).sum(),
+            margin_used: self.margin_used(),
+            maintenance_margin: self.maintenance_margin(),
         };
 
         self.equity_curve.push(snapshot);
@@ -260,7 +784,8 @@ mod tests {
         );
 
         assert!(portfolio.has_position("BTC-USD-PERP"));
-        assert_eq!(portfolio.cash(), 9_950.0); // 10000 - 50000 - 50
+        assert_eq!(portfolio.cash(), -40_050.0); // 10000 - 50000 - 50
+        assert_eq!(portfolio.total_equity(), 9_950.0);
 
         // Update price
         portfolio.update_price("BTC-USD-PERP".to_string(), 51_000.0);
@@ -280,6 +805,99 @@ mod tests {
         assert!(trade.pnl > 0.0);
     }
 
+    #[test]
+    fn test_scaling_in_averages_cost() {
+        let mut portfolio = Portfolio::new(10_000.0);
+        let timestamp = Utc::now();
+
+        portfolio.apply_fill("ETH", Side::Buy, 1.0, 100.0, 0.0, timestamp);
+        portfolio.apply_fill("ETH", Side::Buy, 1.0, 110.0, 0.0, timestamp);
+
+        let position = portfolio.get_position("ETH").unwrap();
+        assert_eq!(position.side, PositionSide::Long);
+        assert_eq!(position.quantity, 2.0);
+        assert_eq!(position.entry_price, 105.0);
+        assert!(portfolio.trades().is_empty());
+        assert_eq!(portfolio.total_equity(), 10_010.0);
+    }
+
+    #[test]
+    fn test_partial_reduce_by_lot_matching() {
+        let reduce = |lot_matching| {
+            let mut portfolio = Portfolio::new(10_000.0).with_lot_matching(lot_matching);
+            let timestamp = Utc::now();
+            portfolio.apply_fill("ETH", Side::Buy, 1.0, 100.0, 1.0, timestamp);
+            portfolio.apply_fill("ETH", Side::Buy, 1.0, 110.0, 1.0, timestamp);
+            portfolio.apply_fill("ETH", Side::Sell, 0.5, 120.0, 1.0, timestamp);
+            portfolio
+        };
+
+        let fifo = reduce(LotMatching::Fifo);
+        let trade = &fifo.trades()[0];
+        assert_eq!((trade.entry_price, trade.quantity), (100.0, 0.5));
+        assert_eq!(trade.commission, 1.5); // half the entry + all of the exit
+        assert_eq!(trade.pnl, 10.0 - 1.5);
+        let position = fifo.get_position("ETH").unwrap();
+        assert_eq!(position.quantity, 1.5);
+        assert!((position.entry_price - 320.0 / 3.0).abs() < 1e-9);
+
+        let lifo = reduce(LotMatching::Lifo);
+        let trade = &lifo.trades()[0];
+        assert_eq!(trade.entry_price, 110.0);
+        assert_eq!(trade.pnl, 5.0 - 1.5);
+        assert!((lifo.get_position("ETH").unwrap().entry_price - 310.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reduce_across_lots() {
+        let mut portfolio = Portfolio::new(10_000.0);
+        let timestamp = Utc::now();
+        portfolio.apply_fill("ETH", Side::Sell, 1.0, 100.0, 0.0, timestamp);
+        portfolio.apply_fill("ETH", Side::Sell, 1.0, 90.0, 0.0, timestamp);
+        portfolio.apply_fill("ETH", Side::Buy, 1.5, 80.0, 0.0, timestamp);
+
+        let pnls: Vec<_> = portfolio
+            .trades()
+            .iter()
+            .map(|t| (t.quantity, t.pnl))
+            .collect();
+        assert_eq!(pnls, vec![(1.0, 20.0), (0.5, 5.0)]);
+        assert!(portfolio
+            .trades()
+            .iter()
+            .all(|t| t.side == PositionSide::Short));
+
+        let position = portfolio.get_position("ETH").unwrap();
+        assert_eq!((position.quantity, position.entry_price), (0.5, 90.0));
+    }
+
+    #[test]
+    fn test_flip_closes_and_reopens() {
+        let mut portfolio = Portfolio::new(10_000.0);
+        let timestamp = Utc::now();
+        portfolio.apply_fill("ETH", Side::Buy, 1.0, 100.0, 0.0, timestamp);
+
+        // Sell 3: closes the long and opens a 2 unit short, commission split 1:2
+        portfolio.apply_fill("ETH", Side::Sell, 3.0, 90.0, 3.0, timestamp);
+
+        assert_eq!(portfolio.trades().len(), 1);
+        assert_eq!(portfolio.trades()[0].pnl, -11.0);
+
+        let position = portfolio.get_position("ETH").unwrap();
+        assert_eq!(position.side, PositionSide::Short);
+        assert_eq!((position.quantity, position.entry_price), (2.0, 90.0));
+
+        // Equity: capital - loss - commission, short valued at market
+        assert_eq!(portfolio.total_equity(), 10_000.0 - 10.0 - 3.0);
+        portfolio.update_price("ETH".to_string(), 80.0);
+        assert_eq!(portfolio.total_equity(), 10_000.0 - 10.0 - 3.0 + 20.0);
+
+        portfolio.close_position("ETH", 80.0, 0.0, timestamp);
+        assert!(!portfolio.has_position("ETH"));
+        assert_eq!(portfolio.trades()[1].pnl, 20.0 - 2.0);
+        assert_eq!(portfolio.cash(), portfolio.total_equity());
+    }
+
     #[test]
     fn test_equity_snapshots() {
         let mut portfolio = Portfolio::new(10_000.0);
@@ -289,4 +907,140 @@ mod tests {
         assert_eq!(portfolio.equity_curve().len(), 1);
         assert_eq!(portfolio.equity_curve()[0].equity, 10_000.0);
     }
+
+    fn perpetual_portfolio() -> Portfolio {
+        let perpetuals = HashMap::from([("BTC".to_string(), PerpetualConfig::new(10.0).unwrap())]);
+        Portfolio::new(1_000.0).with_perpetuals(perpetuals)
+    }
+
+    #[test]
+    fn test_funding_payments() {
+        let mut portfolio = perpetual_portfolio();
+        let timestamp = Utc::now();
+        assert!(portfolio.apply_funding("BTC", 0.001, timestamp).is_none());
+
+        portfolio.apply_fill("BTC", Side::Buy, 10.0, 100.0, 0.0, timestamp);
+        let payment = portfolio.apply_funding("BTC", 0.001, timestamp).unwrap();
+        assert_eq!(payment.amount, -1.0);
+
+        // Shorts receive a positive rate
+        portfolio.apply_fill("BTC", Side::Sell, 20.0, 100.0, 0.0, timestamp);
+        let payment = portfolio.apply_funding("BTC", 0.001, timestamp).unwrap();
+        assert_eq!(payment.side, PositionSide::Short);
+        assert_eq!(payment.amount, 1.0);
+
+        assert_eq!(portfolio.funding_pnl(), 0.0);
+        assert_eq!(portfolio.funding_payments().len(), 2);
+    }
+
+    #[test]
+    fn test_margin_usage() {
+        let mut portfolio = perpetual_portfolio();
+        let timestamp = Utc::now();
+        portfolio.apply_fill("BTC", Side::Buy, 50.0, 100.0, 0.0, timestamp);
+        portfolio.apply_fill("ETH", Side::Buy, 1.0, 100.0, 0.0, timestamp);
+
+        // Only perpetuals use margin
+        assert_eq!(portfolio.margin_used(), 500.0);
+        assert_eq!(portfolio.maintenance_margin(), 250.0);
+        assert_eq!(
+            portfolio.margin_after_fill("BTC", Side::Buy, 50.0, 100.0),
+            1_000.0
+        );
+        assert_eq!(
+            portfolio.margin_after_fill("BTC", Side::Sell, 80.0, 100.0),
+            300.0
+        );
+
+        portfolio.record_snapshot(timestamp);
+        assert_eq!(portfolio.equity_curve()[0].margin_used, 500.0);
+    }
+
+    #[test]
+    fn test_liquidation_below_maintenance_margin() {
+        let mut portfolio = perpetual_portfolio();
+        let timestamp = Utc::now();
+        portfolio.apply_fill("BTC", Side::Buy, 50.0, 100.0, 0.0, timestamp);
+
+        // Equity 250 against 212.5 of maintenance margin
+        portfolio.update_price("BTC".to_string(), 85.0);
+        assert!(portfolio
+            .liquidate_if_breached(|_| None, |_| 0.0, timestamp)
+            .is_empty());
+
+        // Equity 150 against 207.5
+        portfolio.update_price("BTC".to_string(), 83.0);
+        let liquidations = portfolio.liquidate_if_breached(|_| None, |_| 0.0, timestamp);
+        assert_eq!(liquidations.len(), 1);
+        assert_eq!(liquidations[0].price, 83.0);
+        assert_eq!(liquidations[0].quantity, 50.0);
+        assert!(!portfolio.has_position("BTC"));
+        assert_eq!(portfolio.cash(), 150.0);
+        assert_eq!(portfolio.trades()[0].pnl, -850.0);
+    }
+
+    #[test]
+    fn test_liquidation_on_wick() {
+        let mut portfolio = perpetual_portfolio();
+        let timestamp = Utc::now();
+        portfolio.apply_fill("BTC", Side::Buy, 50.0, 100.0, 0.0, timestamp);
+
+        // The bar closes at 90, but its low breached the margin on the way
+        portfolio.update_price("BTC".to_string(), 90.0);
+        let wick = BarRange {
+            open: 100.0,
+            low: 80.0,
+            high: 100.0,
+        };
+        let liquidations = portfolio.liquidate_if_breached(|_| Some(wick), |_| 0.0, timestamp);
+        assert_eq!(liquidations.len(), 1);
+
+        // Closed where equity meets the 5% maintenance margin:
+        // 50 * p - 4,000 = 0.05 * 50 * p
+        let level = 4_000.0 / (50.0 * 0.95);
+        assert!((liquidations[0].price - level).abs() < 1e-9);
+        assert!((portfolio.cash() - 0.05 * 50.0 * level).abs() < 1e-9);
+
+        // A bar opening below the level fills at its open
+        let mut portfolio = perpetual_portfolio();
+        portfolio.apply_fill("BTC", Side::Buy, 50.0, 100.0, 0.0, timestamp);
+        let gap = BarRange {
+            open: 82.0,
+            low: 80.0,
+            high: 90.0,
+        };
+        let liquidations = portfolio.liquidate_if_breached(|_| Some(gap), |_| 0.0, timestamp);
+        assert_eq!(liquidations[0].price, 82.0);
+
+        // Short positions are checked against the high
+        let mut portfolio = perpetual_portfolio();
+        portfolio.apply_fill("BTC", Side::Sell, 50.0, 100.0, 0.0, timestamp);
+        let spike = BarRange {
+            open: 100.0,
+            low: 100.0,
+            high: 120.0,
+        };
+        let liquidations = portfolio.liquidate_if_breached(|_| Some(spike), |_| 0.0, timestamp);
+        let level = 6_000.0 / (50.0 * 1.05);
+        assert!((liquidations[0].price - level).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_trade_excursions() {
+        let mut portfolio = Portfolio::new(10_000.0);
+        let timestamp = Utc::now();
+        portfolio.apply_fill("BTC", Side::Buy, 2.0, 100.0, 0.0, timestamp);
+        portfolio.mark_range("BTC", 95.0, 104.0);
+        portfolio.update_price("BTC".to_string(), 110.0);
+        portfolio.apply_fill("BTC", Side::Sell, 2.0, 108.0, 0.0, timestamp);
+
+        let trade = &portfolio.trades()[0];
+        assert_eq!(trade.mae, -10.0);
+        assert_eq!(trade.mfe, 20.0);
+
+        portfolio.apply_fill("BTC", Side::Sell, 1.0, 100.0, 0.0, timestamp);
+        portfolio.record_snapshot(timestamp);
+        assert_eq!(portfolio.equity_curve()[0].gross_exposure, 100.0);
+        assert_eq!(portfolio.equity_curve()[0].positions_value, -100.0);
+    }
 }
@@ -0,0 +1,264 @@
+//! Market events replayed by tick-level backtests.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use velora_core::types::{BookLevel, OrderBook, Price, Side, Symbol, Tick, Trade};
+
+/// One recorded market event
+///
+/// Trades and ticks come straight from `velora-data` (see
+/// `DataStorage::get_trades` and `DataStorage::get_ticks`); order books are
+/// replayed as full snapshots followed by deltas.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReplayEvent {
+    /// A public trade; its `side` is the aggressor's
+    Trade(Trade),
+
+    /// A price update without an aggressor side
+    Tick(Tick),
+
+    /// Full order book, replacing the current one
+    BookSnapshot(OrderBook),
+
+    /// Change of one order book level
+    BookDelta(BookDelta),
+}
+
+impl ReplayEvent {
+    /// Symbol of the event
+    pub fn symbol(&self) -> &str {
+        match self {
+            ReplayEvent::Trade(trade) => trade.symbol.as_str(),
+            ReplayEvent::Tick(tick) => tick.symbol.as_str(),
+            ReplayEvent::BookSnapshot(book) => book.symbol.as_str(),
+            ReplayEvent::BookDelta(delta) => delta.symbol.as_str(),
+        }
+    }
+
+    /// When the event happened
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            ReplayEvent::Trade(trade) => trade.timestamp,
+            ReplayEvent::Tick(tick) => tick.timestamp,
+            ReplayEvent::BookSnapshot(book) => book.timestamp,
+            ReplayEvent::BookDelta(delta) => delta.timestamp,
+        }
+    }
+}
+
+impl From<Trade> for ReplayEvent {
+    fn from(trade: Trade) -> Self {
+        ReplayEvent::Trade(trade)
+    }
+}
+
+impl From<Tick> for ReplayEvent {
+    fn from(tick: Tick) -> Self {
+        ReplayEvent::Tick(tick)
+    }
+}
+
+impl From<OrderBook> for ReplayEvent {
+    fn from(book: OrderBook) -> Self {
+        ReplayEvent::BookSnapshot(book)
+    }
+}
+
+impl From<BookDelta> for ReplayEvent {
+    fn from(delta: BookDelta) -> Self {
+        ReplayEvent::BookDelta(delta)
+    }
+}
+
+/// New size of one price level of an order book
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookDelta {
+    /// Symbol of the book
+    pub symbol: String,
+
+    /// Book side: `Buy` for bids, `Sell` for asks
+    pub side: Side,
+
+    /// Price of the level
+    pub price: f64,
+
+    /// Total quantity now resting at the level (0 removes it)
+    pub quantity: f64,
+
+    /// When the level changed
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Sort events by time, keeping the recorded order of simultaneous events
+pub fn sort_events(events: &mut [ReplayEvent]) {
+    events.sort_by_key(ReplayEvent::timestamp);
+}
+
+/// Aggregated depth of one symbol
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DepthBook {
+    bids: BTreeMap<Price, f64>,
+    asks: BTreeMap<Price, f64>,
+}
+
+impl DepthBook {
+    /// Replace the book with a snapshot
+    pub(crate) fn reset(&mut self, book: &OrderBook) {
+        let levels = |levels: &[BookLevel]| {
+            levels
+                .iter()
+                .filter(|level| level.quantity.into_inner() > 0.0)
+                .map(|level| (level.price, level.quantity.into_inner()))
+                .collect()
+        };
+        self.bids = levels(&book.bids);
+        self.asks = levels(&book.asks);
+    }
+
+    /// Set the quantity of a level, removing it when empty
+    pub(crate) fn set_level(&mut self, side: Side, price: f64, quantity: f64) {
+        let levels = self.levels_mut(side);
+        if quantity > 0.0 {
+            levels.insert(price.into(), quantity);
+        } else {
+            levels.remove(&Price::from(price));
+        }
+    }
+
+    /// Quantity resting at a price on one side
+    pub(crate) fn level(&self, side: Side, price: f64) -> f64 {
+        let levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        levels.get(&Price::from(price)).copied().unwrap_or_default()
+    }
+
+    /// Best bid price
+    pub(crate) fn best_bid(&self) -> Option<f64> {
+        self.bids.keys().next_back().map(|price| price.into_inner())
+    }
+
+    /// Best ask price
+    pub(crate) fn best_ask(&self) -> Option<f64> {
+        self.asks.keys().next().map(|price| price.into_inner())
+    }
+
+    /// Take up to `quantity` of liquidity for an aggressive order
+    ///
+    /// Walks the opposite side from the best level, never past `limit`, and
+    /// removes what was taken. Returns the filled quantity and its average
+    /// price, or None if nothing could be taken.
+    pub(crate) fn take(
+        &mut self,
+        side: Side,
+        quantity: f64,
+        limit: Option<f64>,
+    ) -> Option<(f64, f64)> {
+        let mut filled = 0.0;
+        let mut notional = 0.0;
+        while filled < quantity {
+            let levels = self.levels_mut(side.opposite());
+            let best = match side {
+                Side::Buy => levels.iter_mut().next(),
+                Side::Sell => levels.iter_mut().next_back(),
+            };
+            let Some((price, available)) = best else {
+                break;
+            };
+            let price = price.into_inner();
+            let crosses = limit.is_none_or(|limit| match side {
+                Side::Buy => price <= limit,
+                Side::Sell => price >= limit,
+            });
+            if !crosses {
+                break;
+            }
+
+            let taken = available.min(quantity - filled);
+            *available -= taken;
+            if *available <= 0.0 {
+                levels.remove(&Price::from(price));
+            }
+            filled += taken;
+            notional += taken * price;
+        }
+
+        (filled > 0.0).then(|| (filled, notional / filled))
+    }
+
+    /// Convert to an order book snapshot
+    pub(crate) fn to_order_book(&self, symbol: &str, timestamp: DateTime<Utc>) -> OrderBook {
+        let level = |(price, quantity): (&Price, &f64)| BookLevel {
+            price: *price,
+            quantity: (*quantity).into(),
+        };
+        OrderBook {
+            symbol: Symbol::new(symbol),
+            bids: self.bids.iter().rev().map(level).collect(),
+            asks: self.asks.iter().map(level).collect(),
+            timestamp,
+        }
+    }
+
+    fn levels_mut(&mut self, side: Side) -> &mut BTreeMap<Price, f64> {
+        match side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book(bids: &[(f64, f64)], asks: &[(f64, f64)]) -> OrderBook {
+        let levels = |levels: &[(f64, f64)]| {
+            levels
+                .iter()
+                .map(|&(price, quantity)| BookLevel {
+                    price: price.into(),
+                    quantity: quantity.into(),
+                })
+                .collect()
+        };
+        OrderBook {
+            symbol: Symbol::new("BTC"),
+            bids: levels(bids),
+            asks: levels(asks),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_depth_book_levels() {
+        let mut depth = DepthBook::default();
+        depth.reset(&book(&[(99.0, 1.0), (98.0, 2.0)], &[(101.0, 1.0)]));
+        assert_eq!(depth.best_bid(), Some(99.0));
+        assert_eq!(depth.best_ask(), Some(101.0));
+
+        depth.set_level(Side::Buy, 99.0, 0.0);
+        depth.set_level(Side::Sell, 100.5, 3.0);
+        assert_eq!(depth.best_bid(), Some(98.0));
+        assert_eq!(depth.best_ask(), Some(100.5));
+        assert_eq!(depth.level(Side::Sell, 100.5), 3.0);
+    }
+
+    #[test]
+    fn test_take_walks_the_book() {
+        let mut depth = DepthBook::default();
+        depth.reset(&book(&[], &[(101.0, 1.0), (102.0, 1.0), (103.0, 5.0)]));
+
+        let (filled, price) = depth.take(Side::Buy, 3.0, Some(102.0)).unwrap();
+        assert_eq!(filled, 2.0);
+        assert_eq!(price, 101.5);
+        assert_eq!(depth.best_ask(), Some(103.0));
+
+        let (filled, price) = depth.take(Side::Buy, 1.0, None).unwrap();
+        assert_eq!((filled, price), (1.0, 103.0));
+        assert_eq!(depth.level(Side::Sell, 103.0), 4.0);
+        assert!(depth.take(Side::Sell, 1.0, None).is_none());
+    }
+}
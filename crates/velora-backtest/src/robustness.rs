@@ -0,0 +1,500 @@
+//! Monte Carlo robustness analysis of backtest trades.
+//!
+//! A backtest is one path through its trades. [`MonteCarlo`] replays the
+//! trades in resampled orders (and with perturbed costs) to get the
+//! distribution of outcomes the same edge could have produced.
+
+use crate::backtester::BacktestReport;
+use crate::errors::{BacktestError, BacktestResult};
+use crate::portfolio::CompletedTrade;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// How trade sequences are resampled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Resampling {
+    /// Same trades in a random order
+    Shuffle,
+
+    /// Trades drawn independently with replacement
+    #[default]
+    Bootstrap,
+
+    /// Runs of consecutive trades drawn with replacement, keeping streaks
+    /// and regime effects within each block
+    BlockBootstrap {
+        /// Trades per block
+        block_size: usize,
+    },
+}
+
+/// Random cost changes applied to every resampled trade
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Perturbation {
+    /// Extra adverse slippage on entry and exit, drawn uniformly from zero
+    /// to this many basis points
+    pub max_slippage_bps: f64,
+
+    /// Commission scaled by a factor drawn uniformly from
+    /// `1 - commission_jitter` to `1 + commission_jitter`
+    pub commission_jitter: f64,
+}
+
+/// Seeded Monte Carlo simulation of trade sequences
+///
+/// # Example
+///
+/// ```ignore
+/// let analysis = MonteCarlo::new(5_000)
+///     .with_resampling(Resampling::BlockBootstrap { block_size: 5 })
+///     .with_perturbation(Perturbation { max_slippage_bps: 2.0, commission_jitter: 0.2 })
+///     .with_seed(42)
+///     .analyze(&report)?;
+///
+/// let (low, high) = analysis.max_drawdown.confidence_interval(0.95);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MonteCarlo {
+    simulations: usize,
+    resampling: Resampling,
+    perturbation: Perturbation,
+    ruin_level: f64,
+    seed: u64,
+}
+
+/// Sampled values of one statistic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Distribution {
+    /// Samples, sorted ascending
+    pub samples: Vec<f64>,
+
+    /// Mean of the samples
+    pub mean: f64,
+
+    /// Standard deviation of the samples
+    pub std_dev: f64,
+}
+
+/// Outcome distributions of a Monte Carlo analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloReport {
+    /// Number of simulated paths
+    pub simulations: usize,
+
+    /// How trades were resampled
+    pub resampling: Resampling,
+
+    /// Cost changes applied to trades
+    pub perturbation: Perturbation,
+
+    /// Equity at the end of each path
+    pub final_equity: Distribution,
+
+    /// Total return of each path as percentage
+    pub total_return: Distribution,
+
+    /// Maximum drawdown of each path as percentage (negative)
+    pub max_drawdown: Distribution,
+
+    /// Sharpe ratio of the trade returns of each path
+    pub sharpe_ratio: Distribution,
+
+    /// Fraction of initial capital at or below which a path is ruined
+    pub ruin_level: f64,
+
+    /// Fraction of paths ruined
+    pub risk_of_ruin: f64,
+}
+
+/// Outcome of one simulated path
+struct PathStats {
+    final_equity: f64,
+    max_drawdown: f64,
+    sharpe_ratio: f64,
+    ruined: bool,
+}
+
+impl MonteCarlo {
+    /// Create an analysis of `simulations` bootstrapped paths
+    pub fn new(simulations: usize) -> Self {
+        Self {
+            simulations,
+            resampling: Resampling::default(),
+            perturbation: Perturbation::default(),
+            ruin_level: 0.5,
+            seed: 0,
+        }
+    }
+
+    /// Set how trades are resampled
+    pub fn with_resampling(mut self, resampling: Resampling) -> Self {
+        self.resampling = resampling;
+        self
+    }
+
+    /// Set random cost changes
+    pub fn with_perturbation(mut self, perturbation: Perturbation) -> Self {
+        self.perturbation = perturbation;
+        self
+    }
+
+    /// Set the fraction of initial capital counted as ruin (defaults to 0.5)
+    pub fn with_ruin_level(mut self, ruin_level: f64) -> Self {
+        self.ruin_level = ruin_level;
+        self
+    }
+
+    /// Set the random seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Analyze the trades of a backtest
+    pub fn analyze(&self, report: &BacktestReport) -> BacktestResult<MonteCarloReport> {
+        self.analyze_trades(&report.trades, report.config.initial_capital)
+    }
+
+    /// Analyze a sequence of trades starting from `initial_capital`
+    ///
+    /// Each path is as long as `trades`. Sharpe ratios are annualized from
+    /// the trade frequency of the original sequence, or per trade if it
+    /// spans no time.
+    pub fn analyze_trades(
+        &self,
+        trades: &[CompletedTrade],
+        initial_capital: f64,
+    ) -> BacktestResult<MonteCarloReport> {
+        if trades.is_empty() {
+            return Err(BacktestError::DataError(
+                "No trades to resample".to_string(),
+            ));
+        }
+        if self.simulations == 0 || initial_capital <= 0.0 {
+            return Err(BacktestError::InvalidConfig(
+                "Monte Carlo needs simulations and positive capital".to_string(),
+            ));
+        }
+
+        let annualization = annualization(trades);
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let paths: Vec<PathStats> = (0..self.simulations)
+            .map(|_| {
+                let pnls: Vec<f64> = self
+                    .resample(trades, &mut rng)
+                    .into_iter()
+                    .map(|trade| self.perturbed_pnl(trade, &mut rng))
+                    .collect();
+                path_stats(&pnls, initial_capital, self.ruin_level, annualization)
+            })
+            .collect();
+
+        let collect = |stat: fn(&PathStats) -> f64| Distribution::new(paths.iter().map(stat));
+        Ok(MonteCarloReport {
+            simulations: self.simulations,
+            resampling: self.resampling,
+            perturbation: self.perturbation,
+            final_equity: collect(|path| path.final_equity),
+            total_return: Distribution::new(
+                paths
+                    .iter()
+                    .map(|path| (path.final_equity / initial_capital - 1.0) * 100.0),
+            ),
+            max_drawdown: collect(|path| path.max_drawdown),
+            sharpe_ratio: collect(|path| path.sharpe_ratio),
+            ruin_level: self.ruin_level,
+            risk_of_ruin: paths.iter().filter(|path| path.ruined).count() as f64
+                / paths.len() as f64,
+        })
+    }
+
+    /// Draw one trade sequence as long as the original
+    fn resample<'a>(
+        &self,
+        trades: &'a [CompletedTrade],
+        rng: &mut StdRng,
+    ) -> Vec<&'a CompletedTrade> {
+        match self.resampling {
+            Resampling::Shuffle => {
+                let mut path: Vec<&CompletedTrade> = trades.iter().collect();
+                path.shuffle(rng);
+                path
+            }
+            Resampling::Bootstrap => (0..trades.len())
+                .map(|_| &trades[rng.random_range(0..trades.len())])
+                .collect(),
+            Resampling::BlockBootstrap { block_size } => {
+                // Circular blocks, so trades at the end are drawn as often
+                let block_size = block_size.clamp(1, trades.len());
+                let mut path = Vec::with_capacity(trades.len());
+                while path.len() < trades.len() {
+                    let start = rng.random_range(0..trades.len());
+                    let take = block_size.min(trades.len() - path.len());
+                    path.extend((start..start + take).map(|i| &trades[i % trades.len()]));
+                }
+                path
+            }
+        }
+    }
+
+    /// P&L of a trade after extra slippage and commission changes
+    fn perturbed_pnl(&self, trade: &CompletedTrade, rng: &mut StdRng) -> f64 {
+        let Perturbation {
+            max_slippage_bps,
+            commission_jitter,
+        } = self.perturbation;
+
+        let mut pnl = trade.pnl;
+        if max_slippage_bps > 0.0 {
+            let bps: f64 = rng.random_range(0.0..=max_slippage_bps);
+            let notional = (trade.entry_price + trade.exit_price) * trade.quantity;
+            pnl -= notional * bps / 10_000.0;
+        }
+        if commission_jitter > 0.0 {
+            let scale: f64 = rng.random_range(-commission_jitter..=commission_jitter);
+            pnl -= trade.commission * scale;
+        }
+        pnl
+    }
+}
+
+impl Distribution {
+    fn new(samples: impl Iterator<Item = f64>) -> Self {
+        let mut samples: Vec<f64> = samples.collect();
+        samples.sort_by(f64::total_cmp);
+
+        let n = samples.len().max(1) as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        Self {
+            samples,
+            mean,
+            std_dev: variance.sqrt(),
+        }
+    }
+
+    /// Value below which `percentile` percent of samples fall (interpolated)
+    pub fn percentile(&self, percentile: f64) -> f64 {
+        let Some(last) = self.samples.len().checked_sub(1) else {
+            return 0.0;
+        };
+        let rank = (percentile / 100.0).clamp(0.0, 1.0) * last as f64;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        let weight = rank - lower as f64;
+        self.samples[lower] * (1.0 - weight) + self.samples[upper] * weight
+    }
+
+    /// Central interval holding `level` of the samples (e.g. 0.95)
+    pub fn confidence_interval(&self, level: f64) -> (f64, f64) {
+        let tail = (1.0 - level.clamp(0.0, 1.0)) / 2.0 * 100.0;
+        (self.percentile(tail), self.percentile(100.0 - tail))
+    }
+}
+
+impl MonteCarloReport {
+    /// Print a summary of the distributions
+    pub fn print_summary(&self) {
+        println!(
+            "\n=== Monte Carlo Analysis ({} paths, {:?}) ===\n",
+            self.simulations, self.resampling
+        );
+
+        println!(
+            "  {:<18} {:>12} {:>12} {:>12} {:>12}",
+            "", "5th", "Median", "95th", "Mean"
+        );
+        let row = |name: &str, distribution: &Distribution| {
+            println!(
+                "  {:<18} {:>12.2} {:>12.2} {:>12.2} {:>12.2}",
+                name,
+                distribution.percentile(5.0),
+                distribution.percentile(50.0),
+                distribution.percentile(95.0),
+                distribution.mean
+            );
+        };
+        row("Final Equity", &self.final_equity);
+        row("Total Return %", &self.total_return);
+        row("Max Drawdown %", &self.max_drawdown);
+        row("Sharpe Ratio", &self.sharpe_ratio);
+
+        println!(
+            "\nRisk of Ruin (equity <= {:.0}% of capital): {:.2}%",
+            self.ruin_level * 100.0,
+            self.risk_of_ruin * 100.0
+        );
+    }
+
+    /// Export the report to JSON
+    pub fn to_json(&self) -> BacktestResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Square root of the number of trades per year in the original sequence
+fn annualization(trades: &[CompletedTrade]) -> f64 {
+    let start = trades.iter().map(|trade| trade.entry_time).min();
+    let end = trades.iter().map(|trade| trade.exit_time).max();
+    let years = match (start, end) {
+        (Some(start), Some(end)) => (end - start).num_seconds() as f64 / (365.25 * 86_400.0),
+        _ => 0.0,
+    };
+    if years > 0.0 {
+        (trades.len() as f64 / years).sqrt()
+    } else {
+        1.0
+    }
+}
+
+/// Walk the equity path of a sequence of trade P&Ls
+fn path_stats(
+    pnls: &[f64],
+    initial_capital: f64,
+    ruin_level: f64,
+    annualization: f64,
+) -> PathStats {
+    let ruin_equity = initial_capital * ruin_level;
+    let mut equity = initial_capital;
+    let mut peak = initial_capital;
+    let mut max_drawdown: f64 = 0.0;
+    let mut ruined = false;
+    let mut returns = Vec::with_capacity(pnls.len());
+
+    for pnl in pnls {
+        if equity > 0.0 {
+            returns.push(pnl / equity);
+        }
+        equity += pnl;
+        peak = peak.max(equity);
+        max_drawdown = max_drawdown.min((equity - peak) / peak * 100.0);
+        ruined |= equity <= ruin_equity;
+    }
+
+    let n = returns.len().max(1) as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let std_dev = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n).sqrt();
+    let sharpe_ratio = if std_dev > 0.0 {
+        mean / std_dev * annualization
+    } else {
+        0.0
+    };
+
+    PathStats {
+        final_equity: equity,
+        max_drawdown,
+        sharpe_ratio,
+        ruined,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use velora_strategy::PositionSide;
+
+    fn trades(pnls: &[f64]) -> Vec<CompletedTrade> {
+        let start = Utc::now();
+        pnls.iter()
+            .enumerate()
+            .map(|(i, &pnl)| CompletedTrade {
+                symbol: "BTC".to_string(),
+                side: PositionSide::Long,
+                entry_time: start + Duration::days(i as i64),
+                exit_time: start + Duration::days(i as i64 + 1),
+                entry_price: 100.0,
+                exit_price: 100.0 + pnl / 100.0,
+                quantity: 100.0,
+                pnl,
+                pnl_pct: pnl,
+                commission: 1.0,
+                mae: 0.0,
+                mfe: 0.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_shuffle_keeps_final_equity() {
+        let trades = trades(&[300.0, -200.0, 100.0, -400.0, 250.0]);
+        let report = MonteCarlo::new(200)
+            .with_resampling(Resampling::Shuffle)
+            .analyze_trades(&trades, 10_000.0)
+            .unwrap();
+
+        assert_eq!(report.final_equity.samples.len(), 200);
+        assert_eq!(report.final_equity.percentile(0.0), 10_050.0);
+        assert_eq!(report.final_equity.percentile(100.0), 10_050.0);
+        // Order changes the drawdown, from losses in a row to losses apart
+        assert!(report.max_drawdown.percentile(0.0) < report.max_drawdown.percentile(100.0));
+        assert_eq!(report.risk_of_ruin, 0.0);
+    }
+
+    #[test]
+    fn test_analysis_is_seeded() {
+        let trades = trades(&[300.0, -200.0, 100.0, -400.0, 250.0]);
+        let run = |seed| {
+            MonteCarlo::new(50)
+                .with_seed(seed)
+                .with_perturbation(Perturbation {
+                    max_slippage_bps: 5.0,
+                    commission_jitter: 0.5,
+                })
+                .analyze_trades(&trades, 10_000.0)
+                .unwrap()
+                .final_equity
+                .samples
+        };
+        assert_eq!(run(1), run(1));
+        assert_ne!(run(1), run(2));
+    }
+
+    #[test]
+    fn test_full_blocks_are_rotations() {
+        let trades = trades(&[300.0, -200.0, 100.0, -400.0, 250.0]);
+        let report = MonteCarlo::new(20)
+            .with_resampling(Resampling::BlockBootstrap { block_size: 5 })
+            .analyze_trades(&trades, 10_000.0)
+            .unwrap();
+        assert_eq!(report.final_equity.std_dev, 0.0);
+    }
+
+    #[test]
+    fn test_perturbation_costs_and_ruin() {
+        let trades = trades(&[-3_000.0, 1_000.0, -3_000.0, 1_000.0]);
+        let costly = MonteCarlo::new(100)
+            .with_resampling(Resampling::Shuffle)
+            .with_perturbation(Perturbation {
+                max_slippage_bps: 50.0,
+                commission_jitter: 0.0,
+            })
+            .analyze_trades(&trades, 10_000.0)
+            .unwrap();
+        assert!(costly.final_equity.percentile(100.0) < 6_000.0);
+
+        // Bootstrapped paths are ruined when losses come before the wins offset them
+        let report = MonteCarlo::new(500)
+            .analyze_trades(&trades, 10_000.0)
+            .unwrap();
+        assert!(report.risk_of_ruin > 0.0 && report.risk_of_ruin < 1.0);
+    }
+
+    #[test]
+    fn test_percentiles_interpolate() {
+        let distribution = Distribution::new([4.0, 1.0, 3.0, 2.0, 5.0].into_iter());
+        assert_eq!(distribution.percentile(50.0), 3.0);
+        assert_eq!(distribution.percentile(25.0), 2.0);
+        assert_eq!(distribution.percentile(12.5), 1.5);
+        assert_eq!(distribution.confidence_interval(0.5), (2.0, 4.0));
+        assert_eq!(distribution.mean, 3.0);
+    }
+
+    #[test]
+    fn test_requires_trades() {
+        let result = MonteCarlo::new(10).analyze_trades(&[], 10_000.0);
+        assert!(matches!(result, Err(BacktestError::DataError(_))));
+    }
+}
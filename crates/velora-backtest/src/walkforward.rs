@@ -0,0 +1,640 @@
+//! Walk-forward analysis: re-optimize in sample, validate out of sample.
+//!
+//! History is split into consecutive windows. On each, the [`Optimizer`]
+//! picks parameters on the in-sample part, which are then backtested on the
+//! out-of-sample part that follows, after replaying the end of the in-sample
+//! part without trading so indicators are warmed up. Each out-of-sample run
+//! starts with the capital the previous one ended with, and the runs are
+//! stitched into one compounded equity curve, so the combined report only
+//! reflects results on data the parameters were not fitted to.
+
+use crate::errors::{BacktestError, BacktestResult};
+use crate::feed::{time_span, CandleSeries};
+use crate::optimizer::{Optimizer, ParameterSet};
+use crate::performance::{calculate_metrics, PerformanceMetrics};
+use crate::portfolio::{CompletedTrade, EquityPoint};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// How in-sample windows advance
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowMode {
+    /// Fixed-length in-sample window sliding forward
+    #[default]
+    Rolling,
+
+    /// In-sample window always starting at the beginning of the history
+    Anchored,
+}
+
+/// Walk-forward analysis of an optimized strategy
+///
+/// # Example
+///
+/// ```ignore
+/// let report = WalkForward::new(optimizer, Duration::days(90), Duration::days(30))
+///     .with_mode(WindowMode::Anchored)
+///     .run(&series)?;
+///
+/// report.print_summary();
+/// ```
+pub struct WalkForward {
+    optimizer: Optimizer,
+    in_sample: Duration,
+    out_of_sample: Duration,
+    warmup: Duration,
+    mode: WindowMode,
+}
+
+/// One in-sample/out-of-sample step of a walk-forward analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardWindow {
+    /// Start of the in-sample period
+    pub in_sample_start: DateTime<Utc>,
+
+    /// End of the in-sample period and start of the out-of-sample one
+    pub in_sample_end: DateTime<Utc>,
+
+    /// End of the out-of-sample period
+    pub out_of_sample_end: DateTime<Utc>,
+
+    /// Parameters chosen in sample
+    pub parameters: ParameterSet,
+
+    /// Objective score in sample
+    pub in_sample_score: f64,
+
+    /// Objective score out of sample
+    pub out_of_sample_score: f64,
+
+    /// Metrics of the chosen parameters in sample
+    pub in_sample: PerformanceMetrics,
+
+    /// Metrics of the chosen parameters out of sample
+    pub out_of_sample: PerformanceMetrics,
+
+    /// Out-of-sample return per unit of time relative to the in-sample one
+    /// (None when the in-sample return is not positive)
+    pub efficiency: Option<f64>,
+}
+
+/// How consistently one parameter was chosen across windows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterStability {
+    /// Parameter name
+    pub name: String,
+
+    /// Number of different values chosen
+    pub distinct_values: usize,
+
+    /// Value chosen most often
+    pub most_common: Value,
+
+    /// Fraction of windows choosing the most common value
+    pub most_common_share: f64,
+
+    /// Mean of the chosen values (numeric parameters only)
+    pub mean: Option<f64>,
+
+    /// Standard deviation of the chosen values (numeric parameters only)
+    pub std_dev: Option<f64>,
+}
+
+/// Combined result of a walk-forward analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardReport {
+    /// How the windows advanced
+    pub mode: WindowMode,
+
+    /// Windows in time order
+    pub windows: Vec<WalkForwardWindow>,
+
+    /// Out-of-sample equity curves stitched end to end, each run starting
+    /// from the equity the previous one ended with
+    pub equity_curve: Vec<EquityPoint>,
+
+    /// Out-of-sample trades
+    pub trades: Vec<CompletedTrade>,
+
+    /// Metrics of the stitched out-of-sample run
+    pub metrics: PerformanceMetrics,
+
+    /// Walk-forward efficiency: out-of-sample return per unit of time over
+    /// the in-sample one, across all windows (None when in-sample returns
+    /// are not positive)
+    pub efficiency: Option<f64>,
+
+    /// Stability of each parameter across windows
+    pub parameter_stability: Vec<ParameterStability>,
+}
+
+impl WalkForward {
+    /// Create a rolling walk-forward analysis
+    ///
+    /// Each window optimizes on `in_sample` of history and validates on the
+    /// `out_of_sample` that follows; windows advance by `out_of_sample`, so
+    /// out-of-sample periods do not overlap. The whole in-sample period
+    /// warms strategies up before each out-of-sample run.
+    pub fn new(optimizer: Optimizer, in_sample: Duration, out_of_sample: Duration) -> Self {
+        Self {
+            optimizer,
+            in_sample,
+            out_of_sample,
+            warmup: in_sample,
+            mode: WindowMode::default(),
+        }
+    }
+
+    /// Set how much in-sample history is replayed before each out-of-sample
+    /// run (see [`BacktestConfig::warmup_until`])
+    ///
+    /// Strategies see its candles but do not trade on them; it only needs to
+    /// cover the longest indicator lookback. Zero starts every run cold.
+    ///
+    /// [`BacktestConfig::warmup_until`]: crate::config::BacktestConfig::warmup_until
+    pub fn with_warmup(mut self, warmup: Duration) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    /// Set how in-sample windows advance
+    pub fn with_mode(mut self, mode: WindowMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Run the analysis on per-symbol candle series
+    ///
+    /// Windows without candles in either period are skipped. Strategies
+    /// start fresh on each out-of-sample period, warmed up on the in-sample
+    /// candles before it (see [`WalkForward::with_warmup`]). Out-of-sample
+    /// runs follow one another, each starting with the capital the last one
+    /// ended with. Like [`Optimizer::run`], this blocks the calling thread.
+    pub fn run(&self, series: &[CandleSeries]) -> BacktestResult<WalkForwardReport> {
+        if self.in_sample <= Duration::zero() || self.out_of_sample <= Duration::zero() {
+            return Err(BacktestError::InvalidConfig(
+                "Walk-forward periods must be positive".to_string(),
+            ));
+        }
+        if self.warmup < Duration::zero() {
+            return Err(BacktestError::InvalidConfig(
+                "Walk-forward warm-up must not be negative".to_string(),
+            ));
+        }
+        let (start, end) = time_span(series)
+            .ok_or_else(|| BacktestError::DataError("No candles provided".to_string()))?;
+
+        // 1. Optimize on each in-sample period
+        let mut chosen = Vec::new();
+        for (in_sample_start, in_sample_end, out_of_sample_end) in self.windows(start, end) {
+            let in_sample = window(series, in_sample_start, in_sample_end);
+            let out_of_sample = window(series, in_sample_end, out_of_sample_end);
+            if !has_candles(&in_sample) || !has_candles(&out_of_sample) {
+                continue;
+            }
+
+            let optimization = self.optimizer.run(&in_sample)?;
+            let best = optimization.best().cloned().ok_or_else(|| {
+                BacktestError::InvalidConfig("Optimizer produced no successful trials".to_string())
+            })?;
+            chosen.push(((in_sample_start, in_sample_end, out_of_sample_end), best));
+        }
+        if chosen.is_empty() {
+            return Err(BacktestError::InvalidConfig(
+                "History is too short for one walk-forward window".to_string(),
+            ));
+        }
+
+        // 2. Validate the chosen parameters out of sample, each run warmed up
+        //    on in-sample candles and compounding on the equity of the last
+        let config = self.optimizer.config();
+        let initial_capital = config.initial_capital;
+        let objective = self.optimizer.objective();
+        let mut windows = Vec::new();
+        let mut equity_curve = Vec::new();
+        let mut trades = Vec::new();
+        let mut funding_pnl = 0.0;
+        let mut carried = initial_capital;
+        for ((in_sample_start, in_sample_end, out_of_sample_end), best) in chosen {
+            let warmup_start = (in_sample_end - self.warmup).max(in_sample_start);
+            let run_config = config
+                .clone()
+                .with_capital(carried)
+                .with_warmup_until(Some(in_sample_end));
+            let report = self.optimizer.backtest_with(
+                &run_config,
+                &best.parameters,
+                &window(series, warmup_start, out_of_sample_end),
+            )?;
+
+            equity_curve.extend(report.equity_curve.iter().cloned());
+            if let Some(last) = report.equity_curve.last() {
+                carried = last.equity;
+            }
+            trades.extend(report.trades);
+            funding_pnl += report.metrics.funding_pnl;
+
+            windows.push(WalkForwardWindow {
+                in_sample_start,
+                in_sample_end,
+                out_of_sample_end,
+                parameters: best.parameters,
+                in_sample_score: best.score,
+                out_of_sample_score: objective.score(&report.metrics),
+                efficiency: efficiency(
+                    best.metrics.total_return,
+                    in_sample_end - in_sample_start,
+                    report.metrics.total_return,
+                    out_of_sample_end - in_sample_end,
+                ),
+                in_sample: best.metrics,
+                out_of_sample: report.metrics,
+            });
+        }
+
+        let metrics = calculate_metrics(
+            &equity_curve,
+            &trades,
+            funding_pnl,
+            initial_capital,
+            self.optimizer.config().rolling_window,
+        );
+        let in_sample_time = windows
+            .iter()
+            .map(|w| w.in_sample_end - w.in_sample_start)
+            .fold(Duration::zero(), |total, time| total + time);
+        let out_of_sample_time = windows
+            .iter()
+            .map(|w| w.out_of_sample_end - w.in_sample_end)
+            .fold(Duration::zero(), |total, time| total + time);
+
+        Ok(WalkForwardReport {
+            mode: self.mode,
+            efficiency: efficiency(
+                windows.iter().map(|w| w.in_sample.total_return).sum(),
+                in_sample_time,
+                windows.iter().map(|w| w.out_of_sample.total_return).sum(),
+                out_of_sample_time,
+            ),
+            parameter_stability: parameter_stability(&windows),
+            windows,
+            equity_curve,
+            trades,
+            metrics,
+        })
+    }
+
+    /// In-sample start, in-sample end and out-of-sample end of each window
+    fn windows(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<(DateTime<Utc>, DateTime<Utc>, DateTime<Utc>)> {
+        let mut windows = Vec::new();
+        for step in 0.. {
+            let advance = self.out_of_sample * step;
+            let (in_sample_start, in_sample_end) = match self.mode {
+                WindowMode::Rolling => (start + advance, start + advance + self.in_sample),
+                WindowMode::Anchored => (start, start + self.in_sample + advance),
+            };
+            if in_sample_end >= end {
+                break;
+            }
+            windows.push((
+                in_sample_start,
+                in_sample_end,
+                (in_sample_end + self.out_of_sample).min(end),
+            ));
+        }
+        windows
+    }
+}
+
+impl WalkForwardReport {
+    /// Print a summary of the analysis
+    pub fn print_summary(&self) {
+        println!("\n=== Walk-Forward Analysis ({:?}) ===\n", self.mode);
+
+        for (index, window) in self.windows.iter().enumerate() {
+            println!(
+                "Window {:>3}: IS {} -> {}  OOS -> {}",
+                index + 1,
+                window.in_sample_start.format("%Y-%m-%d %H:%M"),
+                window.in_sample_end.format("%Y-%m-%d %H:%M"),
+                window.out_of_sample_end.format("%Y-%m-%d %H:%M"),
+            );
+            println!(
+                "  IS Return: {:>8.2}%  OOS Return: {:>8.2}%  Efficiency: {}",
+                window.in_sample.total_return,
+                window.out_of_sample.total_return,
+                format_efficiency(window.efficiency),
+            );
+            let parameters: Vec<String> = window
+                .parameters
+                .iter()
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect();
+            println!("  Parameters: {}", parameters.join(", "));
+        }
+
+        println!(
+            "\nWalk-Forward Efficiency: {}",
+            format_efficiency(self.efficiency)
+        );
+
+        println!("\nParameter Stability:");
+        for stability in &self.parameter_stability {
+            println!(
+                "  {:<20} {} distinct, {} in {:.0}% of windows",
+                stability.name,
+                stability.distinct_values,
+                stability.most_common,
+                stability.most_common_share * 100.0,
+            );
+        }
+
+        self.metrics.print_summary();
+    }
+
+    /// Export the report to JSON
+    pub fn to_json(&self) -> BacktestResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Copy of the series restricted to `[start, end)`
+fn window(series: &[CandleSeries], start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<CandleSeries> {
+    series
+        .iter()
+        .map(|series| series.window(start, end))
+        .collect()
+}
+
+fn has_candles(series: &[CandleSeries]) -> bool {
+    series.iter().any(|series| !series.candles.is_empty())
+}
+
+/// Out-of-sample return rate over the in-sample one
+fn efficiency(
+    in_sample_return: f64,
+    in_sample_time: Duration,
+    out_of_sample_return: f64,
+    out_of_sample_time: Duration,
+) -> Option<f64> {
+    let rate =
+        |total_return: f64, time: Duration| total_return / time.num_milliseconds().max(1) as f64;
+    let in_sample_rate = rate(in_sample_return, in_sample_time);
+    (in_sample_rate > 0.0).then(|| rate(out_of_sample_return, out_of_sample_time) / in_sample_rate)
+}
+
+fn parameter_stability(windows: &[WalkForwardWindow]) -> Vec<ParameterStability> {
+    let names: BTreeSet<&String> = windows
+        .iter()
+        .flat_map(|window| window.parameters.iter().map(|(name, _)| name))
+        .collect();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let values: Vec<&Value> = windows
+                .iter()
+                .filter_map(|window| window.parameters.get(name))
+                .collect();
+
+            let mut counts: Vec<(&Value, usize)> = Vec::new();
+            for value in &values {
+                match counts.iter_mut().find(|(seen, _)| seen == value) {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((value, 1)),
+                }
+            }
+            let (most_common, count) =
+                counts
+                    .iter()
+                    .copied()
+                    .fold((&Value::Null, 0), |best, entry| {
+                        if entry.1 > best.1 {
+                            entry
+                        } else {
+                            best
+                        }
+                    });
+
+            let numbers: Option<Vec<f64>> = values.iter().map(|value| value.as_f64()).collect();
+            let (mean, std_dev) = match numbers.filter(|numbers| !numbers.is_empty()) {
+                Some(numbers) => {
+                    let n = numbers.len() as f64;
+                    let mean = numbers.iter().sum::<f64>() / n;
+                    let variance = numbers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+                    (Some(mean), Some(variance.sqrt()))
+                }
+                None => (None, None),
+            };
+
+            ParameterStability {
+                name: name.clone(),
+                distinct_values: counts.len(),
+                most_common: most_common.clone(),
+                most_common_share: count as f64 / values.len().max(1) as f64,
+                mean,
+                std_dev,
+            }
+        })
+        .collect()
+}
+
+fn format_efficiency(efficiency: Option<f64>) -> String {
+    efficiency.map_or_else(
+        || "n/a".to_string(),
+        |efficiency| format!("{efficiency:.2}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BacktestConfig, ExecutionConfig};
+    use crate::optimizer::{Objective, ParameterSpace};
+    use async_trait::async_trait;
+    use velora_core::types::{Candle, Interval, Symbol};
+    use velora_strategy::{Signal, Strategy, StrategyConfig, StrategyContext, StrategyState};
+
+    /// Buys `quantity` once it has seen `lookback` candles and closes three
+    /// candles later
+    struct HoldStrategy {
+        config: StrategyConfig,
+        quantity: f64,
+        lookback: usize,
+        candles_seen: usize,
+    }
+
+    #[async_trait]
+    impl Strategy for HoldStrategy {
+        fn name(&self) -> &str {
+            "Hold"
+        }
+        fn config(&self) -> &StrategyConfig {
+            &self.config
+        }
+        fn state(&self) -> StrategyState {
+            StrategyState::Running
+        }
+
+        async fn on_candle(
+            &mut self,
+            _candle: &Candle,
+            _ctx: &StrategyContext,
+        ) -> velora_strategy::StrategyResult<Signal> {
+            self.candles_seen += 1;
+            Ok(match self.candles_seen.checked_sub(self.lookback) {
+                Some(1) => Signal::buy("BTC", self.quantity),
+                Some(4) => Signal::close("BTC"),
+                _ => Signal::Hold,
+            })
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    fn rising_series(minutes: i64) -> Vec<CandleSeries> {
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let candles = (0..minutes)
+            .map(|i| {
+                let price = 100.0 + i as f64;
+                Candle {
+                    symbol: Symbol::new("BTC"),
+                    timestamp: start + Duration::minutes(i),
+                    open: price.into(),
+                    high: price.into(),
+                    low: price.into(),
+                    close: price.into(),
+                    volume: 100.0.into(),
+                }
+            })
+            .collect();
+        vec![CandleSeries::new("BTC", Interval::Minute1, candles)]
+    }
+
+    fn walk_forward(mode: WindowMode) -> WalkForward {
+        walk_forward_with_lookback(mode, 0).with_warmup(Duration::zero())
+    }
+
+    fn walk_forward_with_lookback(mode: WindowMode, lookback: usize) -> WalkForward {
+        let config = BacktestConfig::new().with_execution(ExecutionConfig::optimistic());
+        let space = ParameterSpace::new().float("quantity", 1.0, 3.0, 3);
+        let optimizer = Optimizer::new(config, space, move |params| {
+            let strategy = HoldStrategy {
+                config: StrategyConfig::new("Hold"),
+                quantity: params.get_f64("quantity").unwrap_or(1.0),
+                lookback,
+                candles_seen: 0,
+            };
+            Ok(Box::new(strategy) as Box<dyn Strategy>)
+        })
+        .with_objective(Objective::TotalPnl)
+        .with_threads(2);
+
+        WalkForward::new(optimizer, Duration::minutes(10), Duration::minutes(5)).with_mode(mode)
+    }
+
+    #[test]
+    fn test_rolling_windows_stitch_out_of_sample() {
+        let report = walk_forward(WindowMode::Rolling)
+            .run(&rising_series(40))
+            .unwrap();
+
+        // In-sample periods end at 10, 15, ..., 35 minutes
+        assert_eq!(report.windows.len(), 6);
+        let first = &report.windows[0];
+        assert_eq!(
+            first.in_sample_end - first.in_sample_start,
+            Duration::minutes(10)
+        );
+        assert_eq!(
+            report.windows[1].in_sample_start - first.in_sample_start,
+            Duration::minutes(5)
+        );
+
+        // Stitched curve only covers out-of-sample periods and compounds
+        assert!(report
+            .equity_curve
+            .iter()
+            .all(|point| point.timestamp > first.in_sample_end));
+        assert!(report
+            .equity_curve
+            .windows(2)
+            .all(|pair| pair[0].timestamp < pair[1].timestamp));
+        let pnl: f64 = report.trades.iter().map(|trade| trade.pnl).sum();
+        assert_eq!(report.trades.len(), 6);
+        assert_eq!(report.equity_curve.last().unwrap().equity, 10_000.0 + pnl);
+        assert_eq!(report.metrics.total_pnl, pnl);
+
+        // The same parameters win every window
+        let stability = &report.parameter_stability[0];
+        assert_eq!(stability.name, "quantity");
+        assert_eq!(stability.distinct_values, 1);
+        assert_eq!(stability.most_common_share, 1.0);
+        assert_eq!(stability.std_dev, Some(0.0));
+        assert!(report.efficiency.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_out_of_sample_runs_are_warmed_up() {
+        // The strategy needs 10 candles before it trades, as many as fit in
+        // the in-sample period, and more than an out-of-sample period has
+        let cold = walk_forward_with_lookback(WindowMode::Rolling, 10)
+            .with_warmup(Duration::zero())
+            .run(&rising_series(40))
+            .unwrap();
+        assert!(cold.trades.is_empty());
+
+        let report = walk_forward_with_lookback(WindowMode::Rolling, 10)
+            .run(&rising_series(40))
+            .unwrap();
+        assert_eq!(report.windows.len(), 6);
+
+        // Warm-up candles only feed indicators: trades and equity points
+        // all fall in out-of-sample periods, starting on their first candle
+        assert_eq!(report.trades.len(), 6);
+        for (window, trade) in report.windows.iter().zip(&report.trades) {
+            assert_eq!(
+                trade.entry_time,
+                window.in_sample_end + Duration::minutes(1)
+            );
+        }
+        let first = &report.windows[0];
+        assert!(report
+            .equity_curve
+            .iter()
+            .all(|point| point.timestamp > first.in_sample_end));
+
+        // Each run starts with the equity the previous one ended with
+        let pnl: f64 = report.trades.iter().map(|trade| trade.pnl).sum();
+        assert!(pnl > 0.0);
+        assert_eq!(report.equity_curve.last().unwrap().equity, 10_000.0 + pnl);
+    }
+
+    #[test]
+    fn test_anchored_windows_grow() {
+        let report = walk_forward(WindowMode::Anchored)
+            .run(&rising_series(40))
+            .unwrap();
+
+        assert_eq!(report.windows.len(), 6);
+        let first = &report.windows[0];
+        let last = report.windows.last().unwrap();
+        assert_eq!(last.in_sample_start, first.in_sample_start);
+        assert_eq!(
+            last.in_sample_end - last.in_sample_start,
+            Duration::minutes(35)
+        );
+    }
+
+    #[test]
+    fn test_history_shorter_than_window() {
+        let result = walk_forward(WindowMode::Rolling).run(&rising_series(8));
+        assert!(matches!(result, Err(BacktestError::InvalidConfig(_))));
+    }
+}
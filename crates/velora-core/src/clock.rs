@@ -0,0 +1,208 @@
+//! Time sources for live trading, simulations and replays.
+//!
+//! Components that stamp orders, fills or snapshots read the time from a
+//! [`Clock`] instead of the system clock, so replaying history through them
+//! produces the historical timestamps and reproducible runs.
+
+use chrono::{DateTime, Duration, Utc};
+use std::fmt;
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// Source of the current time
+pub trait Clock: Send + Sync + fmt::Debug {
+    /// Current time
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Note the timestamp of a market event that was just received
+    ///
+    /// Replay clocks move to it; other clocks ignore it.
+    fn observe(&self, _timestamp: DateTime<Utc>) {}
+
+    /// Whether time passes on its own, so timers can wait for it to pass
+    fn is_realtime(&self) -> bool {
+        false
+    }
+}
+
+/// Clock shared by the components of an engine
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Wall-clock time, for live and paper trading
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LiveClock;
+
+impl LiveClock {
+    /// Wall clock behind a shared handle
+    pub fn shared() -> SharedClock {
+        Arc::new(Self)
+    }
+}
+
+impl Clock for LiveClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn is_realtime(&self) -> bool {
+        true
+    }
+}
+
+/// Clock that only moves when told to
+///
+/// Useful for tests and simulations that step time themselves.
+#[derive(Debug)]
+pub struct SimulatedClock {
+    now: RwLock<DateTime<Utc>>,
+}
+
+impl SimulatedClock {
+    /// Create a clock standing at `start`
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            now: RwLock::new(start),
+        }
+    }
+
+    /// Set the current time (may move backwards)
+    pub fn set(&self, time: DateTime<Utc>) {
+        *self.now.write().unwrap_or_else(PoisonError::into_inner) = time;
+    }
+
+    /// Move the clock forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.write().unwrap_or_else(PoisonError::into_inner);
+        *now += duration;
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.read().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Clock following the timestamps of replayed market events
+///
+/// Stands at the latest event observed and never moves backwards, so an
+/// out-of-order event does not rewind time.
+#[derive(Debug)]
+pub struct ReplayClock {
+    now: RwLock<DateTime<Utc>>,
+}
+
+impl ReplayClock {
+    /// Create a clock standing at `start`, usually the first event's timestamp
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            now: RwLock::new(start),
+        }
+    }
+}
+
+impl Clock for ReplayClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn observe(&self, timestamp: DateTime<Utc>) {
+        let mut now = self.now.write().unwrap_or_else(PoisonError::into_inner);
+        if timestamp > *now {
+            *now = timestamp;
+        }
+    }
+}
+
+/// Periodic deadline measured on a clock's time
+///
+/// Engines driven by event timestamps poll it after each event to fire
+/// timers on simulated time.
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    period: Duration,
+    next: Option<DateTime<Utc>>,
+}
+
+impl Schedule {
+    /// Create a schedule firing every `period`
+    pub fn every(period: Duration) -> Self {
+        Self { period, next: None }
+    }
+
+    /// Period between deadlines
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Whether a deadline has passed at `now`, moving to the next one if so
+    ///
+    /// The first call is always due. Deadlines missed between two calls are
+    /// skipped rather than reported one by one.
+    pub fn is_due(&mut self, now: DateTime<Utc>) -> bool {
+        let due = *self.next.get_or_insert(now);
+        if now < due {
+            return false;
+        }
+
+        let period = self.period.num_milliseconds().max(1);
+        let missed = (now - due).num_milliseconds() / period;
+        self.next = Some(due + Duration::milliseconds((missed + 1) * period));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_live_clock() {
+        let before = Utc::now();
+        let clock = LiveClock;
+        clock.observe(start());
+        assert!(clock.now() >= before);
+        assert!(clock.is_realtime());
+    }
+
+    #[test]
+    fn test_simulated_clock() {
+        let clock = SimulatedClock::new(start());
+        clock.observe(start() + Duration::hours(1));
+        assert_eq!(clock.now(), start());
+
+        clock.advance(Duration::seconds(30));
+        assert_eq!(clock.now(), start() + Duration::seconds(30));
+
+        clock.set(start() - Duration::days(1));
+        assert_eq!(clock.now(), start() - Duration::days(1));
+        assert!(!clock.is_realtime());
+    }
+
+    #[test]
+    fn test_schedule_skips_missed_deadlines() {
+        let mut schedule = Schedule::every(Duration::seconds(10));
+        assert!(schedule.is_due(start()));
+        assert!(!schedule.is_due(start() + Duration::seconds(9)));
+        assert!(schedule.is_due(start() + Duration::seconds(10)));
+
+        // Five deadlines passed at once fire a single time
+        assert!(schedule.is_due(start() + Duration::seconds(65)));
+        assert!(!schedule.is_due(start() + Duration::seconds(69)));
+        assert!(schedule.is_due(start() + Duration::seconds(70)));
+    }
+
+    #[test]
+    fn test_replay_clock_never_rewinds() {
+        let clock: SharedClock = Arc::new(ReplayClock::new(start()));
+        clock.observe(start() + Duration::minutes(5));
+        assert_eq!(clock.now(), start() + Duration::minutes(5));
+
+        clock.observe(start() + Duration::minutes(2));
+        assert_eq!(clock.now(), start() + Duration::minutes(5));
+    }
+}
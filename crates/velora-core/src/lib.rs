@@ -13,6 +13,7 @@
 //! - **Market data structures**: Ticks, candles, order books
 //! - **Error handling**: Unified error type with conversions
 //! - **Configuration management**: TOML-based configuration
+//! - **Clocks**: Live, simulated and replay time sources for reproducible runs
 //!
 //! ## Example
 //!
@@ -35,11 +36,13 @@
 #![warn(missing_debug_implementations)]
 #![warn(rust_2018_idioms)]
 
+pub mod clock;
 pub mod config;
 pub mod errors;
 pub mod types;
 
 // Re-export commonly used types for convenience
+pub use clock::{Clock, LiveClock, ReplayClock, Schedule, SharedClock, SimulatedClock};
 pub use config::*;
 pub use errors::{Result, VeloraError};
 pub use types::*;
@@ -161,7 +161,11 @@ pub struct Order {
 impl Order {
     /// Creates a new market order.
     pub fn new_market(symbol: Symbol, side: Side, quantity: Volume) -> Self {
-        let now = Utc::now();
+        Self::new_market_at(symbol, side, quantity, Utc::now())
+    }
+
+    /// Creates a new market order at a time, such as a [`Clock`](crate::Clock) reading.
+    pub fn new_market_at(symbol: Symbol, side: Side, quantity: Volume, now: DateTime<Utc>) -> Self {
         Order {
             id: Uuid::new_v4(),
             symbol,
@@ -178,7 +182,17 @@ impl Order {
 
     /// Creates a new limit order.
     pub fn new_limit(symbol: Symbol, side: Side, price: Price, quantity: Volume) -> Self {
-        let now = Utc::now();
+        Self::new_limit_at(symbol, side, price, quantity, Utc::now())
+    }
+
+    /// Creates a new limit order at a time, such as a [`Clock`](crate::Clock) reading.
+    pub fn new_limit_at(
+        symbol: Symbol,
+        side: Side,
+        price: Price,
+        quantity: Volume,
+        now: DateTime<Utc>,
+    ) -> Self {
         Order {
             id: Uuid::new_v4(),
             symbol,
@@ -496,6 +510,29 @@ mod tests {
         assert!(order.is_active());
     }
 
+    #[test]
+    fn test_order_stamped_by_clock() {
+        let clock = crate::SimulatedClock::new(DateTime::UNIX_EPOCH);
+        let order = Order::new_market_at(
+            Symbol::new("BTC/USD"),
+            Side::Buy,
+            OrderedFloat(0.1),
+            crate::Clock::now(&clock),
+        );
+        assert_eq!(order.timestamp, DateTime::UNIX_EPOCH);
+        assert_eq!(order.updated_at, DateTime::UNIX_EPOCH);
+
+        let order = Order::new_limit_at(
+            Symbol::new("BTC/USD"),
+            Side::Sell,
+            OrderedFloat(50000.0),
+            OrderedFloat(0.1),
+            crate::Clock::now(&clock),
+        );
+        assert_eq!(order.timestamp, DateTime::UNIX_EPOCH);
+        assert_eq!(order.updated_at, DateTime::UNIX_EPOCH);
+    }
+
     #[test]
     fn test_limit_order_creation() {
         let order = Order::new_limit(
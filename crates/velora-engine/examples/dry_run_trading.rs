@@ -74,6 +74,9 @@ impl Strategy for TestStrategy {
                 quantity: 0.01,
                 limit_price: None,
                 stop_price: None,
+                stop_loss: None,
+                take_profit: None,
+                trailing_stop: None,
                 metadata: std::collections::HashMap::new(),
             });
         }
@@ -90,6 +93,9 @@ impl Strategy for TestStrategy {
                 quantity: 0.01,
                 limit_price: None,
                 stop_price: None,
+                stop_loss: None,
+                take_profit: None,
+                trailing_stop: None,
                 metadata: std::collections::HashMap::new(),
             });
         }
@@ -1,6 +1,8 @@
 //! Configuration types for the trading engine
 
+use crate::journal::JournalConfig;
 use serde::{Deserialize, Serialize};
+use velora_core::Interval;
 
 /// Execution mode for the trading engine
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -20,6 +22,9 @@ pub struct EngineConfig {
     /// Symbols to trade
     pub symbols: Vec<String>,
 
+    /// Candle interval subscribed to on the exchange
+    pub candle_interval: Interval,
+
     /// Initial capital for trading
     pub initial_capital: f64,
 
@@ -29,6 +34,11 @@ pub struct EngineConfig {
     /// Heartbeat interval in milliseconds
     pub heartbeat_interval_ms: u64,
 
+    /// Interval between `Strategy::on_timer` calls in milliseconds, on the
+    /// engine clock (None disables the timer)
+    #[serde(default = "default_timer_interval_ms")]
+    pub timer_interval_ms: Option<u64>,
+
     /// Delay before attempting reconnection (ms)
     pub reconnect_delay_ms: u64,
 
@@ -40,6 +50,11 @@ pub struct EngineConfig {
 
     /// Metrics configuration
     pub metrics: MetricsConfig,
+
+    /// Write-ahead journal the engine recovers its state from on start
+    /// (None keeps state in memory only)
+    #[serde(default)]
+    pub journal: Option<JournalConfig>,
 }
 
 /// Metrics configuration
@@ -60,17 +75,25 @@ impl Default for EngineConfig {
         Self {
             mode: ExecutionMode::DryRun,
             symbols: Vec::new(),
+            candle_interval: Interval::Minute1,
             initial_capital: 10_000.0,
             max_orders_per_second: 5,
             heartbeat_interval_ms: 1000,
+            timer_interval_ms: default_timer_interval_ms(),
             reconnect_delay_ms: 5000,
             max_reconnect_attempts: 10,
             enable_risk_checks: true,
             metrics: MetricsConfig::default(),
+            journal: None,
         }
     }
 }
 
+/// Strategy timer fires every second unless configured otherwise
+fn default_timer_interval_ms() -> Option<u64> {
+    Some(1000)
+}
+
 impl Default for MetricsConfig {
     fn default() -> Self {
         Self {
@@ -113,6 +136,12 @@ impl EngineConfigBuilder {
         self
     }
 
+    /// Set the candle interval subscribed to on the exchange
+    pub fn candle_interval(mut self, interval: Interval) -> Self {
+        self.config.candle_interval = interval;
+        self
+    }
+
     /// Set initial capital
     pub fn initial_capital(mut self, capital: f64) -> Self {
         self.config.initial_capital = capital;
@@ -131,6 +160,12 @@ impl EngineConfigBuilder {
         self
     }
 
+    /// Set the strategy timer interval (None disables `Strategy::on_timer`)
+    pub fn timer_interval_ms(mut self, ms: Option<u64>) -> Self {
+        self.config.timer_interval_ms = ms;
+        self
+    }
+
     /// Set reconnect delay
     pub fn reconnect_delay_ms(mut self, ms: u64) -> Self {
         self.config.reconnect_delay_ms = ms;
@@ -155,6 +190,12 @@ impl EngineConfigBuilder {
         self
     }
 
+    /// Journal orders, fills and positions to recover them after a restart
+    pub fn journal(mut self, journal: JournalConfig) -> Self {
+        self.config.journal = Some(journal);
+        self
+    }
+
     /// Build the configuration
     pub fn build(self) -> EngineConfig {
         self.config
@@ -170,8 +211,10 @@ mod tests {
         let config = EngineConfig::default();
 
         assert_eq!(config.mode, ExecutionMode::DryRun);
+        assert_eq!(config.candle_interval, Interval::Minute1);
         assert_eq!(config.initial_capital, 10_000.0);
         assert_eq!(config.max_orders_per_second, 5);
+        assert_eq!(config.timer_interval_ms, Some(1000));
         assert!(config.enable_risk_checks);
     }
 
@@ -183,6 +226,7 @@ mod tests {
             .add_symbol("ETH-USD-PERP".to_string())
             .initial_capital(50_000.0)
             .max_orders_per_second(10)
+            .timer_interval_ms(None)
             .enable_risk_checks(false)
             .build();
 
@@ -190,6 +234,7 @@ mod tests {
         assert_eq!(config.symbols.len(), 2);
         assert_eq!(config.initial_capital, 50_000.0);
         assert_eq!(config.max_orders_per_second, 10);
+        assert_eq!(config.timer_interval_ms, None);
         assert!(!config.enable_risk_checks);
     }
 
@@ -1,20 +1,30 @@
 //! Main trading engine orchestration
 
-use crate::config::EngineConfig;
+use crate::config::{EngineConfig, ExecutionMode};
 use crate::errors::{EngineError, EngineResult};
-use crate::events::{Fill, MarketEvent, OrderStatus};
+use crate::events::{Fill, MarketEvent, OrderId, OrderStatus, OrderUpdate};
 use crate::execution::ExecutionHandler;
+use crate::feeds::{Feed, ReconnectPolicy};
+use crate::journal::{Journal, JournalRecord, JournalSnapshot};
 use crate::order_manager::{Order, OrderManager};
 use crate::position_tracker::{EquitySnapshot, PositionTracker};
 use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::broadcast;
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
 use tokio::time::interval;
 use tracing::{debug, info, warn};
-use velora_core::{Candle, OrderType};
-use velora_strategy::{Signal, Strategy, StrategyContext};
+use velora_core::{Candle, LiveClock, OrderBook, OrderType, Schedule, SharedClock, Tick, Trade};
+use velora_exchange::{Exchange, FeeModel, FundingRate};
+use velora_risk::{
+    OrderRequest, PortfolioState, PositionSizer, RiskDecision, RiskError, RiskManager,
+};
+use velora_strategy::{PositionSide, Signal, Strategy, StrategyContext};
 
 /// Main trading engine
 pub struct TradingEngine {
@@ -36,9 +46,21 @@ pub struct TradingEngine {
     /// Strategy context
     context: StrategyContext,
 
+    /// Pre-trade risk checks (used when `enable_risk_checks` is set)
+    risk_manager: Option<RiskManager>,
+
+    /// Sizes Buy/Sell signals that carry no quantity
+    position_sizer: Option<Box<dyn PositionSizer>>,
+
+    /// Stop-loss/take-profit orders managed for open positions, by symbol
+    brackets: HashMap<String, Bracket>,
+
     /// Market event channel sender (for injecting events in examples)
     market_tx: Option<UnboundedSender<MarketEvent>>,
 
+    /// Tasks forwarding exchange streams into the market channel
+    feed_handles: Vec<JoinHandle<()>>,
+
     /// Shutdown channel
     shutdown_tx: Option<broadcast::Sender<()>>,
 
@@ -47,6 +69,102 @@ pub struct TradingEngine {
 
     /// Engine start time
     start_time: Option<DateTime<Utc>>,
+
+    /// Time source shared with the engine's components
+    clock: SharedClock,
+
+    /// Heartbeat deadlines on a clock that is not realtime
+    heartbeat: Schedule,
+
+    /// Strategy timer deadlines on a clock that is not realtime
+    timer: Option<Schedule>,
+
+    /// Whether orders changed since the strategy was last notified
+    orders_changed: bool,
+
+    /// Fills processed so far
+    fills: Vec<Fill>,
+
+    /// Write-ahead journal, open while the engine runs with one configured
+    journal: Option<Journal>,
+
+    /// Order audit events already written to the journal
+    journaled_events: usize,
+}
+
+/// Exit orders the engine maintains for a position
+///
+/// Attached by an entry signal or set through `Signal::Modify`; the orders
+/// are resized with the position, replaced when their level moves and
+/// cancelled once the position is flat. Journaled so a restarted engine
+/// keeps managing them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bracket {
+    /// Side of the position the exits protect
+    pub side: PositionSide,
+
+    /// Stop-loss trigger price
+    pub stop_loss: Option<f64>,
+
+    /// Take-profit limit price
+    pub take_profit: Option<f64>,
+
+    /// Trailing stop distance
+    pub trailing_stop: Option<f64>,
+
+    /// Best price seen since the position was protected
+    pub extreme: Option<f64>,
+
+    /// Entry order the exits wait for
+    pub entry: Option<OrderId>,
+
+    /// Working stop-loss order
+    pub stop_order: Option<OrderId>,
+
+    /// Working take-profit order
+    pub target_order: Option<OrderId>,
+}
+
+impl Bracket {
+    fn new(side: PositionSide) -> Self {
+        Self {
+            side,
+            stop_loss: None,
+            take_profit: None,
+            trailing_stop: None,
+            extreme: None,
+            entry: None,
+            stop_order: None,
+            target_order: None,
+        }
+    }
+
+    /// Effective stop level, the tighter of the fixed and trailing stops
+    fn stop_level(&self) -> Option<f64> {
+        let trailing = self
+            .trailing_stop
+            .zip(self.extreme)
+            .map(|(distance, extreme)| match self.side {
+                PositionSide::Long => extreme - distance,
+                PositionSide::Short => extreme + distance,
+            });
+
+        match (self.stop_loss, trailing) {
+            (Some(fixed), Some(trailing)) => Some(match self.side {
+                PositionSide::Long => fixed.max(trailing),
+                PositionSide::Short => fixed.min(trailing),
+            }),
+            (fixed, trailing) => fixed.or(trailing),
+        }
+    }
+
+    /// Move the trailing reference to a new best price
+    fn ratchet(&mut self, high: f64, low: f64) {
+        self.extreme = self.extreme.map(|extreme| match self.side {
+            PositionSide::Long => extreme.max(high),
+            PositionSide::Short => extreme.min(low),
+        });
+    }
 }
 
 /// Engine state
@@ -109,6 +227,12 @@ impl TradingEngine {
         let position_tracker = PositionTracker::new(config.initial_capital);
         let execution_handler = ExecutionHandler::new(config.mode);
         let context = StrategyContext::new(config.initial_capital);
+        let heartbeat = Schedule::every(chrono::Duration::milliseconds(
+            config.heartbeat_interval_ms as i64,
+        ));
+        let timer = config
+            .timer_interval_ms
+            .map(|ms| Schedule::every(chrono::Duration::milliseconds(ms as i64)));
 
         Self {
             config,
@@ -117,19 +241,89 @@ impl TradingEngine {
             position_tracker,
             execution_handler,
             context,
+            risk_manager: None,
+            position_sizer: None,
+            brackets: HashMap::new(),
             market_tx: None,
+            feed_handles: Vec::new(),
             shutdown_tx: None,
             state: EngineState::Idle,
             start_time: None,
+            clock: LiveClock::shared(),
+            heartbeat,
+            timer,
+            orders_changed: false,
+            fills: Vec::new(),
+            journal: None,
+            journaled_events: 0,
         }
     }
 
+    /// Read the time from a clock instead of the wall clock
+    ///
+    /// Orders, fills, equity snapshots and the strategy context all use it.
+    /// A clock that is not realtime (see `Clock::is_realtime`) is moved by
+    /// the timestamps of the market events the engine processes, and the
+    /// heartbeat and strategy timer fire on its time instead of a wall-clock
+    /// interval, so a replay of stored history stamps everything with
+    /// historical time.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.order_manager = self.order_manager.with_clock(clock.clone());
+        self.position_tracker = self.position_tracker.with_clock(clock.clone());
+        self.execution_handler = self.execution_handler.with_clock(clock.clone());
+        self.context = self.context.with_clock(clock.clone());
+        self.clock = clock;
+        self
+    }
+
+    /// Clock the engine reads the time from
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
     /// Attach a trading strategy
     pub fn with_strategy(mut self, strategy: Box<dyn Strategy>) -> Self {
         self.strategy = Some(strategy);
         self
     }
 
+    /// Attach a risk manager
+    ///
+    /// Orders are checked against it when `EngineConfig::enable_risk_checks` is set.
+    pub fn with_risk_manager(mut self, risk_manager: RiskManager) -> Self {
+        self.risk_manager = Some(risk_manager);
+        self
+    }
+
+    /// Attach a position sizer
+    ///
+    /// Unsized Buy/Sell signals get their quantity from it before risk checks run.
+    /// Without one, the engine sizes them as the strategy's configuration
+    /// describes (see [`velora_risk::sizing::from_strategy_config`]).
+    pub fn with_position_sizer(mut self, sizer: Box<dyn PositionSizer>) -> Self {
+        self.position_sizer = Some(sizer);
+        self
+    }
+
+    /// Attach an exchange
+    ///
+    /// `start` streams candles, trades and (in live mode) order updates from
+    /// it for every configured symbol. Orders are routed to it in live mode;
+    /// dry-run keeps simulating fills on the exchange's market data.
+    pub fn with_exchange(mut self, exchange: Box<dyn Exchange>) -> Self {
+        self.execution_handler = self.execution_handler.with_exchange(exchange);
+        self
+    }
+
+    /// Charge dry-run fills by an exchange fee schedule
+    ///
+    /// Build it from the exchange's markets with `FeeModel::from_market`, so
+    /// paper trading pays the same maker/taker rates as the backtester.
+    pub fn with_fee_model(mut self, fees: FeeModel) -> Self {
+        self.execution_handler = self.execution_handler.with_fee_model(fees);
+        self
+    }
+
     /// Start the trading engine with an external market event receiver
     /// This is useful for examples and testing where you want to control the event flow
     pub async fn start_with_receiver(
@@ -147,9 +341,11 @@ impl TradingEngine {
         }
 
         info!("Starting trading engine in {:?} mode", self.config.mode);
+        self.init_position_sizer()?;
+        self.recover_journal()?;
 
         self.state = EngineState::Running;
-        self.start_time = Some(Utc::now());
+        self.start_time = Some(self.clock.now());
 
         let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
         self.shutdown_tx = Some(shutdown_tx);
@@ -161,7 +357,9 @@ impl TradingEngine {
     }
 
     /// Start the trading engine
-    /// In production, you would connect to exchange websocket feeds here
+    ///
+    /// With an exchange attached, the engine connects to it and runs until
+    /// every exchange feed has ended (or it is stopped).
     pub async fn start(&mut self) -> EngineResult<()> {
         if self.state != EngineState::Idle {
             return Err(EngineError::AlreadyRunning);
@@ -173,25 +371,82 @@ impl TradingEngine {
             ));
         }
 
-        info!("Starting trading engine in {:?} mode", self.config.mode);
+        let has_exchange = self.execution_handler.exchange().is_some();
+        if self.config.mode == ExecutionMode::Live && !has_exchange {
+            return Err(EngineError::InvalidConfig(
+                "Live mode requires an exchange".to_string(),
+            ));
+        }
+        if has_exchange && self.config.symbols.is_empty() {
+            return Err(EngineError::InvalidConfig(
+                "No symbols configured".to_string(),
+            ));
+        }
 
-        self.state = EngineState::Running;
-        self.start_time = Some(Utc::now());
+        info!("Starting trading engine in {:?} mode", self.config.mode);
+        self.init_position_sizer()?;
+        self.recover_journal()?;
 
         // Create channels
         let (market_tx, market_rx) = mpsc::unbounded_channel();
         let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
 
-        self.market_tx = Some(market_tx);
+        if has_exchange {
+            self.execution_handler.connect().await?;
+            if self.config.mode == ExecutionMode::Live && self.journal.is_some() {
+                self.reconcile_orders().await?;
+            }
+            // Feeds own the only senders, so the loop ends with them
+            self.spawn_feeds(market_tx);
+        } else {
+            self.market_tx = Some(market_tx);
+        }
         self.shutdown_tx = Some(shutdown_tx);
 
-        // TODO: In production, spawn tasks to connect to exchange feeds
-        // and send events to market_tx
+        self.state = EngineState::Running;
+        self.start_time = Some(self.clock.now());
 
         // Run event loop
-        self.run_event_loop(market_rx, shutdown_rx).await?;
+        let result = self.run_event_loop(market_rx, shutdown_rx).await;
+        self.shutdown_feeds().await?;
 
-        Ok(())
+        result
+    }
+
+    /// Spawn the exchange feeds for the configured symbols
+    fn spawn_feeds(&mut self, market_tx: UnboundedSender<MarketEvent>) {
+        let Some(exchange) = self.execution_handler.exchange().cloned() else {
+            return;
+        };
+
+        let policy = ReconnectPolicy {
+            delay: Duration::from_millis(self.config.reconnect_delay_ms),
+            max_attempts: self.config.max_reconnect_attempts,
+        };
+
+        let mut feeds = Vec::new();
+        for symbol in &self.config.symbols {
+            feeds.push(Feed::Candles(symbol.clone(), self.config.candle_interval));
+            feeds.push(Feed::Trades(symbol.clone()));
+        }
+        if self.config.mode == ExecutionMode::Live {
+            feeds.push(Feed::Orders);
+        }
+
+        info!("Streaming {} feeds from {}", feeds.len(), exchange.name());
+        self.feed_handles = feeds
+            .into_iter()
+            .map(|feed| feed.spawn(Arc::clone(&exchange), market_tx.clone(), policy))
+            .collect();
+    }
+
+    /// Stop the exchange feeds and disconnect from the exchange
+    async fn shutdown_feeds(&mut self) -> EngineResult<()> {
+        for handle in self.feed_handles.drain(..) {
+            handle.abort();
+            let _ = handle.await;
+        }
+        self.execution_handler.disconnect().await
     }
 
     /// Stop the trading engine
@@ -209,6 +464,7 @@ impl TradingEngine {
             info!("Cancelling order: {}", order.id);
             self.execution_handler.cancel_order(order.id).await?;
         }
+        self.process_execution().await?;
 
         // Send shutdown signal
         if let Some(ref shutdown_tx) = self.shutdown_tx {
@@ -223,9 +479,10 @@ impl TradingEngine {
 
     /// Get current engine status
     pub fn status(&self) -> EngineStatus {
+        let now = self.clock.now();
         let uptime_secs = self
             .start_time
-            .map(|start| (Utc::now() - start).num_seconds())
+            .map(|start| (now - start).num_seconds())
             .unwrap_or(0);
 
         EngineStatus {
@@ -233,12 +490,12 @@ impl TradingEngine {
             uptime_secs,
             total_orders: self.order_manager.total_orders(),
             active_orders: self.order_manager.get_active_orders().len(),
-            total_fills: 0, // TODO: Track fills
+            total_fills: self.fills.len(),
             open_positions: self.position_tracker.position_count(),
             current_equity: self.position_tracker.total_equity(),
             unrealized_pnl: self.position_tracker.total_unrealized_pnl(),
             realized_pnl: self.position_tracker.total_realized_pnl(),
-            last_update: Utc::now(),
+            last_update: now,
         }
     }
 
@@ -247,6 +504,11 @@ impl TradingEngine {
         self.position_tracker.get_equity_history()
     }
 
+    /// Fills processed so far, in the order they were applied
+    pub fn fills(&self) -> &[Fill] {
+        &self.fills
+    }
+
     /// Main event loop
     async fn run_event_loop(
         &mut self,
@@ -254,22 +516,67 @@ impl TradingEngine {
         mut shutdown_rx: broadcast::Receiver<()>,
     ) -> EngineResult<()> {
         let mut heartbeat = interval(Duration::from_millis(self.config.heartbeat_interval_ms));
+        let mut timer = interval(Duration::from_millis(
+            self.config.timer_interval_ms.unwrap_or(1).max(1),
+        ));
+        let realtime = self.clock.is_realtime();
+        let timed = self.timer.is_some();
+
+        let strategy = self
+            .strategy
+            .as_mut()
+            .ok_or_else(|| EngineError::InvalidConfig("No strategy".to_string()))?;
+        strategy.initialize(&self.context).await?;
 
         loop {
             tokio::select! {
                 // Process market events
                 event = market_rx.recv() => {
+                    if let Some(timestamp) = event.as_ref().and_then(|event| self.event_time(event)) {
+                        self.clock.observe(timestamp);
+                    }
+
                     match event {
                         Some(MarketEvent::Candle(candle)) => {
                             if let Err(e) = self.process_candle(candle).await {
                                 warn!("Error processing candle: {}", e);
                             }
                         }
+                        Some(MarketEvent::Tick(tick)) => {
+                            if let Err(e) = self.process_tick(tick).await {
+                                warn!("Error processing tick: {}", e);
+                            }
+                        }
+                        Some(MarketEvent::Trade(trade)) => {
+                            if let Err(e) = self.process_trade(trade).await {
+                                warn!("Error processing trade: {}", e);
+                            }
+                        }
+                        Some(MarketEvent::OrderBook(book)) => {
+                            if let Err(e) = self.process_order_book(book).await {
+                                warn!("Error processing order book: {}", e);
+                            }
+                        }
+                        Some(MarketEvent::Funding(funding)) => {
+                            if let Err(e) = self.process_funding(&funding) {
+                                warn!("Error processing funding: {}", e);
+                            }
+                        }
                         Some(MarketEvent::OrderUpdate(update)) => {
-                            if let Err(e) = self.order_manager.update_order(update.order_id, update) {
+                            if let Err(e) = self.apply_order_update(update) {
                                 warn!("Error updating order: {}", e);
                             }
                         }
+                        Some(MarketEvent::ExchangeOrderUpdate(order)) => {
+                            if let Some(update) = self.execution_handler.apply_exchange_order(&order) {
+                                if let Err(e) = self.apply_order_update(update) {
+                                    warn!("Error updating order: {}", e);
+                                }
+                            }
+                            if let Err(e) = self.process_execution().await {
+                                warn!("Error processing fills: {}", e);
+                            }
+                        }
                         Some(MarketEvent::Error(msg)) => {
                             warn!("Market error: {}", msg);
                         }
@@ -284,14 +591,30 @@ impl TradingEngine {
                             break;
                         }
                     }
+
+                    if let Err(e) = self.notify_order_changes().await {
+                        warn!("Error processing order update: {}", e);
+                    }
+                    if let Err(e) = self.compact_journal() {
+                        warn!("Error compacting journal: {}", e);
+                    }
+                    if !realtime {
+                        self.heartbeat_on_clock().await?;
+                        self.timer_on_clock().await;
+                    }
                 }
 
-                // Heartbeat tick
-                _ = heartbeat.tick() => {
+                // Heartbeat tick (clocks that are not realtime beat on events)
+                _ = heartbeat.tick(), if realtime => {
                     debug!("Heartbeat");
                     self.on_heartbeat().await?;
                 }
 
+                // Strategy timer (clocks that are not realtime fire it on events)
+                _ = timer.tick(), if realtime && timed => {
+                    self.on_timer().await;
+                }
+
                 // Shutdown signal
                 _ = shutdown_rx.recv() => {
                     info!("Received shutdown signal");
@@ -300,12 +623,113 @@ impl TradingEngine {
             }
         }
 
+        if let Some(strategy) = self.strategy.as_mut() {
+            strategy.shutdown(&self.context).await?;
+        }
+
         self.state = EngineState::Stopped;
         info!("Event loop stopped");
 
         Ok(())
     }
 
+    /// Time a market event happened, by which it moves a replay clock
+    ///
+    /// Candles are stamped with their open time but become known when they
+    /// close, one candle interval later.
+    fn event_time(&self, event: &MarketEvent) -> Option<DateTime<Utc>> {
+        match event {
+            MarketEvent::Candle(candle) => Some(
+                candle.timestamp
+                    + chrono::Duration::seconds(self.config.candle_interval.to_seconds()),
+            ),
+            MarketEvent::Tick(tick) => Some(tick.timestamp),
+            MarketEvent::Trade(trade) => Some(trade.timestamp),
+            MarketEvent::OrderBook(book) => Some(book.timestamp),
+            MarketEvent::Funding(funding) => Some(funding.timestamp),
+            MarketEvent::OrderUpdate(update) => Some(update.timestamp),
+            MarketEvent::ExchangeOrderUpdate(order) => Some(order.updated_at),
+            MarketEvent::Error(_) | MarketEvent::Disconnected | MarketEvent::Reconnected => None,
+        }
+    }
+
+    /// Fire the heartbeat once the clock has reached it
+    ///
+    /// Used with clocks that are not realtime. Beats missed between two
+    /// events are skipped rather than fired in a burst.
+    async fn heartbeat_on_clock(&mut self) -> EngineResult<()> {
+        let now = self.clock.now();
+        if !self.heartbeat.is_due(now) {
+            return Ok(());
+        }
+
+        debug!("Heartbeat at {}", now);
+        self.on_heartbeat().await
+    }
+
+    /// Fire the strategy timer once the clock has reached it
+    ///
+    /// Used with clocks that are not realtime, skipping missed deadlines
+    /// like the heartbeat.
+    async fn timer_on_clock(&mut self) {
+        let now = self.clock.now();
+        if self.timer.as_mut().is_some_and(|timer| timer.is_due(now)) {
+            self.on_timer().await;
+        }
+    }
+
+    /// Call the strategy timer and execute its signal
+    async fn on_timer(&mut self) {
+        let result = match self.strategy.as_mut() {
+            Some(strategy) => strategy.on_timer(&self.context).await,
+            None => return,
+        };
+
+        let result = match result {
+            Ok(signal) => self.dispatch(signal).await,
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = result {
+            warn!("Error processing timer: {}", e);
+        }
+    }
+
+    /// Tell the strategy that orders changed since the last event
+    ///
+    /// Called at most once per event; changes made by the signal it returns
+    /// are reported after the next one.
+    async fn notify_order_changes(&mut self) -> EngineResult<()> {
+        if !std::mem::take(&mut self.orders_changed) {
+            return Ok(());
+        }
+
+        let strategy = self
+            .strategy
+            .as_mut()
+            .ok_or_else(|| EngineError::InvalidConfig("No strategy".to_string()))?;
+        let signal = strategy.on_order_update(&self.context).await?;
+        self.dispatch(signal).await
+    }
+
+    /// Apply an order status update and journal the order's new state
+    fn apply_order_update(&mut self, update: OrderUpdate) -> EngineResult<()> {
+        let order_id = update.order_id;
+        self.order_manager.update_order(order_id, update)?;
+        self.orders_changed = true;
+        self.journal_order(order_id)
+    }
+
+    /// Execute a signal returned by a strategy callback, if actionable
+    ///
+    /// Every callback goes through here, so signals are sized, risk checked
+    /// and placed the same way whatever produced them.
+    async fn dispatch(&mut self, signal: Signal) -> EngineResult<()> {
+        if signal.is_actionable() {
+            self.execute_signal(signal).await?;
+        }
+        Ok(())
+    }
+
     /// Process a new candle
     async fn process_candle(&mut self, candle: Candle) -> EngineResult<()> {
         debug!("Processing candle for {}", candle.symbol);
@@ -314,6 +738,13 @@ impl TradingEngine {
         self.position_tracker
             .update_position_price(candle.symbol.as_str(), candle.close.into_inner());
 
+        // Keep circuit breakers current even when no orders are placed
+        if let Some(risk_manager) = self.risk_manager.as_mut() {
+            let equity = self.position_tracker.available_cash()
+                + self.position_tracker.total_unrealized_pnl();
+            risk_manager.update_equity(equity, candle.timestamp);
+        }
+
         // Update strategy context with market snapshot
         let snapshot = velora_strategy::MarketSnapshot {
             last_price: candle.close.into_inner(),
@@ -324,12 +755,20 @@ impl TradingEngine {
         };
         self.context
             .update_market_snapshot(candle.symbol.as_str(), snapshot)?;
-
-        // Process any fills from execution handler
-        let fills = self.execution_handler.drain_fills();
-        for fill in fills {
-            self.process_fill(fill).await?;
-        }
+        self.context
+            .add_candle(candle.symbol.as_str(), candle.clone())?;
+
+        // Fill resting dry-run orders the candle traded through
+        self.execution_handler.on_candle(&candle);
+        self.process_execution().await?;
+        self.trail_stop(
+            candle.symbol.as_str(),
+            candle.high.into_inner(),
+            candle.low.into_inner(),
+        )
+        .await?;
+        self.context
+            .update_capital(self.position_tracker.available_cash())?;
 
         // Call strategy
         let strategy = self
@@ -338,54 +777,109 @@ impl TradingEngine {
             .ok_or_else(|| EngineError::InvalidConfig("No strategy".to_string()))?;
 
         let signal = strategy.on_candle(&candle, &self.context).await?;
-
-        // Execute signal if actionable
-        if signal.is_actionable() {
-            self.execute_signal(signal, &candle).await?;
-        }
-
-        Ok(())
+        self.dispatch(signal).await
     }
 
     /// Execute a trading signal
-    async fn execute_signal(&mut self, signal: Signal, candle: &Candle) -> EngineResult<()> {
+    ///
+    /// Buy/Sell signals without a limit price are placed at the symbol's
+    /// last price.
+    async fn execute_signal(&mut self, signal: Signal) -> EngineResult<()> {
+        let Some(signal) = self.size_signal(signal)? else {
+            return Ok(());
+        };
+
         match signal {
             Signal::Buy {
                 symbol,
                 quantity,
                 limit_price,
                 stop_price,
+                stop_loss,
+                take_profit,
+                trailing_stop,
                 ..
             } => {
-                self.place_order(
-                    symbol,
-                    velora_core::Side::Buy,
-                    quantity,
-                    limit_price.or(Some(candle.close.into_inner())),
-                    stop_price,
-                )
-                .await?;
+                let order_id = self
+                    .place_order(
+                        symbol.clone(),
+                        velora_core::Side::Buy,
+                        quantity,
+                        Some(self.order_price(&symbol, limit_price)?),
+                        stop_price,
+                        false,
+                    )
+                    .await?;
+                let mut exits = Bracket::new(PositionSide::Long);
+                exits.stop_loss = stop_loss;
+                exits.take_profit = take_profit;
+                exits.trailing_stop = trailing_stop;
+                self.attach_bracket(&symbol, order_id, exits).await?;
             }
             Signal::Sell {
                 symbol,
                 quantity,
                 limit_price,
                 stop_price,
+                stop_loss,
+                take_profit,
+                trailing_stop,
                 ..
             } => {
-                self.place_order(
-                    symbol,
-                    velora_core::Side::Sell,
-                    quantity,
-                    limit_price.or(Some(candle.close.into_inner())),
-                    stop_price,
-                )
-                .await?;
-            }
-            Signal::Close { .. } | Signal::Modify { .. } => {
-                return Err(EngineError::OrderError(
-                    "Close and Modify signals not yet implemented in live engine".to_string(),
-                ));
+                let order_id = self
+                    .place_order(
+                        symbol.clone(),
+                        velora_core::Side::Sell,
+                        quantity,
+                        Some(self.order_price(&symbol, limit_price)?),
+                        stop_price,
+                        false,
+                    )
+                    .await?;
+                let mut exits = Bracket::new(PositionSide::Short);
+                exits.stop_loss = stop_loss;
+                exits.take_profit = take_profit;
+                exits.trailing_stop = trailing_stop;
+                self.attach_bracket(&symbol, order_id, exits).await?;
+            }
+            Signal::Close {
+                symbol, quantity, ..
+            } => {
+                let Some(position) = self.position_tracker.get_position(&symbol) else {
+                    debug!("No {} position to close", symbol);
+                    return Ok(());
+                };
+
+                let side = exit_side(position.side);
+                let quantity = quantity.map_or(position.quantity, |q| q.min(position.quantity));
+                self.place_order(symbol, side, quantity, None, None, true)
+                    .await?;
+            }
+            Signal::Modify {
+                symbol,
+                stop_loss,
+                take_profit,
+                trailing_stop,
+                ..
+            } => {
+                let Some(position) = self.position_tracker.get_position(&symbol) else {
+                    warn!("Ignoring Modify for {}: no open position", symbol);
+                    return Ok(());
+                };
+
+                let (side, price) = (position.side, position.current_price);
+                let bracket = self
+                    .brackets
+                    .entry(symbol.clone())
+                    .or_insert_with(|| Bracket::new(side));
+                // Levels left out of the signal keep their current value
+                bracket.stop_loss = stop_loss.or(bracket.stop_loss);
+                bracket.take_profit = take_profit.or(bracket.take_profit);
+                bracket.trailing_stop = trailing_stop.or(bracket.trailing_stop);
+                bracket.extreme = bracket.extreme.or(Some(price));
+
+                self.sync_bracket(&symbol).await?;
+                self.process_execution().await?;
             }
             Signal::Hold => {}
         }
@@ -393,7 +887,19 @@ impl TradingEngine {
         Ok(())
     }
 
+    /// Price of an entry order: its limit price, else the last price
+    fn order_price(&self, symbol: &str, limit_price: Option<f64>) -> EngineResult<f64> {
+        match limit_price {
+            Some(price) => Ok(price),
+            None => self.context.get_last_price(symbol)?.ok_or_else(|| {
+                EngineError::MarketDataError(format!("No market data for {symbol}"))
+            }),
+        }
+    }
+
     /// Place an order
+    ///
+    /// Reduce-only orders skip the pre-trade risk checks.
     async fn place_order(
         &mut self,
         symbol: String,
@@ -401,7 +907,8 @@ impl TradingEngine {
         quantity: f64,
         price: Option<f64>,
         stop_price: Option<f64>,
-    ) -> EngineResult<()> {
+        reduce_only: bool,
+    ) -> EngineResult<OrderId> {
         // Determine order type
         let order_type = match (price, stop_price) {
             (Some(_), None) => OrderType::Limit,
@@ -410,80 +917,2172 @@ impl TradingEngine {
             (None, Some(_)) => OrderType::StopMarket,
         };
 
+        let quantity = if reduce_only {
+            quantity
+        } else {
+            self.check_risk(&symbol, side, quantity, price.or(stop_price))?
+        };
+
         // Create order
-        let order = Order::new(symbol, side, order_type, quantity, price);
+        let mut order = Order::new_at(symbol, side, order_type, quantity, price, self.clock.now())
+            .with_reduce_only(reduce_only);
+        if let Some(stop_price) = stop_price {
+            order = order.with_stop_price(stop_price);
+        }
 
         info!(
             "Placing {:?} {} order for {} @ {:?}",
             side, order.symbol, order.quantity, price
         );
 
-        // Submit to order manager (rate limit check)
+        let order_id = self.submit_order(order).await?;
+        self.process_execution().await?;
+        Ok(order_id)
+    }
+
+    /// Attach the exits of an entry signal to its position
+    ///
+    /// The exits are placed once the entry fills. A position already
+    /// protected on the same side takes the new levels; exits for the
+    /// opposite side are ignored until that position is closed.
+    async fn attach_bracket(
+        &mut self,
+        symbol: &str,
+        entry: OrderId,
+        exits: Bracket,
+    ) -> EngineResult<()> {
+        if exits.stop_loss.is_none() && exits.take_profit.is_none() && exits.trailing_stop.is_none()
+        {
+            return Ok(());
+        }
+
+        let bracket = match self.brackets.remove(symbol) {
+            Some(current) if current.side != exits.side => {
+                warn!(
+                    "Ignoring exits of {} entry: {:?} position is already protected",
+                    symbol, current.side
+                );
+                self.brackets.insert(symbol.to_string(), current);
+                return Ok(());
+            }
+            Some(current) => Bracket {
+                stop_loss: exits.stop_loss.or(current.stop_loss),
+                take_profit: exits.take_profit.or(current.take_profit),
+                trailing_stop: exits.trailing_stop.or(current.trailing_stop),
+                entry: Some(entry),
+                ..current
+            },
+            None => Bracket {
+                entry: Some(entry),
+                ..exits
+            },
+        };
+        self.brackets.insert(symbol.to_string(), bracket);
+
+        self.sync_bracket(symbol).await?;
+        self.process_execution().await
+    }
+
+    /// Send an order to the order manager and execution handler
+    ///
+    /// Fills and updates it produces are left queued for `process_execution`.
+    async fn submit_order(&mut self, order: Order) -> EngineResult<OrderId> {
+        // Submit to order manager (rate limit check), journaled before it leaves
         self.order_manager.submit_order(order.clone())?;
+        self.journal_order(order.id)?;
 
         // Execute via execution handler
-        let order_id = self.execution_handler.submit_order(&order).await?;
+        let order_id = match self.execution_handler.submit_order(&order).await {
+            Ok(order_id) => order_id,
+            Err(e) => {
+                self.order_manager.update_order(
+                    order.id,
+                    OrderUpdate {
+                        order_id: order.id,
+                        status: OrderStatus::Failed,
+                        filled_quantity: 0.0,
+                        average_price: 0.0,
+                        timestamp: self.clock.now(),
+                        error_message: Some(e.to_string()),
+                    },
+                )?;
+                self.journal_order(order.id)?;
+                return Err(e);
+            }
+        };
+        if let Some(exchange_order_id) = self.execution_handler.exchange_order_id(order_id) {
+            let record = JournalRecord::ExchangeOrder {
+                order_id,
+                exchange_order_id: exchange_order_id.to_string(),
+            };
+            self.journal(record)?;
+        }
+        self.order_manager.mark_submitted(order_id)?;
+        self.journal_order(order_id)?;
 
         info!("Order submitted: {}", order_id);
 
-        Ok(())
+        Ok(order_id)
     }
 
-    /// Process a fill
-    async fn process_fill(&mut self, fill: Fill) -> EngineResult<()> {
-        info!(
-            "Processing fill: {:?} {} {} @ {}",
-            fill.side, fill.quantity, fill.symbol, fill.price
-        );
+    /// Cancel a working order
+    async fn cancel_order(&mut self, order_id: OrderId) -> EngineResult<()> {
+        self.order_manager.cancel_order(order_id)?;
+        self.journal_order(order_id)?;
+        self.execution_handler.cancel_order(order_id).await
+    }
+
+    /// Whether an order can still fill
+    fn is_working(&self, order_id: OrderId) -> bool {
+        self.order_manager
+            .get_order(order_id)
+            .is_some_and(Order::is_active)
+    }
+
+    /// Bring the exit orders of a symbol in line with its position
+    ///
+    /// Exits are cancelled and the bracket dropped once the position is flat
+    /// or has flipped side (one exit filling cancels the other).
+    async fn sync_bracket(&mut self, symbol: &str) -> EngineResult<()> {
+        let Some(mut bracket) = self.brackets.remove(symbol) else {
+            return Ok(());
+        };
+
+        // Forget orders that filled or were cancelled
+        bracket.entry = bracket.entry.filter(|id| self.is_working(*id));
+        bracket.stop_order = bracket.stop_order.filter(|id| self.is_working(*id));
+        bracket.target_order = bracket.target_order.filter(|id| self.is_working(*id));
+
+        let position = self
+            .position_tracker
+            .get_position(symbol)
+            .filter(|position| position.side == bracket.side)
+            .map(|position| (position.quantity, position.average_entry_price));
+
+        let Some((quantity, entry_price)) = position else {
+            if bracket.entry.is_some() {
+                // Exits are placed once the entry fills
+                self.brackets.insert(symbol.to_string(), bracket);
+                return self.journal_bracket(symbol);
+            }
 
-        // Update order manager
-        let update = crate::events::OrderUpdate {
-            order_id: fill.order_id,
-            status: OrderStatus::Filled,
-            filled_quantity: fill.quantity,
-            average_price: fill.price,
-            timestamp: fill.timestamp,
-            error_message: None,
+            info!("{} position closed, cancelling its exit orders", symbol);
+            for order_id in [bracket.stop_order, bracket.target_order]
+                .into_iter()
+                .flatten()
+            {
+                if let Err(e) = self.cancel_order(order_id).await {
+                    warn!("Failed to cancel exit order {}: {}", order_id, e);
+                }
+            }
+            return self.journal_bracket(symbol);
         };
 
-        self.order_manager.update_order(fill.order_id, update)?;
+        // A trailing stop starts from the entry price
+        bracket.extreme = bracket.extreme.or(Some(entry_price));
+        bracket.stop_order = self
+            .sync_exit(symbol, &bracket, quantity, bracket.stop_order, true)
+            .await?;
+        bracket.target_order = self
+            .sync_exit(symbol, &bracket, quantity, bracket.target_order, false)
+            .await?;
+
+        self.brackets.insert(symbol.to_string(), bracket);
+        self.journal_bracket(symbol)?;
+        self.sync_context_position(symbol)
+    }
 
-        // Update positions
-        self.position_tracker.process_fill(&fill)?;
+    /// Journal the current exits of a symbol
+    fn journal_bracket(&mut self, symbol: &str) -> EngineResult<()> {
+        let record = JournalRecord::Bracket {
+            symbol: symbol.to_string(),
+            bracket: self.brackets.get(symbol).cloned(),
+        };
+        self.journal(record)
+    }
 
-        // Update strategy context with new position
-        if let Some(position) = self.position_tracker.get_position(&fill.symbol) {
-            let strategy_position = velora_strategy::Position {
-                symbol: position.symbol.clone(),
-                side: position.side,
-                quantity: position.quantity,
-                entry_price: position.average_entry_price,
-                current_price: position.current_price,
-                opened_at: position.opened_at,
-                updated_at: position.last_updated,
-                stop_loss: None,
-                take_profit: None,
-                unrealized_pnl: position.unrealized_pnl,
-                metadata: std::collections::HashMap::new(),
-            };
+    /// Follow the market with the trailing stop of a symbol
+    ///
+    /// Runs after resting orders were matched against the same prices, so a
+    /// bar's extreme only moves the stop for the bars after it.
+    async fn trail_stop(&mut self, symbol: &str, high: f64, low: f64) -> EngineResult<()> {
+        let Some(bracket) = self.brackets.get_mut(symbol) else {
+            return Ok(());
+        };
+        let (before, extreme) = (bracket.stop_level(), bracket.extreme);
+        bracket.ratchet(high, low);
+        if bracket.stop_level() == before {
+            // A fixed stop still rules, but the trailing reference may have moved
+            if bracket.extreme != extreme {
+                return self.journal_bracket(symbol);
+            }
+            return Ok(());
+        }
 
-            self.context.update_position(strategy_position)?;
+        self.sync_bracket(symbol).await?;
+        self.process_execution().await
+    }
+
+    /// Keep one exit order at its level and the position's size
+    ///
+    /// The order is cancelled and replaced when either has changed. Returns
+    /// the working order afterwards.
+    async fn sync_exit(
+        &mut self,
+        symbol: &str,
+        bracket: &Bracket,
+        quantity: f64,
+        current: Option<OrderId>,
+        stop_loss: bool,
+    ) -> EngineResult<Option<OrderId>> {
+        let level = if stop_loss {
+            bracket.stop_level()
         } else {
-            // Position was closed
-            self.context.remove_position(&fill.symbol)?;
+            bracket.take_profit
+        };
+        let Some(level) = level else {
+            return Ok(current);
+        };
+
+        if let Some(order_id) = current {
+            let unchanged = self.order_manager.get_order(order_id).is_some_and(|order| {
+                let order_level = if stop_loss {
+                    order.stop_price
+                } else {
+                    order.price
+                };
+                order_level == Some(level)
+                    && (order.quantity - order.filled_quantity - quantity).abs()
+                        <= f64::EPSILON * quantity
+            });
+            if unchanged {
+                return Ok(current);
+            }
+
+            debug!("Replacing exit order {} of {}", order_id, symbol);
+            if let Err(e) = self.cancel_order(order_id).await {
+                // Most likely filled meanwhile; its fill resyncs the bracket
+                warn!("Failed to cancel exit order {}: {}", order_id, e);
+                return Ok(current);
+            }
+        }
+
+        let side = exit_side(bracket.side);
+        let now = self.clock.now();
+        let order = if stop_loss {
+            Order::new_at(
+                symbol.to_string(),
+                side,
+                OrderType::StopMarket,
+                quantity,
+                None,
+                now,
+            )
+            .with_stop_price(level)
+        } else {
+            Order::new_at(
+                symbol.to_string(),
+                side,
+                OrderType::Limit,
+                quantity,
+                Some(level),
+                now,
+            )
+        };
+
+        info!(
+            "Placing {} {} exit for {} {} @ {}",
+            symbol,
+            if stop_loss {
+                "stop-loss"
+            } else {
+                "take-profit"
+            },
+            quantity,
+            symbol,
+            level
+        );
+        let order_id = self.submit_order(order.with_reduce_only(true)).await?;
+        Ok(Some(order_id))
+    }
+
+    /// Default to the sizer described by the strategy's configuration
+    fn init_position_sizer(&mut self) -> EngineResult<()> {
+        if self.position_sizer.is_some() {
+            return Ok(());
         }
+        let Some(strategy) = self.strategy.as_ref() else {
+            return Ok(());
+        };
 
+        let sizer = velora_risk::sizing::from_strategy_config(strategy.config())
+            .map_err(|e| EngineError::InvalidConfig(e.to_string()))?;
+        debug!("Sizing unsized signals with {}", sizer.name());
+        self.position_sizer = Some(sizer);
         Ok(())
     }
 
-    /// Heartbeat callback
-    async fn on_heartbeat(&mut self) -> EngineResult<()> {
-        // Record equity snapshot
-        self.position_tracker.record_snapshot();
+    /// Fill in the quantity of an unsized Buy/Sell signal
+    ///
+    /// Returns None if the sizer does not have enough data yet, or if there
+    /// is no sizer to ask.
+    fn size_signal(&self, signal: Signal) -> EngineResult<Option<Signal>> {
+        if !signal.needs_sizing() {
+            return Ok(Some(signal));
+        }
 
-        // Clean up old completed orders (keep last 1000)
-        // TODO: Implement in order_manager
+        let (symbol, side) = match &signal {
+            Signal::Buy { symbol, .. } => (symbol.as_str(), velora_core::Side::Buy),
+            Signal::Sell { symbol, .. } => (symbol.as_str(), velora_core::Side::Sell),
+            _ => return Ok(Some(signal)),
+        };
 
-        Ok(())
+        let Some(sizer) = self.position_sizer.as_ref() else {
+            warn!("Skipping unsized {} signal: no position sizer", symbol);
+            return Ok(None);
+        };
+
+        match sizer.size(symbol, side, &self.context) {
+            Ok(quantity) if quantity > 0.0 => Ok(Some(signal.with_quantity(quantity))),
+            Ok(_) => Ok(None),
+            Err(RiskError::InsufficientData(reason)) => {
+                debug!("Skipping {} signal: {}", symbol, reason);
+                Ok(None)
+            }
+            Err(RiskError::Strategy(e)) => Err(e.into()),
+            Err(e) => Err(EngineError::InvalidConfig(e.to_string())),
+        }
+    }
+
+    /// Run pre-trade risk checks and return the quantity to submit
+    fn check_risk(
+        &mut self,
+        symbol: &str,
+        side: velora_core::Side,
+        quantity: f64,
+        price: Option<f64>,
+    ) -> EngineResult<f64> {
+        if !self.config.enable_risk_checks {
+            return Ok(quantity);
+        }
+
+        let Some(risk_manager) = self.risk_manager.as_mut() else {
+            return Ok(quantity);
+        };
+
+        let price = price.ok_or_else(|| {
+            EngineError::RiskViolation(format!("No reference price for {symbol}"))
+        })?;
+
+        let mut state = PortfolioState::new(
+            self.position_tracker.available_cash() + self.position_tracker.total_unrealized_pnl(),
+            self.clock.now(),
+        );
+        for position in self.position_tracker.get_positions() {
+            let notional = position.quantity * position.current_price;
+            let signed = match position.side {
+                PositionSide::Long => notional,
+                PositionSide::Short => -notional,
+            };
+            state.positions.insert(position.symbol.clone(), signed);
+        }
+
+        let request = OrderRequest::new(symbol, side, quantity, price);
+        match risk_manager.check_order(&request, &state) {
+            RiskDecision::Approved => Ok(quantity),
+            RiskDecision::Resized {
+                quantity: resized,
+                reason,
+            } => {
+                warn!(
+                    "Risk check resized {} order from {} to {}: {}",
+                    symbol, quantity, resized, reason
+                );
+                Ok(resized)
+            }
+            RiskDecision::Rejected(reason) => Err(EngineError::RiskViolation(reason.to_string())),
+        }
+    }
+
+    /// Apply the order updates and fills queued by the execution handler
+    ///
+    /// Exit orders of the symbols that filled are resynced, until no more
+    /// updates are produced.
+    async fn process_execution(&mut self) -> EngineResult<()> {
+        loop {
+            let updates = self.execution_handler.drain_updates();
+            let fills = self.execution_handler.drain_fills();
+            if updates.is_empty() && fills.is_empty() {
+                return Ok(());
+            }
+            self.orders_changed = true;
+
+            for update in updates {
+                if let Err(e) = self.apply_order_update(update) {
+                    warn!("Error updating order: {}", e);
+                }
+            }
+
+            let mut filled = BTreeSet::new();
+            for fill in fills {
+                filled.insert(fill.symbol.clone());
+                self.process_fill(fill).await?;
+            }
+
+            for symbol in filled {
+                self.sync_bracket(&symbol).await?;
+            }
+        }
+    }
+
+    /// Process a trade price update
+    async fn process_tick(&mut self, tick: Tick) -> EngineResult<()> {
+        self.apply_traded_price(&tick).await?;
+
+        let strategy = self
+            .strategy
+            .as_mut()
+            .ok_or_else(|| EngineError::InvalidConfig("No strategy".to_string()))?;
+        let signal = strategy.on_tick(&tick, &self.context).await?;
+        self.dispatch(signal).await
+    }
+
+    /// Process a trade printed on the exchange
+    async fn process_trade(&mut self, trade: Trade) -> EngineResult<()> {
+        let tick = Tick {
+            symbol: trade.symbol.clone(),
+            price: trade.price,
+            volume: trade.quantity,
+            timestamp: trade.timestamp,
+        };
+        self.apply_traded_price(&tick).await?;
+        self.context
+            .add_trade(trade.symbol.as_str(), trade.clone())?;
+
+        let strategy = self
+            .strategy
+            .as_mut()
+            .ok_or_else(|| EngineError::InvalidConfig("No strategy".to_string()))?;
+        let signal = strategy.on_trade(&trade, &self.context).await?;
+        self.dispatch(signal).await
+    }
+
+    /// Process an order book snapshot
+    ///
+    /// Positions are marked at the mid price, and the strategy gets it
+    /// through `on_tick` as in the backtester's replays. Quotes do not fill
+    /// dry-run orders; only traded prices do.
+    async fn process_order_book(&mut self, book: OrderBook) -> EngineResult<()> {
+        let (symbol, timestamp) = (book.symbol.clone(), book.timestamp);
+        let best_bid = book.best_bid().map(|level| level.price.into_inner());
+        let best_ask = book.best_ask().map(|level| level.price.into_inner());
+        let mid = book.mid_price();
+        self.context.update_order_book(book)?;
+
+        // A one-sided book only refreshes the quotes of a known price
+        let Some(mid) = mid else {
+            if let Some(snapshot) = self.context.get_market_snapshot(symbol.as_str())? {
+                let snapshot = velora_strategy::MarketSnapshot {
+                    best_bid,
+                    best_ask,
+                    timestamp,
+                    ..snapshot
+                };
+                self.context
+                    .update_market_snapshot(symbol.as_str(), snapshot)?;
+            }
+            return Ok(());
+        };
+
+        let price = mid.into_inner();
+        self.position_tracker
+            .update_position_price(symbol.as_str(), price);
+        let snapshot = velora_strategy::MarketSnapshot {
+            last_price: price,
+            timestamp,
+            best_bid,
+            best_ask,
+            ..self.market_snapshot(symbol.as_str(), timestamp)?
+        };
+        self.context
+            .update_market_snapshot(symbol.as_str(), snapshot)?;
+
+        let quote = Tick {
+            symbol,
+            price: mid,
+            volume: 0.0.into(),
+            timestamp,
+        };
+        let strategy = self
+            .strategy
+            .as_mut()
+            .ok_or_else(|| EngineError::InvalidConfig("No strategy".to_string()))?;
+        let signal = strategy.on_tick(&quote, &self.context).await?;
+        self.dispatch(signal).await
+    }
+
+    /// Settle a funding rate on the open position in its symbol
+    fn process_funding(&mut self, funding: &FundingRate) -> EngineResult<()> {
+        let rate = funding.rate.to_f64().ok_or_else(|| {
+            EngineError::MarketDataError(format!("Invalid funding rate {}", funding.rate))
+        })?;
+        let symbol = funding.symbol.as_str();
+        if let Some(position) = self.position_tracker.get_position(symbol) {
+            let record = JournalRecord::Funding {
+                symbol: symbol.to_string(),
+                rate,
+                price: position.current_price,
+            };
+            self.journal(record)?;
+        }
+        let payment = self.position_tracker.apply_funding(symbol, rate);
+        if payment != 0.0 {
+            info!("Funding on {} at rate {}: paid {}", symbol, rate, payment);
+            self.sync_context_position(symbol)?;
+        }
+        self.context
+            .update_capital(self.position_tracker.available_cash())?;
+        Ok(())
+    }
+
+    /// Apply a traded price to positions, the context and resting dry-run orders
+    async fn apply_traded_price(&mut self, tick: &Tick) -> EngineResult<()> {
+        let symbol = tick.symbol.as_str();
+        let price = tick.price.into_inner();
+        self.position_tracker.update_position_price(symbol, price);
+
+        let snapshot = velora_strategy::MarketSnapshot {
+            last_price: price,
+            timestamp: tick.timestamp,
+            ..self.market_snapshot(symbol, tick.timestamp)?
+        };
+        self.context.update_market_snapshot(symbol, snapshot)?;
+
+        self.execution_handler.on_tick(tick);
+        self.process_execution().await?;
+        self.trail_stop(symbol, price, price).await?;
+        self.context
+            .update_capital(self.position_tracker.available_cash())?;
+        Ok(())
+    }
+
+    /// Current market snapshot of a symbol, to update field by field
+    fn market_snapshot(
+        &self,
+        symbol: &str,
+        timestamp: DateTime<Utc>,
+    ) -> EngineResult<velora_strategy::MarketSnapshot> {
+        Ok(self
+            .context
+            .get_market_snapshot(symbol)?
+            .unwrap_or(velora_strategy::MarketSnapshot {
+                last_price: 0.0,
+                timestamp,
+                best_bid: None,
+                best_ask: None,
+                volume_24h: None,
+            }))
+    }
+
+    /// Process a fill
+    async fn process_fill(&mut self, fill: Fill) -> EngineResult<()> {
+        info!(
+            "Processing fill: {:?} {} {} @ {}",
+            fill.side, fill.quantity, fill.symbol, fill.price
+        );
+
+        // Update positions once the fill is journaled
+        self.journal(JournalRecord::Fill(fill.clone()))?;
+        self.position_tracker.process_fill(&fill)?;
+        let symbol = fill.symbol.clone();
+        self.fills.push(fill);
+
+        // Update strategy context with new position
+        self.sync_context_position(&symbol)
+    }
+
+    /// Mirror a tracked position, with its exit levels, into the strategy context
+    fn sync_context_position(&mut self, symbol: &str) -> EngineResult<()> {
+        if let Some(position) = self.position_tracker.get_position(symbol) {
+            let bracket = self
+                .brackets
+                .get(symbol)
+                .filter(|bracket| bracket.side == position.side);
+            let strategy_position = velora_strategy::Position {
+                symbol: position.symbol.clone(),
+                side: position.side,
+                quantity: position.quantity,
+                entry_price: position.average_entry_price,
+                current_price: position.current_price,
+                opened_at: position.opened_at,
+                updated_at: position.last_updated,
+                stop_loss: bracket.and_then(Bracket::stop_level),
+                take_profit: bracket.and_then(|bracket| bracket.take_profit),
+                unrealized_pnl: position.unrealized_pnl,
+                metadata: std::collections::HashMap::new(),
+            };
+
+            self.context.update_position(strategy_position)?;
+        } else {
+            // Position was closed
+            self.context.remove_position(symbol)?;
+        }
+
+        Ok(())
+    }
+
+    /// Rebuild orders, positions and fills from the configured journal
+    ///
+    /// Runs before the engine accepts market events, so no signal acts on
+    /// a partial state. History from before the last compaction stays in the
+    /// archived segments. Working orders are handed back to the execution
+    /// handler. Orders recorded as created but not as reaching the exchange
+    /// are marked failed, since whether they were placed is unknown. The
+    /// exits of protected positions are restored, so their orders keep being
+    /// resized and trailed.
+    fn recover_journal(&mut self) -> EngineResult<()> {
+        let Some(config) = self.config.journal.clone() else {
+            return Ok(());
+        };
+        let (journal, records) = Journal::open(config)?;
+
+        let mut orders: Vec<Order> = Vec::new();
+        let mut positions: HashMap<OrderId, usize> = HashMap::new();
+        let mut history = Vec::new();
+        let mut exchange_orders: HashMap<OrderId, String> = HashMap::new();
+        let mut fills = Vec::new();
+        let mut brackets: HashMap<String, Bracket> = HashMap::new();
+        for record in records {
+            match record {
+                JournalRecord::Snapshot(snapshot) => {
+                    positions = snapshot
+                        .orders
+                        .iter()
+                        .enumerate()
+                        .map(|(index, order)| (order.id, index))
+                        .collect();
+                    orders = snapshot.orders;
+                    history.clear();
+                    exchange_orders = snapshot.exchange_orders.into_iter().collect();
+                    self.position_tracker
+                        .restore(snapshot.positions, snapshot.cash);
+                    brackets = snapshot.brackets.into_iter().collect();
+                    fills.clear();
+                }
+                JournalRecord::Order(order) => match positions.get(&order.id) {
+                    Some(&index) => orders[index] = order,
+                    None => {
+                        positions.insert(order.id, orders.len());
+                        orders.push(order);
+                    }
+                },
+                JournalRecord::OrderEvent(event) => history.push(event),
+                JournalRecord::ExchangeOrder {
+                    order_id,
+                    exchange_order_id,
+                } => {
+                    exchange_orders.insert(order_id, exchange_order_id);
+                }
+                JournalRecord::Fill(fill) => {
+                    self.position_tracker.process_fill(&fill)?;
+                    fills.push(fill);
+                }
+                JournalRecord::Funding {
+                    symbol,
+                    rate,
+                    price,
+                } => {
+                    self.position_tracker.update_position_price(&symbol, price);
+                    self.position_tracker.apply_funding(&symbol, rate);
+                }
+                JournalRecord::Bracket { symbol, bracket } => match bracket {
+                    Some(bracket) => {
+                        brackets.insert(symbol, bracket);
+                    }
+                    None => {
+                        brackets.remove(&symbol);
+                    }
+                },
+            }
+        }
+
+        let now = self.clock.now();
+        let mut interrupted = Vec::new();
+        for order in orders.iter_mut() {
+            if order.status != OrderStatus::Pending {
+                continue;
+            }
+            if exchange_orders.contains_key(&order.id) {
+                order.status = OrderStatus::Submitted;
+            } else {
+                warn!("Order {} was interrupted before submission", order.id);
+                order.status = OrderStatus::Failed;
+                order.error_message = Some("Interrupted before submission".to_string());
+                interrupted.push(order.id);
+            }
+            order.updated_at = now;
+        }
+        let working: Vec<&Order> = orders.iter().filter(|order| order.is_active()).collect();
+        for order in &working {
+            self.execution_handler
+                .restore_order(order, exchange_orders.remove(&order.id));
+        }
+
+        info!(
+            "Recovered {} orders ({} working), {} positions ({} protected) and {} fills from the journal",
+            orders.len(),
+            working.len(),
+            self.position_tracker.position_count(),
+            brackets.len(),
+            fills.len()
+        );
+        self.journaled_events = history.len();
+        self.order_manager.restore(orders, history);
+        self.fills = fills;
+        self.brackets = brackets;
+        self.journal = Some(journal);
+
+        for order_id in interrupted {
+            self.journal_order(order_id)?;
+        }
+        let symbols: Vec<String> = self
+            .position_tracker
+            .get_positions()
+            .into_iter()
+            .map(|position| position.symbol.clone())
+            .collect();
+        for symbol in symbols {
+            self.sync_context_position(&symbol)?;
+        }
+        self.context
+            .update_capital(self.position_tracker.available_cash())?;
+        Ok(())
+    }
+
+    /// Apply the exchange's view of recovered live orders
+    ///
+    /// Picks up fills and cancellations that happened while the engine was down.
+    async fn reconcile_orders(&mut self) -> EngineResult<()> {
+        for update in self.execution_handler.sync_orders().await? {
+            if let Err(e) = self.apply_order_update(update) {
+                warn!("Error updating order: {}", e);
+            }
+        }
+        self.process_execution().await
+    }
+
+    /// Append a record to the journal, if there is one
+    fn journal(&mut self, record: JournalRecord) -> EngineResult<()> {
+        match self.journal.as_mut() {
+            Some(journal) => journal.append(&record),
+            None => Ok(()),
+        }
+    }
+
+    /// Journal an order's state after the audit events recorded since the last call
+    fn journal_order(&mut self, order_id: OrderId) -> EngineResult<()> {
+        let Some(journal) = self.journal.as_mut() else {
+            return Ok(());
+        };
+
+        let history = self.order_manager.get_order_history();
+        for event in &history[self.journaled_events..] {
+            journal.append(&JournalRecord::OrderEvent(event.clone()))?;
+        }
+        self.journaled_events = history.len();
+
+        if let Some(order) = self.order_manager.get_order(order_id) {
+            journal.append(&JournalRecord::Order(order.clone()))?;
+        }
+        Ok(())
+    }
+
+    /// Start a new journal segment from a snapshot of the working state
+    ///
+    /// Only called between events, when every journaled fill has been applied.
+    /// Completed orders, the audit trail and fills before the snapshot stay in
+    /// the archived segments and are not recovered on restart.
+    fn compact_journal(&mut self) -> EngineResult<()> {
+        if !self.journal.as_ref().is_some_and(Journal::needs_compaction) {
+            return Ok(());
+        }
+
+        let snapshot = JournalSnapshot {
+            orders: self
+                .order_manager
+                .all_orders()
+                .filter(|order| !order.is_terminal())
+                .cloned()
+                .collect(),
+            exchange_orders: self.execution_handler.exchange_orders(),
+            positions: self
+                .position_tracker
+                .get_positions()
+                .into_iter()
+                .cloned()
+                .collect(),
+            brackets: self
+                .brackets
+                .iter()
+                .map(|(symbol, bracket)| (symbol.clone(), bracket.clone()))
+                .collect(),
+            cash: self.position_tracker.available_cash(),
+        };
+        match self.journal.as_mut() {
+            Some(journal) => journal.compact(&snapshot),
+            None => Ok(()),
+        }
+    }
+
+    /// Heartbeat callback
+    async fn on_heartbeat(&mut self) -> EngineResult<()> {
+        // Record equity snapshot
+        self.position_tracker.record_snapshot();
+
+        // Clean up old completed orders (keep last 1000)
+        // TODO: Implement in order_manager
+
+        Ok(())
+    }
+}
+
+/// Side of the order that exits a position
+fn exit_side(side: PositionSide) -> velora_core::Side {
+    match side {
+        PositionSide::Long => velora_core::Side::Sell,
+        PositionSide::Short => velora_core::Side::Buy,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ExecutionMode;
+    use async_trait::async_trait;
+    use velora_core::{RiskConfig, Symbol};
+    use velora_strategy::{StrategyConfig, StrategyResult, StrategyState};
+
+    /// Buys on every candle (unsized when `quantity` is None)
+    struct AlwaysBuyStrategy {
+        config: StrategyConfig,
+        quantity: Option<f64>,
+    }
+
+    #[async_trait]
+    impl Strategy for AlwaysBuyStrategy {
+        fn name(&self) -> &str {
+            "AlwaysBuy"
+        }
+
+        fn config(&self) -> &StrategyConfig {
+            &self.config
+        }
+
+        fn state(&self) -> StrategyState {
+            StrategyState::Running
+        }
+
+        async fn on_candle(
+            &mut self,
+            candle: &Candle,
+            _ctx: &StrategyContext,
+        ) -> StrategyResult<Signal> {
+            Ok(match self.quantity {
+                Some(quantity) => Signal::buy(candle.symbol.as_str(), quantity),
+                None => Signal::buy_unsized(candle.symbol.as_str()),
+            })
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    /// Buys once, with a limit below the close
+    struct BuyTheDipStrategy {
+        config: StrategyConfig,
+        discount: f64,
+        trailing_stop: Option<f64>,
+        placed: bool,
+    }
+
+    #[async_trait]
+    impl Strategy for BuyTheDipStrategy {
+        fn name(&self) -> &str {
+            "BuyTheDip"
+        }
+
+        fn config(&self) -> &StrategyConfig {
+            &self.config
+        }
+
+        fn state(&self) -> StrategyState {
+            StrategyState::Running
+        }
+
+        async fn on_candle(
+            &mut self,
+            candle: &Candle,
+            _ctx: &StrategyContext,
+        ) -> StrategyResult<Signal> {
+            if self.placed {
+                return Ok(Signal::Hold);
+            }
+            self.placed = true;
+
+            Ok(Signal::Buy {
+                symbol: candle.symbol.to_string(),
+                quantity: 1.0,
+                limit_price: Some(candle.close.into_inner() - self.discount),
+                stop_price: None,
+                stop_loss: None,
+                take_profit: None,
+                trailing_stop: self.trailing_stop,
+                metadata: std::collections::HashMap::new(),
+            })
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    /// Emits a fixed sequence of signals, then holds
+    struct ScriptedStrategy {
+        config: StrategyConfig,
+        signals: std::collections::VecDeque<Signal>,
+    }
+
+    #[async_trait]
+    impl Strategy for ScriptedStrategy {
+        fn name(&self) -> &str {
+            "Scripted"
+        }
+
+        fn config(&self) -> &StrategyConfig {
+            &self.config
+        }
+
+        fn state(&self) -> StrategyState {
+            StrategyState::Running
+        }
+
+        async fn on_candle(
+            &mut self,
+            _candle: &Candle,
+            _ctx: &StrategyContext,
+        ) -> StrategyResult<Signal> {
+            Ok(self.signals.pop_front().unwrap_or(Signal::Hold))
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    fn scripted(signals: Vec<Signal>) -> TradingEngine {
+        let config = EngineConfig::builder()
+            .mode(ExecutionMode::DryRun)
+            .add_symbol("BTC-USD-PERP".to_string())
+            .enable_risk_checks(false)
+            .build();
+
+        TradingEngine::new(config).with_strategy(Box::new(ScriptedStrategy {
+            config: StrategyConfig::new("Scripted"),
+            signals: signals.into(),
+        }))
+    }
+
+    fn modify(stop_loss: Option<f64>, take_profit: Option<f64>) -> Signal {
+        Signal::Modify {
+            symbol: "BTC-USD-PERP".to_string(),
+            stop_loss,
+            take_profit,
+            trailing_stop: None,
+            metadata: std::collections::HashMap::new(),
+        }
+    }
+
+    fn position_quantity(engine: &TradingEngine) -> Option<f64> {
+        engine
+            .position_tracker
+            .get_position("BTC-USD-PERP")
+            .map(|position| position.quantity)
+    }
+
+    fn candle(price: f64) -> Candle {
+        ohlc(price, price, price, price)
+    }
+
+    fn ohlc(open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle {
+            symbol: Symbol::new("BTC-USD-PERP"),
+            open: open.into(),
+            high: high.into(),
+            low: low.into(),
+            close: close.into(),
+            volume: 10.0.into(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    async fn run_engine(enable_risk_checks: bool) -> TradingEngine {
+        let config = EngineConfig::builder()
+            .mode(ExecutionMode::DryRun)
+            .add_symbol("BTC-USD-PERP".to_string())
+            .enable_risk_checks(enable_risk_checks)
+            .build();
+
+        let risk_manager = RiskManager::new(RiskConfig {
+            max_position_size: 1_000.0,
+            ..Default::default()
+        });
+
+        let mut engine = TradingEngine::new(config)
+            .with_strategy(Box::new(AlwaysBuyStrategy {
+                config: StrategyConfig::new("AlwaysBuy"),
+                quantity: Some(1.0),
+            }))
+            .with_risk_manager(risk_manager);
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tx.send(MarketEvent::Candle(candle(50_000.0))).unwrap();
+        drop(tx);
+
+        engine.start_with_receiver(rx).await.unwrap();
+        engine
+    }
+
+    /// Logs every callback; buys on the first trade and closes on the second timer
+    struct CallbackStrategy {
+        config: StrategyConfig,
+        calls: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl CallbackStrategy {
+        fn log(&self, call: impl Into<String>) {
+            self.calls.lock().unwrap().push(call.into());
+        }
+    }
+
+    #[async_trait]
+    impl Strategy for CallbackStrategy {
+        fn name(&self) -> &str {
+            &self.config.name
+        }
+
+        fn config(&self) -> &StrategyConfig {
+            &self.config
+        }
+
+        fn state(&self) -> StrategyState {
+            StrategyState::Running
+        }
+
+        async fn initialize(&mut self, _ctx: &StrategyContext) -> StrategyResult<()> {
+            self.log("initialize");
+            Ok(())
+        }
+
+        async fn on_trade(
+            &mut self,
+            trade: &Trade,
+            _ctx: &StrategyContext,
+        ) -> StrategyResult<Signal> {
+            self.log(format!("trade {}", trade.price));
+            Ok(Signal::buy(trade.symbol.as_str(), 1.0))
+        }
+
+        async fn on_tick(&mut self, tick: &Tick, _ctx: &StrategyContext) -> StrategyResult<Signal> {
+            self.log(format!("tick {}", tick.price));
+            Ok(Signal::Hold)
+        }
+
+        async fn on_order_update(&mut self, _ctx: &StrategyContext) -> StrategyResult<Signal> {
+            self.log("order update");
+            Ok(Signal::Hold)
+        }
+
+        async fn on_timer(&mut self, ctx: &StrategyContext) -> StrategyResult<Signal> {
+            self.log(format!("timer {}", ctx.now().timestamp()));
+            let timers = self
+                .calls
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|call| call.starts_with("timer"))
+                .count();
+            if timers == 2 {
+                return Ok(Signal::close("BTC-USD-PERP"));
+            }
+            Ok(Signal::Hold)
+        }
+
+        async fn shutdown(&mut self, _ctx: &StrategyContext) -> StrategyResult<()> {
+            self.log("shutdown");
+            Ok(())
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    #[tokio::test]
+    async fn test_dispatches_every_callback() {
+        use chrono::TimeZone;
+        use velora_core::{BookLevel, ReplayClock};
+
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let second = chrono::Duration::seconds(1);
+        let symbol = Symbol::new("BTC-USD-PERP");
+        let config = EngineConfig::builder()
+            .mode(ExecutionMode::DryRun)
+            .add_symbol(symbol.to_string())
+            .timer_interval_ms(Some(10_000))
+            .enable_risk_checks(false)
+            .build();
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut engine = TradingEngine::new(config)
+            .with_strategy(Box::new(CallbackStrategy {
+                config: StrategyConfig::new("Callbacks"),
+                calls: calls.clone(),
+            }))
+            .with_clock(Arc::new(ReplayClock::new(start)));
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tx.send(MarketEvent::Trade(Trade {
+            id: uuid::Uuid::new_v4(),
+            order_id: uuid::Uuid::nil(),
+            symbol: symbol.clone(),
+            side: velora_core::Side::Buy,
+            price: 100.0.into(),
+            quantity: 2.0.into(),
+            fee: 0.0.into(),
+            timestamp: start,
+        }))
+        .unwrap();
+        tx.send(MarketEvent::Funding(FundingRate {
+            symbol: symbol.clone(),
+            rate: rust_decimal::Decimal::new(1, 3),
+            next_funding_time: start + second * 3600,
+            timestamp: start + second * 5,
+        }))
+        .unwrap();
+        tx.send(MarketEvent::Tick(Tick {
+            symbol: symbol.clone(),
+            price: 101.0.into(),
+            volume: 1.0.into(),
+            timestamp: start + second * 6,
+        }))
+        .unwrap();
+        let level = |price: f64| BookLevel {
+            price: price.into(),
+            quantity: 1.0.into(),
+        };
+        tx.send(MarketEvent::OrderBook(OrderBook {
+            symbol: symbol.clone(),
+            bids: vec![level(101.0)],
+            asks: vec![level(103.0)],
+            timestamp: start + second * 12,
+        }))
+        .unwrap();
+        drop(tx);
+        engine.start_with_receiver(rx).await.unwrap();
+
+        let t0 = start.timestamp();
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "initialize".to_string(),
+                "trade 100".to_string(),
+                "order update".to_string(),
+                format!("timer {t0}"),
+                "tick 101".to_string(),
+                "tick 102".to_string(),
+                format!("timer {}", t0 + 12),
+                "shutdown".to_string(),
+            ]
+        );
+
+        // Bought at the trade, paid 0.1% funding on 100, closed at the last traded price
+        let prices: Vec<_> = engine.fills().iter().map(|fill| fill.price).collect();
+        assert_eq!(prices, vec![100.0, 101.0]);
+        assert!(engine
+            .position_tracker
+            .get_position("BTC-USD-PERP")
+            .is_none());
+        let commission: f64 = engine.fills().iter().map(|fill| fill.commission).sum();
+        let cash = 10_000.0 - 0.1 + 1.0 - commission;
+        assert!((engine.position_tracker.available_cash() - cash).abs() < 1e-9);
+
+        let snapshot = engine
+            .context
+            .get_market_snapshot("BTC-USD-PERP")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            (snapshot.best_bid, snapshot.best_ask),
+            (Some(101.0), Some(103.0))
+        );
+        assert_eq!(
+            engine
+                .context
+                .get_recent_trades("BTC-USD-PERP", 10)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replay_clock_stamps_historical_time() {
+        use chrono::TimeZone;
+        use velora_core::ReplayClock;
+
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let minute = chrono::Duration::minutes(1);
+        let config = EngineConfig::builder()
+            .mode(ExecutionMode::DryRun)
+            .add_symbol("BTC-USD-PERP".to_string())
+            .enable_risk_checks(false)
+            .build();
+        let mut engine = TradingEngine::new(config)
+            .with_strategy(Box::new(AlwaysBuyStrategy {
+                config: StrategyConfig::new("AlwaysBuy"),
+                quantity: Some(1.0),
+            }))
+            .with_clock(Arc::new(ReplayClock::new(start)));
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        for i in 0..3 {
+            let mut bar = candle(50_000.0);
+            bar.timestamp = start + minute * i;
+            tx.send(MarketEvent::Candle(bar)).unwrap();
+        }
+        drop(tx);
+        engine.start_with_receiver(rx).await.unwrap();
+
+        // Each candle is acted on when it closes
+        let created: Vec<_> = engine
+            .order_manager
+            .get_completed_orders()
+            .iter()
+            .map(|order| order.created_at)
+            .collect();
+        assert_eq!(
+            created,
+            vec![start + minute, start + minute * 2, start + minute * 3]
+        );
+
+        let position = engine
+            .position_tracker
+            .get_position("BTC-USD-PERP")
+            .unwrap();
+        assert_eq!(position.opened_at, start + minute);
+        assert_eq!(position.last_updated, start + minute * 3);
+
+        // The heartbeat fires on replayed time, once per candle
+        let snapshots: Vec<_> = engine
+            .get_equity_history()
+            .iter()
+            .map(|snapshot| snapshot.timestamp)
+            .collect();
+        assert_eq!(snapshots, created);
+
+        let status = engine.status();
+        assert_eq!(status.last_update, start + minute * 3);
+        assert_eq!(status.uptime_secs, 180);
+        assert_eq!(status.total_fills, 3);
+        assert_eq!(engine.fills()[0].timestamp, start + minute);
+        assert_eq!(engine.context.now(), start + minute * 3);
+    }
+
+    #[tokio::test]
+    async fn test_risk_checks_resize_orders() {
+        let engine = run_engine(true).await;
+
+        let orders = engine.order_manager.get_completed_orders();
+        assert_eq!(orders.len(), 1);
+        // 1.0 BTC requested, capped at 1,000 notional = 0.02 BTC
+        assert_eq!(orders[0].quantity, 0.02);
+    }
+
+    #[tokio::test]
+    async fn test_risk_checks_disabled() {
+        let engine = run_engine(false).await;
+
+        let orders = engine.order_manager.get_completed_orders();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].quantity, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_position_sizer_sizes_unsized_signals() {
+        let config = EngineConfig::builder()
+            .mode(ExecutionMode::DryRun)
+            .add_symbol("BTC-USD-PERP".to_string())
+            .enable_risk_checks(false)
+            .build();
+
+        let mut engine = TradingEngine::new(config)
+            .with_strategy(Box::new(AlwaysBuyStrategy {
+                config: StrategyConfig::new("AlwaysBuy"),
+                quantity: None,
+            }))
+            .with_position_sizer(Box::new(velora_risk::FixedFractional::new(0.1).unwrap()));
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tx.send(MarketEvent::Candle(candle(50_000.0))).unwrap();
+        drop(tx);
+
+        engine.start_with_receiver(rx).await.unwrap();
+
+        let orders = engine.order_manager.get_completed_orders();
+        assert_eq!(orders.len(), 1);
+        // 10% of 10,000 at 50,000 = 0.02 BTC
+        assert_eq!(orders[0].quantity, 0.02);
+    }
+
+    #[tokio::test]
+    async fn test_sizes_from_strategy_config_by_default() {
+        let config = EngineConfig::builder()
+            .mode(ExecutionMode::DryRun)
+            .add_symbol("BTC-USD-PERP".to_string())
+            .enable_risk_checks(false)
+            .build();
+
+        let mut engine = TradingEngine::new(config).with_strategy(Box::new(AlwaysBuyStrategy {
+            config: StrategyConfig::new("AlwaysBuy").with_max_position_size(20.0),
+            quantity: None,
+        }));
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tx.send(MarketEvent::Candle(candle(50_000.0))).unwrap();
+        drop(tx);
+
+        engine.start_with_receiver(rx).await.unwrap();
+
+        let orders = engine.order_manager.get_completed_orders();
+        assert_eq!(orders.len(), 1);
+        // 20% of 10,000 at 50,000 = 0.04 BTC
+        assert_eq!(orders[0].quantity, 0.04);
+    }
+
+    fn dip_buyer() -> TradingEngine {
+        let config = EngineConfig::builder()
+            .mode(ExecutionMode::DryRun)
+            .add_symbol("BTC-USD-PERP".to_string())
+            .enable_risk_checks(false)
+            .build();
+
+        TradingEngine::new(config).with_strategy(Box::new(BuyTheDipStrategy {
+            config: StrategyConfig::new("BuyTheDip"),
+            discount: 1_000.0,
+            trailing_stop: None,
+            placed: false,
+        }))
+    }
+
+    async fn run_dip_buyer(candles: Vec<Candle>) -> TradingEngine {
+        let mut engine = dip_buyer();
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        for candle in candles {
+            tx.send(MarketEvent::Candle(candle)).unwrap();
+        }
+        drop(tx);
+
+        engine.start_with_receiver(rx).await.unwrap();
+        engine
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_limit_order_rests_until_crossed() {
+        let engine = run_dip_buyer(vec![
+            candle(50_000.0),
+            ohlc(50_000.0, 50_500.0, 49_500.0, 50_000.0),
+        ])
+        .await;
+
+        // Limit at 49,000 has not traded yet
+        assert_eq!(engine.order_manager.get_active_orders().len(), 1);
+        assert_eq!(engine.execution_handler.resting_orders(), 1);
+        assert!(engine
+            .position_tracker
+            .get_position("BTC-USD-PERP")
+            .is_none());
+
+        let engine = run_dip_buyer(vec![
+            candle(50_000.0),
+            ohlc(50_000.0, 50_500.0, 49_500.0, 50_000.0),
+            ohlc(49_800.0, 49_900.0, 48_800.0, 49_000.0),
+        ])
+        .await;
+
+        let completed = engine.order_manager.get_completed_orders();
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].status, OrderStatus::Filled);
+        assert_eq!(completed[0].average_fill_price, 49_000.0);
+        let position = engine
+            .position_tracker
+            .get_position("BTC-USD-PERP")
+            .unwrap();
+        assert_eq!(position.quantity, 1.0);
+    }
+
+    fn journaled_dip_buyer(journal: &crate::journal::JournalConfig, placed: bool) -> TradingEngine {
+        let config = EngineConfig::builder()
+            .mode(ExecutionMode::DryRun)
+            .add_symbol("BTC-USD-PERP".to_string())
+            .enable_risk_checks(false)
+            .journal(journal.clone())
+            .build();
+
+        TradingEngine::new(config).with_strategy(Box::new(BuyTheDipStrategy {
+            config: StrategyConfig::new("BuyTheDip"),
+            discount: 1_000.0,
+            trailing_stop: Some(1_000.0),
+            placed,
+        }))
+    }
+
+    /// Stop-loss levels of the working orders
+    fn stop_levels(engine: &TradingEngine) -> Vec<Option<f64>> {
+        engine
+            .order_manager
+            .get_active_orders()
+            .iter()
+            .map(|order| order.stop_price)
+            .collect()
+    }
+
+    /// Crash with a resting order, restart to fill it, then restart again
+    ///
+    /// The entry carries a trailing stop, which keeps following the price
+    /// after the last restart. A compacted journal only recovers the working
+    /// state: the filled order, the replaced stop and the fill are archived.
+    async fn assert_recovers_from_journal(journal: crate::journal::JournalConfig, compacted: bool) {
+        // Killed while the limit order rests
+        let mut engine = journaled_dip_buyer(&journal, false);
+        engine.recover_journal().unwrap();
+        engine.process_candle(candle(50_000.0)).await.unwrap();
+        engine.compact_journal().unwrap();
+        assert_eq!(engine.execution_handler.resting_orders(), 1);
+        drop(engine);
+
+        // The recovered order keeps resting and fills once crossed
+        let mut engine = journaled_dip_buyer(&journal, true);
+        let (tx, rx) = mpsc::unbounded_channel();
+        tx.send(MarketEvent::Candle(ohlc(
+            49_800.0, 49_900.0, 48_800.0, 49_000.0,
+        )))
+        .unwrap();
+        drop(tx);
+        engine.start_with_receiver(rx).await.unwrap();
+
+        // The stop placed at the entry trailed the bar's high
+        let completed = engine.order_manager.get_completed_orders();
+        assert_eq!(completed.len(), 2);
+        assert_eq!(completed[0].status, OrderStatus::Filled);
+        assert_eq!(completed[0].average_fill_price, 49_000.0);
+        assert_eq!(completed[1].status, OrderStatus::Cancelled);
+        assert_eq!(stop_levels(&engine), vec![Some(48_900.0)]);
+        let cash = engine.position_tracker.available_cash();
+        let history = engine.order_manager.get_order_history().len();
+        drop(engine);
+
+        let mut engine = journaled_dip_buyer(&journal, true);
+        engine.recover_journal().unwrap();
+        assert_eq!(position_quantity(&engine), Some(1.0));
+        assert_eq!(engine.position_tracker.available_cash(), cash);
+        assert_eq!(engine.context.available_capital().unwrap(), cash);
+        assert_eq!(stop_levels(&engine), vec![Some(48_900.0)]);
+        assert_eq!(engine.brackets["BTC-USD-PERP"].extreme, Some(49_900.0));
+        if compacted {
+            assert!(engine.order_manager.get_completed_orders().is_empty());
+            assert!(engine.order_manager.get_order_history().is_empty());
+            assert!(engine.fills().is_empty());
+        } else {
+            assert_eq!(engine.order_manager.get_completed_orders().len(), 2);
+            assert_eq!(engine.order_manager.get_order_history().len(), history);
+            assert_eq!(engine.fills().len(), 1);
+        }
+
+        // The recovered stop keeps ratcheting, then closes the position
+        engine
+            .process_candle(ohlc(50_000.0, 50_500.0, 50_000.0, 50_400.0))
+            .await
+            .unwrap();
+        assert_eq!(stop_levels(&engine), vec![Some(49_500.0)]);
+        engine
+            .process_candle(ohlc(50_200.0, 50_300.0, 49_400.0, 49_500.0))
+            .await
+            .unwrap();
+        assert_eq!(position_quantity(&engine), None);
+        assert!(engine.order_manager.get_active_orders().is_empty());
+        assert!(engine.brackets.is_empty());
+    }
+
+    fn journal_dir() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("velora-engine-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[tokio::test]
+    async fn test_recovers_state_from_journal() {
+        let dir = journal_dir();
+        assert_recovers_from_journal(crate::journal::JournalConfig::new(&dir), false).await;
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_recovers_state_from_compacted_journal() {
+        let dir = journal_dir();
+        // Every event overflows the segment, so each one is compacted
+        let journal = crate::journal::JournalConfig::new(&dir)
+            .with_fsync(crate::journal::FsyncPolicy::Never)
+            .with_segment_bytes(1);
+        assert_recovers_from_journal(journal, true).await;
+        // The active segment, next to the archive of compacted ones
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stop_cancels_resting_dry_run_orders() {
+        let mut engine = dip_buyer();
+        engine.process_candle(candle(50_000.0)).await.unwrap();
+        assert_eq!(engine.execution_handler.resting_orders(), 1);
+
+        engine.stop().await.unwrap();
+
+        assert!(engine.order_manager.get_active_orders().is_empty());
+        assert_eq!(engine.execution_handler.resting_orders(), 0);
+        let completed = engine.order_manager.get_completed_orders();
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].status, OrderStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_close_signal_closes_position() {
+        let mut engine = scripted(vec![
+            Signal::buy("BTC-USD-PERP", 1.0),
+            Signal::Close {
+                symbol: "BTC-USD-PERP".to_string(),
+                quantity: Some(0.4),
+                metadata: std::collections::HashMap::new(),
+            },
+            Signal::close("BTC-USD-PERP"),
+            Signal::close("BTC-USD-PERP"),
+        ]);
+
+        engine.process_candle(candle(50_000.0)).await.unwrap();
+        assert_eq!(position_quantity(&engine), Some(1.0));
+
+        engine.process_candle(candle(51_000.0)).await.unwrap();
+        assert_eq!(position_quantity(&engine), Some(0.6));
+
+        engine.process_candle(candle(52_000.0)).await.unwrap();
+        assert_eq!(position_quantity(&engine), None);
+
+        // Nothing left to close
+        engine.process_candle(candle(53_000.0)).await.unwrap();
+
+        let completed = engine.order_manager.get_completed_orders();
+        assert_eq!(completed.len(), 3);
+        assert!(completed[1].reduce_only);
+        assert_eq!(completed[1].order_type, OrderType::Market);
+        assert_eq!(completed[1].side, velora_core::Side::Sell);
+        assert_eq!(completed[2].quantity, 0.6);
+        assert_eq!(completed[2].average_fill_price, 52_000.0);
+    }
+
+    #[tokio::test]
+    async fn test_modify_signal_maintains_exit_orders() {
+        let mut engine = scripted(vec![
+            Signal::buy("BTC-USD-PERP", 1.0),
+            modify(Some(49_000.0), Some(52_000.0)),
+            modify(Some(49_500.0), None),
+        ]);
+
+        engine.process_candle(candle(50_000.0)).await.unwrap();
+        engine.process_candle(candle(50_100.0)).await.unwrap();
+
+        let exits = engine.order_manager.get_active_orders();
+        assert_eq!(exits.len(), 2);
+        assert!(exits
+            .iter()
+            .all(|order| order.reduce_only && order.quantity == 1.0));
+
+        // Moving the stop replaces only the stop-loss order
+        engine.process_candle(candle(50_200.0)).await.unwrap();
+        let mut levels: Vec<_> = engine
+            .order_manager
+            .get_active_orders()
+            .iter()
+            .map(|order| (order.order_type, order.stop_price.or(order.price)))
+            .collect();
+        levels.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        assert_eq!(
+            levels,
+            vec![
+                (OrderType::StopMarket, Some(49_500.0)),
+                (OrderType::Limit, Some(52_000.0)),
+            ]
+        );
+
+        // Take-profit fills and cancels the stop-loss
+        engine
+            .process_candle(ohlc(50_200.0, 52_500.0, 50_100.0, 52_300.0))
+            .await
+            .unwrap();
+        assert_eq!(position_quantity(&engine), None);
+        assert!(engine.order_manager.get_active_orders().is_empty());
+        assert_eq!(engine.execution_handler.resting_orders(), 0);
+        assert!(engine.brackets.is_empty());
+
+        let target = engine
+            .order_manager
+            .get_completed_orders()
+            .iter()
+            .find(|order| order.status == OrderStatus::Filled && order.reduce_only)
+            .unwrap();
+        assert_eq!(target.average_fill_price, 52_000.0);
+    }
+
+    #[tokio::test]
+    async fn test_exit_orders_follow_position_size() {
+        let mut engine = scripted(vec![
+            Signal::buy("BTC-USD-PERP", 1.0),
+            modify(Some(49_000.0), None),
+            Signal::buy("BTC-USD-PERP", 1.0),
+        ]);
+
+        for price in [50_000.0, 50_100.0, 50_200.0] {
+            engine.process_candle(candle(price)).await.unwrap();
+        }
+
+        let exits = engine.order_manager.get_active_orders();
+        assert_eq!(exits.len(), 1);
+        assert_eq!(exits[0].quantity, 2.0);
+        assert_eq!(exits[0].stop_price, Some(49_000.0));
+
+        // Stop-loss triggers on a gap down
+        engine
+            .process_candle(ohlc(48_500.0, 48_600.0, 48_000.0, 48_200.0))
+            .await
+            .unwrap();
+        assert_eq!(position_quantity(&engine), None);
+        assert!(engine.brackets.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_modify_without_position_is_ignored() {
+        let mut engine = scripted(vec![modify(Some(49_000.0), None)]);
+
+        engine.process_candle(candle(50_000.0)).await.unwrap();
+
+        assert_eq!(engine.order_manager.total_orders(), 0);
+        assert!(engine.brackets.is_empty());
+    }
+
+    fn exit_levels(engine: &TradingEngine) -> Vec<(OrderType, Option<f64>)> {
+        let mut levels: Vec<_> = engine
+            .order_manager
+            .get_active_orders()
+            .iter()
+            .filter(|order| order.reduce_only)
+            .map(|order| (order.order_type, order.stop_price.or(order.price)))
+            .collect();
+        levels.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        levels
+    }
+
+    #[tokio::test]
+    async fn test_entry_signal_attaches_exits() {
+        let mut engine = scripted(vec![Signal::buy("BTC-USD-PERP", 1.0)
+            .with_stop_loss(49_000.0)
+            .with_take_profit(52_000.0)]);
+
+        engine.process_candle(candle(50_000.0)).await.unwrap();
+
+        assert_eq!(position_quantity(&engine), Some(1.0));
+        assert_eq!(
+            exit_levels(&engine),
+            vec![
+                (OrderType::StopMarket, Some(49_000.0)),
+                (OrderType::Limit, Some(52_000.0)),
+            ]
+        );
+
+        let position = engine
+            .context
+            .get_position("BTC-USD-PERP")
+            .unwrap()
+            .unwrap();
+        assert_eq!(position.stop_loss, Some(49_000.0));
+        assert_eq!(position.take_profit, Some(52_000.0));
+    }
+
+    #[tokio::test]
+    async fn test_entry_exits_wait_for_resting_entry() {
+        let mut entry = Signal::buy("BTC-USD-PERP", 1.0).with_stop_loss(49_000.0);
+        if let Signal::Buy { limit_price, .. } = &mut entry {
+            *limit_price = Some(49_500.0);
+        }
+        let mut engine = scripted(vec![entry]);
+
+        engine.process_candle(candle(50_000.0)).await.unwrap();
+        assert_eq!(position_quantity(&engine), None);
+        assert!(exit_levels(&engine).is_empty());
+        assert_eq!(engine.brackets.len(), 1);
+
+        engine
+            .process_candle(ohlc(50_000.0, 50_100.0, 49_400.0, 49_800.0))
+            .await
+            .unwrap();
+        assert_eq!(position_quantity(&engine), Some(1.0));
+        assert_eq!(
+            exit_levels(&engine),
+            vec![(OrderType::StopMarket, Some(49_000.0))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_trailing_stop_follows_price() {
+        let mut engine = scripted(vec![
+            Signal::buy("BTC-USD-PERP", 1.0).with_trailing_stop(500.0)
+        ]);
+
+        engine.process_candle(candle(50_000.0)).await.unwrap();
+        assert_eq!(
+            exit_levels(&engine),
+            vec![(OrderType::StopMarket, Some(49_500.0))]
+        );
+
+        // New high moves the stop up; a pullback leaves it there
+        engine
+            .process_candle(ohlc(50_000.0, 51_000.0, 50_200.0, 50_900.0))
+            .await
+            .unwrap();
+        engine
+            .process_candle(ohlc(50_900.0, 50_950.0, 50_600.0, 50_700.0))
+            .await
+            .unwrap();
+        assert_eq!(
+            exit_levels(&engine),
+            vec![(OrderType::StopMarket, Some(50_500.0))]
+        );
+        assert_eq!(
+            engine
+                .context
+                .get_position("BTC-USD-PERP")
+                .unwrap()
+                .unwrap()
+                .stop_loss,
+            Some(50_500.0)
+        );
+
+        engine
+            .process_candle(ohlc(50_700.0, 50_750.0, 50_300.0, 50_400.0))
+            .await
+            .unwrap();
+        assert_eq!(position_quantity(&engine), None);
+        assert!(engine.brackets.is_empty());
+
+        let stop = engine
+            .order_manager
+            .get_completed_orders()
+            .iter()
+            .find(|order| order.status == OrderStatus::Filled && order.reduce_only)
+            .unwrap();
+        assert_eq!(stop.average_fill_price, 50_500.0);
+    }
+
+    /// In-memory exchange streaming fixed candles and filling every order
+    mod mock {
+        use async_trait::async_trait;
+        use chrono::{DateTime, TimeZone, Utc};
+        use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+        use futures::Stream;
+        use std::sync::Mutex;
+        use velora_exchange::types::*;
+        use velora_exchange::{Account, Exchange, MarketData, Streaming, Trading};
+
+        type BoxStream<T> = Box<dyn Stream<Item = Result<T>> + Send + Unpin>;
+
+        pub struct MockExchange {
+            connected: bool,
+            closes: Vec<f64>,
+            pub placed: Mutex<Vec<NewOrder>>,
+            order_tx: Mutex<Option<UnboundedSender<Result<OrderUpdate>>>>,
+            order_rx: Mutex<Option<UnboundedReceiver<Result<OrderUpdate>>>>,
+        }
+
+        impl MockExchange {
+            /// One candle per minute with the given closes
+            pub fn new(closes: Vec<f64>) -> Self {
+                let (tx, rx) = unbounded();
+                Self {
+                    connected: false,
+                    closes,
+                    placed: Mutex::new(Vec::new()),
+                    order_tx: Mutex::new(Some(tx)),
+                    order_rx: Mutex::new(Some(rx)),
+                }
+            }
+        }
+
+        fn minute(n: usize) -> DateTime<Utc> {
+            Utc.timestamp_opt(1_700_000_000 + n as i64 * 60, 0).unwrap()
+        }
+
+        fn unsupported<T>() -> Result<T> {
+            Err(ExchangeError::Unsupported("mock".to_string()))
+        }
+
+        #[async_trait]
+        impl Exchange for MockExchange {
+            fn name(&self) -> &str {
+                "mock"
+            }
+
+            fn exchange_type(&self) -> ExchangeType {
+                ExchangeType::CEX
+            }
+
+            fn supported_instruments(&self) -> &[InstrumentType] {
+                &[InstrumentType::Perpetual]
+            }
+
+            async fn connect(&mut self) -> Result<()> {
+                self.connected = true;
+                Ok(())
+            }
+
+            async fn disconnect(&mut self) -> Result<()> {
+                self.connected = false;
+                Ok(())
+            }
+
+            fn is_connected(&self) -> bool {
+                self.connected
+            }
+
+            fn market_data(&self) -> &dyn MarketData {
+                self
+            }
+
+            fn trading(&self) -> &dyn Trading {
+                self
+            }
+
+            fn account(&self) -> &dyn Account {
+                self
+            }
+
+            fn streaming(&self) -> &dyn Streaming {
+                self
+            }
+        }
+
+        #[async_trait]
+        impl Trading for MockExchange {
+            /// Accept the order and fill it in full on the order stream
+            async fn place_order(&self, order: NewOrder) -> Result<Order> {
+                let mut placed = self.placed.lock().unwrap();
+                placed.push(order.clone());
+
+                let mut accepted = Order {
+                    order_id: placed.len().to_string(),
+                    client_order_id: None,
+                    symbol: order.symbol,
+                    side: order.side,
+                    order_type: order.order_type,
+                    time_in_force: TimeInForce::GoodTilCancel,
+                    quantity: order.quantity,
+                    price: order.price,
+                    stop_price: order.stop_price,
+                    status: OrderStatus::Open,
+                    filled_quantity: Decimal::ZERO,
+                    average_price: None,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                    reduce_only: None,
+                    position_side: None,
+                };
+
+                let mut filled = accepted.clone();
+                filled.status = OrderStatus::Filled;
+                filled.filled_quantity = filled.quantity;
+                filled.average_price = filled.price;
+
+                // Close the order stream after the first fill
+                if let Some(tx) = self.order_tx.lock().unwrap().take() {
+                    let _ = tx.unbounded_send(Ok(OrderUpdate {
+                        order: filled,
+                        timestamp: Utc::now(),
+                    }));
+                }
+
+                accepted.updated_at = Utc::now();
+                Ok(accepted)
+            }
+
+            async fn cancel_order(&self, _symbol: &Symbol, _order_id: &str) -> Result<Order> {
+                unsupported()
+            }
+
+            async fn cancel_all_orders(&self, _symbol: Option<&Symbol>) -> Result<Vec<Order>> {
+                unsupported()
+            }
+
+            async fn modify_order(
+                &self,
+                _order_id: &str,
+                _modifications: OrderModification,
+            ) -> Result<Order> {
+                unsupported()
+            }
+
+            async fn get_order(&self, _symbol: &Symbol, _order_id: &str) -> Result<Order> {
+                unsupported()
+            }
+
+            async fn get_open_orders(&self, _symbol: Option<&Symbol>) -> Result<Vec<Order>> {
+                unsupported()
+            }
+
+            async fn get_order_history(
+                &self,
+                _symbol: &Symbol,
+                _limit: Option<usize>,
+            ) -> Result<Vec<Order>> {
+                unsupported()
+            }
+        }
+
+        #[async_trait]
+        impl Streaming for MockExchange {
+            async fn subscribe_trades(&self, symbol: &Symbol) -> Result<BoxStream<StreamTrade>> {
+                let trade = StreamTrade {
+                    symbol: symbol.clone(),
+                    trade_id: "1".to_string(),
+                    price: Price::from(50_100.0),
+                    quantity: Decimal::ONE,
+                    side: Side::Buy,
+                    timestamp: Utc::now(),
+                    buyer_maker: false,
+                };
+                Ok(Box::new(futures::stream::iter(vec![Ok(trade)])))
+            }
+
+            async fn subscribe_orderbook(
+                &self,
+                _symbol: &Symbol,
+                _depth: Option<usize>,
+            ) -> Result<BoxStream<OrderBookUpdate>> {
+                unsupported()
+            }
+
+            async fn subscribe_ticker(&self, _symbol: &Symbol) -> Result<BoxStream<Ticker>> {
+                unsupported()
+            }
+
+            async fn subscribe_candles(
+                &self,
+                symbol: &Symbol,
+                interval: Interval,
+            ) -> Result<BoxStream<Candle>> {
+                let candles = self
+                    .closes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &close)| {
+                        Ok(Candle {
+                            symbol: symbol.clone(),
+                            interval,
+                            open: close.into(),
+                            high: close.into(),
+                            low: close.into(),
+                            close: close.into(),
+                            volume: Decimal::TEN,
+                            open_time: minute(i),
+                            close_time: minute(i + 1),
+                            trade_count: None,
+                            quote_volume: None,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                Ok(Box::new(futures::stream::iter(candles)))
+            }
+
+            async fn subscribe_orders(&self) -> Result<BoxStream<OrderUpdate>> {
+                match self.order_rx.lock().unwrap().take() {
+                    Some(rx) => Ok(Box::new(rx)),
+                    None => unsupported(),
+                }
+            }
+
+            async fn subscribe_positions(&self) -> Result<BoxStream<PositionUpdate>> {
+                unsupported()
+            }
+
+            async fn subscribe_balances(&self) -> Result<BoxStream<BalanceUpdate>> {
+                unsupported()
+            }
+
+            async fn subscribe_user_data(&self) -> Result<BoxStream<UserDataEvent>> {
+                unsupported()
+            }
+        }
+
+        #[async_trait]
+        impl MarketData for MockExchange {
+            async fn get_markets(&self) -> Result<Vec<Market>> {
+                unsupported()
+            }
+
+            async fn get_market(&self, _symbol: &Symbol) -> Result<Market> {
+                unsupported()
+            }
+
+            async fn get_ticker(&self, _symbol: &Symbol) -> Result<Ticker> {
+                unsupported()
+            }
+
+            async fn get_tickers(&self) -> Result<Vec<Ticker>> {
+                unsupported()
+            }
+
+            async fn get_orderbook(
+                &self,
+                _symbol: &Symbol,
+                _depth: Option<usize>,
+            ) -> Result<OrderBook> {
+                unsupported()
+            }
+
+            async fn get_recent_trades(
+                &self,
+                _symbol: &Symbol,
+                _limit: Option<usize>,
+            ) -> Result<Vec<Trade>> {
+                unsupported()
+            }
+
+            async fn get_candles(
+                &self,
+                _symbol: &Symbol,
+                _interval: Interval,
+                _start_time: Option<DateTime<Utc>>,
+                _end_time: Option<DateTime<Utc>>,
+                _limit: Option<usize>,
+            ) -> Result<Vec<Candle>> {
+                unsupported()
+            }
+
+            async fn get_funding_rate(&self, _symbol: &Symbol) -> Result<Option<FundingRate>> {
+                unsupported()
+            }
+
+            async fn get_funding_rate_history(
+                &self,
+                _symbol: &Symbol,
+                _start_time: Option<DateTime<Utc>>,
+                _end_time: Option<DateTime<Utc>>,
+                _limit: Option<usize>,
+            ) -> Result<Vec<FundingRate>> {
+                unsupported()
+            }
+        }
+
+        #[async_trait]
+        impl Account for MockExchange {
+            async fn get_account_info(&self) -> Result<AccountInfo> {
+                unsupported()
+            }
+
+            async fn get_balances(&self) -> Result<Vec<Balance>> {
+                unsupported()
+            }
+
+            async fn get_balance(&self, _asset: &str) -> Result<Balance> {
+                unsupported()
+            }
+
+            async fn get_positions(&self) -> Result<Vec<Position>> {
+                unsupported()
+            }
+
+            async fn get_position(&self, _symbol: &Symbol) -> Result<Option<Position>> {
+                unsupported()
+            }
+
+            async fn get_trade_history(
+                &self,
+                _symbol: Option<&Symbol>,
+                _limit: Option<usize>,
+            ) -> Result<Vec<TradeExecution>> {
+                unsupported()
+            }
+        }
+    }
+
+    fn exchange_engine(mode: ExecutionMode) -> TradingEngine {
+        let config = EngineConfig::builder()
+            .mode(mode)
+            .add_symbol("BTC-USD-PERP".to_string())
+            .enable_risk_checks(false)
+            .max_reconnect_attempts(0)
+            .build();
+
+        TradingEngine::new(config).with_strategy(Box::new(AlwaysBuyStrategy {
+            config: StrategyConfig::new("AlwaysBuy"),
+            quantity: Some(1.0),
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_live_mode_routes_orders_to_exchange() {
+        // Two candle updates: only the first candle closes
+        let mut engine = exchange_engine(ExecutionMode::Live)
+            .with_exchange(Box::new(mock::MockExchange::new(vec![50_000.0, 50_200.0])));
+
+        engine.start().await.unwrap();
+
+        let exchange = engine.execution_handler.exchange().unwrap();
+        assert!(!exchange.is_connected());
+
+        let completed = engine.order_manager.get_completed_orders();
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].status, OrderStatus::Filled);
+        assert_eq!(completed[0].filled_quantity, 1.0);
+        assert_eq!(completed[0].average_fill_price, 50_000.0);
+        assert!(engine.order_manager.get_pending_orders().is_empty());
+
+        let position = engine
+            .position_tracker
+            .get_position("BTC-USD-PERP")
+            .unwrap();
+        assert_eq!(position.quantity, 1.0);
+        assert_eq!(position.average_entry_price, 50_000.0);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_streams_exchange_market_data() {
+        let mut engine = exchange_engine(ExecutionMode::DryRun)
+            .with_exchange(Box::new(mock::MockExchange::new(vec![50_000.0, 50_200.0])));
+
+        engine.start().await.unwrap();
+
+        // Signals are simulated, not sent to the exchange
+        let orders = engine.order_manager.get_completed_orders();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].status, OrderStatus::Filled);
+        assert_eq!(orders[0].average_fill_price, 50_000.0);
+    }
+
+    #[tokio::test]
+    async fn test_live_mode_requires_exchange() {
+        let mut engine = exchange_engine(ExecutionMode::Live);
+
+        assert!(matches!(
+            engine.start().await,
+            Err(EngineError::InvalidConfig(_))
+        ));
+        assert_eq!(engine.state, EngineState::Idle);
     }
 }
@@ -72,6 +72,10 @@ pub enum EngineError {
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
+    /// Journal could not be read or written
+    #[error("Journal error: {0}")]
+    Journal(String),
+
     /// Generic error
     #[error("{0}")]
     Other(String),
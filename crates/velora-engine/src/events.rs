@@ -3,7 +3,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use velora_core::{Candle, Side};
+use velora_core::{Candle, OrderBook, Side, Tick, Trade};
 
 /// Order ID type alias
 pub type OrderId = Uuid;
@@ -14,9 +14,29 @@ pub enum MarketEvent {
     /// New candle received
     Candle(Candle),
 
+    /// Last traded price update
+    Tick(Tick),
+
+    /// Trade printed on the exchange
+    Trade(Trade),
+
+    /// Order book snapshot
+    OrderBook(OrderBook),
+
+    /// Funding settlement of a perpetual
+    ///
+    /// Open positions in the symbol pay (or receive) the rate on their notional.
+    Funding(velora_exchange::FundingRate),
+
     /// Order status update from exchange
     OrderUpdate(OrderUpdate),
 
+    /// Order as reported by the exchange's order stream
+    ///
+    /// Mapped to the engine's order ID when processed, so updates that
+    /// arrive before the placement response has been handled are not lost.
+    ExchangeOrderUpdate(Box<velora_exchange::Order>),
+
     /// Market data error
     Error(String),
 
@@ -1,12 +1,17 @@
-//! Order execution handling (dry-run simulation for now)
+//! Order execution handling (exchange routing in live mode, simulation in dry-run)
 
 use crate::config::ExecutionMode;
 use crate::errors::{EngineError, EngineResult};
 use crate::events::{Fill, OrderId, OrderStatus, OrderUpdate};
 use crate::order_manager::Order;
-use chrono::Utc;
+use crate::simulation::{PriceBar, SimulatedBook};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::{debug, info, warn};
+use velora_core::{Candle, LiveClock, OrderType, SharedClock, Side, Tick};
+use velora_exchange::{Exchange, FeeModel, Liquidity, NewOrder, Price, Symbol};
 
 /// Handles order execution and fills
 pub struct ExecutionHandler {
@@ -16,11 +21,67 @@ pub struct ExecutionHandler {
     /// Fills generated
     fills: Vec<Fill>,
 
-    /// Simulated order book for dry-run mode
+    /// Order updates generated (drained by the engine)
+    updates: Vec<OrderUpdate>,
+
+    /// Last known prices for dry-run mode
     simulated_prices: HashMap<String, f64>,
 
-    /// Commission rate (as fraction, e.g., 0.001 = 0.1%)
-    commission_rate: f64,
+    /// Resting limit and stop orders for dry-run mode
+    book: SimulatedBook,
+
+    /// Fee schedule of simulated and estimated fills
+    fees: FeeModel,
+
+    /// Notional filled so far, for volume fee tiers
+    traded_volume: f64,
+
+    /// Exchange orders are routed to in live mode
+    exchange: Option<Arc<dyn Exchange>>,
+
+    /// Orders placed on the exchange, keyed by exchange order ID
+    live_orders: HashMap<String, LiveOrder>,
+
+    /// Time source for simulated fills and cancellations
+    clock: SharedClock,
+}
+
+/// Engine-side view of an order resting on the exchange
+#[derive(Debug, Clone)]
+struct LiveOrder {
+    /// Internal order ID
+    order_id: OrderId,
+
+    /// Symbol as the engine knows it
+    symbol: String,
+
+    /// Buy or Sell
+    side: velora_core::Side,
+
+    /// Quantity filled so far
+    filled_quantity: f64,
+
+    /// Average fill price so far
+    average_price: f64,
+}
+
+/// Convert an engine quantity or price to a decimal
+fn to_decimal(value: f64, what: &str) -> EngineResult<Decimal> {
+    Decimal::try_from(value)
+        .map(|d| d.normalize())
+        .map_err(|_| EngineError::OrderError(format!("Invalid {what}: {value}")))
+}
+
+/// Map an exchange order status to the engine's
+fn convert_status(status: velora_exchange::OrderStatus) -> OrderStatus {
+    use velora_exchange::OrderStatus as Exchange;
+    match status {
+        Exchange::Open | Exchange::Pending => OrderStatus::Submitted,
+        Exchange::PartiallyFilled => OrderStatus::PartiallyFilled,
+        Exchange::Filled => OrderStatus::Filled,
+        Exchange::Cancelled | Exchange::Expired => OrderStatus::Cancelled,
+        Exchange::Rejected => OrderStatus::Rejected,
+    }
 }
 
 impl ExecutionHandler {
@@ -29,30 +90,172 @@ impl ExecutionHandler {
         Self {
             mode,
             fills: Vec::new(),
+            updates: Vec::new(),
             simulated_prices: HashMap::new(),
-            commission_rate: 0.001, // 0.1% default
+            book: SimulatedBook::default(),
+            fees: FeeModel::flat(0.001), // 0.1% default
+            traded_volume: 0.0,
+            exchange: None,
+            live_orders: HashMap::new(),
+            clock: LiveClock::shared(),
         }
     }
 
-    /// Set commission rate
+    /// Stamp simulated fills and cancellations with a clock
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Set commission rate, charged on every fill
     pub fn with_commission_rate(mut self, rate: f64) -> Self {
-        self.commission_rate = rate;
+        self.fees = FeeModel::flat(rate);
+        self
+    }
+
+    /// Set the exchange fee schedule
+    ///
+    /// Dry-run orders filling on submission are takers; resting limit orders
+    /// are makers when price action fills them, stops are takers.
+    pub fn with_fee_model(mut self, fees: FeeModel) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    /// Commission of a fill, counting its volume towards the fee tiers
+    fn commission(&mut self, symbol: &str, liquidity: Liquidity, quantity: f64, price: f64) -> f64 {
+        let notional = quantity * price;
+        let commission = self
+            .fees
+            .fee(symbol, liquidity, notional, self.traded_volume);
+        self.traded_volume += notional.abs();
+        commission
+    }
+
+    /// Attach the exchange used for live order routing
+    pub fn with_exchange(mut self, exchange: Box<dyn Exchange>) -> Self {
+        self.exchange = Some(Arc::from(exchange));
         self
     }
 
+    /// Attached exchange, if any
+    pub fn exchange(&self) -> Option<&Arc<dyn Exchange>> {
+        self.exchange.as_ref()
+    }
+
+    /// Connect the attached exchange (no-op without one)
+    ///
+    /// Must be called before the exchange is shared with market data feeds.
+    pub async fn connect(&mut self) -> EngineResult<()> {
+        let Some(exchange) = self.exchange.as_mut() else {
+            return Ok(());
+        };
+
+        let exchange = Arc::get_mut(exchange).ok_or_else(|| {
+            EngineError::ConnectionError("Exchange is in use and cannot be connected".to_string())
+        })?;
+        if !exchange.is_connected() {
+            info!("Connecting to {}", exchange.name());
+            exchange
+                .connect()
+                .await
+                .map_err(|e| EngineError::ConnectionError(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Disconnect the attached exchange
+    ///
+    /// Skipped while market data feeds still hold the exchange.
+    pub async fn disconnect(&mut self) -> EngineResult<()> {
+        let Some(exchange) = self.exchange.as_mut().and_then(Arc::get_mut) else {
+            return Ok(());
+        };
+
+        if exchange.is_connected() {
+            info!("Disconnecting from {}", exchange.name());
+            exchange
+                .disconnect()
+                .await
+                .map_err(|e| EngineError::ConnectionError(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
     /// Submit an order for execution
     pub async fn submit_order(&mut self, order: &Order) -> EngineResult<OrderId> {
         match self.mode {
-            ExecutionMode::Live => {
-                // TODO: Integrate with real exchange API
-                warn!("Live trading not yet implemented - falling back to dry-run");
-                self.submit_order_dry_run(order).await
-            }
+            ExecutionMode::Live => self.submit_order_live(order).await,
             ExecutionMode::DryRun => self.submit_order_dry_run(order).await,
         }
     }
 
+    /// Exchange the order is routed to in live mode
+    fn live_exchange(&self) -> EngineResult<Arc<dyn Exchange>> {
+        self.exchange
+            .clone()
+            .ok_or_else(|| EngineError::InvalidConfig("Live mode requires an exchange".to_string()))
+    }
+
+    /// Submit order to the exchange
+    async fn submit_order_live(&mut self, order: &Order) -> EngineResult<OrderId> {
+        let exchange = self.live_exchange()?;
+
+        let new_order = NewOrder {
+            symbol: Symbol::new(order.symbol.as_str()),
+            side: order.side,
+            order_type: order.order_type,
+            time_in_force: None,
+            quantity: to_decimal(order.quantity, "quantity")?,
+            price: order.price.map(Price::from),
+            stop_price: order.stop_price.map(Price::from),
+            // Client order ID formats differ per exchange; orders are tracked by exchange ID
+            client_order_id: None,
+            reduce_only: order.reduce_only.then_some(true),
+            position_side: None,
+        };
+
+        info!(
+            order_id = %order.id,
+            exchange = exchange.name(),
+            symbol = %order.symbol,
+            side = ?order.side,
+            quantity = order.quantity,
+            price = ?order.price,
+            "Submitting order to exchange"
+        );
+
+        let placed = exchange
+            .trading()
+            .place_order(new_order)
+            .await
+            .map_err(|e| EngineError::Exchange(e.to_string()))?;
+
+        self.live_orders.insert(
+            placed.order_id.clone(),
+            LiveOrder {
+                order_id: order.id,
+                symbol: order.symbol.clone(),
+                side: order.side,
+                filled_quantity: 0.0,
+                average_price: 0.0,
+            },
+        );
+        // The response may already carry fills (e.g. marketable orders)
+        if let Some(update) = self.apply_exchange_order(&placed) {
+            self.updates.push(update);
+        }
+
+        Ok(order.id)
+    }
+
     /// Submit order in dry-run mode (simulated execution)
+    ///
+    /// Orders marketable against the last known price fill immediately in
+    /// full; everything else rests in the simulated book until price action
+    /// fills or triggers it.
     async fn submit_order_dry_run(&mut self, order: &Order) -> EngineResult<OrderId> {
         info!(
             order_id = %order.id,
@@ -60,112 +263,334 @@ impl ExecutionHandler {
             side = ?order.side,
             quantity = order.quantity,
             price = ?order.price,
+            stop_price = ?order.stop_price,
             "[DRY-RUN] Simulating order submission"
         );
 
-        // In dry-run, we simulate instant fills for market orders
-        // For limit orders, we'd need to wait for price to reach the limit
-
-        match order.order_type {
-            velora_core::OrderType::Market => {
-                // Get current price (or use order price if available)
-                let fill_price = self
-                    .simulated_prices
-                    .get(&order.symbol)
-                    .copied()
-                    .or(order.price)
-                    .ok_or_else(|| {
-                        EngineError::MarketDataError(format!(
-                            "No price available for {}",
-                            order.symbol
-                        ))
-                    })?;
-
-                // Create fill immediately
-                let commission = order.quantity * fill_price * self.commission_rate;
-
-                let fill = Fill {
-                    order_id: order.id,
-                    symbol: order.symbol.clone(),
-                    side: order.side,
-                    quantity: order.quantity,
-                    price: fill_price,
-                    commission,
-                    timestamp: Utc::now(),
-                };
-
-                self.fills.push(fill.clone());
+        let last_price = self.simulated_prices.get(&order.symbol).copied();
 
-                debug!(
-                    order_id = %order.id,
-                    fill_price = fill_price,
-                    commission = commission,
-                    "[DRY-RUN] Order filled"
-                );
-
-                Ok(order.id)
-            }
-            velora_core::OrderType::Limit => {
-                // For limit orders in dry-run, we just accept them
-                // In a real implementation, we'd track them and fill when price is hit
-                info!(
-                    order_id = %order.id,
-                    "[DRY-RUN] Limit order accepted (instant fill simulation)"
-                );
+        // Stops already through their stop price act as the order they become
+        let triggered = match (order.stop_price, last_price) {
+            (Some(stop), Some(price)) => match order.side {
+                Side::Buy => price >= stop,
+                Side::Sell => price <= stop,
+            },
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
 
-                let fill_price = order.price.ok_or_else(|| {
+        let fill_price = match order.order_type {
+            OrderType::Market => Some(last_price.or(order.price).ok_or_else(|| {
+                EngineError::MarketDataError(format!("No price available for {}", order.symbol))
+            })?),
+            OrderType::StopMarket => last_price.filter(|_| triggered),
+            OrderType::Limit | OrderType::StopLimit => {
+                let limit = order.price.ok_or_else(|| {
                     EngineError::OrderError("Limit order must have price".to_string())
                 })?;
+                last_price.filter(|&price| {
+                    triggered
+                        && match order.side {
+                            Side::Buy => price <= limit,
+                            Side::Sell => price >= limit,
+                        }
+                })
+            }
+        };
 
-                let commission = order.quantity * fill_price * self.commission_rate;
+        let Some(fill_price) = fill_price else {
+            debug!(order_id = %order.id, triggered, "[DRY-RUN] Order resting");
+            self.book.insert(order.clone(), triggered);
+            return Ok(order.id);
+        };
 
-                let fill = Fill {
-                    order_id: order.id,
-                    symbol: order.symbol.clone(),
-                    side: order.side,
-                    quantity: order.quantity,
-                    price: fill_price,
-                    commission,
-                    timestamp: Utc::now(),
-                };
+        let commission =
+            self.commission(&order.symbol, Liquidity::Taker, order.quantity, fill_price);
 
-                self.fills.push(fill);
-                Ok(order.id)
-            }
-            velora_core::OrderType::StopLimit | velora_core::OrderType::StopMarket => {
-                // Stop orders not yet implemented in dry-run
-                Err(EngineError::OrderError(
-                    "Stop orders not yet implemented in dry-run mode".to_string(),
-                ))
-            }
-        }
+        let fill = Fill {
+            order_id: order.id,
+            symbol: order.symbol.clone(),
+            side: order.side,
+            quantity: order.quantity,
+            price: fill_price,
+            commission,
+            timestamp: self.clock.now(),
+        };
+
+        self.updates.push(self.create_fill_update(order, &fill));
+        self.fills.push(fill);
+
+        debug!(
+            order_id = %order.id,
+            fill_price = fill_price,
+            commission = commission,
+            "[DRY-RUN] Order filled"
+        );
+
+        Ok(order.id)
     }
 
     /// Cancel an order
     pub async fn cancel_order(&mut self, order_id: OrderId) -> EngineResult<()> {
         match self.mode {
             ExecutionMode::Live => {
-                // TODO: Real exchange cancellation
-                warn!("[DRY-RUN] Would cancel order: {}", order_id);
+                let exchange = self.live_exchange()?;
+                let (exchange_id, symbol) = self
+                    .live_orders
+                    .iter()
+                    .find(|(_, live)| live.order_id == order_id)
+                    .map(|(id, live)| (id.clone(), live.symbol.clone()))
+                    .ok_or_else(|| EngineError::OrderNotFound(order_id.to_string()))?;
+
+                info!(
+                    "Cancelling order {} ({} on exchange)",
+                    order_id, exchange_id
+                );
+                let cancelled = exchange
+                    .trading()
+                    .cancel_order(&Symbol::new(symbol), &exchange_id)
+                    .await
+                    .map_err(|e| EngineError::Exchange(e.to_string()))?;
+
+                if let Some(update) = self.apply_exchange_order(&cancelled) {
+                    self.updates.push(update);
+                }
                 Ok(())
             }
             ExecutionMode::DryRun => {
                 info!("[DRY-RUN] Simulating order cancellation: {}", order_id);
+                let (filled_quantity, average_price) = self
+                    .book
+                    .cancel(order_id)
+                    .ok_or_else(|| EngineError::OrderNotFound(order_id.to_string()))?;
+
+                self.updates.push(OrderUpdate {
+                    order_id,
+                    status: OrderStatus::Cancelled,
+                    filled_quantity,
+                    average_price,
+                    timestamp: self.clock.now(),
+                    error_message: None,
+                });
                 Ok(())
             }
         }
     }
 
+    /// Apply an order reported by the exchange
+    ///
+    /// Returns the engine update for orders placed through this handler (None
+    /// for foreign orders) and queues a fill for any newly filled quantity.
+    /// Exchange order updates carry no fees, so commission is estimated with
+    /// the configured fee schedule, taking limit orders as makers.
+    pub fn apply_exchange_order(&mut self, order: &velora_exchange::Order) -> Option<OrderUpdate> {
+        self.rekey_live_order(order);
+        let Some(live) = self.live_orders.get_mut(&order.order_id) else {
+            debug!("Ignoring update for untracked order {}", order.order_id);
+            return None;
+        };
+
+        let status = convert_status(order.status);
+        let filled_quantity = order
+            .filled_quantity
+            .to_f64()
+            .unwrap_or(live.filled_quantity);
+        let average_price = order
+            .average_price
+            .map(|p| p.into_inner())
+            .or(order.price.map(|p| p.into_inner()))
+            .unwrap_or(live.average_price);
+
+        let delta = filled_quantity - live.filled_quantity;
+        if delta > 0.0 {
+            // Price of the new fills from the change in average price
+            let notional =
+                average_price * filled_quantity - live.average_price * live.filled_quantity;
+            let price = if notional > 0.0 {
+                notional / delta
+            } else {
+                average_price
+            };
+
+            let liquidity = match order.order_type {
+                OrderType::Limit => Liquidity::Maker,
+                _ => Liquidity::Taker,
+            };
+            let symbol = live.symbol.clone();
+            live.filled_quantity = filled_quantity;
+            live.average_price = average_price;
+            let (order_id, side) = (live.order_id, live.side);
+
+            let commission = self.commission(&symbol, liquidity, delta, price);
+            self.fills.push(Fill {
+                order_id,
+                symbol,
+                side,
+                quantity: delta,
+                price,
+                commission,
+                timestamp: order.updated_at,
+            });
+        }
+        let live = self.live_orders.get(&order.order_id)?;
+
+        let update = OrderUpdate {
+            order_id: live.order_id,
+            status,
+            filled_quantity: live.filled_quantity,
+            average_price: live.average_price,
+            timestamp: order.updated_at,
+            error_message: (status == OrderStatus::Rejected)
+                .then(|| "Rejected by exchange".to_string()),
+        };
+
+        if !order.is_active() {
+            self.live_orders.remove(&order.order_id);
+        }
+
+        Some(update)
+    }
+
+    /// Track a live order under the order ID its updates carry
+    ///
+    /// Some exchanges (Lighter) only assign the order ID once the order
+    /// executes, so the placement response identifies it by its client order
+    /// ID. The first update carrying both moves the order to the exchange ID.
+    fn rekey_live_order(&mut self, order: &velora_exchange::Order) {
+        if self.live_orders.contains_key(&order.order_id) {
+            return;
+        }
+        let Some(client_order_id) = order.client_order_id.as_deref() else {
+            return;
+        };
+        if let Some(live) = self.live_orders.remove(client_order_id) {
+            debug!(
+                "Tracking order {} under exchange ID {}",
+                client_order_id, order.order_id
+            );
+            self.live_orders.insert(order.order_id.clone(), live);
+        }
+    }
+
+    /// Exchange ID a live order was placed under
+    pub fn exchange_order_id(&self, order_id: OrderId) -> Option<&str> {
+        self.live_orders
+            .iter()
+            .find(|(_, live)| live.order_id == order_id)
+            .map(|(exchange_id, _)| exchange_id.as_str())
+    }
+
+    /// Working live orders, as (internal ID, exchange ID)
+    pub fn exchange_orders(&self) -> Vec<(OrderId, String)> {
+        self.live_orders
+            .iter()
+            .map(|(exchange_id, live)| (live.order_id, exchange_id.clone()))
+            .collect()
+    }
+
+    /// Resume tracking a working order recovered after a restart
+    ///
+    /// Dry-run orders rest in the simulated book again, keeping what they
+    /// already filled. Live orders are matched to exchange updates through
+    /// the exchange ID they were placed under; without one, their updates
+    /// cannot be followed.
+    pub fn restore_order(&mut self, order: &Order, exchange_order_id: Option<String>) {
+        match self.mode {
+            ExecutionMode::DryRun => {
+                let triggered = order.filled_quantity > 0.0;
+                self.book.insert(order.clone(), triggered);
+            }
+            ExecutionMode::Live => {
+                let Some(exchange_order_id) = exchange_order_id else {
+                    warn!("No exchange ID recorded for order {}", order.id);
+                    return;
+                };
+                self.live_orders.insert(
+                    exchange_order_id,
+                    LiveOrder {
+                        order_id: order.id,
+                        symbol: order.symbol.clone(),
+                        side: order.side,
+                        filled_quantity: order.filled_quantity,
+                        average_price: order.average_fill_price,
+                    },
+                );
+            }
+        }
+    }
+
     /// Update simulated market price (for dry-run mode)
     pub fn update_market_price(&mut self, symbol: String, price: f64) {
         self.simulated_prices.insert(symbol, price);
     }
 
+    /// Match resting dry-run orders against a candle and record its close
+    pub fn on_candle(&mut self, candle: &Candle) {
+        self.match_book(candle.symbol.as_str(), &PriceBar::from(candle));
+        self.update_market_price(candle.symbol.to_string(), candle.close.into_inner());
+    }
+
+    /// Match resting dry-run orders against a trade and record its price
+    pub fn on_tick(&mut self, tick: &Tick) {
+        self.match_book(tick.symbol.as_str(), &PriceBar::from(tick));
+        self.update_market_price(tick.symbol.to_string(), tick.price.into_inner());
+    }
+
+    /// Number of orders resting in the dry-run book
+    pub fn resting_orders(&self) -> usize {
+        self.book.len()
+    }
+
+    /// Queue fills and updates for resting orders the bar executes
+    fn match_book(&mut self, symbol: &str, bar: &PriceBar) {
+        if self.mode != ExecutionMode::DryRun {
+            return;
+        }
+
+        for fill in self.book.match_bar(symbol, bar) {
+            debug!(
+                order_id = %fill.order_id,
+                quantity = fill.quantity,
+                price = fill.price,
+                "[DRY-RUN] Resting order filled"
+            );
+
+            self.updates.push(OrderUpdate {
+                order_id: fill.order_id,
+                status: if fill.complete {
+                    OrderStatus::Filled
+                } else {
+                    OrderStatus::PartiallyFilled
+                },
+                filled_quantity: fill.filled_quantity,
+                average_price: fill.average_price,
+                timestamp: fill.timestamp,
+                error_message: None,
+            });
+            let commission =
+                self.commission(&fill.symbol, fill.liquidity, fill.quantity, fill.price);
+            self.fills.push(Fill {
+                order_id: fill.order_id,
+                symbol: fill.symbol,
+                side: fill.side,
+                quantity: fill.quantity,
+                price: fill.price,
+                commission,
+                timestamp: fill.timestamp,
+            });
+        }
+    }
+
     /// Get pending fills (and clear them)
     pub fn drain_fills(&mut self) -> Vec<Fill> {
         std::mem::take(&mut self.fills)
     }
 
+    /// Get pending order updates (and clear them)
+    pub fn drain_updates(&mut self) -> Vec<OrderUpdate> {
+        std::mem::take(&mut self.updates)
+    }
+
     /// Generate order update for a filled order
     pub fn create_fill_update(&self, order: &Order, fill: &Fill) -> OrderUpdate {
         OrderUpdate {
@@ -183,11 +608,30 @@ impl ExecutionHandler {
     }
 
     /// Sync orders with exchange (for live mode)
-    pub async fn sync_orders(&self) -> EngineResult<Vec<OrderUpdate>> {
+    ///
+    /// Polls every tracked order; fills found on the way are queued as usual.
+    pub async fn sync_orders(&mut self) -> EngineResult<Vec<OrderUpdate>> {
         match self.mode {
             ExecutionMode::Live => {
-                // TODO: Fetch order updates from exchange
-                Ok(Vec::new())
+                let exchange = self.live_exchange()?;
+                let tracked: Vec<(String, String)> = self
+                    .live_orders
+                    .iter()
+                    .map(|(id, live)| (id.clone(), live.symbol.clone()))
+                    .collect();
+
+                let mut updates = Vec::new();
+                for (exchange_id, symbol) in tracked {
+                    match exchange
+                        .trading()
+                        .get_order(&Symbol::new(symbol), &exchange_id)
+                        .await
+                    {
+                        Ok(order) => updates.extend(self.apply_exchange_order(&order)),
+                        Err(e) => warn!("Failed to sync order {}: {}", exchange_id, e),
+                    }
+                }
+                Ok(updates)
             }
             ExecutionMode::DryRun => {
                 // In dry-run, no external sync needed
@@ -201,7 +645,108 @@ impl ExecutionHandler {
 mod tests {
     use super::*;
     use crate::order_manager::Order;
-    use velora_core::{OrderType, Side};
+    use chrono::{DateTime, Utc};
+    use velora_core::SimulatedClock;
+
+    #[tokio::test]
+    async fn test_dry_run_fills_use_clock() {
+        let clock = Arc::new(SimulatedClock::new(DateTime::UNIX_EPOCH));
+        let mut handler = ExecutionHandler::new(ExecutionMode::DryRun).with_clock(clock.clone());
+        handler.update_market_price("BTC-USD-PERP".to_string(), 50_000.0);
+
+        let order = Order::new(
+            "BTC-USD-PERP".to_string(),
+            Side::Buy,
+            OrderType::Market,
+            0.1,
+            None,
+        );
+        handler.submit_order(&order).await.unwrap();
+        assert_eq!(handler.drain_fills()[0].timestamp, DateTime::UNIX_EPOCH);
+
+        let resting = Order::new(
+            "BTC-USD-PERP".to_string(),
+            Side::Buy,
+            OrderType::Limit,
+            0.1,
+            Some(49_000.0),
+        );
+        handler.submit_order(&resting).await.unwrap();
+        clock.advance(chrono::Duration::seconds(5));
+        handler.cancel_order(resting.id).await.unwrap();
+
+        let updates = handler.drain_updates();
+        let cancel = updates.last().unwrap();
+        assert_eq!(cancel.status, OrderStatus::Cancelled);
+        assert_eq!(
+            cancel.timestamp,
+            DateTime::UNIX_EPOCH + chrono::Duration::seconds(5)
+        );
+    }
+
+    fn exchange_order(
+        order_id: &str,
+        client_order_id: &str,
+        status: velora_exchange::OrderStatus,
+        filled: Decimal,
+    ) -> velora_exchange::Order {
+        let now = Utc::now();
+        velora_exchange::Order {
+            order_id: order_id.to_string(),
+            client_order_id: Some(client_order_id.to_string()),
+            symbol: Symbol::new("ETH"),
+            side: Side::Buy,
+            order_type: OrderType::Limit,
+            time_in_force: velora_exchange::TimeInForce::GoodTilCancel,
+            quantity: Decimal::ONE,
+            price: Some(Price::from(3_000.0)),
+            stop_price: None,
+            status,
+            filled_quantity: filled,
+            average_price: (filled > Decimal::ZERO).then(|| Price::from(3_000.0)),
+            created_at: now,
+            updated_at: now,
+            reduce_only: None,
+            position_side: None,
+        }
+    }
+
+    #[test]
+    fn test_live_order_follows_assigned_exchange_id() {
+        use velora_exchange::OrderStatus as Exchange;
+
+        // Placed on Lighter: the response identifies the order by its client index
+        let mut handler = ExecutionHandler::new(ExecutionMode::Live);
+        let order = Order::new(
+            "ETH".to_string(),
+            Side::Buy,
+            OrderType::Limit,
+            1.0,
+            Some(3_000.0),
+        );
+        handler.restore_order(&order, Some("55".to_string()));
+
+        // Stream updates carry the assigned order index alongside it
+        let partial = exchange_order(
+            "281474976710657",
+            "55",
+            Exchange::PartiallyFilled,
+            Decimal::new(4, 1),
+        );
+        let update = handler.apply_exchange_order(&partial).unwrap();
+        assert_eq!(update.order_id, order.id);
+        assert_eq!(update.status, OrderStatus::PartiallyFilled);
+        assert_eq!(handler.exchange_order_id(order.id), Some("281474976710657"));
+
+        let filled = exchange_order("281474976710657", "55", Exchange::Filled, Decimal::ONE);
+        let update = handler.apply_exchange_order(&filled).unwrap();
+        assert_eq!(update.status, OrderStatus::Filled);
+
+        let fills = handler.drain_fills();
+        assert_eq!(fills.len(), 2);
+        assert!((fills.iter().map(|fill| fill.quantity).sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(handler.exchange_orders().is_empty());
+    }
 
     #[tokio::test]
     async fn test_submit_market_order_dry_run() {
@@ -233,9 +778,22 @@ mod tests {
         assert_eq!(fill.commission, 5.0); // 0.1 * 50,000 * 0.001
     }
 
+    fn candle(open: f64, high: f64, low: f64, close: f64, volume: f64) -> Candle {
+        Candle {
+            symbol: velora_core::Symbol::new("BTC-USD-PERP"),
+            open: open.into(),
+            high: high.into(),
+            low: low.into(),
+            close: close.into(),
+            volume: volume.into(),
+            timestamp: Utc::now(),
+        }
+    }
+
     #[tokio::test]
     async fn test_submit_limit_order_dry_run() {
         let mut handler = ExecutionHandler::new(ExecutionMode::DryRun).with_commission_rate(0.001);
+        handler.update_market_price("BTC-USD-PERP".to_string(), 50_000.0);
 
         // Create limit order
         let order = Order::new(
@@ -250,13 +808,198 @@ mod tests {
         let result = handler.submit_order(&order).await;
         assert!(result.is_ok());
 
-        // Check fill (instant in dry-run)
+        // Rests until the market trades at the limit
+        assert!(handler.drain_fills().is_empty());
+        assert_eq!(handler.resting_orders(), 1);
+
+        handler.on_candle(&candle(50_000.0, 50_100.0, 49_500.0, 49_600.0, 10.0));
+        assert!(handler.drain_fills().is_empty());
+
+        handler.on_candle(&candle(49_600.0, 49_700.0, 48_900.0, 49_100.0, 10.0));
         let fills = handler.drain_fills();
         assert_eq!(fills.len(), 1);
 
         let fill = &fills[0];
         assert_eq!(fill.price, 49_000.0);
         assert_eq!(fill.commission, 4.9); // 0.1 * 49,000 * 0.001
+        assert_eq!(handler.drain_updates()[0].status, OrderStatus::Filled);
+        assert_eq!(handler.resting_orders(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_fee_model_dry_run() {
+        let market = velora_exchange::Market {
+            symbol: Symbol::new("BTC-USD-PERP"),
+            base_asset: "BTC".to_string(),
+            quote_asset: "USD".to_string(),
+            instrument_type: velora_exchange::InstrumentType::Perpetual,
+            status: velora_exchange::MarketStatus::Trading,
+            min_quantity: Decimal::ZERO,
+            max_quantity: Decimal::ONE_HUNDRED,
+            step_size: Decimal::ONE,
+            tick_size: Decimal::ONE,
+            min_notional: Decimal::ZERO,
+            instrument_info: Some(velora_exchange::InstrumentInfo::Perpetual(
+                velora_exchange::types::PerpetualInfo {
+                    funding_interval: std::time::Duration::from_secs(8 * 3600),
+                    max_leverage: 20,
+                    maker_fee: Decimal::new(-1, 4),
+                    taker_fee: Decimal::new(5, 4),
+                    initial_margin: None,
+                    maintenance_margin: None,
+                },
+            )),
+        };
+        // Other symbols keep the flat 0.1%
+        let fees = FeeModel::flat(0.001).with_market(&market);
+        assert_eq!(fees.rate("ETH-USD-PERP", Liquidity::Maker, 0.0), 0.001);
+        assert_eq!(
+            FeeModel::from_market(&market).unwrap().base,
+            fees.symbols["BTC-USD-PERP"]
+        );
+
+        let mut handler = ExecutionHandler::new(ExecutionMode::DryRun).with_fee_model(fees);
+        handler.update_market_price("BTC-USD-PERP".to_string(), 50_000.0);
+
+        // Crossing on submission pays the taker fee
+        let market_order = Order::new(
+            "BTC-USD-PERP".to_string(),
+            Side::Buy,
+            OrderType::Market,
+            1.0,
+            None,
+        );
+        handler.submit_order(&market_order).await.unwrap();
+        assert_eq!(handler.drain_fills()[0].commission, 25.0);
+
+        // A resting limit earns the maker rebate
+        let limit = Order::new(
+            "BTC-USD-PERP".to_string(),
+            Side::Sell,
+            OrderType::Limit,
+            1.0,
+            Some(51_000.0),
+        );
+        handler.submit_order(&limit).await.unwrap();
+        handler.on_candle(&candle(50_500.0, 51_200.0, 50_400.0, 51_000.0, 10.0));
+        assert!((handler.drain_fills()[0].commission + 5.1).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_marketable_limit_order_fills_at_market() {
+        let mut handler = ExecutionHandler::new(ExecutionMode::DryRun);
+        handler.update_market_price("BTC-USD-PERP".to_string(), 50_000.0);
+
+        let order = Order::new(
+            "BTC-USD-PERP".to_string(),
+            Side::Buy,
+            OrderType::Limit,
+            0.1,
+            Some(51_000.0),
+        );
+        handler.submit_order(&order).await.unwrap();
+
+        let fills = handler.drain_fills();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, 50_000.0);
+        assert_eq!(handler.resting_orders(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_partial_fills_follow_traded_volume() {
+        let mut handler = ExecutionHandler::new(ExecutionMode::DryRun).with_commission_rate(0.0);
+        handler.update_market_price("BTC-USD-PERP".to_string(), 50_000.0);
+
+        let order = Order::new(
+            "BTC-USD-PERP".to_string(),
+            Side::Sell,
+            OrderType::Limit,
+            3.0,
+            Some(51_000.0),
+        );
+        handler.submit_order(&order).await.unwrap();
+
+        handler.on_candle(&candle(50_500.0, 51_200.0, 50_400.0, 51_000.0, 2.0));
+        let updates = handler.drain_updates();
+        assert_eq!(updates[0].status, OrderStatus::PartiallyFilled);
+        assert_eq!(updates[0].filled_quantity, 2.0);
+
+        handler.on_tick(&Tick {
+            symbol: velora_core::Symbol::new("BTC-USD-PERP"),
+            price: 51_050.0.into(),
+            volume: 5.0.into(),
+            timestamp: Utc::now(),
+        });
+        let updates = handler.drain_updates();
+        assert_eq!(updates[0].status, OrderStatus::Filled);
+        assert_eq!(updates[0].filled_quantity, 3.0);
+        assert_eq!(updates[0].average_price, 51_000.0);
+
+        let fills = handler.drain_fills();
+        assert_eq!(fills.iter().map(|f| f.quantity).sum::<f64>(), 3.0);
+    }
+
+    #[tokio::test]
+    async fn test_stop_orders_dry_run() {
+        let mut handler = ExecutionHandler::new(ExecutionMode::DryRun);
+        handler.update_market_price("BTC-USD-PERP".to_string(), 50_000.0);
+
+        let stop_market = Order::new(
+            "BTC-USD-PERP".to_string(),
+            Side::Sell,
+            OrderType::StopMarket,
+            0.1,
+            None,
+        )
+        .with_stop_price(49_000.0);
+        let stop_limit = Order::new(
+            "BTC-USD-PERP".to_string(),
+            Side::Buy,
+            OrderType::StopLimit,
+            0.1,
+            Some(51_200.0),
+        )
+        .with_stop_price(51_000.0);
+        handler.submit_order(&stop_market).await.unwrap();
+        handler.submit_order(&stop_limit).await.unwrap();
+        assert_eq!(handler.resting_orders(), 2);
+
+        handler.on_candle(&candle(50_000.0, 51_100.0, 49_900.0, 51_000.0, 10.0));
+        let fills = handler.drain_fills();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].order_id, stop_limit.id);
+        assert_eq!(fills[0].price, 51_000.0);
+
+        // Gap through the stop fills at the open
+        handler.on_candle(&candle(48_500.0, 48_600.0, 48_000.0, 48_200.0, 10.0));
+        let fills = handler.drain_fills();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].order_id, stop_market.id);
+        assert_eq!(fills[0].price, 48_500.0);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_resting_order_dry_run() {
+        let mut handler = ExecutionHandler::new(ExecutionMode::DryRun);
+
+        let order = Order::new(
+            "BTC-USD-PERP".to_string(),
+            Side::Buy,
+            OrderType::Limit,
+            0.1,
+            Some(49_000.0),
+        );
+        handler.submit_order(&order).await.unwrap();
+
+        handler.cancel_order(order.id).await.unwrap();
+        let updates = handler.drain_updates();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].status, OrderStatus::Cancelled);
+
+        assert!(matches!(
+            handler.cancel_order(order.id).await,
+            Err(EngineError::OrderNotFound(_))
+        ));
     }
 
     #[tokio::test]
@@ -0,0 +1,218 @@
+//! Exchange market data and order feeds
+
+use crate::events::MarketEvent;
+use futures::{Stream, StreamExt};
+use rust_decimal::prelude::ToPrimitive;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use velora_core::{Candle, Interval, Symbol, Trade};
+use velora_exchange::Exchange;
+
+/// Stream of engine events produced by a feed
+type EventStream = Box<dyn Stream<Item = MarketEvent> + Send + Unpin>;
+
+/// A subscription forwarded into the engine's market channel
+#[derive(Debug, Clone)]
+pub(crate) enum Feed {
+    /// Closed candles of a symbol
+    Candles(String, Interval),
+
+    /// Trades of a symbol
+    Trades(String),
+
+    /// Order updates of the account
+    Orders,
+}
+
+/// Reconnection policy of a feed
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ReconnectPolicy {
+    /// Delay before resubscribing
+    pub delay: Duration,
+
+    /// Consecutive resubscriptions allowed without receiving an event
+    pub max_attempts: u32,
+}
+
+impl Feed {
+    /// Subscribe on the exchange and map the stream to engine events
+    async fn subscribe(&self, exchange: &dyn Exchange) -> velora_exchange::Result<EventStream> {
+        let streaming = exchange.streaming();
+        let stream: EventStream = match self {
+            Feed::Candles(symbol, interval) => {
+                let candles = streaming
+                    .subscribe_candles(&Symbol::new(symbol.as_str()), *interval)
+                    .await?;
+                Box::new(closed_candles(candles, symbol.clone()))
+            }
+            Feed::Trades(symbol) => {
+                let trades = streaming
+                    .subscribe_trades(&Symbol::new(symbol.as_str()))
+                    .await?;
+                let symbol = Symbol::new(symbol.as_str());
+                Box::new(trades.map(move |trade| match trade {
+                    Ok(trade) => MarketEvent::Trade(Trade {
+                        id: uuid::Uuid::new_v4(),
+                        order_id: uuid::Uuid::nil(),
+                        symbol: symbol.clone(),
+                        side: trade.side,
+                        price: trade.price,
+                        quantity: trade.quantity.to_f64().unwrap_or_default().into(),
+                        fee: 0.0.into(),
+                        timestamp: trade.timestamp,
+                    }),
+                    Err(e) => MarketEvent::Error(e.to_string()),
+                }))
+            }
+            Feed::Orders => {
+                let orders = streaming.subscribe_orders().await?;
+                Box::new(orders.map(|update| match update {
+                    Ok(update) => MarketEvent::ExchangeOrderUpdate(Box::new(update.order)),
+                    Err(e) => MarketEvent::Error(e.to_string()),
+                }))
+            }
+        };
+        Ok(stream)
+    }
+
+    /// Forward the feed into `tx` until it is exhausted or the engine stops
+    ///
+    /// A `Disconnected` event is sent whenever the stream ends, and
+    /// `Reconnected` once a resubscription delivers events again.
+    pub(crate) fn spawn(
+        self,
+        exchange: Arc<dyn Exchange>,
+        tx: UnboundedSender<MarketEvent>,
+        policy: ReconnectPolicy,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut attempts = 0;
+            loop {
+                match self.subscribe(exchange.as_ref()).await {
+                    Ok(mut stream) => {
+                        debug!("Subscribed to {:?}", self);
+                        while let Some(event) = stream.next().await {
+                            if attempts > 0 {
+                                info!("{:?} feed reconnected", self);
+                                attempts = 0;
+                                if tx.send(MarketEvent::Reconnected).is_err() {
+                                    return;
+                                }
+                            }
+                            if tx.send(event).is_err() {
+                                return;
+                            }
+                        }
+                        warn!("{:?} feed ended", self);
+                    }
+                    Err(e) => {
+                        warn!("Failed to subscribe to {:?}: {}", self, e);
+                        if tx.send(MarketEvent::Error(e.to_string())).is_err() {
+                            return;
+                        }
+                    }
+                }
+
+                if tx.send(MarketEvent::Disconnected).is_err() || attempts >= policy.max_attempts {
+                    return;
+                }
+                attempts += 1;
+                tokio::time::sleep(policy.delay).await;
+            }
+        })
+    }
+}
+
+/// Emit each candle once it has closed
+///
+/// Exchanges push the candle in progress on every update; a candle is final
+/// once one with a later open time arrives.
+fn closed_candles(
+    candles: impl Stream<Item = velora_exchange::Result<velora_exchange::Candle>> + Send + Unpin,
+    symbol: String,
+) -> impl Stream<Item = MarketEvent> + Send + Unpin {
+    let mut current: Option<velora_exchange::Candle> = None;
+    candles.filter_map(move |candle| {
+        let event = match candle {
+            Ok(candle) => {
+                let open_time = candle.open_time;
+                match current.replace(candle) {
+                    Some(previous) if previous.open_time < open_time => {
+                        Some(MarketEvent::Candle(convert_candle(previous, &symbol)))
+                    }
+                    _ => None,
+                }
+            }
+            Err(e) => Some(MarketEvent::Error(e.to_string())),
+        };
+        futures::future::ready(event)
+    })
+}
+
+/// Convert an exchange candle to the engine's representation
+fn convert_candle(candle: velora_exchange::Candle, symbol: &str) -> Candle {
+    Candle {
+        symbol: Symbol::new(symbol),
+        open: candle.open,
+        high: candle.high,
+        low: candle.low,
+        close: candle.close,
+        volume: candle.volume.to_f64().unwrap_or_default().into(),
+        timestamp: candle.open_time,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use rust_decimal::Decimal;
+
+    fn candle(minute: i64, close: f64) -> velora_exchange::Result<velora_exchange::Candle> {
+        let open_time = Utc.timestamp_opt(1_700_000_000 + minute * 60, 0).unwrap();
+        Ok(velora_exchange::Candle {
+            symbol: Symbol::new("BTCUSDT"),
+            interval: Interval::Minute1,
+            open: 100.0.into(),
+            high: 110.0.into(),
+            low: 90.0.into(),
+            close: close.into(),
+            volume: Decimal::new(15, 1),
+            open_time,
+            close_time: open_time + chrono::Duration::seconds(59),
+            trade_count: None,
+            quote_volume: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_closed_candles_waits_for_next_open_time() {
+        let updates = futures::stream::iter(vec![
+            candle(0, 101.0),
+            candle(0, 102.0),
+            candle(1, 103.0),
+            candle(1, 104.0),
+            candle(2, 105.0),
+        ]);
+
+        let events: Vec<_> = closed_candles(updates, "BTC-USD".to_string())
+            .collect()
+            .await;
+
+        assert_eq!(events.len(), 2);
+        let MarketEvent::Candle(first) = &events[0] else {
+            panic!("Expected candle, got {:?}", events[0]);
+        };
+        assert_eq!(first.symbol.as_str(), "BTC-USD");
+        assert_eq!(first.close.into_inner(), 102.0);
+        assert_eq!(first.volume.into_inner(), 1.5);
+
+        let MarketEvent::Candle(second) = &events[1] else {
+            panic!("Expected candle, got {:?}", events[1]);
+        };
+        assert_eq!(second.close.into_inner(), 104.0);
+    }
+}
@@ -0,0 +1,622 @@
+//! Write-ahead journal of engine state
+//!
+//! Order states and audit events, fills, funding settlements and the exits
+//! protecting positions are appended to segment files in a directory before
+//! the engine acts on them, so a restarted engine can rebuild its orders and
+//! positions by replaying them.
+//!
+//! Each record is framed as its length and CRC-32 (both little-endian
+//! `u32`) followed by its JSON encoding. A record torn by a crash at the end
+//! of the last segment is truncated away on open; a bad record anywhere else
+//! is reported as corruption. A record with a valid checksum was committed,
+//! so failing to decode it is an error rather than a torn write. Once the
+//! records appended after the active segment's snapshot outgrow
+//! [`JournalConfig::segment_bytes`], a snapshot of the working state (open
+//! orders, positions, exits and cash) starts a new segment.
+//! The older segments move to an `archive` subdirectory: they keep the full
+//! audit trail of orders and fills, but are not replayed on restart.
+
+use crate::engine::Bracket;
+use crate::errors::{EngineError, EngineResult};
+use crate::events::{Fill, OrderId};
+use crate::order_manager::{Order, OrderEvent};
+use crate::position_tracker::Position;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Extension of segment files
+const SEGMENT_EXTENSION: &str = "wal";
+
+/// Subdirectory compacted segments are moved to
+const ARCHIVE_DIR: &str = "archive";
+
+/// Bytes of the length and checksum preceding each record
+const HEADER_LEN: usize = 8;
+
+/// When appended records are flushed to disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FsyncPolicy {
+    /// Sync after every record (survives power loss, slowest)
+    Always,
+
+    /// Sync after every `n` records
+    Batch(u32),
+
+    /// Leave syncing to the operating system (survives the process
+    /// crashing, not the machine)
+    Never,
+}
+
+/// Configuration of the engine journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalConfig {
+    /// Directory holding the segment files
+    pub dir: PathBuf,
+
+    /// When appended records are synced to disk
+    #[serde(default = "default_fsync")]
+    pub fsync: FsyncPolicy,
+
+    /// Bytes appended after a snapshot past which the journal is compacted
+    #[serde(default = "default_segment_bytes")]
+    pub segment_bytes: u64,
+}
+
+fn default_fsync() -> FsyncPolicy {
+    FsyncPolicy::Always
+}
+
+fn default_segment_bytes() -> u64 {
+    16 * 1024 * 1024
+}
+
+impl JournalConfig {
+    /// Journal in a directory, syncing every record
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            fsync: default_fsync(),
+            segment_bytes: default_segment_bytes(),
+        }
+    }
+
+    /// Set the fsync policy
+    pub fn with_fsync(mut self, fsync: FsyncPolicy) -> Self {
+        self.fsync = fsync;
+        self
+    }
+
+    /// Set the appended size that triggers compaction
+    pub fn with_segment_bytes(mut self, bytes: u64) -> Self {
+        self.segment_bytes = bytes;
+        self
+    }
+}
+
+/// Entry of the journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JournalRecord {
+    /// State of an order after a change
+    Order(Order),
+
+    /// Order audit event
+    OrderEvent(OrderEvent),
+
+    /// Exchange ID an order was placed under (live mode)
+    ExchangeOrder {
+        /// Internal order ID
+        order_id: OrderId,
+        /// Order ID on the exchange
+        exchange_order_id: String,
+    },
+
+    /// Fill about to be applied to positions
+    Fill(Fill),
+
+    /// Funding settlement about to be applied to a position
+    Funding {
+        /// Symbol of the position
+        symbol: String,
+        /// Funding rate
+        rate: f64,
+        /// Mark price the rate applies to
+        price: f64,
+    },
+
+    /// Exits of a position after a change
+    Bracket {
+        /// Symbol of the position
+        symbol: String,
+        /// Stop-loss, take-profit and trailing state; none once dropped
+        bracket: Option<Bracket>,
+    },
+
+    /// Whole engine state, replacing every record before it
+    Snapshot(JournalSnapshot),
+}
+
+/// Working state written when the journal is compacted
+///
+/// Completed orders, the audit trail and fills stay in the archived segments.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JournalSnapshot {
+    /// Orders not yet filled, cancelled or failed
+    pub orders: Vec<Order>,
+
+    /// Exchange IDs of working live orders
+    pub exchange_orders: Vec<(OrderId, String)>,
+
+    /// Open positions
+    pub positions: Vec<Position>,
+
+    /// Exits of protected positions, by symbol
+    #[serde(default)]
+    pub brackets: Vec<(String, Bracket)>,
+
+    /// Available cash
+    pub cash: f64,
+}
+
+/// Append-only journal in a directory of segment files
+#[derive(Debug)]
+pub struct Journal {
+    config: JournalConfig,
+
+    /// Sequence number of the active segment
+    segment: u64,
+
+    /// Active segment, opened for appending
+    file: File,
+
+    /// Bytes in the active segment
+    len: u64,
+
+    /// Bytes of the snapshot the active segment starts with
+    base: u64,
+
+    /// Records appended since the last sync
+    unsynced: u32,
+}
+
+impl Journal {
+    /// Open the journal, returning it with the records it holds
+    ///
+    /// Creates the directory if needed. A torn record at the end of the last
+    /// segment is truncated so appends continue after the last good one. A
+    /// committed record that does not decode, e.g. one written by another
+    /// version, fails the open and leaves the files untouched.
+    pub fn open(config: JournalConfig) -> EngineResult<(Self, Vec<JournalRecord>)> {
+        fs::create_dir_all(&config.dir).map_err(|e| io_error(&config.dir, e))?;
+
+        let segments = list_segments(&config.dir)?;
+        let mut records = Vec::new();
+        let mut active = None;
+        for (index, &segment) in segments.iter().enumerate() {
+            let path = segment_path(&config.dir, segment);
+            let (segment_records, valid_len) = read_segment(&path)?;
+            let file_len = fs::metadata(&path).map_err(|e| io_error(&path, e))?.len();
+
+            if valid_len < file_len {
+                if index + 1 < segments.len() {
+                    return Err(EngineError::Journal(format!(
+                        "Corrupt record in {} at offset {valid_len}",
+                        path.display()
+                    )));
+                }
+                warn!(
+                    "Truncating torn journal record in {} at offset {}",
+                    path.display(),
+                    valid_len
+                );
+                let file = OpenOptions::new()
+                    .write(true)
+                    .open(&path)
+                    .map_err(|e| io_error(&path, e))?;
+                file.set_len(valid_len).map_err(|e| io_error(&path, e))?;
+                file.sync_all().map_err(|e| io_error(&path, e))?;
+            }
+
+            let base = match segment_records.first() {
+                Some(record @ JournalRecord::Snapshot(_)) => encode(record)?.len() as u64,
+                _ => 0,
+            };
+            records.extend(segment_records);
+            active = Some((segment, valid_len, base));
+        }
+
+        let (segment, len, base) = active.unwrap_or((0, 0, 0));
+        let path = segment_path(&config.dir, segment);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| io_error(&path, e))?;
+        if segments.is_empty() {
+            sync_dir(&config.dir)?;
+        }
+
+        info!(
+            "Opened journal {} with {} records",
+            config.dir.display(),
+            records.len()
+        );
+        let journal = Self {
+            config,
+            segment,
+            file,
+            len,
+            base,
+            unsynced: 0,
+        };
+        Ok((journal, records))
+    }
+
+    /// Append a record, syncing it as the fsync policy requires
+    pub fn append(&mut self, record: &JournalRecord) -> EngineResult<()> {
+        let frame = encode(record)?;
+        let path = self.path();
+        self.file
+            .write_all(&frame)
+            .map_err(|e| io_error(&path, e))?;
+        self.len += frame.len() as u64;
+        self.unsynced += 1;
+
+        let sync = match self.config.fsync {
+            FsyncPolicy::Always => true,
+            FsyncPolicy::Batch(n) => self.unsynced >= n.max(1),
+            FsyncPolicy::Never => false,
+        };
+        if sync {
+            self.sync()?;
+        }
+        Ok(())
+    }
+
+    /// Flush appended records to disk
+    pub fn sync(&mut self) -> EngineResult<()> {
+        self.file
+            .sync_data()
+            .map_err(|e| io_error(&self.path(), e))?;
+        self.unsynced = 0;
+        Ok(())
+    }
+
+    /// Whether the records appended since the last snapshot outgrew the limit
+    ///
+    /// Measured past the snapshot, so a large working state does not trigger
+    /// a compaction after every record.
+    pub fn needs_compaction(&self) -> bool {
+        self.len.saturating_sub(self.base) >= self.config.segment_bytes
+    }
+
+    /// Start a new segment holding only `snapshot` and archive the older ones
+    ///
+    /// The snapshot is synced before anything is moved, so a crash at any
+    /// point leaves either the old segments or the snapshot to recover from.
+    pub fn compact(&mut self, snapshot: &JournalSnapshot) -> EngineResult<()> {
+        self.sync()?;
+
+        let segment = self.segment + 1;
+        let path = segment_path(&self.config.dir, segment);
+        let frame = encode(&JournalRecord::Snapshot(snapshot.clone()))?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(&path)
+            .map_err(|e| io_error(&path, e))?;
+        file.write_all(&frame).map_err(|e| io_error(&path, e))?;
+        file.sync_all().map_err(|e| io_error(&path, e))?;
+        sync_dir(&self.config.dir)?;
+
+        let archive = self.config.dir.join(ARCHIVE_DIR);
+        fs::create_dir_all(&archive).map_err(|e| io_error(&archive, e))?;
+        for old in list_segments(&self.config.dir)? {
+            if old < segment {
+                let old_path = segment_path(&self.config.dir, old);
+                let archived = segment_path(&archive, old);
+                fs::rename(&old_path, &archived).map_err(|e| io_error(&old_path, e))?;
+            }
+        }
+        sync_dir(&archive)?;
+        sync_dir(&self.config.dir)?;
+
+        info!(
+            "Compacted journal {} into segment {}",
+            self.config.dir.display(),
+            segment
+        );
+        self.segment = segment;
+        self.file = OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .map_err(|e| io_error(&path, e))?;
+        self.len = frame.len() as u64;
+        self.base = self.len;
+        self.unsynced = 0;
+        Ok(())
+    }
+
+    /// Path of the active segment
+    pub fn path(&self) -> PathBuf {
+        segment_path(&self.config.dir, self.segment)
+    }
+}
+
+/// Frame a record as length, checksum and JSON payload
+fn encode(record: &JournalRecord) -> EngineResult<Vec<u8>> {
+    let payload = serde_json::to_vec(record)
+        .map_err(|e| EngineError::Journal(format!("Failed to encode record: {e}")))?;
+    let len = u32::try_from(payload.len())
+        .map_err(|_| EngineError::Journal("Record too large".to_string()))?;
+
+    let mut frame = Vec::with_capacity(HEADER_LEN + payload.len());
+    frame.extend_from_slice(&len.to_le_bytes());
+    frame.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+    frame.extend_from_slice(&payload);
+    Ok(frame)
+}
+
+/// Records of a segment, with the length of its valid prefix
+///
+/// The prefix ends at the first incomplete or checksum-failing record.
+fn read_segment(path: &Path) -> EngineResult<(Vec<JournalRecord>, u64)> {
+    let mut bytes = Vec::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_end(&mut bytes))
+        .map_err(|e| io_error(path, e))?;
+
+    let mut records = Vec::new();
+    let mut offset = 0;
+    while let Some(header) = bytes.get(offset..offset + HEADER_LEN) {
+        let len = u32::from_le_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let checksum = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        let start = offset + HEADER_LEN;
+        let Some(payload) = bytes.get(start..start + len) else {
+            break;
+        };
+        if crc32fast::hash(payload) != checksum {
+            break;
+        }
+        let record = serde_json::from_slice(payload).map_err(|e| {
+            EngineError::Journal(format!(
+                "Undecodable record in {} at offset {offset}: {e}",
+                path.display()
+            ))
+        })?;
+
+        records.push(record);
+        offset = start + len;
+    }
+    Ok((records, offset as u64))
+}
+
+/// Sequence numbers of the segments in a directory, ascending
+fn list_segments(dir: &Path) -> EngineResult<Vec<u64>> {
+    let mut segments = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| io_error(dir, e))? {
+        let path = entry.map_err(|e| io_error(dir, e))?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(SEGMENT_EXTENSION) {
+            continue;
+        }
+        if let Some(segment) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse().ok())
+        {
+            segments.push(segment);
+        }
+    }
+    segments.sort_unstable();
+    Ok(segments)
+}
+
+fn segment_path(dir: &Path, segment: u64) -> PathBuf {
+    dir.join(format!("{segment:020}.{SEGMENT_EXTENSION}"))
+}
+
+/// Persist the creation and removal of segment files
+fn sync_dir(dir: &Path) -> EngineResult<()> {
+    #[cfg(unix)]
+    File::open(dir)
+        .and_then(|dir| dir.sync_all())
+        .map_err(|e| io_error(dir, e))?;
+    #[cfg(not(unix))]
+    let _ = dir;
+    Ok(())
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> EngineError {
+    EngineError::Journal(format!("{}: {error}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use velora_core::Side;
+    use velora_strategy::PositionSide;
+
+    /// Fresh directory under the system temp dir, removed on drop
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            Self(std::env::temp_dir().join(format!("velora-journal-{}", uuid::Uuid::new_v4())))
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn fill(price: f64) -> JournalRecord {
+        JournalRecord::Fill(Fill {
+            order_id: uuid::Uuid::new_v4(),
+            symbol: "BTC-USD-PERP".to_string(),
+            side: Side::Buy,
+            quantity: 1.0,
+            price,
+            commission: 0.0,
+            timestamp: Utc::now(),
+        })
+    }
+
+    fn fill_prices(records: &[JournalRecord]) -> Vec<f64> {
+        records
+            .iter()
+            .filter_map(|record| match record {
+                JournalRecord::Fill(fill) => Some(fill.price),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reopen_returns_appended_records() {
+        let dir = TempDir::new();
+        let config = JournalConfig::new(&dir.0).with_fsync(FsyncPolicy::Batch(2));
+
+        let (mut journal, records) = Journal::open(config.clone()).unwrap();
+        assert!(records.is_empty());
+        journal.append(&fill(100.0)).unwrap();
+        journal.append(&fill(101.0)).unwrap();
+        journal.append(&fill(102.0)).unwrap();
+        drop(journal);
+
+        let (mut journal, records) = Journal::open(config.clone()).unwrap();
+        assert_eq!(fill_prices(&records), vec![100.0, 101.0, 102.0]);
+        journal.append(&fill(103.0)).unwrap();
+        drop(journal);
+
+        let (_, records) = Journal::open(config).unwrap();
+        assert_eq!(records.len(), 4);
+    }
+
+    #[test]
+    fn test_torn_tail_is_truncated() {
+        let dir = TempDir::new();
+        let config = JournalConfig::new(&dir.0);
+
+        let (mut journal, _) = Journal::open(config.clone()).unwrap();
+        journal.append(&fill(100.0)).unwrap();
+        journal.append(&fill(101.0)).unwrap();
+        let path = journal.path();
+        drop(journal);
+
+        // Cut the last record short, as a crash mid-write would
+        let len = fs::metadata(&path).unwrap().len();
+        let file = OpenOptions::new().write(true).open(&path).unwrap();
+        file.set_len(len - 3).unwrap();
+        drop(file);
+
+        let (mut journal, records) = Journal::open(config.clone()).unwrap();
+        assert_eq!(fill_prices(&records), vec![100.0]);
+        journal.append(&fill(102.0)).unwrap();
+        drop(journal);
+
+        let (_, records) = Journal::open(config).unwrap();
+        assert_eq!(fill_prices(&records), vec![100.0, 102.0]);
+    }
+
+    #[test]
+    fn test_undecodable_record_is_an_error() {
+        let dir = TempDir::new();
+        let config = JournalConfig::new(&dir.0);
+
+        let (mut journal, _) = Journal::open(config.clone()).unwrap();
+        journal.append(&fill(100.0)).unwrap();
+        let path = journal.path();
+        drop(journal);
+
+        // A committed record of a kind this version does not know
+        let payload = br#"{"kind":"from_the_future"}"#;
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&(payload.len() as u32).to_le_bytes())
+            .unwrap();
+        file.write_all(&crc32fast::hash(payload).to_le_bytes())
+            .unwrap();
+        file.write_all(payload).unwrap();
+        drop(file);
+        let len = fs::metadata(&path).unwrap().len();
+
+        let error = Journal::open(config).unwrap_err();
+        assert!(matches!(
+            &error,
+            EngineError::Journal(message) if message.contains("Undecodable record")
+        ));
+        assert_eq!(fs::metadata(&path).unwrap().len(), len);
+    }
+
+    #[test]
+    fn test_checksum_mismatch_in_old_segment_is_an_error() {
+        let dir = TempDir::new();
+        let config = JournalConfig::new(&dir.0);
+
+        let (mut journal, _) = Journal::open(config.clone()).unwrap();
+        journal.append(&fill(100.0)).unwrap();
+        let first = journal.path();
+        drop(journal);
+
+        // A later segment makes the damaged one not the last
+        fs::write(segment_path(&dir.0, 1), encode(&fill(101.0)).unwrap()).unwrap();
+        let mut bytes = fs::read(&first).unwrap();
+        let last = bytes.len() - 2;
+        bytes[last] ^= 0xff;
+        fs::write(&first, bytes).unwrap();
+
+        let error = Journal::open(config).unwrap_err();
+        assert!(matches!(error, EngineError::Journal(_)));
+    }
+
+    #[test]
+    fn test_compaction_replaces_segments_with_snapshot() {
+        let dir = TempDir::new();
+        let config = JournalConfig::new(&dir.0).with_segment_bytes(256);
+        let position = Position {
+            symbol: "BTC-USD-PERP".to_string(),
+            side: PositionSide::Long,
+            quantity: 1.0,
+            average_entry_price: 100.0,
+            current_price: 100.0,
+            unrealized_pnl: 0.0,
+            realized_pnl: 0.0,
+            opened_at: Utc::now(),
+            last_updated: Utc::now(),
+        };
+
+        let (mut journal, _) = Journal::open(config.clone()).unwrap();
+        while !journal.needs_compaction() {
+            journal.append(&fill(100.0)).unwrap();
+        }
+        // A snapshot larger than the limit does not count towards it
+        let snapshot = JournalSnapshot {
+            positions: vec![position; 4],
+            cash: 9_000.0,
+            ..Default::default()
+        };
+        journal.compact(&snapshot).unwrap();
+        assert!(journal.len > config.segment_bytes);
+        assert!(!journal.needs_compaction());
+        journal.append(&fill(105.0)).unwrap();
+        drop(journal);
+
+        assert_eq!(list_segments(&dir.0).unwrap(), vec![1]);
+        assert_eq!(list_segments(&dir.0.join(ARCHIVE_DIR)).unwrap(), vec![0]);
+        let (journal, records) = Journal::open(config).unwrap();
+        assert!(!journal.needs_compaction());
+        assert_eq!(records.len(), 2);
+        assert!(matches!(
+            &records[0],
+            JournalRecord::Snapshot(snapshot) if snapshot.cash == 9_000.0
+        ));
+        assert_eq!(fill_prices(&records), vec![105.0]);
+    }
+}
@@ -7,6 +7,10 @@
 //! - Real-time strategy execution
 //! - Order and position management
 //! - Dry-run (paper trading) mode
+//! - Live order routing and market data through `velora-exchange`
+//! - Injectable clock for replaying history on historical time
+//! - Trade, tick, order book, funding, order update and timer callbacks
+//! - Write-ahead journal for recovering orders and positions after a restart
 //! - Event-driven architecture
 //! - Comprehensive monitoring and logging
 //!
@@ -46,13 +50,17 @@ mod engine;
 mod errors;
 mod events;
 mod execution;
+mod feeds;
+mod journal;
 mod order_manager;
 mod position_tracker;
+mod simulation;
 
 pub use config::{EngineConfig, ExecutionMode, MetricsConfig};
-pub use engine::{EngineState, EngineStatus, TradingEngine};
+pub use engine::{Bracket, EngineState, EngineStatus, TradingEngine};
 pub use errors::{EngineError, EngineResult};
 pub use events::{Fill, MarketEvent, OrderStatus, OrderUpdate};
 pub use execution::ExecutionHandler;
+pub use journal::{FsyncPolicy, Journal, JournalConfig, JournalRecord, JournalSnapshot};
 pub use order_manager::{Order, OrderEvent, OrderEventType, OrderManager};
 pub use position_tracker::{EquitySnapshot, Position, PositionTracker};
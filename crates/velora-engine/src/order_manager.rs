@@ -5,8 +5,7 @@ use crate::events::{OrderId, OrderStatus, OrderUpdate};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::Instant;
-use velora_core::{OrderType, Side};
+use velora_core::{LiveClock, OrderType, SharedClock, Side};
 
 /// Manages order lifecycle: submission, tracking, cancellation
 pub struct OrderManager {
@@ -24,6 +23,9 @@ pub struct OrderManager {
 
     /// Rate limiter
     rate_limiter: RateLimiter,
+
+    /// Time source for order timestamps
+    clock: SharedClock,
 }
 
 /// Order representation
@@ -50,6 +52,14 @@ pub struct Order {
     /// Limit price (for limit orders)
     pub price: Option<f64>,
 
+    /// Trigger price (for stop orders)
+    #[serde(default)]
+    pub stop_price: Option<f64>,
+
+    /// Only reduce an existing position
+    #[serde(default)]
+    pub reduce_only: bool,
+
     /// Current status
     pub status: OrderStatus,
 
@@ -93,8 +103,9 @@ pub enum OrderEventType {
 /// Rate limiter to prevent exceeding exchange limits
 pub struct RateLimiter {
     max_orders_per_second: u32,
-    window_start: Instant,
+    window_start: DateTime<Utc>,
     order_count: u32,
+    clock: SharedClock,
 }
 
 impl OrderManager {
@@ -106,9 +117,17 @@ impl OrderManager {
             completed_orders: Vec::new(),
             order_history: Vec::new(),
             rate_limiter: RateLimiter::new(max_orders_per_second),
+            clock: LiveClock::shared(),
         }
     }
 
+    /// Stamp orders and count rate limit windows with a clock
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.rate_limiter = self.rate_limiter.with_clock(clock.clone());
+        self.clock = clock;
+        self
+    }
+
     /// Submit a new order
     pub fn submit_order(&mut self, mut order: Order) -> EngineResult<OrderId> {
         // Check rate limit
@@ -119,8 +138,8 @@ impl OrderManager {
 
         // Set status to pending
         order.status = OrderStatus::Pending;
-        order.created_at = Utc::now();
-        order.updated_at = Utc::now();
+        order.created_at = self.clock.now();
+        order.updated_at = order.created_at;
 
         let order_id = order.id;
 
@@ -128,7 +147,7 @@ impl OrderManager {
         self.record_event(OrderEvent {
             order_id,
             event_type: OrderEventType::Created,
-            timestamp: Utc::now(),
+            timestamp: order.created_at,
             details: format!(
                 "{:?} {} {} @ {:?}",
                 order.side, order.quantity, order.symbol, order.price
@@ -149,12 +168,12 @@ impl OrderManager {
             .ok_or_else(|| EngineError::OrderNotFound(order_id.to_string()))?;
 
         order.status = OrderStatus::Submitted;
-        order.updated_at = Utc::now();
+        order.updated_at = self.clock.now();
 
         self.record_event(OrderEvent {
             order_id,
             event_type: OrderEventType::Submitted,
-            timestamp: Utc::now(),
+            timestamp: order.updated_at,
             details: "Order submitted to exchange".to_string(),
         });
 
@@ -233,7 +252,7 @@ impl OrderManager {
         self.record_event(OrderEvent {
             order_id,
             event_type: OrderEventType::Cancelled,
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
             details: "Cancel request initiated".to_string(),
         });
 
@@ -281,6 +300,36 @@ impl OrderManager {
         &self.order_history
     }
 
+    /// All orders: completed ones in completion order, then working ones
+    pub fn all_orders(&self) -> impl Iterator<Item = &Order> {
+        self.completed_orders
+            .iter()
+            .chain(self.active_orders.values())
+            .chain(self.pending_orders.values())
+    }
+
+    /// Replace the orders and audit trail with recovered ones
+    ///
+    /// Orders are filed as pending, active or completed by their status.
+    pub fn restore(&mut self, orders: Vec<Order>, history: Vec<OrderEvent>) {
+        self.pending_orders.clear();
+        self.active_orders.clear();
+        self.completed_orders.clear();
+
+        for order in orders {
+            match order.status {
+                OrderStatus::Pending => {
+                    self.pending_orders.insert(order.id, order);
+                }
+                _ if order.is_terminal() => self.completed_orders.push(order),
+                _ => {
+                    self.active_orders.insert(order.id, order);
+                }
+            }
+        }
+        self.order_history = history;
+    }
+
     /// Validate order before submission
     fn validate_order(&self, order: &Order) -> EngineResult<()> {
         if order.quantity <= 0.0 {
@@ -301,6 +350,16 @@ impl OrderManager {
             ));
         }
 
+        if matches!(
+            order.order_type,
+            OrderType::StopMarket | OrderType::StopLimit
+        ) && order.stop_price.is_none()
+        {
+            return Err(EngineError::OrderError(
+                "Stop orders must have a stop price".to_string(),
+            ));
+        }
+
         Ok(())
     }
 
@@ -312,19 +371,28 @@ impl OrderManager {
 
 impl RateLimiter {
     pub fn new(max_orders_per_second: u32) -> Self {
+        let clock = LiveClock::shared();
         Self {
             max_orders_per_second,
-            window_start: Instant::now(),
+            window_start: clock.now(),
             order_count: 0,
+            clock,
         }
     }
 
+    /// Count windows on a clock, so replays are limited in replayed time
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.window_start = clock.now();
+        self.clock = clock;
+        self
+    }
+
     pub fn check_and_increment(&mut self) -> EngineResult<()> {
-        let elapsed = self.window_start.elapsed();
+        let now = self.clock.now();
 
         // Reset window every second
-        if elapsed >= std::time::Duration::from_secs(1) {
-            self.window_start = Instant::now();
+        if now - self.window_start >= chrono::Duration::seconds(1) {
+            self.window_start = now;
             self.order_count = 0;
         }
 
@@ -349,7 +417,18 @@ impl Order {
         quantity: f64,
         price: Option<f64>,
     ) -> Self {
-        let now = Utc::now();
+        Self::new_at(symbol, side, order_type, quantity, price, Utc::now())
+    }
+
+    /// Create a new order at a time, such as a clock reading
+    pub fn new_at(
+        symbol: String,
+        side: Side,
+        order_type: OrderType,
+        quantity: f64,
+        price: Option<f64>,
+        now: DateTime<Utc>,
+    ) -> Self {
         Self {
             id: uuid::Uuid::new_v4(),
             client_order_id: format!("CLT-{}", uuid::Uuid::new_v4()),
@@ -358,6 +437,8 @@ impl Order {
             order_type,
             quantity,
             price,
+            stop_price: None,
+            reduce_only: false,
             status: OrderStatus::Pending,
             created_at: now,
             updated_at: now,
@@ -367,6 +448,18 @@ impl Order {
         }
     }
 
+    /// Set the trigger price of a stop order
+    pub fn with_stop_price(mut self, stop_price: f64) -> Self {
+        self.stop_price = Some(stop_price);
+        self
+    }
+
+    /// Mark the order as only reducing an existing position
+    pub fn with_reduce_only(mut self, reduce_only: bool) -> Self {
+        self.reduce_only = reduce_only;
+        self
+    }
+
     /// Check if order is terminal (won't change anymore)
     pub fn is_terminal(&self) -> bool {
         matches!(
@@ -411,6 +504,21 @@ mod tests {
         assert_eq!(retrieved.status, OrderStatus::Pending);
     }
 
+    #[test]
+    fn test_new_at_stamps_given_time() {
+        let order = Order::new_at(
+            "BTC-USD-PERP".to_string(),
+            Side::Buy,
+            OrderType::Market,
+            0.1,
+            None,
+            DateTime::UNIX_EPOCH,
+        );
+
+        assert_eq!(order.created_at, DateTime::UNIX_EPOCH);
+        assert_eq!(order.updated_at, DateTime::UNIX_EPOCH);
+    }
+
     #[test]
     fn test_mark_submitted() {
         let mut manager = OrderManager::new(10);
@@ -466,6 +574,38 @@ mod tests {
         assert_eq!(completed.average_fill_price, 50_000.0);
     }
 
+    #[test]
+    fn test_restore_files_orders_by_status() {
+        let mut manager = OrderManager::new(10);
+        let order = |status| {
+            let mut order = Order::new(
+                "BTC-USD-PERP".to_string(),
+                Side::Buy,
+                OrderType::Market,
+                0.1,
+                None,
+            );
+            order.status = status;
+            order
+        };
+
+        let working = order(OrderStatus::PartiallyFilled);
+        let working_id = working.id;
+        manager.restore(
+            vec![
+                order(OrderStatus::Filled),
+                working,
+                order(OrderStatus::Pending),
+            ],
+            Vec::new(),
+        );
+
+        assert_eq!(manager.get_completed_orders().len(), 1);
+        assert_eq!(manager.get_active_orders()[0].id, working_id);
+        assert_eq!(manager.get_pending_orders().len(), 1);
+        assert_eq!(manager.all_orders().count(), 3);
+    }
+
     #[test]
     fn test_rate_limiter() {
         let mut limiter = RateLimiter::new(2);
@@ -478,6 +618,32 @@ mod tests {
         assert!(limiter.check_and_increment().is_err());
     }
 
+    #[test]
+    fn test_rate_limiter_counts_clock_time() {
+        let clock = std::sync::Arc::new(velora_core::SimulatedClock::new(DateTime::UNIX_EPOCH));
+        let mut manager = OrderManager::new(1).with_clock(clock.clone());
+        let order = || {
+            Order::new(
+                "BTC-USD-PERP".to_string(),
+                Side::Buy,
+                OrderType::Market,
+                0.1,
+                None,
+            )
+        };
+
+        let order_id = manager.submit_order(order()).unwrap();
+        assert_eq!(
+            manager.get_order(order_id).unwrap().created_at,
+            DateTime::UNIX_EPOCH
+        );
+        assert!(manager.submit_order(order()).is_err());
+
+        // A second of simulated time opens a new window
+        clock.advance(chrono::Duration::seconds(1));
+        assert!(manager.submit_order(order()).is_ok());
+    }
+
     #[test]
     fn test_validate_order() {
         let manager = OrderManager::new(10);
@@ -502,6 +668,19 @@ mod tests {
         );
         assert!(manager.validate_order(&order).is_err());
 
+        // Invalid: stop order without stop price
+        let order = Order::new(
+            "BTC-USD-PERP".to_string(),
+            Side::Sell,
+            OrderType::StopMarket,
+            0.1,
+            None,
+        );
+        assert!(manager.validate_order(&order).is_err());
+        assert!(manager
+            .validate_order(&order.with_stop_price(48_000.0))
+            .is_ok());
+
         // Valid: market order
         let order = Order::new(
             "BTC-USD-PERP".to_string(),
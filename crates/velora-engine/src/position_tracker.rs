@@ -5,6 +5,7 @@ use crate::events::Fill;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use velora_core::{LiveClock, SharedClock};
 use velora_strategy::PositionSide;
 
 /// Tracks positions and calculates real-time P&L
@@ -23,6 +24,9 @@ pub struct PositionTracker {
 
     /// Current market prices for each symbol
     current_prices: HashMap<String, f64>,
+
+    /// Time source for equity snapshots
+    clock: SharedClock,
 }
 
 /// A position in a symbol
@@ -87,9 +91,16 @@ impl PositionTracker {
             initial_capital,
             equity_history: Vec::new(),
             current_prices: HashMap::new(),
+            clock: LiveClock::shared(),
         }
     }
 
+    /// Stamp equity snapshots with a clock
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Process a fill (open, add to, or reduce position)
     pub fn process_fill(&mut self, fill: &Fill) -> EngineResult<()> {
         // Deduct commission from cash
@@ -257,6 +268,40 @@ impl PositionTracker {
         }
     }
 
+    /// Settle a funding rate on the position in a symbol
+    ///
+    /// Longs pay `rate` on their notional at the current price and shorts
+    /// receive it (the other way round when the rate is negative). Returns
+    /// the amount paid, negative when received.
+    pub fn apply_funding(&mut self, symbol: &str, rate: f64) -> f64 {
+        let Some(position) = self.positions.get_mut(symbol) else {
+            return 0.0;
+        };
+
+        let notional = position.quantity * position.current_price;
+        let payment = match position.side {
+            PositionSide::Long => notional * rate,
+            PositionSide::Short => -notional * rate,
+        };
+        self.cash -= payment;
+        position.realized_pnl -= payment;
+        position.last_updated = self.clock.now();
+        payment
+    }
+
+    /// Replace positions and cash with recovered ones
+    pub fn restore(&mut self, positions: Vec<Position>, cash: f64) {
+        self.current_prices = positions
+            .iter()
+            .map(|position| (position.symbol.clone(), position.current_price))
+            .collect();
+        self.positions = positions
+            .into_iter()
+            .map(|position| (position.symbol.clone(), position))
+            .collect();
+        self.cash = cash;
+    }
+
     /// Update position's unrealized P&L
     fn update_position_pnl(&mut self, position: &mut Position) {
         position.unrealized_pnl = match position.side {
@@ -309,7 +354,7 @@ impl PositionTracker {
             .sum();
 
         EquitySnapshot {
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
             total_equity: self.total_equity(),
             cash: self.cash,
             positions_value,
@@ -439,6 +484,25 @@ mod tests {
         assert_eq!(tracker.total_equity(), 15_195.0);
     }
 
+    #[test]
+    fn test_apply_funding() {
+        let mut tracker = PositionTracker::new(10_000.0);
+        assert_eq!(tracker.apply_funding("BTC-USD-PERP", 0.001), 0.0);
+
+        let fill = create_fill("BTC-USD-PERP", Side::Buy, 0.1, 50_000.0);
+        tracker.process_fill(&fill).unwrap();
+        tracker.update_position_price("BTC-USD-PERP", 60_000.0);
+
+        // Long pays 0.1% of 0.1 * 60,000 = 6
+        assert_eq!(tracker.apply_funding("BTC-USD-PERP", 0.001), 6.0);
+        assert_eq!(tracker.available_cash(), 9_995.0 - 6.0);
+        assert_eq!(tracker.total_realized_pnl(), -6.0);
+
+        // A negative rate is paid to the long
+        assert_eq!(tracker.apply_funding("BTC-USD-PERP", -0.001), -6.0);
+        assert_eq!(tracker.available_cash(), 9_995.0);
+    }
+
     #[test]
     fn test_snapshot() {
         let mut tracker = PositionTracker::new(10_000.0);
@@ -0,0 +1,420 @@
+//! Simulated order book of resting dry-run orders
+
+use crate::events::OrderId;
+use crate::order_manager::Order;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use velora_core::{Candle, OrderType, Side, Tick};
+use velora_exchange::Liquidity;
+
+/// Price action an order book is matched against
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PriceBar {
+    /// First traded price
+    pub open: f64,
+
+    /// Highest traded price
+    pub high: f64,
+
+    /// Lowest traded price
+    pub low: f64,
+
+    /// Traded volume (zero when unknown)
+    pub volume: f64,
+
+    /// Event time
+    pub timestamp: DateTime<Utc>,
+}
+
+impl From<&Candle> for PriceBar {
+    fn from(candle: &Candle) -> Self {
+        Self {
+            open: candle.open.into_inner(),
+            high: candle.high.into_inner(),
+            low: candle.low.into_inner(),
+            volume: candle.volume.into_inner(),
+            timestamp: candle.timestamp,
+        }
+    }
+}
+
+impl From<&Tick> for PriceBar {
+    fn from(tick: &Tick) -> Self {
+        let price = tick.price.into_inner();
+        Self {
+            open: price,
+            high: price,
+            low: price,
+            volume: tick.volume.into_inner(),
+            timestamp: tick.timestamp,
+        }
+    }
+}
+
+/// Execution of a resting order
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BookFill {
+    /// Order that was filled
+    pub order_id: OrderId,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// Buy or Sell
+    pub side: Side,
+
+    /// Quantity of this execution
+    pub quantity: f64,
+
+    /// Price of this execution
+    pub price: f64,
+
+    /// Total quantity filled so far
+    pub filled_quantity: f64,
+
+    /// Average price of all executions so far
+    pub average_price: f64,
+
+    /// Whether the order is now completely filled
+    pub complete: bool,
+
+    /// Maker for limits filled at rest, taker for stops
+    pub liquidity: Liquidity,
+
+    /// Execution time
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Order resting in the book
+#[derive(Debug, Clone)]
+struct RestingOrder {
+    /// The engine order
+    order: Order,
+
+    /// Stop price reached (always true for limit orders)
+    triggered: bool,
+
+    /// Quantity filled so far
+    filled_quantity: f64,
+
+    /// Average fill price so far
+    average_price: f64,
+
+    /// Arrival sequence, for time priority
+    sequence: u64,
+}
+
+impl RestingOrder {
+    fn remaining(&self) -> f64 {
+        self.order.quantity - self.filled_quantity
+    }
+
+    /// Priority at equal time: stop-markets first, then the most aggressive limit
+    fn priority(&self) -> f64 {
+        match (self.order.order_type, self.order.price) {
+            (OrderType::Market | OrderType::StopMarket, _) | (_, None) => f64::INFINITY,
+            (_, Some(limit)) => match self.order.side {
+                Side::Buy => limit,
+                Side::Sell => -limit,
+            },
+        }
+    }
+
+    /// Whether a stop order triggers within the bar
+    fn triggers(&self, bar: &PriceBar) -> bool {
+        let Some(stop) = self.order.stop_price else {
+            return true;
+        };
+        match self.order.side {
+            Side::Buy => bar.high >= stop,
+            Side::Sell => bar.low <= stop,
+        }
+    }
+
+    /// Price the order executes at within the bar, if it does
+    ///
+    /// Resting limits fill at their limit price. A stop-market fills where it
+    /// triggered, which is the open if the market gapped through the stop. A
+    /// stop-limit triggered inside the bar fills at the trigger price only if
+    /// that is within its limit; otherwise it rests at its limit.
+    fn execution_price(&self, bar: &PriceBar, triggered_now: bool) -> Option<f64> {
+        let side = self.order.side;
+        // Where a stop triggered inside this bar was hit
+        let trigger = self.order.stop_price.map(|stop| match side {
+            Side::Buy => bar.open.max(stop),
+            Side::Sell => bar.open.min(stop),
+        });
+
+        match (self.order.order_type, self.order.price) {
+            (OrderType::StopMarket, _) => Some(trigger.unwrap_or(bar.open)),
+            (_, Some(limit)) if triggered_now => {
+                let trigger = trigger.unwrap_or(bar.open);
+                let within = match side {
+                    Side::Buy => trigger <= limit,
+                    Side::Sell => trigger >= limit,
+                };
+                within.then_some(trigger)
+            }
+            (_, Some(limit)) => match side {
+                Side::Buy => (bar.low <= limit).then_some(limit),
+                Side::Sell => (bar.high >= limit).then_some(limit),
+            },
+            (_, None) => Some(bar.open),
+        }
+    }
+}
+
+/// Resting limit and stop orders per symbol
+///
+/// Orders are matched against candles and trades: limits fill when price
+/// trades through them, stops trigger when price reaches the stop. Fills are
+/// capped at the traded volume of the bar, shared in price-time priority
+/// between orders of the same side.
+#[derive(Debug, Default)]
+pub(crate) struct SimulatedBook {
+    orders: HashMap<String, Vec<RestingOrder>>,
+    next_sequence: u64,
+}
+
+impl SimulatedBook {
+    /// Rest an order in the book
+    ///
+    /// `triggered` marks a stop order whose stop price has already been reached.
+    /// Quantity the order already filled (when restoring it) is not filled again.
+    pub fn insert(&mut self, order: Order, triggered: bool) {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+
+        let triggered = triggered || order.stop_price.is_none();
+        self.orders
+            .entry(order.symbol.clone())
+            .or_default()
+            .push(RestingOrder {
+                filled_quantity: order.filled_quantity,
+                average_price: order.average_fill_price,
+                order,
+                triggered,
+                sequence,
+            });
+    }
+
+    /// Remove an order, returning its filled quantity and average price
+    pub fn cancel(&mut self, order_id: OrderId) -> Option<(f64, f64)> {
+        for orders in self.orders.values_mut() {
+            if let Some(index) = orders.iter().position(|r| r.order.id == order_id) {
+                let resting = orders.remove(index);
+                return Some((resting.filled_quantity, resting.average_price));
+            }
+        }
+        None
+    }
+
+    /// Number of resting orders
+    pub fn len(&self) -> usize {
+        self.orders.values().map(Vec::len).sum()
+    }
+
+    /// Match the resting orders of a symbol against a price bar
+    pub fn match_bar(&mut self, symbol: &str, bar: &PriceBar) -> Vec<BookFill> {
+        let Some(orders) = self.orders.get_mut(symbol) else {
+            return Vec::new();
+        };
+
+        // Work out what executes, then allocate volume in priority order
+        let mut executable: Vec<(usize, f64, Liquidity)> = Vec::new();
+        for (index, resting) in orders.iter_mut().enumerate() {
+            let triggered_now = !resting.triggered && resting.triggers(bar);
+            if !resting.triggered && !triggered_now {
+                continue;
+            }
+            resting.triggered = true;
+
+            if let Some(price) = resting.execution_price(bar, triggered_now) {
+                // Stops execute as they trigger; other orders waited on the book
+                let liquidity = match resting.order.order_type {
+                    OrderType::Limit => Liquidity::Maker,
+                    OrderType::StopLimit if !triggered_now => Liquidity::Maker,
+                    _ => Liquidity::Taker,
+                };
+                executable.push((index, price, liquidity));
+            }
+        }
+        executable.sort_by(|(a, ..), (b, ..)| {
+            let (a, b) = (&orders[*a], &orders[*b]);
+            b.priority()
+                .total_cmp(&a.priority())
+                .then(a.sequence.cmp(&b.sequence))
+        });
+
+        // Volume left for each side; zero volume means unknown and uncapped
+        let capped = bar.volume > 0.0;
+        let mut buy_volume = bar.volume;
+        let mut sell_volume = bar.volume;
+
+        let mut fills = Vec::new();
+        for (index, price, liquidity) in executable {
+            let resting = &mut orders[index];
+            let available = match resting.order.side {
+                Side::Buy => &mut buy_volume,
+                Side::Sell => &mut sell_volume,
+            };
+
+            let quantity = if capped {
+                resting.remaining().min(*available)
+            } else {
+                resting.remaining()
+            };
+            if quantity <= 0.0 {
+                continue;
+            }
+            *available -= quantity;
+
+            let filled = resting.filled_quantity + quantity;
+            resting.average_price =
+                (resting.average_price * resting.filled_quantity + price * quantity) / filled;
+            resting.filled_quantity = filled;
+
+            fills.push(BookFill {
+                order_id: resting.order.id,
+                symbol: symbol.to_string(),
+                side: resting.order.side,
+                quantity,
+                price,
+                filled_quantity: resting.filled_quantity,
+                average_price: resting.average_price,
+                complete: resting.remaining() <= f64::EPSILON * resting.order.quantity,
+                liquidity,
+                timestamp: bar.timestamp,
+            });
+        }
+
+        orders.retain(|resting| resting.remaining() > f64::EPSILON * resting.order.quantity);
+        fills
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(open: f64, high: f64, low: f64, volume: f64) -> PriceBar {
+        PriceBar {
+            open,
+            high,
+            low,
+            volume,
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn limit(side: Side, quantity: f64, price: f64) -> Order {
+        Order::new(
+            "BTC-USD".to_string(),
+            side,
+            OrderType::Limit,
+            quantity,
+            Some(price),
+        )
+    }
+
+    fn stop(side: Side, order_type: OrderType, stop: f64, price: Option<f64>) -> Order {
+        Order::new("BTC-USD".to_string(), side, order_type, 1.0, price).with_stop_price(stop)
+    }
+
+    #[test]
+    fn test_limit_fills_when_price_trades_through() {
+        let mut book = SimulatedBook::default();
+        book.insert(limit(Side::Buy, 1.0, 95.0), false);
+        book.insert(limit(Side::Sell, 1.0, 110.0), false);
+
+        assert!(book
+            .match_bar("BTC-USD", &bar(100.0, 105.0, 96.0, 0.0))
+            .is_empty());
+
+        let fills = book.match_bar("BTC-USD", &bar(100.0, 105.0, 94.0, 0.0));
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].side, Side::Buy);
+        assert_eq!(fills[0].price, 95.0);
+        assert!(fills[0].complete);
+        assert_eq!(book.len(), 1);
+
+        // Resting limits are not price-improved by gaps
+        let fills = book.match_bar("BTC-USD", &bar(112.0, 115.0, 111.0, 0.0));
+        assert_eq!(fills[0].price, 110.0);
+        assert_eq!(book.len(), 0);
+    }
+
+    #[test]
+    fn test_partial_fills_follow_volume_in_priority_order() {
+        let mut book = SimulatedBook::default();
+        let first = limit(Side::Buy, 2.0, 95.0);
+        let better = limit(Side::Buy, 2.0, 97.0);
+        let (first_id, better_id) = (first.id, better.id);
+        book.insert(first, false);
+        book.insert(better, false);
+
+        let fills = book.match_bar("BTC-USD", &bar(100.0, 100.0, 90.0, 3.0));
+        assert_eq!(fills.len(), 2);
+        assert_eq!((fills[0].order_id, fills[0].quantity), (better_id, 2.0));
+        assert!(fills[0].complete);
+        assert_eq!((fills[1].order_id, fills[1].quantity), (first_id, 1.0));
+        assert!(!fills[1].complete);
+
+        let fills = book.match_bar("BTC-USD", &bar(96.0, 96.0, 94.0, 5.0));
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].filled_quantity, 2.0);
+        assert_eq!(fills[0].average_price, 95.0);
+        assert!(fills[0].complete);
+        assert_eq!(book.len(), 0);
+    }
+
+    #[test]
+    fn test_stop_market_triggers() {
+        let mut book = SimulatedBook::default();
+        book.insert(stop(Side::Sell, OrderType::StopMarket, 90.0, None), false);
+
+        assert!(book
+            .match_bar("BTC-USD", &bar(100.0, 101.0, 91.0, 0.0))
+            .is_empty());
+
+        let fills = book.match_bar("BTC-USD", &bar(95.0, 96.0, 85.0, 0.0));
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, 90.0);
+
+        // Gap below the stop fills at the open
+        book.insert(stop(Side::Sell, OrderType::StopMarket, 90.0, None), false);
+        let fills = book.match_bar("BTC-USD", &bar(80.0, 82.0, 79.0, 0.0));
+        assert_eq!(fills[0].price, 80.0);
+    }
+
+    #[test]
+    fn test_stop_limit_rests_after_gapping_past_limit() {
+        let mut book = SimulatedBook::default();
+        book.insert(
+            stop(Side::Buy, OrderType::StopLimit, 100.0, Some(101.0)),
+            false,
+        );
+
+        // Triggered at the open of 103, above the limit
+        assert!(book
+            .match_bar("BTC-USD", &bar(103.0, 104.0, 102.0, 0.0))
+            .is_empty());
+        assert_eq!(book.len(), 1);
+
+        let fills = book.match_bar("BTC-USD", &bar(102.0, 102.0, 100.5, 0.0));
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, 101.0);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut book = SimulatedBook::default();
+        let order = limit(Side::Buy, 1.0, 95.0);
+        let order_id = order.id;
+        book.insert(order, false);
+
+        assert_eq!(book.cancel(order_id), Some((0.0, 0.0)));
+        assert_eq!(book.len(), 0);
+        assert_eq!(book.cancel(order_id), None);
+    }
+}
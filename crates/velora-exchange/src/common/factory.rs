@@ -41,6 +41,20 @@ impl ExchangeFactory {
         auth: AuthConfig,
     ) -> Result<Box<dyn Exchange>> {
         match (exchange_type, name.to_lowercase().as_str()) {
+            #[cfg(feature = "binance")]
+            (ExchangeType::CEX, "binance") => {
+                use crate::exchanges::binance::BinanceExchange;
+                let exchange = BinanceExchange::new(auth)?;
+                Ok(Box::new(exchange))
+            }
+
+            #[cfg(feature = "binance")]
+            (ExchangeType::CEX, "binance-futures") => {
+                use crate::exchanges::binance::BinanceExchange;
+                let exchange = BinanceExchange::usdm_futures(auth)?;
+                Ok(Box::new(exchange))
+            }
+
             #[cfg(feature = "lighter")]
             (ExchangeType::DexZk, "lighter") => {
                 use crate::exchanges::lighter::LighterExchange;
@@ -69,6 +83,9 @@ impl ExchangeFactory {
         #[cfg(feature = "binance")]
         exchanges.push(("binance", ExchangeType::CEX));
 
+        #[cfg(feature = "binance")]
+        exchanges.push(("binance-futures", ExchangeType::CEX));
+
         #[cfg(feature = "lighter")]
         exchanges.push(("lighter", ExchangeType::DexZk));
 
@@ -78,6 +78,16 @@ impl RateLimiter {
         self.limiter.until_ready().await;
     }
 
+    /// Wait until a request of the given weight can be made
+    ///
+    /// Exchanges such as Binance charge each endpoint a weight against a shared
+    /// budget. Weights larger than the burst size are clamped to it.
+    pub async fn wait_weight(&self, weight: u32) {
+        let weight = NonZeroU32::new(weight.clamp(1, self.max_requests)).expect("Non-zero weight");
+        // Cannot fail: the weight never exceeds the burst size
+        let _ = self.limiter.until_n_ready(weight).await;
+    }
+
     /// Check if a request can be made immediately (non-blocking)
     ///
     /// Returns `true` if the request can proceed, `false` otherwise.
@@ -123,6 +133,17 @@ mod tests {
         assert_eq!(limiter.period(), Duration::from_secs(60));
     }
 
+    #[tokio::test]
+    async fn test_weighted_requests() {
+        let limiter = RateLimiter::new(10, Duration::from_secs(60));
+
+        // A heavy request consumes most of the budget
+        limiter.wait_weight(8).await;
+        assert!(limiter.check());
+        assert!(limiter.check());
+        assert!(!limiter.check());
+    }
+
     #[tokio::test]
     async fn test_rate_limiting() {
         let limiter = RateLimiter::new(2, Duration::from_secs(1));
@@ -89,6 +89,26 @@ impl RestClient {
             .await
     }
 
+    /// Perform a POST request with a URL-encoded form body
+    pub async fn post_form<T: DeserializeOwned, F: Serialize + ?Sized>(
+        &self,
+        endpoint: &str,
+        form: &F,
+    ) -> Result<T> {
+        let url = format!("{}{}", self.base_url, endpoint);
+        debug!("POST (form) request to: {}", url);
+
+        let request = self
+            .client
+            .post(&url)
+            .headers(self.default_headers.clone())
+            .form(form);
+
+        let response = request.send().await.map_err(ExchangeError::Network)?;
+
+        self.handle_response(response).await
+    }
+
     /// Perform a PUT request
     pub async fn put<T: DeserializeOwned, B: Serialize>(
         &self,
@@ -137,9 +157,14 @@ impl RestClient {
         let status = response.status();
 
         if status.is_success() {
-            response
-                .json::<T>()
-                .await
+            let body = response.text().await.map_err(ExchangeError::Network)?;
+            // Empty bodies (e.g. 204 No Content) deserialize as null
+            let body = if body.trim().is_empty() {
+                "null"
+            } else {
+                &body
+            };
+            serde_json::from_str(body)
                 .map_err(|e| ExchangeError::ParseError(format!("Failed to parse response: {e}")))
         } else {
             let error_text = response
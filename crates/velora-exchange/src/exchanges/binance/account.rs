@@ -0,0 +1,268 @@
+//! Binance account implementation
+
+use async_trait::async_trait;
+use reqwest::Method;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+use crate::{
+    traits::Account,
+    types::{
+        AccountInfo, AccountType, Balance, ExchangeError, MarginType, Position, PositionSide,
+        Result, Side, Symbol, TradeExecution,
+    },
+};
+
+use super::{
+    binance_symbol, endpoints, millis_to_datetime, parse_decimal, parse_price,
+    rest::{BinanceRest, Params},
+    types::*,
+    BinanceProduct,
+};
+
+/// Binance account component
+pub struct BinanceAccount {
+    rest: Arc<BinanceRest>,
+}
+
+/// Map a Binance margin type ("cross"/"crossed" or "isolated")
+pub(crate) fn convert_margin_type(margin_type: &str) -> MarginType {
+    if margin_type.eq_ignore_ascii_case("isolated") {
+        MarginType::Isolated
+    } else {
+        MarginType::Cross
+    }
+}
+
+/// Side and size of a futures position
+///
+/// In one-way mode the amount is signed; in hedge mode the position side says
+/// which leg it is and the amount of a short leg is negative as well.
+pub(crate) fn position_side(position_side: &str, amount: Decimal) -> (PositionSide, Decimal) {
+    let side = match position_side {
+        "LONG" => PositionSide::Long,
+        "SHORT" => PositionSide::Short,
+        _ if amount < Decimal::ZERO => PositionSide::Short,
+        _ => PositionSide::Long,
+    };
+    (side, amount.abs())
+}
+
+impl BinanceAccount {
+    pub(crate) fn new(rest: Arc<BinanceRest>) -> Self {
+        Self { rest }
+    }
+
+    /// Fetch every USDⓈ-M position, flat ones included
+    pub(crate) async fn position_risk(rest: &BinanceRest) -> Result<Vec<BinancePositionRisk>> {
+        rest.signed(Method::GET, endpoints::USDM_POSITION_RISK, Vec::new(), 5)
+            .await
+    }
+
+    /// Convert a USDⓈ-M position, or None when flat
+    pub(crate) fn convert_position(position: BinancePositionRisk) -> Result<Option<Position>> {
+        let amount = parse_decimal(&position.position_amt, "position amount")?;
+        if amount.is_zero() {
+            return Ok(None);
+        }
+
+        let (side, quantity) = position_side(&position.position_side, amount);
+        let leverage = position.leverage.parse::<u32>().unwrap_or(1).max(1);
+        let margin_type = convert_margin_type(&position.margin_type);
+        let margin = match margin_type {
+            MarginType::Isolated => parse_decimal(&position.isolated_margin, "isolated margin")?,
+            MarginType::Cross => {
+                parse_decimal(&position.notional, "notional")?.abs() / Decimal::from(leverage)
+            }
+        };
+        let liquidation_price = parse_price(&position.liquidation_price, "liquidation price")?;
+
+        Ok(Some(Position {
+            symbol: Symbol::from(position.symbol.as_str()),
+            side,
+            quantity,
+            entry_price: parse_price(&position.entry_price, "entry price")?,
+            mark_price: parse_price(&position.mark_price, "mark price")?,
+            liquidation_price: (liquidation_price.0 > 0.0).then_some(liquidation_price),
+            leverage,
+            unrealized_pnl: parse_decimal(&position.un_realized_profit, "unrealized pnl")?,
+            realized_pnl: Decimal::ZERO, // Not reported per position
+            margin,
+            margin_type,
+            update_time: millis_to_datetime(position.update_time),
+        }))
+    }
+
+    fn convert_trade(trade: BinanceUserTrade, symbol: Symbol) -> Result<TradeExecution> {
+        Ok(TradeExecution {
+            trade_id: trade.id.to_string(),
+            order_id: trade.order_id.to_string(),
+            symbol,
+            side: if trade.is_buyer {
+                Side::Buy
+            } else {
+                Side::Sell
+            },
+            price: parse_price(&trade.price, "trade price")?,
+            quantity: parse_decimal(&trade.qty, "trade quantity")?,
+            fee: parse_decimal(&trade.commission, "commission")?,
+            fee_asset: trade.commission_asset,
+            is_maker: trade.is_maker,
+            timestamp: millis_to_datetime(trade.time),
+        })
+    }
+}
+
+#[async_trait]
+impl Account for BinanceAccount {
+    async fn get_account_info(&self) -> Result<AccountInfo> {
+        let routes = self.rest.routes();
+        match self.rest.product() {
+            BinanceProduct::Spot => {
+                let account: BinanceSpotAccount = self
+                    .rest
+                    .signed(Method::GET, routes.account, Vec::new(), 20)
+                    .await?;
+
+                // Commissions are reported in basis points
+                Ok(AccountInfo {
+                    account_type: AccountType::Spot,
+                    can_trade: account.can_trade,
+                    can_withdraw: account.can_withdraw,
+                    can_deposit: account.can_deposit,
+                    maker_commission: Decimal::new(account.maker_commission, 4),
+                    taker_commission: Decimal::new(account.taker_commission, 4),
+                    update_time: millis_to_datetime(account.update_time),
+                })
+            }
+            BinanceProduct::UsdmFutures => {
+                let account: BinanceFuturesAccount = self
+                    .rest
+                    .signed(Method::GET, routes.account, Vec::new(), 5)
+                    .await?;
+
+                // Fee tiers live behind a separate endpoint and are not reported here
+                Ok(AccountInfo {
+                    account_type: AccountType::Futures,
+                    can_trade: account.can_trade,
+                    can_withdraw: account.can_withdraw,
+                    can_deposit: account.can_deposit,
+                    maker_commission: Decimal::ZERO,
+                    taker_commission: Decimal::ZERO,
+                    update_time: millis_to_datetime(account.update_time),
+                })
+            }
+        }
+    }
+
+    async fn get_balances(&self) -> Result<Vec<Balance>> {
+        let routes = self.rest.routes();
+        match self.rest.product() {
+            BinanceProduct::Spot => {
+                let account: BinanceSpotAccount = self
+                    .rest
+                    .signed(Method::GET, routes.account, Vec::new(), 20)
+                    .await?;
+
+                account
+                    .balances
+                    .into_iter()
+                    .map(|b| {
+                        Ok(Balance {
+                            free: parse_decimal(&b.free, "free balance")?,
+                            locked: parse_decimal(&b.locked, "locked balance")?,
+                            asset: b.asset,
+                        })
+                    })
+                    .collect()
+            }
+            BinanceProduct::UsdmFutures => {
+                let account: BinanceFuturesAccount = self
+                    .rest
+                    .signed(Method::GET, routes.account, Vec::new(), 5)
+                    .await?;
+
+                account
+                    .assets
+                    .into_iter()
+                    .map(|a| {
+                        let wallet = parse_decimal(&a.wallet_balance, "wallet balance")?;
+                        let available = parse_decimal(&a.available_balance, "available balance")?;
+                        // Available balance includes unrealized profit; never report more
+                        // free than the wallet holds
+                        let free = available.min(wallet);
+                        Ok(Balance {
+                            asset: a.asset,
+                            free,
+                            locked: wallet - free,
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    async fn get_balance(&self, asset: &str) -> Result<Balance> {
+        self.get_balances()
+            .await?
+            .into_iter()
+            .find(|b| b.asset.eq_ignore_ascii_case(asset))
+            .ok_or_else(|| ExchangeError::InvalidRequest(format!("No {asset} balance on Binance")))
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        if self.rest.product() == BinanceProduct::Spot {
+            return Ok(Vec::new());
+        }
+
+        let mut positions = Vec::new();
+        for position in Self::position_risk(&self.rest).await? {
+            if let Some(position) = Self::convert_position(position)? {
+                positions.push(position);
+            }
+        }
+        Ok(positions)
+    }
+
+    async fn get_position(&self, symbol: &Symbol) -> Result<Option<Position>> {
+        let native = binance_symbol(symbol);
+        Ok(self
+            .get_positions()
+            .await?
+            .into_iter()
+            .find(|p| p.symbol.as_str() == native)
+            .map(|mut p| {
+                p.symbol = symbol.clone();
+                p
+            }))
+    }
+
+    async fn get_trade_history(
+        &self,
+        symbol: Option<&Symbol>,
+        limit: Option<usize>,
+    ) -> Result<Vec<TradeExecution>> {
+        let symbol = symbol.ok_or_else(|| {
+            ExchangeError::InvalidRequest("Binance trade history requires a symbol".to_string())
+        })?;
+
+        let mut params: Params = vec![("symbol", binance_symbol(symbol))];
+        if let Some(limit) = limit {
+            params.push(("limit", limit.clamp(1, 1000).to_string()));
+        }
+        let trades: Vec<BinanceUserTrade> = self
+            .rest
+            .signed(
+                Method::GET,
+                self.rest.routes().my_trades,
+                params,
+                self.rest.weight(20, 5),
+            )
+            .await?;
+
+        trades
+            .into_iter()
+            .map(|t| Self::convert_trade(t, symbol.clone()))
+            .collect()
+    }
+}
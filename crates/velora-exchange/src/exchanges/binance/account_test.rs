@@ -0,0 +1,299 @@
+//! Tests for Binance account implementation
+
+#[cfg(test)]
+mod tests {
+    use super::super::account::*;
+    use super::super::rest::BinanceRest;
+    use super::super::BinanceProduct;
+
+    use crate::auth::ApiKeyAuth;
+    use crate::common::{RateLimiter, RestClient};
+    use crate::traits::Account;
+    use crate::types::{AccountType, ExchangeError, MarginType, PositionSide, Price, Side, Symbol};
+
+    use mockito::{Matcher, Server};
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+
+    const POSITION_RISK: &str = r#"[
+        {
+            "symbol": "BTCUSDT", "positionAmt": "-0.010", "entryPrice": "60000.0",
+            "breakEvenPrice": "60010.0", "markPrice": "59000.00", "unRealizedProfit": "10.00",
+            "liquidationPrice": "75000.5", "leverage": "10", "maxNotionalValue": "1000000",
+            "marginType": "cross", "isolatedMargin": "0.00000000", "isAutoAddMargin": "false",
+            "positionSide": "BOTH", "notional": "-590.00", "isolatedWallet": "0",
+            "updateTime": 1700000000000
+        },
+        {
+            "symbol": "ETHUSDT", "positionAmt": "0.000", "entryPrice": "0.0",
+            "markPrice": "3000.00", "unRealizedProfit": "0.00", "liquidationPrice": "0",
+            "leverage": "20", "marginType": "cross", "isolatedMargin": "0.00000000",
+            "positionSide": "BOTH", "notional": "0", "updateTime": 0
+        },
+        {
+            "symbol": "SOLUSDT", "positionAmt": "5", "entryPrice": "100.0",
+            "markPrice": "101.00", "unRealizedProfit": "5.00", "liquidationPrice": "0",
+            "leverage": "5", "marginType": "isolated", "isolatedMargin": "105.00",
+            "positionSide": "LONG", "notional": "505", "updateTime": 1700000000000
+        }
+    ]"#;
+
+    /// Helper to create an account component against a mocked server
+    fn setup_test(server: &Server, product: BinanceProduct) -> BinanceAccount {
+        let rest_client =
+            Arc::new(RestClient::new(server.url(), std::time::Duration::from_secs(30)).unwrap());
+        let rate_limiter = Arc::new(RateLimiter::new(1000, std::time::Duration::from_secs(1)));
+        let rest = BinanceRest::new(
+            rest_client,
+            Arc::clone(&rate_limiter),
+            rate_limiter,
+            Some(ApiKeyAuth::new("test-api-key", "test-secret")),
+            product,
+        );
+
+        BinanceAccount::new(Arc::new(rest))
+    }
+
+    #[tokio::test]
+    async fn test_spot_account_info_and_balances() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/api/v3/account")
+            .match_header("x-mbx-apikey", "test-api-key")
+            .match_query(Matcher::Regex("signature=".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "makerCommission": 10, "takerCommission": 15, "buyerCommission": 0,
+                    "sellerCommission": 0, "canTrade": true, "canWithdraw": false,
+                    "canDeposit": true, "brokered": false, "updateTime": 1700000000000,
+                    "accountType": "SPOT",
+                    "balances": [
+                        {"asset": "BTC", "free": "0.50000000", "locked": "0.10000000"},
+                        {"asset": "USDT", "free": "1000.00", "locked": "0.00"}
+                    ],
+                    "permissions": ["SPOT"]
+                }"#,
+            )
+            .expect(2)
+            .create_async()
+            .await;
+
+        let account = setup_test(&server, BinanceProduct::Spot);
+
+        let info = account.get_account_info().await.unwrap();
+        assert_eq!(info.account_type, AccountType::Spot);
+        assert!(info.can_trade);
+        assert!(!info.can_withdraw);
+        assert_eq!(info.maker_commission, dec!(0.001));
+        assert_eq!(info.taker_commission, dec!(0.0015));
+
+        let btc = account.get_balance("btc").await.unwrap();
+        assert_eq!(btc.free, dec!(0.5));
+        assert_eq!(btc.locked, dec!(0.1));
+        assert_eq!(btc.total(), dec!(0.6));
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_futures_balances_cap_free_at_wallet() {
+        let mut server = Server::new_async().await;
+
+        let _mock = server
+            .mock("GET", "/fapi/v2/account")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "canTrade": true, "canWithdraw": true, "canDeposit": true,
+                    "updateTime": 0, "totalWalletBalance": "1500.00",
+                    "assets": [
+                        {"asset": "USDT", "walletBalance": "1000.00", "availableBalance": "700.00",
+                         "unrealizedProfit": "-10.00", "marginBalance": "990.00"},
+                        {"asset": "BNB", "walletBalance": "1.00", "availableBalance": "1.20",
+                         "unrealizedProfit": "0.20", "marginBalance": "1.20"}
+                    ],
+                    "positions": []
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let account = setup_test(&server, BinanceProduct::UsdmFutures);
+        let balances = account.get_balances().await.unwrap();
+
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].asset, "USDT");
+        assert_eq!(balances[0].free, dec!(700));
+        assert_eq!(balances[0].locked, dec!(300));
+        assert_eq!(balances[1].free, dec!(1));
+        assert_eq!(balances[1].locked, dec!(0));
+    }
+
+    #[tokio::test]
+    async fn test_missing_balance() {
+        let mut server = Server::new_async().await;
+
+        let _mock = server
+            .mock("GET", "/api/v3/account")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"makerCommission": 10, "takerCommission": 10, "canTrade": true,
+                    "canWithdraw": true, "canDeposit": true, "updateTime": 0, "balances": []}"#,
+            )
+            .create_async()
+            .await;
+
+        let account = setup_test(&server, BinanceProduct::Spot);
+        assert!(matches!(
+            account.get_balance("DOGE").await,
+            Err(ExchangeError::InvalidRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_futures_positions_skip_flat() {
+        let mut server = Server::new_async().await;
+
+        let _mock = server
+            .mock("GET", "/fapi/v2/positionRisk")
+            .match_query(Matcher::Regex("signature=".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(POSITION_RISK)
+            .create_async()
+            .await;
+
+        let account = setup_test(&server, BinanceProduct::UsdmFutures);
+        let positions = account.get_positions().await.unwrap();
+
+        assert_eq!(positions.len(), 2);
+
+        let btc = &positions[0];
+        assert_eq!(btc.side, PositionSide::Short);
+        assert_eq!(btc.quantity, dec!(0.01));
+        assert_eq!(btc.leverage, 10);
+        assert_eq!(btc.margin_type, MarginType::Cross);
+        assert_eq!(btc.margin, dec!(59));
+        assert_eq!(btc.liquidation_price, Some(Price::from(75000.5)));
+
+        let sol = &positions[1];
+        assert_eq!(sol.side, PositionSide::Long);
+        assert_eq!(sol.margin_type, MarginType::Isolated);
+        assert_eq!(sol.margin, dec!(105));
+        assert_eq!(sol.liquidation_price, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_position_keeps_caller_symbol() {
+        let mut server = Server::new_async().await;
+
+        let _mock = server
+            .mock("GET", "/fapi/v2/positionRisk")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(POSITION_RISK)
+            .create_async()
+            .await;
+
+        let account = setup_test(&server, BinanceProduct::UsdmFutures);
+        let symbol = Symbol::from("BTC/USDT");
+
+        let position = account.get_position(&symbol).await.unwrap().unwrap();
+        assert_eq!(position.symbol, symbol);
+
+        let flat = account
+            .get_position(&Symbol::from("ETHUSDT"))
+            .await
+            .unwrap();
+        assert!(flat.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_spot_has_no_positions() {
+        let server = Server::new_async().await;
+        let account = setup_test(&server, BinanceProduct::Spot);
+
+        assert!(account.get_positions().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_trade_history() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/fapi/v1/userTrades")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()),
+                Matcher::UrlEncoded("limit".into(), "1000".into()),
+                Matcher::Regex("signature=".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                    {
+                        "buyer": false, "commission": "-0.07819010", "commissionAsset": "USDT",
+                        "id": 698759, "maker": true, "orderId": 25851813, "price": "7819.01",
+                        "qty": "0.002", "quoteQty": "15.63802", "realizedPnl": "-0.91539999",
+                        "side": "SELL", "positionSide": "BOTH", "symbol": "BTCUSDT",
+                        "time": 1700000000000
+                    }
+                ]"#,
+            )
+            .create_async()
+            .await;
+
+        let account = setup_test(&server, BinanceProduct::UsdmFutures);
+        let symbol = Symbol::from("BTC/USDT");
+        let trades = account
+            .get_trade_history(Some(&symbol), Some(5000))
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].trade_id, "698759");
+        assert_eq!(trades[0].order_id, "25851813");
+        assert_eq!(trades[0].symbol, symbol);
+        assert_eq!(trades[0].side, Side::Sell);
+        assert!(trades[0].is_maker);
+        assert_eq!(trades[0].fee, dec!(-0.0781901));
+    }
+
+    #[tokio::test]
+    async fn test_trade_history_requires_symbol() {
+        let server = Server::new_async().await;
+        let account = setup_test(&server, BinanceProduct::Spot);
+
+        assert!(matches!(
+            account.get_trade_history(None, None).await,
+            Err(ExchangeError::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_position_side() {
+        assert_eq!(
+            position_side("BOTH", dec!(-2)),
+            (PositionSide::Short, dec!(2))
+        );
+        assert_eq!(
+            position_side("BOTH", dec!(2)),
+            (PositionSide::Long, dec!(2))
+        );
+        assert_eq!(
+            position_side("SHORT", dec!(-1)),
+            (PositionSide::Short, dec!(1))
+        );
+        assert_eq!(convert_margin_type("crossed"), MarginType::Cross);
+        assert_eq!(convert_margin_type("ISOLATED"), MarginType::Isolated);
+    }
+}
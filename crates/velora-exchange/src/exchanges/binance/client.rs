@@ -0,0 +1,176 @@
+//! Binance Exchange client implementation
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::{
+    auth::AuthConfig,
+    common::{RateLimiter, RestClient, WebSocketClient},
+    traits::{Account, Exchange, MarketData, Streaming, Trading},
+    types::{ExchangeError, ExchangeType, InstrumentType, Result},
+};
+
+use super::{
+    account::BinanceAccount, market_data::BinanceMarketData, rest::BinanceRest,
+    streaming::BinanceStreaming, trading::BinanceTrading, BinanceProduct, SUPPORTED_INSTRUMENTS,
+};
+
+/// Binance Exchange implementation
+pub struct BinanceExchange {
+    /// Product line served by this client
+    product: BinanceProduct,
+
+    /// WebSocket client
+    ws_client: Arc<RwLock<WebSocketClient>>,
+
+    /// Connection status
+    connected: bool,
+
+    /// Market data component
+    market_data: BinanceMarketData,
+
+    /// Trading component
+    trading: BinanceTrading,
+
+    /// Account component
+    account: BinanceAccount,
+
+    /// Streaming component
+    streaming: BinanceStreaming,
+}
+
+impl BinanceExchange {
+    /// Create a Binance spot client (`AuthConfig::None` for read-only mode)
+    pub fn new(auth: AuthConfig) -> Result<Self> {
+        Self::with_product(BinanceProduct::Spot, false, auth)
+    }
+
+    /// Create a Binance USDⓈ-M futures client
+    pub fn usdm_futures(auth: AuthConfig) -> Result<Self> {
+        Self::with_product(BinanceProduct::UsdmFutures, false, auth)
+    }
+
+    /// Create a client for the Binance testnet of a product line
+    pub fn testnet(product: BinanceProduct, auth: AuthConfig) -> Result<Self> {
+        Self::with_product(product, true, auth)
+    }
+
+    /// Create a client with custom URLs (for testing/proxies)
+    ///
+    /// `ws_url` is the stream host without path, e.g. `wss://stream.binance.com:9443`.
+    pub fn new_with_config(
+        product: BinanceProduct,
+        rest_url: String,
+        ws_url: String,
+        auth: AuthConfig,
+    ) -> Result<Self> {
+        let rest_client = Arc::new(RestClient::new(rest_url, Duration::from_secs(30))?);
+        let ws_client = Arc::new(RwLock::new(WebSocketClient::new(format!("{ws_url}/ws"))));
+
+        let api_key = match auth {
+            AuthConfig::ApiKey(api_key) => Some(api_key),
+            AuthConfig::None => None, // No authentication (read-only mode)
+            _ => {
+                return Err(ExchangeError::Authentication(
+                    "Binance requires API key authentication (got incompatible auth type)"
+                        .to_string(),
+                ));
+            }
+        };
+
+        let rest = Arc::new(BinanceRest::new(
+            rest_client,
+            Arc::new(RateLimiter::binance()),
+            Arc::new(RateLimiter::binance_orders()),
+            api_key,
+            product,
+        ));
+
+        Ok(Self {
+            product,
+            ws_client: Arc::clone(&ws_client),
+            connected: false,
+            market_data: BinanceMarketData::new(Arc::clone(&rest)),
+            trading: BinanceTrading::new(Arc::clone(&rest)),
+            account: BinanceAccount::new(Arc::clone(&rest)),
+            streaming: BinanceStreaming::new(ws_client, rest),
+        })
+    }
+
+    /// Product line served by this client
+    pub fn product(&self) -> BinanceProduct {
+        self.product
+    }
+
+    fn with_product(product: BinanceProduct, testnet: bool, auth: AuthConfig) -> Result<Self> {
+        let (rest_url, ws_url) = product.base_urls(testnet);
+        Self::new_with_config(product, rest_url.to_string(), ws_url.to_string(), auth)
+    }
+}
+
+#[async_trait]
+impl Exchange for BinanceExchange {
+    fn name(&self) -> &str {
+        "binance"
+    }
+
+    fn exchange_type(&self) -> ExchangeType {
+        ExchangeType::CEX
+    }
+
+    fn supported_instruments(&self) -> &[InstrumentType] {
+        match self.product {
+            BinanceProduct::Spot => &SUPPORTED_INSTRUMENTS[..1],
+            BinanceProduct::UsdmFutures => &SUPPORTED_INSTRUMENTS[1..],
+        }
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        info!("Connecting to Binance exchange ({:?})", self.product);
+
+        let mut ws = self.ws_client.write().await;
+        ws.connect().await?;
+        drop(ws);
+
+        self.connected = true;
+        info!("Successfully connected to Binance");
+
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        info!("Disconnecting from Binance exchange");
+
+        let mut ws = self.ws_client.write().await;
+        ws.close().await?;
+        drop(ws);
+
+        self.connected = false;
+        info!("Disconnected from Binance");
+
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    fn market_data(&self) -> &dyn MarketData {
+        &self.market_data
+    }
+
+    fn trading(&self) -> &dyn Trading {
+        &self.trading
+    }
+
+    fn account(&self) -> &dyn Account {
+        &self.account
+    }
+
+    fn streaming(&self) -> &dyn Streaming {
+        &self.streaming
+    }
+}
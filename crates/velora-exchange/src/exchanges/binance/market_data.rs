@@ -0,0 +1,470 @@
+//! Binance market data implementation
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    traits::MarketData,
+    types::{
+        Candle, ExchangeError, FundingRate, InstrumentType, Interval, Market, MarketStatus,
+        OrderBook, PriceLevel, Result, Side, Symbol, Ticker, Trade,
+    },
+};
+
+use super::{
+    binance_symbol, endpoints, millis_to_datetime, parse_decimal, parse_price,
+    rest::{BinanceRest, Params},
+    types::*,
+    BinanceProduct,
+};
+
+/// Depth limits accepted by the USDⓈ-M depth endpoint
+const USDM_DEPTH_LIMITS: &[usize] = &[5, 10, 20, 50, 100, 500, 1000];
+
+/// Binance market data component
+pub struct BinanceMarketData {
+    rest: Arc<BinanceRest>,
+}
+
+impl BinanceMarketData {
+    pub(crate) fn new(rest: Arc<BinanceRest>) -> Self {
+        Self { rest }
+    }
+
+    /// Binance interval string
+    #[doc(hidden)]
+    pub fn interval_to_string(interval: Interval) -> &'static str {
+        match interval {
+            Interval::Second1 => "1s",
+            Interval::Minute1 => "1m",
+            Interval::Minute5 => "5m",
+            Interval::Minute15 => "15m",
+            Interval::Minute30 => "30m",
+            Interval::Hour1 => "1h",
+            Interval::Hour4 => "4h",
+            Interval::Day1 => "1d",
+            Interval::Week1 => "1w",
+        }
+    }
+
+    /// Depth limit to request and its weight
+    ///
+    /// USDⓈ-M only accepts a fixed set of limits, so the requested depth is
+    /// rounded up and the book truncated afterwards.
+    #[doc(hidden)]
+    pub fn depth_request(product: BinanceProduct, depth: Option<usize>) -> (usize, u32) {
+        let depth = depth.unwrap_or(100).max(1);
+        match product {
+            BinanceProduct::Spot => {
+                let limit = depth.min(5000);
+                let weight = match limit {
+                    0..=100 => 5,
+                    101..=500 => 25,
+                    501..=1000 => 50,
+                    _ => 250,
+                };
+                (limit, weight)
+            }
+            BinanceProduct::UsdmFutures => {
+                let limit = USDM_DEPTH_LIMITS
+                    .iter()
+                    .copied()
+                    .find(|limit| *limit >= depth)
+                    .unwrap_or(1000);
+                let weight = match limit {
+                    0..=50 => 2,
+                    51..=100 => 5,
+                    101..=500 => 10,
+                    _ => 20,
+                };
+                (limit, weight)
+            }
+        }
+    }
+
+    /// Convert `[price, quantity]` levels
+    pub(crate) fn convert_levels(levels: &[[String; 2]]) -> Result<Vec<PriceLevel>> {
+        levels
+            .iter()
+            .map(|[price, quantity]| {
+                Ok(PriceLevel {
+                    price: parse_price(price, "level price")?,
+                    quantity: parse_decimal(quantity, "level quantity")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Convert a depth snapshot, keeping at most `depth` levels per side
+    pub(crate) fn convert_depth(
+        depth: BinanceDepth,
+        symbol: Symbol,
+        max_levels: Option<usize>,
+    ) -> Result<OrderBook> {
+        let mut bids = Self::convert_levels(&depth.bids)?;
+        let mut asks = Self::convert_levels(&depth.asks)?;
+        if let Some(max_levels) = max_levels {
+            bids.truncate(max_levels);
+            asks.truncate(max_levels);
+        }
+
+        Ok(OrderBook {
+            symbol,
+            bids,
+            asks,
+            timestamp: Utc::now(),
+            last_update_id: Some(depth.last_update_id),
+        })
+    }
+
+    fn convert_market(product: BinanceProduct, info: BinanceSymbolInfo) -> Result<Market> {
+        let status = match info.status.as_str() {
+            "TRADING" => MarketStatus::Trading,
+            "PRE_TRADING" | "PENDING_TRADING" => MarketStatus::PreTrading,
+            "POST_TRADING" => MarketStatus::PostTrading,
+            "BREAK" => MarketStatus::Break,
+            "HALT" => MarketStatus::Halted,
+            _ => MarketStatus::Closed, // END_OF_DAY, SETTLING, DELIVERING, CLOSE, ...
+        };
+
+        let instrument_type = match (product, info.contract_type.as_deref()) {
+            (BinanceProduct::Spot, _) => InstrumentType::Spot,
+            (BinanceProduct::UsdmFutures, Some("PERPETUAL") | None) => InstrumentType::Perpetual,
+            (BinanceProduct::UsdmFutures, Some(_)) => InstrumentType::Futures,
+        };
+
+        let mut market = Market {
+            symbol: Symbol::from(info.symbol.as_str()),
+            base_asset: info.base_asset,
+            quote_asset: info.quote_asset,
+            instrument_type,
+            status,
+            min_quantity: Decimal::ZERO,
+            max_quantity: Decimal::MAX,
+            step_size: Decimal::ZERO,
+            tick_size: Decimal::ZERO,
+            min_notional: Decimal::ZERO,
+            instrument_info: None,
+        };
+
+        for filter in info.filters {
+            match filter {
+                BinanceFilter::PriceFilter { tick_size } => {
+                    market.tick_size = parse_decimal(&tick_size, "tick size")?;
+                }
+                BinanceFilter::LotSize {
+                    min_qty,
+                    max_qty,
+                    step_size,
+                } => {
+                    market.min_quantity = parse_decimal(&min_qty, "min quantity")?;
+                    market.max_quantity = parse_decimal(&max_qty, "max quantity")?;
+                    market.step_size = parse_decimal(&step_size, "step size")?;
+                }
+                BinanceFilter::MinNotional { min_notional }
+                | BinanceFilter::Notional { min_notional } => {
+                    market.min_notional = parse_decimal(&min_notional, "min notional")?;
+                }
+                BinanceFilter::Other => {}
+            }
+        }
+
+        Ok(market)
+    }
+
+    fn convert_ticker(
+        ticker: BinanceTicker24h,
+        book: Option<&BinanceBookTicker>,
+        symbol: Symbol,
+    ) -> Result<Ticker> {
+        // Futures tickers carry no book, spot tickers do
+        let (bid, bid_size, ask, ask_size) = match book {
+            Some(book) => (
+                book.bid_price.as_str(),
+                book.bid_qty.as_str(),
+                book.ask_price.as_str(),
+                book.ask_qty.as_str(),
+            ),
+            None => (
+                ticker.bid_price.as_deref().unwrap_or("0"),
+                ticker.bid_qty.as_deref().unwrap_or("0"),
+                ticker.ask_price.as_deref().unwrap_or("0"),
+                ticker.ask_qty.as_deref().unwrap_or("0"),
+            ),
+        };
+
+        Ok(Ticker {
+            symbol,
+            last_price: parse_price(&ticker.last_price, "last price")?,
+            bid: parse_price(bid, "bid price")?,
+            bid_size: parse_decimal(bid_size, "bid size")?,
+            ask: parse_price(ask, "ask price")?,
+            ask_size: parse_decimal(ask_size, "ask size")?,
+            volume_24h: parse_decimal(&ticker.volume, "volume")?,
+            high_24h: parse_price(&ticker.high_price, "high price")?,
+            low_24h: parse_price(&ticker.low_price, "low price")?,
+            price_change_24h: parse_decimal(&ticker.price_change, "price change")?,
+            price_change_percent_24h: parse_decimal(
+                &ticker.price_change_percent,
+                "price change percent",
+            )?,
+            timestamp: millis_to_datetime(ticker.close_time),
+        })
+    }
+
+    fn convert_trade(trade: BinanceTrade, symbol: Symbol) -> Result<Trade> {
+        Ok(Trade {
+            trade_id: trade.id.to_string(),
+            symbol,
+            price: parse_price(&trade.price, "trade price")?,
+            quantity: parse_decimal(&trade.qty, "trade quantity")?,
+            side: if trade.is_buyer_maker {
+                Side::Sell // Taker sold into the bid
+            } else {
+                Side::Buy
+            },
+            timestamp: millis_to_datetime(trade.time),
+            buyer_maker: Some(trade.is_buyer_maker),
+        })
+    }
+
+    fn convert_kline(kline: BinanceKline, symbol: Symbol, interval: Interval) -> Result<Candle> {
+        Ok(Candle {
+            symbol,
+            interval,
+            open: parse_price(&kline.1, "open")?,
+            high: parse_price(&kline.2, "high")?,
+            low: parse_price(&kline.3, "low")?,
+            close: parse_price(&kline.4, "close")?,
+            volume: parse_decimal(&kline.5, "volume")?,
+            open_time: millis_to_datetime(kline.0),
+            close_time: millis_to_datetime(kline.6),
+            trade_count: Some(kline.8),
+            quote_volume: Some(parse_decimal(&kline.7, "quote volume")?),
+        })
+    }
+}
+
+#[async_trait]
+impl MarketData for BinanceMarketData {
+    async fn get_markets(&self) -> Result<Vec<Market>> {
+        let product = self.rest.product();
+        let info: BinanceExchangeInfo = self
+            .rest
+            .public(
+                self.rest.routes().exchange_info,
+                Vec::new(),
+                self.rest.weight(20, 1),
+            )
+            .await?;
+
+        info.symbols
+            .into_iter()
+            .map(|s| Self::convert_market(product, s))
+            .collect()
+    }
+
+    async fn get_market(&self, symbol: &Symbol) -> Result<Market> {
+        let product = self.rest.product();
+        let native = binance_symbol(symbol);
+        let params: Params = match product {
+            BinanceProduct::Spot => vec![("symbol", native.clone())],
+            // USDⓈ-M cannot filter exchangeInfo by symbol
+            BinanceProduct::UsdmFutures => Vec::new(),
+        };
+        let info: BinanceExchangeInfo = self
+            .rest
+            .public(
+                self.rest.routes().exchange_info,
+                params,
+                self.rest.weight(20, 1),
+            )
+            .await?;
+
+        let info = info
+            .symbols
+            .into_iter()
+            .find(|s| s.symbol == native)
+            .ok_or_else(|| ExchangeError::MarketNotFound(symbol.to_string()))?;
+        Self::convert_market(product, info)
+    }
+
+    async fn get_ticker(&self, symbol: &Symbol) -> Result<Ticker> {
+        let routes = self.rest.routes();
+        let params: Params = vec![("symbol", binance_symbol(symbol))];
+        let ticker: BinanceTicker24h = self
+            .rest
+            .public(routes.ticker_24h, params.clone(), self.rest.weight(2, 1))
+            .await?;
+
+        let book = match self.rest.product() {
+            BinanceProduct::Spot => None,
+            BinanceProduct::UsdmFutures => Some(
+                self.rest
+                    .public::<BinanceBookTicker>(routes.book_ticker, params, 2)
+                    .await?,
+            ),
+        };
+
+        Self::convert_ticker(ticker, book.as_ref(), symbol.clone())
+    }
+
+    async fn get_tickers(&self) -> Result<Vec<Ticker>> {
+        let routes = self.rest.routes();
+        let tickers: Vec<BinanceTicker24h> = self
+            .rest
+            .public(routes.ticker_24h, Vec::new(), self.rest.weight(80, 40))
+            .await?;
+
+        let books: HashMap<String, BinanceBookTicker> = match self.rest.product() {
+            BinanceProduct::Spot => HashMap::new(),
+            BinanceProduct::UsdmFutures => self
+                .rest
+                .public::<Vec<BinanceBookTicker>>(routes.book_ticker, Vec::new(), 5)
+                .await?
+                .into_iter()
+                .map(|b| (b.symbol.clone(), b))
+                .collect(),
+        };
+
+        tickers
+            .into_iter()
+            .map(|t| {
+                let symbol = Symbol::from(t.symbol.as_str());
+                let book = books.get(&t.symbol);
+                Self::convert_ticker(t, book, symbol)
+            })
+            .collect()
+    }
+
+    async fn get_orderbook(&self, symbol: &Symbol, depth: Option<usize>) -> Result<OrderBook> {
+        let (limit, weight) = Self::depth_request(self.rest.product(), depth);
+        let params: Params = vec![
+            ("symbol", binance_symbol(symbol)),
+            ("limit", limit.to_string()),
+        ];
+        let snapshot: BinanceDepth = self
+            .rest
+            .public(self.rest.routes().depth, params, weight)
+            .await?;
+
+        Self::convert_depth(snapshot, symbol.clone(), depth)
+    }
+
+    async fn get_recent_trades(&self, symbol: &Symbol, limit: Option<usize>) -> Result<Vec<Trade>> {
+        let mut params: Params = vec![("symbol", binance_symbol(symbol))];
+        if let Some(limit) = limit {
+            params.push(("limit", limit.min(1000).to_string()));
+        }
+        let trades: Vec<BinanceTrade> = self
+            .rest
+            .public(self.rest.routes().trades, params, self.rest.weight(25, 5))
+            .await?;
+
+        trades
+            .into_iter()
+            .map(|t| Self::convert_trade(t, symbol.clone()))
+            .collect()
+    }
+
+    async fn get_candles(
+        &self,
+        symbol: &Symbol,
+        interval: Interval,
+        start_time: Option<DateTime<Utc>>,
+        end_time: Option<DateTime<Utc>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<Candle>> {
+        let limit = limit.unwrap_or(500).clamp(1, 1000);
+        let mut params: Params = vec![
+            ("symbol", binance_symbol(symbol)),
+            ("interval", Self::interval_to_string(interval).to_string()),
+            ("limit", limit.to_string()),
+        ];
+        if let Some(start) = start_time {
+            params.push(("startTime", start.timestamp_millis().to_string()));
+        }
+        if let Some(end) = end_time {
+            params.push(("endTime", end.timestamp_millis().to_string()));
+        }
+        let weight = match (self.rest.product(), limit) {
+            (BinanceProduct::Spot, _) => 2,
+            (BinanceProduct::UsdmFutures, 0..=99) => 1,
+            (BinanceProduct::UsdmFutures, 100..=499) => 2,
+            (BinanceProduct::UsdmFutures, _) => 5,
+        };
+
+        let klines: Vec<BinanceKline> = self
+            .rest
+            .public(self.rest.routes().klines, params, weight)
+            .await?;
+
+        klines
+            .into_iter()
+            .map(|k| Self::convert_kline(k, symbol.clone(), interval))
+            .collect()
+    }
+
+    async fn get_funding_rate(&self, symbol: &Symbol) -> Result<Option<FundingRate>> {
+        if self.rest.product() == BinanceProduct::Spot {
+            return Ok(None);
+        }
+
+        let params: Params = vec![("symbol", binance_symbol(symbol))];
+        let index: BinancePremiumIndex = self
+            .rest
+            .public(endpoints::USDM_PREMIUM_INDEX, params, 1)
+            .await?;
+
+        Ok(Some(FundingRate {
+            symbol: symbol.clone(),
+            rate: parse_decimal(&index.last_funding_rate, "funding rate")?,
+            next_funding_time: millis_to_datetime(index.next_funding_time),
+            timestamp: millis_to_datetime(index.time),
+        }))
+    }
+
+    async fn get_funding_rate_history(
+        &self,
+        symbol: &Symbol,
+        start_time: Option<DateTime<Utc>>,
+        end_time: Option<DateTime<Utc>>,
+        limit: Option<usize>,
+    ) -> Result<Vec<FundingRate>> {
+        if self.rest.product() == BinanceProduct::Spot {
+            return Ok(Vec::new());
+        }
+
+        let mut params: Params = vec![("symbol", binance_symbol(symbol))];
+        if let Some(start) = start_time {
+            params.push(("startTime", start.timestamp_millis().to_string()));
+        }
+        if let Some(end) = end_time {
+            params.push(("endTime", end.timestamp_millis().to_string()));
+        }
+        if let Some(limit) = limit {
+            params.push(("limit", limit.min(1000).to_string()));
+        }
+
+        let history: Vec<BinanceFundingRate> = self
+            .rest
+            .public(endpoints::USDM_FUNDING_RATE, params, 1)
+            .await?;
+
+        // USDⓈ-M funds every 8 hours
+        history
+            .into_iter()
+            .map(|f| {
+                let timestamp = millis_to_datetime(f.funding_time);
+                Ok(FundingRate {
+                    symbol: symbol.clone(),
+                    rate: parse_decimal(&f.funding_rate, "funding rate")?,
+                    next_funding_time: timestamp + chrono::Duration::hours(8),
+                    timestamp,
+                })
+            })
+            .collect()
+    }
+}
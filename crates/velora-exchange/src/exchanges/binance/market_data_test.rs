@@ -0,0 +1,449 @@
+//! Tests for Binance market data implementation
+
+#[cfg(test)]
+mod tests {
+    use super::super::market_data::*;
+    use super::super::rest::BinanceRest;
+    use super::super::BinanceProduct;
+
+    use crate::common::{RateLimiter, RestClient};
+    use crate::traits::MarketData;
+    use crate::types::{InstrumentType, Interval, MarketStatus, Price, Side, Symbol};
+
+    use chrono::DateTime;
+    use mockito::{Matcher, Server};
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+
+    /// Helper to create a test market data instance with mocked server
+    fn setup_test(server: &Server, product: BinanceProduct) -> BinanceMarketData {
+        let rest_client =
+            Arc::new(RestClient::new(server.url(), std::time::Duration::from_secs(30)).unwrap());
+        let rate_limiter = Arc::new(RateLimiter::new(1000, std::time::Duration::from_secs(1)));
+        let rest = BinanceRest::new(
+            rest_client,
+            Arc::clone(&rate_limiter),
+            rate_limiter,
+            None,
+            product,
+        );
+
+        BinanceMarketData::new(Arc::new(rest))
+    }
+
+    #[tokio::test]
+    async fn test_get_markets_spot() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/api/v3/exchangeInfo")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "timezone": "UTC",
+                    "symbols": [
+                        {
+                            "symbol": "BTCUSDT",
+                            "status": "TRADING",
+                            "baseAsset": "BTC",
+                            "quoteAsset": "USDT",
+                            "filters": [
+                                {"filterType": "PRICE_FILTER", "minPrice": "0.01", "maxPrice": "1000000.00", "tickSize": "0.01"},
+                                {"filterType": "LOT_SIZE", "minQty": "0.00001", "maxQty": "9000.00000", "stepSize": "0.00001"},
+                                {"filterType": "ICEBERG_PARTS", "limit": 10},
+                                {"filterType": "NOTIONAL", "minNotional": "5.00", "applyMinToMarket": true, "maxNotional": "9000000.00"}
+                            ]
+                        },
+                        {
+                            "symbol": "LUNAUSDT",
+                            "status": "BREAK",
+                            "baseAsset": "LUNA",
+                            "quoteAsset": "USDT",
+                            "filters": []
+                        }
+                    ]
+                }"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let market_data = setup_test(&server, BinanceProduct::Spot);
+        let markets = market_data.get_markets().await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(markets.len(), 2);
+
+        let btc = &markets[0];
+        assert_eq!(btc.symbol.as_str(), "BTCUSDT");
+        assert_eq!(btc.base_asset, "BTC");
+        assert_eq!(btc.instrument_type, InstrumentType::Spot);
+        assert_eq!(btc.status, MarketStatus::Trading);
+        assert_eq!(btc.tick_size, dec!(0.01));
+        assert_eq!(btc.min_quantity, dec!(0.00001));
+        assert_eq!(btc.step_size, dec!(0.00001));
+        assert_eq!(btc.min_notional, dec!(5.00));
+
+        assert_eq!(markets[1].status, MarketStatus::Break);
+    }
+
+    #[tokio::test]
+    async fn test_get_market_futures_contract_types() {
+        let mut server = Server::new_async().await;
+
+        let body = r#"{
+            "symbols": [
+                {
+                    "symbol": "BTCUSDT",
+                    "status": "TRADING",
+                    "baseAsset": "BTC",
+                    "quoteAsset": "USDT",
+                    "contractType": "PERPETUAL",
+                    "deliveryDate": 4133404800000,
+                    "filters": [
+                        {"filterType": "PRICE_FILTER", "tickSize": "0.10", "minPrice": "556.80", "maxPrice": "4529764"},
+                        {"filterType": "LOT_SIZE", "minQty": "0.001", "maxQty": "1000", "stepSize": "0.001"},
+                        {"filterType": "MIN_NOTIONAL", "notional": "100"}
+                    ]
+                },
+                {
+                    "symbol": "BTCUSDT_250627",
+                    "status": "TRADING",
+                    "baseAsset": "BTC",
+                    "quoteAsset": "USDT",
+                    "contractType": "CURRENT_QUARTER",
+                    "deliveryDate": 1751011200000,
+                    "filters": []
+                }
+            ]
+        }"#;
+        let mock = server
+            .mock("GET", "/fapi/v1/exchangeInfo")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let market_data = setup_test(&server, BinanceProduct::UsdmFutures);
+
+        let perp = market_data
+            .get_market(&Symbol::from("BTC/USDT"))
+            .await
+            .unwrap();
+        assert_eq!(perp.instrument_type, InstrumentType::Perpetual);
+        assert_eq!(perp.tick_size, dec!(0.10));
+        assert_eq!(perp.min_notional, dec!(100));
+
+        let quarterly = market_data
+            .get_market(&Symbol::from("BTCUSDT_250627"))
+            .await
+            .unwrap();
+        assert_eq!(quarterly.instrument_type, InstrumentType::Futures);
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_market_not_found() {
+        let mut server = Server::new_async().await;
+
+        let _mock = server
+            .mock("GET", "/api/v3/exchangeInfo")
+            .match_query(Matcher::UrlEncoded("symbol".into(), "DOGEBTC".into()))
+            .with_status(400)
+            .with_body(r#"{"code":-1121,"msg":"Invalid symbol."}"#)
+            .create_async()
+            .await;
+
+        let market_data = setup_test(&server, BinanceProduct::Spot);
+        let result = market_data.get_market(&Symbol::from("DOGE/BTC")).await;
+
+        assert!(matches!(
+            result,
+            Err(crate::types::ExchangeError::MarketNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_ticker_futures_uses_book_ticker() {
+        let mut server = Server::new_async().await;
+
+        let ticker_mock = server
+            .mock("GET", "/fapi/v1/ticker/24hr")
+            .match_query(Matcher::UrlEncoded("symbol".into(), "ETHUSDT".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "symbol": "ETHUSDT",
+                    "priceChange": "-94.99999800",
+                    "priceChangePercent": "-2.850",
+                    "weightedAvgPrice": "3300.0",
+                    "lastPrice": "3240.50",
+                    "lastQty": "1.0",
+                    "openPrice": "3335.50",
+                    "highPrice": "3350.00",
+                    "lowPrice": "3200.00",
+                    "volume": "125000.123",
+                    "quoteVolume": "412500000.0",
+                    "openTime": 1700000000000,
+                    "closeTime": 1700086400000,
+                    "firstId": 1,
+                    "lastId": 2,
+                    "count": 2
+                }"#,
+            )
+            .create_async()
+            .await;
+        let book_mock = server
+            .mock("GET", "/fapi/v1/ticker/bookTicker")
+            .match_query(Matcher::UrlEncoded("symbol".into(), "ETHUSDT".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "symbol": "ETHUSDT",
+                    "bidPrice": "3240.40",
+                    "bidQty": "12.5",
+                    "askPrice": "3240.60",
+                    "askQty": "8.25",
+                    "time": 1700086400000
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let market_data = setup_test(&server, BinanceProduct::UsdmFutures);
+        let symbol = Symbol::from("ETH/USDT");
+        let ticker = market_data.get_ticker(&symbol).await.unwrap();
+
+        ticker_mock.assert_async().await;
+        book_mock.assert_async().await;
+        assert_eq!(ticker.symbol, symbol);
+        assert_eq!(ticker.last_price, Price::from(3240.50));
+        assert_eq!(ticker.bid, Price::from(3240.40));
+        assert_eq!(ticker.bid_size, dec!(12.5));
+        assert_eq!(ticker.ask, Price::from(3240.60));
+        assert_eq!(ticker.ask_size, dec!(8.25));
+        assert_eq!(ticker.volume_24h, dec!(125000.123));
+        assert_eq!(ticker.price_change_percent_24h, dec!(-2.850));
+        assert_eq!(
+            ticker.timestamp,
+            DateTime::from_timestamp_millis(1700086400000).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_orderbook_rounds_futures_depth() {
+        let mut server = Server::new_async().await;
+
+        // 15 levels are not a valid futures limit; 20 is requested instead
+        let mock = server
+            .mock("GET", "/fapi/v1/depth")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()),
+                Matcher::UrlEncoded("limit".into(), "20".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "lastUpdateId": 1027024,
+                    "E": 1589436922972,
+                    "T": 1589436922959,
+                    "bids": [["4.00000000", "431.00000000"], ["3.99000000", "9.00000000"]],
+                    "asks": [["4.00000200", "12.00000000"], ["4.01000000", "18.00000000"]]
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let market_data = setup_test(&server, BinanceProduct::UsdmFutures);
+        let book = market_data
+            .get_orderbook(&Symbol::from("BTCUSDT"), Some(15))
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(book.last_update_id, Some(1027024));
+        assert_eq!(book.bids.len(), 2);
+        assert_eq!(book.bids[0].price, Price::from(4.0));
+        assert_eq!(book.bids[0].quantity, dec!(431));
+        assert_eq!(book.asks[1].price, Price::from(4.01));
+    }
+
+    #[test]
+    fn test_depth_request_weights() {
+        assert_eq!(
+            BinanceMarketData::depth_request(BinanceProduct::Spot, None),
+            (100, 5)
+        );
+        assert_eq!(
+            BinanceMarketData::depth_request(BinanceProduct::Spot, Some(5000)),
+            (5000, 250)
+        );
+        assert_eq!(
+            BinanceMarketData::depth_request(BinanceProduct::UsdmFutures, Some(1)),
+            (5, 2)
+        );
+        assert_eq!(
+            BinanceMarketData::depth_request(BinanceProduct::UsdmFutures, Some(501)),
+            (1000, 20)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_recent_trades() {
+        let mut server = Server::new_async().await;
+
+        let _mock = server
+            .mock("GET", "/api/v3/trades")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()),
+                Matcher::UrlEncoded("limit".into(), "2".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                    {"id": 28457, "price": "4.00000100", "qty": "12.00000000", "quoteQty": "48.000012", "time": 1499865549590, "isBuyerMaker": true, "isBestMatch": true},
+                    {"id": 28458, "price": "4.00000200", "qty": "1.50000000", "quoteQty": "6.000003", "time": 1499865549591, "isBuyerMaker": false, "isBestMatch": true}
+                ]"#,
+            )
+            .create_async()
+            .await;
+
+        let market_data = setup_test(&server, BinanceProduct::Spot);
+        let trades = market_data
+            .get_recent_trades(&Symbol::from("BTCUSDT"), Some(2))
+            .await
+            .unwrap();
+
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].trade_id, "28457");
+        assert_eq!(trades[0].side, Side::Sell);
+        assert_eq!(trades[0].buyer_maker, Some(true));
+        assert_eq!(trades[1].side, Side::Buy);
+        assert_eq!(trades[1].quantity, dec!(1.5));
+    }
+
+    #[tokio::test]
+    async fn test_get_candles() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("GET", "/api/v3/klines")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()),
+                Matcher::UrlEncoded("interval".into(), "1h".into()),
+                Matcher::UrlEncoded("limit".into(), "500".into()),
+                Matcher::UrlEncoded("startTime".into(), "1499040000000".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                    [1499040000000, "0.01634790", "0.80000000", "0.01575800", "0.01577100", "148976.11427815",
+                     1499043599999, "2434.19055334", 308, "1756.87402397", "28.46694368", "0"]
+                ]"#,
+            )
+            .create_async()
+            .await;
+
+        let market_data = setup_test(&server, BinanceProduct::Spot);
+        let candles = market_data
+            .get_candles(
+                &Symbol::from("BTCUSDT"),
+                Interval::Hour1,
+                DateTime::from_timestamp_millis(1499040000000),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(candles.len(), 1);
+        let candle = &candles[0];
+        assert_eq!(candle.open, Price::from(0.0163479));
+        assert_eq!(candle.high, Price::from(0.8));
+        assert_eq!(candle.volume, dec!(148976.11427815));
+        assert_eq!(candle.trade_count, Some(308));
+        assert_eq!(candle.quote_volume, Some(dec!(2434.19055334)));
+        assert_eq!(
+            candle.close_time,
+            DateTime::from_timestamp_millis(1499043599999).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_funding_rate_spot_and_futures() {
+        let mut server = Server::new_async().await;
+
+        let spot = setup_test(&server, BinanceProduct::Spot);
+        assert!(spot
+            .get_funding_rate(&Symbol::from("BTCUSDT"))
+            .await
+            .unwrap()
+            .is_none());
+
+        let _premium = server
+            .mock("GET", "/fapi/v1/premiumIndex")
+            .match_query(Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "symbol": "BTCUSDT",
+                    "markPrice": "11793.63104562",
+                    "indexPrice": "11781.80495970",
+                    "lastFundingRate": "0.00038246",
+                    "interestRate": "0.00010000",
+                    "nextFundingTime": 1597392000000,
+                    "time": 1597370495002
+                }"#,
+            )
+            .create_async()
+            .await;
+        let _history = server
+            .mock("GET", "/fapi/v1/fundingRate")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()),
+                Matcher::UrlEncoded("limit".into(), "2".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                    {"symbol": "BTCUSDT", "fundingRate": "-0.03750000", "fundingTime": 1570608000000, "markPrice": "34287.54619963"},
+                    {"symbol": "BTCUSDT", "fundingRate": "0.00010000", "fundingTime": 1570636800000, "markPrice": "34287.54619963"}
+                ]"#,
+            )
+            .create_async()
+            .await;
+
+        let futures = setup_test(&server, BinanceProduct::UsdmFutures);
+        let symbol = Symbol::from("BTCUSDT");
+
+        let rate = futures.get_funding_rate(&symbol).await.unwrap().unwrap();
+        assert_eq!(rate.rate, dec!(0.00038246));
+        assert_eq!(
+            rate.next_funding_time,
+            DateTime::from_timestamp_millis(1597392000000).unwrap()
+        );
+
+        let history = futures
+            .get_funding_rate_history(&symbol, None, None, Some(2))
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].rate, dec!(-0.0375));
+        assert_eq!(
+            history[0].next_funding_time,
+            DateTime::from_timestamp_millis(1570636800000).unwrap()
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! Binance Exchange implementation (CEX)
+//!
+//! A client serves one Binance product line:
+//! - Spot
+//! - USDⓈ-M futures (perpetual and delivery contracts)
+//!
+//! Requests are signed with HMAC-SHA256 API keys and throttled against
+//! Binance's request-weight and order-count limits.
+
+mod account;
+#[cfg(test)]
+mod account_test;
+mod client;
+mod market_data;
+#[cfg(test)]
+mod market_data_test;
+mod rest;
+mod streaming;
+#[cfg(test)]
+mod streaming_test;
+mod trading;
+#[cfg(test)]
+mod trading_test;
+mod types;
+
+pub use client::BinanceExchange;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+use crate::types::{ExchangeError, InstrumentType, Price, Result, Symbol};
+
+/// Binance product line served by a client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinanceProduct {
+    /// Spot market (`api.binance.com`)
+    Spot,
+    /// USDⓈ-margined futures (`fapi.binance.com`)
+    UsdmFutures,
+}
+
+impl BinanceProduct {
+    /// REST and WebSocket base URLs
+    pub(crate) fn base_urls(&self, testnet: bool) -> (&'static str, &'static str) {
+        match (self, testnet) {
+            (Self::Spot, false) => (endpoints::SPOT_REST_URL, endpoints::SPOT_WS_URL),
+            (Self::Spot, true) => (
+                endpoints::SPOT_TESTNET_REST_URL,
+                endpoints::SPOT_TESTNET_WS_URL,
+            ),
+            (Self::UsdmFutures, false) => (endpoints::USDM_REST_URL, endpoints::USDM_WS_URL),
+            (Self::UsdmFutures, true) => (
+                endpoints::USDM_TESTNET_REST_URL,
+                endpoints::USDM_TESTNET_WS_URL,
+            ),
+        }
+    }
+
+    /// REST paths for this product
+    pub(crate) fn routes(&self) -> &'static endpoints::Routes {
+        match self {
+            Self::Spot => &endpoints::SPOT,
+            Self::UsdmFutures => &endpoints::USDM,
+        }
+    }
+}
+
+/// Binance API endpoints
+pub(crate) mod endpoints {
+    // Spot
+    pub const SPOT_REST_URL: &str = "https://api.binance.com";
+    pub const SPOT_WS_URL: &str = "wss://stream.binance.com:9443";
+    pub const SPOT_TESTNET_REST_URL: &str = "https://testnet.binance.vision";
+    pub const SPOT_TESTNET_WS_URL: &str = "wss://stream.testnet.binance.vision";
+
+    // USDⓈ-M futures
+    pub const USDM_REST_URL: &str = "https://fapi.binance.com";
+    pub const USDM_WS_URL: &str = "wss://fstream.binance.com";
+    pub const USDM_TESTNET_REST_URL: &str = "https://testnet.binancefuture.com";
+    pub const USDM_TESTNET_WS_URL: &str = "wss://stream.binancefuture.com";
+
+    /// REST paths that differ between product lines
+    pub struct Routes {
+        pub exchange_info: &'static str,
+        pub ticker_24h: &'static str,
+        pub book_ticker: &'static str,
+        pub depth: &'static str,
+        pub trades: &'static str,
+        pub klines: &'static str,
+        pub order: &'static str,
+        pub open_orders: &'static str,
+        /// DELETE cancels every open order of a symbol
+        pub cancel_all: &'static str,
+        pub all_orders: &'static str,
+        pub account: &'static str,
+        pub my_trades: &'static str,
+        pub listen_key: &'static str,
+    }
+
+    pub const SPOT: Routes = Routes {
+        exchange_info: "/api/v3/exchangeInfo",
+        ticker_24h: "/api/v3/ticker/24hr",
+        book_ticker: "/api/v3/ticker/bookTicker",
+        depth: "/api/v3/depth",
+        trades: "/api/v3/trades",
+        klines: "/api/v3/klines",
+        order: "/api/v3/order",
+        open_orders: "/api/v3/openOrders",
+        cancel_all: "/api/v3/openOrders",
+        all_orders: "/api/v3/allOrders",
+        account: "/api/v3/account",
+        my_trades: "/api/v3/myTrades",
+        listen_key: "/api/v3/userDataStream",
+    };
+
+    pub const USDM: Routes = Routes {
+        exchange_info: "/fapi/v1/exchangeInfo",
+        ticker_24h: "/fapi/v1/ticker/24hr",
+        book_ticker: "/fapi/v1/ticker/bookTicker",
+        depth: "/fapi/v1/depth",
+        trades: "/fapi/v1/trades",
+        klines: "/fapi/v1/klines",
+        order: "/fapi/v1/order",
+        open_orders: "/fapi/v1/openOrders",
+        cancel_all: "/fapi/v1/allOpenOrders",
+        all_orders: "/fapi/v1/allOrders",
+        account: "/fapi/v2/account",
+        my_trades: "/fapi/v1/userTrades",
+        listen_key: "/fapi/v1/listenKey",
+    };
+
+    // Spot only
+    pub const SPOT_CANCEL_REPLACE: &str = "/api/v3/order/cancelReplace";
+
+    // USDⓈ-M only
+    pub const USDM_PREMIUM_INDEX: &str = "/fapi/v1/premiumIndex";
+    pub const USDM_FUNDING_RATE: &str = "/fapi/v1/fundingRate";
+    pub const USDM_POSITION_RISK: &str = "/fapi/v2/positionRisk";
+}
+
+/// Supported instruments on Binance
+pub(crate) const SUPPORTED_INSTRUMENTS: &[InstrumentType] = &[
+    InstrumentType::Spot,
+    InstrumentType::Perpetual,
+    InstrumentType::Futures,
+];
+
+/// Binance symbol for a velora symbol (`BTC/USDT` and `btc-usdt` become `BTCUSDT`)
+///
+/// Underscores are kept for delivery contracts such as `BTCUSDT_250627`.
+pub(crate) fn binance_symbol(symbol: &Symbol) -> String {
+    symbol
+        .as_str()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Parse a decimal string from a Binance response
+pub(crate) fn parse_decimal(s: &str, what: &str) -> Result<Decimal> {
+    Decimal::from_str(s)
+        .map_err(|e| ExchangeError::ParseError(format!("Failed to parse {what} '{s}': {e}")))
+}
+
+/// Parse a price string from a Binance response
+pub(crate) fn parse_price(s: &str, what: &str) -> Result<Price> {
+    Ok(Price::from(parse_decimal(s, what)?.to_f64().unwrap_or(0.0)))
+}
+
+/// Binance millisecond timestamp as a UTC time
+pub(crate) fn millis_to_datetime(millis: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(millis).unwrap_or_else(Utc::now)
+}
+
+/// Exact decimal form of a price for a request (3012.345 stays 3012.345)
+pub(crate) fn format_price(price: Price) -> Result<String> {
+    Decimal::from_str(&price.to_string())
+        .map(|d| d.normalize().to_string())
+        .map_err(|e| ExchangeError::InvalidOrder(format!("Invalid price {price}: {e}")))
+}
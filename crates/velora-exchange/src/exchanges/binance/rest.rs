@@ -0,0 +1,280 @@
+//! Weighted, optionally signed REST access shared by the Binance components
+
+use chrono::Utc;
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Method,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::sync::Arc;
+
+use crate::{
+    auth::ApiKeyAuth,
+    common::{RateLimiter, RestClient},
+    types::{ExchangeError, Result},
+};
+
+use super::{endpoints::Routes, BinanceProduct};
+
+/// Query parameters of a request, in order
+pub(crate) type Params = Vec<(&'static str, String)>;
+
+/// `recvWindow` sent with signed requests
+const RECV_WINDOW_MS: u64 = 5_000;
+
+/// How a request is authenticated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Security {
+    /// Public endpoint
+    None,
+    /// API key header only (user data stream management)
+    ApiKey,
+    /// API key header plus HMAC-SHA256 signature of the query
+    Signed,
+}
+
+/// Error body returned by Binance
+#[derive(Debug, Deserialize)]
+struct BinanceErrorBody {
+    code: i32,
+    msg: String,
+}
+
+/// REST access for one Binance product line
+///
+/// Every request waits for its endpoint weight on the shared weight limiter;
+/// order placement additionally counts against the order limiter.
+pub(crate) struct BinanceRest {
+    rest_client: Arc<RestClient>,
+    rate_limiter: Arc<RateLimiter>,
+    order_limiter: Arc<RateLimiter>,
+    auth: Option<ApiKeyAuth>,
+    product: BinanceProduct,
+}
+
+impl BinanceRest {
+    pub fn new(
+        rest_client: Arc<RestClient>,
+        rate_limiter: Arc<RateLimiter>,
+        order_limiter: Arc<RateLimiter>,
+        auth: Option<ApiKeyAuth>,
+        product: BinanceProduct,
+    ) -> Self {
+        Self {
+            rest_client,
+            rate_limiter,
+            order_limiter,
+            auth,
+            product,
+        }
+    }
+
+    /// Product line this client talks to
+    pub fn product(&self) -> BinanceProduct {
+        self.product
+    }
+
+    /// REST paths of the product line
+    pub fn routes(&self) -> &'static Routes {
+        self.product.routes()
+    }
+
+    /// Weight of an endpoint that exists on both product lines
+    pub fn weight(&self, spot: u32, usdm: u32) -> u32 {
+        match self.product {
+            BinanceProduct::Spot => spot,
+            BinanceProduct::UsdmFutures => usdm,
+        }
+    }
+
+    /// Unauthenticated GET
+    pub async fn public<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        params: Params,
+        weight: u32,
+    ) -> Result<T> {
+        self.send(Method::GET, endpoint, params, Security::None, weight)
+            .await
+    }
+
+    /// Signed request (trading and account endpoints)
+    pub async fn signed<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        params: Params,
+        weight: u32,
+    ) -> Result<T> {
+        self.send(method, endpoint, params, Security::Signed, weight)
+            .await
+    }
+
+    /// Signed request that places or amends an order
+    pub async fn order<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        params: Params,
+        weight: u32,
+    ) -> Result<T> {
+        self.order_limiter.wait().await;
+        self.signed(method, endpoint, params, weight).await
+    }
+
+    /// Request authenticated by API key only (listen key management)
+    pub async fn keyed<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        params: Params,
+        weight: u32,
+    ) -> Result<T> {
+        self.send(method, endpoint, params, Security::ApiKey, weight)
+            .await
+    }
+
+    /// API credentials, or an error in read-only mode
+    fn auth(&self) -> Result<&ApiKeyAuth> {
+        self.auth.as_ref().ok_or_else(|| {
+            ExchangeError::Authentication(
+                "Binance private endpoints require an API key".to_string(),
+            )
+        })
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        params: Params,
+        security: Security,
+        weight: u32,
+    ) -> Result<T> {
+        // Wait before stamping the request so it cannot expire in the queue
+        self.rate_limiter.wait_weight(weight).await;
+
+        let mut headers = HeaderMap::new();
+        if security != Security::None {
+            let auth = self.auth()?;
+            let api_key = HeaderValue::from_str(auth.api_key())
+                .map_err(|e| ExchangeError::Authentication(format!("Invalid API key: {e}")))?;
+            headers.insert("x-mbx-apikey", api_key);
+        }
+
+        let mut query = encode_query(&params, security == Security::Signed);
+        if security == Security::Signed {
+            let signature = self.auth()?.sign_query(&query);
+            query.push_str(&format!("&signature={signature}"));
+        }
+
+        let endpoint = if query.is_empty() {
+            endpoint.to_string()
+        } else {
+            query.insert(0, '?');
+            format!("{endpoint}{query}")
+        };
+
+        let empty: Option<&()> = None;
+        self.rest_client
+            .request(method, &endpoint, empty, headers)
+            .await
+            .map_err(map_error)
+    }
+}
+
+/// URL-encode the parameters, stamping them when the request is signed
+fn encode_query(params: &Params, signed: bool) -> String {
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    for (key, value) in params {
+        query.append_pair(key, value);
+    }
+    if signed {
+        query
+            .append_pair("recvWindow", &RECV_WINDOW_MS.to_string())
+            .append_pair("timestamp", &Utc::now().timestamp_millis().to_string());
+    }
+    query.finish()
+}
+
+/// Refine a failed request using the Binance error code in the body
+fn map_error(error: ExchangeError) -> ExchangeError {
+    let body = match &error {
+        ExchangeError::InvalidRequest(body) => body,
+        // 418 means the IP was banned for ignoring 429s
+        ExchangeError::ApiError { code: 418, message } => {
+            return ExchangeError::RateLimit(format!("IP banned by Binance: {message}"));
+        }
+        ExchangeError::ApiError { message, .. } => message,
+        _ => return error,
+    };
+    let Ok(body) = serde_json::from_str::<BinanceErrorBody>(body) else {
+        return error;
+    };
+
+    match body.code {
+        -1003 | -1015 => ExchangeError::RateLimit(body.msg),
+        -1022 | -2014 | -2015 => ExchangeError::Authentication(body.msg),
+        -1121 => ExchangeError::MarketNotFound(body.msg),
+        -2011 | -2013 => ExchangeError::OrderNotFound(body.msg),
+        -2019 => ExchangeError::InsufficientBalance(body.msg),
+        -2010 if body.msg.to_lowercase().contains("insufficient balance") => {
+            ExchangeError::InsufficientBalance(body.msg)
+        }
+        -2010 | -1013 | -1111 | -1116 | -1117 | -4003 | -4164 => {
+            ExchangeError::InvalidOrder(body.msg)
+        }
+        code => ExchangeError::ApiError {
+            code,
+            message: body.msg,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_error_codes() {
+        let body = |code: i32, msg: &str| {
+            ExchangeError::InvalidRequest(format!(r#"{{"code":{code},"msg":"{msg}"}}"#))
+        };
+
+        assert!(matches!(
+            map_error(body(-2013, "Order does not exist.")),
+            ExchangeError::OrderNotFound(_)
+        ));
+        assert!(matches!(
+            map_error(body(
+                -2010,
+                "Account has insufficient balance for requested action."
+            )),
+            ExchangeError::InsufficientBalance(_)
+        ));
+        assert!(matches!(
+            map_error(body(-1013, "Filter failure: LOT_SIZE")),
+            ExchangeError::InvalidOrder(_)
+        ));
+        assert!(matches!(
+            map_error(body(-1121, "Invalid symbol.")),
+            ExchangeError::MarketNotFound(_)
+        ));
+        assert!(matches!(
+            map_error(body(-1021, "Timestamp outside recvWindow.")),
+            ExchangeError::ApiError { code: -1021, .. }
+        ));
+        assert!(matches!(
+            map_error(ExchangeError::ApiError {
+                code: 418,
+                message: "banned".to_string()
+            }),
+            ExchangeError::RateLimit(_)
+        ));
+        // Bodies that are not Binance errors pass through unchanged
+        assert!(matches!(
+            map_error(ExchangeError::InvalidRequest("Bad Request".to_string())),
+            ExchangeError::InvalidRequest(_)
+        ));
+    }
+}
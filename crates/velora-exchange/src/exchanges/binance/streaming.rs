@@ -0,0 +1,819 @@
+//! Binance streaming implementation
+//!
+//! Every subscription opens its own connection to the raw stream endpoint, so
+//! concurrent streams never compete for frames. Order books are kept in sync
+//! with the REST snapshot; account streams use a listen key that is kept alive
+//! for as long as the stream lives.
+
+use async_trait::async_trait;
+use futures::stream::{Stream, StreamExt};
+use reqwest::Method;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::{sync::RwLock, task::JoinHandle};
+use tracing::{debug, info, warn};
+
+use crate::{
+    common::WebSocketClient,
+    traits::Streaming,
+    types::{
+        BalanceUpdate, Candle, ExchangeError, Interval, MarginType, OrderBookUpdate, OrderUpdate,
+        Position, PositionUpdate, Result, Side, StreamTrade, Symbol, Ticker, UserDataEvent,
+    },
+};
+
+use super::{
+    account::{convert_margin_type, position_side, BinanceAccount},
+    binance_symbol,
+    market_data::BinanceMarketData,
+    millis_to_datetime, parse_decimal, parse_price,
+    rest::{BinanceRest, Params},
+    trading::convert_order,
+    types::*,
+    BinanceProduct,
+};
+
+/// Boxed stream returned by every subscription
+type BoxStream<T> = Box<dyn Stream<Item = Result<T>> + Send + Unpin>;
+
+/// Listen keys expire after 60 minutes without a keepalive
+const LISTEN_KEY_KEEPALIVE: Duration = Duration::from_secs(30 * 60);
+
+/// Binance streaming component
+pub struct BinanceStreaming {
+    ws_client: Arc<RwLock<WebSocketClient>>,
+    rest: Arc<BinanceRest>,
+}
+
+impl BinanceStreaming {
+    pub(crate) fn new(ws_client: Arc<RwLock<WebSocketClient>>, rest: Arc<BinanceRest>) -> Self {
+        Self { ws_client, rest }
+    }
+
+    /// Open a dedicated connection subscribed to `streams`
+    async fn connect(&self, streams: Vec<String>) -> Result<WebSocketClient> {
+        let url = self.ws_client.read().await.url().to_string();
+        let mut ws = WebSocketClient::new(url);
+        ws.connect().await?;
+
+        debug!("Sending Binance subscription: {:?}", streams);
+        ws.send_json(&BinanceWsSubscribe {
+            method: "SUBSCRIBE".to_string(),
+            params: streams,
+            id: 1,
+        })
+        .await?;
+
+        Ok(ws)
+    }
+
+    /// Open a connection to the user data stream
+    ///
+    /// The returned guard keeps the listen key alive until it is dropped.
+    async fn connect_user_data(&self) -> Result<(WebSocketClient, ListenKeyGuard)> {
+        let routes = self.rest.routes();
+        let key: BinanceListenKey = self
+            .rest
+            .keyed(
+                Method::POST,
+                routes.listen_key,
+                Vec::new(),
+                self.rest.weight(2, 1),
+            )
+            .await?;
+
+        let url = self.ws_client.read().await.url().to_string();
+        let mut ws = WebSocketClient::new(format!("{url}/{}", key.listen_key));
+        ws.connect().await?;
+
+        let rest = Arc::clone(&self.rest);
+        let listen_key = key.listen_key;
+        let keepalive = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(LISTEN_KEY_KEEPALIVE);
+            interval.tick().await; // The first tick completes immediately
+            loop {
+                interval.tick().await;
+                let params: Params = match rest.product() {
+                    BinanceProduct::Spot => vec![("listenKey", listen_key.clone())],
+                    BinanceProduct::UsdmFutures => Vec::new(),
+                };
+                if let Err(e) = rest
+                    .keyed::<serde_json::Value>(
+                        Method::PUT,
+                        rest.routes().listen_key,
+                        params,
+                        rest.weight(2, 1),
+                    )
+                    .await
+                {
+                    warn!("Failed to keep Binance listen key alive: {}", e);
+                }
+            }
+        });
+
+        Ok((ws, ListenKeyGuard(keepalive)))
+    }
+
+    /// User data events of this account
+    async fn user_data(&self) -> Result<BoxStream<UserDataEvent>> {
+        let (ws, guard) = self.connect_user_data().await?;
+
+        // Position events carry no leverage; start from the current settings
+        let mut leverage = HashMap::new();
+        if self.rest.product() == BinanceProduct::UsdmFutures {
+            for position in BinanceAccount::position_risk(&self.rest).await? {
+                if let Ok(value) = position.leverage.parse::<u32>() {
+                    leverage.insert(position.symbol, value);
+                }
+            }
+        }
+        let mut state = UserDataState { leverage };
+
+        Ok(event_stream(ws, guard, move |event| state.apply(event)))
+    }
+}
+
+/// Stops the listen key keepalive when the stream is dropped
+struct ListenKeyGuard(JoinHandle<()>);
+
+impl Drop for ListenKeyGuard {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Parse a text frame; replies to subscription requests yield None
+fn parse_frame(text: &str) -> Result<Option<BinanceWsEvent>> {
+    let value: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| ExchangeError::ParseError(format!("Invalid Binance frame: {e}")))?;
+
+    if let Some(error) = value.get("error") {
+        return Err(ExchangeError::ApiError {
+            code: error
+                .get("code")
+                .and_then(|c| c.as_i64())
+                .unwrap_or_default() as i32,
+            message: error
+                .get("msg")
+                .and_then(|m| m.as_str())
+                .unwrap_or_default()
+                .to_string(),
+        });
+    }
+    if value.get("e").is_none() {
+        return Ok(None);
+    }
+
+    parse_payload(value).map(Some)
+}
+
+fn parse_payload<T: DeserializeOwned>(value: serde_json::Value) -> Result<T> {
+    serde_json::from_value(value)
+        .map_err(|e| ExchangeError::ParseError(format!("Invalid Binance event: {e}")))
+}
+
+/// Drive a connection, mapping every event to zero or more items
+///
+/// `guard` lives as long as the stream. The stream ends when the server closes
+/// the connection, after a transport error, or when the listen key expires.
+fn event_stream<T, G, F>(ws: WebSocketClient, guard: G, handle: F) -> BoxStream<T>
+where
+    T: Send + 'static,
+    G: Send + 'static,
+    F: FnMut(BinanceWsEvent) -> Result<Vec<T>> + Send + 'static,
+{
+    let state = (Some(ws), guard, handle, VecDeque::<Result<T>>::new());
+    let stream = futures::stream::unfold(
+        state,
+        |(mut ws, guard, mut handle, mut pending)| async move {
+            loop {
+                if let Some(item) = pending.pop_front() {
+                    return Some((item, (ws, guard, handle, pending)));
+                }
+
+                let client = ws.as_mut()?;
+                let text = match client.recv().await {
+                    Ok(Some(text)) => text,
+                    Ok(None) => {
+                        info!("Binance WebSocket closed");
+                        return None;
+                    }
+                    Err(e) => return Some((Err(e), (None, guard, handle, pending))),
+                };
+
+                match parse_frame(&text) {
+                    Ok(None) => {}
+                    Ok(Some(BinanceWsEvent::ListenKeyExpired)) => {
+                        let error =
+                            ExchangeError::Connection("Binance listen key expired".to_string());
+                        return Some((Err(error), (None, guard, handle, pending)));
+                    }
+                    Ok(Some(event)) => match handle(event) {
+                        Ok(items) => pending.extend(items.into_iter().map(Ok)),
+                        Err(e) => pending.push_back(Err(e)),
+                    },
+                    Err(e) => pending.push_back(Err(e)),
+                }
+            }
+        },
+    );
+
+    Box::new(Box::pin(stream))
+}
+
+/// Whether a diff-depth event applies to the book
+#[derive(Debug, PartialEq, Eq)]
+enum DepthStep {
+    /// Already contained in the snapshot
+    Stale,
+    /// Continues the book
+    Apply,
+    /// Updates were missed; the book must be rebuilt from a snapshot
+    Gap,
+}
+
+/// Order book synchronization state of a diff-depth stream
+struct DepthSync {
+    rest: Arc<BinanceRest>,
+    symbol: Symbol,
+    depth: Option<usize>,
+    /// Final update id applied so far
+    last_update_id: u64,
+    /// True once the first event after a snapshot has been applied
+    synced: bool,
+}
+
+impl DepthSync {
+    /// Fetch a snapshot and restart synchronization from it
+    async fn snapshot(&mut self) -> Result<OrderBookUpdate> {
+        let (limit, weight) =
+            BinanceMarketData::depth_request(self.rest.product(), self.depth.or(Some(1000)));
+        let params: Params = vec![
+            ("symbol", binance_symbol(&self.symbol)),
+            ("limit", limit.to_string()),
+        ];
+        let snapshot: BinanceDepth = self
+            .rest
+            .public(self.rest.routes().depth, params, weight)
+            .await?;
+
+        self.last_update_id = snapshot.last_update_id;
+        self.synced = false;
+        let book = BinanceMarketData::convert_depth(snapshot, self.symbol.clone(), self.depth)?;
+
+        Ok(OrderBookUpdate {
+            symbol: book.symbol,
+            bids: book.bids,
+            asks: book.asks,
+            first_update_id: None,
+            final_update_id: book.last_update_id,
+            timestamp: book.timestamp,
+        })
+    }
+
+    /// Classify an event against the book
+    fn step(&self, event: &BinanceWsDepth) -> DepthStep {
+        let last = self.last_update_id;
+        if event.final_update_id <= last {
+            return DepthStep::Stale;
+        }
+
+        let continues = match (self.rest.product(), self.synced) {
+            // First event after the snapshot must straddle it
+            (BinanceProduct::Spot, false) => event.first_update_id <= last + 1,
+            (BinanceProduct::UsdmFutures, false) => event.first_update_id <= last,
+            (BinanceProduct::Spot, true) => event.first_update_id == last + 1,
+            (BinanceProduct::UsdmFutures, true) => event.prev_final_update_id == Some(last),
+        };
+        if continues {
+            DepthStep::Apply
+        } else {
+            DepthStep::Gap
+        }
+    }
+
+    fn apply(&mut self, event: BinanceWsDepth) -> Result<OrderBookUpdate> {
+        self.last_update_id = event.final_update_id;
+        self.synced = true;
+
+        Ok(OrderBookUpdate {
+            symbol: self.symbol.clone(),
+            bids: BinanceMarketData::convert_levels(&event.bids)?,
+            asks: BinanceMarketData::convert_levels(&event.asks)?,
+            first_update_id: Some(event.first_update_id),
+            final_update_id: Some(event.final_update_id),
+            timestamp: millis_to_datetime(event.event_time),
+        })
+    }
+}
+
+/// Drive a diff-depth connection, re-fetching the snapshot after a gap
+fn depth_stream(
+    ws: WebSocketClient,
+    sync: DepthSync,
+    snapshot: OrderBookUpdate,
+) -> BoxStream<OrderBookUpdate> {
+    let pending = VecDeque::from([Ok(snapshot)]);
+    let state = (Some(ws), sync, pending);
+    let stream = futures::stream::unfold(state, |(mut ws, mut sync, mut pending)| async move {
+        loop {
+            if let Some(item) = pending.pop_front() {
+                return Some((item, (ws, sync, pending)));
+            }
+
+            let client = ws.as_mut()?;
+            let text = match client.recv().await {
+                Ok(Some(text)) => text,
+                Ok(None) => {
+                    info!("Binance WebSocket closed");
+                    return None;
+                }
+                Err(e) => return Some((Err(e), (None, sync, pending))),
+            };
+
+            let event = match parse_frame(&text) {
+                Ok(Some(BinanceWsEvent::DepthUpdate(event))) => event,
+                Ok(_) => continue,
+                Err(e) => {
+                    pending.push_back(Err(e));
+                    continue;
+                }
+            };
+
+            match sync.step(&event) {
+                DepthStep::Stale => {}
+                DepthStep::Apply => pending.push_back(sync.apply(event)),
+                DepthStep::Gap => {
+                    warn!(
+                        "Binance order book gap for {} after update {}, resyncing",
+                        sync.symbol, sync.last_update_id
+                    );
+                    match sync.snapshot().await {
+                        Ok(snapshot) => pending.push_back(Ok(snapshot)),
+                        Err(e) => return Some((Err(e), (None, sync, pending))),
+                    }
+                }
+            }
+        }
+    });
+
+    Box::new(Box::pin(stream))
+}
+
+fn convert_trade(
+    symbol: &Symbol,
+    trade_id: u64,
+    price: &str,
+    quantity: &str,
+    time: i64,
+    buyer_maker: bool,
+) -> Result<StreamTrade> {
+    Ok(StreamTrade {
+        symbol: symbol.clone(),
+        trade_id: trade_id.to_string(),
+        price: parse_price(price, "trade price")?,
+        quantity: parse_decimal(quantity, "trade quantity")?,
+        // The taker sold if the resting maker was the buyer
+        side: if buyer_maker { Side::Sell } else { Side::Buy },
+        timestamp: millis_to_datetime(time),
+        buyer_maker,
+    })
+}
+
+/// Ticker state; USDⓈ-M tickers take bid/ask from the book ticker stream
+#[derive(Default)]
+struct TickerState {
+    book: Option<BinanceWsBookTicker>,
+}
+
+impl TickerState {
+    fn apply(&mut self, event: BinanceWsEvent, symbol: &Symbol) -> Result<Vec<Ticker>> {
+        let ticker = match event {
+            BinanceWsEvent::BookTicker(book) => {
+                self.book = Some(book);
+                return Ok(Vec::new());
+            }
+            BinanceWsEvent::Ticker(ticker) => ticker,
+            _ => return Ok(Vec::new()),
+        };
+
+        let (bid, bid_size, ask, ask_size) = match &self.book {
+            Some(book) => (
+                book.bid_price.as_str(),
+                book.bid_qty.as_str(),
+                book.ask_price.as_str(),
+                book.ask_qty.as_str(),
+            ),
+            None => (
+                ticker.bid_price.as_deref().unwrap_or("0"),
+                ticker.bid_qty.as_deref().unwrap_or("0"),
+                ticker.ask_price.as_deref().unwrap_or("0"),
+                ticker.ask_qty.as_deref().unwrap_or("0"),
+            ),
+        };
+
+        Ok(vec![Ticker {
+            symbol: symbol.clone(),
+            last_price: parse_price(&ticker.last_price, "last price")?,
+            bid: parse_price(bid, "bid price")?,
+            bid_size: parse_decimal(bid_size, "bid size")?,
+            ask: parse_price(ask, "ask price")?,
+            ask_size: parse_decimal(ask_size, "ask size")?,
+            volume_24h: parse_decimal(&ticker.volume, "volume")?,
+            high_24h: parse_price(&ticker.high_price, "high price")?,
+            low_24h: parse_price(&ticker.low_price, "low price")?,
+            price_change_24h: parse_decimal(&ticker.price_change, "price change")?,
+            price_change_percent_24h: parse_decimal(
+                &ticker.price_change_percent,
+                "price change percent",
+            )?,
+            timestamp: millis_to_datetime(ticker.event_time),
+        }])
+    }
+}
+
+/// Account state needed to convert user data events
+struct UserDataState {
+    /// Leverage per Binance symbol (USDⓈ-M)
+    leverage: HashMap<String, u32>,
+}
+
+impl UserDataState {
+    fn apply(&mut self, event: BinanceWsEvent) -> Result<Vec<UserDataEvent>> {
+        match event {
+            BinanceWsEvent::ExecutionReport(report) => {
+                // Cancels report the cancel request's id in `c` and the order's in `C`
+                let client_order_id = if report.orig_client_order_id.is_empty() {
+                    report.client_order_id
+                } else {
+                    report.orig_client_order_id
+                };
+                let order = convert_order(
+                    BinanceOrder {
+                        symbol: report.symbol,
+                        order_id: report.order_id,
+                        client_order_id,
+                        price: report.price,
+                        orig_qty: report.quantity,
+                        executed_qty: report.filled_quantity,
+                        cummulative_quote_qty: Some(report.filled_quote),
+                        avg_price: None,
+                        status: report.status,
+                        time_in_force: report.time_in_force,
+                        order_type: report.order_type,
+                        side: report.side,
+                        stop_price: Some(report.stop_price),
+                        reduce_only: None,
+                        position_side: None,
+                        time: Some(report.created_time),
+                        update_time: Some(report.transaction_time),
+                        transact_time: None,
+                    },
+                    None,
+                )?;
+                Ok(vec![UserDataEvent::OrderUpdate(OrderUpdate {
+                    timestamp: millis_to_datetime(report.event_time),
+                    order,
+                })])
+            }
+            BinanceWsEvent::OrderTradeUpdate(update) => {
+                let o = update.order;
+                let order = convert_order(
+                    BinanceOrder {
+                        symbol: o.symbol,
+                        order_id: o.order_id,
+                        client_order_id: o.client_order_id,
+                        price: o.price,
+                        orig_qty: o.quantity,
+                        executed_qty: o.filled_quantity,
+                        cummulative_quote_qty: None,
+                        avg_price: Some(o.average_price),
+                        status: o.status,
+                        time_in_force: o.time_in_force,
+                        order_type: o.order_type,
+                        side: o.side,
+                        stop_price: Some(o.stop_price),
+                        reduce_only: Some(o.reduce_only),
+                        position_side: Some(o.position_side),
+                        time: None,
+                        update_time: Some(o.transaction_time),
+                        transact_time: None,
+                    },
+                    None,
+                )?;
+                Ok(vec![UserDataEvent::OrderUpdate(OrderUpdate {
+                    timestamp: millis_to_datetime(update.event_time),
+                    order,
+                })])
+            }
+            BinanceWsEvent::AccountPosition(account) => {
+                let timestamp = millis_to_datetime(account.event_time);
+                account
+                    .balances
+                    .into_iter()
+                    .map(|b| {
+                        Ok(UserDataEvent::BalanceUpdate(BalanceUpdate {
+                            free: parse_decimal(&b.free, "free balance")?,
+                            locked: parse_decimal(&b.locked, "locked balance")?,
+                            asset: b.asset,
+                            timestamp,
+                        }))
+                    })
+                    .collect()
+            }
+            BinanceWsEvent::AccountUpdate(update) => {
+                let timestamp = millis_to_datetime(update.event_time);
+                let mut events = Vec::new();
+
+                for b in update.data.balances {
+                    let wallet = parse_decimal(&b.wallet_balance, "wallet balance")?;
+                    let cross = parse_decimal(&b.cross_wallet_balance, "cross wallet balance")?;
+                    // Margin held by isolated positions is not free
+                    events.push(UserDataEvent::BalanceUpdate(BalanceUpdate {
+                        asset: b.asset,
+                        free: cross,
+                        locked: (wallet - cross).max(Decimal::ZERO),
+                        timestamp,
+                    }));
+                }
+
+                for p in update.data.positions {
+                    let amount = parse_decimal(&p.position_amt, "position amount")?;
+                    let (side, quantity) = position_side(&p.position_side, amount);
+                    let entry_price = parse_price(&p.entry_price, "entry price")?;
+                    let leverage = self.leverage.get(&p.symbol).copied().unwrap_or(1).max(1);
+                    let margin_type = convert_margin_type(&p.margin_type);
+                    let margin = match margin_type {
+                        MarginType::Isolated => {
+                            parse_decimal(&p.isolated_wallet, "isolated wallet")?
+                        }
+                        MarginType::Cross => {
+                            let entry = parse_decimal(&p.entry_price, "entry price")?;
+                            quantity * entry / Decimal::from(leverage)
+                        }
+                    };
+
+                    // Flat positions are reported so consumers can drop them
+                    events.push(UserDataEvent::PositionUpdate(PositionUpdate {
+                        position: Position {
+                            symbol: Symbol::from(p.symbol.as_str()),
+                            side,
+                            quantity,
+                            entry_price,
+                            mark_price: entry_price, // Not part of the event
+                            liquidation_price: None,
+                            leverage,
+                            unrealized_pnl: parse_decimal(&p.unrealized_pnl, "unrealized pnl")?,
+                            realized_pnl: Decimal::ZERO,
+                            margin,
+                            margin_type,
+                            update_time: timestamp,
+                        },
+                        timestamp,
+                    }));
+                }
+
+                Ok(events)
+            }
+            BinanceWsEvent::AccountConfigUpdate(update) => {
+                if let Some(config) = update.config {
+                    self.leverage.insert(config.symbol, config.leverage);
+                }
+                Ok(Vec::new())
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+}
+
+/// Keep the user data events `select` picks out
+fn filter_user_data<T, F>(events: BoxStream<UserDataEvent>, mut select: F) -> BoxStream<T>
+where
+    T: Send + 'static,
+    F: FnMut(UserDataEvent) -> Option<T> + Send + 'static,
+{
+    let stream = events.filter_map(move |event| {
+        let item = match event {
+            Ok(event) => select(event).map(Ok),
+            Err(e) => Some(Err(e)),
+        };
+        futures::future::ready(item)
+    });
+    Box::new(Box::pin(stream))
+}
+
+#[async_trait]
+impl Streaming for BinanceStreaming {
+    async fn subscribe_trades(&self, symbol: &Symbol) -> Result<BoxStream<StreamTrade>> {
+        info!("Subscribing to Binance trades for {}", symbol);
+        // USDⓈ-M has no raw trade stream for all users
+        let channel = match self.rest.product() {
+            BinanceProduct::Spot => "trade",
+            BinanceProduct::UsdmFutures => "aggTrade",
+        };
+        let native = binance_symbol(symbol).to_lowercase();
+        let ws = self.connect(vec![format!("{native}@{channel}")]).await?;
+        let symbol = symbol.clone();
+
+        Ok(event_stream(ws, (), move |event| match event {
+            BinanceWsEvent::Trade(t) => Ok(vec![convert_trade(
+                &symbol,
+                t.trade_id,
+                &t.price,
+                &t.quantity,
+                t.trade_time,
+                t.buyer_maker,
+            )?]),
+            BinanceWsEvent::AggTrade(t) => Ok(vec![convert_trade(
+                &symbol,
+                t.trade_id,
+                &t.price,
+                &t.quantity,
+                t.trade_time,
+                t.buyer_maker,
+            )?]),
+            _ => Ok(Vec::new()),
+        }))
+    }
+
+    /// The first update is a REST snapshot (`first_update_id` is None); later
+    /// updates carry changed levels, with a zero quantity removing a level.
+    /// When an update is missed a fresh snapshot is emitted instead.
+    async fn subscribe_orderbook(
+        &self,
+        symbol: &Symbol,
+        depth: Option<usize>,
+    ) -> Result<BoxStream<OrderBookUpdate>> {
+        info!(
+            "Subscribing to Binance orderbook for {} (depth: {:?})",
+            symbol, depth
+        );
+        let native = binance_symbol(symbol).to_lowercase();
+        // Events buffer on the connection while the snapshot is fetched
+        let ws = self.connect(vec![format!("{native}@depth@100ms")]).await?;
+
+        let mut sync = DepthSync {
+            rest: Arc::clone(&self.rest),
+            symbol: symbol.clone(),
+            depth,
+            last_update_id: 0,
+            synced: false,
+        };
+        let snapshot = sync.snapshot().await?;
+
+        Ok(depth_stream(ws, sync, snapshot))
+    }
+
+    async fn subscribe_ticker(&self, symbol: &Symbol) -> Result<BoxStream<Ticker>> {
+        info!("Subscribing to Binance ticker for {}", symbol);
+        let native = binance_symbol(symbol).to_lowercase();
+        let mut streams = vec![format!("{native}@ticker")];
+        if self.rest.product() == BinanceProduct::UsdmFutures {
+            streams.push(format!("{native}@bookTicker"));
+        }
+        let ws = self.connect(streams).await?;
+        let symbol = symbol.clone();
+        let mut state = TickerState::default();
+
+        Ok(event_stream(ws, (), move |event| {
+            state.apply(event, &symbol)
+        }))
+    }
+
+    /// Emits the candle in progress on every update; `close_time` is final
+    /// once the interval has ended.
+    async fn subscribe_candles(
+        &self,
+        symbol: &Symbol,
+        interval: Interval,
+    ) -> Result<BoxStream<Candle>> {
+        info!(
+            "Subscribing to Binance candles for {} ({:?})",
+            symbol, interval
+        );
+        let native = binance_symbol(symbol).to_lowercase();
+        let interval_str = BinanceMarketData::interval_to_string(interval);
+        let ws = self
+            .connect(vec![format!("{native}@kline_{interval_str}")])
+            .await?;
+        let symbol = symbol.clone();
+
+        Ok(event_stream(ws, (), move |event| {
+            let BinanceWsEvent::Kline(event) = event else {
+                return Ok(Vec::new());
+            };
+            let k = event.kline;
+            Ok(vec![Candle {
+                symbol: symbol.clone(),
+                interval,
+                open: parse_price(&k.open, "open")?,
+                high: parse_price(&k.high, "high")?,
+                low: parse_price(&k.low, "low")?,
+                close: parse_price(&k.close, "close")?,
+                volume: parse_decimal(&k.volume, "volume")?,
+                open_time: millis_to_datetime(k.open_time),
+                close_time: millis_to_datetime(k.close_time),
+                trade_count: Some(k.trade_count),
+                quote_volume: Some(parse_decimal(&k.quote_volume, "quote volume")?),
+            }])
+        }))
+    }
+
+    async fn subscribe_orders(&self) -> Result<BoxStream<OrderUpdate>> {
+        info!("Subscribing to Binance order updates");
+        let events = self.user_data().await?;
+        Ok(filter_user_data(events, |event| match event {
+            UserDataEvent::OrderUpdate(update) => Some(update),
+            _ => None,
+        }))
+    }
+
+    async fn subscribe_positions(&self) -> Result<BoxStream<PositionUpdate>> {
+        info!("Subscribing to Binance position updates");
+        let events = self.user_data().await?;
+        Ok(filter_user_data(events, |event| match event {
+            UserDataEvent::PositionUpdate(update) => Some(update),
+            _ => None,
+        }))
+    }
+
+    async fn subscribe_balances(&self) -> Result<BoxStream<BalanceUpdate>> {
+        info!("Subscribing to Binance balance updates");
+        let events = self.user_data().await?;
+        Ok(filter_user_data(events, |event| match event {
+            UserDataEvent::BalanceUpdate(update) => Some(update),
+            _ => None,
+        }))
+    }
+
+    async fn subscribe_user_data(&self) -> Result<BoxStream<UserDataEvent>> {
+        info!("Subscribing to Binance user data");
+        self.user_data().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn depth_event(first: u64, last: u64, prev: Option<u64>) -> BinanceWsDepth {
+        BinanceWsDepth {
+            event_time: 0,
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: first,
+            final_update_id: last,
+            prev_final_update_id: prev,
+            bids: Vec::new(),
+            asks: Vec::new(),
+        }
+    }
+
+    fn sync(product: BinanceProduct, last_update_id: u64, synced: bool) -> DepthSync {
+        let rest_client = Arc::new(
+            crate::common::RestClient::new("http://localhost", Duration::from_secs(1)).unwrap(),
+        );
+        let limiter = Arc::new(crate::common::RateLimiter::new(10, Duration::from_secs(1)));
+        DepthSync {
+            rest: Arc::new(BinanceRest::new(
+                rest_client,
+                Arc::clone(&limiter),
+                limiter,
+                None,
+                product,
+            )),
+            symbol: Symbol::from("BTCUSDT"),
+            depth: None,
+            last_update_id,
+            synced,
+        }
+    }
+
+    #[test]
+    fn test_spot_depth_sequencing() {
+        let book = sync(BinanceProduct::Spot, 100, false);
+        assert_eq!(book.step(&depth_event(90, 100, None)), DepthStep::Stale);
+        assert_eq!(book.step(&depth_event(95, 105, None)), DepthStep::Apply);
+        assert_eq!(book.step(&depth_event(102, 105, None)), DepthStep::Gap);
+
+        let book = sync(BinanceProduct::Spot, 105, true);
+        assert_eq!(book.step(&depth_event(106, 110, None)), DepthStep::Apply);
+        assert_eq!(book.step(&depth_event(107, 110, None)), DepthStep::Gap);
+    }
+
+    #[test]
+    fn test_futures_depth_sequencing() {
+        let book = sync(BinanceProduct::UsdmFutures, 100, false);
+        assert_eq!(book.step(&depth_event(95, 105, Some(94))), DepthStep::Apply);
+        assert_eq!(book.step(&depth_event(101, 105, Some(100))), DepthStep::Gap);
+
+        let book = sync(BinanceProduct::UsdmFutures, 105, true);
+        assert_eq!(
+            book.step(&depth_event(110, 120, Some(105))),
+            DepthStep::Apply
+        );
+        assert_eq!(book.step(&depth_event(110, 120, Some(104))), DepthStep::Gap);
+    }
+}
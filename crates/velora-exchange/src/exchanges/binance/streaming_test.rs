@@ -0,0 +1,569 @@
+//! Tests for Binance streaming implementation
+
+#[cfg(test)]
+mod tests {
+    use super::super::rest::BinanceRest;
+    use super::super::streaming::*;
+    use super::super::BinanceProduct;
+
+    use crate::auth::ApiKeyAuth;
+    use crate::common::{RateLimiter, RestClient, WebSocketClient};
+    use crate::traits::Streaming;
+    use crate::types::{
+        ExchangeError, Interval, MarginType, OrderStatus, PositionSide, Price, Side, Symbol,
+        UserDataEvent,
+    };
+
+    use futures::{SinkExt, StreamExt};
+    use mockito::{Matcher, Server};
+    use rust_decimal_macros::dec;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+    use tokio::sync::RwLock;
+    use tokio::task::JoinHandle;
+    use tokio_tungstenite::{accept_async, tungstenite::Message};
+
+    /// Serve a single connection: read `subscriptions` messages, replay
+    /// `frames`, close, and return every message the client sent
+    async fn serve(frames: Vec<Value>, subscriptions: usize) -> (String, JoinHandle<Vec<Value>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(socket).await.unwrap();
+
+            let mut received = Vec::new();
+            while received.len() < subscriptions {
+                if let Some(Ok(Message::Text(text))) = ws.next().await {
+                    received.push(serde_json::from_str(&text).unwrap());
+                }
+            }
+
+            for frame in frames {
+                ws.send(Message::Text(frame.to_string())).await.unwrap();
+            }
+            ws.close(None).await.ok();
+
+            while let Some(Ok(message)) = ws.next().await {
+                if let Message::Text(text) = message {
+                    received.push(serde_json::from_str(&text).unwrap());
+                }
+            }
+            received
+        });
+
+        (url, handle)
+    }
+
+    /// Helper to create a streaming component against local servers
+    fn setup_test(
+        server: &Server,
+        ws_url: String,
+        product: BinanceProduct,
+        authenticated: bool,
+    ) -> BinanceStreaming {
+        let rest_client =
+            Arc::new(RestClient::new(server.url(), std::time::Duration::from_secs(30)).unwrap());
+        let rate_limiter = Arc::new(RateLimiter::new(1000, std::time::Duration::from_secs(1)));
+        let auth = authenticated.then(|| ApiKeyAuth::new("test-api-key", "test-secret"));
+        let rest = BinanceRest::new(
+            rest_client,
+            Arc::clone(&rate_limiter),
+            rate_limiter,
+            auth,
+            product,
+        );
+        let ws_client = Arc::new(RwLock::new(WebSocketClient::new(ws_url)));
+
+        BinanceStreaming::new(ws_client, Arc::new(rest))
+    }
+
+    fn subscribe(streams: &[&str]) -> Value {
+        json!({ "method": "SUBSCRIBE", "params": streams, "id": 1 })
+    }
+
+    fn depth(first: u64, last: u64, bid: &str) -> Value {
+        json!({
+            "e": "depthUpdate", "E": 1700000000000i64 + last as i64, "s": "BTCUSDT",
+            "U": first, "u": last, "b": [[bid, "1.0"]], "a": [["60010.00", "0.00"]]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_trades() {
+        let server = Server::new_async().await;
+        let (url, handle) = serve(
+            vec![
+                json!({ "result": null, "id": 1 }),
+                json!({
+                    "e": "trade", "E": 1700000000001i64, "s": "BTCUSDT", "t": 12345,
+                    "p": "60000.10", "q": "0.250", "T": 1700000000000i64, "m": true, "M": true
+                }),
+                json!({
+                    "e": "trade", "E": 1700000000003i64, "s": "BTCUSDT", "t": 12346,
+                    "p": "60000.20", "q": "0.100", "T": 1700000000002i64, "m": false, "M": true
+                }),
+            ],
+            1,
+        )
+        .await;
+        let streaming = setup_test(&server, url, BinanceProduct::Spot, false);
+
+        let trades: Vec<_> = streaming
+            .subscribe_trades(&Symbol::from("BTC/USDT"))
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(trades.len(), 2);
+        let first = trades[0].as_ref().unwrap();
+        assert_eq!(first.trade_id, "12345");
+        assert_eq!(first.symbol, Symbol::from("BTC/USDT"));
+        assert_eq!(first.side, Side::Sell);
+        assert_eq!(first.quantity, dec!(0.25));
+        assert_eq!(trades[1].as_ref().unwrap().side, Side::Buy);
+
+        assert_eq!(handle.await.unwrap()[0], subscribe(&["btcusdt@trade"]));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_futures_trades_uses_agg_trades() {
+        let server = Server::new_async().await;
+        let (url, handle) = serve(
+            vec![json!({
+                "e": "aggTrade", "E": 1700000000001i64, "s": "ETHUSDT", "a": 5933014,
+                "p": "3000.5", "q": "2.5", "f": 100, "l": 105, "T": 1700000000000i64, "m": false
+            })],
+            1,
+        )
+        .await;
+        let streaming = setup_test(&server, url, BinanceProduct::UsdmFutures, false);
+
+        let trades: Vec<_> = streaming
+            .subscribe_trades(&Symbol::from("ETHUSDT"))
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(trades.len(), 1);
+        let trade = trades[0].as_ref().unwrap();
+        assert_eq!(trade.trade_id, "5933014");
+        assert_eq!(trade.price, Price::from(3000.5));
+        assert_eq!(trade.side, Side::Buy);
+
+        assert_eq!(handle.await.unwrap()[0], subscribe(&["ethusdt@aggTrade"]));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_orderbook_resyncs_after_gap() {
+        let mut server = Server::new_async().await;
+        let snapshot = server
+            .mock("GET", "/api/v3/depth")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("symbol".into(), "BTCUSDT".into()),
+                Matcher::UrlEncoded("limit".into(), "1000".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "lastUpdateId": 100,
+                    "bids": [["60000.00", "1.5"], ["59999.00", "2.0"]],
+                    "asks": [["60010.00", "0.5"]]
+                }"#,
+            )
+            .expect(2)
+            .create_async()
+            .await;
+        let (url, handle) = serve(
+            vec![
+                json!({ "result": null, "id": 1 }),
+                depth(95, 100, "59990.00"),
+                depth(99, 102, "60001.00"),
+                depth(103, 105, "60002.00"),
+                depth(110, 112, "60003.00"),
+            ],
+            1,
+        )
+        .await;
+        let streaming = setup_test(&server, url, BinanceProduct::Spot, false);
+
+        let updates: Vec<_> = streaming
+            .subscribe_orderbook(&Symbol::from("BTCUSDT"), None)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(updates.len(), 4);
+        let first = updates[0].as_ref().unwrap();
+        assert_eq!(first.first_update_id, None);
+        assert_eq!(first.final_update_id, Some(100));
+        assert_eq!(first.bids[0].price, Price::from(60000.0));
+        assert_eq!(first.bids.len(), 2);
+
+        // The event already in the snapshot is dropped
+        let second = updates[1].as_ref().unwrap();
+        assert_eq!(second.first_update_id, Some(99));
+        assert_eq!(second.final_update_id, Some(102));
+        assert_eq!(second.bids[0].price, Price::from(60001.0));
+        assert_eq!(second.asks[0].quantity, dec!(0));
+
+        assert_eq!(updates[2].as_ref().unwrap().final_update_id, Some(105));
+
+        // 106..=109 were missed, so the book is rebuilt
+        assert_eq!(updates[3].as_ref().unwrap().first_update_id, None);
+
+        snapshot.assert_async().await;
+        assert_eq!(
+            handle.await.unwrap()[0],
+            subscribe(&["btcusdt@depth@100ms"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_futures_ticker_merges_book_ticker() {
+        let server = Server::new_async().await;
+        let (url, handle) = serve(
+            vec![
+                json!({
+                    "e": "bookTicker", "u": 400900217, "E": 1700000000000i64,
+                    "T": 1700000000000i64, "s": "BTCUSDT", "b": "59999.90", "B": "3.5",
+                    "a": "60000.10", "A": "1.2"
+                }),
+                json!({
+                    "e": "24hrTicker", "E": 1700000000500i64, "s": "BTCUSDT", "p": "-100.00",
+                    "P": "-0.166", "w": "60050.00", "c": "60000.00", "Q": "0.010",
+                    "o": "60100.00", "h": "61000.00", "l": "59000.00", "v": "12345.6",
+                    "q": "741000000.0", "O": 1699913600000i64, "C": 1700000000000i64,
+                    "F": 1, "L": 100, "n": 100
+                }),
+            ],
+            1,
+        )
+        .await;
+        let streaming = setup_test(&server, url, BinanceProduct::UsdmFutures, false);
+
+        let tickers: Vec<_> = streaming
+            .subscribe_ticker(&Symbol::from("BTCUSDT"))
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(tickers.len(), 1);
+        let ticker = tickers[0].as_ref().unwrap();
+        assert_eq!(ticker.last_price, Price::from(60000.0));
+        assert_eq!(ticker.bid, Price::from(59999.9));
+        assert_eq!(ticker.ask_size, dec!(1.2));
+        assert_eq!(ticker.volume_24h, dec!(12345.6));
+        assert_eq!(ticker.price_change_percent_24h, dec!(-0.166));
+
+        assert_eq!(
+            handle.await.unwrap()[0],
+            subscribe(&["btcusdt@ticker", "btcusdt@bookTicker"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_candles() {
+        let server = Server::new_async().await;
+        let (url, handle) = serve(
+            vec![json!({
+                "e": "kline", "E": 1700000030000i64, "s": "BTCUSDT",
+                "k": {
+                    "t": 1700000000000i64, "T": 1700000059999i64, "s": "BTCUSDT", "i": "1m",
+                    "f": 100, "L": 200, "o": "60000.00", "c": "60050.00", "h": "60100.00",
+                    "l": "59950.00", "v": "12.5", "n": 101, "x": false, "q": "750000.00",
+                    "V": "6.0", "Q": "360000.00", "B": "0"
+                }
+            })],
+            1,
+        )
+        .await;
+        let streaming = setup_test(&server, url, BinanceProduct::Spot, false);
+
+        let candles: Vec<_> = streaming
+            .subscribe_candles(&Symbol::from("BTCUSDT"), Interval::Minute1)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(candles.len(), 1);
+        let candle = candles[0].as_ref().unwrap();
+        assert_eq!(candle.interval, Interval::Minute1);
+        assert_eq!(candle.high, Price::from(60100.0));
+        assert_eq!(candle.close, Price::from(60050.0));
+        assert_eq!(candle.trade_count, Some(101));
+        assert_eq!(candle.quote_volume, Some(dec!(750000)));
+
+        assert_eq!(handle.await.unwrap()[0], subscribe(&["btcusdt@kline_1m"]));
+    }
+
+    #[tokio::test]
+    async fn test_subscription_error() {
+        let server = Server::new_async().await;
+        let (url, _handle) = serve(
+            vec![json!({ "error": { "code": 2, "msg": "Invalid request" }, "id": 1 })],
+            1,
+        )
+        .await;
+        let streaming = setup_test(&server, url, BinanceProduct::Spot, false);
+
+        let trades: Vec<_> = streaming
+            .subscribe_trades(&Symbol::from("BTCUSDT"))
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(trades.len(), 1);
+        assert!(matches!(
+            trades[0],
+            Err(ExchangeError::ApiError { code: 2, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_spot_user_data() {
+        let mut server = Server::new_async().await;
+        let listen_key = server
+            .mock("POST", "/api/v3/userDataStream")
+            .match_header("x-mbx-apikey", "test-api-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"listenKey": "pqia91ma19a5s61cv6a81va65sdf19v8a65a1a5s61cv6a81va65sdf19v8a65a1"}"#)
+            .create_async()
+            .await;
+        let (url, _handle) = serve(
+            vec![
+                json!({
+                    "e": "executionReport", "E": 1700000000100i64, "s": "ETHBTC",
+                    "c": "cancel-req", "S": "BUY", "o": "LIMIT", "f": "GTC", "q": "1.00000000",
+                    "p": "0.10264410", "P": "0.00000000", "F": "0.00000000", "g": -1,
+                    "C": "my-order", "x": "CANCELED", "X": "CANCELED", "r": "NONE",
+                    "i": 4293153, "l": "0.00000000", "z": "0.00000000", "L": "0.00000000",
+                    "n": "0", "N": null, "T": 1700000000099i64, "t": -1, "w": false,
+                    "m": false, "O": 1700000000000i64, "Z": "0.00000000"
+                }),
+                json!({
+                    "e": "outboundAccountPosition", "E": 1700000000200i64,
+                    "u": 1700000000199i64,
+                    "B": [{ "a": "ETH", "f": "10000.000000", "l": "0.000000" }]
+                }),
+            ],
+            0,
+        )
+        .await;
+        let streaming = setup_test(&server, url, BinanceProduct::Spot, true);
+
+        let events: Vec<_> = streaming
+            .subscribe_user_data()
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        listen_key.assert_async().await;
+        assert_eq!(events.len(), 2);
+        match events[0].as_ref().unwrap() {
+            UserDataEvent::OrderUpdate(update) => {
+                assert_eq!(update.order.order_id, "4293153");
+                assert_eq!(update.order.client_order_id.as_deref(), Some("my-order"));
+                assert_eq!(update.order.status, OrderStatus::Cancelled);
+                assert_eq!(update.order.symbol, Symbol::from("ETHBTC"));
+            }
+            other => panic!("Expected order update, got {other:?}"),
+        }
+        match events[1].as_ref().unwrap() {
+            UserDataEvent::BalanceUpdate(update) => {
+                assert_eq!(update.asset, "ETH");
+                assert_eq!(update.free, dec!(10000));
+            }
+            other => panic!("Expected balance update, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_futures_positions_track_leverage() {
+        let mut server = Server::new_async().await;
+        let _listen_key = server
+            .mock("POST", "/fapi/v1/listenKey")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"listenKey": "futures-key"}"#)
+            .create_async()
+            .await;
+        let _position_risk = server
+            .mock("GET", "/fapi/v2/positionRisk")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[{
+                    "symbol": "BTCUSDT", "positionAmt": "0", "entryPrice": "0", "markPrice": "0",
+                    "unRealizedProfit": "0", "liquidationPrice": "0", "leverage": "20",
+                    "marginType": "cross", "isolatedMargin": "0", "positionSide": "BOTH",
+                    "notional": "0", "updateTime": 0
+                }]"#,
+            )
+            .create_async()
+            .await;
+        let account_update = |amount: &str| {
+            json!({
+                "e": "ACCOUNT_UPDATE", "E": 1700000000000i64, "T": 1700000000000i64,
+                "a": {
+                    "m": "ORDER",
+                    "B": [{ "a": "USDT", "wb": "1000.00", "cw": "900.00", "bc": "0" }],
+                    "P": [{
+                        "s": "BTCUSDT", "pa": amount, "ep": "60000.0", "cr": "0",
+                        "up": "0", "mt": "cross", "iw": "0", "ps": "BOTH"
+                    }]
+                }
+            })
+        };
+        let (url, _handle) = serve(
+            vec![
+                account_update("0.1"),
+                json!({
+                    "e": "ACCOUNT_CONFIG_UPDATE", "E": 1700000000100i64, "T": 1700000000100i64,
+                    "ac": { "s": "BTCUSDT", "l": 10 }
+                }),
+                account_update("-0.1"),
+            ],
+            0,
+        )
+        .await;
+        let streaming = setup_test(&server, url, BinanceProduct::UsdmFutures, true);
+
+        let positions: Vec<_> = streaming
+            .subscribe_positions()
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(positions.len(), 2);
+        let first = &positions[0].as_ref().unwrap().position;
+        assert_eq!(first.side, PositionSide::Long);
+        assert_eq!(first.leverage, 20);
+        assert_eq!(first.margin, dec!(300));
+        assert_eq!(first.margin_type, MarginType::Cross);
+
+        let second = &positions[1].as_ref().unwrap().position;
+        assert_eq!(second.side, PositionSide::Short);
+        assert_eq!(second.quantity, dec!(0.1));
+        assert_eq!(second.leverage, 10);
+        assert_eq!(second.margin, dec!(600));
+    }
+
+    #[tokio::test]
+    async fn test_futures_order_and_balance_updates() {
+        let mut server = Server::new_async().await;
+        let _listen_key = server
+            .mock("POST", "/fapi/v1/listenKey")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"listenKey": "futures-key"}"#)
+            .create_async()
+            .await;
+        let _position_risk = server
+            .mock("GET", "/fapi/v2/positionRisk")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("[]")
+            .create_async()
+            .await;
+        let (url, _handle) = serve(
+            vec![
+                json!({
+                    "e": "ORDER_TRADE_UPDATE", "E": 1700000000000i64, "T": 1700000000000i64,
+                    "o": {
+                        "s": "BTCUSDT", "c": "tp-1", "S": "SELL", "o": "LIMIT", "f": "GTC",
+                        "q": "0.100", "p": "61000", "ap": "61000", "sp": "0", "x": "TRADE",
+                        "X": "FILLED", "i": 8886774, "l": "0.100", "z": "0.100",
+                        "L": "61000", "T": 1700000000000i64, "R": true, "ps": "BOTH"
+                    }
+                }),
+                json!({
+                    "e": "ACCOUNT_UPDATE", "E": 1700000000001i64, "T": 1700000000001i64,
+                    "a": {
+                        "m": "ORDER",
+                        "B": [{ "a": "USDT", "wb": "1100.00", "cw": "1100.00", "bc": "0" }],
+                        "P": []
+                    }
+                }),
+            ],
+            0,
+        )
+        .await;
+        let streaming = setup_test(&server, url, BinanceProduct::UsdmFutures, true);
+
+        let orders: Vec<_> = streaming.subscribe_orders().await.unwrap().collect().await;
+
+        assert_eq!(orders.len(), 1);
+        let order = &orders[0].as_ref().unwrap().order;
+        assert_eq!(order.order_id, "8886774");
+        assert_eq!(order.status, OrderStatus::Filled);
+        assert_eq!(order.filled_quantity, dec!(0.1));
+        assert_eq!(order.average_price, Some(Price::from(61000.0)));
+        assert_eq!(order.reduce_only, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_listen_key_expired_ends_stream() {
+        let mut server = Server::new_async().await;
+        let _listen_key = server
+            .mock("POST", "/api/v3/userDataStream")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"listenKey": "expired-key"}"#)
+            .create_async()
+            .await;
+        let (url, _handle) = serve(
+            vec![
+                json!({ "e": "listenKeyExpired", "E": 1700000000000i64, "listenKey": "expired-key" }),
+                json!({
+                    "e": "outboundAccountPosition", "E": 1700000000200i64,
+                    "u": 1700000000199i64,
+                    "B": [{ "a": "ETH", "f": "1.0", "l": "0.0" }]
+                }),
+            ],
+            0,
+        )
+        .await;
+        let streaming = setup_test(&server, url, BinanceProduct::Spot, true);
+
+        let balances: Vec<_> = streaming
+            .subscribe_balances()
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(balances.len(), 1);
+        assert!(matches!(balances[0], Err(ExchangeError::Connection(_))));
+    }
+
+    #[tokio::test]
+    async fn test_user_data_requires_api_key() {
+        let server = Server::new_async().await;
+        let streaming = setup_test(
+            &server,
+            "ws://127.0.0.1:1".to_string(),
+            BinanceProduct::Spot,
+            false,
+        );
+
+        assert!(matches!(
+            streaming.subscribe_user_data().await,
+            Err(ExchangeError::Authentication(_))
+        ));
+    }
+}
@@ -0,0 +1,461 @@
+//! Binance trading implementation
+
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Method;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::{collections::BTreeSet, sync::Arc};
+use tracing::info;
+
+use crate::{
+    traits::Trading,
+    types::{
+        ExchangeError, NewOrder, Order, OrderModification, OrderStatus, OrderType, PositionSide,
+        Price, Result, Side, Symbol, TimeInForce,
+    },
+};
+
+use super::{
+    binance_symbol, endpoints, format_price, millis_to_datetime, parse_decimal,
+    rest::{BinanceRest, Params},
+    types::*,
+    BinanceProduct,
+};
+
+/// Binance trading component
+pub struct BinanceTrading {
+    rest: Arc<BinanceRest>,
+}
+
+fn side_str(side: Side) -> &'static str {
+    match side {
+        Side::Buy => "BUY",
+        Side::Sell => "SELL",
+    }
+}
+
+fn position_side_str(side: PositionSide) -> &'static str {
+    match side {
+        PositionSide::Long => "LONG",
+        PositionSide::Short => "SHORT",
+        PositionSide::Both => "BOTH",
+    }
+}
+
+/// Request parameter identifying an order: numeric ids are exchange ids,
+/// anything else is taken as a client order id
+pub(crate) fn order_id_param(order_id: &str) -> (&'static str, String) {
+    if !order_id.is_empty() && order_id.bytes().all(|b| b.is_ascii_digit()) {
+        ("orderId", order_id.to_string())
+    } else {
+        ("origClientOrderId", order_id.to_string())
+    }
+}
+
+/// Parse an optional price field ("0" means unset)
+fn parse_optional_price(s: Option<&str>, what: &str) -> Result<Option<Price>> {
+    let Some(s) = s.filter(|s| !s.is_empty()) else {
+        return Ok(None);
+    };
+    let value = parse_decimal(s, what)?;
+    Ok((value > Decimal::ZERO)
+        .then(|| value.to_f64().map(Price::from))
+        .flatten())
+}
+
+/// Map a Binance order status
+pub(crate) fn convert_status(status: &str) -> OrderStatus {
+    match status {
+        "NEW" | "PENDING_NEW" => OrderStatus::Open,
+        "PARTIALLY_FILLED" => OrderStatus::PartiallyFilled,
+        "FILLED" => OrderStatus::Filled,
+        "CANCELED" | "PENDING_CANCEL" => OrderStatus::Cancelled,
+        "REJECTED" => OrderStatus::Rejected,
+        "EXPIRED" | "EXPIRED_IN_MATCH" => OrderStatus::Expired,
+        _ => OrderStatus::Pending,
+    }
+}
+
+/// Convert a Binance order
+///
+/// `symbol` is the caller's symbol when known; otherwise the Binance symbol is used.
+pub(crate) fn convert_order(order: BinanceOrder, symbol: Option<&Symbol>) -> Result<Order> {
+    let side = match order.side.as_str() {
+        "BUY" => Side::Buy,
+        "SELL" => Side::Sell,
+        other => {
+            return Err(ExchangeError::ParseError(format!(
+                "Unknown order side '{other}'"
+            )))
+        }
+    };
+
+    let price = parse_optional_price(Some(&order.price), "order price")?;
+    let order_type = match order.order_type.as_str() {
+        "MARKET" => OrderType::Market,
+        "LIMIT" | "LIMIT_MAKER" => OrderType::Limit,
+        "STOP_LOSS_LIMIT" | "TAKE_PROFIT_LIMIT" | "STOP" => OrderType::StopLimit,
+        // Futures TAKE_PROFIT is a limit order, spot TAKE_PROFIT is not
+        "TAKE_PROFIT" if price.is_some() => OrderType::StopLimit,
+        _ => OrderType::StopMarket,
+    };
+    let time_in_force = match (order.order_type.as_str(), order.time_in_force.as_str()) {
+        ("LIMIT_MAKER", _) | (_, "GTX") => TimeInForce::GoodTilCrossing,
+        (_, "IOC") => TimeInForce::ImmediateOrCancel,
+        (_, "FOK") => TimeInForce::FillOrKill,
+        _ => TimeInForce::GoodTilCancel,
+    };
+
+    let filled_quantity = parse_decimal(&order.executed_qty, "executed quantity")?;
+    let average_price = match parse_optional_price(order.avg_price.as_deref(), "average price")? {
+        Some(avg) => Some(avg),
+        // Spot reports the quote amount instead of an average
+        None if filled_quantity > Decimal::ZERO => match order.cummulative_quote_qty.as_deref() {
+            Some(quote) => (parse_decimal(quote, "quote quantity")? / filled_quantity)
+                .to_f64()
+                .map(Price::from),
+            None => None,
+        },
+        None => None,
+    };
+
+    let created_at = order
+        .time
+        .or(order.transact_time)
+        .map(millis_to_datetime)
+        .unwrap_or_else(Utc::now);
+    let updated_at = order
+        .update_time
+        .or(order.transact_time)
+        .map(millis_to_datetime)
+        .unwrap_or(created_at);
+
+    Ok(Order {
+        order_id: order.order_id.to_string(),
+        client_order_id: (!order.client_order_id.is_empty()).then_some(order.client_order_id),
+        symbol: symbol
+            .cloned()
+            .unwrap_or_else(|| Symbol::from(order.symbol.as_str())),
+        side,
+        order_type,
+        time_in_force,
+        quantity: parse_decimal(&order.orig_qty, "order quantity")?,
+        price,
+        stop_price: parse_optional_price(order.stop_price.as_deref(), "stop price")?,
+        status: convert_status(&order.status),
+        filled_quantity,
+        average_price,
+        created_at,
+        updated_at,
+        reduce_only: order.reduce_only,
+        position_side: match order.position_side.as_deref() {
+            Some("LONG") => Some(PositionSide::Long),
+            Some("SHORT") => Some(PositionSide::Short),
+            Some("BOTH") => Some(PositionSide::Both),
+            _ => None,
+        },
+    })
+}
+
+impl BinanceTrading {
+    pub(crate) fn new(rest: Arc<BinanceRest>) -> Self {
+        Self { rest }
+    }
+
+    /// Request parameters for a new order
+    fn order_params(&self, order: &NewOrder) -> Result<Params> {
+        if order.quantity <= Decimal::ZERO {
+            return Err(ExchangeError::InvalidOrder(
+                "Quantity must be positive".to_string(),
+            ));
+        }
+
+        let product = self.rest.product();
+        let limit_price = |what: &str| {
+            order
+                .price
+                .ok_or_else(|| ExchangeError::InvalidOrder(format!("{what} requires a price")))
+                .and_then(format_price)
+        };
+        let stop_price = || {
+            order
+                .stop_price
+                .ok_or_else(|| {
+                    ExchangeError::InvalidOrder("Stop order requires a stop price".to_string())
+                })
+                .and_then(format_price)
+        };
+        let time_in_force = order.time_in_force.unwrap_or(TimeInForce::GoodTilCancel);
+
+        let mut params: Params = vec![
+            ("symbol", binance_symbol(&order.symbol)),
+            ("side", side_str(order.side).to_string()),
+        ];
+
+        match (product, order.order_type) {
+            (_, OrderType::Market) => params.push(("type", "MARKET".to_string())),
+            // Spot expresses post-only as its own order type
+            (BinanceProduct::Spot, OrderType::Limit)
+                if time_in_force == TimeInForce::GoodTilCrossing =>
+            {
+                params.push(("type", "LIMIT_MAKER".to_string()));
+                params.push(("price", limit_price("Limit order")?));
+            }
+            (_, OrderType::Limit) => {
+                params.push(("type", "LIMIT".to_string()));
+                params.push(("timeInForce", time_in_force.to_string()));
+                params.push(("price", limit_price("Limit order")?));
+            }
+            (BinanceProduct::Spot, OrderType::StopMarket) => {
+                params.push(("type", "STOP_LOSS".to_string()));
+                params.push(("stopPrice", stop_price()?));
+            }
+            (BinanceProduct::UsdmFutures, OrderType::StopMarket) => {
+                params.push(("type", "STOP_MARKET".to_string()));
+                params.push(("stopPrice", stop_price()?));
+            }
+            (_, OrderType::StopLimit) => {
+                let order_type = match product {
+                    BinanceProduct::Spot => "STOP_LOSS_LIMIT",
+                    BinanceProduct::UsdmFutures => "STOP",
+                };
+                params.push(("type", order_type.to_string()));
+                params.push(("timeInForce", time_in_force.to_string()));
+                params.push(("price", limit_price("Stop-limit order")?));
+                params.push(("stopPrice", stop_price()?));
+            }
+        }
+
+        params.push(("quantity", order.quantity.normalize().to_string()));
+        if let Some(client_order_id) = &order.client_order_id {
+            params.push(("newClientOrderId", client_order_id.clone()));
+        }
+
+        match product {
+            BinanceProduct::Spot => {
+                if order.reduce_only == Some(true) {
+                    return Err(ExchangeError::InvalidOrder(
+                        "Reduce-only orders are only available on Binance futures".to_string(),
+                    ));
+                }
+            }
+            BinanceProduct::UsdmFutures => {
+                if order.reduce_only == Some(true) {
+                    params.push(("reduceOnly", "true".to_string()));
+                }
+                if let Some(side) = order.position_side {
+                    params.push(("positionSide", position_side_str(side).to_string()));
+                }
+            }
+        }
+
+        params.push(("newOrderRespType", "RESULT".to_string()));
+        Ok(params)
+    }
+
+    /// Find an open order by exchange or client order id
+    async fn find_open_order(&self, order_id: &str) -> Result<Order> {
+        self.get_open_orders(None)
+            .await?
+            .into_iter()
+            .find(|o| o.order_id == order_id || o.client_order_id.as_deref() == Some(order_id))
+            .ok_or_else(|| ExchangeError::OrderNotFound(order_id.to_string()))
+    }
+}
+
+#[async_trait]
+impl Trading for BinanceTrading {
+    async fn place_order(&self, order: NewOrder) -> Result<Order> {
+        let params = self.order_params(&order)?;
+        let response: BinanceOrder = self
+            .rest
+            .order(Method::POST, self.rest.routes().order, params, 1)
+            .await?;
+        info!(
+            "Placed Binance {:?} order {} for {}",
+            order.side, response.order_id, order.symbol
+        );
+
+        convert_order(response, Some(&order.symbol))
+    }
+
+    async fn cancel_order(&self, symbol: &Symbol, order_id: &str) -> Result<Order> {
+        let params: Params = vec![("symbol", binance_symbol(symbol)), order_id_param(order_id)];
+        let response: BinanceOrder = self
+            .rest
+            .signed(Method::DELETE, self.rest.routes().order, params, 1)
+            .await?;
+        info!("Cancelled Binance order {} for {}", order_id, symbol);
+
+        convert_order(response, Some(symbol))
+    }
+
+    async fn cancel_all_orders(&self, symbol: Option<&Symbol>) -> Result<Vec<Order>> {
+        let open_orders = self.get_open_orders(symbol).await?;
+
+        // Binance cancels per symbol; skip symbols without open orders
+        let symbols: BTreeSet<String> = open_orders
+            .iter()
+            .map(|o| binance_symbol(&o.symbol))
+            .collect();
+        for native in symbols {
+            let params: Params = vec![("symbol", native)];
+            self.rest
+                .signed::<serde_json::Value>(
+                    Method::DELETE,
+                    self.rest.routes().cancel_all,
+                    params,
+                    1,
+                )
+                .await?;
+        }
+
+        let now = Utc::now();
+        Ok(open_orders
+            .into_iter()
+            .map(|mut o| {
+                o.status = OrderStatus::Cancelled;
+                o.updated_at = now;
+                o
+            })
+            .collect())
+    }
+
+    async fn modify_order(
+        &self,
+        order_id: &str,
+        modifications: OrderModification,
+    ) -> Result<Order> {
+        if modifications.stop_price.is_some() {
+            return Err(ExchangeError::Unsupported(
+                "Binance cannot modify stop prices".to_string(),
+            ));
+        }
+
+        let existing = self.find_open_order(order_id).await?;
+        if existing.order_type != OrderType::Limit {
+            return Err(ExchangeError::Unsupported(
+                "Binance can only modify limit orders".to_string(),
+            ));
+        }
+
+        let price = modifications
+            .price
+            .or(existing.price)
+            .ok_or_else(|| ExchangeError::InvalidOrder("Limit order requires a price".to_string()))
+            .and_then(format_price)?;
+        let quantity = modifications
+            .quantity
+            .unwrap_or(existing.quantity)
+            .normalize()
+            .to_string();
+        let native = binance_symbol(&existing.symbol);
+
+        let response = match self.rest.product() {
+            BinanceProduct::UsdmFutures => {
+                let params: Params = vec![
+                    ("symbol", native),
+                    ("orderId", existing.order_id.clone()),
+                    ("side", side_str(existing.side).to_string()),
+                    ("quantity", quantity),
+                    ("price", price),
+                ];
+                self.rest
+                    .order::<BinanceOrder>(Method::PUT, self.rest.routes().order, params, 1)
+                    .await?
+            }
+            BinanceProduct::Spot => {
+                // Spot has no amend for price changes, so cancel and replace atomically
+                let mut params: Params = vec![
+                    ("symbol", native),
+                    ("side", side_str(existing.side).to_string()),
+                ];
+                if existing.time_in_force == TimeInForce::GoodTilCrossing {
+                    params.push(("type", "LIMIT_MAKER".to_string()));
+                } else {
+                    params.push(("type", "LIMIT".to_string()));
+                    params.push(("timeInForce", existing.time_in_force.to_string()));
+                }
+                params.extend([
+                    ("quantity", quantity),
+                    ("price", price),
+                    ("cancelReplaceMode", "STOP_ON_FAILURE".to_string()),
+                    ("cancelOrderId", existing.order_id.clone()),
+                    ("newOrderRespType", "RESULT".to_string()),
+                ]);
+                let response: BinanceCancelReplace = self
+                    .rest
+                    .order(Method::POST, endpoints::SPOT_CANCEL_REPLACE, params, 1)
+                    .await?;
+                response
+                    .new_order_response
+                    .ok_or_else(|| ExchangeError::ApiError {
+                        code: 0,
+                        message: format!(
+                            "Cancel-replace failed: cancel {}, new order {}",
+                            response.cancel_result, response.new_order_result
+                        ),
+                    })?
+            }
+        };
+        info!("Modified Binance order {}", order_id);
+
+        convert_order(response, Some(&existing.symbol))
+    }
+
+    async fn get_order(&self, symbol: &Symbol, order_id: &str) -> Result<Order> {
+        let params: Params = vec![("symbol", binance_symbol(symbol)), order_id_param(order_id)];
+        let response: BinanceOrder = self
+            .rest
+            .signed(
+                Method::GET,
+                self.rest.routes().order,
+                params,
+                self.rest.weight(4, 1),
+            )
+            .await?;
+
+        convert_order(response, Some(symbol))
+    }
+
+    async fn get_open_orders(&self, symbol: Option<&Symbol>) -> Result<Vec<Order>> {
+        let (params, weight): (Params, u32) = match symbol {
+            Some(symbol) => (
+                vec![("symbol", binance_symbol(symbol))],
+                self.rest.weight(6, 1),
+            ),
+            None => (Vec::new(), self.rest.weight(80, 40)),
+        };
+        let orders: Vec<BinanceOrder> = self
+            .rest
+            .signed(Method::GET, self.rest.routes().open_orders, params, weight)
+            .await?;
+
+        orders
+            .into_iter()
+            .map(|o| convert_order(o, symbol))
+            .collect()
+    }
+
+    async fn get_order_history(&self, symbol: &Symbol, limit: Option<usize>) -> Result<Vec<Order>> {
+        let mut params: Params = vec![("symbol", binance_symbol(symbol))];
+        if let Some(limit) = limit {
+            params.push(("limit", limit.clamp(1, 1000).to_string()));
+        }
+        let orders: Vec<BinanceOrder> = self
+            .rest
+            .signed(
+                Method::GET,
+                self.rest.routes().all_orders,
+                params,
+                self.rest.weight(20, 5),
+            )
+            .await?;
+
+        orders
+            .into_iter()
+            .map(|o| convert_order(o, Some(symbol)))
+            .collect()
+    }
+}
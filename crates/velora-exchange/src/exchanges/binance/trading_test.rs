@@ -0,0 +1,559 @@
+//! Tests for Binance trading implementation
+
+#[cfg(test)]
+mod tests {
+    use super::super::rest::BinanceRest;
+    use super::super::trading::*;
+    use super::super::BinanceProduct;
+
+    use crate::auth::ApiKeyAuth;
+    use crate::common::{RateLimiter, RestClient};
+    use crate::traits::Trading;
+    use crate::types::{
+        ExchangeError, NewOrder, OrderModification, OrderStatus, OrderType, PositionSide, Price,
+        Side, Symbol, TimeInForce,
+    };
+
+    use mockito::{Matcher, Server};
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+
+    const API_KEY: &str = "test-api-key";
+
+    const SPOT_OPEN_ORDERS: &str = r#"[
+        {
+            "symbol": "BTCUSDT",
+            "orderId": 28,
+            "orderListId": -1,
+            "clientOrderId": "my-order-28",
+            "price": "60000.00",
+            "origQty": "0.01000000",
+            "executedQty": "0.00400000",
+            "cummulativeQuoteQty": "240.00000000",
+            "status": "PARTIALLY_FILLED",
+            "timeInForce": "GTC",
+            "type": "LIMIT",
+            "side": "BUY",
+            "stopPrice": "0.00000000",
+            "icebergQty": "0.00000000",
+            "time": 1700000000000,
+            "updateTime": 1700000100000,
+            "isWorking": true,
+            "origQuoteOrderQty": "0.00000000"
+        }
+    ]"#;
+
+    const FUTURES_OPEN_ORDERS: &str = r#"[
+        {
+            "avgPrice": "0.00000",
+            "clientOrderId": "abc",
+            "cumQuote": "0",
+            "executedQty": "0",
+            "orderId": 1917641,
+            "origQty": "0.40",
+            "origType": "LIMIT",
+            "price": "3000",
+            "reduceOnly": false,
+            "side": "SELL",
+            "positionSide": "BOTH",
+            "status": "NEW",
+            "stopPrice": "0",
+            "closePosition": false,
+            "symbol": "ETHUSDT",
+            "time": 1700000000000,
+            "timeInForce": "GTX",
+            "type": "LIMIT",
+            "updateTime": 1700000000000,
+            "workingType": "CONTRACT_PRICE",
+            "priceProtect": false
+        }
+    ]"#;
+
+    /// Helper to create a trading component against a mocked server
+    fn setup_test(server: &Server, product: BinanceProduct, authenticated: bool) -> BinanceTrading {
+        let rest_client =
+            Arc::new(RestClient::new(server.url(), std::time::Duration::from_secs(30)).unwrap());
+        let rate_limiter = Arc::new(RateLimiter::new(1000, std::time::Duration::from_secs(1)));
+        let auth = authenticated.then(|| ApiKeyAuth::new(API_KEY, "test-secret"));
+        let rest = BinanceRest::new(
+            rest_client,
+            Arc::clone(&rate_limiter),
+            rate_limiter,
+            auth,
+            product,
+        );
+
+        BinanceTrading::new(Arc::new(rest))
+    }
+
+    /// Matcher for the query of a signed request carrying `pairs`
+    fn signed_query(pairs: &[(&str, &str)]) -> Matcher {
+        let mut matchers: Vec<Matcher> = pairs
+            .iter()
+            .map(|(k, v)| Matcher::UrlEncoded(k.to_string(), v.to_string()))
+            .collect();
+        matchers.push(Matcher::Regex("timestamp=\\d+".into()));
+        matchers.push(Matcher::Regex("&signature=[0-9a-f]{64}$".into()));
+        Matcher::AllOf(matchers)
+    }
+
+    #[tokio::test]
+    async fn test_place_limit_order_is_signed() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("POST", "/api/v3/order")
+            .match_header("x-mbx-apikey", API_KEY)
+            .match_query(signed_query(&[
+                ("symbol", "BTCUSDT"),
+                ("side", "BUY"),
+                ("type", "LIMIT"),
+                ("timeInForce", "GTC"),
+                ("price", "60000.5"),
+                ("quantity", "0.01"),
+                ("newClientOrderId", "my-order"),
+                ("newOrderRespType", "RESULT"),
+                ("recvWindow", "5000"),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "symbol": "BTCUSDT",
+                    "orderId": 28,
+                    "orderListId": -1,
+                    "clientOrderId": "my-order",
+                    "transactTime": 1700000000000,
+                    "price": "60000.50000000",
+                    "origQty": "0.01000000",
+                    "executedQty": "0.00000000",
+                    "cummulativeQuoteQty": "0.00000000",
+                    "status": "NEW",
+                    "timeInForce": "GTC",
+                    "type": "LIMIT",
+                    "side": "BUY",
+                    "workingTime": 1700000000000,
+                    "selfTradePreventionMode": "NONE"
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server, BinanceProduct::Spot, true);
+        let symbol = Symbol::from("BTC/USDT");
+        let mut order =
+            NewOrder::limit(symbol.clone(), Side::Buy, Price::from(60000.5), dec!(0.01));
+        order.client_order_id = Some("my-order".to_string());
+
+        let placed = trading.place_order(order).await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(placed.order_id, "28");
+        assert_eq!(placed.client_order_id.as_deref(), Some("my-order"));
+        assert_eq!(placed.symbol, symbol);
+        assert_eq!(placed.status, OrderStatus::Open);
+        assert_eq!(placed.price, Some(Price::from(60000.5)));
+        assert_eq!(placed.quantity, dec!(0.01));
+        assert_eq!(placed.average_price, None);
+    }
+
+    #[tokio::test]
+    async fn test_spot_post_only_becomes_limit_maker() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("POST", "/api/v3/order")
+            .match_query(signed_query(&[("type", "LIMIT_MAKER"), ("price", "100")]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "symbol": "ETHUSDT", "orderId": 5, "clientOrderId": "x",
+                    "transactTime": 1700000000000, "price": "100.00", "origQty": "1.0",
+                    "executedQty": "0.0", "cummulativeQuoteQty": "0.0", "status": "NEW",
+                    "timeInForce": "GTC", "type": "LIMIT_MAKER", "side": "SELL"
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server, BinanceProduct::Spot, true);
+        let mut order = NewOrder::limit(
+            Symbol::from("ETHUSDT"),
+            Side::Sell,
+            Price::from(100.0),
+            dec!(1),
+        );
+        order.time_in_force = Some(TimeInForce::GoodTilCrossing);
+
+        let placed = trading.place_order(order).await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(placed.order_type, OrderType::Limit);
+        assert_eq!(placed.time_in_force, TimeInForce::GoodTilCrossing);
+    }
+
+    #[tokio::test]
+    async fn test_futures_stop_market_reduce_only() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("POST", "/fapi/v1/order")
+            .match_query(signed_query(&[
+                ("symbol", "ETHUSDT"),
+                ("side", "SELL"),
+                ("type", "STOP_MARKET"),
+                ("stopPrice", "2900"),
+                ("quantity", "0.4"),
+                ("reduceOnly", "true"),
+                ("positionSide", "BOTH"),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "clientOrderId": "stop-1", "cumQty": "0", "cumQuote": "0", "executedQty": "0",
+                    "orderId": 22542179, "avgPrice": "0.00000", "origQty": "0.4", "price": "0",
+                    "reduceOnly": true, "side": "SELL", "positionSide": "BOTH", "status": "NEW",
+                    "stopPrice": "2900", "closePosition": false, "symbol": "ETHUSDT",
+                    "timeInForce": "GTC", "type": "STOP_MARKET", "origType": "STOP_MARKET",
+                    "updateTime": 1700000000000, "workingType": "CONTRACT_PRICE", "priceProtect": false
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server, BinanceProduct::UsdmFutures, true);
+        let mut order = NewOrder::market(Symbol::from("ETHUSDT"), Side::Sell, dec!(0.4));
+        order.order_type = OrderType::StopMarket;
+        order.stop_price = Some(Price::from(2900.0));
+        order.reduce_only = Some(true);
+        order.position_side = Some(PositionSide::Both);
+
+        let placed = trading.place_order(order).await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(placed.order_type, OrderType::StopMarket);
+        assert_eq!(placed.price, None);
+        assert_eq!(placed.stop_price, Some(Price::from(2900.0)));
+        assert_eq!(placed.reduce_only, Some(true));
+        assert_eq!(placed.position_side, Some(PositionSide::Both));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_orders_are_rejected_locally() {
+        let server = Server::new_async().await;
+        let spot = setup_test(&server, BinanceProduct::Spot, true);
+
+        let mut reduce_only = NewOrder::market(Symbol::from("BTCUSDT"), Side::Sell, dec!(1));
+        reduce_only.reduce_only = Some(true);
+        assert!(matches!(
+            spot.place_order(reduce_only).await,
+            Err(ExchangeError::InvalidOrder(_))
+        ));
+
+        let mut stop = NewOrder::market(Symbol::from("BTCUSDT"), Side::Sell, dec!(1));
+        stop.order_type = OrderType::StopLimit;
+        assert!(matches!(
+            spot.place_order(stop).await,
+            Err(ExchangeError::InvalidOrder(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_mode_requires_api_key() {
+        let server = Server::new_async().await;
+        let trading = setup_test(&server, BinanceProduct::Spot, false);
+
+        let order = NewOrder::market(Symbol::from("BTCUSDT"), Side::Buy, dec!(1));
+        assert!(matches!(
+            trading.place_order(order).await,
+            Err(ExchangeError::Authentication(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_insufficient_balance_error() {
+        let mut server = Server::new_async().await;
+
+        let _mock = server
+            .mock("POST", "/api/v3/order")
+            .match_query(Matcher::Any)
+            .with_status(400)
+            .with_body(
+                r#"{"code":-2010,"msg":"Account has insufficient balance for requested action."}"#,
+            )
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server, BinanceProduct::Spot, true);
+        let order = NewOrder::market(Symbol::from("BTCUSDT"), Side::Buy, dec!(100));
+
+        assert!(matches!(
+            trading.place_order(order).await,
+            Err(ExchangeError::InsufficientBalance(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_order_by_client_id() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("DELETE", "/api/v3/order")
+            .match_query(signed_query(&[
+                ("symbol", "BTCUSDT"),
+                ("origClientOrderId", "my-order-28"),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "symbol": "BTCUSDT", "origClientOrderId": "my-order-28", "orderId": 28,
+                    "orderListId": -1, "clientOrderId": "cancel-1", "transactTime": 1700000200000,
+                    "price": "60000.00", "origQty": "0.01", "executedQty": "0.004",
+                    "cummulativeQuoteQty": "240.00", "status": "CANCELED", "timeInForce": "GTC",
+                    "type": "LIMIT", "side": "BUY"
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server, BinanceProduct::Spot, true);
+        let cancelled = trading
+            .cancel_order(&Symbol::from("BTCUSDT"), "my-order-28")
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(cancelled.status, OrderStatus::Cancelled);
+        assert_eq!(cancelled.filled_quantity, dec!(0.004));
+        assert_eq!(cancelled.average_price, Some(Price::from(60000.0)));
+    }
+
+    #[test]
+    fn test_order_id_param() {
+        assert_eq!(order_id_param("28"), ("orderId", "28".to_string()));
+        assert_eq!(
+            order_id_param("my-order"),
+            ("origClientOrderId", "my-order".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel_all_orders_per_symbol() {
+        let mut server = Server::new_async().await;
+
+        let open = server
+            .mock("GET", "/api/v3/openOrders")
+            .match_query(Matcher::Regex("signature=".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(SPOT_OPEN_ORDERS)
+            .create_async()
+            .await;
+        let cancel = server
+            .mock("DELETE", "/api/v3/openOrders")
+            .match_query(signed_query(&[("symbol", "BTCUSDT")]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("[]")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server, BinanceProduct::Spot, true);
+        let cancelled = trading.cancel_all_orders(None).await.unwrap();
+
+        open.assert_async().await;
+        cancel.assert_async().await;
+        assert_eq!(cancelled.len(), 1);
+        assert_eq!(cancelled[0].status, OrderStatus::Cancelled);
+        assert_eq!(cancelled[0].average_price, Some(Price::from(60000.0)));
+    }
+
+    #[tokio::test]
+    async fn test_modify_futures_order_amends_in_place() {
+        let mut server = Server::new_async().await;
+
+        let _open = server
+            .mock("GET", "/fapi/v1/openOrders")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(FUTURES_OPEN_ORDERS)
+            .create_async()
+            .await;
+        let amend = server
+            .mock("PUT", "/fapi/v1/order")
+            .match_query(signed_query(&[
+                ("symbol", "ETHUSDT"),
+                ("orderId", "1917641"),
+                ("side", "SELL"),
+                ("quantity", "0.4"),
+                ("price", "3010"),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "orderId": 1917641, "symbol": "ETHUSDT", "status": "NEW", "clientOrderId": "abc",
+                    "price": "3010", "avgPrice": "0.00", "origQty": "0.40", "executedQty": "0",
+                    "cumQuote": "0", "timeInForce": "GTX", "type": "LIMIT", "reduceOnly": false,
+                    "side": "SELL", "positionSide": "BOTH", "stopPrice": "0", "origType": "LIMIT",
+                    "updateTime": 1700000300000
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server, BinanceProduct::UsdmFutures, true);
+        let modified = trading
+            .modify_order(
+                "1917641",
+                OrderModification {
+                    quantity: None,
+                    price: Some(Price::from(3010.0)),
+                    stop_price: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        amend.assert_async().await;
+        assert_eq!(modified.price, Some(Price::from(3010.0)));
+        assert_eq!(modified.time_in_force, TimeInForce::GoodTilCrossing);
+    }
+
+    #[tokio::test]
+    async fn test_modify_spot_order_cancel_replaces() {
+        let mut server = Server::new_async().await;
+
+        let _open = server
+            .mock("GET", "/api/v3/openOrders")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(SPOT_OPEN_ORDERS)
+            .create_async()
+            .await;
+        let replace = server
+            .mock("POST", "/api/v3/order/cancelReplace")
+            .match_query(signed_query(&[
+                ("symbol", "BTCUSDT"),
+                ("side", "BUY"),
+                ("type", "LIMIT"),
+                ("timeInForce", "GTC"),
+                ("quantity", "0.02"),
+                ("price", "60000"),
+                ("cancelReplaceMode", "STOP_ON_FAILURE"),
+                ("cancelOrderId", "28"),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "cancelResult": "SUCCESS",
+                    "newOrderResult": "SUCCESS",
+                    "cancelResponse": {"symbol": "BTCUSDT", "orderId": 28, "status": "CANCELED"},
+                    "newOrderResponse": {
+                        "symbol": "BTCUSDT", "orderId": 29, "orderListId": -1, "clientOrderId": "new-29",
+                        "transactTime": 1700000400000, "price": "60000.00", "origQty": "0.02",
+                        "executedQty": "0.00", "cummulativeQuoteQty": "0.00", "status": "NEW",
+                        "timeInForce": "GTC", "type": "LIMIT", "side": "BUY"
+                    }
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server, BinanceProduct::Spot, true);
+        let modified = trading
+            .modify_order(
+                "my-order-28",
+                OrderModification {
+                    quantity: Some(dec!(0.02)),
+                    price: None,
+                    stop_price: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        replace.assert_async().await;
+        assert_eq!(modified.order_id, "29");
+        assert_eq!(modified.quantity, dec!(0.02));
+    }
+
+    #[tokio::test]
+    async fn test_modify_unknown_order() {
+        let mut server = Server::new_async().await;
+
+        let _open = server
+            .mock("GET", "/api/v3/openOrders")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("[]")
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server, BinanceProduct::Spot, true);
+        let result = trading
+            .modify_order(
+                "404",
+                OrderModification {
+                    quantity: Some(dec!(1)),
+                    price: None,
+                    stop_price: None,
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(ExchangeError::OrderNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_order_history() {
+        let mut server = Server::new_async().await;
+
+        let _mock = server
+            .mock("GET", "/api/v3/allOrders")
+            .match_query(signed_query(&[("symbol", "BTCUSDT"), ("limit", "10")]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                    {
+                        "symbol": "BTCUSDT", "orderId": 1, "clientOrderId": "a", "price": "0.00",
+                        "origQty": "0.5", "executedQty": "0.5", "cummulativeQuoteQty": "15000.00",
+                        "status": "FILLED", "timeInForce": "GTC", "type": "MARKET", "side": "SELL",
+                        "stopPrice": "0.00", "time": 1700000000000, "updateTime": 1700000000001
+                    },
+                    {
+                        "symbol": "BTCUSDT", "orderId": 2, "clientOrderId": "b", "price": "29000.00",
+                        "origQty": "0.5", "executedQty": "0.0", "cummulativeQuoteQty": "0.0",
+                        "status": "EXPIRED", "timeInForce": "IOC", "type": "STOP_LOSS_LIMIT", "side": "SELL",
+                        "stopPrice": "29100.00", "time": 1700000000000, "updateTime": 1700000000001
+                    }
+                ]"#,
+            )
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server, BinanceProduct::Spot, true);
+        let history = trading
+            .get_order_history(&Symbol::from("BTCUSDT"), Some(10))
+            .await
+            .unwrap();
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].status, OrderStatus::Filled);
+        assert_eq!(history[0].order_type, OrderType::Market);
+        assert_eq!(history[0].average_price, Some(Price::from(30000.0)));
+        assert_eq!(history[1].status, OrderStatus::Expired);
+        assert_eq!(history[1].order_type, OrderType::StopLimit);
+        assert_eq!(history[1].time_in_force, TimeInForce::ImmediateOrCancel);
+        assert_eq!(history[1].stop_price, Some(Price::from(29100.0)));
+    }
+}
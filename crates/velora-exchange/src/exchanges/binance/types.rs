@@ -0,0 +1,617 @@
+//! Binance-specific API request/response types
+//!
+//! Spot and USDⓈ-M futures share most payloads; fields that only one product
+//! line returns are optional.
+
+use serde::{Deserialize, Serialize};
+
+// ============ Market Data ============
+
+/// Response of `exchangeInfo`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceExchangeInfo {
+    pub symbols: Vec<BinanceSymbolInfo>,
+}
+
+/// Trading rules of a symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceSymbolInfo {
+    pub symbol: String,
+    pub status: String, // "TRADING", "BREAK", "HALT", ...
+    pub base_asset: String,
+    pub quote_asset: String,
+    #[serde(default)]
+    pub contract_type: Option<String>, // futures: "PERPETUAL", "CURRENT_QUARTER", ...
+    #[serde(default)]
+    pub delivery_date: Option<i64>, // futures, milliseconds
+    #[serde(default)]
+    pub filters: Vec<BinanceFilter>,
+}
+
+/// Symbol filter; only the ones we map are decoded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "filterType", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BinanceFilter {
+    PriceFilter {
+        #[serde(rename = "tickSize")]
+        tick_size: String,
+    },
+    LotSize {
+        #[serde(rename = "minQty")]
+        min_qty: String,
+        #[serde(rename = "maxQty")]
+        max_qty: String,
+        #[serde(rename = "stepSize")]
+        step_size: String,
+    },
+    MinNotional {
+        #[serde(rename = "minNotional", alias = "notional")]
+        min_notional: String,
+    },
+    Notional {
+        #[serde(rename = "minNotional")]
+        min_notional: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// 24h rolling ticker (spot also carries the best bid/ask)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceTicker24h {
+    pub symbol: String,
+    pub price_change: String,
+    pub price_change_percent: String,
+    pub last_price: String,
+    #[serde(default)]
+    pub bid_price: Option<String>,
+    #[serde(default)]
+    pub bid_qty: Option<String>,
+    #[serde(default)]
+    pub ask_price: Option<String>,
+    #[serde(default)]
+    pub ask_qty: Option<String>,
+    pub high_price: String,
+    pub low_price: String,
+    pub volume: String,
+    pub close_time: i64,
+}
+
+/// Best bid/ask
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceBookTicker {
+    pub symbol: String,
+    pub bid_price: String,
+    pub bid_qty: String,
+    pub ask_price: String,
+    pub ask_qty: String,
+}
+
+/// Order book snapshot; levels are `[price, quantity]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceDepth {
+    pub last_update_id: u64,
+    pub bids: Vec<[String; 2]>,
+    pub asks: Vec<[String; 2]>,
+}
+
+/// Public trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceTrade {
+    pub id: u64,
+    pub price: String,
+    pub qty: String,
+    pub time: i64,
+    pub is_buyer_maker: bool,
+}
+
+/// Kline row: open time, OHLCV, close time, quote volume, trade count, ...
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceKline(
+    pub i64,
+    pub String,
+    pub String,
+    pub String,
+    pub String,
+    pub String,
+    pub i64,
+    pub String,
+    pub u64,
+    pub String,
+    pub String,
+    pub serde_json::Value,
+);
+
+/// Mark price and current funding (USDⓈ-M)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinancePremiumIndex {
+    pub symbol: String,
+    pub mark_price: String,
+    pub last_funding_rate: String,
+    pub next_funding_time: i64,
+    pub time: i64,
+}
+
+/// Historical funding rate (USDⓈ-M)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceFundingRate {
+    pub symbol: String,
+    pub funding_rate: String,
+    pub funding_time: i64,
+}
+
+// ============ Trading ============
+
+/// Order as returned by the order endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceOrder {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub price: String,
+    pub orig_qty: String,
+    pub executed_qty: String,
+    #[serde(default, alias = "cumQuote")]
+    pub cummulative_quote_qty: Option<String>,
+    #[serde(default)]
+    pub avg_price: Option<String>, // futures only
+    pub status: String,
+    pub time_in_force: String,
+    #[serde(rename = "type")]
+    pub order_type: String,
+    pub side: String,
+    #[serde(default)]
+    pub stop_price: Option<String>,
+    #[serde(default)]
+    pub reduce_only: Option<bool>, // futures only
+    #[serde(default)]
+    pub position_side: Option<String>, // futures only
+    #[serde(default)]
+    pub time: Option<i64>,
+    #[serde(default)]
+    pub update_time: Option<i64>,
+    #[serde(default)]
+    pub transact_time: Option<i64>, // set on new/cancel responses
+}
+
+/// Response of the spot cancel-replace endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceCancelReplace {
+    pub cancel_result: String,
+    pub new_order_result: String,
+    pub new_order_response: Option<BinanceOrder>,
+}
+
+// ============ Account ============
+
+/// Spot account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceSpotAccount {
+    pub maker_commission: i64, // basis points
+    pub taker_commission: i64, // basis points
+    pub can_trade: bool,
+    pub can_withdraw: bool,
+    pub can_deposit: bool,
+    pub update_time: i64,
+    pub balances: Vec<BinanceSpotBalance>,
+}
+
+/// Spot asset balance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceSpotBalance {
+    pub asset: String,
+    pub free: String,
+    pub locked: String,
+}
+
+/// USDⓈ-M futures account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceFuturesAccount {
+    pub can_trade: bool,
+    pub can_withdraw: bool,
+    pub can_deposit: bool,
+    pub update_time: i64,
+    pub assets: Vec<BinanceFuturesAsset>,
+}
+
+/// USDⓈ-M futures margin asset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceFuturesAsset {
+    pub asset: String,
+    pub wallet_balance: String,
+    pub available_balance: String,
+}
+
+/// USDⓈ-M futures position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinancePositionRisk {
+    pub symbol: String,
+    pub position_amt: String, // signed in one-way mode
+    pub entry_price: String,
+    pub mark_price: String,
+    pub un_realized_profit: String,
+    pub liquidation_price: String,
+    pub leverage: String,
+    pub margin_type: String, // "cross" or "isolated"
+    pub isolated_margin: String,
+    pub position_side: String, // "BOTH", "LONG", "SHORT"
+    pub notional: String,
+    #[serde(default)]
+    pub update_time: i64,
+}
+
+/// Trade of the account (`myTrades` / `userTrades`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceUserTrade {
+    pub symbol: String,
+    pub id: u64,
+    pub order_id: u64,
+    pub price: String,
+    pub qty: String,
+    pub commission: String,
+    pub commission_asset: String,
+    pub time: i64,
+    #[serde(alias = "buyer")]
+    pub is_buyer: bool,
+    #[serde(alias = "maker")]
+    pub is_maker: bool,
+}
+
+/// Response of listen key creation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinanceListenKey {
+    pub listen_key: String,
+}
+
+// ============ WebSocket ============
+
+/// Live subscription request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsSubscribe {
+    pub method: String,
+    pub params: Vec<String>,
+    pub id: u64,
+}
+
+/// Stream event, tagged by its `e` field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "e")]
+pub enum BinanceWsEvent {
+    #[serde(rename = "trade")]
+    Trade(BinanceWsTrade),
+    #[serde(rename = "aggTrade")]
+    AggTrade(BinanceWsAggTrade),
+    #[serde(rename = "depthUpdate")]
+    DepthUpdate(BinanceWsDepth),
+    #[serde(rename = "24hrTicker")]
+    Ticker(BinanceWsTicker),
+    #[serde(rename = "bookTicker")]
+    BookTicker(BinanceWsBookTicker),
+    #[serde(rename = "kline")]
+    Kline(BinanceWsKlineEvent),
+    #[serde(rename = "executionReport")]
+    ExecutionReport(BinanceWsExecutionReport),
+    #[serde(rename = "outboundAccountPosition")]
+    AccountPosition(BinanceWsAccountPosition),
+    #[serde(rename = "ORDER_TRADE_UPDATE")]
+    OrderTradeUpdate(BinanceWsOrderTradeUpdate),
+    #[serde(rename = "ACCOUNT_UPDATE")]
+    AccountUpdate(BinanceWsAccountUpdate),
+    #[serde(rename = "ACCOUNT_CONFIG_UPDATE")]
+    AccountConfigUpdate(BinanceWsAccountConfigUpdate),
+    #[serde(rename = "listenKeyExpired")]
+    ListenKeyExpired,
+    #[serde(other)]
+    Other,
+}
+
+/// Trade (spot)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsTrade {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "t")]
+    pub trade_id: u64,
+    #[serde(rename = "p")]
+    pub price: String,
+    #[serde(rename = "q")]
+    pub quantity: String,
+    #[serde(rename = "T")]
+    pub trade_time: i64,
+    #[serde(rename = "m")]
+    pub buyer_maker: bool,
+}
+
+/// Trades of one taker order at one price (USDⓈ-M)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsAggTrade {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "a")]
+    pub trade_id: u64,
+    #[serde(rename = "p")]
+    pub price: String,
+    #[serde(rename = "q")]
+    pub quantity: String,
+    #[serde(rename = "T")]
+    pub trade_time: i64,
+    #[serde(rename = "m")]
+    pub buyer_maker: bool,
+}
+
+/// Diff-depth update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsDepth {
+    #[serde(rename = "E")]
+    pub event_time: i64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "U")]
+    pub first_update_id: u64,
+    #[serde(rename = "u")]
+    pub final_update_id: u64,
+    /// Final update id of the previous event (futures only)
+    #[serde(rename = "pu", default)]
+    pub prev_final_update_id: Option<u64>,
+    #[serde(rename = "b")]
+    pub bids: Vec<[String; 2]>,
+    #[serde(rename = "a")]
+    pub asks: Vec<[String; 2]>,
+}
+
+/// 24h rolling ticker (spot also carries the best bid/ask)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsTicker {
+    #[serde(rename = "E")]
+    pub event_time: i64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "p")]
+    pub price_change: String,
+    #[serde(rename = "P")]
+    pub price_change_percent: String,
+    #[serde(rename = "c")]
+    pub last_price: String,
+    #[serde(rename = "b", default)]
+    pub bid_price: Option<String>,
+    #[serde(rename = "B", default)]
+    pub bid_qty: Option<String>,
+    #[serde(rename = "a", default)]
+    pub ask_price: Option<String>,
+    #[serde(rename = "A", default)]
+    pub ask_qty: Option<String>,
+    #[serde(rename = "h")]
+    pub high_price: String,
+    #[serde(rename = "l")]
+    pub low_price: String,
+    #[serde(rename = "v")]
+    pub volume: String,
+}
+
+/// Best bid/ask (futures `bookTicker`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsBookTicker {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "b")]
+    pub bid_price: String,
+    #[serde(rename = "B")]
+    pub bid_qty: String,
+    #[serde(rename = "a")]
+    pub ask_price: String,
+    #[serde(rename = "A")]
+    pub ask_qty: String,
+}
+
+/// Kline event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsKlineEvent {
+    #[serde(rename = "k")]
+    pub kline: BinanceWsKline,
+}
+
+/// Kline in progress (`closed` once the interval has ended)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsKline {
+    #[serde(rename = "t")]
+    pub open_time: i64,
+    #[serde(rename = "T")]
+    pub close_time: i64,
+    #[serde(rename = "o")]
+    pub open: String,
+    #[serde(rename = "h")]
+    pub high: String,
+    #[serde(rename = "l")]
+    pub low: String,
+    #[serde(rename = "c")]
+    pub close: String,
+    #[serde(rename = "v")]
+    pub volume: String,
+    #[serde(rename = "q")]
+    pub quote_volume: String,
+    #[serde(rename = "n")]
+    pub trade_count: u64,
+    #[serde(rename = "x")]
+    pub closed: bool,
+}
+
+/// Spot order update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsExecutionReport {
+    #[serde(rename = "E")]
+    pub event_time: i64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+    /// Original client order id when the event is a cancel
+    #[serde(rename = "C", default)]
+    pub orig_client_order_id: String,
+    #[serde(rename = "S")]
+    pub side: String,
+    #[serde(rename = "o")]
+    pub order_type: String,
+    #[serde(rename = "f")]
+    pub time_in_force: String,
+    #[serde(rename = "q")]
+    pub quantity: String,
+    #[serde(rename = "p")]
+    pub price: String,
+    #[serde(rename = "P")]
+    pub stop_price: String,
+    #[serde(rename = "X")]
+    pub status: String,
+    #[serde(rename = "i")]
+    pub order_id: u64,
+    #[serde(rename = "z")]
+    pub filled_quantity: String,
+    #[serde(rename = "Z")]
+    pub filled_quote: String,
+    #[serde(rename = "O")]
+    pub created_time: i64,
+    #[serde(rename = "T")]
+    pub transaction_time: i64,
+}
+
+/// Spot balances changed by an account event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsAccountPosition {
+    #[serde(rename = "E")]
+    pub event_time: i64,
+    #[serde(rename = "B")]
+    pub balances: Vec<BinanceWsSpotBalance>,
+}
+
+/// Spot balance in an account event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsSpotBalance {
+    #[serde(rename = "a")]
+    pub asset: String,
+    #[serde(rename = "f")]
+    pub free: String,
+    #[serde(rename = "l")]
+    pub locked: String,
+}
+
+/// Futures order update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsOrderTradeUpdate {
+    #[serde(rename = "E")]
+    pub event_time: i64,
+    #[serde(rename = "o")]
+    pub order: BinanceWsFuturesOrder,
+}
+
+/// Order in a futures order update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsFuturesOrder {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+    #[serde(rename = "S")]
+    pub side: String,
+    #[serde(rename = "o")]
+    pub order_type: String,
+    #[serde(rename = "f")]
+    pub time_in_force: String,
+    #[serde(rename = "q")]
+    pub quantity: String,
+    #[serde(rename = "p")]
+    pub price: String,
+    #[serde(rename = "ap")]
+    pub average_price: String,
+    #[serde(rename = "sp")]
+    pub stop_price: String,
+    #[serde(rename = "X")]
+    pub status: String,
+    #[serde(rename = "i")]
+    pub order_id: u64,
+    #[serde(rename = "z")]
+    pub filled_quantity: String,
+    #[serde(rename = "R")]
+    pub reduce_only: bool,
+    #[serde(rename = "ps")]
+    pub position_side: String,
+    #[serde(rename = "T")]
+    pub transaction_time: i64,
+}
+
+/// Futures balance and position changes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsAccountUpdate {
+    #[serde(rename = "E")]
+    pub event_time: i64,
+    #[serde(rename = "a")]
+    pub data: BinanceWsAccountData,
+}
+
+/// Payload of a futures account update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsAccountData {
+    #[serde(rename = "B", default)]
+    pub balances: Vec<BinanceWsFuturesBalance>,
+    #[serde(rename = "P", default)]
+    pub positions: Vec<BinanceWsFuturesPosition>,
+}
+
+/// Futures balance in an account update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsFuturesBalance {
+    #[serde(rename = "a")]
+    pub asset: String,
+    #[serde(rename = "wb")]
+    pub wallet_balance: String,
+    #[serde(rename = "cw")]
+    pub cross_wallet_balance: String,
+}
+
+/// Futures position in an account update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsFuturesPosition {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "pa")]
+    pub position_amt: String,
+    #[serde(rename = "ep")]
+    pub entry_price: String,
+    #[serde(rename = "up")]
+    pub unrealized_pnl: String,
+    #[serde(rename = "mt")]
+    pub margin_type: String,
+    #[serde(rename = "iw")]
+    pub isolated_wallet: String,
+    #[serde(rename = "ps")]
+    pub position_side: String,
+}
+
+/// Futures leverage change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsAccountConfigUpdate {
+    #[serde(rename = "ac", default)]
+    pub config: Option<BinanceWsLeverage>,
+}
+
+/// Leverage of a symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceWsLeverage {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "l")]
+    pub leverage: u32,
+}
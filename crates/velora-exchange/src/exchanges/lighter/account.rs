@@ -1,71 +1,198 @@
 //! Lighter account implementation
 
 use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use std::sync::Arc;
 
 use crate::{
-    auth::EvmWalletAuth,
     common::{RateLimiter, RestClient},
     traits::Account,
-    types::{AccountInfo, Balance, Position, Result, Symbol, TradeExecution},
+    types::{
+        AccountInfo, AccountType, Balance, ExchangeError, MarginType, Position, PositionSide,
+        Price, Result, Side, Symbol, TradeExecution,
+    },
 };
 
+use super::{
+    endpoints,
+    markets::{parse_decimal, LighterMarkets},
+    signer::LighterSigner,
+    types::*,
+};
+
+/// Lighter accounts are margined in USDC
+pub(crate) const COLLATERAL_ASSET: &str = "USDC";
+
 /// Lighter account component
 pub struct LighterAccount {
     rest_client: Arc<RestClient>,
     rate_limiter: Arc<RateLimiter>,
-    auth: Option<EvmWalletAuth>,
+    signer: Option<Arc<LighterSigner>>,
+    markets: Arc<LighterMarkets>,
 }
 
 impl LighterAccount {
-    pub fn new(
+    pub(crate) fn new(
         rest_client: Arc<RestClient>,
         rate_limiter: Arc<RateLimiter>,
-        auth: Option<EvmWalletAuth>,
+        signer: Option<Arc<LighterSigner>>,
+        markets: Arc<LighterMarkets>,
     ) -> Self {
         Self {
             rest_client,
             rate_limiter,
-            auth,
+            signer,
+            markets,
         }
     }
+
+    /// Signer, or an error in read-only mode
+    fn signer(&self) -> Result<&LighterSigner> {
+        self.signer.as_deref().ok_or_else(|| {
+            ExchangeError::Authentication(
+                "Lighter account access requires an EVM wallet".to_string(),
+            )
+        })
+    }
+
+    /// Fetch the account details (balances and positions)
+    async fn fetch_account(&self) -> Result<LighterAccountDetails> {
+        let account_index = self.signer()?.account_index().await?;
+        let endpoint = format!("{}?by=index&value={}", endpoints::ACCOUNT, account_index);
+
+        self.rate_limiter.wait().await;
+        let response: LighterApiResponse<AccountData> = self.rest_client.get(&endpoint).await?;
+
+        response
+            .data
+            .accounts
+            .into_iter()
+            .next()
+            .ok_or_else(|| ExchangeError::Internal(format!("Account {account_index} not found")))
+    }
+
+    /// Convert a Lighter position to our Position type (quantity is zero if flat)
+    pub(crate) fn convert_position(position: LighterPosition) -> Result<Position> {
+        let quantity = parse_decimal(&position.position, "position size")?;
+
+        let to_price = |s: &str, what: &str| -> Result<Price> {
+            Ok(Price::from(
+                parse_decimal(s, what)?.to_f64().unwrap_or_default(),
+            ))
+        };
+
+        let value = parse_decimal(&position.position_value, "position value")?;
+        let margin_fraction =
+            parse_decimal(&position.initial_margin_fraction, "initial margin fraction")?
+                / Decimal::ONE_HUNDRED;
+        let leverage = if margin_fraction > Decimal::ZERO {
+            (Decimal::ONE / margin_fraction)
+                .round()
+                .to_u32()
+                .unwrap_or(1)
+        } else {
+            1
+        };
+
+        let margin_type = if position.margin_mode == 1 {
+            MarginType::Isolated
+        } else {
+            MarginType::Cross
+        };
+        let margin = match margin_type {
+            MarginType::Isolated => parse_decimal(&position.allocated_margin, "allocated margin")?,
+            MarginType::Cross => value.abs() * margin_fraction,
+        };
+
+        let liquidation_price = to_price(&position.liquidation_price, "liquidation price")?;
+        let entry_price = to_price(&position.avg_entry_price, "entry price")?;
+
+        let mark_price = if quantity.is_zero() {
+            entry_price
+        } else {
+            Price::from((value / quantity).abs().to_f64().unwrap_or_default())
+        };
+
+        Ok(Position {
+            symbol: Symbol::from(position.symbol.as_str()),
+            side: if position.sign < 0 {
+                PositionSide::Short
+            } else {
+                PositionSide::Long
+            },
+            quantity: quantity.abs(),
+            entry_price,
+            mark_price,
+            liquidation_price: (liquidation_price.into_inner() > 0.0).then_some(liquidation_price),
+            leverage,
+            unrealized_pnl: parse_decimal(&position.unrealized_pnl, "unrealized pnl")?,
+            realized_pnl: parse_decimal(&position.realized_pnl, "realized pnl")?,
+            margin,
+            margin_type,
+            update_time: Utc::now(),
+        })
+    }
 }
 
 #[async_trait]
 impl Account for LighterAccount {
     async fn get_account_info(&self) -> Result<AccountInfo> {
-        self.rate_limiter.wait().await;
-
-        // TODO: Implement account info retrieval
-        todo!("Implement Lighter get_account_info")
+        let account = self.fetch_account().await?;
+
+        Ok(AccountInfo {
+            account_type: AccountType::Futures,
+            can_trade: account.status == 1,
+            can_withdraw: true,
+            can_deposit: true,
+            // Standard Lighter accounts trade without fees
+            maker_commission: Decimal::ZERO,
+            taker_commission: Decimal::ZERO,
+            update_time: Utc::now(),
+        })
     }
 
     async fn get_balances(&self) -> Result<Vec<Balance>> {
-        self.rate_limiter.wait().await;
+        let account = self.fetch_account().await?;
+
+        let collateral = parse_decimal(&account.collateral, "collateral")?;
+        let available = parse_decimal(&account.available_balance, "available balance")?;
 
-        // TODO: Implement balances retrieval
-        todo!("Implement Lighter get_balances")
+        Ok(vec![Balance {
+            asset: COLLATERAL_ASSET.to_string(),
+            free: available,
+            locked: (collateral - available).max(Decimal::ZERO),
+        }])
     }
 
     async fn get_balance(&self, asset: &str) -> Result<Balance> {
-        self.rate_limiter.wait().await;
-
-        // TODO: Implement single balance retrieval
-        todo!("Implement Lighter get_balance for {}", asset)
+        self.get_balances()
+            .await?
+            .into_iter()
+            .find(|b| b.asset.eq_ignore_ascii_case(asset))
+            .ok_or_else(|| ExchangeError::InvalidRequest(format!("No {asset} balance on Lighter")))
     }
 
     async fn get_positions(&self) -> Result<Vec<Position>> {
-        self.rate_limiter.wait().await;
-
-        // TODO: Implement positions retrieval
-        todo!("Implement Lighter get_positions")
+        let account = self.fetch_account().await?;
+
+        let mut positions = Vec::new();
+        for position in account.positions {
+            let position = Self::convert_position(position)?;
+            if !position.quantity.is_zero() {
+                positions.push(position);
+            }
+        }
+        Ok(positions)
     }
 
     async fn get_position(&self, symbol: &Symbol) -> Result<Option<Position>> {
-        self.rate_limiter.wait().await;
-
-        // TODO: Implement single position retrieval
-        todo!("Implement Lighter get_position for {}", symbol)
+        Ok(self
+            .get_positions()
+            .await?
+            .into_iter()
+            .find(|p| p.symbol == *symbol))
     }
 
     async fn get_trade_history(
@@ -73,9 +200,55 @@ impl Account for LighterAccount {
         symbol: Option<&Symbol>,
         limit: Option<usize>,
     ) -> Result<Vec<TradeExecution>> {
+        let signer = self.signer()?;
+        let account_index = signer.account_index().await?;
+
+        let mut endpoint = format!(
+            "{}?account_index={}&sort_by=timestamp&sort_dir=desc&limit={}&auth={}",
+            endpoints::TRADES,
+            account_index,
+            limit.unwrap_or(100),
+            signer.auth_token().await?
+        );
+        if let Some(symbol) = symbol {
+            let market = self.markets.get(symbol).await?;
+            endpoint.push_str(&format!("&market_id={}", market.market_id));
+        }
+
         self.rate_limiter.wait().await;
+        let response: LighterApiResponse<AccountTradesData> =
+            self.rest_client.get(&endpoint).await?;
+
+        let mut executions = Vec::with_capacity(response.data.trades.len());
+        for trade in response.data.trades {
+            let market = self.markets.by_id(trade.market_id).await?;
+            let price = parse_decimal(&trade.price, "trade price")?;
+            let quantity = parse_decimal(&trade.size, "trade size")?;
+
+            let is_ask = trade.ask_account_id == account_index;
+            let is_maker = is_ask == trade.is_maker_ask;
+            let fee_rate = if is_maker {
+                trade.maker_fee
+            } else {
+                trade.taker_fee
+            };
+            let fee =
+                Decimal::from(fee_rate.unwrap_or(0)) / Decimal::from(1_000_000) * price * quantity;
+
+            executions.push(TradeExecution {
+                trade_id: trade.trade_id.to_string(),
+                order_id: if is_ask { trade.ask_id } else { trade.bid_id }.to_string(),
+                symbol: market.symbol,
+                side: if is_ask { Side::Sell } else { Side::Buy },
+                price: Price::from(price.to_f64().unwrap_or_default()),
+                quantity,
+                fee,
+                fee_asset: COLLATERAL_ASSET.to_string(),
+                is_maker,
+                timestamp: trade.timestamp,
+            });
+        }
 
-        // TODO: Implement trade history retrieval
-        todo!("Implement Lighter get_trade_history")
+        Ok(executions)
     }
 }
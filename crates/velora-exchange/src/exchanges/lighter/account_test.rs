@@ -0,0 +1,329 @@
+//! Tests for Lighter account implementation
+
+#[cfg(test)]
+mod tests {
+    use super::super::account::*;
+    use super::super::markets::LighterMarkets;
+    use super::super::signer::LighterSigner;
+
+    use crate::auth::EvmWalletAuth;
+    use crate::common::{RateLimiter, RestClient};
+    use crate::traits::Account;
+    use crate::types::{AccountType, MarginType, PositionSide, Price, Side, Symbol};
+
+    use mockito::{Matcher, Mock, Server};
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+
+    const TEST_PRIVATE_KEY: &str =
+        "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    const TEST_ADDRESS: &str = "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23";
+    const ACCOUNT_INDEX: i64 = 42;
+
+    const ACCOUNT: &str = r#"{
+        "code": 200,
+        "total": 1,
+        "accounts": [
+            {
+                "code": 0,
+                "account_type": 0,
+                "index": 42,
+                "l1_address": "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23",
+                "cancel_all_time": 0,
+                "total_order_count": 3,
+                "pending_order_count": 0,
+                "status": 1,
+                "collateral": "10000.000000",
+                "available_balance": "8500.000000",
+                "total_asset_value": "10125.500000",
+                "cross_asset_value": "10125.500000",
+                "positions": [
+                    {
+                        "market_id": 0,
+                        "symbol": "ETH",
+                        "initial_margin_fraction": "20.00",
+                        "open_order_count": 1,
+                        "pending_order_count": 0,
+                        "position_tied_order_count": 0,
+                        "sign": 1,
+                        "position": "2.0000",
+                        "avg_entry_price": "3000.00",
+                        "position_value": "6100.000000",
+                        "unrealized_pnl": "100.000000",
+                        "realized_pnl": "25.500000",
+                        "liquidation_price": "2400.00",
+                        "margin_mode": 0,
+                        "allocated_margin": "0.000000"
+                    },
+                    {
+                        "market_id": 1,
+                        "symbol": "BTC",
+                        "initial_margin_fraction": "10.00",
+                        "open_order_count": 0,
+                        "pending_order_count": 0,
+                        "position_tied_order_count": 0,
+                        "sign": -1,
+                        "position": "0.05000",
+                        "avg_entry_price": "60000.0",
+                        "position_value": "3000.000000",
+                        "unrealized_pnl": "0.000000",
+                        "realized_pnl": "0.000000",
+                        "liquidation_price": "0",
+                        "margin_mode": 1,
+                        "allocated_margin": "400.000000"
+                    },
+                    {
+                        "market_id": 2,
+                        "symbol": "SOL",
+                        "initial_margin_fraction": "10.00",
+                        "open_order_count": 0,
+                        "pending_order_count": 0,
+                        "position_tied_order_count": 0,
+                        "sign": 1,
+                        "position": "0.000",
+                        "avg_entry_price": "0.000",
+                        "position_value": "0.000000",
+                        "unrealized_pnl": "0.000000",
+                        "realized_pnl": "-12.000000",
+                        "liquidation_price": "0",
+                        "margin_mode": 0,
+                        "allocated_margin": "0.000000"
+                    }
+                ]
+            }
+        ]
+    }"#;
+
+    const ORDER_BOOKS: &str = r#"{
+        "code": 200,
+        "order_books": [
+            {
+                "symbol": "ETH",
+                "market_id": 0,
+                "status": "active",
+                "taker_fee": "0.0000",
+                "maker_fee": "0.0000",
+                "liquidation_fee": "1.0000",
+                "min_base_amount": "0.0050",
+                "min_quote_amount": "10.000000",
+                "order_quote_limit": "",
+                "supported_size_decimals": 4,
+                "supported_price_decimals": 2,
+                "supported_quote_decimals": 6
+            }
+        ]
+    }"#;
+
+    /// Helper to create an account component with mocked server
+    async fn setup_test(server: &Server, account_index: Option<i64>) -> LighterAccount {
+        let rest_client =
+            Arc::new(RestClient::new(server.url(), std::time::Duration::from_secs(30)).unwrap());
+        let rate_limiter = Arc::new(RateLimiter::new(100, std::time::Duration::from_secs(1)));
+
+        let signer = Arc::new(LighterSigner::new(
+            Arc::clone(&rest_client),
+            Arc::clone(&rate_limiter),
+            EvmWalletAuth::new(TEST_PRIVATE_KEY),
+        ));
+        if let Some(index) = account_index {
+            signer.set_account_index(index).await;
+        }
+
+        let markets = Arc::new(LighterMarkets::new(
+            Arc::clone(&rest_client),
+            Arc::clone(&rate_limiter),
+        ));
+
+        LighterAccount::new(rest_client, rate_limiter, Some(signer), markets)
+    }
+
+    async fn mock_account(server: &mut Server) -> Mock {
+        server
+            .mock("GET", "/api/v1/account")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("by".into(), "index".into()),
+                Matcher::UrlEncoded("value".into(), ACCOUNT_INDEX.to_string()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ACCOUNT)
+            .create_async()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_account_index_resolved_from_l1_address() {
+        let mut server = Server::new_async().await;
+        let lookup = server
+            .mock("GET", "/api/v1/accountsByL1Address")
+            .match_query(Matcher::UrlEncoded("l1_address".into(), TEST_ADDRESS.into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(
+                r#"{{
+                    "code": 200,
+                    "l1_address": "{TEST_ADDRESS}",
+                    "sub_accounts": [
+                        {{"code": 0, "account_type": 0, "index": 42, "l1_address": "{TEST_ADDRESS}"}}
+                    ]
+                }}"#
+            ))
+            .expect(1)
+            .create_async()
+            .await;
+        let _account = mock_account(&mut server).await;
+
+        let account = setup_test(&server, None).await;
+
+        // Second call uses the cached index
+        assert!(account.get_account_info().await.unwrap().can_trade);
+        account.get_balances().await.unwrap();
+
+        lookup.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_account_info_and_balances() {
+        let mut server = Server::new_async().await;
+        let _account = mock_account(&mut server).await;
+
+        let account = setup_test(&server, Some(ACCOUNT_INDEX)).await;
+
+        let info = account.get_account_info().await.unwrap();
+        assert_eq!(info.account_type, AccountType::Futures);
+        assert!(info.can_trade);
+
+        let balances = account.get_balances().await.unwrap();
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].asset, "USDC");
+        assert_eq!(balances[0].free, dec!(8500));
+        assert_eq!(balances[0].locked, dec!(1500));
+        assert_eq!(balances[0].total(), dec!(10000));
+
+        assert_eq!(account.get_balance("usdc").await.unwrap().free, dec!(8500));
+        assert!(account.get_balance("ETH").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_positions() {
+        let mut server = Server::new_async().await;
+        let _account = mock_account(&mut server).await;
+
+        let account = setup_test(&server, Some(ACCOUNT_INDEX)).await;
+        let positions = account.get_positions().await.unwrap();
+
+        // Flat SOL position is skipped
+        assert_eq!(positions.len(), 2);
+
+        let eth = &positions[0];
+        assert_eq!(eth.symbol, Symbol::from("ETH"));
+        assert_eq!(eth.side, PositionSide::Long);
+        assert_eq!(eth.quantity, dec!(2));
+        assert_eq!(eth.entry_price, Price::from(3000.0));
+        assert_eq!(eth.mark_price, Price::from(3050.0));
+        assert_eq!(eth.liquidation_price, Some(Price::from(2400.0)));
+        assert_eq!(eth.leverage, 5);
+        assert_eq!(eth.margin_type, MarginType::Cross);
+        assert_eq!(eth.margin, dec!(1220));
+        assert_eq!(eth.unrealized_pnl, dec!(100));
+
+        let btc = &positions[1];
+        assert!(btc.is_short());
+        assert_eq!(btc.leverage, 10);
+        assert_eq!(btc.margin_type, MarginType::Isolated);
+        assert_eq!(btc.margin, dec!(400));
+        assert_eq!(btc.liquidation_price, None);
+
+        let sol = account.get_position(&Symbol::from("SOL")).await.unwrap();
+        assert!(sol.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_trade_history() {
+        let mut server = Server::new_async().await;
+        let _books = server
+            .mock("GET", "/api/v1/orderBooks")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ORDER_BOOKS)
+            .create_async()
+            .await;
+
+        let _trades = server
+            .mock("GET", "/api/v1/trades")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("account_index".into(), ACCOUNT_INDEX.to_string()),
+                Matcher::UrlEncoded("market_id".into(), "0".into()),
+                Matcher::UrlEncoded("limit".into(), "2".into()),
+                Matcher::Regex("auth=".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                "code": 200,
+                "next_cursor": "",
+                "trades": [
+                    {
+                        "trade_id": 9001,
+                        "tx_hash": "0a1b",
+                        "type": "trade",
+                        "market_id": 0,
+                        "size": "0.5000",
+                        "price": "3000.00",
+                        "usd_amount": "1500.000000",
+                        "ask_id": 281474976710600,
+                        "bid_id": 281474976710700,
+                        "ask_account_id": 42,
+                        "bid_account_id": 77,
+                        "is_maker_ask": true,
+                        "block_height": 38100,
+                        "taker_fee": 200,
+                        "maker_fee": 20,
+                        "timestamp": 1729000000000
+                    },
+                    {
+                        "trade_id": 9002,
+                        "tx_hash": "0a1c",
+                        "type": "trade",
+                        "market_id": 0,
+                        "size": "1.0000",
+                        "price": "3010.00",
+                        "usd_amount": "3010.000000",
+                        "ask_id": 281474976710800,
+                        "bid_id": 281474976710657,
+                        "ask_account_id": 77,
+                        "bid_account_id": 42,
+                        "is_maker_ask": true,
+                        "block_height": 38200,
+                        "timestamp": 1729000100000
+                    }
+                ]
+            }"#,
+            )
+            .create_async()
+            .await;
+
+        let account = setup_test(&server, Some(ACCOUNT_INDEX)).await;
+        let trades = account
+            .get_trade_history(Some(&Symbol::from("ETH")), Some(2))
+            .await
+            .unwrap();
+
+        assert_eq!(trades.len(), 2);
+
+        // We were the resting ask
+        assert_eq!(trades[0].side, Side::Sell);
+        assert!(trades[0].is_maker);
+        assert_eq!(trades[0].order_id, "281474976710600");
+        assert_eq!(trades[0].fee, dec!(0.03)); // 20 ppm of 1,500
+        assert_eq!(trades[0].fee_asset, "USDC");
+
+        // We lifted the ask as taker, no fee reported
+        assert_eq!(trades[1].side, Side::Buy);
+        assert!(!trades[1].is_maker);
+        assert_eq!(trades[1].order_id, "281474976710657");
+        assert_eq!(trades[1].fee, dec!(0));
+        assert_eq!(trades[1].symbol, Symbol::from("ETH"));
+    }
+}
@@ -14,8 +14,9 @@ use crate::{
 };
 
 use super::{
-    account::LighterAccount, endpoints, market_data::LighterMarketData,
-    streaming::LighterStreaming, trading::LighterTrading, SUPPORTED_INSTRUMENTS,
+    account::LighterAccount, endpoints, market_data::LighterMarketData, markets::LighterMarkets,
+    signer::LighterSigner, streaming::LighterStreaming, trading::LighterTrading,
+    SUPPORTED_INSTRUMENTS,
 };
 
 /// Lighter Exchange implementation
@@ -32,6 +33,9 @@ pub struct LighterExchange {
     /// Authentication
     auth: Option<EvmWalletAuth>,
 
+    /// Transaction signer (None in read-only mode)
+    signer: Option<Arc<LighterSigner>>,
+
     /// Connection status
     connected: bool,
 
@@ -74,10 +78,19 @@ impl LighterExchange {
         ws_host: String,
         auth: Option<AuthConfig>,
     ) -> Result<Self> {
-        let ws_url = format!("wss://{ws_host}");
+        let ws_url = format!("wss://{ws_host}/stream");
         Self::new_with_urls_sync(api_url, ws_url, auth)
     }
 
+    /// Trade from a specific sub-account instead of the first account owned by the wallet
+    pub async fn set_account_index(&self, account_index: i64) -> Result<()> {
+        let signer = self.signer.as_ref().ok_or_else(|| {
+            ExchangeError::Authentication("Lighter read-only mode has no account".to_string())
+        })?;
+        signer.set_account_index(account_index).await;
+        Ok(())
+    }
+
     /// Internal constructor with custom URLs (async version)
     async fn new_with_urls(
         rest_base_url: String,
@@ -115,25 +128,41 @@ impl LighterExchange {
         let market_data =
             LighterMarketData::new(Arc::clone(&rest_client), Arc::clone(&rate_limiter));
 
+        let markets = Arc::new(LighterMarkets::new(
+            Arc::clone(&rest_client),
+            Arc::clone(&rate_limiter),
+        ));
+
+        let signer = wallet_auth.clone().map(|wallet| {
+            Arc::new(LighterSigner::new(
+                Arc::clone(&rest_client),
+                Arc::clone(&rate_limiter),
+                wallet,
+            ))
+        });
+
         let trading = LighterTrading::new(
             Arc::clone(&rest_client),
             Arc::clone(&rate_limiter),
-            wallet_auth.clone(),
+            signer.clone(),
+            Arc::clone(&markets),
         );
 
         let account = LighterAccount::new(
             Arc::clone(&rest_client),
             Arc::clone(&rate_limiter),
-            wallet_auth.clone(),
+            signer.clone(),
+            Arc::clone(&markets),
         );
 
-        let streaming = LighterStreaming::new(Arc::clone(&ws_client), wallet_auth.clone());
+        let streaming = LighterStreaming::new(Arc::clone(&ws_client), signer.clone(), markets);
 
         Ok(Self {
             rest_client,
             ws_client,
             rate_limiter,
             auth: wallet_auth,
+            signer,
             connected: false,
             market_data,
             trading,
@@ -0,0 +1,164 @@
+//! Lighter market metadata cache
+//!
+//! Orders are submitted with integer market indices and amounts scaled by each
+//! market's decimals, so trading and account components share this cache.
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+use tokio::sync::RwLock;
+
+use crate::{
+    common::{RateLimiter, RestClient},
+    types::{ExchangeError, Price, Result, Symbol},
+};
+
+use super::{endpoints, types::*};
+
+/// Market metadata needed to build transactions
+#[derive(Debug, Clone)]
+pub(crate) struct MarketInfo {
+    pub symbol: Symbol,
+    pub market_id: u64,
+    pub size_decimals: u32,
+    pub price_decimals: u32,
+}
+
+impl MarketInfo {
+    /// Convert a base quantity to Lighter's integer base amount
+    pub fn to_base_amount(&self, quantity: Decimal) -> Result<i64> {
+        scale(quantity, self.size_decimals, "quantity")
+    }
+
+    /// Convert a price to Lighter's integer price
+    pub fn to_price(&self, price: Price) -> Result<i64> {
+        // Go through the shortest decimal representation so 3012.345 stays 3012.345
+        let decimal = Decimal::from_str(&price.to_string())
+            .map_err(|e| ExchangeError::InvalidOrder(format!("Invalid price {price}: {e}")))?;
+        scale(decimal, self.price_decimals, "price")
+    }
+}
+
+/// Scale a decimal to an integer with `decimals` fractional digits
+fn scale(value: Decimal, decimals: u32, what: &str) -> Result<i64> {
+    let scaled = (value * Decimal::from(10u64.pow(decimals)))
+        .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero);
+    if scaled <= Decimal::ZERO {
+        return Err(ExchangeError::InvalidOrder(format!(
+            "{what} {value} rounds to zero at {decimals} decimals"
+        )));
+    }
+    scaled
+        .to_i64()
+        .ok_or_else(|| ExchangeError::InvalidOrder(format!("{what} {value} is out of range")))
+}
+
+/// Lazily populated map of Lighter markets
+pub(crate) struct LighterMarkets {
+    rest_client: Arc<RestClient>,
+    rate_limiter: Arc<RateLimiter>,
+    by_symbol: RwLock<HashMap<String, MarketInfo>>,
+}
+
+impl LighterMarkets {
+    pub fn new(rest_client: Arc<RestClient>, rate_limiter: Arc<RateLimiter>) -> Self {
+        Self {
+            rest_client,
+            rate_limiter,
+            by_symbol: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Fetch all markets from the exchange and replace the cache
+    async fn refresh(&self) -> Result<()> {
+        self.rate_limiter.wait().await;
+        let response: LighterApiResponse<OrderBooksData> =
+            self.rest_client.get(endpoints::ORDERBOOKS).await?;
+
+        let mut by_symbol = self.by_symbol.write().await;
+        by_symbol.clear();
+        for info in response.data.order_books {
+            by_symbol.insert(
+                info.symbol.clone(),
+                MarketInfo {
+                    symbol: Symbol::from(info.symbol.as_str()),
+                    market_id: info.market_id,
+                    size_decimals: info.supported_size_decimals,
+                    price_decimals: info.supported_price_decimals,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Look up a market by symbol
+    pub async fn get(&self, symbol: &Symbol) -> Result<MarketInfo> {
+        if let Some(info) = self.by_symbol.read().await.get(symbol.as_str()) {
+            return Ok(info.clone());
+        }
+
+        self.refresh().await?;
+        self.by_symbol
+            .read()
+            .await
+            .get(symbol.as_str())
+            .cloned()
+            .ok_or_else(|| ExchangeError::MarketNotFound(symbol.to_string()))
+    }
+
+    /// Look up a market by its numeric id
+    pub async fn by_id(&self, market_id: u64) -> Result<MarketInfo> {
+        let find = |map: &HashMap<String, MarketInfo>| {
+            map.values().find(|m| m.market_id == market_id).cloned()
+        };
+
+        if let Some(info) = find(&*self.by_symbol.read().await) {
+            return Ok(info);
+        }
+
+        self.refresh().await?;
+        find(&*self.by_symbol.read().await)
+            .ok_or_else(|| ExchangeError::MarketNotFound(format!("market_id {market_id}")))
+    }
+
+    /// Symbols of all markets, keyed by market id
+    pub async fn symbols(&self) -> Result<HashMap<u64, Symbol>> {
+        if self.by_symbol.read().await.is_empty() {
+            self.refresh().await?;
+        }
+        Ok(self
+            .by_symbol
+            .read()
+            .await
+            .values()
+            .map(|m| (m.market_id, m.symbol.clone()))
+            .collect())
+    }
+}
+
+/// Parse a decimal string from a Lighter response
+pub(crate) fn parse_decimal(s: &str, what: &str) -> Result<Decimal> {
+    Decimal::from_str(s)
+        .map_err(|e| ExchangeError::ParseError(format!("Failed to parse {what} '{s}': {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_scaling() {
+        let market = MarketInfo {
+            symbol: Symbol::from("ETH"),
+            market_id: 0,
+            size_decimals: 4,
+            price_decimals: 2,
+        };
+
+        assert_eq!(market.to_base_amount(dec!(0.5)).unwrap(), 5_000);
+        assert_eq!(market.to_price(Price::from(3012.345)).unwrap(), 301_235);
+        assert!(market.to_base_amount(dec!(0.00001)).is_err());
+    }
+}
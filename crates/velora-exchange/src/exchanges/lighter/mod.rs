@@ -6,12 +6,20 @@
 //! - EVM wallet authentication
 
 mod account;
+#[cfg(test)]
+mod account_test;
 mod client;
 mod market_data;
 #[cfg(test)]
 mod market_data_test;
+mod markets;
+mod signer;
 mod streaming;
+#[cfg(test)]
+mod streaming_test;
 mod trading;
+#[cfg(test)]
+mod trading_test;
 mod types;
 
 pub use client::LighterExchange;
@@ -22,7 +30,7 @@ use crate::types::InstrumentType;
 pub(crate) mod endpoints {
     // Mainnet configuration
     pub const REST_BASE_URL: &str = "https://mainnet.zklighter.elliot.ai";
-    pub const WS_BASE_URL: &str = "wss://mainnet.zklighter.elliot.ai";
+    pub const WS_BASE_URL: &str = "wss://mainnet.zklighter.elliot.ai/stream";
 
     // Market data endpoints (note: camelCase naming)
     pub const ORDERBOOKS: &str = "/api/v1/orderBooks";
@@ -38,6 +46,7 @@ pub(crate) mod endpoints {
     // Trading endpoints
     pub const SEND_TX: &str = "/api/v1/sendtx";
     pub const SEND_TX_BATCH: &str = "/api/v1/sendtxbatch";
+    pub const NEXT_NONCE: &str = "/api/v1/nextNonce";
     pub const ACCOUNT_ACTIVE_ORDERS: &str = "/api/v1/accountActiveOrders";
     pub const ACCOUNT_INACTIVE_ORDERS: &str = "/api/v1/accountInactiveOrders";
 
@@ -61,6 +70,35 @@ pub(crate) mod endpoints {
     pub const BLOCKS: &str = "/api/v1/blocks";
 }
 
+/// Lighter transaction types and order enums (as sent in `tx_info`)
+pub(crate) mod tx {
+    // Transaction types
+    pub const TYPE_CREATE_ORDER: u8 = 14;
+    pub const TYPE_CANCEL_ORDER: u8 = 15;
+    pub const TYPE_CANCEL_ALL_ORDERS: u8 = 16;
+    pub const TYPE_MODIFY_ORDER: u8 = 17;
+
+    // Order types
+    pub const ORDER_TYPE_LIMIT: u8 = 0;
+    pub const ORDER_TYPE_MARKET: u8 = 1;
+    pub const ORDER_TYPE_STOP_LOSS: u8 = 2;
+    pub const ORDER_TYPE_STOP_LOSS_LIMIT: u8 = 3;
+
+    // Time in force
+    pub const TIF_IMMEDIATE_OR_CANCEL: u8 = 0;
+    pub const TIF_GOOD_TILL_TIME: u8 = 1;
+    pub const TIF_POST_ONLY: u8 = 2;
+
+    // Cancel-all modes
+    pub const CANCEL_ALL_IMMEDIATE: u8 = 0;
+
+    /// Transactions expire if not included within this window
+    pub const TX_EXPIRY_MS: i64 = 10 * 60 * 1000;
+
+    /// Resting orders expire after 28 days (Lighter maximum)
+    pub const ORDER_EXPIRY_MS: i64 = 28 * 24 * 60 * 60 * 1000;
+}
+
 /// Supported instruments on Lighter
 pub(crate) const SUPPORTED_INSTRUMENTS: &[InstrumentType] =
     &[InstrumentType::Spot, InstrumentType::Perpetual];
@@ -0,0 +1,199 @@
+//! Lighter transaction signing and nonce tracking
+//!
+//! Every write (create, cancel, modify) is a signed L2 transaction posted to
+//! `sendTx`. Transactions carry a strictly increasing per-key nonce, which is
+//! fetched once from the exchange and then tracked locally. A rejected
+//! transaction clears the local nonce so the next one resyncs with the server.
+
+use chrono::Utc;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::{
+    auth::EvmWalletAuth,
+    common::{RateLimiter, RestClient},
+    types::{ExchangeError, Result},
+};
+
+use super::{endpoints, types::*};
+
+/// Auth tokens are valid for this long
+const AUTH_TOKEN_TTL_SECS: i64 = 10 * 60;
+
+/// Refresh auth tokens when less than this remains
+const AUTH_TOKEN_REFRESH_SECS: i64 = 60;
+
+/// Signs transactions and read-auth tokens for one Lighter account
+pub(crate) struct LighterSigner {
+    rest_client: Arc<RestClient>,
+    rate_limiter: Arc<RateLimiter>,
+    auth: EvmWalletAuth,
+    api_key_index: u8,
+    account_index: Mutex<Option<i64>>,
+    nonce: Mutex<Option<i64>>,
+    auth_token: Mutex<Option<(String, i64)>>,
+}
+
+impl LighterSigner {
+    pub fn new(
+        rest_client: Arc<RestClient>,
+        rate_limiter: Arc<RateLimiter>,
+        auth: EvmWalletAuth,
+    ) -> Self {
+        Self {
+            rest_client,
+            rate_limiter,
+            auth,
+            api_key_index: 0,
+            account_index: Mutex::new(None),
+            nonce: Mutex::new(None),
+            auth_token: Mutex::new(None),
+        }
+    }
+
+    /// API key index used for signing
+    pub fn api_key_index(&self) -> u8 {
+        self.api_key_index
+    }
+
+    /// Use a specific account index instead of looking it up by L1 address
+    pub async fn set_account_index(&self, account_index: i64) {
+        *self.account_index.lock().await = Some(account_index);
+    }
+
+    /// Account index owned by the wallet (looked up once by L1 address)
+    pub async fn account_index(&self) -> Result<i64> {
+        let mut cached = self.account_index.lock().await;
+        if let Some(index) = *cached {
+            return Ok(index);
+        }
+
+        let address = self
+            .auth
+            .address()
+            .map_err(|e| ExchangeError::Authentication(format!("Invalid EVM wallet: {e}")))?;
+        let endpoint = format!("{}?l1_address={:?}", endpoints::ACCOUNTS_BY_L1, address);
+
+        self.rate_limiter.wait().await;
+        let response: LighterApiResponse<AccountsByL1Data> =
+            self.rest_client.get(&endpoint).await?;
+
+        let index = response
+            .data
+            .sub_accounts
+            .first()
+            .map(|a| a.index)
+            .ok_or_else(|| {
+                ExchangeError::Authentication(format!("No Lighter account for {address:?}"))
+            })?;
+
+        debug!("Resolved Lighter account index {}", index);
+        *cached = Some(index);
+        Ok(index)
+    }
+
+    /// Reserve the next transaction nonce
+    pub async fn next_nonce(&self) -> Result<i64> {
+        let mut nonce = self.nonce.lock().await;
+
+        let next = match *nonce {
+            Some(n) => n,
+            None => {
+                let endpoint = format!(
+                    "{}?account_index={}&api_key_index={}",
+                    endpoints::NEXT_NONCE,
+                    self.account_index().await?,
+                    self.api_key_index
+                );
+
+                self.rate_limiter.wait().await;
+                let response: LighterApiResponse<NextNonceData> =
+                    self.rest_client.get(&endpoint).await?;
+                response.data.nonce
+            }
+        };
+
+        *nonce = Some(next + 1);
+        Ok(next)
+    }
+
+    /// Forget the local nonce so the next transaction fetches it again
+    pub async fn reset_nonce(&self) {
+        *self.nonce.lock().await = None;
+    }
+
+    /// Sign a transaction body and submit it
+    pub async fn send_tx<T: Serialize + Sync>(&self, tx_type: u8, tx: &T) -> Result<SendTxData> {
+        let tx_info = self.sign_tx(tx).await?;
+
+        self.rate_limiter.wait().await;
+        let form = [("tx_type", tx_type.to_string()), ("tx_info", tx_info)];
+        let result: Result<LighterApiResponse<SendTxData>> =
+            self.rest_client.post_form(endpoints::SEND_TX, &form).await;
+
+        match result {
+            Ok(response) if response.code == 200 => Ok(response.data),
+            Ok(response) => {
+                self.reset_nonce().await;
+                Err(ExchangeError::ApiError {
+                    code: response.code,
+                    message: response.data.message.unwrap_or_default(),
+                })
+            }
+            Err(e) => {
+                warn!("Lighter transaction rejected, resyncing nonce: {}", e);
+                self.reset_nonce().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Serialize a transaction and attach the wallet signature as `Sig`
+    pub async fn sign_tx<T: Serialize + Sync>(&self, tx: &T) -> Result<String> {
+        let mut value = serde_json::to_value(tx)?;
+        let payload = serde_json::to_string(&value)?;
+        let signature = self.sign(payload.as_bytes()).await?;
+
+        if let serde_json::Value::Object(map) = &mut value {
+            map.insert("Sig".to_string(), serde_json::Value::String(signature));
+        }
+
+        Ok(serde_json::to_string(&value)?)
+    }
+
+    /// Auth token for private read endpoints (`auth` query parameter)
+    pub async fn auth_token(&self) -> Result<String> {
+        let mut cached = self.auth_token.lock().await;
+        let now = Utc::now().timestamp();
+
+        if let Some((token, expiry)) = cached.as_ref() {
+            if expiry - now > AUTH_TOKEN_REFRESH_SECS {
+                return Ok(token.clone());
+            }
+        }
+
+        let expiry = now + AUTH_TOKEN_TTL_SECS;
+        let message = format!(
+            "{}:{}:{}",
+            expiry,
+            self.account_index().await?,
+            self.api_key_index
+        );
+        let token = format!("{}:{}", message, self.sign(message.as_bytes()).await?);
+
+        *cached = Some((token.clone(), expiry));
+        Ok(token)
+    }
+
+    /// EIP-191 signature as 0x-prefixed hex
+    async fn sign(&self, message: &[u8]) -> Result<String> {
+        let signature = self
+            .auth
+            .sign_message(message)
+            .await
+            .map_err(|e| ExchangeError::Authentication(format!("Signing failed: {e}")))?;
+        Ok(format!("0x{signature}"))
+    }
+}
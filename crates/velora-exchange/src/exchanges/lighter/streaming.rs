@@ -1,97 +1,723 @@
 //! Lighter streaming implementation
+//!
+//! Every subscription opens its own connection to the Lighter stream endpoint,
+//! so concurrent streams never compete for frames. Account channels are
+//! authenticated with the signer's auth token.
 
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
 use futures::stream::Stream;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
 
 use crate::{
-    auth::EvmWalletAuth,
     common::WebSocketClient,
     traits::Streaming,
     types::{
-        BalanceUpdate, Candle, Interval, OrderBookUpdate, OrderUpdate, PositionUpdate, Result,
-        StreamTrade, Symbol, Ticker, UserDataEvent,
+        BalanceUpdate, Candle, ExchangeError, Interval, OrderBookUpdate, OrderUpdate,
+        PositionUpdate, Price, PriceLevel, Result, Side, StreamTrade, Symbol, Ticker,
+        UserDataEvent,
     },
 };
 
+use super::{
+    account::{LighterAccount, COLLATERAL_ASSET},
+    markets::{parse_decimal, LighterMarkets},
+    signer::LighterSigner,
+    trading::convert_order,
+    types::*,
+};
+
+/// Boxed stream returned by every subscription
+type BoxStream<T> = Box<dyn Stream<Item = Result<T>> + Send + Unpin>;
+
+/// Reply to the server's application-level ping
+const PONG: &str = r#"{"type":"pong"}"#;
+
 /// Lighter streaming component
 pub struct LighterStreaming {
     ws_client: Arc<RwLock<WebSocketClient>>,
-    auth: Option<EvmWalletAuth>,
+    signer: Option<Arc<LighterSigner>>,
+    markets: Arc<LighterMarkets>,
 }
 
 impl LighterStreaming {
-    pub fn new(ws_client: Arc<RwLock<WebSocketClient>>, auth: Option<EvmWalletAuth>) -> Self {
-        Self { ws_client, auth }
+    pub(crate) fn new(
+        ws_client: Arc<RwLock<WebSocketClient>>,
+        signer: Option<Arc<LighterSigner>>,
+        markets: Arc<LighterMarkets>,
+    ) -> Self {
+        Self {
+            ws_client,
+            signer,
+            markets,
+        }
+    }
+
+    /// Signer, or an error in read-only mode
+    fn signer(&self) -> Result<&LighterSigner> {
+        self.signer.as_deref().ok_or_else(|| {
+            ExchangeError::Authentication(
+                "Lighter account streams require an EVM wallet".to_string(),
+            )
+        })
+    }
+
+    /// Open a dedicated connection subscribed to `channels`
+    async fn connect(&self, channels: &[String], auth: Option<String>) -> Result<WebSocketClient> {
+        let url = self.ws_client.read().await.url().to_string();
+        let mut ws = WebSocketClient::new(url);
+        ws.connect().await?;
+
+        for channel in channels {
+            let subscription = LighterWsSubscribe {
+                msg_type: "subscribe".to_string(),
+                channel: channel.clone(),
+                auth: auth.clone(),
+            };
+            debug!("Sending Lighter subscription: {}", subscription.channel);
+            ws.send_json(&subscription).await?;
+        }
+
+        Ok(ws)
+    }
+
+    /// Open a connection subscribed to public channels of one market
+    async fn connect_market(&self, symbol: &Symbol, channels: &[&str]) -> Result<WebSocketClient> {
+        let market_id = self.markets.get(symbol).await?.market_id;
+        let channels: Vec<String> = channels
+            .iter()
+            .map(|channel| format!("{channel}/{market_id}"))
+            .collect();
+        self.connect(&channels, None).await
+    }
+
+    /// Open an authenticated connection subscribed to account channels
+    async fn connect_account(&self, channels: &[&str]) -> Result<WebSocketClient> {
+        let signer = self.signer()?;
+        let account_index = signer.account_index().await?;
+        let auth = signer.auth_token().await?;
+        let channels: Vec<String> = channels
+            .iter()
+            .map(|channel| format!("{channel}/{account_index}"))
+            .collect();
+        self.connect(&channels, Some(auth)).await
+    }
+}
+
+/// What arrived on a connection
+enum Received {
+    /// Channel data
+    Frame(LighterWsFrame),
+    /// Unparseable or error frame; the connection stays usable
+    Invalid(ExchangeError),
+    /// The server closed the connection
+    Closed,
+}
+
+/// Wait for the next data frame, answering pings on the way
+///
+/// Error frames are returned as `ApiError`s. An `Err` is a transport error,
+/// after which the connection must be dropped.
+async fn receive(client: &mut WebSocketClient) -> Result<Received> {
+    loop {
+        let Some(text) = client.recv().await? else {
+            info!("Lighter WebSocket closed");
+            return Ok(Received::Closed);
+        };
+
+        let frame: LighterWsFrame = match serde_json::from_str(&text) {
+            Ok(frame) => frame,
+            Err(e) => {
+                return Ok(Received::Invalid(ExchangeError::ParseError(format!(
+                    "Invalid Lighter frame: {e}"
+                ))))
+            }
+        };
+
+        if let Some(error) = frame.error {
+            return Ok(Received::Invalid(ExchangeError::ApiError {
+                code: error.code,
+                message: error.message,
+            }));
+        }
+
+        match frame.msg_type.as_str() {
+            "ping" => client.send(PONG).await?,
+            "connected" | "pong" => {}
+            _ => return Ok(Received::Frame(frame)),
+        }
+    }
+}
+
+/// Drive a connection, mapping every data frame to zero or more items
+///
+/// Pings are answered and error frames are surfaced as `ApiError`s. The stream
+/// ends when the server closes the connection or after a transport error.
+fn frame_stream<T, F>(ws: WebSocketClient, handle: F) -> BoxStream<T>
+where
+    T: Send + 'static,
+    F: FnMut(LighterWsFrame) -> Result<Vec<T>> + Send + 'static,
+{
+    let state = (Some(ws), handle, VecDeque::<Result<T>>::new());
+    let stream = futures::stream::unfold(state, |(mut ws, mut handle, mut pending)| async move {
+        loop {
+            if let Some(item) = pending.pop_front() {
+                return Some((item, (ws, handle, pending)));
+            }
+
+            let client = ws.as_mut()?;
+            match receive(client).await {
+                Ok(Received::Frame(frame)) => match handle(frame) {
+                    Ok(items) => pending.extend(items.into_iter().map(Ok)),
+                    Err(e) => pending.push_back(Err(e)),
+                },
+                Ok(Received::Invalid(e)) => pending.push_back(Err(e)),
+                Ok(Received::Closed) => return None,
+                Err(e) => return Some((Err(e), (None, handle, pending))),
+            }
+        }
+    });
+
+    Box::new(Box::pin(stream))
+}
+
+/// Whether an `order_book` frame applies to the book
+#[derive(Debug, PartialEq, Eq)]
+enum BookStep {
+    /// Sent before the current snapshot, or already applied
+    Stale,
+    /// A snapshot, or an update continuing the book
+    Apply,
+    /// Updates were missed; the book must be rebuilt from a snapshot
+    Gap,
+}
+
+/// Order book synchronization state of an `order_book` stream
+struct BookSync {
+    symbol: Symbol,
+    /// Channel resubscribed to for a new snapshot
+    channel: String,
+    depth: Option<usize>,
+    /// Offset of the last frame applied; None while waiting for a snapshot
+    last_offset: Option<u64>,
+}
+
+impl BookSync {
+    /// Classify a frame against the book; every update advances the offset
+    /// by one
+    fn step(&self, snapshot: bool, offset: u64) -> BookStep {
+        if snapshot {
+            return BookStep::Apply;
+        }
+        match self.last_offset {
+            None => BookStep::Stale,
+            Some(last) if offset <= last => BookStep::Stale,
+            Some(last) if offset == last + 1 => BookStep::Apply,
+            Some(_) => BookStep::Gap,
+        }
+    }
+
+    fn apply(&mut self, snapshot: bool, data: LighterWsOrderBookData) -> Result<OrderBookUpdate> {
+        let mut bids = parse_levels(&data.order_book.bids)?;
+        let mut asks = parse_levels(&data.order_book.asks)?;
+        bids.sort_by_key(|(price, _)| std::cmp::Reverse(*price));
+        asks.sort_by_key(|(price, _)| *price);
+        if let (true, Some(depth)) = (snapshot, self.depth) {
+            bids.truncate(depth);
+            asks.truncate(depth);
+        }
+
+        let offset = data.order_book.offset;
+        let first_update_id = if snapshot {
+            None
+        } else {
+            self.last_offset.map(|last| last + 1)
+        };
+        self.last_offset = Some(offset);
+
+        Ok(OrderBookUpdate {
+            symbol: self.symbol.clone(),
+            bids: to_price_levels(bids),
+            asks: to_price_levels(asks),
+            first_update_id,
+            final_update_id: Some(offset),
+            timestamp: millis_or_now(data.timestamp),
+        })
+    }
+
+    /// Drop the book and subscribe again, which makes the server send a new
+    /// snapshot
+    async fn resubscribe(&mut self, client: &mut WebSocketClient) -> Result<()> {
+        self.last_offset = None;
+        for msg_type in ["unsubscribe", "subscribe"] {
+            client
+                .send_json(&LighterWsSubscribe {
+                    msg_type: msg_type.to_string(),
+                    channel: self.channel.clone(),
+                    auth: None,
+                })
+                .await?;
+        }
+        Ok(())
     }
 }
 
+/// Drive an `order_book` connection, resubscribing after a gap
+fn order_book_stream(ws: WebSocketClient, sync: BookSync) -> BoxStream<OrderBookUpdate> {
+    let state = (Some(ws), sync, VecDeque::new());
+    let stream = futures::stream::unfold(state, |(mut ws, mut sync, mut pending)| async move {
+        loop {
+            if let Some(item) = pending.pop_front() {
+                return Some((item, (ws, sync, pending)));
+            }
+
+            let client = ws.as_mut()?;
+            let frame = match receive(client).await {
+                Ok(Received::Frame(frame)) => frame,
+                Ok(Received::Invalid(e)) => {
+                    pending.push_back(Err(e));
+                    continue;
+                }
+                Ok(Received::Closed) => return None,
+                Err(e) => return Some((Err(e), (None, sync, pending))),
+            };
+
+            let snapshot = match frame.msg_type.as_str() {
+                "subscribed/order_book" => true,
+                "update/order_book" => false,
+                _ => continue,
+            };
+            let data: LighterWsOrderBookData = match parse_payload(frame) {
+                Ok(data) => data,
+                Err(e) => {
+                    pending.push_back(Err(e));
+                    continue;
+                }
+            };
+
+            match sync.step(snapshot, data.order_book.offset) {
+                BookStep::Stale => {}
+                BookStep::Apply => pending.push_back(sync.apply(snapshot, data)),
+                BookStep::Gap => {
+                    warn!(
+                        "Lighter order book gap for {} after offset {:?}, resyncing",
+                        sync.symbol, sync.last_offset
+                    );
+                    if let Err(e) = sync.resubscribe(client).await {
+                        return Some((Err(e), (None, sync, pending)));
+                    }
+                }
+            }
+        }
+    });
+
+    Box::new(Box::pin(stream))
+}
+
+/// Channel a frame belongs to, e.g. `order_book` for `update/order_book`
+fn frame_kind(frame: &LighterWsFrame) -> &str {
+    frame.msg_type.rsplit('/').next().unwrap_or_default()
+}
+
+/// True for the snapshot sent in reply to a subscription
+fn is_snapshot(frame: &LighterWsFrame) -> bool {
+    frame.msg_type.starts_with("subscribed/")
+}
+
+/// Deserialize the channel-specific part of a frame
+fn parse_payload<T: DeserializeOwned>(frame: LighterWsFrame) -> Result<T> {
+    serde_json::from_value(frame.payload).map_err(|e| {
+        ExchangeError::ParseError(format!("Invalid Lighter {} frame: {e}", frame.msg_type))
+    })
+}
+
+fn to_price(value: Decimal) -> Price {
+    Price::from(value.to_f64().unwrap_or_default())
+}
+
+fn millis_or_now(millis: Option<i64>) -> DateTime<Utc> {
+    millis
+        .and_then(DateTime::from_timestamp_millis)
+        .unwrap_or_else(Utc::now)
+}
+
+/// Parse order book levels as (price, size) pairs
+fn parse_levels(levels: &[LighterWsPriceLevel]) -> Result<Vec<(Decimal, Decimal)>> {
+    levels
+        .iter()
+        .map(|level| {
+            Ok((
+                parse_decimal(&level.price, "level price")?,
+                parse_decimal(&level.size, "level size")?,
+            ))
+        })
+        .collect()
+}
+
+fn to_price_levels(levels: Vec<(Decimal, Decimal)>) -> Vec<PriceLevel> {
+    levels
+        .into_iter()
+        .map(|(price, quantity)| PriceLevel {
+            price: to_price(price),
+            quantity,
+        })
+        .collect()
+}
+
+/// Convert public trades, oldest first
+fn convert_trades(trades: Vec<LighterTrade>, symbol: &Symbol) -> Result<Vec<StreamTrade>> {
+    let mut converted = trades
+        .into_iter()
+        .map(|trade| {
+            Ok(StreamTrade {
+                symbol: symbol.clone(),
+                trade_id: trade.trade_id.to_string(),
+                price: to_price(parse_decimal(&trade.price, "trade price")?),
+                quantity: parse_decimal(&trade.size, "trade size")?,
+                // The taker bought if the resting maker was the ask
+                side: if trade.is_maker_ask {
+                    Side::Buy
+                } else {
+                    Side::Sell
+                },
+                timestamp: trade.timestamp,
+                buyer_maker: !trade.is_maker_ask,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    converted.sort_by_key(|trade| trade.timestamp);
+    Ok(converted)
+}
+
+/// Local copy of the top of the book, used to fill ticker bid/ask
+#[derive(Default)]
+struct LocalBook {
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+}
+
+impl LocalBook {
+    fn apply(&mut self, frame: LighterWsFrame) -> Result<()> {
+        let snapshot = is_snapshot(&frame);
+        let data: LighterWsOrderBookData = parse_payload(frame)?;
+        if snapshot {
+            self.bids.clear();
+            self.asks.clear();
+        }
+
+        for (book, levels) in [
+            (&mut self.bids, &data.order_book.bids),
+            (&mut self.asks, &data.order_book.asks),
+        ] {
+            for (price, size) in parse_levels(levels)? {
+                if size.is_zero() {
+                    book.remove(&price);
+                } else {
+                    book.insert(price, size);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn best_bid(&self) -> (Decimal, Decimal) {
+        self.bids
+            .last_key_value()
+            .map(|(p, s)| (*p, *s))
+            .unwrap_or_default()
+    }
+
+    fn best_ask(&self) -> (Decimal, Decimal) {
+        self.asks
+            .first_key_value()
+            .map(|(p, s)| (*p, *s))
+            .unwrap_or_default()
+    }
+}
+
+/// Candle being built from the trade feed
+struct CandleBuilder {
+    symbol: Symbol,
+    interval: Interval,
+    current: Option<Candle>,
+}
+
+impl CandleBuilder {
+    /// Add a trade, returning the previous candle if the trade opened a new one
+    fn push(&mut self, trade: &StreamTrade) -> Option<Candle> {
+        let seconds = self.interval.to_seconds();
+        let ts = trade.timestamp.timestamp();
+        let open_time =
+            DateTime::from_timestamp(ts - ts.rem_euclid(seconds), 0).unwrap_or(trade.timestamp);
+        let notional = trade.quantity * Decimal::try_from(trade.price.0).unwrap_or_default();
+
+        if let Some(candle) = self.current.as_mut() {
+            if candle.open_time == open_time {
+                candle.high = candle.high.max(trade.price);
+                candle.low = candle.low.min(trade.price);
+                candle.close = trade.price;
+                candle.volume += trade.quantity;
+                candle.trade_count = candle.trade_count.map(|n| n + 1);
+                candle.quote_volume = candle.quote_volume.map(|v| v + notional);
+                return None;
+            }
+            // Late trades for an emitted candle are ignored
+            if open_time < candle.open_time {
+                return None;
+            }
+        }
+
+        self.current.replace(Candle {
+            symbol: self.symbol.clone(),
+            interval: self.interval,
+            open: trade.price,
+            high: trade.price,
+            low: trade.price,
+            close: trade.price,
+            volume: trade.quantity,
+            open_time,
+            close_time: open_time + Duration::seconds(seconds),
+            trade_count: Some(1),
+            quote_volume: Some(notional),
+        })
+    }
+}
+
+/// Convert an `account_all_orders` frame
+fn order_updates(
+    frame: LighterWsFrame,
+    symbols: &HashMap<u64, Symbol>,
+) -> Result<Vec<OrderUpdate>> {
+    let data: LighterWsAccountOrdersData = parse_payload(frame)?;
+
+    let mut updates = Vec::new();
+    for order in data.orders.into_values().flatten() {
+        let Some(symbol) = symbols.get(&order.market_index) else {
+            warn!("Lighter order for unknown market {}", order.market_index);
+            continue;
+        };
+        let order = convert_order(order, symbol.clone())?;
+        updates.push(OrderUpdate {
+            timestamp: order.updated_at,
+            order,
+        });
+    }
+    updates.sort_by_key(|update| update.timestamp);
+    Ok(updates)
+}
+
+/// Convert an `account_all` frame; flat positions are only reported as updates
+fn position_updates(frame: LighterWsFrame) -> Result<Vec<PositionUpdate>> {
+    let snapshot = is_snapshot(&frame);
+    let data: LighterWsAccountData = parse_payload(frame)?;
+
+    let mut updates = Vec::new();
+    for position in data.positions.into_values() {
+        let position = LighterAccount::convert_position(position)?;
+        if snapshot && position.quantity.is_zero() {
+            continue;
+        }
+        updates.push(PositionUpdate {
+            timestamp: position.update_time,
+            position,
+        });
+    }
+    Ok(updates)
+}
+
+/// Convert a `user_stats` frame to a collateral balance
+fn balance_updates(frame: LighterWsFrame) -> Result<Vec<BalanceUpdate>> {
+    let data: LighterWsUserStatsData = parse_payload(frame)?;
+    let collateral = parse_decimal(&data.stats.collateral, "collateral")?;
+    let available = parse_decimal(&data.stats.available_balance, "available balance")?;
+
+    Ok(vec![BalanceUpdate {
+        asset: COLLATERAL_ASSET.to_string(),
+        free: available,
+        locked: (collateral - available).max(Decimal::ZERO),
+        timestamp: Utc::now(),
+    }])
+}
+
 #[async_trait]
 impl Streaming for LighterStreaming {
-    async fn subscribe_trades(
-        &self,
-        symbol: &Symbol,
-    ) -> Result<Box<dyn Stream<Item = Result<StreamTrade>> + Send + Unpin>> {
-        // TODO: Implement trades WebSocket subscription
-        todo!("Implement Lighter subscribe_trades for {}", symbol)
+    async fn subscribe_trades(&self, symbol: &Symbol) -> Result<BoxStream<StreamTrade>> {
+        info!("Subscribing to Lighter trades for {}", symbol);
+        let ws = self.connect_market(symbol, &["trade"]).await?;
+        let symbol = symbol.clone();
+
+        Ok(frame_stream(ws, move |frame| {
+            // The subscription reply replays recent trades; only stream new ones
+            if frame_kind(&frame) != "trade" || is_snapshot(&frame) {
+                return Ok(Vec::new());
+            }
+            let data: LighterWsTradesData = parse_payload(frame)?;
+            convert_trades(data.trades, &symbol)
+        }))
     }
 
+    /// The first update is a snapshot (`first_update_id` is None); later
+    /// updates carry changed levels, with a zero quantity removing a level.
+    /// Update ids are the book offsets. When an update is missed the channel
+    /// is resubscribed and its new snapshot is emitted instead.
     async fn subscribe_orderbook(
         &self,
         symbol: &Symbol,
         depth: Option<usize>,
-    ) -> Result<Box<dyn Stream<Item = Result<OrderBookUpdate>> + Send + Unpin>> {
-        // TODO: Implement orderbook WebSocket subscription
-        todo!("Implement Lighter subscribe_orderbook for {}", symbol)
+    ) -> Result<BoxStream<OrderBookUpdate>> {
+        info!(
+            "Subscribing to Lighter orderbook for {} (depth: {:?})",
+            symbol, depth
+        );
+        let market_id = self.markets.get(symbol).await?.market_id;
+        let channel = format!("order_book/{market_id}");
+        let ws = self.connect(std::slice::from_ref(&channel), None).await?;
+
+        let sync = BookSync {
+            symbol: symbol.clone(),
+            channel,
+            depth,
+            last_offset: None,
+        };
+        Ok(order_book_stream(ws, sync))
     }
 
-    async fn subscribe_ticker(
-        &self,
-        symbol: &Symbol,
-    ) -> Result<Box<dyn Stream<Item = Result<Ticker>> + Send + Unpin>> {
-        // TODO: Implement ticker WebSocket subscription
-        todo!("Implement Lighter subscribe_ticker for {}", symbol)
+    async fn subscribe_ticker(&self, symbol: &Symbol) -> Result<BoxStream<Ticker>> {
+        info!("Subscribing to Lighter ticker for {}", symbol);
+        // Market stats carry no quotes, so keep a local book for bid/ask
+        let ws = self
+            .connect_market(symbol, &["order_book", "market_stats"])
+            .await?;
+        let symbol = symbol.clone();
+        let mut book = LocalBook::default();
+
+        Ok(frame_stream(ws, move |frame| match frame_kind(&frame) {
+            "order_book" => book.apply(frame).map(|_| Vec::new()),
+            "market_stats" => {
+                let stats = parse_payload::<LighterWsMarketStatsData>(frame)?.market_stats;
+                let last = parse_decimal(&stats.last_trade_price, "last trade price")?;
+                let change = Decimal::try_from(stats.daily_price_change).unwrap_or_default();
+                let (bid, bid_size) = book.best_bid();
+                let (ask, ask_size) = book.best_ask();
+
+                Ok(vec![Ticker {
+                    symbol: symbol.clone(),
+                    last_price: to_price(last),
+                    bid: to_price(bid),
+                    bid_size,
+                    ask: to_price(ask),
+                    ask_size,
+                    volume_24h: Decimal::try_from(stats.daily_base_token_volume)
+                        .unwrap_or_default(),
+                    high_24h: Price::from(stats.daily_price_high),
+                    low_24h: Price::from(stats.daily_price_low),
+                    // The daily change is a fraction of the price 24h ago
+                    price_change_24h: (last * change / (Decimal::ONE + change)).round_dp(8),
+                    price_change_percent_24h: change * Decimal::ONE_HUNDRED,
+                    timestamp: Utc::now(),
+                }])
+            }
+            _ => Ok(Vec::new()),
+        }))
     }
 
+    /// Lighter has no candle channel, so candles are built from the trade
+    /// feed. The in-progress candle is emitted after every trade batch.
     async fn subscribe_candles(
         &self,
         symbol: &Symbol,
         interval: Interval,
-    ) -> Result<Box<dyn Stream<Item = Result<Candle>> + Send + Unpin>> {
-        // TODO: Implement candles WebSocket subscription
-        todo!(
-            "Implement Lighter subscribe_candles for {} interval {:?}",
-            symbol,
-            interval
-        )
+    ) -> Result<BoxStream<Candle>> {
+        info!(
+            "Subscribing to Lighter candles for {} ({:?})",
+            symbol, interval
+        );
+        let ws = self.connect_market(symbol, &["trade"]).await?;
+        let mut builder = CandleBuilder {
+            symbol: symbol.clone(),
+            interval,
+            current: None,
+        };
+
+        Ok(frame_stream(ws, move |frame| {
+            if frame_kind(&frame) != "trade" || is_snapshot(&frame) {
+                return Ok(Vec::new());
+            }
+            let data: LighterWsTradesData = parse_payload(frame)?;
+
+            let mut candles = Vec::new();
+            for trade in convert_trades(data.trades, &builder.symbol)? {
+                candles.extend(builder.push(&trade));
+            }
+            candles.extend(builder.current.clone());
+            Ok(candles)
+        }))
     }
 
-    async fn subscribe_orders(
-        &self,
-    ) -> Result<Box<dyn Stream<Item = Result<OrderUpdate>> + Send + Unpin>> {
-        // TODO: Implement orders WebSocket subscription
-        todo!("Implement Lighter subscribe_orders")
+    async fn subscribe_orders(&self) -> Result<BoxStream<OrderUpdate>> {
+        info!("Subscribing to Lighter order updates");
+        let ws = self.connect_account(&["account_all_orders"]).await?;
+        let symbols = self.markets.symbols().await?;
+
+        Ok(frame_stream(ws, move |frame| {
+            if frame_kind(&frame) != "account_all_orders" {
+                return Ok(Vec::new());
+            }
+            order_updates(frame, &symbols)
+        }))
     }
 
-    async fn subscribe_positions(
-        &self,
-    ) -> Result<Box<dyn Stream<Item = Result<PositionUpdate>> + Send + Unpin>> {
-        // TODO: Implement positions WebSocket subscription
-        todo!("Implement Lighter subscribe_positions")
+    async fn subscribe_positions(&self) -> Result<BoxStream<PositionUpdate>> {
+        info!("Subscribing to Lighter position updates");
+        let ws = self.connect_account(&["account_all"]).await?;
+
+        Ok(frame_stream(ws, |frame| {
+            if frame_kind(&frame) != "account_all" {
+                return Ok(Vec::new());
+            }
+            position_updates(frame)
+        }))
     }
 
-    async fn subscribe_balances(
-        &self,
-    ) -> Result<Box<dyn Stream<Item = Result<BalanceUpdate>> + Send + Unpin>> {
-        // TODO: Implement balances WebSocket subscription
-        todo!("Implement Lighter subscribe_balances")
+    async fn subscribe_balances(&self) -> Result<BoxStream<BalanceUpdate>> {
+        info!("Subscribing to Lighter balance updates");
+        let ws = self.connect_account(&["user_stats"]).await?;
+
+        Ok(frame_stream(ws, |frame| {
+            if frame_kind(&frame) != "user_stats" {
+                return Ok(Vec::new());
+            }
+            balance_updates(frame)
+        }))
     }
 
-    async fn subscribe_user_data(
-        &self,
-    ) -> Result<Box<dyn Stream<Item = Result<UserDataEvent>> + Send + Unpin>> {
-        // TODO: Implement user data WebSocket subscription
-        todo!("Implement Lighter subscribe_user_data")
+    async fn subscribe_user_data(&self) -> Result<BoxStream<UserDataEvent>> {
+        info!("Subscribing to Lighter user data");
+        let ws = self
+            .connect_account(&["account_all_orders", "account_all", "user_stats"])
+            .await?;
+        let symbols = self.markets.symbols().await?;
+
+        Ok(frame_stream(ws, move |frame| match frame_kind(&frame) {
+            "account_all_orders" => Ok(order_updates(frame, &symbols)?
+                .into_iter()
+                .map(UserDataEvent::OrderUpdate)
+                .collect()),
+            "account_all" => Ok(position_updates(frame)?
+                .into_iter()
+                .map(UserDataEvent::PositionUpdate)
+                .collect()),
+            "user_stats" => Ok(balance_updates(frame)?
+                .into_iter()
+                .map(UserDataEvent::BalanceUpdate)
+                .collect()),
+            _ => Ok(Vec::new()),
+        }))
     }
 }
@@ -0,0 +1,654 @@
+//! Tests for Lighter streaming implementation
+
+#[cfg(test)]
+mod tests {
+    use super::super::markets::LighterMarkets;
+    use super::super::signer::LighterSigner;
+    use super::super::streaming::*;
+
+    use crate::auth::EvmWalletAuth;
+    use crate::common::{RateLimiter, RestClient, WebSocketClient};
+    use crate::traits::Streaming;
+    use crate::types::{
+        ExchangeError, Interval, OrderStatus, PositionSide, Price, Side, Symbol, UserDataEvent,
+    };
+
+    use futures::{SinkExt, StreamExt};
+    use mockito::{Mock, Server};
+    use rust_decimal_macros::dec;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+    use tokio::sync::RwLock;
+    use tokio::task::JoinHandle;
+    use tokio_tungstenite::{accept_async, tungstenite::Message};
+
+    const TEST_PRIVATE_KEY: &str =
+        "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    const ACCOUNT_INDEX: i64 = 42;
+
+    const ORDER_BOOKS: &str = r#"{
+        "code": 200,
+        "order_books": [
+            {
+                "symbol": "ETH",
+                "market_id": 0,
+                "status": "active",
+                "taker_fee": "0.0000",
+                "maker_fee": "0.0000",
+                "liquidation_fee": "1.0000",
+                "min_base_amount": "0.0050",
+                "min_quote_amount": "10.000000",
+                "order_quote_limit": "",
+                "supported_size_decimals": 4,
+                "supported_price_decimals": 2,
+                "supported_quote_decimals": 6
+            }
+        ]
+    }"#;
+
+    /// Serve a single connection: read `subscriptions` messages, replay
+    /// `frames`, close, and return every message the client sent
+    async fn serve(frames: Vec<Value>, subscriptions: usize) -> (String, JoinHandle<Vec<Value>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(socket).await.unwrap();
+
+            let mut received = Vec::new();
+            while received.len() < subscriptions {
+                if let Some(Ok(Message::Text(text))) = ws.next().await {
+                    received.push(serde_json::from_str(&text).unwrap());
+                }
+            }
+
+            for frame in frames {
+                ws.send(Message::Text(frame.to_string())).await.unwrap();
+            }
+            ws.close(None).await.ok();
+
+            while let Some(Ok(message)) = ws.next().await {
+                if let Message::Text(text) = message {
+                    received.push(serde_json::from_str(&text).unwrap());
+                }
+            }
+            received
+        });
+
+        (url, handle)
+    }
+
+    /// Helper to create a streaming component against local servers
+    async fn setup_test(server: &Server, ws_url: String, authenticated: bool) -> LighterStreaming {
+        let rest_client =
+            Arc::new(RestClient::new(server.url(), std::time::Duration::from_secs(30)).unwrap());
+        let rate_limiter = Arc::new(RateLimiter::new(100, std::time::Duration::from_secs(1)));
+
+        let signer = if authenticated {
+            let signer = Arc::new(LighterSigner::new(
+                Arc::clone(&rest_client),
+                Arc::clone(&rate_limiter),
+                EvmWalletAuth::new(TEST_PRIVATE_KEY),
+            ));
+            signer.set_account_index(ACCOUNT_INDEX).await;
+            Some(signer)
+        } else {
+            None
+        };
+
+        let markets = Arc::new(LighterMarkets::new(rest_client, rate_limiter));
+        let ws_client = Arc::new(RwLock::new(WebSocketClient::new(ws_url)));
+
+        LighterStreaming::new(ws_client, signer, markets)
+    }
+
+    async fn mock_order_books(server: &mut Server) -> Mock {
+        server
+            .mock("GET", "/api/v1/orderBooks")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ORDER_BOOKS)
+            .create_async()
+            .await
+    }
+
+    fn subscribe(channel: &str) -> Value {
+        json!({ "type": "subscribe", "channel": channel })
+    }
+
+    fn trade(id: u64, price: &str, size: &str, is_maker_ask: bool, timestamp: i64) -> Value {
+        json!({
+            "trade_id": id, "market_id": 0, "price": price, "size": size,
+            "is_maker_ask": is_maker_ask, "timestamp": timestamp,
+            "tx_hash": "0xabc", "type": "trade"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_orderbook() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let (url, handle) = serve(
+            vec![
+                json!({ "session_id": "s1", "type": "connected" }),
+                json!({
+                    "channel": "order_book:0",
+                    "offset": 100,
+                    "order_book": {
+                        "code": 0,
+                        "asks": [
+                            {"price": "3001.00", "size": "2.0000"},
+                            {"price": "3000.50", "size": "1.0000"},
+                            {"price": "3002.00", "size": "3.0000"}
+                        ],
+                        "bids": [
+                            {"price": "2999.00", "size": "1.5000"},
+                            {"price": "2999.50", "size": "0.5000"},
+                            {"price": "2998.00", "size": "4.0000"}
+                        ],
+                        "offset": 100
+                    },
+                    "timestamp": 1729000000000i64,
+                    "type": "subscribed/order_book"
+                }),
+                json!({ "type": "ping" }),
+                json!({
+                    "channel": "order_book:0",
+                    "offset": 101,
+                    "order_book": {
+                        "code": 0,
+                        "asks": [{"price": "3000.50", "size": "0"}],
+                        "bids": [{"price": "2999.75", "size": "0.2500"}],
+                        "offset": 101
+                    },
+                    "type": "update/order_book"
+                }),
+            ],
+            1,
+        )
+        .await;
+        let streaming = setup_test(&server, url, false).await;
+
+        let mut stream = streaming
+            .subscribe_orderbook(&Symbol::from("ETH"), Some(2))
+            .await
+            .unwrap();
+
+        let snapshot = stream.next().await.unwrap().unwrap();
+        assert_eq!(snapshot.first_update_id, None);
+        assert_eq!(snapshot.final_update_id, Some(100));
+        assert_eq!(snapshot.timestamp.timestamp_millis(), 1729000000000);
+        // Sorted best first and truncated to the requested depth
+        assert_eq!(snapshot.bids.len(), 2);
+        assert_eq!(snapshot.bids[0].price, Price::from(2999.5));
+        assert_eq!(snapshot.bids[1].price, Price::from(2999.0));
+        assert_eq!(snapshot.asks.len(), 2);
+        assert_eq!(snapshot.asks[0].price, Price::from(3000.5));
+        assert_eq!(snapshot.asks[0].quantity, dec!(1));
+
+        let update = stream.next().await.unwrap().unwrap();
+        assert_eq!(update.first_update_id, Some(101));
+        assert_eq!(update.final_update_id, Some(101));
+        assert_eq!(update.asks[0].quantity, dec!(0));
+        assert_eq!(update.bids[0].price, Price::from(2999.75));
+
+        assert!(stream.next().await.is_none());
+
+        let received = handle.await.unwrap();
+        assert_eq!(received[0], subscribe("order_book/0"));
+        assert_eq!(received[1], json!({ "type": "pong" }));
+    }
+
+    fn book_frame(msg_type: &str, offset: u64, bid: &str) -> Value {
+        json!({
+            "channel": "order_book:0",
+            "order_book": {
+                "asks": [],
+                "bids": [{"price": bid, "size": "1.0000"}],
+                "offset": offset
+            },
+            "type": msg_type
+        })
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_orderbook_resyncs_after_gap() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let (url, handle) = serve(
+            vec![
+                book_frame("subscribed/order_book", 100, "2999.00"),
+                book_frame("update/order_book", 101, "2999.10"),
+                // 102 was missed: this update and those before the new
+                // snapshot are dropped
+                book_frame("update/order_book", 103, "2999.30"),
+                book_frame("update/order_book", 104, "2999.40"),
+                json!({ "channel": "order_book:0", "type": "unsubscribed/order_book" }),
+                book_frame("subscribed/order_book", 110, "2998.00"),
+                book_frame("update/order_book", 111, "2998.10"),
+            ],
+            1,
+        )
+        .await;
+        let streaming = setup_test(&server, url, false).await;
+
+        let updates: Vec<_> = streaming
+            .subscribe_orderbook(&Symbol::from("ETH"), None)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        let ids: Vec<_> = updates
+            .iter()
+            .map(|update| (update.first_update_id, update.final_update_id))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                (None, Some(100)),
+                (Some(101), Some(101)),
+                (None, Some(110)),
+                (Some(111), Some(111)),
+            ]
+        );
+        assert_eq!(updates[2].bids[0].price, Price::from(2998.0));
+
+        let received = handle.await.unwrap();
+        assert_eq!(
+            received,
+            vec![
+                subscribe("order_book/0"),
+                json!({ "type": "unsubscribe", "channel": "order_book/0" }),
+                subscribe("order_book/0"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_trades() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let (url, handle) = serve(
+            vec![
+                // Recent trades replayed on subscribe are skipped
+                json!({
+                    "channel": "trade:0",
+                    "trades": [trade(1, "2990.00", "1.0000", true, 1728999000000)],
+                    "type": "subscribed/trade"
+                }),
+                json!({
+                    "channel": "trade:0",
+                    "trades": [
+                        trade(3, "3001.00", "0.2000", false, 1729000001000),
+                        trade(2, "3000.00", "0.5000", true, 1729000000000)
+                    ],
+                    "type": "update/trade"
+                }),
+            ],
+            1,
+        )
+        .await;
+        let streaming = setup_test(&server, url, false).await;
+
+        let trades: Vec<_> = streaming
+            .subscribe_trades(&Symbol::from("ETH"))
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(trades.len(), 2);
+        let first = trades[0].as_ref().unwrap();
+        assert_eq!(first.trade_id, "2");
+        assert_eq!(first.side, Side::Buy);
+        assert!(!first.buyer_maker);
+        assert_eq!(first.quantity, dec!(0.5));
+
+        let second = trades[1].as_ref().unwrap();
+        assert_eq!(second.trade_id, "3");
+        assert_eq!(second.side, Side::Sell);
+        assert!(second.buyer_maker);
+        assert_eq!(second.price, Price::from(3001.0));
+
+        assert_eq!(handle.await.unwrap()[0], subscribe("trade/0"));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_ticker() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let (url, handle) = serve(
+            vec![
+                json!({
+                    "channel": "order_book:0",
+                    "order_book": {
+                        "asks": [{"price": "3001.00", "size": "2.0000"}],
+                        "bids": [{"price": "2999.00", "size": "1.5000"}],
+                        "offset": 7
+                    },
+                    "type": "subscribed/order_book"
+                }),
+                json!({
+                    "channel": "order_book:0",
+                    "order_book": {
+                        "asks": [],
+                        "bids": [{"price": "3000.00", "size": "0.7500"}],
+                        "offset": 8
+                    },
+                    "type": "update/order_book"
+                }),
+                json!({
+                    "channel": "market_stats:0",
+                    "market_stats": {
+                        "market_id": 0,
+                        "index_price": "3000.10",
+                        "mark_price": "3000.20",
+                        "open_interest": "235.25",
+                        "last_trade_price": "3000.50",
+                        "current_funding_rate": "0.0057",
+                        "funding_rate": "0.0005",
+                        "funding_timestamp": 1729000000000i64,
+                        "daily_base_token_volume": 1250.5,
+                        "daily_quote_token_volume": 3751500.0,
+                        "daily_price_low": 2950.0,
+                        "daily_price_high": 3050.0,
+                        "daily_price_change": 0.25
+                    },
+                    "type": "update/market_stats"
+                }),
+            ],
+            2,
+        )
+        .await;
+        let streaming = setup_test(&server, url, false).await;
+
+        let tickers: Vec<_> = streaming
+            .subscribe_ticker(&Symbol::from("ETH"))
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(tickers.len(), 1);
+        let ticker = tickers[0].as_ref().unwrap();
+        assert_eq!(ticker.last_price, Price::from(3000.5));
+        assert_eq!(ticker.bid, Price::from(3000.0));
+        assert_eq!(ticker.bid_size, dec!(0.75));
+        assert_eq!(ticker.ask, Price::from(3001.0));
+        assert_eq!(ticker.volume_24h, dec!(1250.5));
+        assert_eq!(ticker.high_24h, Price::from(3050.0));
+        assert_eq!(ticker.price_change_percent_24h, dec!(25));
+        // Price 24h ago was 3000.5 / 1.25 = 2400.4
+        assert_eq!(ticker.price_change_24h, dec!(600.1));
+
+        let received = handle.await.unwrap();
+        assert_eq!(received[0], subscribe("order_book/0"));
+        assert_eq!(received[1], subscribe("market_stats/0"));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_candles() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        // 2024-10-15 13:46:00 UTC is a minute boundary
+        let minute = 1729000000000i64 - 40_000;
+        let (url, _handle) = serve(
+            vec![
+                json!({
+                    "channel": "trade:0",
+                    "trades": [
+                        trade(1, "3000.00", "1.0000", true, minute + 10_000),
+                        trade(2, "3010.00", "0.5000", false, minute + 50_000),
+                        trade(3, "3005.00", "2.0000", true, minute + 65_000)
+                    ],
+                    "type": "update/trade"
+                }),
+                json!({
+                    "channel": "trade:0",
+                    "trades": [trade(4, "2995.00", "1.0000", false, minute + 70_000)],
+                    "type": "update/trade"
+                }),
+            ],
+            1,
+        )
+        .await;
+        let streaming = setup_test(&server, url, false).await;
+
+        let candles: Vec<_> = streaming
+            .subscribe_candles(&Symbol::from("ETH"), Interval::Minute1)
+            .await
+            .unwrap()
+            .map(|c| c.unwrap())
+            .collect()
+            .await;
+
+        // Completed first minute, then the in-progress second minute twice
+        assert_eq!(candles.len(), 3);
+
+        let first = &candles[0];
+        assert_eq!(first.open_time.timestamp_millis(), minute);
+        assert_eq!(first.close_time.timestamp_millis(), minute + 60_000);
+        assert_eq!(first.open, Price::from(3000.0));
+        assert_eq!(first.high, Price::from(3010.0));
+        assert_eq!(first.close, Price::from(3010.0));
+        assert_eq!(first.volume, dec!(1.5));
+        assert_eq!(first.trade_count, Some(2));
+        assert_eq!(first.quote_volume, Some(dec!(4505)));
+
+        assert_eq!(candles[1].open_time.timestamp_millis(), minute + 60_000);
+        assert_eq!(candles[1].volume, dec!(2));
+
+        let last = &candles[2];
+        assert_eq!(last.open, Price::from(3005.0));
+        assert_eq!(last.low, Price::from(2995.0));
+        assert_eq!(last.close, Price::from(2995.0));
+        assert_eq!(last.volume, dec!(3));
+        assert_eq!(last.trade_count, Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_user_data() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let (url, handle) = serve(
+            vec![
+                json!({
+                    "channel": "account_all_orders:42",
+                    "orders": {
+                        "0": [{
+                            "order_index": 281474976710657i64,
+                            "client_order_index": 55,
+                            "market_index": 0,
+                            "initial_base_amount": "0.5000",
+                            "remaining_base_amount": "0.0000",
+                            "filled_base_amount": "0.5000",
+                            "filled_quote_amount": "1500.000000",
+                            "price": "3000.00",
+                            "is_ask": false,
+                            "type": "limit",
+                            "time_in_force": "good-till-time",
+                            "reduce_only": false,
+                            "trigger_price": "0.00",
+                            "status": "filled",
+                            "timestamp": 1729000000
+                        }]
+                    },
+                    "type": "update/account_all_orders"
+                }),
+                json!({
+                    "channel": "account_all:42",
+                    "positions": {
+                        "0": {
+                            "market_id": 0,
+                            "symbol": "ETH",
+                            "initial_margin_fraction": "20.00",
+                            "sign": 1,
+                            "position": "0.5000",
+                            "avg_entry_price": "3000.00",
+                            "position_value": "1510.000000",
+                            "unrealized_pnl": "10.000000",
+                            "realized_pnl": "0.000000",
+                            "liquidation_price": "2500.00",
+                            "margin_mode": 0,
+                            "allocated_margin": "0.000000"
+                        }
+                    },
+                    "type": "update/account_all"
+                }),
+                json!({
+                    "channel": "user_stats:42",
+                    "stats": {
+                        "collateral": "10000.000000",
+                        "portfolio_value": "10010.000000",
+                        "available_balance": "9698.000000"
+                    },
+                    "type": "update/user_stats"
+                }),
+            ],
+            3,
+        )
+        .await;
+        let streaming = setup_test(&server, url, true).await;
+
+        let events: Vec<_> = streaming
+            .subscribe_user_data()
+            .await
+            .unwrap()
+            .map(|e| e.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(events.len(), 3);
+        match &events[0] {
+            UserDataEvent::OrderUpdate(update) => {
+                assert_eq!(update.order.symbol, Symbol::from("ETH"));
+                assert_eq!(update.order.status, OrderStatus::Filled);
+                assert_eq!(update.order.side, Side::Buy);
+                assert_eq!(update.timestamp.timestamp(), 1729000000);
+            }
+            other => panic!("Expected order update, got {other:?}"),
+        }
+        match &events[1] {
+            UserDataEvent::PositionUpdate(update) => {
+                assert_eq!(update.position.side, PositionSide::Long);
+                assert_eq!(update.position.quantity, dec!(0.5));
+                assert_eq!(update.position.mark_price, Price::from(3020.0));
+                assert_eq!(update.position.leverage, 5);
+            }
+            other => panic!("Expected position update, got {other:?}"),
+        }
+        match &events[2] {
+            UserDataEvent::BalanceUpdate(update) => {
+                assert_eq!(update.asset, "USDC");
+                assert_eq!(update.free, dec!(9698));
+                assert_eq!(update.locked, dec!(302));
+            }
+            other => panic!("Expected balance update, got {other:?}"),
+        }
+
+        let received = handle.await.unwrap();
+        let channels: Vec<_> = received.iter().map(|m| m["channel"].clone()).collect();
+        assert_eq!(
+            channels,
+            vec![
+                json!("account_all_orders/42"),
+                json!("account_all/42"),
+                json!("user_stats/42")
+            ]
+        );
+        // Auth token is `expiry:account:api_key:signature`
+        let auth = received[0]["auth"].as_str().unwrap();
+        assert_eq!(auth.split(':').nth(1), Some("42"));
+        assert_eq!(received[1]["auth"], received[0]["auth"]);
+    }
+
+    #[tokio::test]
+    async fn test_position_close_is_streamed() {
+        let server = Server::new_async().await;
+        let flat = |msg_type: &str| {
+            json!({
+                "channel": "account_all:42",
+                "positions": {
+                    "1": {
+                        "market_id": 1, "symbol": "BTC", "initial_margin_fraction": "10.00",
+                        "sign": -1, "position": "0.00000", "avg_entry_price": "60000.0",
+                        "position_value": "0.000000", "unrealized_pnl": "0.000000",
+                        "realized_pnl": "-12.500000", "liquidation_price": "0",
+                        "margin_mode": 0, "allocated_margin": "0.000000"
+                    }
+                },
+                "type": msg_type
+            })
+        };
+        let (url, _handle) = serve(
+            vec![flat("subscribed/account_all"), flat("update/account_all")],
+            1,
+        )
+        .await;
+        let streaming = setup_test(&server, url, true).await;
+
+        let updates: Vec<_> = streaming
+            .subscribe_positions()
+            .await
+            .unwrap()
+            .map(|u| u.unwrap())
+            .collect()
+            .await;
+
+        // Flat positions in the snapshot are skipped, but a close is reported
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].position.symbol, Symbol::from("BTC"));
+        assert_eq!(updates[0].position.quantity, dec!(0));
+        assert_eq!(updates[0].position.realized_pnl, dec!(-12.5));
+    }
+
+    #[tokio::test]
+    async fn test_error_frame() {
+        let server = Server::new_async().await;
+        let (url, _handle) = serve(
+            vec![
+                json!({ "error": { "code": 30005, "message": "Invalid Channel" } }),
+                json!({
+                    "channel": "user_stats:42",
+                    "stats": { "collateral": "100", "available_balance": "100" },
+                    "type": "subscribed/user_stats"
+                }),
+            ],
+            1,
+        )
+        .await;
+        let streaming = setup_test(&server, url, true).await;
+
+        let mut stream = streaming.subscribe_balances().await.unwrap();
+
+        match stream.next().await.unwrap() {
+            Err(ExchangeError::ApiError { code, message }) => {
+                assert_eq!(code, 30005);
+                assert_eq!(message, "Invalid Channel");
+            }
+            other => panic!("Expected ApiError, got {other:?}"),
+        }
+        let balance = stream.next().await.unwrap().unwrap();
+        assert_eq!(balance.free, dec!(100));
+        assert_eq!(balance.locked, dec!(0));
+    }
+
+    #[tokio::test]
+    async fn test_private_streams_require_wallet() {
+        let server = Server::new_async().await;
+        let streaming = setup_test(&server, "ws://127.0.0.1:1".to_string(), false).await;
+
+        assert!(matches!(
+            streaming.subscribe_orders().await,
+            Err(ExchangeError::Authentication(_))
+        ));
+        assert!(matches!(
+            streaming.subscribe_user_data().await,
+            Err(ExchangeError::Authentication(_))
+        ));
+    }
+}
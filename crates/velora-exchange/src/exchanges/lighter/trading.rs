@@ -1,47 +1,351 @@
 //! Lighter trading implementation
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use std::sync::Arc;
+use tracing::{info, warn};
 
 use crate::{
-    auth::EvmWalletAuth,
     common::{RateLimiter, RestClient},
     traits::Trading,
-    types::{NewOrder, Order, OrderModification, Price, Result, Side, Symbol},
+    types::{
+        ExchangeError, NewOrder, Order, OrderModification, OrderStatus, OrderType, Price, Result,
+        Side, Symbol, TimeInForce,
+    },
 };
 
+use super::{
+    endpoints,
+    markets::{parse_decimal, LighterMarkets, MarketInfo},
+    signer::LighterSigner,
+    tx,
+    types::*,
+};
+
+/// Worst-price bound applied to market orders (Lighter market orders need a price)
+const MARKET_ORDER_SLIPPAGE: f64 = 0.05;
+
+/// Client order indices are 48-bit on Lighter
+const MAX_CLIENT_ORDER_INDEX: i64 = (1 << 48) - 1;
+
 /// Lighter trading component
 pub struct LighterTrading {
     rest_client: Arc<RestClient>,
     rate_limiter: Arc<RateLimiter>,
-    auth: Option<EvmWalletAuth>,
+    signer: Option<Arc<LighterSigner>>,
+    markets: Arc<LighterMarkets>,
 }
 
 impl LighterTrading {
-    pub fn new(
+    pub(crate) fn new(
         rest_client: Arc<RestClient>,
         rate_limiter: Arc<RateLimiter>,
-        auth: Option<EvmWalletAuth>,
+        signer: Option<Arc<LighterSigner>>,
+        markets: Arc<LighterMarkets>,
     ) -> Self {
         Self {
             rest_client,
             rate_limiter,
-            auth,
+            signer,
+            markets,
         }
     }
+
+    /// Signer, or an error in read-only mode
+    fn signer(&self) -> Result<&LighterSigner> {
+        self.signer.as_deref().ok_or_else(|| {
+            ExchangeError::Authentication("Lighter trading requires an EVM wallet".to_string())
+        })
+    }
+
+    /// Worst acceptable price for a market order, from the last trade price
+    async fn market_order_price(&self, market: &MarketInfo, side: Side) -> Result<Price> {
+        self.rate_limiter.wait().await;
+        let endpoint = format!(
+            "{}?market_id={}",
+            endpoints::ORDERBOOK_DETAILS,
+            market.market_id
+        );
+        let response: LighterApiResponse<OrderBookDetailsData> =
+            self.rest_client.get(&endpoint).await?;
+
+        let last = response
+            .data
+            .order_book_details
+            .first()
+            .and_then(|d| d.last_trade_price)
+            .ok_or_else(|| {
+                ExchangeError::InvalidOrder(format!("No reference price for {}", market.symbol))
+            })?;
+
+        Ok(Price::from(match side {
+            Side::Buy => last * (1.0 + MARKET_ORDER_SLIPPAGE),
+            Side::Sell => last * (1.0 - MARKET_ORDER_SLIPPAGE),
+        }))
+    }
+
+    /// Fetch account orders from an active/inactive orders endpoint
+    async fn fetch_orders(
+        &self,
+        endpoint: &str,
+        symbol: Option<&Symbol>,
+        limit: Option<usize>,
+    ) -> Result<Vec<Order>> {
+        let signer = self.signer()?;
+
+        let mut query = format!(
+            "{}?account_index={}&auth={}",
+            endpoint,
+            signer.account_index().await?,
+            signer.auth_token().await?
+        );
+        if let Some(symbol) = symbol {
+            let market = self.markets.get(symbol).await?;
+            query.push_str(&format!("&market_id={}", market.market_id));
+        }
+        if let Some(limit) = limit {
+            query.push_str(&format!("&limit={limit}"));
+        }
+
+        self.rate_limiter.wait().await;
+        let response: LighterApiResponse<AccountOrdersData> = self.rest_client.get(&query).await?;
+
+        let mut orders = Vec::with_capacity(response.data.orders.len());
+        for order in response.data.orders {
+            let market = self.markets.by_id(order.market_index).await?;
+            orders.push(convert_order(order, market.symbol)?);
+        }
+        Ok(orders)
+    }
+
+    /// Find an open order by order index or client order index
+    async fn find_open_order(&self, symbol: Option<&Symbol>, order_id: &str) -> Result<Order> {
+        self.get_open_orders(symbol)
+            .await?
+            .into_iter()
+            .find(|o| matches_id(o, order_id))
+            .ok_or_else(|| ExchangeError::OrderNotFound(order_id.to_string()))
+    }
+
+    /// Sign and submit a cancel for one order
+    async fn send_cancel(&self, market: &MarketInfo, order_id: &str) -> Result<()> {
+        let signer = self.signer()?;
+        let index = parse_order_index(order_id)?;
+
+        let cancel = CancelOrderTx {
+            account_index: signer.account_index().await?,
+            api_key_index: signer.api_key_index(),
+            market_index: market.market_id,
+            index,
+            expired_at: Utc::now().timestamp_millis() + tx::TX_EXPIRY_MS,
+            nonce: signer.next_nonce().await?,
+        };
+
+        let response = signer.send_tx(tx::TYPE_CANCEL_ORDER, &cancel).await?;
+        info!(
+            "Cancelled Lighter order {} (tx {})",
+            order_id, response.tx_hash
+        );
+        Ok(())
+    }
+}
+
+/// Parse an order id (order index or client order index)
+fn parse_order_index(order_id: &str) -> Result<i64> {
+    order_id
+        .parse()
+        .map_err(|_| ExchangeError::InvalidOrder(format!("Invalid Lighter order id: {order_id}")))
+}
+
+/// True if the order matches either its exchange or client index
+fn matches_id(order: &Order, order_id: &str) -> bool {
+    order.order_id == order_id || order.client_order_id.as_deref() == Some(order_id)
+}
+
+/// Map Lighter order status strings to OrderStatus
+fn convert_status(status: &str, filled: Decimal) -> OrderStatus {
+    match status {
+        "open" if filled > Decimal::ZERO => OrderStatus::PartiallyFilled,
+        "open" => OrderStatus::Open,
+        "in-progress" | "pending" => OrderStatus::Pending,
+        "filled" => OrderStatus::Filled,
+        s if s.starts_with("canceled") && s.contains("expired") => OrderStatus::Expired,
+        s if s.starts_with("canceled") => OrderStatus::Cancelled,
+        _ => OrderStatus::Rejected,
+    }
+}
+
+/// Convert a Lighter account order to our Order type
+pub(crate) fn convert_order(order: LighterAccountOrder, symbol: Symbol) -> Result<Order> {
+    let quantity = parse_decimal(&order.initial_base_amount, "order size")?;
+    let filled = parse_decimal(&order.filled_base_amount, "filled size")?;
+    let filled_quote = parse_decimal(&order.filled_quote_amount, "filled quote")?;
+    let price = parse_decimal(&order.price, "order price")?;
+    let trigger = parse_decimal(&order.trigger_price, "trigger price")?;
+
+    let order_type = match order.order_type.as_str() {
+        "market" => OrderType::Market,
+        "stop-loss" | "take-profit" => OrderType::StopMarket,
+        "stop-loss-limit" | "take-profit-limit" => OrderType::StopLimit,
+        _ => OrderType::Limit,
+    };
+
+    let time_in_force = match order.time_in_force.as_str() {
+        "immediate-or-cancel" => TimeInForce::ImmediateOrCancel,
+        "post-only" => TimeInForce::GoodTilCrossing,
+        _ => TimeInForce::GoodTilCancel,
+    };
+
+    let to_price = |d: Decimal| d.to_f64().map(Price::from);
+    let timestamp = DateTime::from_timestamp(order.timestamp, 0).unwrap_or_else(Utc::now);
+
+    Ok(Order {
+        order_id: order.order_index.to_string(),
+        client_order_id: Some(order.client_order_index.to_string()),
+        symbol,
+        side: if order.is_ask { Side::Sell } else { Side::Buy },
+        order_type,
+        time_in_force,
+        quantity,
+        price: (price > Decimal::ZERO).then(|| to_price(price)).flatten(),
+        stop_price: (trigger > Decimal::ZERO)
+            .then(|| to_price(trigger))
+            .flatten(),
+        status: convert_status(&order.status, filled),
+        filled_quantity: filled,
+        average_price: (filled > Decimal::ZERO)
+            .then(|| to_price(filled_quote / filled))
+            .flatten(),
+        created_at: timestamp,
+        updated_at: timestamp,
+        reduce_only: Some(order.reduce_only),
+        position_side: None,
+    })
 }
 
 #[async_trait]
 impl Trading for LighterTrading {
     async fn place_order(&self, order: NewOrder) -> Result<Order> {
-        self.rate_limiter.wait().await;
+        let signer = self.signer()?;
+
+        if order.quantity <= Decimal::ZERO {
+            return Err(ExchangeError::InvalidOrder(
+                "Quantity must be positive".to_string(),
+            ));
+        }
+
+        let market = self.markets.get(&order.symbol).await?;
 
-        // TODO: Implement actual order placement with EVM wallet signing
-        // 1. Create order payload
-        // 2. Sign with EVM wallet
-        // 3. Submit to Lighter API
-        todo!("Implement Lighter place_order")
+        let (order_type, price) = match order.order_type {
+            OrderType::Limit => (
+                tx::ORDER_TYPE_LIMIT,
+                order.price.ok_or_else(|| {
+                    ExchangeError::InvalidOrder("Limit order requires a price".to_string())
+                })?,
+            ),
+            OrderType::Market => match order.price {
+                Some(price) => (tx::ORDER_TYPE_MARKET, price),
+                None => (
+                    tx::ORDER_TYPE_MARKET,
+                    self.market_order_price(&market, order.side).await?,
+                ),
+            },
+            OrderType::StopMarket => (
+                tx::ORDER_TYPE_STOP_LOSS,
+                match order.price {
+                    Some(price) => price,
+                    None => self.market_order_price(&market, order.side).await?,
+                },
+            ),
+            OrderType::StopLimit => (
+                tx::ORDER_TYPE_STOP_LOSS_LIMIT,
+                order.price.ok_or_else(|| {
+                    ExchangeError::InvalidOrder("Stop-limit order requires a price".to_string())
+                })?,
+            ),
+        };
+
+        let trigger_price = match order.order_type {
+            OrderType::StopMarket | OrderType::StopLimit => {
+                let stop = order.stop_price.ok_or_else(|| {
+                    ExchangeError::InvalidOrder("Stop order requires a stop price".to_string())
+                })?;
+                market.to_price(stop)?
+            }
+            _ => 0,
+        };
+
+        let time_in_force = match (order.order_type, order.time_in_force) {
+            (OrderType::Market, _) => tx::TIF_IMMEDIATE_OR_CANCEL,
+            (_, Some(TimeInForce::ImmediateOrCancel)) => tx::TIF_IMMEDIATE_OR_CANCEL,
+            (_, Some(TimeInForce::GoodTilCrossing)) => tx::TIF_POST_ONLY,
+            (_, Some(TimeInForce::FillOrKill)) => {
+                return Err(ExchangeError::Unsupported(
+                    "Lighter does not support fill-or-kill orders".to_string(),
+                ));
+            }
+            (_, Some(TimeInForce::GoodTilCancel) | None) => tx::TIF_GOOD_TILL_TIME,
+        };
+
+        let client_order_index = match order.client_order_id.as_deref() {
+            Some(id) => id.parse::<i64>().map_err(|_| {
+                ExchangeError::InvalidOrder(format!(
+                    "Lighter client order ids must be integers, got {id}"
+                ))
+            })?,
+            None => Utc::now().timestamp_micros() & MAX_CLIENT_ORDER_INDEX,
+        };
+
+        let now = Utc::now();
+        let create = CreateOrderTx {
+            account_index: signer.account_index().await?,
+            api_key_index: signer.api_key_index(),
+            market_index: market.market_id,
+            client_order_index,
+            base_amount: market.to_base_amount(order.quantity)?,
+            price: market.to_price(price)?,
+            is_ask: u8::from(order.side == Side::Sell),
+            order_type,
+            time_in_force,
+            reduce_only: u8::from(order.reduce_only.unwrap_or(false)),
+            trigger_price,
+            order_expiry: if time_in_force == tx::TIF_IMMEDIATE_OR_CANCEL {
+                0
+            } else {
+                now.timestamp_millis() + tx::ORDER_EXPIRY_MS
+            },
+            expired_at: now.timestamp_millis() + tx::TX_EXPIRY_MS,
+            nonce: signer.next_nonce().await?,
+        };
+
+        let response = signer.send_tx(tx::TYPE_CREATE_ORDER, &create).await?;
+        info!(
+            "Placed Lighter {:?} order {} for {} (tx {})",
+            order.side, client_order_index, order.symbol, response.tx_hash
+        );
+
+        // The order index is assigned when the transaction is executed, so the
+        // client order index identifies the order until then
+        Ok(Order {
+            order_id: client_order_index.to_string(),
+            client_order_id: Some(client_order_index.to_string()),
+            symbol: order.symbol,
+            side: order.side,
+            order_type: order.order_type,
+            time_in_force: order.time_in_force.unwrap_or(TimeInForce::GoodTilCancel),
+            quantity: order.quantity,
+            price: Some(price),
+            stop_price: order.stop_price,
+            status: OrderStatus::Pending,
+            filled_quantity: Decimal::ZERO,
+            average_price: None,
+            created_at: now,
+            updated_at: now,
+            reduce_only: order.reduce_only,
+            position_side: order.position_side,
+        })
     }
 
     async fn place_market_order(
@@ -66,21 +370,67 @@ impl Trading for LighterTrading {
     }
 
     async fn cancel_order(&self, symbol: &Symbol, order_id: &str) -> Result<Order> {
-        self.rate_limiter.wait().await;
+        let market = self.markets.get(symbol).await?;
+        let mut order = self.find_open_order(Some(symbol), order_id).await?;
 
-        // TODO: Implement order cancellation
-        todo!(
-            "Implement Lighter cancel_order for {} order {}",
-            symbol,
-            order_id
-        )
+        // Cancels are signed with the order index, whichever id the caller has
+        self.send_cancel(&market, &order.order_id).await?;
+
+        order.status = OrderStatus::Cancelled;
+        order.updated_at = Utc::now();
+        Ok(order)
     }
 
     async fn cancel_all_orders(&self, symbol: Option<&Symbol>) -> Result<Vec<Order>> {
-        self.rate_limiter.wait().await;
+        let signer = self.signer()?;
+        let mut orders = self.get_open_orders(symbol).await?;
 
-        // TODO: Implement cancel all orders
-        todo!("Implement Lighter cancel_all_orders")
+        match symbol {
+            // Lighter's cancel-all is account wide, so cancel one by one for a single market.
+            // Every order is attempted; only a market where none cancel is an error.
+            Some(symbol) => {
+                let market = self.markets.get(symbol).await?;
+                let mut cancelled = Vec::with_capacity(orders.len());
+                let mut first_error = None;
+                for order in orders {
+                    match self.send_cancel(&market, &order.order_id).await {
+                        Ok(()) => cancelled.push(order),
+                        Err(e) => {
+                            warn!("Failed to cancel Lighter order {}: {}", order.order_id, e);
+                            first_error.get_or_insert(e);
+                        }
+                    }
+                }
+                if cancelled.is_empty() {
+                    if let Some(e) = first_error {
+                        return Err(e);
+                    }
+                }
+                orders = cancelled;
+            }
+            None => {
+                let now = Utc::now().timestamp_millis();
+                let cancel_all = CancelAllOrdersTx {
+                    account_index: signer.account_index().await?,
+                    api_key_index: signer.api_key_index(),
+                    time_in_force: tx::CANCEL_ALL_IMMEDIATE,
+                    time: 0,
+                    expired_at: now + tx::TX_EXPIRY_MS,
+                    nonce: signer.next_nonce().await?,
+                };
+                let response = signer
+                    .send_tx(tx::TYPE_CANCEL_ALL_ORDERS, &cancel_all)
+                    .await?;
+                info!("Cancelled all Lighter orders (tx {})", response.tx_hash);
+            }
+        }
+
+        let now = Utc::now();
+        for order in &mut orders {
+            order.status = OrderStatus::Cancelled;
+            order.updated_at = now;
+        }
+        Ok(orders)
     }
 
     async fn modify_order(
@@ -88,34 +438,74 @@ impl Trading for LighterTrading {
         order_id: &str,
         modifications: OrderModification,
     ) -> Result<Order> {
-        self.rate_limiter.wait().await;
+        let signer = self.signer()?;
+        let mut order = self.find_open_order(None, order_id).await?;
+        let market = self.markets.get(&order.symbol).await?;
+
+        let quantity = modifications
+            .quantity
+            .unwrap_or_else(|| order.remaining_quantity());
+        let price = modifications.price.or(order.price).ok_or_else(|| {
+            ExchangeError::InvalidOrder(format!("Order {order_id} has no price to keep"))
+        })?;
+        let stop_price = modifications.stop_price.or(order.stop_price);
+
+        let modify = ModifyOrderTx {
+            account_index: signer.account_index().await?,
+            api_key_index: signer.api_key_index(),
+            market_index: market.market_id,
+            index: parse_order_index(&order.order_id)?,
+            base_amount: market.to_base_amount(quantity)?,
+            price: market.to_price(price)?,
+            trigger_price: stop_price
+                .map(|p| market.to_price(p))
+                .transpose()?
+                .unwrap_or(0),
+            expired_at: Utc::now().timestamp_millis() + tx::TX_EXPIRY_MS,
+            nonce: signer.next_nonce().await?,
+        };
+
+        let response = signer.send_tx(tx::TYPE_MODIFY_ORDER, &modify).await?;
+        info!(
+            "Modified Lighter order {} (tx {})",
+            order_id, response.tx_hash
+        );
 
-        // TODO: Implement order modification
-        todo!("Implement Lighter modify_order for {}", order_id)
+        order.quantity = order.filled_quantity + quantity;
+        order.price = Some(price);
+        order.stop_price = stop_price;
+        order.updated_at = Utc::now();
+        Ok(order)
     }
 
     async fn get_order(&self, symbol: &Symbol, order_id: &str) -> Result<Order> {
-        self.rate_limiter.wait().await;
+        if let Some(order) = self
+            .get_open_orders(Some(symbol))
+            .await?
+            .into_iter()
+            .find(|o| matches_id(o, order_id))
+        {
+            return Ok(order);
+        }
 
-        // TODO: Implement get order
-        todo!(
-            "Implement Lighter get_order for {} order {}",
-            symbol,
-            order_id
-        )
+        self.get_order_history(symbol, None)
+            .await?
+            .into_iter()
+            .find(|o| matches_id(o, order_id))
+            .ok_or_else(|| ExchangeError::OrderNotFound(order_id.to_string()))
     }
 
     async fn get_open_orders(&self, symbol: Option<&Symbol>) -> Result<Vec<Order>> {
-        self.rate_limiter.wait().await;
-
-        // TODO: Implement get open orders
-        todo!("Implement Lighter get_open_orders")
+        self.fetch_orders(endpoints::ACCOUNT_ACTIVE_ORDERS, symbol, None)
+            .await
     }
 
     async fn get_order_history(&self, symbol: &Symbol, limit: Option<usize>) -> Result<Vec<Order>> {
-        self.rate_limiter.wait().await;
-
-        // TODO: Implement order history
-        todo!("Implement Lighter get_order_history for {}", symbol)
+        self.fetch_orders(
+            endpoints::ACCOUNT_INACTIVE_ORDERS,
+            Some(symbol),
+            Some(limit.unwrap_or(100)),
+        )
+        .await
     }
 }
@@ -0,0 +1,689 @@
+//! Tests for Lighter trading implementation
+
+#[cfg(test)]
+mod tests {
+    use super::super::markets::LighterMarkets;
+    use super::super::signer::LighterSigner;
+    use super::super::trading::*;
+
+    use crate::auth::EvmWalletAuth;
+    use crate::common::{RateLimiter, RestClient};
+    use crate::traits::Trading;
+    use crate::types::{
+        ExchangeError, NewOrder, OrderModification, OrderStatus, OrderType, Price, Side, Symbol,
+        TimeInForce,
+    };
+
+    use mockito::{Matcher, Mock, Server};
+    use rust_decimal_macros::dec;
+    use std::sync::Arc;
+
+    const TEST_PRIVATE_KEY: &str =
+        "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    const ACCOUNT_INDEX: i64 = 42;
+
+    const ORDER_BOOKS: &str = r#"{
+        "code": 200,
+        "order_books": [
+            {
+                "symbol": "ETH",
+                "market_id": 0,
+                "status": "active",
+                "taker_fee": "0.0000",
+                "maker_fee": "0.0000",
+                "liquidation_fee": "1.0000",
+                "min_base_amount": "0.0050",
+                "min_quote_amount": "10.000000",
+                "order_quote_limit": "",
+                "supported_size_decimals": 4,
+                "supported_price_decimals": 2,
+                "supported_quote_decimals": 6
+            },
+            {
+                "symbol": "BTC",
+                "market_id": 1,
+                "status": "active",
+                "taker_fee": "0.0000",
+                "maker_fee": "0.0000",
+                "liquidation_fee": "1.0000",
+                "min_base_amount": "0.00020",
+                "min_quote_amount": "10.000000",
+                "order_quote_limit": "",
+                "supported_size_decimals": 5,
+                "supported_price_decimals": 1,
+                "supported_quote_decimals": 6
+            }
+        ]
+    }"#;
+
+    const ACTIVE_ORDERS: &str = r#"{
+        "code": 200,
+        "orders": [
+            {
+                "order_index": 281474976710657,
+                "client_order_index": 55,
+                "order_id": "281474976710657",
+                "client_order_id": "55",
+                "market_index": 0,
+                "owner_account_index": 42,
+                "initial_base_amount": "0.5000",
+                "remaining_base_amount": "0.3000",
+                "filled_base_amount": "0.2000",
+                "filled_quote_amount": "600.000000",
+                "price": "3000.00",
+                "nonce": 6,
+                "is_ask": false,
+                "side": "buy",
+                "type": "limit",
+                "time_in_force": "good-till-time",
+                "reduce_only": false,
+                "trigger_price": "0.00",
+                "order_expiry": 1731456000000,
+                "status": "open",
+                "trigger_status": "na",
+                "block_height": 38293,
+                "timestamp": 1729000000
+            }
+        ]
+    }"#;
+
+    const INACTIVE_ORDERS: &str = r#"{
+        "code": 200,
+        "next_cursor": "",
+        "orders": [
+            {
+                "order_index": 281474976710600,
+                "client_order_index": 12,
+                "order_id": "281474976710600",
+                "client_order_id": "12",
+                "market_index": 0,
+                "owner_account_index": 42,
+                "initial_base_amount": "1.0000",
+                "remaining_base_amount": "0.0000",
+                "filled_base_amount": "1.0000",
+                "filled_quote_amount": "2990.500000",
+                "price": "3000.00",
+                "nonce": 2,
+                "is_ask": true,
+                "side": "sell",
+                "type": "market",
+                "time_in_force": "immediate-or-cancel",
+                "reduce_only": true,
+                "trigger_price": "0.00",
+                "order_expiry": 0,
+                "status": "filled",
+                "trigger_status": "na",
+                "block_height": 38100,
+                "timestamp": 1728990000
+            }
+        ]
+    }"#;
+
+    const TX_OK: &str = r#"{
+        "code": 200,
+        "message": "{\"ratelimit\": \"didn't use volume quota\"}",
+        "tx_hash": "0b8e8b5a6e7c4d2a",
+        "predicted_execution_time_ms": 1729000000123
+    }"#;
+
+    /// Helper to create a trading component for an authenticated account
+    async fn setup_test(server: &Server) -> LighterTrading {
+        let rest_client =
+            Arc::new(RestClient::new(server.url(), std::time::Duration::from_secs(30)).unwrap());
+        let rate_limiter = Arc::new(RateLimiter::new(100, std::time::Duration::from_secs(1)));
+
+        let signer = Arc::new(LighterSigner::new(
+            Arc::clone(&rest_client),
+            Arc::clone(&rate_limiter),
+            EvmWalletAuth::new(TEST_PRIVATE_KEY),
+        ));
+        signer.set_account_index(ACCOUNT_INDEX).await;
+
+        let markets = Arc::new(LighterMarkets::new(
+            Arc::clone(&rest_client),
+            Arc::clone(&rate_limiter),
+        ));
+
+        LighterTrading::new(rest_client, rate_limiter, Some(signer), markets)
+    }
+
+    /// Matcher for a URL-encoded `"Field":value` pair inside `tx_info`
+    fn tx_field(field: &str, value: i64) -> Matcher {
+        Matcher::Regex(format!("%22{field}%22%3A{value}(%2C|%7D)"))
+    }
+
+    async fn mock_order_books(server: &mut Server) -> Mock {
+        server
+            .mock("GET", "/api/v1/orderBooks")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ORDER_BOOKS)
+            .create_async()
+            .await
+    }
+
+    async fn mock_next_nonce(server: &mut Server, nonce: i64, hits: usize) -> Mock {
+        server
+            .mock("GET", "/api/v1/nextNonce")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("account_index".into(), ACCOUNT_INDEX.to_string()),
+                Matcher::UrlEncoded("api_key_index".into(), "0".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(r#"{{"code": 200, "nonce": {nonce}}}"#))
+            .expect(hits)
+            .create_async()
+            .await
+    }
+
+    async fn mock_active_orders(server: &mut Server) -> Mock {
+        server
+            .mock("GET", "/api/v1/accountActiveOrders")
+            .match_query(Matcher::UrlEncoded(
+                "account_index".into(),
+                ACCOUNT_INDEX.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ACTIVE_ORDERS)
+            .create_async()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_place_limit_order_signs_and_tracks_nonce() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let nonce = mock_next_nonce(&mut server, 7, 1).await;
+
+        let first = server
+            .mock("POST", "/api/v1/sendtx")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("tx_type".into(), "14".into()),
+                tx_field("AccountIndex", ACCOUNT_INDEX),
+                tx_field("MarketIndex", 0),
+                tx_field("BaseAmount", 5_000),
+                tx_field("Price", 301_235),
+                tx_field("IsAsk", 0),
+                tx_field("Type", 0),
+                tx_field("TimeInForce", 1),
+                tx_field("Nonce", 7),
+                Matcher::Regex("%22Sig%22%3A%220x[0-9a-f]{130}%22".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(TX_OK)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let second = server
+            .mock("POST", "/api/v1/sendtx")
+            .match_body(Matcher::AllOf(vec![
+                tx_field("IsAsk", 1),
+                tx_field("Nonce", 8),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(TX_OK)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server).await;
+        let eth = Symbol::from("ETH");
+
+        let order = trading
+            .place_limit_order(&eth, Side::Buy, Price::from(3012.345), dec!(0.5))
+            .await
+            .unwrap();
+        assert_eq!(order.status, OrderStatus::Pending);
+        assert_eq!(order.symbol, eth);
+        assert_eq!(order.order_id, order.client_order_id.clone().unwrap());
+
+        trading
+            .place_limit_order(&eth, Side::Sell, Price::from(3100.0), dec!(0.5))
+            .await
+            .unwrap();
+
+        nonce.assert_async().await;
+        first.assert_async().await;
+        second.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_nonce_resyncs_after_rejection() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let nonce = mock_next_nonce(&mut server, 7, 2).await;
+
+        let _rejected = server
+            .mock("POST", "/api/v1/sendtx")
+            .with_status(400)
+            .with_body(r#"{"code": 21104, "message": "invalid nonce"}"#)
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server).await;
+        let eth = Symbol::from("ETH");
+
+        for _ in 0..2 {
+            let result = trading
+                .place_limit_order(&eth, Side::Buy, Price::from(3000.0), dec!(0.5))
+                .await;
+            assert!(matches!(result, Err(ExchangeError::InvalidRequest(_))));
+        }
+
+        // Each rejected transaction forces a fresh nonce lookup
+        nonce.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_place_market_order_uses_slippage_bound() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let _nonce = mock_next_nonce(&mut server, 1, 1).await;
+
+        let _details = server
+            .mock("GET", "/api/v1/orderBookDetails")
+            .match_query(Matcher::UrlEncoded("market_id".into(), "1".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                "code": 200,
+                "order_book_details": [
+                    {
+                        "symbol": "BTC",
+                        "market_id": 1,
+                        "status": "active",
+                        "taker_fee": "0.0000",
+                        "maker_fee": "0.0000",
+                        "liquidation_fee": "1.0000",
+                        "min_base_amount": "0.00020",
+                        "min_quote_amount": "10.000000",
+                        "order_quote_limit": "",
+                        "supported_size_decimals": 5,
+                        "supported_price_decimals": 1,
+                        "supported_quote_decimals": 6,
+                        "size_decimals": 5,
+                        "price_decimals": 1,
+                        "quote_multiplier": 1,
+                        "default_initial_margin_fraction": 500,
+                        "min_initial_margin_fraction": 200,
+                        "maintenance_margin_fraction": 120,
+                        "closeout_margin_fraction": 80,
+                        "last_trade_price": 60000.0,
+                        "daily_trades_count": 1000,
+                        "daily_base_token_volume": 12.5,
+                        "daily_quote_token_volume": 750000.0,
+                        "daily_price_low": 59000.0,
+                        "daily_price_high": 61000.0,
+                        "daily_price_change": 1.2,
+                        "open_interest": 100.0
+                    }
+                ]
+            }"#,
+            )
+            .create_async()
+            .await;
+
+        let send = server
+            .mock("POST", "/api/v1/sendtx")
+            .match_body(Matcher::AllOf(vec![
+                tx_field("MarketIndex", 1),
+                tx_field("Type", 1),
+                tx_field("TimeInForce", 0),
+                tx_field("OrderExpiry", 0),
+                tx_field("BaseAmount", 1_000),
+                // 60,000 - 5% worst price for a sell
+                tx_field("Price", 570_000),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(TX_OK)
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server).await;
+        let order = trading
+            .place_market_order(&Symbol::from("BTC"), Side::Sell, dec!(0.01))
+            .await
+            .unwrap();
+
+        assert_eq!(order.order_type, OrderType::Market);
+        send.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_place_order_validation() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let trading = setup_test(&server).await;
+
+        let mut fok = NewOrder::limit(Symbol::from("ETH"), Side::Buy, Price::from(3000.0), dec!(1));
+        fok.time_in_force = Some(TimeInForce::FillOrKill);
+        assert!(matches!(
+            trading.place_order(fok).await,
+            Err(ExchangeError::Unsupported(_))
+        ));
+
+        let zero = NewOrder::limit(Symbol::from("ETH"), Side::Buy, Price::from(3000.0), dec!(0));
+        assert!(matches!(
+            trading.place_order(zero).await,
+            Err(ExchangeError::InvalidOrder(_))
+        ));
+
+        let unknown = NewOrder::market(Symbol::from("DOGE"), Side::Buy, dec!(1));
+        assert!(matches!(
+            trading.place_order(unknown).await,
+            Err(ExchangeError::MarketNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_trading_fails() {
+        let server = Server::new_async().await;
+        let rest_client =
+            Arc::new(RestClient::new(server.url(), std::time::Duration::from_secs(30)).unwrap());
+        let rate_limiter = Arc::new(RateLimiter::new(100, std::time::Duration::from_secs(1)));
+        let markets = Arc::new(LighterMarkets::new(
+            Arc::clone(&rest_client),
+            Arc::clone(&rate_limiter),
+        ));
+        let trading = LighterTrading::new(rest_client, rate_limiter, None, markets);
+
+        let result = trading
+            .place_market_order(&Symbol::from("ETH"), Side::Buy, dec!(1))
+            .await;
+        assert!(matches!(result, Err(ExchangeError::Authentication(_))));
+
+        let result = trading.get_open_orders(None).await;
+        assert!(matches!(result, Err(ExchangeError::Authentication(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_open_orders() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let _orders = mock_active_orders(&mut server).await;
+
+        let trading = setup_test(&server).await;
+        let orders = trading
+            .get_open_orders(Some(&Symbol::from("ETH")))
+            .await
+            .unwrap();
+
+        assert_eq!(orders.len(), 1);
+        let order = &orders[0];
+        assert_eq!(order.order_id, "281474976710657");
+        assert_eq!(order.client_order_id.as_deref(), Some("55"));
+        assert_eq!(order.symbol, Symbol::from("ETH"));
+        assert_eq!(order.side, Side::Buy);
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+        assert_eq!(order.quantity, dec!(0.5));
+        assert_eq!(order.filled_quantity, dec!(0.2));
+        assert_eq!(order.price, Some(Price::from(3000.0)));
+        assert_eq!(order.average_price, Some(Price::from(3000.0)));
+        assert_eq!(order.stop_price, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_order_falls_back_to_history() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let _active = mock_active_orders(&mut server).await;
+        let _inactive = server
+            .mock("GET", "/api/v1/accountInactiveOrders")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("account_index".into(), ACCOUNT_INDEX.to_string()),
+                Matcher::UrlEncoded("market_id".into(), "0".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(INACTIVE_ORDERS)
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server).await;
+        let eth = Symbol::from("ETH");
+
+        // By client order index
+        let order = trading.get_order(&eth, "12").await.unwrap();
+        assert_eq!(order.order_id, "281474976710600");
+        assert_eq!(order.status, OrderStatus::Filled);
+        assert_eq!(order.order_type, OrderType::Market);
+        assert_eq!(order.side, Side::Sell);
+        assert_eq!(order.reduce_only, Some(true));
+        assert_eq!(order.average_price, Some(Price::from(2990.5)));
+
+        assert!(matches!(
+            trading.get_order(&eth, "999").await,
+            Err(ExchangeError::OrderNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_order() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let _orders = mock_active_orders(&mut server).await;
+        let _nonce = mock_next_nonce(&mut server, 9, 1).await;
+
+        let cancel = server
+            .mock("POST", "/api/v1/sendtx")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("tx_type".into(), "15".into()),
+                tx_field("MarketIndex", 0),
+                tx_field("Index", 281_474_976_710_657),
+                tx_field("Nonce", 9),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(TX_OK)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server).await;
+        let order = trading
+            .cancel_order(&Symbol::from("ETH"), "281474976710657")
+            .await
+            .unwrap();
+
+        assert_eq!(order.status, OrderStatus::Cancelled);
+        cancel.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_cancel_order_by_placed_id() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let _orders = mock_active_orders(&mut server).await;
+        let _nonce = mock_next_nonce(&mut server, 9, 1).await;
+
+        let create = server
+            .mock("POST", "/api/v1/sendtx")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("tx_type".into(), "14".into()),
+                tx_field("ClientOrderIndex", 55),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(TX_OK)
+            .expect(1)
+            .create_async()
+            .await;
+        let cancel = server
+            .mock("POST", "/api/v1/sendtx")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("tx_type".into(), "15".into()),
+                tx_field("Index", 281_474_976_710_657),
+                tx_field("Nonce", 10),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(TX_OK)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server).await;
+        let eth = Symbol::from("ETH");
+        let mut new_order = NewOrder::limit(eth.clone(), Side::Buy, Price::from(3000.0), dec!(0.5));
+        new_order.client_order_id = Some("55".to_string());
+        let placed = trading.place_order(new_order).await.unwrap();
+        assert_eq!(placed.order_id, "55");
+
+        // The placed id is the client index; the cancel targets the order index
+        let order = trading.cancel_order(&eth, &placed.order_id).await.unwrap();
+
+        assert_eq!(order.order_id, "281474976710657");
+        assert_eq!(order.status, OrderStatus::Cancelled);
+        create.assert_async().await;
+        cancel.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_cancel_all_orders() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let _orders = mock_active_orders(&mut server).await;
+        let _nonce = mock_next_nonce(&mut server, 3, 1).await;
+
+        let cancel_all = server
+            .mock("POST", "/api/v1/sendtx")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("tx_type".into(), "16".into()),
+                tx_field("TimeInForce", 0),
+                tx_field("Nonce", 3),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(TX_OK)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server).await;
+        let orders = trading.cancel_all_orders(None).await.unwrap();
+
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].status, OrderStatus::Cancelled);
+        cancel_all.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_cancel_all_orders_of_market_survives_a_failure() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        // The rejected cancel forces a fresh nonce lookup
+        let _nonce = mock_next_nonce(&mut server, 9, 2).await;
+
+        let mut active: serde_json::Value = serde_json::from_str(ACTIVE_ORDERS).unwrap();
+        let template = active["orders"][0].clone();
+        let indices = [
+            281_474_976_710_657_i64,
+            281_474_976_710_658,
+            281_474_976_710_659,
+        ];
+        active["orders"] = indices
+            .iter()
+            .map(|index| {
+                let mut order = template.clone();
+                order["order_index"] = (*index).into();
+                order["order_id"] = index.to_string().into();
+                order
+            })
+            .collect();
+        let _orders = server
+            .mock("GET", "/api/v1/accountActiveOrders")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(active.to_string())
+            .create_async()
+            .await;
+
+        let mut cancels = Vec::new();
+        for index in indices {
+            let (status, body) = if index == indices[1] {
+                (400, r#"{"code": 21120, "message": "order not found"}"#)
+            } else {
+                (200, TX_OK)
+            };
+            let cancel = server
+                .mock("POST", "/api/v1/sendtx")
+                .match_body(Matcher::AllOf(vec![
+                    Matcher::UrlEncoded("tx_type".into(), "15".into()),
+                    tx_field("Index", index),
+                ]))
+                .with_status(status)
+                .with_header("content-type", "application/json")
+                .with_body(body)
+                .expect(1)
+                .create_async()
+                .await;
+            cancels.push(cancel);
+        }
+
+        let trading = setup_test(&server).await;
+        let orders = trading
+            .cancel_all_orders(Some(&Symbol::from("ETH")))
+            .await
+            .unwrap();
+
+        // Every cancel was sent; only the successful ones come back
+        for cancel in &cancels {
+            cancel.assert_async().await;
+        }
+        let ids: Vec<&str> = orders.iter().map(|order| order.order_id.as_str()).collect();
+        assert_eq!(ids, ["281474976710657", "281474976710659"]);
+        assert!(orders
+            .iter()
+            .all(|order| order.status == OrderStatus::Cancelled));
+    }
+
+    #[tokio::test]
+    async fn test_modify_order() {
+        let mut server = Server::new_async().await;
+        let _books = mock_order_books(&mut server).await;
+        let _orders = mock_active_orders(&mut server).await;
+        let _nonce = mock_next_nonce(&mut server, 4, 1).await;
+
+        let modify = server
+            .mock("POST", "/api/v1/sendtx")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("tx_type".into(), "17".into()),
+                tx_field("Index", 281_474_976_710_657),
+                // Remaining 0.3 ETH kept, price moved to 2950.00
+                tx_field("BaseAmount", 3_000),
+                tx_field("Price", 295_000),
+                tx_field("TriggerPrice", 0),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(TX_OK)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server).await;
+        let order = trading
+            .modify_order(
+                "55",
+                OrderModification {
+                    quantity: None,
+                    price: Some(Price::from(2950.0)),
+                    stop_price: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(order.price, Some(Price::from(2950.0)));
+        assert_eq!(order.quantity, dec!(0.5));
+        modify.assert_async().await;
+    }
+}
@@ -144,50 +144,127 @@ pub struct LighterTrade {
     pub timestamp: DateTime<Utc>,
 }
 
-/// Lighter account response
+/// Wrapper for accountsByL1Address endpoint
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LighterAccount {
-    pub account_id: String,
+pub struct AccountsByL1Data {
     pub l1_address: String,
-    pub balances: Vec<LighterBalance>,
+    pub sub_accounts: Vec<LighterAccountSummary>,
 }
 
+/// Account summary (from /accountsByL1Address)
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LighterBalance {
-    pub token_id: String,
-    pub available: String,
-    pub locked: String,
+pub struct LighterAccountSummary {
+    pub index: i64,
+    pub l1_address: String,
+    pub account_type: u8,
+}
+
+/// Wrapper for account endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountData {
+    pub total: u64,
+    pub accounts: Vec<LighterAccountDetails>,
+}
+
+/// Lighter account details (from /account)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterAccountDetails {
+    pub index: i64,
+    pub l1_address: String,
+    pub account_type: u8,
+    pub status: u8, // 1 = active
+    pub collateral: String,
+    pub available_balance: String,
+    pub total_asset_value: String,
+    pub positions: Vec<LighterPosition>,
 }
 
-/// Lighter position response
+/// Lighter position (nested in account details)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LighterPosition {
-    pub order_book_id: String,
-    pub size: String,
-    pub entry_price: String,
-    pub mark_price: String,
+    pub market_id: u64,
+    pub symbol: String,
+    pub initial_margin_fraction: String, // percent, e.g. "10.00" = 10x
+    pub sign: i8,                        // 1 = long, -1 = short
+    pub position: String,
+    pub avg_entry_price: String,
+    pub position_value: String,
     pub unrealized_pnl: String,
     pub realized_pnl: String,
-    pub margin: String,
-    pub leverage: i32,
+    pub liquidation_price: String,
+    pub margin_mode: u8, // 0 = cross, 1 = isolated
+    pub allocated_margin: String,
 }
 
-/// Lighter active order response (for account orders)
+/// Wrapper for nextNonce endpoint
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LighterActiveOrder {
-    pub id: String,
-    pub order_book_id: String,
-    pub client_order_id: Option<String>,
-    pub side: String,       // "buy" or "sell"
-    pub order_type: String, // "limit" or "market"
-    pub price: Option<String>,
+pub struct NextNonceData {
+    pub nonce: i64,
+}
+
+/// Response from sendTx
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendTxData {
+    #[serde(default)]
+    pub message: Option<String>,
+    pub tx_hash: String,
+}
+
+/// Wrapper for accountActiveOrders / accountInactiveOrders endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountOrdersData {
+    pub orders: Vec<LighterAccountOrder>,
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// Order owned by the account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterAccountOrder {
+    pub order_index: i64,
+    pub client_order_index: i64,
+    pub market_index: u64,
+    pub initial_base_amount: String,
+    pub remaining_base_amount: String,
+    pub filled_base_amount: String,
+    pub filled_quote_amount: String,
+    pub price: String,
+    pub is_ask: bool,
+    #[serde(rename = "type")]
+    pub order_type: String, // "limit", "market", "stop-loss", ...
+    pub time_in_force: String, // "good-till-time", "immediate-or-cancel", "post-only"
+    pub reduce_only: bool,
+    pub trigger_price: String,
+    pub status: String, // "open", "filled", "canceled", ...
+    pub timestamp: i64, // seconds
+}
+
+/// Wrapper for account trades
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountTradesData {
+    pub trades: Vec<LighterAccountTrade>,
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// Trade involving the account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterAccountTrade {
+    pub trade_id: u64,
+    pub market_id: u64,
     pub size: String,
-    pub filled_size: String,
-    pub status: String,
-    #[serde(with = "chrono::serde::ts_milliseconds")]
-    pub created_at: DateTime<Utc>,
+    pub price: String,
+    pub ask_id: i64,
+    pub bid_id: i64,
+    pub ask_account_id: i64,
+    pub bid_account_id: i64,
+    pub is_maker_ask: bool,
+    #[serde(default)]
+    pub taker_fee: Option<u32>, // millionths of notional
+    #[serde(default)]
+    pub maker_fee: Option<u32>, // millionths of notional
     #[serde(with = "chrono::serde::ts_milliseconds")]
-    pub updated_at: DateTime<Utc>,
+    pub timestamp: DateTime<Utc>,
 }
 
 /// Lighter funding rate response
@@ -201,16 +278,176 @@ pub struct LighterFundingRate {
     pub next_funding_time: DateTime<Utc>,
 }
 
-/// Lighter order request
+/// Signed create-order transaction body
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LighterOrderRequest {
-    pub order_book_id: String,
-    pub side: String,
-    pub order_type: String,
-    pub price: Option<String>,
+#[serde(rename_all = "PascalCase")]
+pub struct CreateOrderTx {
+    pub account_index: i64,
+    pub api_key_index: u8,
+    pub market_index: u64,
+    pub client_order_index: i64,
+    pub base_amount: i64,
+    pub price: i64,
+    pub is_ask: u8,
+    #[serde(rename = "Type")]
+    pub order_type: u8,
+    pub time_in_force: u8,
+    pub reduce_only: u8,
+    pub trigger_price: i64,
+    pub order_expiry: i64,
+    pub expired_at: i64,
+    pub nonce: i64,
+}
+
+/// Signed cancel-order transaction body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CancelOrderTx {
+    pub account_index: i64,
+    pub api_key_index: u8,
+    pub market_index: u64,
+    pub index: i64,
+    pub expired_at: i64,
+    pub nonce: i64,
+}
+
+/// Signed cancel-all-orders transaction body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CancelAllOrdersTx {
+    pub account_index: i64,
+    pub api_key_index: u8,
+    pub time_in_force: u8,
+    pub time: i64,
+    pub expired_at: i64,
+    pub nonce: i64,
+}
+
+/// Signed modify-order transaction body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ModifyOrderTx {
+    pub account_index: i64,
+    pub api_key_index: u8,
+    pub market_index: u64,
+    pub index: i64,
+    pub base_amount: i64,
+    pub price: i64,
+    pub trigger_price: i64,
+    pub expired_at: i64,
+    pub nonce: i64,
+}
+
+// ============ WebSocket Types ============
+
+/// Channel subscription request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterWsSubscribe {
+    #[serde(rename = "type")]
+    pub msg_type: String,
+    pub channel: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<String>,
+}
+
+/// Envelope of every WebSocket frame; the payload depends on `msg_type`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterWsFrame {
+    #[serde(rename = "type", default)]
+    pub msg_type: String, // e.g. "subscribed/order_book", "update/order_book", "ping"
+    #[serde(default)]
+    pub channel: String,
+    #[serde(default)]
+    pub error: Option<LighterWsError>,
+    #[serde(flatten)]
+    pub payload: serde_json::Value,
+}
+
+/// Error reported on the WebSocket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterWsError {
+    pub code: i32,
+    pub message: String,
+}
+
+/// `order_book` channel payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterWsOrderBookData {
+    pub order_book: LighterWsOrderBook,
+    #[serde(default)]
+    pub timestamp: Option<i64>, // milliseconds
+}
+
+/// Order book snapshot (on subscribe) or changed levels (on update)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterWsOrderBook {
+    #[serde(default)]
+    pub asks: Vec<LighterWsPriceLevel>,
+    #[serde(default)]
+    pub bids: Vec<LighterWsPriceLevel>,
+    pub offset: u64,
+}
+
+/// Order book level; size "0" removes the level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterWsPriceLevel {
+    pub price: String,
     pub size: String,
-    pub client_order_id: Option<String>,
-    pub time_in_force: Option<String>,
+}
+
+/// `trade` channel payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterWsTradesData {
+    #[serde(default)]
+    pub trades: Vec<LighterTrade>,
+}
+
+/// `market_stats` channel payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterWsMarketStatsData {
+    pub market_stats: LighterMarketStats,
+}
+
+/// Rolling 24h market statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterMarketStats {
+    pub market_id: u64,
+    pub last_trade_price: String,
+    pub mark_price: String,
+    pub daily_base_token_volume: f64,
+    pub daily_quote_token_volume: f64,
+    pub daily_price_low: f64,
+    pub daily_price_high: f64,
+    pub daily_price_change: f64, // percent
+}
+
+/// `account_all_orders` channel payload, keyed by market id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterWsAccountOrdersData {
+    #[serde(default)]
+    pub orders: std::collections::HashMap<String, Vec<LighterAccountOrder>>,
+}
+
+/// `account_all` channel payload, keyed by market id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterWsAccountData {
+    #[serde(default)]
+    pub positions: std::collections::HashMap<String, LighterPosition>,
+}
+
+/// `user_stats` channel payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterWsUserStatsData {
+    pub stats: LighterUserStats,
+}
+
+/// Account collateral summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LighterUserStats {
+    pub collateral: String,
+    pub available_balance: String,
+    #[serde(default)]
+    pub portfolio_value: Option<String>,
 }
 
 /// Response indicating feature not available
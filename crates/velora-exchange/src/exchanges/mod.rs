@@ -1,9 +1,13 @@
 //! Exchange implementations
 //!
 //! This module contains concrete implementations of the Exchange trait for different platforms:
+//! - Binance (spot and USDⓈ-M futures CEX)
 //! - Lighter (zkRollup DEX)
 //! - Paradex (Starknet L2 DEX)
 
+#[cfg(feature = "binance")]
+pub mod binance;
+
 #[cfg(feature = "lighter")]
 pub mod lighter;
 
@@ -1,71 +1,165 @@
 //! Paradex account implementation
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use std::sync::Arc;
 
 use crate::{
-    auth::StarknetWalletAuth,
-    common::{RateLimiter, RestClient},
     traits::Account,
-    types::{AccountInfo, Balance, Position, Result, Symbol, TradeExecution},
+    types::{
+        AccountInfo, AccountType, Balance, ExchangeError, MarginType, Position, PositionSide,
+        Price, Result, Side, Symbol, TradeExecution,
+    },
 };
 
+use super::{endpoints, parse_decimal, signer::ParadexSigner, types::*};
+
 /// Paradex account component
 pub struct ParadexAccount {
-    rest_client: Arc<RestClient>,
-    rate_limiter: Arc<RateLimiter>,
-    auth: Option<StarknetWalletAuth>,
+    signer: Option<Arc<ParadexSigner>>,
 }
 
 impl ParadexAccount {
-    pub fn new(
-        rest_client: Arc<RestClient>,
-        rate_limiter: Arc<RateLimiter>,
-        auth: Option<StarknetWalletAuth>,
-    ) -> Self {
-        Self {
-            rest_client,
-            rate_limiter,
-            auth,
+    pub(crate) fn new(signer: Option<Arc<ParadexSigner>>) -> Self {
+        Self { signer }
+    }
+
+    /// Signer, or an error in read-only mode
+    fn signer(&self) -> Result<&ParadexSigner> {
+        self.signer.as_deref().ok_or_else(|| {
+            ExchangeError::Authentication(
+                "Paradex account access requires a Starknet wallet".to_string(),
+            )
+        })
+    }
+
+    /// Fetch the account margin summary
+    async fn fetch_summary(&self) -> Result<ParadexAccountSummary> {
+        self.signer()?.get(endpoints::ACCOUNT).await
+    }
+
+    /// Convert a Paradex position to our Position type (None if flat)
+    fn convert_position(position: ParadexPosition) -> Result<Option<Position>> {
+        let size = parse_decimal(&position.size, "position size")?;
+        if position.status == "CLOSED" || size.is_zero() {
+            return Ok(None);
         }
+
+        let parse_or_zero = |s: &str, what: &str| {
+            if s.is_empty() {
+                Ok(Decimal::ZERO)
+            } else {
+                parse_decimal(s, what)
+            }
+        };
+        let to_price = |d: Decimal| Price::from(d.to_f64().unwrap_or_default());
+
+        let entry = parse_decimal(&position.average_entry_price, "entry price")?;
+        let unrealized_pnl = parse_decimal(&position.unrealized_pnl, "unrealized pnl")?;
+        let liquidation = parse_or_zero(&position.liquidation_price, "liquidation price")?;
+        let cost = parse_or_zero(&position.cost, "position cost")?;
+        let leverage = parse_or_zero(&position.leverage, "leverage")?
+            .round()
+            .to_u32()
+            .unwrap_or(1)
+            .max(1);
+
+        Ok(Some(Position {
+            symbol: Symbol::from(position.market.as_str()),
+            side: if position.side == "SHORT" || size < Decimal::ZERO {
+                PositionSide::Short
+            } else {
+                PositionSide::Long
+            },
+            quantity: size.abs(),
+            entry_price: to_price(entry),
+            // Size is signed, so this holds for both sides
+            mark_price: to_price(entry + unrealized_pnl / size),
+            liquidation_price: (liquidation > Decimal::ZERO).then(|| to_price(liquidation)),
+            leverage,
+            unrealized_pnl,
+            realized_pnl: parse_or_zero(&position.realized_positional_pnl, "realized pnl")?,
+            margin: cost.abs() / Decimal::from(leverage),
+            // Paradex accounts are cross margined
+            margin_type: MarginType::Cross,
+            update_time: DateTime::from_timestamp_millis(position.last_updated_at)
+                .unwrap_or_else(Utc::now),
+        }))
     }
 }
 
 #[async_trait]
 impl Account for ParadexAccount {
     async fn get_account_info(&self) -> Result<AccountInfo> {
-        self.rate_limiter.wait().await;
-
-        // TODO: Implement account info retrieval
-        todo!("Implement Paradex get_account_info")
+        let summary = self.fetch_summary().await?;
+
+        Ok(AccountInfo {
+            account_type: AccountType::Futures,
+            can_trade: summary.status == "ACTIVE",
+            can_withdraw: true,
+            can_deposit: true,
+            // Fees are reported on each fill
+            maker_commission: Decimal::ZERO,
+            taker_commission: Decimal::ZERO,
+            update_time: DateTime::from_timestamp_millis(summary.updated_at)
+                .unwrap_or_else(Utc::now),
+        })
     }
 
     async fn get_balances(&self) -> Result<Vec<Balance>> {
-        self.rate_limiter.wait().await;
-
-        // TODO: Implement balances retrieval
-        todo!("Implement Paradex get_balances")
+        let summary = self.fetch_summary().await?;
+        let response: ParadexApiResponse<Vec<ParadexBalance>> =
+            self.signer()?.get(endpoints::BALANCES).await?;
+
+        // Initial margin is held against the settlement asset
+        let margin = parse_decimal(&summary.initial_margin_requirement, "initial margin")?;
+
+        let mut balances = Vec::with_capacity(response.results.len());
+        for balance in response.results {
+            let total = parse_decimal(&balance.size, "balance")?;
+            let locked = if balance.token == summary.settlement_asset {
+                margin.min(total).max(Decimal::ZERO)
+            } else {
+                Decimal::ZERO
+            };
+            balances.push(Balance {
+                asset: balance.token,
+                free: total - locked,
+                locked,
+            });
+        }
+        Ok(balances)
     }
 
     async fn get_balance(&self, asset: &str) -> Result<Balance> {
-        self.rate_limiter.wait().await;
-
-        // TODO: Implement single balance retrieval
-        todo!("Implement Paradex get_balance for {}", asset)
+        self.get_balances()
+            .await?
+            .into_iter()
+            .find(|b| b.asset.eq_ignore_ascii_case(asset))
+            .ok_or_else(|| ExchangeError::InvalidRequest(format!("No {asset} balance on Paradex")))
     }
 
     async fn get_positions(&self) -> Result<Vec<Position>> {
-        self.rate_limiter.wait().await;
-
-        // TODO: Implement positions retrieval
-        todo!("Implement Paradex get_positions")
+        let response: ParadexApiResponse<Vec<ParadexPosition>> =
+            self.signer()?.get(endpoints::POSITIONS).await?;
+
+        let mut positions = Vec::new();
+        for position in response.results {
+            if let Some(position) = Self::convert_position(position)? {
+                positions.push(position);
+            }
+        }
+        Ok(positions)
     }
 
     async fn get_position(&self, symbol: &Symbol) -> Result<Option<Position>> {
-        self.rate_limiter.wait().await;
-
-        // TODO: Implement single position retrieval
-        todo!("Implement Paradex get_position for {}", symbol)
+        Ok(self
+            .get_positions()
+            .await?
+            .into_iter()
+            .find(|p| p.symbol == *symbol))
     }
 
     async fn get_trade_history(
@@ -73,9 +167,36 @@ impl Account for ParadexAccount {
         symbol: Option<&Symbol>,
         limit: Option<usize>,
     ) -> Result<Vec<TradeExecution>> {
-        self.rate_limiter.wait().await;
+        let mut endpoint = format!("{}?page_size={}", endpoints::FILLS, limit.unwrap_or(100));
+        if let Some(symbol) = symbol {
+            endpoint.push_str(&format!("&market={symbol}"));
+        }
+
+        let response: ParadexPaginatedResponse<Vec<ParadexFill>> =
+            self.signer()?.get(&endpoint).await?;
+
+        let mut executions = Vec::with_capacity(response.results.len());
+        for fill in response.results {
+            let price = parse_decimal(&fill.price, "fill price")?;
+            executions.push(TradeExecution {
+                trade_id: fill.id,
+                order_id: fill.order_id,
+                symbol: Symbol::from(fill.market.as_str()),
+                side: if fill.side == "SELL" {
+                    Side::Sell
+                } else {
+                    Side::Buy
+                },
+                price: Price::from(price.to_f64().unwrap_or_default()),
+                quantity: parse_decimal(&fill.size, "fill size")?,
+                fee: parse_decimal(&fill.fee, "fill fee")?,
+                fee_asset: fill.fee_currency,
+                is_maker: fill.liquidity == "MAKER",
+                timestamp: DateTime::from_timestamp_millis(fill.created_at)
+                    .unwrap_or_else(Utc::now),
+            });
+        }
 
-        // TODO: Implement trade history retrieval
-        todo!("Implement Paradex get_trade_history")
+        Ok(executions)
     }
 }
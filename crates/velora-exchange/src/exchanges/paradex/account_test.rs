@@ -0,0 +1,231 @@
+//! Tests for Paradex account implementation
+
+#[cfg(test)]
+mod tests {
+    use super::super::account::*;
+    use super::super::signer::ParadexSigner;
+
+    use crate::auth::StarknetWalletAuth;
+    use crate::common::{RateLimiter, RestClient};
+    use crate::traits::Account;
+    use crate::types::{AccountType, MarginType, PositionSide, Price, Side, Symbol};
+
+    use mockito::{Matcher, Mock, Server};
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    const TEST_PRIVATE_KEY: &str =
+        "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+    const ACCOUNT_ADDRESS: &str = "0x5f2a";
+    const JWT: &str = "test-jwt-token";
+
+    /// Helper to create an account component with mocked server
+    async fn setup_test(server: &mut Server) -> (ParadexAccount, Vec<Mock>) {
+        let config = server
+            .mock("GET", "/v1/system/config")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "starknet_chain_id": "PRIVATE_SN_POTC_SEPOLIA" }).to_string())
+            .create_async()
+            .await;
+        let auth = server
+            .mock("POST", "/v1/auth")
+            .match_header("paradex-starknet-account", ACCOUNT_ADDRESS)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "jwt_token": JWT }).to_string())
+            .create_async()
+            .await;
+
+        let rest_client =
+            Arc::new(RestClient::new(server.url(), std::time::Duration::from_secs(30)).unwrap());
+        let rate_limiter = Arc::new(RateLimiter::new(100, std::time::Duration::from_secs(1)));
+
+        let signer = Arc::new(ParadexSigner::new(
+            rest_client,
+            rate_limiter,
+            StarknetWalletAuth::new(TEST_PRIVATE_KEY),
+        ));
+        signer.set_account_address(ACCOUNT_ADDRESS).await.unwrap();
+
+        (ParadexAccount::new(Some(signer)), vec![config, auth])
+    }
+
+    async fn mock_get(server: &mut Server, path: &str, body: serde_json::Value) -> Mock {
+        server
+            .mock("GET", path)
+            .match_header("authorization", format!("Bearer {JWT}").as_str())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .create_async()
+            .await
+    }
+
+    fn account_summary() -> serde_json::Value {
+        json!({
+            "account": ACCOUNT_ADDRESS,
+            "account_value": "10125.5",
+            "free_collateral": "8500",
+            "initial_margin_requirement": "1500",
+            "maintenance_margin_requirement": "750",
+            "margin_cushion": "9375.5",
+            "seq_no": 42,
+            "settlement_asset": "USDC",
+            "status": "ACTIVE",
+            "total_collateral": "10125.5",
+            "updated_at": 1700000000000i64
+        })
+    }
+
+    #[tokio::test]
+    async fn test_get_account_info_and_balances() {
+        let mut server = Server::new_async().await;
+        let (account, _mocks) = setup_test(&mut server).await;
+        let _summary = mock_get(&mut server, "/v1/account", account_summary()).await;
+        let _balances = mock_get(
+            &mut server,
+            "/v1/balance",
+            json!({
+                "results": [
+                    {"token": "USDC", "size": "10000", "last_updated_at": 1700000000000i64},
+                    {"token": "ETH", "size": "1.5", "last_updated_at": 1700000000000i64}
+                ]
+            }),
+        )
+        .await;
+
+        let info = account.get_account_info().await.unwrap();
+        assert_eq!(info.account_type, AccountType::Futures);
+        assert!(info.can_trade);
+
+        let balances = account.get_balances().await.unwrap();
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].asset, "USDC");
+        assert_eq!(balances[0].free, dec!(8500));
+        assert_eq!(balances[0].locked, dec!(1500));
+        assert_eq!(balances[1].free, dec!(1.5));
+        assert_eq!(balances[1].locked, dec!(0));
+
+        assert_eq!(account.get_balance("eth").await.unwrap().total(), dec!(1.5));
+        assert!(account.get_balance("BTC").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_positions() {
+        let mut server = Server::new_async().await;
+        let (account, _mocks) = setup_test(&mut server).await;
+        let _positions = mock_get(
+            &mut server,
+            "/v1/positions",
+            json!({
+                "results": [
+                    {
+                        "id": "p1", "market": "ETH-USD-PERP", "status": "OPEN", "side": "LONG",
+                        "size": "2", "average_entry_price": "3000", "unrealized_pnl": "100",
+                        "realized_positional_pnl": "25.5", "liquidation_price": "2400",
+                        "leverage": "5", "cost": "6000", "last_updated_at": 1700000000000i64
+                    },
+                    {
+                        "id": "p2", "market": "BTC-USD-PERP", "status": "OPEN", "side": "SHORT",
+                        "size": "-0.05", "average_entry_price": "60000", "unrealized_pnl": "50",
+                        "realized_positional_pnl": "0", "liquidation_price": "",
+                        "leverage": "", "cost": "-3000", "last_updated_at": 1700000000000i64
+                    },
+                    {
+                        "id": "p3", "market": "SOL-USD-PERP", "status": "CLOSED", "side": "LONG",
+                        "size": "0", "average_entry_price": "0", "unrealized_pnl": "0",
+                        "realized_positional_pnl": "-12", "last_updated_at": 1700000000000i64
+                    }
+                ]
+            }),
+        )
+        .await;
+
+        let positions = account.get_positions().await.unwrap();
+
+        // Closed SOL position is skipped
+        assert_eq!(positions.len(), 2);
+
+        let eth = &positions[0];
+        assert_eq!(eth.symbol, Symbol::from("ETH-USD-PERP"));
+        assert_eq!(eth.side, PositionSide::Long);
+        assert_eq!(eth.quantity, dec!(2));
+        assert_eq!(eth.entry_price, Price::from(3000.0));
+        assert_eq!(eth.mark_price, Price::from(3050.0));
+        assert_eq!(eth.liquidation_price, Some(Price::from(2400.0)));
+        assert_eq!(eth.leverage, 5);
+        assert_eq!(eth.margin, dec!(1200));
+        assert_eq!(eth.margin_type, MarginType::Cross);
+        assert_eq!(eth.realized_pnl, dec!(25.5));
+
+        let btc = &positions[1];
+        assert!(btc.is_short());
+        assert_eq!(btc.quantity, dec!(0.05));
+        // Short in profit means the mark is below entry
+        assert_eq!(btc.mark_price, Price::from(59000.0));
+        assert_eq!(btc.leverage, 1);
+        assert_eq!(btc.liquidation_price, None);
+
+        let sol = account
+            .get_position(&Symbol::from("SOL-USD-PERP"))
+            .await
+            .unwrap();
+        assert!(sol.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_trade_history() {
+        let mut server = Server::new_async().await;
+        let (account, _mocks) = setup_test(&mut server).await;
+        let _fills = server
+            .mock("GET", "/v1/fills")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("market".into(), "ETH-USD-PERP".into()),
+                Matcher::UrlEncoded("page_size".into(), "2".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "next": null,
+                    "prev": null,
+                    "results": [
+                        {
+                            "id": "f1", "order_id": "o1", "client_id": "", "market": "ETH-USD-PERP",
+                            "side": "SELL", "price": "3000", "size": "0.5", "fee": "0.15",
+                            "fee_currency": "USDC", "liquidity": "MAKER",
+                            "created_at": 1700000000000i64
+                        },
+                        {
+                            "id": "f2", "order_id": "o2", "client_id": "", "market": "ETH-USD-PERP",
+                            "side": "BUY", "price": "3010", "size": "1", "fee": "0.602",
+                            "fee_currency": "USDC", "liquidity": "TAKER",
+                            "created_at": 1700000100000i64
+                        }
+                    ]
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let trades = account
+            .get_trade_history(Some(&Symbol::from("ETH-USD-PERP")), Some(2))
+            .await
+            .unwrap();
+
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].side, Side::Sell);
+        assert!(trades[0].is_maker);
+        assert_eq!(trades[0].fee, dec!(0.15));
+        assert_eq!(trades[0].fee_asset, "USDC");
+        assert_eq!(trades[0].timestamp.timestamp_millis(), 1700000000000);
+
+        assert_eq!(trades[1].side, Side::Buy);
+        assert!(!trades[1].is_maker);
+        assert_eq!(trades[1].order_id, "o2");
+        assert_eq!(trades[1].price, Price::from(3010.0));
+    }
+}
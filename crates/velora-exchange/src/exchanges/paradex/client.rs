@@ -14,7 +14,7 @@ use crate::{
 };
 
 use super::{
-    account::ParadexAccount, endpoints, market_data::ParadexMarketData,
+    account::ParadexAccount, endpoints, market_data::ParadexMarketData, signer::ParadexSigner,
     streaming::ParadexStreaming, trading::ParadexTrading, SUPPORTED_INSTRUMENTS,
 };
 
@@ -32,6 +32,9 @@ pub struct ParadexExchange {
     /// Authentication
     auth: Option<StarknetWalletAuth>,
 
+    /// Request and order signer (None in read-only mode)
+    signer: Option<Arc<ParadexSigner>>,
+
     /// Connection status
     connected: bool,
 
@@ -74,17 +77,17 @@ impl ParadexExchange {
         let market_data =
             ParadexMarketData::new(Arc::clone(&rest_client), Arc::clone(&rate_limiter));
 
-        let trading = ParadexTrading::new(
-            Arc::clone(&rest_client),
-            Arc::clone(&rate_limiter),
-            wallet_auth.clone(),
-        );
+        let signer = wallet_auth.clone().map(|wallet| {
+            Arc::new(ParadexSigner::new(
+                Arc::clone(&rest_client),
+                Arc::clone(&rate_limiter),
+                wallet,
+            ))
+        });
+
+        let trading = ParadexTrading::new(signer.clone());
 
-        let account = ParadexAccount::new(
-            Arc::clone(&rest_client),
-            Arc::clone(&rate_limiter),
-            wallet_auth.clone(),
-        );
+        let account = ParadexAccount::new(signer.clone());
 
         let streaming = ParadexStreaming::new(Arc::clone(&ws_client), wallet_auth.clone());
 
@@ -93,6 +96,7 @@ impl ParadexExchange {
             ws_client,
             rate_limiter,
             auth: wallet_auth,
+            signer,
             connected: false,
             market_data,
             trading,
@@ -100,6 +104,26 @@ impl ParadexExchange {
             streaming,
         })
     }
+
+    /// Signer, or an error in read-only mode
+    fn signer(&self) -> Result<&ParadexSigner> {
+        self.signer.as_deref().ok_or_else(|| {
+            ExchangeError::Authentication("Paradex requires a Starknet wallet".to_string())
+        })
+    }
+
+    /// Use a specific Paradex account address instead of deriving it from the key
+    pub async fn set_account_address(&self, address: &str) -> Result<()> {
+        self.signer()?.set_account_address(address).await
+    }
+
+    /// Register the account with Paradex (needed once, before trading)
+    ///
+    /// # Arguments
+    /// * `ethereum_address` - L1 address the Starknet key was derived for
+    pub async fn onboard(&self, ethereum_address: &str) -> Result<()> {
+        self.signer()?.onboard(ethereum_address).await
+    }
 }
 
 #[async_trait]
@@ -5,15 +5,23 @@
 //! - Starknet wallet authentication
 
 mod account;
+#[cfg(test)]
+mod account_test;
 mod client;
 mod market_data;
+mod signer;
 mod streaming;
 mod trading;
+#[cfg(test)]
+mod trading_test;
+mod typed_data;
 pub(crate) mod types;
 
 pub use client::ParadexExchange;
 
-use crate::types::InstrumentType;
+use rust_decimal::Decimal;
+
+use crate::types::{ExchangeError, InstrumentType, Result};
 
 /// Paradex API endpoints
 pub(crate) mod endpoints {
@@ -23,6 +31,7 @@ pub(crate) mod endpoints {
 
     // Authentication
     pub const AUTH_JWT: &str = "/v1/auth";
+    pub const ONBOARDING: &str = "/v1/onboarding";
 
     // Market data endpoints
     pub const MARKETS: &str = "/v1/markets";
@@ -38,7 +47,8 @@ pub(crate) mod endpoints {
     pub const ORDERS: &str = "/v1/orders";
     pub const CANCEL_ORDER: &str = "/v1/orders"; // DELETE /:id
     pub const CANCEL_ALL: &str = "/v1/orders";
-    pub const MODIFY_ORDER: &str = "/v1/orders"; // PATCH /:id
+    pub const MODIFY_ORDER: &str = "/v1/orders"; // PUT /:id
+    pub const ORDERS_HISTORY: &str = "/v1/orders-history";
     pub const ALGO_ORDERS: &str = "/v1/algo-orders";
     pub const BLOCK_TRADE: &str = "/v1/block-trade";
 
@@ -46,8 +56,8 @@ pub(crate) mod endpoints {
     pub const ACCOUNT: &str = "/v1/account";
     pub const ACCOUNT_PROFILE: &str = "/v1/account/profile";
     pub const MARGIN: &str = "/v1/account/margin";
-    pub const BALANCES: &str = "/v1/account/balance";
-    pub const POSITIONS: &str = "/v1/account/positions";
+    pub const BALANCES: &str = "/v1/balance";
+    pub const POSITIONS: &str = "/v1/positions";
     pub const FILLS: &str = "/v1/fills";
     pub const FUNDING_PAYMENTS: &str = "/v1/funding";
     pub const PNL: &str = "/v1/pnl";
@@ -62,3 +72,9 @@ pub(crate) mod endpoints {
 
 /// Supported instruments on Paradex
 pub(crate) const SUPPORTED_INSTRUMENTS: &[InstrumentType] = &[InstrumentType::Perpetual];
+
+/// Parse a decimal string from a Paradex response
+pub(crate) fn parse_decimal(s: &str, what: &str) -> Result<Decimal> {
+    s.parse()
+        .map_err(|e| ExchangeError::ParseError(format!("Failed to parse {what} '{s}': {e}")))
+}
@@ -0,0 +1,279 @@
+//! Paradex request authentication and order signing
+//!
+//! Private endpoints take a short-lived JWT obtained from `POST /v1/auth` with
+//! a STARK signature over an auth request. Orders carry their own signature
+//! over the order fields. The account address is derived from the wallet's
+//! public key and the account contract class hashes in the system config,
+//! unless it is set explicitly.
+
+use chrono::Utc;
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Method,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use starknet::core::{
+    types::Felt,
+    utils::{get_contract_address, get_selector_from_name},
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::{
+    auth::StarknetWalletAuth,
+    common::{RateLimiter, RestClient},
+    types::{ExchangeError, Result},
+};
+
+use super::{
+    endpoints,
+    typed_data::{self, OrderMessage},
+    types::*,
+};
+
+/// JWTs issued by Paradex are valid for this long
+const JWT_TTL_SECS: i64 = 5 * 60;
+
+/// Refresh the JWT when less than this remains
+const JWT_REFRESH_SECS: i64 = 60;
+
+/// Validity of the auth request signature
+const AUTH_SIGNATURE_EXPIRY_SECS: i64 = 24 * 60 * 60;
+
+/// Signs requests and orders for one Paradex account
+pub(crate) struct ParadexSigner {
+    rest_client: Arc<RestClient>,
+    rate_limiter: Arc<RateLimiter>,
+    auth: StarknetWalletAuth,
+    config: Mutex<Option<ParadexSystemConfig>>,
+    account: Mutex<Option<Felt>>,
+    jwt: Mutex<Option<(String, i64)>>,
+}
+
+impl ParadexSigner {
+    pub fn new(
+        rest_client: Arc<RestClient>,
+        rate_limiter: Arc<RateLimiter>,
+        auth: StarknetWalletAuth,
+    ) -> Self {
+        Self {
+            rest_client,
+            rate_limiter,
+            auth,
+            config: Mutex::new(None),
+            account: Mutex::new(None),
+            jwt: Mutex::new(None),
+        }
+    }
+
+    /// Use a specific account address instead of deriving it from the key
+    pub async fn set_account_address(&self, address: &str) -> Result<()> {
+        let address = Felt::from_hex(address).map_err(|e| {
+            ExchangeError::Authentication(format!("Invalid Starknet address {address}: {e}"))
+        })?;
+        *self.account.lock().await = Some(address);
+        Ok(())
+    }
+
+    /// System config (fetched once)
+    async fn config(&self) -> Result<ParadexSystemConfig> {
+        let mut cached = self.config.lock().await;
+        if let Some(config) = cached.as_ref() {
+            return Ok(config.clone());
+        }
+
+        self.rate_limiter.wait().await;
+        let config: ParadexSystemConfig = self.rest_client.get(endpoints::SYSTEM_CONFIG).await?;
+
+        *cached = Some(config.clone());
+        Ok(config)
+    }
+
+    /// Starknet chain id used in the signing domain
+    pub async fn chain_id(&self) -> Result<String> {
+        Ok(self.config().await?.starknet_chain_id)
+    }
+
+    /// Public key of the wallet
+    fn public_key(&self) -> Result<Felt> {
+        self.auth
+            .public_key()
+            .map_err(|e| ExchangeError::Authentication(format!("Invalid Starknet wallet: {e}")))
+    }
+
+    /// Paradex account address (derived once from the public key)
+    pub async fn account_address(&self) -> Result<Felt> {
+        if let Some(address) = *self.account.lock().await {
+            return Ok(address);
+        }
+
+        let config = self.config().await?;
+        let parse_hash = |hash: &str, what: &str| {
+            Felt::from_hex(hash).map_err(|e| {
+                ExchangeError::Authentication(format!("Invalid {what} in system config: {e}"))
+            })
+        };
+        let proxy_hash = parse_hash(&config.paraclear_account_proxy_hash, "account proxy hash")?;
+        let account_hash = parse_hash(&config.paraclear_account_hash, "account hash")?;
+        let initialize = get_selector_from_name("initialize")
+            .map_err(|e| ExchangeError::Internal(format!("Invalid selector: {e}")))?;
+
+        // Paradex accounts are proxies initialized with the wallet public key
+        let public_key = self.public_key()?;
+        let address = get_contract_address(
+            public_key,
+            proxy_hash,
+            &[account_hash, initialize, Felt::TWO, public_key, Felt::ZERO],
+            Felt::ZERO,
+        );
+
+        debug!("Derived Paradex account {}", address.to_hex_string());
+        *self.account.lock().await = Some(address);
+        Ok(address)
+    }
+
+    /// Sign a message hash, formatted as Paradex expects (`["r","s"]`)
+    fn sign(&self, hash: Felt) -> Result<String> {
+        let (r, s) = self
+            .auth
+            .sign_hash(hash)
+            .map_err(|e| ExchangeError::Authentication(format!("Signing failed: {e}")))?;
+        Ok(format!(r#"["{r}","{s}"]"#))
+    }
+
+    /// Sign an order or order modification
+    pub async fn sign_order(&self, order: &OrderMessage<'_>) -> Result<String> {
+        let hash = typed_data::order_hash(
+            &self.chain_id().await?,
+            self.account_address().await?,
+            order,
+        )?;
+        self.sign(hash)
+    }
+
+    /// Headers carrying the account and a signature over `hash`
+    async fn signed_headers(&self, hash: Felt) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        insert_header(
+            &mut headers,
+            "paradex-starknet-account",
+            &self.account_address().await?.to_hex_string(),
+        )?;
+        insert_header(
+            &mut headers,
+            "paradex-starknet-signature",
+            &self.sign(hash)?,
+        )?;
+        Ok(headers)
+    }
+
+    /// Register the account with Paradex (needed once per account)
+    pub async fn onboard(&self, ethereum_address: &str) -> Result<()> {
+        let account = self.account_address().await?;
+        let hash = typed_data::onboarding_hash(&self.chain_id().await?, account)?;
+
+        let mut headers = self.signed_headers(hash).await?;
+        insert_header(&mut headers, "paradex-ethereum-account", ethereum_address)?;
+
+        let body = ParadexOnboardingRequest {
+            public_key: self.public_key()?.to_hex_string(),
+        };
+
+        self.rate_limiter.wait().await;
+        let _: serde_json::Value = self
+            .rest_client
+            .request(Method::POST, endpoints::ONBOARDING, Some(&body), headers)
+            .await?;
+
+        info!("Onboarded Paradex account {}", account.to_hex_string());
+        Ok(())
+    }
+
+    /// JWT for private endpoints (refreshed shortly before it expires)
+    pub async fn jwt(&self) -> Result<String> {
+        let mut cached = self.jwt.lock().await;
+        let now = Utc::now().timestamp();
+
+        if let Some((token, expiry)) = cached.as_ref() {
+            if expiry - now > JWT_REFRESH_SECS {
+                return Ok(token.clone());
+            }
+        }
+
+        let expiration = now + AUTH_SIGNATURE_EXPIRY_SECS;
+        let hash = typed_data::auth_request_hash(
+            &self.chain_id().await?,
+            self.account_address().await?,
+            now,
+            expiration,
+        )?;
+
+        let mut headers = self.signed_headers(hash).await?;
+        insert_header(&mut headers, "paradex-timestamp", &now.to_string())?;
+        insert_header(
+            &mut headers,
+            "paradex-signature-expiration",
+            &expiration.to_string(),
+        )?;
+
+        self.rate_limiter.wait().await;
+        let empty: Option<&()> = None;
+        let response: ParadexAuthResponse = self
+            .rest_client
+            .request(Method::POST, endpoints::AUTH_JWT, empty, headers)
+            .await?;
+
+        *cached = Some((response.jwt_token.clone(), now + JWT_TTL_SECS));
+        Ok(response.jwt_token)
+    }
+
+    /// Forget the cached JWT so the next request authenticates again
+    pub async fn reset_jwt(&self) {
+        *self.jwt.lock().await = None;
+    }
+
+    /// Send an authenticated request to a private endpoint
+    pub async fn request<T: DeserializeOwned, B: Serialize>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: Option<&B>,
+    ) -> Result<T> {
+        let mut headers = HeaderMap::new();
+        insert_header(
+            &mut headers,
+            "authorization",
+            &format!("Bearer {}", self.jwt().await?),
+        )?;
+
+        self.rate_limiter.wait().await;
+        let result = self
+            .rest_client
+            .request(method, endpoint, body, headers)
+            .await;
+
+        if let Err(ExchangeError::Authentication(e)) = &result {
+            warn!(
+                "Paradex rejected JWT, re-authenticating on next request: {}",
+                e
+            );
+            self.reset_jwt().await;
+        }
+        result
+    }
+
+    /// Authenticated GET request
+    pub async fn get<T: DeserializeOwned>(&self, endpoint: &str) -> Result<T> {
+        let empty: Option<&()> = None;
+        self.request(Method::GET, endpoint, empty).await
+    }
+}
+
+/// Insert a header, rejecting values that are not valid header text
+fn insert_header(headers: &mut HeaderMap, name: &'static str, value: &str) -> Result<()> {
+    let value = HeaderValue::from_str(value)
+        .map_err(|e| ExchangeError::InvalidRequest(format!("Invalid {name} header: {e}")))?;
+    headers.insert(name, value);
+    Ok(())
+}
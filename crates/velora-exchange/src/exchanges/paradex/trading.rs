@@ -1,47 +1,226 @@
 //! Paradex trading implementation
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
+use std::str::FromStr;
 use std::sync::Arc;
+use tracing::info;
 
 use crate::{
-    auth::StarknetWalletAuth,
-    common::{RateLimiter, RestClient},
     traits::Trading,
-    types::{NewOrder, Order, OrderModification, Price, Result, Side, Symbol},
+    types::{
+        ExchangeError, NewOrder, Order, OrderModification, OrderStatus, OrderType, Price, Result,
+        Side, Symbol, TimeInForce,
+    },
 };
 
+use super::{endpoints, parse_decimal, signer::ParadexSigner, typed_data::OrderMessage, types::*};
+
+/// Order flag marking reduce-only orders
+const FLAG_REDUCE_ONLY: &str = "REDUCE_ONLY";
+
 /// Paradex trading component
 pub struct ParadexTrading {
-    rest_client: Arc<RestClient>,
-    rate_limiter: Arc<RateLimiter>,
-    auth: Option<StarknetWalletAuth>,
+    signer: Option<Arc<ParadexSigner>>,
 }
 
 impl ParadexTrading {
-    pub fn new(
-        rest_client: Arc<RestClient>,
-        rate_limiter: Arc<RateLimiter>,
-        auth: Option<StarknetWalletAuth>,
-    ) -> Self {
-        Self {
-            rest_client,
-            rate_limiter,
-            auth,
+    pub(crate) fn new(signer: Option<Arc<ParadexSigner>>) -> Self {
+        Self { signer }
+    }
+
+    /// Signer, or an error in read-only mode
+    fn signer(&self) -> Result<&ParadexSigner> {
+        self.signer.as_deref().ok_or_else(|| {
+            ExchangeError::Authentication("Paradex trading requires a Starknet wallet".to_string())
+        })
+    }
+
+    /// Fetch one order by exchange id
+    async fn fetch_order(&self, order_id: &str) -> Result<ParadexOrder> {
+        let endpoint = format!("{}/{}", endpoints::ORDERS, order_id);
+        match self.signer()?.get(&endpoint).await {
+            Err(ExchangeError::ApiError { code: 404, .. }) => {
+                Err(ExchangeError::OrderNotFound(order_id.to_string()))
+            }
+            result => result,
         }
     }
 }
 
+/// Paradex side string
+fn side_str(side: Side) -> &'static str {
+    match side {
+        Side::Buy => "BUY",
+        Side::Sell => "SELL",
+    }
+}
+
+/// Exact decimal form of a price (3012.345 stays 3012.345)
+fn price_to_decimal(price: Price) -> Result<Decimal> {
+    Decimal::from_str(&price.to_string())
+        .map_err(|e| ExchangeError::InvalidOrder(format!("Invalid price {price}: {e}")))
+}
+
+/// Parse an optional decimal field ("" or "0" mean unset)
+fn parse_optional_price(s: &str, what: &str) -> Result<Option<Price>> {
+    if s.is_empty() {
+        return Ok(None);
+    }
+    let value = parse_decimal(s, what)?;
+    Ok((value > Decimal::ZERO)
+        .then(|| value.to_f64().map(Price::from))
+        .flatten())
+}
+
+/// Map Paradex order status to OrderStatus
+fn convert_status(order: &ParadexOrder, filled: Decimal, remaining: Decimal) -> OrderStatus {
+    match order.status.as_str() {
+        "NEW" => OrderStatus::Pending,
+        "OPEN" | "UNTRIGGERED" if filled > Decimal::ZERO => OrderStatus::PartiallyFilled,
+        "OPEN" | "UNTRIGGERED" => OrderStatus::Open,
+        "CLOSED" if remaining.is_zero() => OrderStatus::Filled,
+        "CLOSED" if order.cancel_reason.contains("EXPIRED") => OrderStatus::Expired,
+        "CLOSED" => OrderStatus::Cancelled,
+        _ => OrderStatus::Rejected,
+    }
+}
+
+/// Convert a Paradex order to our Order type
+fn convert_order(order: ParadexOrder) -> Result<Order> {
+    let quantity = parse_decimal(&order.size, "order size")?;
+    let remaining = parse_decimal(&order.remaining_size, "remaining size")?;
+    let filled = quantity - remaining;
+
+    let order_type = match order.order_type.as_str() {
+        "MARKET" => OrderType::Market,
+        "STOP_MARKET" | "TAKE_PROFIT_MARKET" => OrderType::StopMarket,
+        "STOP_LIMIT" | "TAKE_PROFIT_LIMIT" => OrderType::StopLimit,
+        _ => OrderType::Limit,
+    };
+
+    let time_in_force = match order.instruction.as_str() {
+        "IOC" => TimeInForce::ImmediateOrCancel,
+        "POST_ONLY" => TimeInForce::GoodTilCrossing,
+        _ => TimeInForce::GoodTilCancel,
+    };
+
+    let to_datetime = |ms: i64| DateTime::from_timestamp_millis(ms).unwrap_or_else(Utc::now);
+
+    Ok(Order {
+        status: convert_status(&order, filled, remaining),
+        order_id: order.id,
+        client_order_id: (!order.client_id.is_empty()).then_some(order.client_id),
+        symbol: Symbol::from(order.market.as_str()),
+        side: if order.side == "SELL" {
+            Side::Sell
+        } else {
+            Side::Buy
+        },
+        order_type,
+        time_in_force,
+        quantity,
+        price: parse_optional_price(&order.price, "order price")?,
+        stop_price: parse_optional_price(&order.trigger_price, "trigger price")?,
+        filled_quantity: filled,
+        average_price: parse_optional_price(&order.avg_fill_price, "average fill price")?,
+        created_at: to_datetime(order.created_at),
+        updated_at: to_datetime(order.last_updated_at),
+        reduce_only: Some(order.flags.iter().any(|f| f == FLAG_REDUCE_ONLY)),
+        position_side: None,
+    })
+}
+
 #[async_trait]
 impl Trading for ParadexTrading {
     async fn place_order(&self, order: NewOrder) -> Result<Order> {
-        self.rate_limiter.wait().await;
+        let signer = self.signer()?;
 
-        // TODO: Implement actual order placement with Starknet wallet signing
-        // 1. Create order payload
-        // 2. Sign with Starknet wallet
-        // 3. Submit to Paradex API
-        todo!("Implement Paradex place_order")
+        if order.quantity <= Decimal::ZERO {
+            return Err(ExchangeError::InvalidOrder(
+                "Quantity must be positive".to_string(),
+            ));
+        }
+
+        let limit_price = |what: &str| {
+            order
+                .price
+                .ok_or_else(|| ExchangeError::InvalidOrder(format!("{what} requires a price")))
+                .and_then(price_to_decimal)
+        };
+        let (order_type, price) = match order.order_type {
+            OrderType::Market => ("MARKET", Decimal::ZERO),
+            OrderType::Limit => ("LIMIT", limit_price("Limit order")?),
+            OrderType::StopMarket => ("STOP_MARKET", Decimal::ZERO),
+            OrderType::StopLimit => ("STOP_LIMIT", limit_price("Stop-limit order")?),
+        };
+
+        let trigger_price = match order.order_type {
+            OrderType::StopMarket | OrderType::StopLimit => {
+                let stop = order.stop_price.ok_or_else(|| {
+                    ExchangeError::InvalidOrder("Stop order requires a stop price".to_string())
+                })?;
+                Some(price_to_decimal(stop)?.normalize().to_string())
+            }
+            _ => None,
+        };
+
+        let instruction = match (order.order_type, order.time_in_force) {
+            (OrderType::Market | OrderType::StopMarket, _) => "IOC",
+            (_, Some(TimeInForce::ImmediateOrCancel)) => "IOC",
+            (_, Some(TimeInForce::GoodTilCrossing)) => "POST_ONLY",
+            (_, Some(TimeInForce::FillOrKill)) => {
+                return Err(ExchangeError::Unsupported(
+                    "Paradex does not support fill-or-kill orders".to_string(),
+                ));
+            }
+            (_, Some(TimeInForce::GoodTilCancel) | None) => "GTC",
+        };
+
+        let market = order.symbol.as_str();
+        let signature_timestamp = Utc::now().timestamp_millis();
+        let signature = signer
+            .sign_order(&OrderMessage {
+                timestamp_ms: signature_timestamp,
+                market,
+                side: order.side,
+                order_type,
+                size: order.quantity,
+                price,
+                modify_id: None,
+            })
+            .await?;
+
+        let request = ParadexOrderRequest {
+            market: market.to_string(),
+            side: side_str(order.side).to_string(),
+            order_type: order_type.to_string(),
+            size: order.quantity.normalize().to_string(),
+            price: price.normalize().to_string(),
+            instruction: instruction.to_string(),
+            client_id: order.client_order_id.clone(),
+            trigger_price,
+            flags: if order.reduce_only.unwrap_or(false) {
+                vec![FLAG_REDUCE_ONLY.to_string()]
+            } else {
+                Vec::new()
+            },
+            signature,
+            signature_timestamp,
+        };
+
+        let response: ParadexOrder = signer
+            .request(Method::POST, endpoints::ORDERS, Some(&request))
+            .await?;
+        info!(
+            "Placed Paradex {:?} order {} for {}",
+            order.side, response.id, order.symbol
+        );
+
+        convert_order(response)
     }
 
     async fn place_market_order(
@@ -66,21 +245,45 @@ impl Trading for ParadexTrading {
     }
 
     async fn cancel_order(&self, symbol: &Symbol, order_id: &str) -> Result<Order> {
-        self.rate_limiter.wait().await;
+        let mut order = convert_order(self.fetch_order(order_id).await?)?;
+        if order.symbol != *symbol {
+            return Err(ExchangeError::OrderNotFound(format!(
+                "{order_id} is not a {symbol} order"
+            )));
+        }
 
-        // TODO: Implement order cancellation
-        todo!(
-            "Implement Paradex cancel_order for {} order {}",
-            symbol,
-            order_id
-        )
+        let empty: Option<&()> = None;
+        let endpoint = format!("{}/{}", endpoints::CANCEL_ORDER, order_id);
+        self.signer()?
+            .request::<(), _>(Method::DELETE, &endpoint, empty)
+            .await?;
+        info!("Cancelled Paradex order {}", order_id);
+
+        order.status = OrderStatus::Cancelled;
+        order.updated_at = Utc::now();
+        Ok(order)
     }
 
     async fn cancel_all_orders(&self, symbol: Option<&Symbol>) -> Result<Vec<Order>> {
-        self.rate_limiter.wait().await;
+        let signer = self.signer()?;
+        let mut orders = self.get_open_orders(symbol).await?;
+
+        let endpoint = match symbol {
+            Some(symbol) => format!("{}?market={}", endpoints::CANCEL_ALL, symbol),
+            None => endpoints::CANCEL_ALL.to_string(),
+        };
+        let empty: Option<&()> = None;
+        signer
+            .request::<(), _>(Method::DELETE, &endpoint, empty)
+            .await?;
+        info!("Cancelled {} Paradex orders", orders.len());
 
-        // TODO: Implement cancel all orders
-        todo!("Implement Paradex cancel_all_orders")
+        let now = Utc::now();
+        for order in &mut orders {
+            order.status = OrderStatus::Cancelled;
+            order.updated_at = now;
+        }
+        Ok(orders)
     }
 
     async fn modify_order(
@@ -88,34 +291,92 @@ impl Trading for ParadexTrading {
         order_id: &str,
         modifications: OrderModification,
     ) -> Result<Order> {
-        self.rate_limiter.wait().await;
+        let signer = self.signer()?;
+        let order = self.fetch_order(order_id).await?;
+
+        if modifications.stop_price.is_some() {
+            return Err(ExchangeError::Unsupported(
+                "Paradex cannot modify trigger prices".to_string(),
+            ));
+        }
+
+        let size = match modifications.quantity {
+            Some(quantity) => quantity,
+            None => parse_decimal(&order.size, "order size")?,
+        };
+        let price = match modifications.price {
+            Some(price) => price_to_decimal(price)?,
+            None => parse_decimal(&order.price, "order price")?,
+        };
+        let side = if order.side == "SELL" {
+            Side::Sell
+        } else {
+            Side::Buy
+        };
+
+        let signature_timestamp = Utc::now().timestamp_millis();
+        let signature = signer
+            .sign_order(&OrderMessage {
+                timestamp_ms: signature_timestamp,
+                market: &order.market,
+                side,
+                order_type: &order.order_type,
+                size,
+                price,
+                modify_id: Some(&order.id),
+            })
+            .await?;
+
+        let request = ParadexModifyOrderRequest {
+            id: order.id.clone(),
+            market: order.market.clone(),
+            side: order.side.clone(),
+            order_type: order.order_type.clone(),
+            size: size.normalize().to_string(),
+            price: price.normalize().to_string(),
+            signature,
+            signature_timestamp,
+        };
 
-        // TODO: Implement order modification
-        todo!("Implement Paradex modify_order for {}", order_id)
+        let endpoint = format!("{}/{}", endpoints::MODIFY_ORDER, order_id);
+        let response: ParadexOrder = signer
+            .request(Method::PUT, &endpoint, Some(&request))
+            .await?;
+        info!("Modified Paradex order {}", order_id);
+
+        convert_order(response)
     }
 
     async fn get_order(&self, symbol: &Symbol, order_id: &str) -> Result<Order> {
-        self.rate_limiter.wait().await;
-
-        // TODO: Implement get order
-        todo!(
-            "Implement Paradex get_order for {} order {}",
-            symbol,
-            order_id
-        )
+        let order = convert_order(self.fetch_order(order_id).await?)?;
+        if order.symbol != *symbol {
+            return Err(ExchangeError::OrderNotFound(format!(
+                "{order_id} is not a {symbol} order"
+            )));
+        }
+        Ok(order)
     }
 
     async fn get_open_orders(&self, symbol: Option<&Symbol>) -> Result<Vec<Order>> {
-        self.rate_limiter.wait().await;
+        let endpoint = match symbol {
+            Some(symbol) => format!("{}?market={}", endpoints::ORDERS, symbol),
+            None => endpoints::ORDERS.to_string(),
+        };
 
-        // TODO: Implement get open orders
-        todo!("Implement Paradex get_open_orders")
+        let response: ParadexApiResponse<Vec<ParadexOrder>> = self.signer()?.get(&endpoint).await?;
+        response.results.into_iter().map(convert_order).collect()
     }
 
     async fn get_order_history(&self, symbol: &Symbol, limit: Option<usize>) -> Result<Vec<Order>> {
-        self.rate_limiter.wait().await;
+        let endpoint = format!(
+            "{}?market={}&page_size={}",
+            endpoints::ORDERS_HISTORY,
+            symbol,
+            limit.unwrap_or(100)
+        );
 
-        // TODO: Implement order history
-        todo!("Implement Paradex get_order_history for {}", symbol)
+        let response: ParadexPaginatedResponse<Vec<ParadexOrder>> =
+            self.signer()?.get(&endpoint).await?;
+        response.results.into_iter().map(convert_order).collect()
     }
 }
@@ -0,0 +1,504 @@
+//! Tests for Paradex trading implementation
+
+#[cfg(test)]
+mod tests {
+    use super::super::signer::ParadexSigner;
+    use super::super::trading::*;
+    use super::super::typed_data::{self, OrderMessage};
+
+    use crate::auth::StarknetWalletAuth;
+    use crate::common::{RateLimiter, RestClient};
+    use crate::traits::Trading;
+    use crate::types::{
+        ExchangeError, NewOrder, OrderModification, OrderStatus, OrderType, Price, Side, Symbol,
+        TimeInForce,
+    };
+
+    use mockito::{Matcher, Mock, Server};
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+    use starknet::core::types::Felt;
+    use starknet_crypto::verify;
+    use std::sync::Arc;
+
+    const TEST_PRIVATE_KEY: &str =
+        "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+    const CHAIN_ID: &str = "PRIVATE_SN_PARACLEAR_MAINNET";
+    const JWT: &str = "test-jwt-token";
+
+    /// Account address derived from TEST_PRIVATE_KEY and the config below
+    const EXPECTED_ADDRESS: &str =
+        "0x465c3859bfb26ad6528d78bc362e5af984b8b5194637fe87d497a31295d9e12";
+
+    fn system_config() -> String {
+        json!({
+            "starknet_chain_id": CHAIN_ID,
+            "paraclear_account_proxy_hash":
+                "0x3530cc4759d78042f1b543bf797f5f3d647cde0388c33734cf91b7f7b9314a9",
+            "paraclear_account_hash":
+                "0x41cb0280ebadaa75f996d8d92c6f265f6d040bb3ba442e5f86a554f1765244e",
+            "paraclear_decimals": 8
+        })
+        .to_string()
+    }
+
+    fn order_json(status: &str, remaining: &str) -> serde_json::Value {
+        json!({
+            "id": "1700000000000100",
+            "account": "0x1",
+            "market": "ETH-USD-PERP",
+            "side": "BUY",
+            "type": "LIMIT",
+            "size": "0.5",
+            "remaining_size": remaining,
+            "price": "3000",
+            "avg_fill_price": "",
+            "status": status,
+            "instruction": "GTC",
+            "client_id": "my-order",
+            "flags": [],
+            "cancel_reason": "",
+            "created_at": 1700000000000i64,
+            "last_updated_at": 1700000001000i64
+        })
+    }
+
+    /// Mock the system config and JWT auth endpoints
+    async fn mock_auth(server: &mut Server) -> (Mock, Mock) {
+        let config = server
+            .mock("GET", "/v1/system/config")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(system_config())
+            .create_async()
+            .await;
+
+        let auth = server
+            .mock("POST", "/v1/auth")
+            .match_header("paradex-starknet-account", Matcher::Regex("^0x".into()))
+            .match_header(
+                "paradex-starknet-signature",
+                Matcher::Regex(r#"^\["\d+","\d+"\]$"#.into()),
+            )
+            .match_header("paradex-timestamp", Matcher::Regex(r"^\d+$".into()))
+            .match_header(
+                "paradex-signature-expiration",
+                Matcher::Regex(r"^\d+$".into()),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "jwt_token": JWT }).to_string())
+            .create_async()
+            .await;
+
+        (config, auth)
+    }
+
+    fn setup_signer(server: &Server) -> Arc<ParadexSigner> {
+        let rest_client =
+            Arc::new(RestClient::new(server.url(), std::time::Duration::from_secs(30)).unwrap());
+        let rate_limiter = Arc::new(RateLimiter::new(100, std::time::Duration::from_secs(1)));
+
+        Arc::new(ParadexSigner::new(
+            rest_client,
+            rate_limiter,
+            StarknetWalletAuth::new(TEST_PRIVATE_KEY),
+        ))
+    }
+
+    fn setup_test(server: &Server) -> ParadexTrading {
+        ParadexTrading::new(Some(setup_signer(server)))
+    }
+
+    fn bearer() -> Matcher {
+        Matcher::Exact(format!("Bearer {JWT}"))
+    }
+
+    #[tokio::test]
+    async fn test_account_address_derivation() {
+        let mut server = Server::new_async().await;
+        let _auth = mock_auth(&mut server).await;
+
+        let signer = setup_signer(&server);
+        let address = signer.account_address().await.unwrap();
+        assert_eq!(address.to_hex_string(), EXPECTED_ADDRESS);
+
+        signer.set_account_address("0xabc").await.unwrap();
+        assert_eq!(
+            signer.account_address().await.unwrap(),
+            Felt::from_hex("0xabc").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_order_signature_is_fixed_and_valid() {
+        let mut server = Server::new_async().await;
+        let _auth = mock_auth(&mut server).await;
+        let signer = setup_signer(&server);
+
+        let order = OrderMessage {
+            timestamp_ms: 1_700_000_000_000,
+            market: "ETH-USD-PERP",
+            side: Side::Buy,
+            order_type: "LIMIT",
+            size: dec!(0.5),
+            price: dec!(3000),
+            modify_id: None,
+        };
+        let signature = signer.sign_order(&order).await.unwrap();
+        assert_eq!(
+            signature,
+            concat!(
+                r#"["3108483415561968056522338737940328430808143681705747519158291748725407437223","#,
+                r#""2772240936795139811818101581457004086663899138919684562009546321638721456842"]"#
+            )
+        );
+
+        // The signature verifies against the wallet's public key
+        let rs: Vec<String> = serde_json::from_str(&signature).unwrap();
+        let r = Felt::from_dec_str(&rs[0]).unwrap();
+        let s = Felt::from_dec_str(&rs[1]).unwrap();
+        let hash =
+            typed_data::order_hash(CHAIN_ID, signer.account_address().await.unwrap(), &order)
+                .unwrap();
+        let public_key = StarknetWalletAuth::new(TEST_PRIVATE_KEY)
+            .public_key()
+            .unwrap();
+        assert!(verify(&public_key, &hash, &r, &s).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_jwt_is_cached() {
+        let mut server = Server::new_async().await;
+        let (_config, auth) = mock_auth(&mut server).await;
+        let _orders = server
+            .mock("GET", "/v1/orders")
+            .match_header("authorization", bearer())
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "results": [] }).to_string())
+            .expect(2)
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server);
+        assert!(trading.get_open_orders(None).await.unwrap().is_empty());
+        assert!(trading.get_open_orders(None).await.unwrap().is_empty());
+
+        auth.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_place_limit_order() {
+        let mut server = Server::new_async().await;
+        let _auth = mock_auth(&mut server).await;
+
+        let place = server
+            .mock("POST", "/v1/orders")
+            .match_header("authorization", bearer())
+            .match_body(Matcher::AllOf(vec![
+                Matcher::PartialJson(json!({
+                    "market": "ETH-USD-PERP",
+                    "side": "BUY",
+                    "type": "LIMIT",
+                    "size": "0.5",
+                    "price": "3000",
+                    "instruction": "POST_ONLY",
+                    "client_id": "my-order",
+                    "flags": ["REDUCE_ONLY"]
+                })),
+                Matcher::Regex(r#""signature":"\[\\"\d+\\",\\"\d+\\"\]""#.into()),
+                Matcher::Regex(r#""signature_timestamp":\d+"#.into()),
+            ]))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(order_json("NEW", "0.5").to_string())
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server);
+        let mut order = NewOrder::limit(
+            Symbol::from("ETH-USD-PERP"),
+            Side::Buy,
+            Price::from(3000.0),
+            dec!(0.5),
+        );
+        order.time_in_force = Some(TimeInForce::GoodTilCrossing);
+        order.client_order_id = Some("my-order".to_string());
+        order.reduce_only = Some(true);
+
+        let placed = trading.place_order(order).await.unwrap();
+        assert_eq!(placed.order_id, "1700000000000100");
+        assert_eq!(placed.client_order_id.as_deref(), Some("my-order"));
+        assert_eq!(placed.status, OrderStatus::Pending);
+        assert_eq!(placed.price, Some(Price::from(3000.0)));
+
+        place.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_place_market_and_stop_orders() {
+        let mut server = Server::new_async().await;
+        let _auth = mock_auth(&mut server).await;
+
+        let _market = server
+            .mock("POST", "/v1/orders")
+            .match_body(Matcher::PartialJson(json!({
+                "type": "MARKET",
+                "side": "SELL",
+                "price": "0",
+                "instruction": "IOC"
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "id": "1", "market": "BTC-USD-PERP", "side": "SELL", "type": "MARKET",
+                    "size": "0.01", "remaining_size": "0.01", "price": "0", "status": "NEW",
+                    "created_at": 1700000000000i64, "last_updated_at": 1700000000000i64
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let _stop = server
+            .mock("POST", "/v1/orders")
+            .match_body(Matcher::PartialJson(json!({
+                "type": "STOP_LIMIT",
+                "price": "58000",
+                "trigger_price": "58500"
+            })))
+            .with_status(201)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "id": "2", "market": "BTC-USD-PERP", "side": "SELL", "type": "STOP_LIMIT",
+                    "size": "0.01", "remaining_size": "0.01", "price": "58000",
+                    "trigger_price": "58500", "status": "UNTRIGGERED",
+                    "created_at": 1700000000000i64, "last_updated_at": 1700000000000i64
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server);
+        let btc = Symbol::from("BTC-USD-PERP");
+
+        let market = trading
+            .place_market_order(&btc, Side::Sell, dec!(0.01))
+            .await
+            .unwrap();
+        assert_eq!(market.order_type, OrderType::Market);
+        assert_eq!(market.price, None);
+
+        let mut stop = NewOrder::limit(btc.clone(), Side::Sell, Price::from(58000.0), dec!(0.01));
+        stop.order_type = OrderType::StopLimit;
+        stop.stop_price = Some(Price::from(58500.0));
+        let stop = trading.place_order(stop).await.unwrap();
+        assert_eq!(stop.order_type, OrderType::StopLimit);
+        assert_eq!(stop.status, OrderStatus::Open);
+        assert_eq!(stop.stop_price, Some(Price::from(58500.0)));
+    }
+
+    #[tokio::test]
+    async fn test_place_order_validation() {
+        let server = Server::new_async().await;
+        let trading = setup_test(&server);
+        let eth = Symbol::from("ETH-USD-PERP");
+
+        let mut fok = NewOrder::limit(eth.clone(), Side::Buy, Price::from(3000.0), dec!(1));
+        fok.time_in_force = Some(TimeInForce::FillOrKill);
+        assert!(matches!(
+            trading.place_order(fok).await,
+            Err(ExchangeError::Unsupported(_))
+        ));
+
+        let mut no_price = NewOrder::market(eth.clone(), Side::Buy, dec!(1));
+        no_price.order_type = OrderType::Limit;
+        assert!(matches!(
+            trading.place_order(no_price).await,
+            Err(ExchangeError::InvalidOrder(_))
+        ));
+
+        assert!(trading
+            .place_market_order(&eth, Side::Buy, dec!(0))
+            .await
+            .is_err());
+
+        let read_only = ParadexTrading::new(None);
+        assert!(matches!(
+            read_only.get_open_orders(None).await,
+            Err(ExchangeError::Authentication(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_order() {
+        let mut server = Server::new_async().await;
+        let _auth = mock_auth(&mut server).await;
+
+        let _get = server
+            .mock("GET", "/v1/orders/1700000000000100")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(order_json("OPEN", "0.2").to_string())
+            .create_async()
+            .await;
+        let cancel = server
+            .mock("DELETE", "/v1/orders/1700000000000100")
+            .match_header("authorization", bearer())
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server);
+        let eth = Symbol::from("ETH-USD-PERP");
+
+        let cancelled = trading
+            .cancel_order(&eth, "1700000000000100")
+            .await
+            .unwrap();
+        assert_eq!(cancelled.status, OrderStatus::Cancelled);
+        assert_eq!(cancelled.filled_quantity, dec!(0.3));
+
+        // Wrong market is rejected before anything is cancelled
+        assert!(matches!(
+            trading
+                .cancel_order(&Symbol::from("BTC-USD-PERP"), "1700000000000100")
+                .await,
+            Err(ExchangeError::OrderNotFound(_))
+        ));
+
+        cancel.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_cancel_all_orders_for_market() {
+        let mut server = Server::new_async().await;
+        let _auth = mock_auth(&mut server).await;
+
+        let _open = server
+            .mock("GET", "/v1/orders")
+            .match_query(Matcher::UrlEncoded("market".into(), "ETH-USD-PERP".into()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "results": [order_json("OPEN", "0.5")] }).to_string())
+            .create_async()
+            .await;
+        let cancel_all = server
+            .mock("DELETE", "/v1/orders")
+            .match_query(Matcher::UrlEncoded("market".into(), "ETH-USD-PERP".into()))
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server);
+        let cancelled = trading
+            .cancel_all_orders(Some(&Symbol::from("ETH-USD-PERP")))
+            .await
+            .unwrap();
+
+        assert_eq!(cancelled.len(), 1);
+        assert_eq!(cancelled[0].status, OrderStatus::Cancelled);
+        cancel_all.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_modify_order() {
+        let mut server = Server::new_async().await;
+        let _auth = mock_auth(&mut server).await;
+
+        let _get = server
+            .mock("GET", "/v1/orders/1700000000000100")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(order_json("OPEN", "0.5").to_string())
+            .create_async()
+            .await;
+
+        let mut modified = order_json("OPEN", "0.5");
+        modified["price"] = json!("2950.5");
+        let modify = server
+            .mock("PUT", "/v1/orders/1700000000000100")
+            .match_header("authorization", bearer())
+            .match_body(Matcher::PartialJson(json!({
+                "id": "1700000000000100",
+                "market": "ETH-USD-PERP",
+                "side": "BUY",
+                "type": "LIMIT",
+                "size": "0.5",
+                "price": "2950.5"
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(modified.to_string())
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server);
+        let order = trading
+            .modify_order(
+                "1700000000000100",
+                OrderModification {
+                    quantity: None,
+                    price: Some(Price::from(2950.5)),
+                    stop_price: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(order.price, Some(Price::from(2950.5)));
+        modify.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_order_and_history() {
+        let mut server = Server::new_async().await;
+        let _auth = mock_auth(&mut server).await;
+
+        let _missing = server
+            .mock("GET", "/v1/orders/404")
+            .with_status(404)
+            .with_body(r#"{"error":"ORDER_ID_NOT_FOUND"}"#)
+            .create_async()
+            .await;
+
+        let mut filled = order_json("CLOSED", "0");
+        filled["avg_fill_price"] = json!("2999.5");
+        let mut cancelled = order_json("CLOSED", "0.5");
+        cancelled["id"] = json!("1700000000000101");
+        cancelled["cancel_reason"] = json!("USER_CANCELED");
+        let _history = server
+            .mock("GET", "/v1/orders-history")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("market".into(), "ETH-USD-PERP".into()),
+                Matcher::UrlEncoded("page_size".into(), "2".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({ "next": null, "prev": null, "results": [filled, cancelled] }).to_string(),
+            )
+            .create_async()
+            .await;
+
+        let trading = setup_test(&server);
+        let eth = Symbol::from("ETH-USD-PERP");
+
+        assert!(matches!(
+            trading.get_order(&eth, "404").await,
+            Err(ExchangeError::OrderNotFound(_))
+        ));
+
+        let history = trading.get_order_history(&eth, Some(2)).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].status, OrderStatus::Filled);
+        assert_eq!(history[0].filled_quantity, dec!(0.5));
+        assert_eq!(history[0].average_price, Some(Price::from(2999.5)));
+        assert_eq!(history[1].status, OrderStatus::Cancelled);
+        assert_eq!(history[1].filled_quantity, dec!(0));
+    }
+}
@@ -0,0 +1,247 @@
+//! Starknet typed data hashing for Paradex
+//!
+//! Paradex requests and orders are authenticated with STARK signatures over
+//! SNIP-12 (revision 0) typed data: a Pedersen hash of the message struct,
+//! bound to the Paradex domain (name, chain id, version) and the signing
+//! account address.
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use starknet::core::{
+    crypto::compute_hash_on_elements,
+    types::Felt,
+    utils::{cairo_short_string_to_felt, starknet_keccak},
+};
+
+use crate::types::{ExchangeError, Result, Side};
+
+/// Paradex domain name
+const DOMAIN_NAME: &str = "Paradex";
+
+/// Paradex domain version
+const DOMAIN_VERSION: u64 = 1;
+
+/// Prefix of every signed message
+const MESSAGE_PREFIX: &str = "StarkNet Message";
+
+const DOMAIN_TYPE: &str = "StarkNetDomain(name:felt,chainId:felt,version:felt)";
+const ORDER_TYPE: &str =
+    "Order(timestamp:felt,market:felt,side:felt,orderType:felt,size:felt,price:felt)";
+const MODIFY_ORDER_TYPE: &str =
+    "ModifyOrder(timestamp:felt,market:felt,side:felt,orderType:felt,size:felt,price:felt,id:felt)";
+const REQUEST_TYPE: &str =
+    "Request(method:felt,path:felt,body:felt,timestamp:felt,expiration:felt)";
+const ONBOARDING_TYPE: &str = "Constant(action:felt)";
+
+/// Order sizes and prices are signed as fixed-point integers with 8 decimals
+const QUANTUM_DECIMALS: u32 = 8;
+
+/// Fields of a signed order (or order modification)
+#[derive(Debug, Clone)]
+pub(crate) struct OrderMessage<'a> {
+    /// Signature timestamp in milliseconds
+    pub timestamp_ms: i64,
+    pub market: &'a str,
+    pub side: Side,
+    /// Paradex order type, e.g. `LIMIT` or `STOP_MARKET`
+    pub order_type: &'a str,
+    pub size: Decimal,
+    /// Zero for market orders
+    pub price: Decimal,
+    /// Exchange order id when signing a modification
+    pub modify_id: Option<&'a str>,
+}
+
+/// Encode an ASCII string of at most 31 characters as a felt
+pub(crate) fn short_string(s: &str) -> Result<Felt> {
+    cairo_short_string_to_felt(s)
+        .map_err(|e| ExchangeError::InvalidRequest(format!("Cannot encode '{s}' as felt: {e}")))
+}
+
+/// Encode a decimal as an 8-decimal fixed-point felt
+pub(crate) fn to_quantums(value: Decimal) -> Result<Felt> {
+    let scaled = value * Decimal::from(10u64.pow(QUANTUM_DECIMALS));
+    if scaled.fract() != Decimal::ZERO || scaled < Decimal::ZERO {
+        return Err(ExchangeError::InvalidOrder(format!(
+            "{value} is not representable with {QUANTUM_DECIMALS} decimals"
+        )));
+    }
+    scaled
+        .to_u128()
+        .map(Felt::from)
+        .ok_or_else(|| ExchangeError::InvalidOrder(format!("{value} is out of range")))
+}
+
+/// Hash of a struct: its type hash followed by its fields
+fn struct_hash(type_def: &str, fields: &[Felt]) -> Felt {
+    let mut elements = Vec::with_capacity(fields.len() + 1);
+    elements.push(starknet_keccak(type_def.as_bytes()));
+    elements.extend_from_slice(fields);
+    compute_hash_on_elements(&elements)
+}
+
+/// Hash of the Paradex domain for a chain
+fn domain_hash(chain_id: &str) -> Result<Felt> {
+    Ok(struct_hash(
+        DOMAIN_TYPE,
+        &[
+            short_string(DOMAIN_NAME)?,
+            short_string(chain_id)?,
+            Felt::from(DOMAIN_VERSION),
+        ],
+    ))
+}
+
+/// Final message hash signed by the account
+fn message_hash(chain_id: &str, account: Felt, struct_hash: Felt) -> Result<Felt> {
+    Ok(compute_hash_on_elements(&[
+        short_string(MESSAGE_PREFIX)?,
+        domain_hash(chain_id)?,
+        account,
+        struct_hash,
+    ]))
+}
+
+/// Hash of an order or order modification
+pub(crate) fn order_hash(chain_id: &str, account: Felt, order: &OrderMessage<'_>) -> Result<Felt> {
+    let mut fields = vec![
+        Felt::from(order.timestamp_ms as u64),
+        short_string(order.market)?,
+        Felt::from(match order.side {
+            Side::Buy => 1u8,
+            Side::Sell => 2u8,
+        }),
+        short_string(order.order_type)?,
+        to_quantums(order.size)?,
+        to_quantums(order.price)?,
+    ];
+
+    let type_def = match order.modify_id {
+        Some(id) => {
+            fields.push(Felt::from_dec_str(id).map_err(|_| {
+                ExchangeError::InvalidOrder(format!("Invalid Paradex order id: {id}"))
+            })?);
+            MODIFY_ORDER_TYPE
+        }
+        None => ORDER_TYPE,
+    };
+
+    message_hash(chain_id, account, struct_hash(type_def, &fields))
+}
+
+/// Hash of the JWT auth request (`POST /v1/auth`)
+pub(crate) fn auth_request_hash(
+    chain_id: &str,
+    account: Felt,
+    timestamp: i64,
+    expiration: i64,
+) -> Result<Felt> {
+    let request = struct_hash(
+        REQUEST_TYPE,
+        &[
+            short_string("POST")?,
+            short_string("/v1/auth")?,
+            short_string("")?,
+            Felt::from(timestamp as u64),
+            Felt::from(expiration as u64),
+        ],
+    );
+    message_hash(chain_id, account, request)
+}
+
+/// Hash of the one-time onboarding message
+pub(crate) fn onboarding_hash(chain_id: &str, account: Felt) -> Result<Felt> {
+    let onboarding = struct_hash(ONBOARDING_TYPE, &[short_string("Onboarding")?]);
+    message_hash(chain_id, account, onboarding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    // Reference vectors below come from an independent SNIP-12 revision 0
+    // encoder; starknet-core's TypedData agrees on the order struct hash.
+
+    #[test]
+    fn test_domain_hash_matches_reference() {
+        assert_eq!(
+            domain_hash("PRIVATE_SN_PARACLEAR_MAINNET").unwrap(),
+            Felt::from_hex("0x06f74f207280b65cf663fb8d7763fac1e7398cd6d7da5d7681dc300ee4278a0a")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_order_hash_matches_reference() {
+        let account =
+            Felt::from_hex("0x4a1bcf1e5b1f3de4e9c3ab2f9d15a8d8e3c1d5a2b4e6f708192a3b4c5d6e7f8")
+                .unwrap();
+        let order = OrderMessage {
+            timestamp_ms: 1_700_000_000_000,
+            market: "ETH-USD-PERP",
+            side: Side::Buy,
+            order_type: "LIMIT",
+            size: dec!(0.5),
+            price: dec!(3000),
+            modify_id: None,
+        };
+        assert_eq!(
+            order_hash("PRIVATE_SN_PARACLEAR_MAINNET", account, &order).unwrap(),
+            Felt::from_hex("0x06c7ca9938f862d0ac07608b0b7ee623fef27c12a754257eecc8f9848fe10082")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_quantums() {
+        assert_eq!(to_quantums(dec!(0.5)).unwrap(), Felt::from(50_000_000u64));
+        assert_eq!(
+            to_quantums(dec!(65000.1)).unwrap(),
+            Felt::from(6_500_010_000_000u64)
+        );
+        assert_eq!(to_quantums(Decimal::ZERO).unwrap(), Felt::ZERO);
+        assert!(to_quantums(dec!(0.000000001)).is_err());
+        assert!(to_quantums(dec!(-1)).is_err());
+    }
+
+    #[test]
+    fn test_order_hash_binds_every_field() {
+        let account = Felt::from_hex("0x1234").unwrap();
+        let order = OrderMessage {
+            timestamp_ms: 1_700_000_000_000,
+            market: "ETH-USD-PERP",
+            side: Side::Buy,
+            order_type: "LIMIT",
+            size: dec!(0.5),
+            price: dec!(3000),
+            modify_id: None,
+        };
+        let hash = order_hash("PRIVATE_SN_PARACLEAR_MAINNET", account, &order).unwrap();
+
+        let sell = OrderMessage {
+            side: Side::Sell,
+            ..order.clone()
+        };
+        let modify = OrderMessage {
+            modify_id: Some("1700000000000100"),
+            ..order.clone()
+        };
+        assert_ne!(
+            hash,
+            order_hash("PRIVATE_SN_PARACLEAR_MAINNET", account, &sell).unwrap()
+        );
+        assert_ne!(
+            hash,
+            order_hash("PRIVATE_SN_PARACLEAR_MAINNET", account, &modify).unwrap()
+        );
+        assert_ne!(
+            hash,
+            order_hash("PRIVATE_SN_POTC_SEPOLIA", account, &order).unwrap()
+        );
+        assert_eq!(
+            hash,
+            order_hash("PRIVATE_SN_PARACLEAR_MAINNET", account, &order).unwrap()
+        );
+    }
+}
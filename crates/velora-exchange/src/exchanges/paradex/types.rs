@@ -107,36 +107,57 @@ pub struct ParadexTrade {
     pub trade_type: String, // "FILL", "RPI" (Retail Price Improvement), etc.
 }
 
-/// Paradex account response
+/// Paradex system config (only the fields needed for signing)
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ParadexAccount {
-    pub account_id: String,
-    pub starknet_address: String,
-    pub created_at: i64,
+pub struct ParadexSystemConfig {
+    pub starknet_chain_id: String,
+    #[serde(default)]
+    pub paraclear_account_proxy_hash: String,
+    #[serde(default)]
+    pub paraclear_account_hash: String,
+}
+
+/// Paradex account summary response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParadexAccountSummary {
+    pub account: String,
+    pub account_value: String,
+    pub free_collateral: String,
+    pub total_collateral: String,
+    pub initial_margin_requirement: String,
+    pub maintenance_margin_requirement: String,
+    #[serde(default)]
+    pub settlement_asset: String,
+    pub status: String, // "ACTIVE", "LIQUIDATION", ...
+    pub updated_at: i64,
 }
 
 /// Paradex balance response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParadexBalance {
-    pub asset: String,
-    pub available: String,
-    pub locked: String,
-    pub total: String,
+    pub token: String,
+    pub size: String,
+    pub last_updated_at: i64,
 }
 
 /// Paradex position response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParadexPosition {
+    pub id: String,
     pub market: String,
-    pub side: String, // "LONG" or "SHORT"
-    pub size: String,
-    pub entry_price: String,
-    pub mark_price: String,
-    pub liquidation_price: Option<String>,
+    pub status: String, // "OPEN" or "CLOSED"
+    pub side: String,   // "LONG" or "SHORT"
+    pub size: String,   // Signed, negative for shorts
+    pub average_entry_price: String,
     pub unrealized_pnl: String,
-    pub realized_pnl: String,
-    pub margin: String,
+    #[serde(default)]
+    pub realized_positional_pnl: String,
+    #[serde(default)]
+    pub liquidation_price: String,
+    #[serde(default)]
     pub leverage: String,
+    #[serde(default)]
+    pub cost: String,
     pub last_updated_at: i64,
 }
 
@@ -144,37 +165,62 @@ pub struct ParadexPosition {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParadexOrder {
     pub id: String,
-    pub client_id: Option<String>,
+    #[serde(default)]
+    pub client_id: String,
     pub market: String,
-    pub side: String,   // "BUY" or "SELL"
-    pub r#type: String, // "LIMIT" or "MARKET"
-    pub price: Option<String>,
+    pub side: String, // "BUY" or "SELL"
+    #[serde(rename = "type")]
+    pub order_type: String, // "LIMIT", "MARKET", "STOP_LIMIT", "STOP_MARKET"
     pub size: String,
-    pub filled_size: String,
     pub remaining_size: String,
-    pub status: String,
-    pub time_in_force: String,
+    pub price: String,
+    #[serde(default)]
+    pub avg_fill_price: String,
+    pub status: String, // "NEW", "UNTRIGGERED", "OPEN" or "CLOSED"
+    #[serde(default)]
+    pub instruction: String, // "GTC", "IOC" or "POST_ONLY"
+    #[serde(default)]
+    pub trigger_price: String,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    #[serde(default)]
+    pub cancel_reason: String,
     pub created_at: i64,
-    pub updated_at: i64,
+    pub last_updated_at: i64,
 }
 
 /// Paradex new order request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParadexOrderRequest {
     pub market: String,
-    pub side: String,   // "BUY" or "SELL"
-    pub r#type: String, // "LIMIT" or "MARKET"
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub price: Option<String>,
+    pub side: String,
+    #[serde(rename = "type")]
+    pub order_type: String,
     pub size: String,
+    pub price: String,
+    pub instruction: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub time_in_force: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub post_only: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub reduce_only: Option<bool>,
+    pub trigger_price: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub flags: Vec<String>,
+    pub signature: String,
+    pub signature_timestamp: i64,
+}
+
+/// Paradex modify order request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParadexModifyOrderRequest {
+    pub id: String,
+    pub market: String,
+    pub side: String,
+    #[serde(rename = "type")]
+    pub order_type: String,
+    pub size: String,
+    pub price: String,
+    pub signature: String,
+    pub signature_timestamp: i64,
 }
 
 /// Paradex funding payment response
@@ -207,24 +253,22 @@ pub struct ParadexFill {
     pub price: String,
     pub size: String,
     pub fee: String,
+    #[serde(default)]
+    pub fee_currency: String,
     pub liquidity: String, // "MAKER" or "TAKER"
     pub created_at: i64,
 }
 
-/// Paradex JWT auth request
+/// Paradex onboarding request
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ParadexAuthRequest {
-    pub starknet_address: String,
-    pub starknet_signature: String,
-    pub timestamp: i64,
+pub struct ParadexOnboardingRequest {
+    pub public_key: String,
 }
 
 /// Paradex JWT auth response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParadexAuthResponse {
     pub jwt_token: String,
-    pub refresh_token: String,
-    pub expires_at: i64,
 }
 
 // ============================================================================
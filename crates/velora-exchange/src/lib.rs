@@ -57,10 +57,10 @@ pub mod exchanges;
 
 // Re-export commonly used types
 pub use types::{
-    AccountInfo, AccountType, Balance, Candle, ExchangeError, ExchangeType, FundingRate,
-    InstrumentInfo, InstrumentType, Interval, MarginType, Market, MarketStatus, NewOrder,
-    OptionType, Order, OrderBook, OrderStatus, OrderType, Position, PositionSide, Price, Result,
-    Side, Symbol, Ticker, TimeInForce, Trade,
+    AccountInfo, AccountType, Balance, Candle, ExchangeError, ExchangeType, FeeModel, FeeRates,
+    FeeTier, FundingRate, InstrumentInfo, InstrumentType, Interval, Liquidity, MarginType, Market,
+    MarketStatus, NewOrder, OptionType, Order, OrderBook, OrderStatus, OrderType, Position,
+    PositionSide, Price, Result, Side, Symbol, Ticker, TimeInForce, Trade,
 };
 
 // Re-export traits
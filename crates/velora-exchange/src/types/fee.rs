@@ -0,0 +1,282 @@
+//! Trading fee schedules.
+
+use super::*;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Whether a fill added liquidity to the book or took it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Liquidity {
+    /// A resting order filled by someone else's order
+    Maker,
+    /// An order that crossed the spread
+    #[default]
+    Taker,
+}
+
+/// Maker and taker fee rates, as fractions of the notional
+///
+/// A negative maker rate is a rebate paid to the maker.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeRates {
+    /// Rate paid by resting orders
+    pub maker: f64,
+    /// Rate paid by crossing orders
+    pub taker: f64,
+}
+
+impl FeeRates {
+    /// Create maker and taker rates
+    pub fn new(maker: f64, taker: f64) -> Self {
+        Self { maker, taker }
+    }
+
+    /// Rate of a fill with the given liquidity
+    pub fn rate(&self, liquidity: Liquidity) -> f64 {
+        match liquidity {
+            Liquidity::Maker => self.maker,
+            Liquidity::Taker => self.taker,
+        }
+    }
+
+    /// Rates listed in instrument information (None for options)
+    pub fn from_instrument(info: &InstrumentInfo) -> Option<Self> {
+        let (maker, taker) = match info {
+            InstrumentInfo::Spot(spot) => (spot.maker_fee, spot.taker_fee),
+            InstrumentInfo::Perpetual(perpetual) => (perpetual.maker_fee, perpetual.taker_fee),
+            InstrumentInfo::Futures(futures) => (futures.maker_fee, futures.taker_fee),
+            InstrumentInfo::Options(_) => return None,
+        };
+        Some(Self::new(
+            maker.to_f64().unwrap_or_default(),
+            taker.to_f64().unwrap_or_default(),
+        ))
+    }
+}
+
+/// Fee rates that apply once the traded volume reaches `min_volume`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeTier {
+    /// Traded notional volume needed for the tier
+    pub min_volume: f64,
+    /// Rates of the tier
+    pub rates: FeeRates,
+}
+
+/// Exchange fee schedule used to simulate fills
+///
+/// Fills pay the maker or taker rate of their symbol's override if there is
+/// one, otherwise of the highest volume tier reached, otherwise the base
+/// rates. Volume is the notional traded before the fill; simulators count it
+/// from the start of their run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeModel {
+    /// Rates below the first volume tier
+    pub base: FeeRates,
+
+    /// Volume tiers, by ascending minimum volume
+    #[serde(default)]
+    pub tiers: Vec<FeeTier>,
+
+    /// Rates of single symbols, replacing the base rates and tiers
+    #[serde(default)]
+    pub symbols: HashMap<String, FeeRates>,
+}
+
+impl FeeModel {
+    /// Create a schedule with maker and taker rates
+    pub fn new(maker: f64, taker: f64) -> Self {
+        Self {
+            base: FeeRates::new(maker, taker),
+            tiers: Vec::new(),
+            symbols: HashMap::new(),
+        }
+    }
+
+    /// Create a schedule charging the same rate on every fill
+    pub fn flat(rate: f64) -> Self {
+        Self::new(rate, rate)
+    }
+
+    /// Create a schedule from the fees of an exchange market
+    ///
+    /// Returns None if the market carries no fee information.
+    pub fn from_market(market: &Market) -> Option<Self> {
+        let rates = FeeRates::from_instrument(market.instrument_info.as_ref()?)?;
+        Some(Self::new(rates.maker, rates.taker))
+    }
+
+    /// Add a volume tier
+    pub fn with_tier(mut self, min_volume: f64, maker: f64, taker: f64) -> Self {
+        self.tiers.push(FeeTier {
+            min_volume,
+            rates: FeeRates::new(maker, taker),
+        });
+        self.tiers
+            .sort_by(|a, b| a.min_volume.total_cmp(&b.min_volume));
+        self
+    }
+
+    /// Charge a symbol its own rates
+    pub fn with_symbol(mut self, symbol: impl Into<String>, maker: f64, taker: f64) -> Self {
+        self.symbols
+            .insert(symbol.into(), FeeRates::new(maker, taker));
+        self
+    }
+
+    /// Charge a market's symbol the fees it lists (unchanged without fee information)
+    pub fn with_market(mut self, market: &Market) -> Self {
+        if let Some(rates) = market
+            .instrument_info
+            .as_ref()
+            .and_then(FeeRates::from_instrument)
+        {
+            self.symbols.insert(market.symbol.to_string(), rates);
+        }
+        self
+    }
+
+    /// Rates of a symbol after trading `volume`
+    pub fn rates(&self, symbol: &str, volume: f64) -> FeeRates {
+        if let Some(rates) = self.symbols.get(symbol) {
+            return *rates;
+        }
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| volume >= tier.min_volume)
+            .map_or(self.base, |tier| tier.rates)
+    }
+
+    /// Rate of a fill after trading `volume`
+    pub fn rate(&self, symbol: &str, liquidity: Liquidity, volume: f64) -> f64 {
+        self.rates(symbol, volume).rate(liquidity)
+    }
+
+    /// Fee of a fill of `notional` after trading `volume` (negative for rebates)
+    pub fn fee(&self, symbol: &str, liquidity: Liquidity, notional: f64, volume: f64) -> f64 {
+        notional.abs() * self.rate(symbol, liquidity, volume)
+    }
+}
+
+impl From<&AccountInfo> for FeeModel {
+    /// The account's maker and taker commission on every symbol
+    fn from(account: &AccountInfo) -> Self {
+        Self::new(
+            account.maker_commission.to_f64().unwrap_or_default(),
+            account.taker_commission.to_f64().unwrap_or_default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn perpetual_market(maker_fee: Decimal, taker_fee: Decimal) -> Market {
+        Market {
+            symbol: Symbol::from("BTC-USD-PERP"),
+            base_asset: "BTC".to_string(),
+            quote_asset: "USD".to_string(),
+            instrument_type: InstrumentType::Perpetual,
+            status: MarketStatus::Trading,
+            min_quantity: dec!(0.001),
+            max_quantity: dec!(1000),
+            step_size: dec!(0.001),
+            tick_size: dec!(0.1),
+            min_notional: dec!(10),
+            instrument_info: Some(InstrumentInfo::Perpetual(PerpetualInfo {
+                funding_interval: std::time::Duration::from_secs(8 * 3600),
+                max_leverage: 20,
+                maker_fee,
+                taker_fee,
+                initial_margin: None,
+                maintenance_margin: None,
+            })),
+        }
+    }
+
+    #[test]
+    fn test_tiers_apply_from_their_minimum_volume() {
+        // Tiers added out of order are kept sorted
+        let fees = FeeModel::new(0.0010, 0.0020)
+            .with_tier(5_000_000.0, 0.0004, 0.0010)
+            .with_tier(1_000_000.0, 0.0008, 0.0015);
+
+        assert_eq!(fees.rate("BTC", Liquidity::Taker, 0.0), 0.0020);
+        assert_eq!(fees.rate("BTC", Liquidity::Taker, 999_999.99), 0.0020);
+        assert_eq!(fees.rate("BTC", Liquidity::Taker, 1_000_000.0), 0.0015);
+        assert_eq!(fees.rate("BTC", Liquidity::Maker, 4_999_999.99), 0.0008);
+        assert_eq!(fees.rate("BTC", Liquidity::Maker, 5_000_000.0), 0.0004);
+        assert_eq!(fees.rate("BTC", Liquidity::Taker, 1e9), 0.0010);
+    }
+
+    #[test]
+    fn test_symbol_rates_override_tiers() {
+        let fees = FeeModel::new(0.0010, 0.0020)
+            .with_tier(1_000_000.0, 0.0008, 0.0015)
+            .with_symbol("ETH", 0.0, 0.0005);
+
+        assert_eq!(fees.rates("ETH", 0.0), FeeRates::new(0.0, 0.0005));
+        assert_eq!(fees.rates("ETH", 2_000_000.0), FeeRates::new(0.0, 0.0005));
+        assert_eq!(
+            fees.rates("BTC", 2_000_000.0),
+            FeeRates::new(0.0008, 0.0015)
+        );
+    }
+
+    #[test]
+    fn test_maker_rebate_is_a_negative_fee() {
+        let fees = FeeModel::new(-0.0002, 0.0005);
+
+        assert_eq!(fees.fee("BTC", Liquidity::Maker, 10_000.0, 0.0), -2.0);
+        assert_eq!(fees.fee("BTC", Liquidity::Taker, 10_000.0, 0.0), 5.0);
+        // Sells report a negative notional but pay the same fee
+        assert_eq!(fees.fee("BTC", Liquidity::Maker, -10_000.0, 0.0), -2.0);
+    }
+
+    #[test]
+    fn test_fees_from_market() {
+        let market = perpetual_market(dec!(-0.0001), dec!(0.0004));
+        let fees = FeeModel::from_market(&market).unwrap();
+        assert_eq!(fees.base, FeeRates::new(-0.0001, 0.0004));
+        assert!(fees.tiers.is_empty());
+
+        let fees = FeeModel::flat(0.001).with_market(&market);
+        assert_eq!(
+            fees.rates("BTC-USD-PERP", 0.0),
+            FeeRates::new(-0.0001, 0.0004)
+        );
+        assert_eq!(fees.rates("ETH-USD-PERP", 0.0), FeeRates::new(0.001, 0.001));
+
+        // No fee information leaves the schedule unchanged
+        let market = Market {
+            instrument_info: None,
+            ..market
+        };
+        assert!(FeeModel::from_market(&market).is_none());
+        assert_eq!(
+            FeeModel::flat(0.001).with_market(&market),
+            FeeModel::flat(0.001)
+        );
+    }
+
+    #[test]
+    fn test_fees_from_account() {
+        let account = AccountInfo {
+            account_type: AccountType::Spot,
+            can_trade: true,
+            can_withdraw: true,
+            can_deposit: true,
+            maker_commission: dec!(0.001),
+            taker_commission: dec!(0.0015),
+            update_time: chrono::Utc::now(),
+        };
+
+        let fees = FeeModel::from(&account);
+        assert_eq!(fees, FeeModel::new(0.001, 0.0015));
+    }
+}
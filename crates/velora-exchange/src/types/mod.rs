@@ -7,6 +7,7 @@ pub mod account;
 pub mod candle;
 pub mod common;
 pub mod error;
+pub mod fee;
 pub mod instrument;
 pub mod market;
 pub mod order;
@@ -19,6 +20,7 @@ pub use account::*;
 pub use candle::*;
 pub use common::*;
 pub use error::*;
+pub use fee::*;
 pub use instrument::*;
 pub use market::*;
 pub use order::*;
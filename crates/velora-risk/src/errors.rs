@@ -0,0 +1,26 @@
+//! Error types for risk management.
+
+use thiserror::Error;
+
+/// Result type for risk operations
+pub type RiskResult<T> = Result<T, RiskError>;
+
+/// Errors that can occur while sizing or checking orders
+#[derive(Debug, Error)]
+pub enum RiskError {
+    /// Strategy context error
+    #[error("Strategy error: {0}")]
+    Strategy(#[from] velora_strategy::StrategyError),
+
+    /// Indicator error
+    #[error("Indicator error: {0}")]
+    Indicator(#[from] velora_ta::IndicatorError),
+
+    /// Market data needed for sizing is missing
+    #[error("Insufficient data: {0}")]
+    InsufficientData(String),
+
+    /// Invalid sizer parameter
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+}
@@ -1,5 +1,41 @@
 //! # velora-risk
 //!
 //! Risk management for the Velora HFT platform.
+//!
+//! The [`RiskManager`] enforces the limits from
+//! [`velora_core::RiskConfig`] before an order is sent. It is used by both
+//! the backtester and the live trading engine, so a strategy sees the same
+//! rules in simulation and in production.
+//!
+//! Position sizers in [`sizing`] turn equity and volatility into order
+//! quantities, so strategies can leave sizing to the engine.
+//!
+//! ## Example
+//!
+//! ```
+//! use chrono::Utc;
+//! use velora_core::{RiskConfig, Side};
+//! use velora_risk::{OrderRequest, PortfolioState, RiskDecision, RiskManager};
+//!
+//! let mut risk = RiskManager::new(RiskConfig::default());
+//!
+//! let state = PortfolioState::new(10_000.0, Utc::now());
+//! let order = OrderRequest::new("BTC/USD", Side::Buy, 0.01, 50_000.0);
+//!
+//! assert_eq!(risk.check_order(&order, &state), RiskDecision::Approved);
+//! ```
 
 #![warn(missing_docs)]
+
+pub mod errors;
+pub mod manager;
+pub mod sizing;
+pub mod types;
+
+// Re-exports
+pub use errors::{RiskError, RiskResult};
+pub use manager::RiskManager;
+pub use sizing::{
+    FixedFractional, FixedNotional, KellySizer, PositionSizer, VolatilityMeasure, VolatilityTarget,
+};
+pub use types::{OrderRequest, PortfolioState, RiskDecision, RiskViolation};
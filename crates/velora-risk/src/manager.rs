@@ -0,0 +1,387 @@
+//! Pre-trade risk manager.
+
+use crate::types::{OrderRequest, PortfolioState, RiskDecision, RiskViolation};
+use chrono::{DateTime, NaiveDate, Utc};
+use velora_core::RiskConfig;
+
+/// Quantities below this are treated as zero when resizing.
+const MIN_QUANTITY: f64 = 1e-12;
+
+/// Checks orders against the limits in [`RiskConfig`].
+///
+/// All limits are expressed in quote currency notional:
+///
+/// - `max_position_size` caps the notional of a single order
+/// - `position_limits` caps the absolute position notional per symbol
+/// - `max_total_exposure` caps the gross notional across all symbols
+/// - `max_daily_loss` halts new risk once equity falls that far below the
+///   start-of-day (UTC) equity; the halt clears on the next day
+/// - `max_drawdown_percent` halts new risk once equity falls that far below
+///   its peak; the halt stays until [`RiskManager::reset_halt`] is called
+///
+/// Orders that only reduce an existing position are always approved, so a
+/// strategy can flatten even while a circuit breaker is tripped.
+#[derive(Debug, Clone)]
+pub struct RiskManager {
+    /// Risk limits
+    config: RiskConfig,
+
+    /// Highest equity seen
+    peak_equity: Option<f64>,
+
+    /// Equity at the start of the current trading day
+    day_start_equity: Option<f64>,
+
+    /// Current trading day (UTC)
+    current_day: Option<NaiveDate>,
+
+    /// Tripped circuit breaker, if any
+    halt: Option<RiskViolation>,
+}
+
+impl RiskManager {
+    /// Create a new risk manager
+    pub fn new(config: RiskConfig) -> Self {
+        Self {
+            config,
+            peak_equity: None,
+            day_start_equity: None,
+            current_day: None,
+            halt: None,
+        }
+    }
+
+    /// Get the risk configuration
+    pub fn config(&self) -> &RiskConfig {
+        &self.config
+    }
+
+    /// Record the latest account equity.
+    ///
+    /// Updates the peak and start-of-day equity and trips the drawdown or
+    /// daily loss circuit breaker when a limit is reached.
+    pub fn update_equity(&mut self, equity: f64, timestamp: DateTime<Utc>) {
+        let day = timestamp.date_naive();
+        if self.current_day != Some(day) {
+            self.current_day = Some(day);
+            self.day_start_equity = Some(equity);
+            if matches!(self.halt, Some(RiskViolation::DailyLoss { .. })) {
+                self.halt = None;
+            }
+        }
+
+        let peak = self.peak_equity.map_or(equity, |p| p.max(equity));
+        self.peak_equity = Some(peak);
+
+        if matches!(self.halt, Some(RiskViolation::MaxDrawdown { .. })) {
+            return;
+        }
+
+        let drawdown_pct = self.drawdown_pct(equity);
+        if drawdown_pct >= self.config.max_drawdown_percent {
+            self.halt = Some(RiskViolation::MaxDrawdown {
+                drawdown_pct,
+                limit_pct: self.config.max_drawdown_percent,
+            });
+            return;
+        }
+
+        let loss = self.daily_loss(equity);
+        if self.halt.is_none() && loss >= self.config.max_daily_loss {
+            self.halt = Some(RiskViolation::DailyLoss {
+                loss,
+                limit: self.config.max_daily_loss,
+            });
+        }
+    }
+
+    /// Check an order against all limits.
+    ///
+    /// The equity in `state` is recorded first, so a single call both
+    /// updates the circuit breakers and evaluates the order.
+    pub fn check_order(&mut self, order: &OrderRequest, state: &PortfolioState) -> RiskDecision {
+        if !is_positive(order.quantity) || !is_positive(order.price) {
+            return RiskDecision::Rejected(RiskViolation::InvalidOrder(format!(
+                "quantity {} and price {} must be positive",
+                order.quantity, order.price
+            )));
+        }
+
+        self.update_equity(state.equity, state.timestamp);
+
+        let current = state.position(&order.symbol);
+        let resulting = current + order.signed_notional();
+
+        // Reducing orders never add risk
+        if resulting.abs() <= current.abs() {
+            return RiskDecision::Approved;
+        }
+
+        if let Some(halt) = &self.halt {
+            return RiskDecision::Rejected(halt.clone());
+        }
+
+        let requested = order.notional();
+        let mut allowed = requested;
+        let mut binding: Option<RiskViolation> = None;
+
+        if requested > self.config.max_position_size {
+            allowed = self.config.max_position_size;
+            binding = Some(RiskViolation::MaxPositionSize {
+                requested,
+                limit: self.config.max_position_size,
+            });
+        }
+
+        if let Some(&limit) = self.config.position_limits.get(&order.symbol) {
+            let room = room_under_limit(current, order.side, limit);
+            if room < allowed {
+                allowed = room;
+                binding = Some(RiskViolation::SymbolLimit {
+                    symbol: order.symbol.clone(),
+                    resulting: resulting.abs(),
+                    limit,
+                });
+            }
+        }
+
+        let other_exposure = state.total_exposure() - current.abs();
+        let room = room_under_limit(
+            current,
+            order.side,
+            self.config.max_total_exposure - other_exposure,
+        );
+        if room < allowed {
+            allowed = room;
+            binding = Some(RiskViolation::TotalExposure {
+                resulting: other_exposure + resulting.abs(),
+                limit: self.config.max_total_exposure,
+            });
+        }
+
+        match binding {
+            None => RiskDecision::Approved,
+            Some(reason) => {
+                let quantity = allowed.max(0.0) / order.price;
+                if quantity <= MIN_QUANTITY {
+                    RiskDecision::Rejected(reason)
+                } else {
+                    RiskDecision::Resized { quantity, reason }
+                }
+            }
+        }
+    }
+
+    /// Returns true if a circuit breaker is tripped
+    pub fn is_halted(&self) -> bool {
+        self.halt.is_some()
+    }
+
+    /// Get the tripped circuit breaker, if any
+    pub fn halt_reason(&self) -> Option<&RiskViolation> {
+        self.halt.as_ref()
+    }
+
+    /// Clear a tripped circuit breaker and restart peak tracking
+    pub fn reset_halt(&mut self) {
+        self.halt = None;
+        self.peak_equity = None;
+    }
+
+    /// Drawdown from peak equity in percent
+    pub fn drawdown_pct(&self, equity: f64) -> f64 {
+        match self.peak_equity {
+            Some(peak) if peak > 0.0 && equity < peak => (peak - equity) / peak * 100.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Loss since the start of the current day (positive = loss)
+    pub fn daily_loss(&self, equity: f64) -> f64 {
+        self.day_start_equity
+            .map(|start| (start - equity).max(0.0))
+            .unwrap_or(0.0)
+    }
+}
+
+/// Returns true for finite values greater than zero.
+fn is_positive(value: f64) -> bool {
+    value.is_finite() && value > 0.0
+}
+
+/// Largest order notional on `side` that keeps `|current + order| <= limit`.
+fn room_under_limit(current: f64, side: velora_core::Side, limit: f64) -> f64 {
+    let direction = match side {
+        velora_core::Side::Buy => 1.0,
+        velora_core::Side::Sell => -1.0,
+    };
+
+    if current * direction >= 0.0 {
+        limit - current.abs()
+    } else {
+        limit + current.abs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use velora_core::Side;
+
+    fn config() -> RiskConfig {
+        let mut config = RiskConfig {
+            max_position_size: 1_000.0,
+            max_total_exposure: 3_000.0,
+            max_drawdown_percent: 10.0,
+            max_daily_loss: 500.0,
+            ..Default::default()
+        };
+        config
+            .position_limits
+            .insert("BTC/USD".to_string(), 1_500.0);
+        config
+    }
+
+    fn day(d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, d, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_order_within_limits() {
+        let mut risk = RiskManager::new(config());
+        let state = PortfolioState::new(10_000.0, day(1));
+        let order = OrderRequest::new("BTC/USD", Side::Buy, 0.01, 50_000.0);
+
+        assert_eq!(risk.check_order(&order, &state), RiskDecision::Approved);
+    }
+
+    #[test]
+    fn test_max_position_size_resizes() {
+        let mut risk = RiskManager::new(config());
+        let state = PortfolioState::new(10_000.0, day(1));
+        let order = OrderRequest::new("ETH/USD", Side::Buy, 1.0, 2_000.0);
+
+        match risk.check_order(&order, &state) {
+            RiskDecision::Resized { quantity, reason } => {
+                assert_eq!(quantity, 0.5);
+                assert!(matches!(reason, RiskViolation::MaxPositionSize { .. }));
+            }
+            other => panic!("expected resize, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_symbol_limit() {
+        let mut risk = RiskManager::new(config());
+        let state = PortfolioState::new(10_000.0, day(1)).with_position("BTC/USD", 1_200.0);
+        let order = OrderRequest::new("BTC/USD", Side::Buy, 0.01, 50_000.0);
+
+        match risk.check_order(&order, &state) {
+            RiskDecision::Resized { quantity, reason } => {
+                assert!((quantity - 0.006).abs() < 1e-12);
+                assert!(matches!(reason, RiskViolation::SymbolLimit { .. }));
+            }
+            other => panic!("expected resize, got {other:?}"),
+        }
+
+        let full = PortfolioState::new(10_000.0, day(1)).with_position("BTC/USD", 1_500.0);
+        assert!(matches!(
+            risk.check_order(&order, &full),
+            RiskDecision::Rejected(RiskViolation::SymbolLimit { .. })
+        ));
+    }
+
+    #[test]
+    fn test_total_exposure() {
+        let mut risk = RiskManager::new(config());
+        let state = PortfolioState::new(10_000.0, day(1))
+            .with_position("ETH/USD", 1_500.0)
+            .with_position("SOL/USD", -1_500.0);
+        let order = OrderRequest::new("BNB/USD", Side::Buy, 1.0, 100.0);
+
+        assert!(matches!(
+            risk.check_order(&order, &state),
+            RiskDecision::Rejected(RiskViolation::TotalExposure { .. })
+        ));
+    }
+
+    #[test]
+    fn test_reducing_order_always_approved() {
+        let mut risk = RiskManager::new(config());
+        let state = PortfolioState::new(10_000.0, day(1)).with_position("ETH/USD", 2_500.0);
+
+        // Larger than max_position_size, but only reduces the long
+        let order = OrderRequest::new("ETH/USD", Side::Sell, 1.0, 2_000.0);
+        assert_eq!(risk.check_order(&order, &state), RiskDecision::Approved);
+    }
+
+    #[test]
+    fn test_flip_counts_against_limits() {
+        let mut risk = RiskManager::new(config());
+        let state = PortfolioState::new(10_000.0, day(1)).with_position("BTC/USD", 500.0);
+
+        // Sell 2_000 notional: closes 500 long and opens 1_500 short,
+        // but the order itself is capped at 1_000
+        let order = OrderRequest::new("BTC/USD", Side::Sell, 0.04, 50_000.0);
+        match risk.check_order(&order, &state) {
+            RiskDecision::Resized { quantity, .. } => assert_eq!(quantity, 0.02),
+            other => panic!("expected resize, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_daily_loss_circuit_breaker() {
+        let mut risk = RiskManager::new(config());
+        risk.update_equity(10_000.0, day(1));
+        risk.update_equity(10_200.0, day(1) + Duration::hours(1));
+        risk.update_equity(9_500.0, day(1) + Duration::hours(2));
+
+        assert!(matches!(
+            risk.halt_reason(),
+            Some(RiskViolation::DailyLoss { .. })
+        ));
+
+        let order = OrderRequest::new("ETH/USD", Side::Buy, 0.1, 2_000.0);
+        let state = PortfolioState::new(9_500.0, day(1) + Duration::hours(3));
+        assert!(!risk.check_order(&order, &state).is_allowed());
+
+        // Next day the breaker clears
+        let state = PortfolioState::new(9_500.0, day(2));
+        assert_eq!(risk.check_order(&order, &state), RiskDecision::Approved);
+    }
+
+    #[test]
+    fn test_drawdown_circuit_breaker() {
+        let mut risk = RiskManager::new(RiskConfig {
+            max_daily_loss: 1_000_000.0,
+            ..config()
+        });
+        risk.update_equity(10_000.0, day(1));
+        risk.update_equity(8_900.0, day(2));
+
+        assert!(matches!(
+            risk.halt_reason(),
+            Some(RiskViolation::MaxDrawdown { .. })
+        ));
+
+        // Sticky across days
+        risk.update_equity(9_500.0, day(3));
+        assert!(risk.is_halted());
+
+        risk.reset_halt();
+        assert!(!risk.is_halted());
+    }
+
+    #[test]
+    fn test_invalid_order() {
+        let mut risk = RiskManager::new(config());
+        let state = PortfolioState::new(10_000.0, day(1));
+        let order = OrderRequest::new("ETH/USD", Side::Buy, 0.0, 2_000.0);
+
+        assert!(matches!(
+            risk.check_order(&order, &state),
+            RiskDecision::Rejected(RiskViolation::InvalidOrder(_))
+        ));
+    }
+}
@@ -0,0 +1,454 @@
+//! Position sizing models.
+//!
+//! A [`PositionSizer`] turns account equity and the latest market snapshot
+//! into an order quantity. Strategies can emit unsized signals
+//! (see [`Signal::buy_unsized`](velora_strategy::Signal::buy_unsized)) and let
+//! the backtester or live engine fill in the quantity with the same sizer.
+
+use crate::errors::{RiskError, RiskResult};
+use velora_core::Side;
+use velora_strategy::{StrategyConfig, StrategyContext};
+use velora_ta::{OhlcBar, SingleIndicator, StdDev, ATR};
+
+/// Number of periods of history fed to volatility indicators.
+const VOLATILITY_LOOKBACK_MULTIPLE: usize = 3;
+
+/// Computes order quantities from account state.
+pub trait PositionSizer: Send + Sync {
+    /// Sizer name
+    fn name(&self) -> &str;
+
+    /// Quantity (in base units) to trade for a new position in `symbol`
+    fn size(&self, symbol: &str, side: Side, ctx: &StrategyContext) -> RiskResult<f64>;
+}
+
+/// Build the sizer described by a strategy configuration.
+///
+/// Uses [`VolatilityTarget`] when `use_volatility_sizing` is set and
+/// [`FixedFractional`] otherwise. Both are capped at
+/// `max_position_size_pct` of equity.
+pub fn from_strategy_config(config: &StrategyConfig) -> RiskResult<Box<dyn PositionSizer>> {
+    let max_fraction = config.max_position_size_pct / 100.0;
+
+    if config.use_volatility_sizing {
+        Ok(Box::new(
+            VolatilityTarget::atr(14, 2.0, 0.01)?.with_max_fraction(max_fraction),
+        ))
+    } else {
+        Ok(Box::new(FixedFractional::new(max_fraction)?))
+    }
+}
+
+/// Latest price for a symbol from the strategy context.
+fn last_price(symbol: &str, ctx: &StrategyContext) -> RiskResult<f64> {
+    let price = ctx
+        .get_last_price(symbol)?
+        .ok_or_else(|| RiskError::InsufficientData(format!("No price for {symbol}")))?;
+
+    if price <= 0.0 {
+        return Err(RiskError::InsufficientData(format!(
+            "Invalid price {price} for {symbol}"
+        )));
+    }
+
+    Ok(price)
+}
+
+/// Validate a fraction in (0, 1].
+fn check_fraction(name: &str, value: f64) -> RiskResult<()> {
+    if value.is_finite() && value > 0.0 && value <= 1.0 {
+        Ok(())
+    } else {
+        Err(RiskError::InvalidParameter(format!(
+            "{name} must be in (0, 1], got {value}"
+        )))
+    }
+}
+
+/// Trade a fixed notional amount per position.
+#[derive(Debug, Clone)]
+pub struct FixedNotional {
+    notional: f64,
+}
+
+impl FixedNotional {
+    /// Create a fixed-notional sizer (quote currency per position)
+    pub fn new(notional: f64) -> RiskResult<Self> {
+        if !(notional.is_finite() && notional > 0.0) {
+            return Err(RiskError::InvalidParameter(format!(
+                "Notional must be positive, got {notional}"
+            )));
+        }
+        Ok(Self { notional })
+    }
+}
+
+impl PositionSizer for FixedNotional {
+    fn name(&self) -> &str {
+        "FixedNotional"
+    }
+
+    fn size(&self, symbol: &str, _side: Side, ctx: &StrategyContext) -> RiskResult<f64> {
+        Ok(self.notional / last_price(symbol, ctx)?)
+    }
+}
+
+/// Trade a fixed fraction of equity per position.
+#[derive(Debug, Clone)]
+pub struct FixedFractional {
+    fraction: f64,
+}
+
+impl FixedFractional {
+    /// Create a fixed-fractional sizer (e.g., 0.1 = 10% of equity)
+    pub fn new(fraction: f64) -> RiskResult<Self> {
+        check_fraction("Fraction", fraction)?;
+        Ok(Self { fraction })
+    }
+}
+
+impl PositionSizer for FixedFractional {
+    fn name(&self) -> &str {
+        "FixedFractional"
+    }
+
+    fn size(&self, symbol: &str, _side: Side, ctx: &StrategyContext) -> RiskResult<f64> {
+        let equity = ctx.total_equity()?;
+        Ok((equity * self.fraction).max(0.0) / last_price(symbol, ctx)?)
+    }
+}
+
+/// Volatility measure used by [`VolatilityTarget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolatilityMeasure {
+    /// Average True Range over `period` candles
+    Atr(usize),
+    /// Standard deviation of closes over `period` candles
+    StdDev(usize),
+}
+
+/// Size positions so that a move of `multiple` volatility units costs
+/// `risk_fraction` of equity.
+///
+/// With ATR(14), a multiple of 2 and 1% risk, a position loses 1% of equity
+/// if price moves against it by two ATRs. Volatility is computed from the
+/// candles held in the strategy context.
+#[derive(Debug, Clone)]
+pub struct VolatilityTarget {
+    measure: VolatilityMeasure,
+    multiple: f64,
+    risk_fraction: f64,
+    max_fraction: Option<f64>,
+}
+
+impl VolatilityTarget {
+    /// Create a volatility-targeted sizer
+    pub fn new(measure: VolatilityMeasure, multiple: f64, risk_fraction: f64) -> RiskResult<Self> {
+        let period = match measure {
+            VolatilityMeasure::Atr(period) | VolatilityMeasure::StdDev(period) => period,
+        };
+        if period == 0 {
+            return Err(RiskError::InvalidParameter(
+                "Volatility period must be greater than 0".to_string(),
+            ));
+        }
+        if !(multiple.is_finite() && multiple > 0.0) {
+            return Err(RiskError::InvalidParameter(format!(
+                "Volatility multiple must be positive, got {multiple}"
+            )));
+        }
+        check_fraction("Risk fraction", risk_fraction)?;
+
+        Ok(Self {
+            measure,
+            multiple,
+            risk_fraction,
+            max_fraction: None,
+        })
+    }
+
+    /// ATR-based sizer
+    pub fn atr(period: usize, multiple: f64, risk_fraction: f64) -> RiskResult<Self> {
+        Self::new(VolatilityMeasure::Atr(period), multiple, risk_fraction)
+    }
+
+    /// Standard-deviation-based sizer
+    pub fn std_dev(period: usize, multiple: f64, risk_fraction: f64) -> RiskResult<Self> {
+        Self::new(VolatilityMeasure::StdDev(period), multiple, risk_fraction)
+    }
+
+    /// Cap the position notional at a fraction of equity
+    pub fn with_max_fraction(mut self, max_fraction: f64) -> Self {
+        self.max_fraction = Some(max_fraction);
+        self
+    }
+
+    /// Current volatility (in price units) for a symbol
+    pub fn volatility(&self, symbol: &str, ctx: &StrategyContext) -> RiskResult<f64> {
+        let candles = ctx.get_candles(symbol)?;
+
+        let value = match self.measure {
+            VolatilityMeasure::Atr(period) => {
+                let lookback = period * VOLATILITY_LOOKBACK_MULTIPLE + 1;
+                let start = candles.len().saturating_sub(lookback);
+                let mut atr = ATR::new(period)?;
+                let mut value = None;
+                for candle in &candles[start..] {
+                    let bar = OhlcBar::new(
+                        candle.open.into_inner(),
+                        candle.high.into_inner(),
+                        candle.low.into_inner(),
+                        candle.close.into_inner(),
+                    );
+                    value = atr.update_ohlc(&bar, candle.timestamp)?;
+                }
+                value
+            }
+            VolatilityMeasure::StdDev(period) => {
+                let start = candles.len().saturating_sub(period);
+                let mut std_dev = StdDev::new(period)?;
+                let mut value = None;
+                for candle in &candles[start..] {
+                    value = std_dev.update(candle.close.into_inner(), candle.timestamp)?;
+                }
+                value
+            }
+        };
+
+        match value {
+            Some(v) if v > 0.0 => Ok(v),
+            Some(_) => Err(RiskError::InsufficientData(format!(
+                "Zero volatility for {symbol}"
+            ))),
+            None => Err(RiskError::InsufficientData(format!(
+                "Not enough candles for {:?} on {symbol} (have {})",
+                self.measure,
+                candles.len()
+            ))),
+        }
+    }
+}
+
+impl PositionSizer for VolatilityTarget {
+    fn name(&self) -> &str {
+        "VolatilityTarget"
+    }
+
+    fn size(&self, symbol: &str, _side: Side, ctx: &StrategyContext) -> RiskResult<f64> {
+        let equity = ctx.total_equity()?.max(0.0);
+        let stop_distance = self.volatility(symbol, ctx)? * self.multiple;
+        let mut quantity = equity * self.risk_fraction / stop_distance;
+
+        if let Some(max_fraction) = self.max_fraction {
+            let price = last_price(symbol, ctx)?;
+            quantity = quantity.min(equity * max_fraction / price);
+        }
+
+        Ok(quantity)
+    }
+}
+
+/// Fractional Kelly criterion sizing.
+///
+/// The full Kelly fraction is `p - (1 - p) / b` where `p` is the win rate
+/// and `b` the payoff ratio (average win / average loss). Full Kelly is
+/// very aggressive, so only `kelly_fraction` of it is used (0.5 = half Kelly).
+#[derive(Debug, Clone)]
+pub struct KellySizer {
+    win_rate: f64,
+    payoff_ratio: f64,
+    kelly_fraction: f64,
+    max_fraction: f64,
+}
+
+impl KellySizer {
+    /// Create a Kelly sizer from a win rate (0-1) and payoff ratio
+    pub fn new(win_rate: f64, payoff_ratio: f64, kelly_fraction: f64) -> RiskResult<Self> {
+        if !(0.0..=1.0).contains(&win_rate) {
+            return Err(RiskError::InvalidParameter(format!(
+                "Win rate must be in [0, 1], got {win_rate}"
+            )));
+        }
+        if !(payoff_ratio.is_finite() && payoff_ratio > 0.0) {
+            return Err(RiskError::InvalidParameter(format!(
+                "Payoff ratio must be positive, got {payoff_ratio}"
+            )));
+        }
+        check_fraction("Kelly fraction", kelly_fraction)?;
+
+        Ok(Self {
+            win_rate,
+            payoff_ratio,
+            kelly_fraction,
+            max_fraction: 1.0,
+        })
+    }
+
+    /// Create a Kelly sizer from trade statistics
+    pub fn from_trade_stats(
+        win_rate: f64,
+        avg_win: f64,
+        avg_loss: f64,
+        kelly_fraction: f64,
+    ) -> RiskResult<Self> {
+        if avg_loss == 0.0 {
+            return Err(RiskError::InvalidParameter(
+                "Average loss must be non-zero".to_string(),
+            ));
+        }
+        Self::new(win_rate, avg_win.abs() / avg_loss.abs(), kelly_fraction)
+    }
+
+    /// Cap the position notional at a fraction of equity
+    pub fn with_max_fraction(mut self, max_fraction: f64) -> Self {
+        self.max_fraction = max_fraction;
+        self
+    }
+
+    /// Full Kelly fraction (may be negative when the edge is negative)
+    pub fn kelly(&self) -> f64 {
+        self.win_rate - (1.0 - self.win_rate) / self.payoff_ratio
+    }
+
+    /// Fraction of equity to allocate (never negative)
+    pub fn allocation(&self) -> f64 {
+        (self.kelly() * self.kelly_fraction).clamp(0.0, self.max_fraction)
+    }
+}
+
+impl PositionSizer for KellySizer {
+    fn name(&self) -> &str {
+        "Kelly"
+    }
+
+    fn size(&self, symbol: &str, _side: Side, ctx: &StrategyContext) -> RiskResult<f64> {
+        let equity = ctx.total_equity()?.max(0.0);
+        Ok(equity * self.allocation() / last_price(symbol, ctx)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use velora_core::{Candle, Symbol};
+    use velora_strategy::MarketSnapshot;
+
+    fn context(price: f64) -> StrategyContext {
+        let ctx = StrategyContext::new(10_000.0);
+        ctx.update_market_snapshot(
+            "BTC/USD",
+            MarketSnapshot {
+                last_price: price,
+                timestamp: Utc::now(),
+                best_bid: None,
+                best_ask: None,
+                volume_24h: None,
+            },
+        )
+        .unwrap();
+        ctx
+    }
+
+    fn add_candles(ctx: &StrategyContext, count: usize, range: f64) {
+        let start = Utc::now();
+        for i in 0..count {
+            ctx.add_candle(
+                "BTC/USD",
+                Candle {
+                    symbol: Symbol::new("BTC/USD"),
+                    open: 100.0.into(),
+                    high: (100.0 + range / 2.0).into(),
+                    low: (100.0 - range / 2.0).into(),
+                    close: 100.0.into(),
+                    volume: 1.0.into(),
+                    timestamp: start + Duration::minutes(i as i64),
+                },
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_fixed_notional() {
+        let ctx = context(50_000.0);
+        let sizer = FixedNotional::new(1_000.0).unwrap();
+        assert_eq!(sizer.size("BTC/USD", Side::Buy, &ctx).unwrap(), 0.02);
+
+        assert!(sizer.size("ETH/USD", Side::Buy, &ctx).is_err());
+        assert!(FixedNotional::new(0.0).is_err());
+    }
+
+    #[test]
+    fn test_fixed_fractional() {
+        let ctx = context(50_000.0);
+        let sizer = FixedFractional::new(0.1).unwrap();
+        // 10% of 10,000 = 1,000 notional
+        assert_eq!(sizer.size("BTC/USD", Side::Buy, &ctx).unwrap(), 0.02);
+
+        assert!(FixedFractional::new(1.5).is_err());
+    }
+
+    #[test]
+    fn test_volatility_target_atr() {
+        let ctx = context(100.0);
+        add_candles(&ctx, 20, 2.0);
+
+        let sizer = VolatilityTarget::atr(14, 2.0, 0.01).unwrap();
+        // ATR = 2, stop = 4, risk = 100 -> 25 units
+        let quantity = sizer.size("BTC/USD", Side::Buy, &ctx).unwrap();
+        assert!((quantity - 25.0).abs() < 1e-9);
+
+        // Capped at 10% of equity = 10 units at 100
+        let capped = sizer.with_max_fraction(0.1);
+        let quantity = capped.size("BTC/USD", Side::Buy, &ctx).unwrap();
+        assert!((quantity - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_volatility_target_needs_history() {
+        let ctx = context(100.0);
+        add_candles(&ctx, 5, 2.0);
+
+        let sizer = VolatilityTarget::atr(14, 2.0, 0.01).unwrap();
+        assert!(matches!(
+            sizer.size("BTC/USD", Side::Buy, &ctx),
+            Err(RiskError::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn test_kelly() {
+        let sizer = KellySizer::new(0.6, 1.0, 0.5).unwrap();
+        // Full Kelly = 0.6 - 0.4 = 0.2, half Kelly = 0.1
+        assert!((sizer.kelly() - 0.2).abs() < 1e-12);
+        assert!((sizer.allocation() - 0.1).abs() < 1e-12);
+
+        let ctx = context(50_000.0);
+        let quantity = sizer.size("BTC/USD", Side::Buy, &ctx).unwrap();
+        assert!((quantity - 0.02).abs() < 1e-12);
+
+        // Negative edge never sizes a position
+        let losing = KellySizer::from_trade_stats(0.3, 100.0, -100.0, 1.0).unwrap();
+        assert_eq!(losing.allocation(), 0.0);
+    }
+
+    #[test]
+    fn test_from_strategy_config() {
+        let config = StrategyConfig::new("Test").with_max_position_size(20.0);
+        let sizer = from_strategy_config(&config).unwrap();
+        assert_eq!(sizer.name(), "FixedFractional");
+
+        let ctx = context(50_000.0);
+        assert_eq!(sizer.size("BTC/USD", Side::Buy, &ctx).unwrap(), 0.04);
+
+        let config = StrategyConfig {
+            use_volatility_sizing: true,
+            ..config
+        };
+        assert_eq!(
+            from_strategy_config(&config).unwrap().name(),
+            "VolatilityTarget"
+        );
+    }
+}
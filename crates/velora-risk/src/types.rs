@@ -0,0 +1,224 @@
+//! Types shared by the risk checks.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+use velora_core::Side;
+
+/// An order as seen by the risk manager.
+///
+/// Engines convert their own order representation into this before
+/// submission, so the same rules apply in backtesting and live trading.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderRequest {
+    /// Symbol to trade
+    pub symbol: String,
+
+    /// Buy or Sell
+    pub side: Side,
+
+    /// Quantity in base units
+    pub quantity: f64,
+
+    /// Reference price used to value the order (limit price or last price)
+    pub price: f64,
+}
+
+impl OrderRequest {
+    /// Create a new order request
+    pub fn new(symbol: impl Into<String>, side: Side, quantity: f64, price: f64) -> Self {
+        Self {
+            symbol: symbol.into(),
+            side,
+            quantity,
+            price,
+        }
+    }
+
+    /// Order notional value (quantity * price)
+    pub fn notional(&self) -> f64 {
+        self.quantity * self.price
+    }
+
+    /// Signed notional (positive for buys, negative for sells)
+    pub fn signed_notional(&self) -> f64 {
+        match self.side {
+            Side::Buy => self.notional(),
+            Side::Sell => -self.notional(),
+        }
+    }
+}
+
+/// Account state the order is checked against.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PortfolioState {
+    /// Current account equity (cash + unrealized P&L)
+    pub equity: f64,
+
+    /// Signed position notional by symbol (positive = long, negative = short)
+    pub positions: HashMap<String, f64>,
+
+    /// Time of the check (drives the daily loss window)
+    pub timestamp: DateTime<Utc>,
+}
+
+impl PortfolioState {
+    /// Create a new portfolio state with no positions
+    pub fn new(equity: f64, timestamp: DateTime<Utc>) -> Self {
+        Self {
+            equity,
+            positions: HashMap::new(),
+            timestamp,
+        }
+    }
+
+    /// Add a position (signed notional)
+    pub fn with_position(mut self, symbol: impl Into<String>, signed_notional: f64) -> Self {
+        self.positions.insert(symbol.into(), signed_notional);
+        self
+    }
+
+    /// Signed notional held in a symbol
+    pub fn position(&self, symbol: &str) -> f64 {
+        self.positions.get(symbol).copied().unwrap_or(0.0)
+    }
+
+    /// Gross exposure across all symbols
+    pub fn total_exposure(&self) -> f64 {
+        self.positions.values().map(|n| n.abs()).sum()
+    }
+}
+
+/// Outcome of a pre-trade risk check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RiskDecision {
+    /// Order may be submitted as-is
+    Approved,
+
+    /// Order may be submitted with a reduced quantity
+    Resized {
+        /// Quantity that fits within the limits
+        quantity: f64,
+        /// Limit that forced the resize
+        reason: RiskViolation,
+    },
+
+    /// Order must not be submitted
+    Rejected(RiskViolation),
+}
+
+impl RiskDecision {
+    /// Returns true unless the order was rejected
+    pub fn is_allowed(&self) -> bool {
+        !matches!(self, RiskDecision::Rejected(_))
+    }
+
+    /// Quantity to submit, or None if the order was rejected
+    pub fn approved_quantity(&self, requested: f64) -> Option<f64> {
+        match self {
+            RiskDecision::Approved => Some(requested),
+            RiskDecision::Resized { quantity, .. } => Some(*quantity),
+            RiskDecision::Rejected(_) => None,
+        }
+    }
+}
+
+/// A risk limit that an order would breach.
+#[derive(Debug, Clone, PartialEq, Error, Serialize, Deserialize)]
+pub enum RiskViolation {
+    /// Order notional exceeds the per-trade limit
+    #[error("order notional {requested:.2} exceeds max position size {limit:.2}")]
+    MaxPositionSize {
+        /// Requested notional
+        requested: f64,
+        /// Configured limit
+        limit: f64,
+    },
+
+    /// Resulting position exceeds the per-symbol limit
+    #[error("{symbol} position {resulting:.2} would exceed limit {limit:.2}")]
+    SymbolLimit {
+        /// Symbol
+        symbol: String,
+        /// Resulting absolute position notional
+        resulting: f64,
+        /// Configured limit
+        limit: f64,
+    },
+
+    /// Resulting gross exposure exceeds the total exposure limit
+    #[error("total exposure {resulting:.2} would exceed limit {limit:.2}")]
+    TotalExposure {
+        /// Resulting gross exposure
+        resulting: f64,
+        /// Configured limit
+        limit: f64,
+    },
+
+    /// Daily loss circuit breaker is tripped
+    #[error("daily loss {loss:.2} reached limit {limit:.2}")]
+    DailyLoss {
+        /// Loss since start of day
+        loss: f64,
+        /// Configured limit
+        limit: f64,
+    },
+
+    /// Drawdown circuit breaker is tripped
+    #[error("drawdown {drawdown_pct:.2}% reached limit {limit_pct:.2}%")]
+    MaxDrawdown {
+        /// Drawdown from peak equity in percent
+        drawdown_pct: f64,
+        /// Configured limit in percent
+        limit_pct: f64,
+    },
+
+    /// Order parameters cannot be evaluated
+    #[error("invalid order: {0}")]
+    InvalidOrder(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_request_notional() {
+        let buy = OrderRequest::new("BTC/USD", Side::Buy, 0.1, 50_000.0);
+        assert_eq!(buy.notional(), 5_000.0);
+        assert_eq!(buy.signed_notional(), 5_000.0);
+
+        let sell = OrderRequest::new("BTC/USD", Side::Sell, 0.1, 50_000.0);
+        assert_eq!(sell.signed_notional(), -5_000.0);
+    }
+
+    #[test]
+    fn test_portfolio_state_exposure() {
+        let state = PortfolioState::new(10_000.0, Utc::now())
+            .with_position("BTC/USD", 2_000.0)
+            .with_position("ETH/USD", -1_500.0);
+
+        assert_eq!(state.position("BTC/USD"), 2_000.0);
+        assert_eq!(state.position("SOL/USD"), 0.0);
+        assert_eq!(state.total_exposure(), 3_500.0);
+    }
+
+    #[test]
+    fn test_decision_quantity() {
+        assert_eq!(RiskDecision::Approved.approved_quantity(1.0), Some(1.0));
+
+        let resized = RiskDecision::Resized {
+            quantity: 0.5,
+            reason: RiskViolation::MaxPositionSize {
+                requested: 2.0,
+                limit: 1.0,
+            },
+        };
+        assert_eq!(resized.approved_quantity(1.0), Some(0.5));
+
+        let rejected = RiskDecision::Rejected(RiskViolation::InvalidOrder("x".to_string()));
+        assert!(!rejected.is_allowed());
+        assert_eq!(rejected.approved_quantity(1.0), None);
+    }
+}
@@ -5,7 +5,8 @@ use crate::types::Position;
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
-use velora_core::types::{Candle, Trade};
+use velora_core::types::{Candle, OrderBook, Trade};
+use velora_core::{LiveClock, SharedClock};
 
 /// Market data snapshot for a symbol
 #[derive(Debug, Clone)]
@@ -37,11 +38,17 @@ pub struct StrategyContext {
     /// Recent trades by symbol
     trades: Arc<RwLock<HashMap<String, Vec<Trade>>>>,
 
+    /// Latest order books by symbol
+    order_books: Arc<RwLock<HashMap<String, OrderBook>>>,
+
     /// Current capital available
     capital: Arc<RwLock<f64>>,
 
     /// Total capital (initial + P&L)
     total_capital: Arc<RwLock<f64>>,
+
+    /// Time source of the engine running the strategy
+    clock: SharedClock,
 }
 
 impl Default for StrategyContext {
@@ -58,11 +65,32 @@ impl StrategyContext {
             market_data: Arc::new(RwLock::new(HashMap::new())),
             candles: Arc::new(RwLock::new(HashMap::new())),
             trades: Arc::new(RwLock::new(HashMap::new())),
+            order_books: Arc::new(RwLock::new(HashMap::new())),
             capital: Arc::new(RwLock::new(initial_capital)),
             total_capital: Arc::new(RwLock::new(initial_capital)),
+            clock: LiveClock::shared(),
         }
     }
 
+    /// Read the time from a clock instead of the wall clock
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Current time of the engine (historical time during replays)
+    ///
+    /// Strategies should use this rather than `Utc::now()` so they behave
+    /// the same live and in replays.
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    /// Clock the context reads the time from
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
     // === Position Management ===
 
     /// Get current position for a symbol
@@ -254,6 +282,25 @@ impl StrategyContext {
         Ok(())
     }
 
+    /// Get the latest order book for a symbol
+    pub fn get_order_book(&self, symbol: &str) -> StrategyResult<Option<OrderBook>> {
+        let books = self
+            .order_books
+            .read()
+            .map_err(|e| StrategyError::Internal(format!("Lock error: {e}")))?;
+        Ok(books.get(symbol).cloned())
+    }
+
+    /// Update the order book of its symbol
+    pub fn update_order_book(&self, book: OrderBook) -> StrategyResult<()> {
+        let mut books = self
+            .order_books
+            .write()
+            .map_err(|e| StrategyError::Internal(format!("Lock error: {e}")))?;
+        books.insert(book.symbol.to_string(), book);
+        Ok(())
+    }
+
     /// Update position prices based on latest market data
     pub fn update_position_prices(&self) -> StrategyResult<()> {
         let mut positions = self
@@ -288,6 +335,19 @@ mod tests {
         assert_eq!(ctx.total_capital().unwrap(), 10_000.0);
     }
 
+    #[test]
+    fn test_context_clock() {
+        let clock = Arc::new(velora_core::SimulatedClock::new(DateTime::UNIX_EPOCH));
+        let ctx = StrategyContext::new(10_000.0).with_clock(clock.clone());
+        assert_eq!(ctx.now(), DateTime::UNIX_EPOCH);
+
+        clock.advance(chrono::Duration::minutes(1));
+        assert_eq!(
+            ctx.clone().now(),
+            DateTime::UNIX_EPOCH + chrono::Duration::minutes(1)
+        );
+    }
+
     #[test]
     fn test_context_positions() {
         let ctx = StrategyContext::new(10_000.0);
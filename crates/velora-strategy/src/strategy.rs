@@ -44,6 +44,28 @@ pub trait Strategy: Send + Sync {
         Ok(Signal::Hold)
     }
 
+    /// Called once per instant with every candle that closed at it
+    ///
+    /// Multi-symbol backtests call this after the context has been updated
+    /// with all of the candles, so cross-sectional strategies (pairs,
+    /// rotation, baskets) see every symbol at the same instant. The default
+    /// forwards each candle to [`Strategy::on_candle`] in order and keeps the
+    /// actionable signals.
+    async fn on_candles(
+        &mut self,
+        candles: &[Candle],
+        ctx: &StrategyContext,
+    ) -> StrategyResult<Vec<Signal>> {
+        let mut signals = Vec::new();
+        for candle in candles {
+            let signal = self.on_candle(candle, ctx).await?;
+            if signal.is_actionable() {
+                signals.push(signal);
+            }
+        }
+        Ok(signals)
+    }
+
     /// Called when a new trade is received
     ///
     /// Use this for strategies that need tick-by-tick trade data.
@@ -60,9 +82,12 @@ pub trait Strategy: Send + Sync {
         Ok(Signal::Hold)
     }
 
-    /// Called when an order is filled
+    /// Called when orders were filled or changed status
     ///
     /// Use this to track position changes and update strategy state.
+    /// Called at most once per market event, after the event's own callback.
+    /// The trading engine reports any status change (fills, cancellations,
+    /// rejections); the backtester reports fills.
     /// Note: Fill notifications are typically handled by the execution engine,
     /// which updates positions automatically.
     async fn on_order_update(&mut self, ctx: &StrategyContext) -> StrategyResult<Signal> {
@@ -70,7 +95,12 @@ pub trait Strategy: Send + Sync {
         Ok(Signal::Hold)
     }
 
-    /// Called periodically (e.g., every second)
+    /// Called periodically on the engine clock
+    ///
+    /// Fires every `timer_interval_ms` (one second by default) of the
+    /// trading engine or backtester configuration. Clocks that are not
+    /// realtime fire it after the first event past each deadline, skipping
+    /// the deadlines missed in between.
     ///
     /// Use this for time-based logic like trailing stops, position monitoring, etc.
     async fn on_timer(&mut self, ctx: &StrategyContext) -> StrategyResult<Signal> {
@@ -181,4 +211,62 @@ mod tests {
         let result = strategy.initialize(&ctx).await;
         assert!(result.is_ok());
     }
+
+    /// Buys every symbol it sees
+    struct BuyEverything {
+        config: StrategyConfig,
+    }
+
+    #[async_trait]
+    impl Strategy for BuyEverything {
+        fn name(&self) -> &str {
+            &self.config.name
+        }
+
+        fn config(&self) -> &StrategyConfig {
+            &self.config
+        }
+
+        fn state(&self) -> StrategyState {
+            StrategyState::Running
+        }
+
+        async fn on_candle(
+            &mut self,
+            candle: &Candle,
+            _ctx: &StrategyContext,
+        ) -> StrategyResult<Signal> {
+            if candle.symbol.as_str() == "SKIP" {
+                return Ok(Signal::Hold);
+            }
+            Ok(Signal::buy(candle.symbol.as_str(), 1.0))
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    #[tokio::test]
+    async fn test_on_candles_forwards_each_candle() {
+        let candle = |symbol: &str| Candle {
+            symbol: velora_core::types::Symbol::new(symbol),
+            timestamp: chrono::Utc::now(),
+            open: 1.0.into(),
+            high: 1.0.into(),
+            low: 1.0.into(),
+            close: 1.0.into(),
+            volume: 1.0.into(),
+        };
+
+        let mut strategy = BuyEverything {
+            config: StrategyConfig::new("BuyEverything"),
+        };
+        let ctx = StrategyContext::new(10_000.0);
+        let signals = strategy
+            .on_candles(&[candle("ETH"), candle("SKIP"), candle("BTC")], &ctx)
+            .await
+            .unwrap();
+
+        let symbols: Vec<_> = signals.iter().filter_map(Signal::symbol).collect();
+        assert_eq!(symbols, vec!["ETH", "BTC"]);
+    }
 }
@@ -20,6 +20,15 @@ pub enum Signal {
         limit_price: Option<f64>,
         /// Optional stop price
         stop_price: Option<f64>,
+        /// Stop-loss attached to the position once filled
+        #[serde(default)]
+        stop_loss: Option<f64>,
+        /// Take-profit attached to the position once filled
+        #[serde(default)]
+        take_profit: Option<f64>,
+        /// Trailing stop distance (in price) attached to the position once filled
+        #[serde(default)]
+        trailing_stop: Option<f64>,
         /// Strategy-specific metadata
         metadata: HashMap<String, String>,
     },
@@ -34,6 +43,15 @@ pub enum Signal {
         limit_price: Option<f64>,
         /// Optional stop price
         stop_price: Option<f64>,
+        /// Stop-loss attached to the position once filled
+        #[serde(default)]
+        stop_loss: Option<f64>,
+        /// Take-profit attached to the position once filled
+        #[serde(default)]
+        take_profit: Option<f64>,
+        /// Trailing stop distance (in price) attached to the position once filled
+        #[serde(default)]
+        trailing_stop: Option<f64>,
         /// Strategy-specific metadata
         metadata: HashMap<String, String>,
     },
@@ -56,6 +74,9 @@ pub enum Signal {
         stop_loss: Option<f64>,
         /// New take profit price
         take_profit: Option<f64>,
+        /// New trailing stop distance (in price)
+        #[serde(default)]
+        trailing_stop: Option<f64>,
         /// Strategy-specific metadata
         metadata: HashMap<String, String>,
     },
@@ -69,6 +90,9 @@ impl Signal {
             quantity,
             limit_price: None,
             stop_price: None,
+            stop_loss: None,
+            take_profit: None,
+            trailing_stop: None,
             metadata: HashMap::new(),
         }
     }
@@ -80,6 +104,9 @@ impl Signal {
             quantity,
             limit_price: None,
             stop_price: None,
+            stop_loss: None,
+            take_profit: None,
+            trailing_stop: None,
             metadata: HashMap::new(),
         }
     }
@@ -93,6 +120,66 @@ impl Signal {
         }
     }
 
+    /// Create a buy signal whose quantity is chosen by the engine's position sizer
+    pub fn buy_unsized(symbol: impl Into<String>) -> Self {
+        Self::buy(symbol, 0.0)
+    }
+
+    /// Create a sell signal whose quantity is chosen by the engine's position sizer
+    pub fn sell_unsized(symbol: impl Into<String>) -> Self {
+        Self::sell(symbol, 0.0)
+    }
+
+    /// Check if this is a buy or sell without a quantity
+    pub fn needs_sizing(&self) -> bool {
+        match self {
+            Signal::Buy { quantity, .. } | Signal::Sell { quantity, .. } => *quantity <= 0.0,
+            _ => false,
+        }
+    }
+
+    /// Set the quantity of a buy or sell signal (other signals are unchanged)
+    pub fn with_quantity(mut self, new_quantity: f64) -> Self {
+        if let Signal::Buy { quantity, .. } | Signal::Sell { quantity, .. } = &mut self {
+            *quantity = new_quantity;
+        }
+        self
+    }
+
+    /// Attach a stop-loss (Buy/Sell) or move it (Modify); other signals are unchanged
+    pub fn with_stop_loss(mut self, price: f64) -> Self {
+        if let Signal::Buy { stop_loss, .. }
+        | Signal::Sell { stop_loss, .. }
+        | Signal::Modify { stop_loss, .. } = &mut self
+        {
+            *stop_loss = Some(price);
+        }
+        self
+    }
+
+    /// Attach a take-profit (Buy/Sell) or move it (Modify); other signals are unchanged
+    pub fn with_take_profit(mut self, price: f64) -> Self {
+        if let Signal::Buy { take_profit, .. }
+        | Signal::Sell { take_profit, .. }
+        | Signal::Modify { take_profit, .. } = &mut self
+        {
+            *take_profit = Some(price);
+        }
+        self
+    }
+
+    /// Attach a trailing stop `distance` below (long) or above (short) the
+    /// best price reached since entry; other signals are unchanged
+    pub fn with_trailing_stop(mut self, distance: f64) -> Self {
+        if let Signal::Buy { trailing_stop, .. }
+        | Signal::Sell { trailing_stop, .. }
+        | Signal::Modify { trailing_stop, .. } = &mut self
+        {
+            *trailing_stop = Some(distance);
+        }
+        self
+    }
+
     /// Check if signal is actionable (not Hold)
     pub fn is_actionable(&self) -> bool {
         !matches!(self, Signal::Hold)
@@ -328,6 +415,38 @@ mod tests {
         assert_eq!(hold.symbol(), None);
     }
 
+    #[test]
+    fn test_unsized_signal() {
+        let signal = Signal::buy_unsized("BTC-USD-PERP");
+        assert!(signal.needs_sizing());
+
+        let sized = signal.with_quantity(0.25);
+        assert!(!sized.needs_sizing());
+        assert!(matches!(sized, Signal::Buy { quantity, .. } if quantity == 0.25));
+
+        assert!(!Signal::close("BTC-USD-PERP").needs_sizing());
+    }
+
+    #[test]
+    fn test_bracket_builders() {
+        let signal = Signal::sell("BTC-USD-PERP", 1.0)
+            .with_stop_loss(51_000.0)
+            .with_take_profit(48_000.0)
+            .with_trailing_stop(500.0);
+        assert!(matches!(
+            signal,
+            Signal::Sell {
+                stop_loss: Some(51_000.0),
+                take_profit: Some(48_000.0),
+                trailing_stop: Some(500.0),
+                ..
+            }
+        ));
+
+        let close = Signal::close("BTC-USD-PERP").with_stop_loss(49_000.0);
+        assert_eq!(close, Signal::close("BTC-USD-PERP"));
+    }
+
     #[test]
     fn test_position_pnl() {
         let mut pos = Position::new("BTC-USD-PERP", PositionSide::Long, 1.0, 50000.0);
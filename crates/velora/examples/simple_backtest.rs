@@ -148,6 +148,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             slippage_bps: 5.0,
             fill_delay_ms: 0,
             fill_model: FillModel::Realistic,
+            ..Default::default()
         });
     println!("   ✓ Initial capital: ${:,.2}", 10_000.0);
     println!("   ✓ Commission rate: {}%", 0.1);
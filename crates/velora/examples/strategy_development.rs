@@ -212,6 +212,7 @@ async fn run_backtest(
             slippage_bps: 5.0,
             fill_delay_ms: 0,
             fill_model: FillModel::Realistic,
+            ..Default::default()
         });
 
     println!("⚙️  Backtest configuration:");
@@ -134,6 +134,7 @@ async fn test_backtest_integration() {
             slippage_bps: 5.0,
             fill_delay_ms: 0,
             fill_model: FillModel::Realistic,
+            ..Default::default()
         });
 
     // Run backtest